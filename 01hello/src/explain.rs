@@ -0,0 +1,50 @@
+// What `hello --explain` prints: the chapter's notes, in short.
+use rustfun_common::{Explanation, Text, Topic};
+
+pub const EXPLANATION: Explanation = Explanation {
+    chapter: "01hello",
+    summary: Text {
+        en: "the gcd of the command-line arguments",
+        zh: "命令行参数的最大公约数",
+    },
+    topics: &[
+        Topic {
+            title: Text { en: "Functions", zh: "函数" },
+            body: Text {
+                en: "fn introduces a function. Parameters and the return type after -> are always\n\
+                     written out; the last expression of the body, without a semicolon, is the value\n\
+                     the function returns.",
+                zh: "fn 声明函数。参数和 -> 之后的返回类型都要写明; 函数体最后一个不带分号的\n\
+                     表达式就是返回值。",
+            },
+        },
+        Topic {
+            title: Text { en: "Variables and mut", zh: "变量与 mut" },
+            body: Text {
+                en: "let declares a variable, immutable unless it's let mut. gcd's loop reassigns\n\
+                     n and m, so they are declared mut.",
+                zh: "let 声明变量, 除非写成 let mut, 否则不可修改。gcd 的循环要给 n 和 m\n\
+                     重新赋值, 所以它们声明为 mut。",
+            },
+        },
+        Topic {
+            title: Text { en: "Result instead of exceptions", zh: "用 Result 代替异常" },
+            body: Text {
+                en: "Parsing an argument returns a Result: Ok(value) or Err(error). Rust has no\n\
+                     exceptions, so the program checks which one it got, and on Err prints the\n\
+                     usage and exits with status 1.",
+                zh: "解析参数返回 Result: Ok(值) 或 Err(错误)。Rust 没有异常, 程序要检查拿到的是\n\
+                     哪一个; 遇到 Err 就打印用法并以状态 1 退出。",
+            },
+        },
+        Topic {
+            title: Text { en: "Tests", zh: "测试" },
+            body: Text {
+                en: "#[test] marks a function that only `cargo test` compiles and runs. gcd's test\n\
+                     lives next to it, in the shared crate common/.",
+                zh: "#[test] 标记的函数只在 `cargo test` 时编译并运行。gcd 的测试就写在它旁边,\n\
+                     位于共享的 common/ crate 中。",
+            },
+        },
+    ],
+};
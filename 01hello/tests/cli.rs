@@ -0,0 +1,67 @@
+// Run the gcd binary the way a user would and check what it prints and how it exits.
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+fn hello() -> Command {
+    Command::cargo_bin("hello").unwrap()
+}
+
+#[test]
+fn prints_the_gcd() {
+    hello().args(["42", "56"]).assert()
+        .success()
+        .stdout("The greatest common divisor of [42, 56] is 14\n");
+    hello().arg("17").assert()
+        .success()
+        .stdout("The greatest common divisor of [17] is 17\n");
+}
+
+#[test]
+fn usage_without_arguments() {
+    hello().assert()
+        .code(1)
+        .stdout("")
+        .stderr("gcd: missing NUMBER\nUsage: gcd NUMBER ...\n");
+}
+
+#[test]
+fn rejects_non_numbers() {
+    hello().args(["12", "twelve"]).assert()
+        .code(1)
+        .stderr("gcd: NUMBER: not a number: \"twelve\"\nUsage: gcd NUMBER ...\n");
+}
+
+// gcd(0, n) would panic; zero is turned away with the other bad arguments
+#[test]
+fn rejects_zero() {
+    hello().args(["12", "0"]).assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("gcd: NUMBER: zero has no greatest common divisor\n"));
+}
+
+// --lang goes anywhere among the arguments, and changes only what's said about them
+#[test]
+fn speaks_chinese_with_lang_zh() {
+    hello().args(["12", "--lang", "zh", "x"]).assert()
+        .code(1)
+        .stderr("gcd: NUMBER: 不是数字: \"x\"\n用法: gcd NUMBER ...\n");
+    hello().args(["--lang=zh", "12", "18"]).assert()
+        .success()
+        .stdout("The greatest common divisor of [12, 18] is 6\n");
+    hello().env("RUSTFUN_LANG", "zh").assert()
+        .code(1)
+        .stderr("gcd: 缺少 NUMBER\n用法: gcd NUMBER ...\n");
+    hello().args(["--lang", "fr", "12"]).assert()
+        .code(1)
+        .stderr("gcd: unknown language \"fr\"; try en or zh\nUsage: gcd NUMBER ...\n");
+}
+
+#[test]
+fn explains_itself() {
+    hello().arg("--explain").assert()
+        .success()
+        .stdout(predicate::str::starts_with("01hello: the gcd of the command-line arguments\n\n1. Functions\n   fn "));
+    hello().args(["--explain", "--lang", "zh"]).assert()
+        .success()
+        .stdout(predicate::str::starts_with("01hello: 命令行参数的最大公约数\n\n1. 函数\n"));
+}
@@ -0,0 +1,36 @@
+// Snapshots of everything the gcd binary prints, with insta. Each snapshot is a short
+// transcript, kept in tests/snapshots/; a change to the output fails the test and shows
+// the difference, and `cargo insta review` (or INSTA_UPDATE=always) accepts it, so the
+// new output is in the diff for review.
+use assert_cmd::Command;
+
+// The command line, the exit status, and both streams, as one text.
+fn transcript(args: &[&str]) -> String {
+    let output = Command::cargo_bin("hello").unwrap().args(args).output().unwrap();
+    let command: Vec<&str> = std::iter::once("hello").chain(args.iter().copied()).collect();
+    format!("$ {}\nstatus: {}\n--- stdout\n{}--- stderr\n{}",
+            command.join(" "),
+            output.status.code().unwrap(),
+            String::from_utf8(output.stdout).unwrap(),
+            String::from_utf8(output.stderr).unwrap())
+}
+
+#[test]
+fn gcd_output() {
+    insta::assert_snapshot!(transcript(&["42", "56"]));
+}
+
+#[test]
+fn usage_without_arguments() {
+    insta::assert_snapshot!(transcript(&[]));
+}
+
+#[test]
+fn usage_for_bad_numbers() {
+    insta::assert_snapshot!(transcript(&["12", "twelve"]));
+}
+
+#[test]
+fn usage_in_chinese() {
+    insta::assert_snapshot!(transcript(&["--lang", "zh", "12", "0"]));
+}
@@ -0,0 +1,61 @@
+// The same server on axum, which is built on hyper and tokio.
+//
+// 18.  axum handlers are async functions. Their arguments are "extractors" that axum
+//      fills in from the request, and their return value is anything that implements
+//      IntoResponse.
+// 18.1 An async fn only runs on an executor; here that's a single-threaded tokio
+//      runtime, built by hand so that main can stay an ordinary fn for both backends.
+use axum::body::Bytes;
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::Router;
+use std::collections::HashMap;
+
+use crate::handlers::{self, Page, Status};
+use rustfun_error::{Error, Result};
+
+pub fn serve(address: &str) -> Result<()> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_io()
+        .build()?;
+    runtime.block_on(async {
+        let router = Router::new()
+            .route("/", get(get_form))
+            .route("/gcd", post(post_gcd));
+        let listener = tokio::net::TcpListener::bind(address).await.map_err(Error::http)?;
+        axum::serve(listener, router).await.map_err(Error::http)
+    })
+}
+
+async fn get_form() -> Page {
+    handlers::form()
+}
+
+// 19.  axum's Form extractor keeps one value per name, and our form has two `n` fields,
+//      so take the raw body and collect the (name, value) pairs ourselves: the same
+//      map of names to every value that iron's UrlEncodedBody gives.
+async fn post_gcd(body: Bytes) -> Page {
+    let mut form_data: HashMap<String, Vec<String>> = HashMap::new();
+    for (name, value) in form_urlencoded::parse(&body) {
+        form_data.entry(name.into_owned()).or_default().push(value.into_owned());
+    }
+    handlers::gcd(form_data.get("n").map(|nums| nums.as_slice()))
+}
+
+// 20.  Implementing axum's IntoResponse for our own Page lets the handlers above return
+//      a Page directly. A (status, headers, body) tuple already implements it; a String
+//      body on its own is sent as text/plain.
+impl IntoResponse for Page {
+    fn into_response(self) -> Response {
+        let status = match self.status {
+            Status::Ok => StatusCode::OK,
+            Status::BadRequest => StatusCode::BAD_REQUEST,
+        };
+        if self.is_html() {
+            (status, [(header::CONTENT_TYPE, "text/html; charset=utf-8")], self.body).into_response()
+        } else {
+            (status, self.body).into_response()
+        }
+    }
+}
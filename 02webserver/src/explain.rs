@@ -0,0 +1,57 @@
+// What `iron-gcd --explain` prints: the chapter's notes, in short.
+use rustfun_common::{Explanation, Text, Topic};
+
+pub const EXPLANATION: Explanation = Explanation {
+    chapter: "02webserver",
+    summary: Text {
+        en: "a web form that computes gcds, on iron or axum",
+        zh: "计算最大公约数的网页表单, 基于 iron 或 axum",
+    },
+    topics: &[
+        Topic {
+            title: Text { en: "Crates and features", zh: "crate 与 feature" },
+            body: Text {
+                en: "The web frameworks are dependencies in Cargo.toml, each behind a Cargo feature.\n\
+                     #[cfg(feature = \"iron\")] compiles code only when that feature is on.",
+                zh: "两个 web 框架都是 Cargo.toml 里的依赖, 各自放在一个 Cargo feature 后面。\n\
+                     #[cfg(feature = \"iron\")] 让代码只在该 feature 打开时才编译。",
+            },
+        },
+        Topic {
+            title: Text { en: "Handlers", zh: "处理函数" },
+            body: Text {
+                en: "A handler turns a request into a response. The pages themselves are plain\n\
+                     functions returning a Page; each backend converts a Page into its own\n\
+                     response type, so the logic is written once.",
+                zh: "处理函数把请求变成响应。页面本身是返回 Page 的普通函数; 每个后端把 Page\n\
+                     转换成自己的响应类型, 这样逻辑只写一次。",
+            },
+        },
+        Topic {
+            title: Text { en: "Raw strings", zh: "原始字符串" },
+            body: Text {
+                en: "The form's HTML is a raw string, r#\"...\"#: no escapes are processed, so the\n\
+                     quotes inside it need no backslashes.",
+                zh: "表单的 HTML 写成原始字符串 r#\"...\"#: 里面不处理转义, 引号不需要反斜杠。",
+            },
+        },
+        Topic {
+            title: Text { en: "Matching on Result", zh: "对 Result 做 match" },
+            body: Text {
+                en: "Form data that doesn't parse is answered with 400 Bad Request. match makes\n\
+                     the program look at the Err case before it can use the value.",
+                zh: "无法解析的表单数据会得到 400 Bad Request。match 要求程序先处理 Err 的情况,\n\
+                     才能使用其中的值。",
+            },
+        },
+        Topic {
+            title: Text { en: "async, for axum", zh: "axum 的 async" },
+            body: Text {
+                en: "axum's handlers are async functions, run on a tokio runtime that main builds\n\
+                     by hand; iron's are ordinary functions called from its own threads.",
+                zh: "axum 的处理函数是 async 函数, 运行在 main 手动构建的 tokio 运行时上;\n\
+                     iron 的则是在它自己的线程里调用的普通函数。",
+            },
+        },
+    ],
+};
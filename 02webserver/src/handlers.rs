@@ -0,0 +1,95 @@
+// What the server says, independent of the framework that says it. Each backend turns
+// a request into the arguments of these functions, and the Page they return into a
+// response of its own kind.
+use rustfun_common::{gcd_all, parse_numbers, NumbersError};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Status {
+    Ok,
+    BadRequest,
+}
+
+/// A response, before any framework has seen it. Successful pages are HTML; errors
+/// are plain text.
+#[derive(Debug, PartialEq)]
+pub struct Page {
+    pub status: Status,
+    pub body: String,
+}
+
+impl Page {
+    fn ok(body: String) -> Page {
+        Page { status: Status::Ok, body }
+    }
+
+    fn bad_request(body: String) -> Page {
+        log::info!("bad request: {}", body.trim_end());
+        Page { status: Status::BadRequest, body }
+    }
+
+    pub fn is_html(&self) -> bool {
+        self.status == Status::Ok
+    }
+}
+
+/// The page at `/`: a form with two number fields, posted to `/gcd`.
+pub fn form() -> Page {
+    // 8.  Rust “raw string” syntax
+    // 8.1 the letter r, zero or more hash marks (that is, the # character), a double quote
+    // 8.2 then the contents of the string,
+    // 8.3 terminated by another double quote followed by the same number of hash marks
+    // 8.4 no escape sequences like \" are recognized
+    // 8.5 We can always ensure the string ends where we intend by using more hash marks around the
+    //     quotes than ever appear in the text
+    Page::ok(r#"
+        <title>GCD Calculator</title>
+        <form action="/gcd" method="post">
+          <input type="text" name="n"/>
+          <input type="text" name="n"/>
+          <button type="submit">Compute GCD</button>
+        </form>
+    "#.to_string())
+}
+
+/// The answer to a post of the form: `numbers` holds every value of the `n` field, or
+/// is None if there was no such field.
+pub fn gcd(numbers: Option<&[String]>) -> Page {
+    let unparsed_numbers = match numbers {
+        None => return Page::bad_request("form data has no 'n' parameter\n".to_string()),
+        Some(nums) => nums
+    };
+
+    // 17.  parse_numbers also turns away zeros, which would make gcd_all panic.
+    let numbers = match parse_numbers(unparsed_numbers) {
+        Err(NumbersError::NotANumber(unparsed)) => {
+            return Page::bad_request(
+                format!("Value for 'n' parameter not a number: {:?}\n", unparsed));
+        }
+        Err(e) => return Page::bad_request(format!("Bad 'n' parameter: {}\n", e)),
+        Ok(numbers) => numbers
+    };
+
+    let d = gcd_all(&numbers);
+
+    Page::ok(format!("The greatest common divisor of the numbers {:?} is <b>{}</b>\n",
+                     numbers, d))
+}
+
+#[test]
+fn test_gcd_page() {
+    let values = |v: &[&str]| -> Vec<String> { v.iter().map(|s| s.to_string()).collect() };
+
+    let page = gcd(Some(&values(&["12", "18"])));
+    assert_eq!(page.status, Status::Ok);
+    assert!(page.is_html());
+    assert_eq!(page.body, "The greatest common divisor of the numbers [12, 18] is <b>6</b>\n");
+
+    let page = gcd(Some(&values(&["12", "x"])));
+    assert_eq!(page, Page::bad_request("Value for 'n' parameter not a number: \"x\"\n".to_string()));
+    assert!(!page.is_html());
+
+    assert_eq!(gcd(Some(&values(&["0"]))).body,
+               "Bad 'n' parameter: zero has no greatest common divisor\n");
+    assert_eq!(gcd(Some(&[])).body, "Bad 'n' parameter: no numbers given\n");
+    assert_eq!(gcd(None).body, "form data has no 'n' parameter\n");
+}
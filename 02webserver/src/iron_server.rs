@@ -0,0 +1,91 @@
+// The server as the book writes it, on iron, with router and urlencoded.
+//
+// 3.  iron::prelude::* makes all the public names of the iron::prelude module directly visible.
+use iron::prelude::*;
+use iron::status;
+
+use crate::handlers::{self, Page, Status};
+use rustfun_error::{Error, Result};
+
+pub fn serve(address: &str) -> Result<()> {
+    // 4. pass the get_form function to Iron::new, indicating that the server should use that
+    //    function to handle all requests
+    //Iron::new(get_form).http(address).unwrap();
+
+    build_router(address)
+}
+
+// 5. get_form function itself takes a mutable reference, written &mut, to a Request value
+//    representing the HTTP request we’ve been called to handle.
+// 6. _request parameter never be used, giving the parameter a name beginning with _ tells
+//    Rust that we expect the variable to be unused, so it shouldn’t warn about.
+fn get_form(_request: &mut Request) -> IronResult<Response> {
+    // 9.  IronResult<Response>, is another variant of the Result type
+    // 9.1 Ok(r) for some successful Response value r, or Err(e) for some error value e.
+    Ok(respond(handlers::form()))
+}
+
+// Turn the framework-free Page into an iron Response.
+fn respond(page: Page) -> Response {
+    let mut response = Response::new();
+
+    // 7.  The set_mut method uses its argument’s type to decide which part of the response to set
+    // 7.1 status::Ok sets the HTTP status
+    response.set_mut(match page.status {
+        Status::Ok => status::Ok,
+        Status::BadRequest => status::BadRequest,
+    });
+    // 7.2 media type (by mime! macro) sets Content-Type header
+    if page.is_html() {
+        response.set_mut(mime!(Text/Html; Charset=Utf8));
+    }
+    response.set_mut(page.body);
+    response
+}
+
+//10.  Rust allows declarations to occur in any order
+//10.1 macro_rules! definitions used by plain name within a crate are the exception to this
+//     rule: they must appear before they are used. Macros from other crates aren't.
+use router::Router;
+
+fn build_router(address: &str) -> Result<()> {
+
+    //11. create a Router, establish handler functions for two specific paths
+    let mut router = Router::new();
+    router.get("/", get_form, "root");
+    router.post("/gcd", post_gcd, "gcd");
+
+    //12. pass this Router as the request handler to Iron::new
+    //    consults the URL path to decide which handler function to call
+    //12.1 http() only returns once the server is running, handing back a Listening
+    //     guard; the guard's drop waits for the server, so this blocks while it serves.
+    //     Failing to start, say because the port is taken, is an Err instead.
+    Iron::new(router).http(address).map_err(Error::http)?;
+    Ok(())
+}
+
+use urlencoded::UrlEncodedBody;
+
+fn post_gcd(request: &mut Request) -> IronResult<Response> {
+
+    //13.  check `match` expression of a Result type
+    //13.1 if Err(e), it runs the branch with error set to e
+    //13.2 if Ok(v),  it runs the branch with success set to v, aka map -> form_data
+    //14.  the program can only access the value of a Result by first checking which variant it is;
+    //     one can never misinterpret a failure value as a successful completio
+    //15.  ::<UrlEncodedBody> part of the method call is a type parameter indicating which part of
+    //     the Request get_ref should retrieve.
+    //16.  The format! macro uses the same kind of string template as the writeln! and println!
+    //     macros, but returns a string value
+	let form_data = match request.get_ref::<UrlEncodedBody>() {
+		Err(e) => {
+			let mut response = Response::new();
+			response.set_mut(status::BadRequest);
+			response.set_mut(format!("Error parsing form data: {:?}\n", e));
+			return Ok(response);
+		}
+		Ok(map) => map
+	};
+
+	Ok(respond(handlers::gcd(form_data.get("n").map(|nums| nums.as_slice()))))
+}
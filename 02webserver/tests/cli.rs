@@ -0,0 +1,94 @@
+// Start the real server and talk HTTP to it over a plain TcpStream.
+//
+// The server always listens on localhost:3000, so this test needs that port to be free,
+// and the backends take turns on it within one test.
+use assert_cmd::cargo::CommandCargoExt;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::Duration;
+
+struct KillOnDrop(Child);
+
+impl Drop for KillOnDrop {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+// Send one request and return the whole response, status line included.
+fn request(text: &str) -> String {
+    let mut stream = TcpStream::connect("localhost:3000").unwrap();
+    stream.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+    stream.write_all(text.as_bytes()).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    response
+}
+
+fn post_gcd(body: &str) -> String {
+    request(&format!("POST /gcd HTTP/1.0\r\n\
+                      Content-Type: application/x-www-form-urlencoded\r\n\
+                      Content-Length: {}\r\n\r\n{}", body.len(), body))
+}
+
+fn serves_the_form_and_computes_gcds(backend: &str) {
+    let _server = KillOnDrop(Command::cargo_bin("iron-gcd").unwrap()
+        .arg(backend)
+        .stdout(Stdio::null())
+        .spawn()
+        .unwrap());
+    // wait for it to start listening
+    for _ in 0..100 {
+        if TcpStream::connect("localhost:3000").is_ok() {
+            break;
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+
+    let form = request("GET / HTTP/1.0\r\n\r\n");
+    assert!(form.starts_with("HTTP/1.0 200 OK\r\n") || form.starts_with("HTTP/1.1 200 OK\r\n"), "{}", form);
+    assert!(form.contains("<title>GCD Calculator</title>"));
+    assert!(form.to_lowercase().contains("content-type: text/html; charset=utf-8\r\n"), "{}", form);
+
+    let answer = post_gcd("n=12&n=18");
+    assert!(answer.contains(" 200 OK\r\n"), "{}", answer);
+    assert!(answer.ends_with("The greatest common divisor of the numbers [12, 18] is <b>6</b>\n"), "{}", answer);
+
+    let bad = post_gcd("n=12&n=eighteen");
+    assert!(bad.contains(" 400 Bad Request\r\n"), "{}", bad);
+    assert!(bad.ends_with("Value for 'n' parameter not a number: \"eighteen\"\n"), "{}", bad);
+
+    // gcd(0, n) would panic; the handler must answer instead
+    let zero = post_gcd("n=12&n=0");
+    assert!(zero.contains(" 400 Bad Request\r\n"), "{}", zero);
+    assert!(zero.ends_with("Bad 'n' parameter: zero has no greatest common divisor\n"), "{}", zero);
+
+    let missing = post_gcd("m=1");
+    assert!(missing.contains(" 400 Bad Request\r\n"), "{}", missing);
+    assert!(missing.ends_with("form data has no 'n' parameter\n"), "{}", missing);
+
+    // a second server can't have the port, and says so instead of panicking
+    let second = Command::cargo_bin("iron-gcd").unwrap().arg(backend).output().unwrap();
+    assert_eq!(second.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&second.stderr);
+    assert!(stderr.starts_with("iron-gcd: HTTP server: "), "{}", stderr);
+}
+
+#[test]
+fn backends_agree() {
+    #[cfg(feature = "iron")]
+    serves_the_form_and_computes_gcds("iron");
+    #[cfg(feature = "axum")]
+    serves_the_form_and_computes_gcds("axum");
+}
+
+#[test]
+fn rejects_an_unknown_backend() {
+    let output = Command::cargo_bin("iron-gcd").unwrap().arg("rocket").output().unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr)
+                .starts_with("iron-gcd: unknown backend \"rocket\"; this build has "));
+}
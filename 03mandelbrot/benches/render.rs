@@ -0,0 +1,99 @@
+// The render pipeline's hot paths, from the library: escape_time on single points, in f32,
+// f64 and double-double, pixel_to_point over a frame, and a small frame through render_into,
+// serially, the way the slow renderer goes through it, and a row per task on
+// rayon, the way the fast one does. A change to the loop in escape.rs shows up here first.
+//
+//   $ cargo bench -p mandelbrot
+//   mandelbrot::escape_time/f32/main cardioid            time:   [...]
+//   mandelbrot::escape_time/f64/main cardioid            time:   [...]
+//   mandelbrot::escape_time/double-double/main cardioid  time:   [...]
+//   ...
+//   mandelbrot::render/serial                            time:   [...]
+//   mandelbrot::render/rayon                             time:   [...]
+//
+// What to expect: a point in the main cardioid costs a few multiplications, whatever the
+// limit; one in another bulb, the hundred or so iterations it takes the period check to
+// catch its cycle; one near the edge, the few dozen it takes to escape. Double-double is
+// ten to twenty times slower than f64. The rayon frame is about as many times faster as
+// there are cores.
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use mandelbrot::double_double::{parse_complex, Dd};
+use mandelbrot::escape::{escape_time, narrow, widen, Settings};
+use mandelbrot::pixel_to_point;
+use mandelbrot::render::{render_into, RenderOptions, Viewport};
+use num::Complex;
+use rayon::prelude::*;
+use std::hint::black_box;
+
+fn bench_points(c: &mut Criterion) {
+    let mut group = c.benchmark_group("mandelbrot::escape_time");
+    let points = [("main cardioid", "-0.5,0"),
+                  ("period-3 bulb", "-0.12,0.75"),
+                  ("edge", "-0.75,0.1"),
+                  ("outside", "1,1")];
+    for (name, point) in points {
+        let point = parse_complex(point).unwrap();
+        let f64_point = narrow(point);
+        let f32_point: Complex<f32> = widen(f64_point);
+        group.bench_with_input(BenchmarkId::new("f32", name), &f32_point,
+                               |b, &point| b.iter(|| escape_time(black_box(point), Settings::default())));
+        group.bench_with_input(BenchmarkId::new("f64", name), &f64_point,
+                               |b, &point| b.iter(|| escape_time(black_box(point), Settings::default())));
+        group.bench_with_input(BenchmarkId::new("double-double", name), &point,
+                               |b, &point| b.iter(|| escape_time(black_box(point), Settings::default())));
+    }
+    group.finish();
+}
+
+// The same view as the README's example, at 200x150.
+const BOUNDS: (usize, usize) = (200, 150);
+
+fn corners() -> (Complex<f64>, Complex<f64>) {
+    (Complex { re: -1.20, im: 0.35 }, Complex { re: -1.0, im: 0.20 })
+}
+
+fn bench_pixel_to_point(c: &mut Criterion) {
+    let (upper_left, lower_right) = corners();
+    let (dd_upper_left, dd_lower_right) = (parse_complex("-1.20,0.35").unwrap(), parse_complex("-1,0.20").unwrap());
+    let pixels = || (0..BOUNDS.1).flat_map(|row| (0..BOUNDS.0).map(move |column| (column, row)));
+
+    let mut group = c.benchmark_group("mandelbrot::pixel_to_point");
+    group.throughput(Throughput::Elements((BOUNDS.0 * BOUNDS.1) as u64));
+    group.bench_function("f64", |b| b.iter(|| {
+        pixels().map(|pixel| pixel_to_point(BOUNDS, black_box(pixel), upper_left, lower_right).re).sum::<f64>()
+    }));
+    group.bench_function("double-double", |b| b.iter(|| {
+        pixels().map(|pixel| pixel_to_point(BOUNDS, black_box(pixel), dd_upper_left, dd_lower_right).re)
+                .fold(Dd::from(0.0), |sum, re| sum + re)
+    }));
+    group.finish();
+}
+
+fn bench_render(c: &mut Criterion) {
+    let (upper_left, lower_right) = corners();
+    let viewport = Viewport { bounds: BOUNDS, upper_left, lower_right };
+    let options = RenderOptions::default();
+    let mut pixels = vec![0; options.buffer_len(&viewport).unwrap()];
+    // a viewport for each row, as the fast renderer splits the picture
+    let rows: Vec<_> = (0..BOUNDS.1).map(|row| Viewport {
+        bounds: (BOUNDS.0, 1),
+        upper_left: viewport.point((0, row)),
+        lower_right: viewport.point((BOUNDS.0, row + 1)),
+    }).collect();
+
+    let mut group = c.benchmark_group("mandelbrot::render");
+    group.throughput(Throughput::Elements((BOUNDS.0 * BOUNDS.1) as u64));
+    group.bench_function("serial", |b| b.iter(|| render_into(&mut pixels, &viewport, &options)));
+    group.bench_function("rayon", |b| b.iter(|| {
+        pixels.par_chunks_mut(BOUNDS.0).zip(&rows)
+              .for_each(|(line, row)| render_into(line, row, &options));
+    }));
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = rustfun_benches::criterion();
+    targets = bench_points, bench_pixel_to_point, bench_render
+}
+criterion_main!(benches);
@@ -0,0 +1,113 @@
+// Zoom sequences, behind `--frames N`: instead of a picture, an animation of N of them,
+// each closer in on the middle of the view than the one before. The file's format
+// says what kind: an animated GIF, or an animated PNG (APNG), which any browser plays
+// and any other PNG viewer shows the first frame of. A GIF of one frame is a GIF still.
+//
+// Each frame is written as soon as it's rendered, so a thousand frames need one
+// frame's worth of memory, not a thousand.
+//
+// Note:
+//
+// 1.) image writes GIF animations, through its GifEncoder, but not APNG; the png crate
+//     it uses underneath does, so that's called directly.
+// 2.) A GIF has at most 256 colors a frame. GifEncoder picks the 256 that suit each
+//     frame best, which for a smooth palette shows as a slight graininess.
+//
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::error::{EncodingError, ImageFormatHint};
+use image::{Delay, Frame, ImageError, ImageFormat, ImageResult, RgbaImage};
+use std::fs::File;
+use std::io::BufWriter;
+use crate::format::Format;
+
+/// How long each frame shows for, in milliseconds: ten frames a second.
+const DELAY_MS: u16 = 100;
+
+/// An animation being written to a file, one frame at a time.
+pub enum Animation {
+    Gif(GifEncoder<BufWriter<File>>, (u32, u32)),
+    Png(png::Writer<BufWriter<File>>),
+}
+
+impl Animation {
+    /// Start writing `frames` frames of `bounds`, with `channels` bytes per pixel (1 for
+    /// gray, 2 for 16-bit gray, 3 for RGB), to `filename`, in `format`, one that
+    /// `animates`. A GIF can't be 16-bit; a PNG says it was rendered with `options`.
+    pub fn create(filename: &str, format: Format, bounds: (usize, usize), channels: usize, frames: u32,
+                  options: &str) -> ImageResult<Animation>
+    {
+        assert!(format.animates());
+        let output = BufWriter::new(File::create(filename)?);
+        let (width, height) = (bounds.0 as u32, bounds.1 as u32);
+        if format == Format::Gif {
+            let mut encoder = GifEncoder::new(output);
+            encoder.set_repeat(Repeat::Infinite)?;
+            return Ok(Animation::Gif(encoder, (width, height)));
+        }
+        let mut encoder = png::Encoder::new(output, width, height);
+        encoder.set_color(if channels == 3 { png::ColorType::Rgb } else { png::ColorType::Grayscale });
+        encoder.set_depth(if channels == 2 { png::BitDepth::Sixteen } else { png::BitDepth::Eight });
+        // 0 plays is forever, like the GIF's Repeat::Infinite
+        encoder.set_animated(frames, 0).map_err(png_error)?;
+        encoder.set_frame_delay(DELAY_MS, 1000).map_err(png_error)?;
+        crate::metadata::add(&mut encoder, options).map_err(png_error)?;
+        Ok(Animation::Png(encoder.write_header().map_err(png_error)?))
+    }
+
+    /// Write the next frame: `pixels`, laid out as `create` was told.
+    pub fn add(&mut self, pixels: &[u8]) -> ImageResult<()> {
+        match self {
+            Animation::Gif(encoder, (width, height)) => {
+                let channels = pixels.len() / (*width as usize * *height as usize);
+                // GifEncoder takes RGBA frames; gray is the same byte three times
+                let rgba = pixels.chunks(channels)
+                    .flat_map(|p| [p[0], p[channels / 2], p[channels - 1], 255])
+                    .collect();
+                let image = RgbaImage::from_raw(*width, *height, rgba).expect("frame doesn't match bounds");
+                encoder.encode_frame(Frame::from_parts(image, 0, 0, Delay::from_numer_denom_ms(DELAY_MS as u32, 1)))
+            }
+            Animation::Png(writer) => writer.write_image_data(pixels).map_err(png_error),
+        }
+    }
+
+    /// Finish the file. A PNG must have had as many frames as `create` was told.
+    pub fn finish(self) -> ImageResult<()> {
+        match self {
+            // the GIF's trailer is written when the encoder is dropped
+            Animation::Gif(..) => Ok(()),
+            Animation::Png(writer) => writer.finish().map_err(png_error),
+        }
+    }
+}
+
+/// The png crate's errors as image's, so that main reports both the same way.
+pub fn png_error(e: png::EncodingError) -> ImageError {
+    match e {
+        png::EncodingError::IoError(e) => ImageError::IoError(e),
+        e => ImageError::Encoding(EncodingError::new(ImageFormatHint::Exact(ImageFormat::Png), e)),
+    }
+}
+
+#[test]
+fn test_animations() {
+    let dir = tempfile::tempdir().unwrap();
+    let frames: Vec<Vec<u8>> = (0..3u8).map(|f| (0..12).map(|i| i * 20 + f).collect()).collect();
+    for (name, channels) in [("gray.png", 1), ("color.png", 3), ("gray.gif", 1), ("color.gif", 3)] {
+        let file = dir.path().join(name);
+        let bounds = (4, 3 / channels);
+        let format = Format::from_filename(name).unwrap();
+        let mut animation = Animation::create(file.to_str().unwrap(), format, bounds, channels, 3, "").unwrap();
+        for frame in &frames {
+            animation.add(frame).unwrap();
+        }
+        animation.finish().unwrap();
+        let bytes = std::fs::read(&file).unwrap();
+        // an APNG announces its frames in an acTL chunk; a GIF has one image
+        // descriptor, a comma, for each frame, after a graphic control block
+        if name.ends_with(".png") {
+            assert_eq!(&bytes[37..45], b"acTL\0\0\0\x03", "{}", name);
+        } else {
+            assert_eq!(bytes.windows(2).filter(|w| w == b"\x21\xf9").count(), 3, "{}", name);
+        }
+    }
+}
@@ -0,0 +1,181 @@
+// Adaptive anti-aliasing, behind `--antialias N`. One point per pixel makes jagged
+// edges and speckles where the set's detail is finer than the pixels; N by N points per
+// pixel, averaged, smooths them, but costs N*N times as long, and almost all of that is
+// wasted inside the set and far outside it, where every point comes out the same.
+//
+// So the picture is rendered once as usual, and then only the pixels that differ from
+// a neighbour by more than THRESHOLD get the extra points: the edges, which are usually
+// a small part of the image.
+//
+// A picture rendered in parts, the bands of a streamed PNG or a checkpoint, the tiles of
+// --split or --distribute, is anti-aliased a part at a time. A pixel on a part's side
+// still has a neighbour past it, in the next part, and is on an edge when it differs from
+// that one as well: so each part is given the colors just past its sides, Around, for
+// the edges to come out the same as in the picture rendered whole.
+//
+use crate::split::Rectangle;
+use rayon::prelude::*;
+
+/// How far apart, in any one channel, a pixel and its neighbour have to be before the
+/// pixel is taken to be on an edge.
+// 30.  Eight steps of 255: the bands of plain gray coloring are one step apart, and
+//      shouldn't count as edges, but anything with a visible jump should.
+pub const THRESHOLD: u8 = 8;
+
+/// The most points across a pixel --antialias takes: 16 by 16, 256 to a pixel, is
+/// already more than an eye can tell from 8 by 8, and more would overflow the sizes of
+/// the finer image the points are taken from.
+pub const MOST: usize = 16;
+
+/// Which sides of a part of a picture the picture goes on past.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Sides {
+    pub left: bool,
+    pub top: bool,
+    pub right: bool,
+    pub bottom: bool,
+}
+
+impl Sides {
+    /// The sides of `part` of a picture of `bounds` that aren't the picture's own.
+    pub fn of(part: Rectangle, bounds: (usize, usize)) -> Sides {
+        let (x, y, width, height) = part;
+        Sides { left: x > 0, top: y > 0, right: x + width < bounds.0, bottom: y + height < bounds.1 }
+    }
+}
+
+/// The colors of the pixels just past the sides of a part of a picture, as the first pass
+/// colored them: the column left of it and right of it, a pixel for each row, and the row
+/// above it and below it, a pixel for each column. A side the picture ends at is empty.
+#[derive(Debug, Default)]
+pub struct Around {
+    pub left: Vec<u8>,
+    pub top: Vec<u8>,
+    pub right: Vec<u8>,
+    pub bottom: Vec<u8>,
+}
+
+/// Which pixels of `pixels`, an image of `bounds` with `channels` bytes per pixel, differ
+/// from the pixel left, right, above or below by more than THRESHOLD, row by row; those
+/// on its sides from the pixels `around` it, too.
+pub fn edges(pixels: &[u8], bounds: (usize, usize), channels: usize, around: &Around) -> Vec<bool> {
+    let pixel = |column: usize, row: usize| {
+        let at = (row * bounds.0 + column) * channels;
+        &pixels[at .. at + channels]
+    };
+    let differ = |a: &[u8], b: &[u8]| a.iter().zip(b).any(|(a, b)| a.abs_diff(*b) > THRESHOLD);
+    let mut edges = vec![false; bounds.0 * bounds.1];
+    for row in 0 .. bounds.1 {
+        for column in 0 .. bounds.0 {
+            let here = pixel(column, row);
+            // 30.1 Each pair is compared once, from its left or upper pixel, and marks both.
+            if column + 1 < bounds.0 && differ(here, pixel(column + 1, row)) {
+                edges[row * bounds.0 + column] = true;
+                edges[row * bounds.0 + column + 1] = true;
+            }
+            if row + 1 < bounds.1 && differ(here, pixel(column, row + 1)) {
+                edges[row * bounds.0 + column] = true;
+                edges[(row + 1) * bounds.0 + column] = true;
+            }
+        }
+    }
+    // 30.5 Past the sides only this part's pixels are marked: the next part marks its own.
+    fn beyond(side: &[u8], i: usize, channels: usize) -> Option<&[u8]> {
+        side.get(i * channels .. (i + 1) * channels)
+    }
+    for row in 0 .. bounds.1 {
+        if beyond(&around.left, row, channels).is_some_and(|other| differ(pixel(0, row), other)) {
+            edges[row * bounds.0] = true;
+        }
+        if beyond(&around.right, row, channels).is_some_and(|other| differ(pixel(bounds.0 - 1, row), other)) {
+            edges[row * bounds.0 + bounds.0 - 1] = true;
+        }
+    }
+    for column in 0 .. bounds.0 {
+        if beyond(&around.top, column, channels).is_some_and(|other| differ(pixel(column, 0), other)) {
+            edges[column] = true;
+        }
+        if beyond(&around.bottom, column, channels).is_some_and(|other| differ(pixel(column, bounds.1 - 1), other)) {
+            edges[(bounds.1 - 1) * bounds.0 + column] = true;
+        }
+    }
+    edges
+}
+
+/// Repaint the edge pixels of `pixels`, with the colors `around` it, with the average of
+/// `samples` colors, from `sample(column, row, i)` for i in 0..samples. Returns how many
+/// pixels that was.
+// 30.2 The edges are found in the first pass's image, before anything is repainted, so
+//      a repainted pixel doesn't make its neighbours look like edges, or stop them.
+//      Then, like render_c, rows go to rayon's threads one at a time.
+pub fn refine<F>(pixels: &mut [u8],
+                 bounds: (usize, usize),
+                 channels: usize,
+                 around: &Around,
+                 samples: usize,
+                 sample: F) -> usize
+    where F: Fn(usize, usize, usize) -> [u8; 3] + Sync
+{
+    assert!(pixels.len() == bounds.0 * bounds.1 * channels);
+    let edges = edges(pixels, bounds, channels, around);
+    pixels.par_chunks_mut(bounds.0 * channels).enumerate().for_each(|(row, line)| {
+        for column in 0 .. bounds.0 {
+            if !edges[row * bounds.0 + column] {
+                continue;
+            }
+            let mut sum = [0; 3];
+            for i in 0 .. samples {
+                for (total, c) in sum.iter_mut().zip(sample(column, row, i)) {
+                    *total += c as usize;
+                }
+            }
+            for (c, total) in line[column * channels .. (column + 1) * channels].iter_mut().zip(sum) {
+                *c = ((total + samples / 2) / samples) as u8;
+            }
+        }
+    });
+    edges.iter().filter(|&&edge| edge).count()
+}
+
+#[test]
+fn test_edges() {
+    // a bright square in the middle of a dark image: its rim, and the rim around it
+    let bounds = (6, 6);
+    let mut pixels = vec![10; 36];
+    for row in 2..4 {
+        for column in 2..4 {
+            pixels[row * 6 + column] = 200;
+        }
+    }
+    pixels[0] = 10 + THRESHOLD;     // close enough to its neighbours
+    let found = edges(&pixels, bounds, 1, &Around::default());
+    let edge = |column: usize, row: usize| found[row * 6 + column];
+    assert_eq!(found.iter().filter(|&&e| e).count(), 4 + 8);
+    assert!(edge(2, 2) && edge(2, 1) && edge(4, 3));
+    assert!(!edge(0, 0) && !edge(1, 1) && !edge(5, 5));
+
+    // the same, with a bright pixel past the top and one past the right side
+    let around = Around { top: vec![10, 10, 10, 10, 200, 10], right: vec![10, 10, 10, 10, 10, 200], ..Around::default() };
+    let found = edges(&pixels, bounds, 1, &around);
+    let edge = |column: usize, row: usize| found[row * 6 + column];
+    assert_eq!(found.iter().filter(|&&e| e).count(), 4 + 8 + 2);
+    assert!(edge(4, 0) && edge(5, 5) && !edge(5, 0));
+
+    assert_eq!(Sides::of((0, 64, 40, 64), (40, 200)), Sides { top: true, bottom: true, ..Sides::default() });
+    assert_eq!(Sides::of((0, 0, 40, 200), (40, 200)), Sides::default());
+    assert_eq!(Sides::of((128, 0, 72, 128), (200, 200)), Sides { left: true, bottom: true, ..Sides::default() });
+}
+
+#[test]
+fn test_refine() {
+    // two colors side by side: only the pixels either side of the line change
+    let bounds = (4, 2);
+    let mut pixels: Vec<u8> = [[0, 0, 0], [0, 0, 0], [255, 255, 255], [255, 255, 255]]
+        .iter().cycle().take(8).flatten().copied().collect();
+    let refined = refine(&mut pixels, bounds, 3, &Around::default(), 4, |column, _, i| {
+        if column == 1 && i == 0 || column == 2 && i < 3 { [255, 0, 100] } else { [0, 255, 100] }
+    });
+    assert_eq!(refined, 4);
+    let row = [0, 0, 0, 64, 191, 100, 191, 64, 100, 255, 255, 255];
+    assert_eq!(pixels, [row, row].concat());
+}
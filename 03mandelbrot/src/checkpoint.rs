@@ -0,0 +1,114 @@
+// Checkpoints, behind `--checkpoint FILE`: a long render that's interrupted, by Ctrl-C, a
+// crash or a reboot, doesn't have to start over. The image is rendered in bands of rows,
+// and each band is added to the checkpoint file as soon as it's done; `--resume FILE`
+// reads the bands back, renders the rest, and carries on adding to the same file. Once
+// the picture is written, the checkpoint is deleted.
+//
+// The file starts with the options of the render, as --print-config writes them, so
+// `mandelbrot --resume FILE` needs nothing else, and a resume with other options is
+// refused, rather than finishing one picture with the rows of another.
+//
+//   mandelbrot checkpoint
+//   <the length of the options, in bytes>
+//   <the options>
+//   <the pixels of the rows done, as the buffer has them>
+//
+// Note:
+//
+// 1.) Each band is appended, not the whole buffer written again, so checkpointing
+//     costs one more write of the image in all, however many bands there are.
+// 2.) If the render stopped in the middle of writing a band, the file ends in part of
+//     one; main keeps only the whole bands.
+// 3.) A resumed render's checkpoint, the rows kept from the old one and all, is written
+//     beside it first, as FILE.new, and then renamed over it: the rows done are on disk
+//     in one or the other all along, however the resume is interrupted.
+//
+use rustfun_error::{Error, Result};
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+const MAGIC: &str = "mandelbrot checkpoint\n";
+
+/// How many bands of rows a checkpointed picture is rendered in, or as near as it divides.
+// 34.  Enough that an interruption loses little; few enough that each band keeps all
+//      the threads busy, even in the tiles renderer.
+pub const BANDS: usize = 64;
+
+/// A checkpoint file being written, one band at a time.
+pub struct Checkpoint {
+    path: PathBuf,
+    file: File,
+}
+
+impl Checkpoint {
+    /// Start a checkpoint at `path`, replacing any there, of a render with `options`, with
+    /// `done`, the pixels of the rows rendered already.
+    pub fn create<P: AsRef<Path>>(path: P, options: &str, done: &[u8]) -> Result<Checkpoint> {
+        let path = path.as_ref().to_path_buf();
+        let mut new = path.clone().into_os_string();
+        new.push(".new");
+        let new = PathBuf::from(new);
+        let mut file = File::create(&new).map_err(|e| Error::io_at(&new, e))?;
+        write!(file, "{}{}\n{}", MAGIC, options.len(), options).map_err(|e| Error::io_at(&new, e))?;
+        file.write_all(done).and_then(|()| file.sync_all()).map_err(|e| Error::io_at(&new, e))?;
+        std::fs::rename(&new, &path).map_err(|e| Error::io_at(&path, e))?;
+        Ok(Checkpoint { path, file })
+    }
+
+    /// The options and the pixels so far of the checkpoint at `path`.
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<(String, Vec<u8>)> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path).map_err(|e| Error::io_at(path, e))?;
+        let not_one = || Error::Parse(format!("{}: not a mandelbrot checkpoint", path.display()));
+        let rest = bytes.strip_prefix(MAGIC.as_bytes()).ok_or_else(not_one)?;
+        let newline = rest.iter().position(|&b| b == b'\n').ok_or_else(not_one)?;
+        let length: usize = std::str::from_utf8(&rest[..newline]).ok()
+            .and_then(|n| n.parse().ok())
+            .ok_or_else(not_one)?;
+        let rest = &rest[newline + 1..];
+        if rest.len() < length {
+            return Err(not_one());
+        }
+        let options = String::from_utf8(rest[..length].to_vec()).map_err(|_| not_one())?;
+        Ok((options, rest[length..].to_vec()))
+    }
+
+    /// Add the pixels of the next rows.
+    pub fn add(&mut self, pixels: &[u8]) -> Result<()> {
+        self.file.write_all(pixels).map_err(|e| Error::io_at(&self.path, e))
+    }
+
+    /// The render is written: delete the checkpoint.
+    pub fn remove(self) -> Result<()> {
+        drop(self.file);
+        std::fs::remove_file(&self.path).map_err(|e| Error::io_at(&self.path, e))
+    }
+}
+
+#[test]
+fn test_checkpoint() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("render.ckpt");
+    let options = "size = \"4x3\"\nsmooth = true\n";
+    let mut checkpoint = Checkpoint::create(&path, options, &[]).unwrap();
+    assert_eq!(Checkpoint::read(&path).unwrap(), (options.to_string(), vec![]));
+    checkpoint.add(&[1, 2, 3, 4]).unwrap();
+    checkpoint.add(&[5, 6, 7, 8]).unwrap();
+    assert_eq!(Checkpoint::read(&path).unwrap(), (options.to_string(), (1..=8).collect()));
+
+    // started again from the rows done, it replaces the old one only once it has them
+    let (options, done) = Checkpoint::read(&path).unwrap();
+    let mut checkpoint = Checkpoint::create(&path, &options, &done).unwrap();
+    checkpoint.add(&[9]).unwrap();
+    assert_eq!(Checkpoint::read(&path).unwrap(), (options.to_string(), (1..=9).collect()));
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    checkpoint.remove().unwrap();
+    assert!(!path.exists());
+
+    for bytes in [&b"P5\n4 3\n255\n"[..], b"mandelbrot checkpoint\n", b"mandelbrot checkpoint\n99\nsize"] {
+        std::fs::write(&path, bytes).unwrap();
+        assert_eq!(Checkpoint::read(&path).unwrap_err().to_string(),
+                   format!("{}: not a mandelbrot checkpoint", path.display()));
+    }
+}
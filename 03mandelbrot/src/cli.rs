@@ -0,0 +1,449 @@
+// The command line, read by clap: a render's options, its positional arguments, and the
+// subcommands stitch, view and info. `--help` prints what clap makes of the help here,
+// in English or Chinese:
+//
+//   $ mandelbrot --help
+//   Usage: mandelbrot [OPTIONS] FILE PIXELS UPPERLEFT LOWERRIGHT [fast|tiles|slow]
+//      or: mandelbrot stitch INDEX [FILE]
+//   ...
+//
+// Note:
+//
+// 1.) clap's derive makes a parser of a struct: each field an argument, its type what the
+//     argument comes to, and its attributes the rest, the name, the help, and the
+//     value_parser that turns the text into the type. A value_parser here is a parse
+//     function of the program's own, which says None to what it can't take.
+// 2.) The help follows the language, so `--lang` is looked for before the parser is
+//     made. It's an argument too, global, taken after any subcommand, and so is
+//     --deterministic.
+// 3.) clap words its errors in English, and in a shape of its own; exit() says them the
+//     way every chapter says a mistake in its arguments (see common/src/args.rs), the
+//     program's name first and the usage after, in either language.
+// 4.) Any positional argument can be given by name instead, and the ones left fill in the
+//     rest in order, so which is which is main's to say, not clap's: clap takes up to
+//     five, each a value alone, and positional() hands them over in a list. (One list
+//     of five would do, but a list that takes a minus at the start takes every option
+//     after it as well.)
+// 5.) A corner, or a Julia set's c, starts with a minus as often as not: allow_hyphen_values
+//     takes it for a value, rather than an option clap doesn't know.
+// 6.) `Hosts` is Vec<String> by another name: clap's derive takes a field whose type is
+//     spelt Vec to be an option given again and again, where --distribute is one list.
+//
+use crate::format::Format;
+use crate::{antialias, parse_hosts, Backend, Precision};
+use clap::error::{ContextKind, ContextValue, ErrorKind};
+use clap::parser::ValueSource;
+use clap::{ArgAction, ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand};
+use mandelbrot::palette::Palette;
+use mandelbrot::parse_complex;
+use mandelbrot::render::Coloring;
+use num::Complex;
+use rustfun_common::lang::{self, Lang};
+use rustfun_common::{parse_pair, tr, Text};
+use std::fmt::Display;
+use std::collections::VecDeque;
+use std::net::IpAddr;
+use std::path::PathBuf;
+
+const USAGE: Text = Text {
+    en: "mandelbrot [OPTIONS] FILE PIXELS UPPERLEFT LOWERRIGHT [fast|tiles|slow]
+   or: mandelbrot stitch INDEX [FILE]
+   or: mandelbrot view [OPTIONS] [UPPERLEFT LOWERRIGHT]
+   or: mandelbrot info FILE",
+    zh: "mandelbrot [选项] FILE PIXELS UPPERLEFT LOWERRIGHT [fast|tiles|slow]
+  或: mandelbrot stitch INDEX [FILE]
+  或: mandelbrot view [选项] [UPPERLEFT LOWERRIGHT]
+  或: mandelbrot info FILE",
+};
+
+const HELP: Text = Text {
+    en: "Usage: {usage}
+  FILE of - writes a PPM to stdout; the renderer is fast unless named
+  Each positional argument can be given by name instead, in any order
+
+Commands:
+{subcommands}
+
+Options:
+{options}
+
+Example: mandelbrot mandel.png 1000x750 -1.20,0.35 -1,0.20 fast
+         mandelbrot --output mandel.png --size 1000x750 --upper-left -1.20,0.35 --lower-right -1,0.20
+         mandelbrot --julia -0.8,0.156 --palette fire --smooth julia.png 1000x750 -1.6,0.9 1.6,-0.9 fast",
+    zh: "用法: {usage}
+  FILE 为 - 时把 PPM 写到标准输出; 不指定渲染器时用 fast
+  每个位置参数都可以按名字给出, 顺序不限
+
+命令:
+{subcommands}
+
+选项:
+{options}
+
+示例: mandelbrot mandel.png 1000x750 -1.20,0.35 -1,0.20 fast
+      mandelbrot --output mandel.png --size 1000x750 --upper-left -1.20,0.35 --lower-right -1,0.20
+      mandelbrot --julia -0.8,0.156 --palette fire --smooth julia.png 1000x750 -1.6,0.9 1.6,-0.9 fast",
+};
+
+/// A subcommand's help: its usage, what it does, and its arguments.
+const COMMAND_HELP: Text = Text {
+    en: "Usage: {usage}\n{about}\n\nArguments:\n{positionals}\n\nOptions:\n{options}",
+    zh: "用法: {usage}\n{about}\n\n参数:\n{positionals}\n\n选项:\n{options}",
+};
+
+/// The hosts of --distribute, HOST:PORT each: see 6.) above.
+pub type Hosts = Vec<String>;
+
+/// The command line of mandelbrot.
+#[derive(Debug, Parser)]
+#[command(name = "mandelbrot", disable_help_flag = true, disable_help_subcommand = true,
+          args_conflicts_with_subcommands = true)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    #[arg(value_name = "FILE", allow_hyphen_values = true)]
+    file: Option<String>,
+    #[arg(value_name = "PIXELS", allow_hyphen_values = true)]
+    pixels: Option<String>,
+    #[arg(value_name = "UPPERLEFT", allow_hyphen_values = true)]
+    upper_left_corner: Option<String>,
+    #[arg(value_name = "LOWERRIGHT", allow_hyphen_values = true)]
+    lower_right_corner: Option<String>,
+    #[arg(value_name = "RENDERER", allow_hyphen_values = true)]
+    renderer_name: Option<String>,
+    #[arg(long, value_name = "FILE", help = tr!("FILE by name: the picture to write", "即 FILE: 要写的图片"))]
+    pub output: Option<String>,
+    #[arg(long, value_name = "PIXELS", value_parser = by(|s| parse_pair::<usize>(s, 'x')),
+          help = tr!("PIXELS by name: the picture's width and height, like 1000x750", "即 PIXELS: 图片的宽和高, 如 1000x750"))]
+    pub size: Option<(usize, usize)>,
+    #[arg(long, value_name = "RE,IM", allow_hyphen_values = true,
+          help = tr!("UPPERLEFT by name: the point at the picture's upper left corner", "即 UPPERLEFT: 图片左上角的点"))]
+    pub upper_left: Option<String>,
+    #[arg(long, value_name = "RE,IM", allow_hyphen_values = true,
+          help = tr!("LOWERRIGHT by name: the point at its lower right corner", "即 LOWERRIGHT: 右下角的点"))]
+    pub lower_right: Option<String>,
+    #[arg(long, value_name = "NAME", help = tr!("the renderer by name: fast, tiles or slow", "即渲染器: fast, tiles 或 slow"))]
+    pub renderer: Option<String>,
+    #[arg(long, value_name = "FILE", help = tr!("options from a TOML file, or a .json one; the command line's win",
+                                                  "从 TOML 文件 (或 .json 文件) 读取选项; 命令行上的优先"))]
+    pub config: Option<String>,
+    #[arg(long, help = tr!("print the options as a config file, and render nothing",
+                           "把选项输出为配置文件, 不渲染"))]
+    pub print_config: bool,
+    #[arg(long, value_name = "FILE", help = tr!("render each line of FILE, a command line's arguments, or a .json list",
+                                                  "FILE 的每一行是一次渲染的参数, 逐个渲染; 或 .json 列表"))]
+    pub jobs: Option<String>,
+    #[arg(long, value_name = "FILE", help = tr!("keep the rows done in FILE as they're rendered, until the picture is",
+                                                  "渲染时把完成的行存到 FILE, 图片写好后删除"))]
+    pub checkpoint: Option<String>,
+    #[arg(long, value_name = "FILE", help = tr!("carry on from the checkpoint FILE, with its render's options",
+                                                  "从检查点 FILE 接着渲染, 沿用它的选项"))]
+    pub resume: Option<String>,
+    #[arg(long, value_name = "N", value_parser = by(positive::<usize>),
+          help = tr!("the picture as PNGs of N by N pixels, and an index to stitch them with",
+                     "把图片分成 N×N 像素的 PNG, 外加拼接用的索引"))]
+    pub split: Option<usize>,
+    #[arg(long, value_name = "FILE", help = tr!("iteration statistics, as JSON, in FILE; or - to print them to stderr",
+                                                  "把迭代统计以 JSON 写到 FILE; 为 - 时输出到标准错误"))]
+    pub stats: Option<String>,
+    #[arg(long, value_name = "HOST:PORT,...", value_parser = by(parse_hosts),
+          help = tr!("render the picture's tiles on workers at HOST:PORT",
+                     "把图片的方块交给 HOST:PORT 上的工作进程渲染"))]
+    pub distribute: Option<Hosts>,
+    #[arg(long, value_name = "PORT", value_parser = by(|p| p.parse::<u16>().ok()),
+          help = tr!("render tiles for --distribute on PORT, until killed", "在 PORT 上为 --distribute 渲染方块, 直到被终止"))]
+    pub serve_worker: Option<u16>,
+    #[arg(long, value_name = "ADDRESS", requires = "serve_worker", value_parser = by(|a| a.parse::<IpAddr>().ok()),
+          help = tr!("--serve-worker on ADDRESS, 0.0.0.0 for every one (127.0.0.1)",
+                     "--serve-worker 监听的地址, 0.0.0.0 为所有地址 (127.0.0.1)"))]
+    pub bind: Option<IpAddr>,
+    #[command(flatten)]
+    pub set: SetOptions,
+    #[arg(long, help = tr!("--coloring smooth: blend the colors instead of drawing them in bands",
+                           "即 --coloring smooth: 颜色平滑过渡, 不分成一条条的"))]
+    pub smooth: bool,
+    #[arg(long, help = tr!("iterate every pixel of a deep zoom in double-double", "深度缩放时每个像素都用 double-double 迭代"))]
+    pub no_perturbation: bool,
+    #[arg(long, help = tr!("iterate points inside the set to the limit, without looking for cycles",
+                           "集合内的点一直迭代到上限, 不检测循环"))]
+    pub no_period_check: bool,
+    #[arg(long, value_name = "NAME", value_parser = by(Backend::from_name),
+          help = tr!("cpu (the default), or gpu: the escape loop on the graphics card, in f32",
+                     "cpu (默认) 或 gpu: 在显卡上迭代, 用 f32"))]
+    pub backend: Option<Backend>,
+    #[arg(long, value_name = "N", value_parser = by(positive::<usize>),
+          help = tr!("tiles of N by N pixels for the tiles renderer (32)", "tiles 渲染器的方块边长, 以像素计 (32)"))]
+    pub tile_size: Option<usize>,
+    #[arg(long, value_name = "N", value_parser = by(positive::<usize>),
+          help = tr!("render on N threads (one per core)", "用 N 个线程渲染 (每个核一个)"))]
+    pub threads: Option<usize>,
+    #[arg(long, value_name = "N", value_parser = by(|n| n.parse().ok().filter(|n: &usize| (1..=antialias::MOST).contains(n))),
+          help = tr!("N by N points for each pixel on an edge, averaged, N up to 16 (off)",
+                     "边缘上的像素各取 N×N 个点求平均, N 最大 16 (默认关闭)"))]
+    pub antialias: Option<usize>,
+    #[arg(long, value_name = "NAME", value_parser = by(Format::from_name),
+          help = tr!("png, jpeg, bmp, tiff, gif or ppm (the FILE's extension)",
+                     "png, jpeg, bmp, tiff, gif 或 ppm (默认看 FILE 的扩展名)"))]
+    pub format: Option<Format>,
+    #[arg(long, value_name = "N", value_parser = by(positive::<u32>),
+          help = tr!("a .gif or .png animation of N frames, zooming in on the middle (1)",
+                     "向中心放大的 N 帧 .gif 或 .png 动画 (1)"))]
+    pub frames: Option<u32>,
+    #[arg(long, value_name = "F", value_parser = by(|z| z.parse().ok().filter(|&z: &f64| z.is_finite() && z > 0.0)),
+          help = tr!("each frame F times closer than the one before (1.1)", "每帧比上一帧放大 F 倍 (1.1)"))]
+    pub zoom: Option<f64>,
+    #[arg(long, global = true, value_name = "LANG", value_parser = by(|l| l.parse::<Lang>().ok()),
+          help = tr!("en or zh, the language to say all this in", "en 或 zh, 用哪种语言说这些"))]
+    pub lang: Option<Lang>,
+    #[arg(long, global = true, help = tr!("print the same thing on every run: no times", "每次运行输出都一样: 不带时间"))]
+    pub deterministic: bool,
+    #[arg(long, global = true, action = ArgAction::Help, help = tr!("print this help", "输出这份帮助"))]
+    pub help: Option<bool>,
+    /// The positional arguments, in order: see 4.) above.
+    #[arg(skip)]
+    pub positional: VecDeque<String>,
+    /// The arguments it was read from, for the options of a config file to go in behind.
+    #[arg(skip)]
+    pub arguments: Vec<String>,
+}
+
+/// Which set, and how it's colored: the options of a render that `view` takes as well.
+#[derive(Debug, clap::Args)]
+pub struct SetOptions {
+    #[arg(long, value_name = "RE,IM", allow_hyphen_values = true, value_parser = by(parse_complex),
+          help = tr!("the Julia set of c = RE + IM i, not the Mandelbrot set",
+                     "画 c = RE + IM i 的 Julia 集, 而不是 Mandelbrot 集"))]
+    pub julia: Option<Complex<f64>>,
+    #[arg(long, value_name = "D", value_parser = by(a_power),
+          help = tr!("iterate z^D + c, for any D of 2 or more, not z^2 + c (2)", "迭代 z^D + c 而不是 z^2 + c, D 不小于 2 (2)"))]
+    pub power: Option<f64>,
+    #[arg(long, value_name = "N", value_parser = by(positive::<u32>),
+          help = tr!("give up on a point after N iterations (255, or 1000 for distance)",
+                     "每个点最多迭代 N 次 (255, distance 时为 1000)"))]
+    pub limit: Option<u32>,
+    #[arg(long, value_name = "R", value_parser = by(two_or_more),
+          help = tr!("take a point to escape once z is R from 0, for R of 2 or more (2)",
+                     "z 离原点 R 远时算作逃逸, R 不小于 2 (2)"))]
+    pub escape_radius: Option<f64>,
+    #[arg(long, value_name = "NAME", value_parser = by(Palette::from_name),
+          help = tr!("gray (the default), fire, ocean or rainbow, or gray16 for the counts",
+                     "gray (默认), fire, ocean 或 rainbow, gray16 则保存迭代次数"))]
+    pub palette: Option<Palette>,
+    #[arg(long, value_name = "FILE", help = tr!("the colors in FILE, a Fractint .map or a CSV of R,G,B, mixed between",
+                                                  "用 FILE 里的颜色, Fractint .map 或 R,G,B 的 CSV, 中间混合过渡"))]
+    pub palette_file: Option<String>,
+    #[arg(long, value_name = "NAME", value_parser = by(Coloring::from_name),
+          help = tr!("bands (the default), smooth, or distance: thin lines along the edge",
+                     "bands (默认), smooth 或 distance: 沿边缘画细线"))]
+    pub coloring: Option<Coloring>,
+    #[arg(long, value_name = "NAME", value_parser = by(Precision::from_name),
+          help = tr!("f32 for a quick preview, or f64 (the default), double-double as needed",
+                     "f32 用于快速预览, 或 f64 (默认), 需要时用 double-double"))]
+    pub precision: Option<Precision>,
+}
+
+/// What mandelbrot does instead of rendering a picture.
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    #[command(disable_help_flag = true, about = tr!("put the tiles of --split together", "把 --split 的方块拼起来"))]
+    Stitch {
+        #[arg(value_name = "INDEX", help = tr!("the index --split wrote", "--split 写的索引"))]
+        index: PathBuf,
+        #[arg(value_name = "FILE", help = tr!("the PNG to write (the one the index is of)", "要写的 PNG (默认是索引所属的那张)"))]
+        file: Option<PathBuf>,
+    },
+    #[command(disable_help_flag = true, about = tr!("pan and zoom in a window", "在窗口中平移和缩放"))]
+    View(ViewArgs),
+    #[command(disable_help_flag = true, about = tr!("print the options a PNG was rendered with", "输出渲染 PNG 时用的选项"))]
+    Info {
+        #[arg(value_name = "FILE", help = tr!("a PNG mandelbrot wrote", "mandelbrot 写的 PNG"))]
+        file: PathBuf,
+    },
+}
+
+/// `mandelbrot view`'s arguments.
+#[derive(Debug, clap::Args)]
+pub struct ViewArgs {
+    #[arg(long, value_name = "PIXELS", value_parser = by(|s| parse_pair(s, 'x').filter(|&(w, h): &(usize, usize)| w > 0 && h > 0)),
+          help = tr!("the window's size (800x600)", "窗口的大小 (800x600)"))]
+    pub size: Option<(usize, usize)>,
+    #[command(flatten)]
+    pub set: SetOptions,
+    #[arg(value_name = "UPPERLEFT", allow_hyphen_values = true, requires = "lower_right",
+          help = tr!("the corners of the view (the whole set)", "视野的两角 (整个集合)"))]
+    pub upper_left: Option<String>,
+    #[arg(value_name = "LOWERRIGHT", allow_hyphen_values = true)]
+    pub lower_right: Option<String>,
+}
+
+/// A value_parser of `parse`, which says None for what it can't take. Its error goes
+/// unsaid: exit() says what was wrong, from the option and the value.
+fn by<T, F>(parse: F) -> impl Fn(&str) -> Result<T, String> + Clone + Send + Sync + 'static
+    where F: Fn(&str) -> Option<T> + Clone + Send + Sync + 'static
+{
+    move |s| parse(s).ok_or_else(String::new)
+}
+
+/// A count of 1 or more.
+fn positive<T: std::str::FromStr + PartialOrd + Default>(s: &str) -> Option<T> {
+    s.parse().ok().filter(|n| *n > T::default())
+}
+
+/// An escape radius: a number of 2 or more.
+fn two_or_more(s: &str) -> Option<f64> {
+    s.parse().ok().filter(|&d: &f64| d.is_finite() && d >= 2.0)
+}
+
+/// A power: a number of 2 or more, and no more than a u32 holds, for a whole one to be
+/// squared and multiplied.
+fn a_power(s: &str) -> Option<f64> {
+    two_or_more(s).filter(|&d| d <= u32::MAX as f64)
+}
+
+impl Cli {
+    /// The command line `arguments`, the program's name left off. A mistake in them is
+    /// reported, and the program exits; so does --help, once it's printed the help.
+    pub fn parse(arguments: &[String]) -> Cli {
+        // 2.) the language first, for the help to be in it
+        if let Ok(Some(language)) = lang::take_flag(&mut arguments.to_vec()) {
+            lang::set(language);
+        }
+        let program = std::iter::once("mandelbrot".to_string());
+        let matches = command().try_get_matches_from(program.chain(arguments.iter().cloned()))
+            .unwrap_or_else(|e| fail(e));
+        let mut cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| fail(e));
+        if cli.deterministic {
+            rustfun_common::deterministic::set(true);
+        }
+        // --jobs and --serve-worker go with nothing else, but a worker's --bind and --threads
+        alone(&matches, "jobs", &[]);
+        alone(&matches, "serve_worker", &["bind", "threads"]);
+        cli.positional = [cli.file.take(), cli.pixels.take(), cli.upper_left_corner.take(),
+                          cli.lower_right_corner.take(), cli.renderer_name.take()].into_iter().flatten().collect();
+        cli.arguments = arguments.to_vec();
+        cli
+    }
+}
+
+/// The parser, with the help in the current language.
+fn command() -> clap::Command {
+    let help = |command: clap::Command, usage: Text| command.override_usage(usage.get()).help_template(COMMAND_HELP.get());
+    Cli::command()
+        .override_usage(USAGE.get())
+        .help_template(HELP.get())
+        .mut_subcommand("stitch", |c| help(c, Text { en: "mandelbrot stitch INDEX [FILE]", zh: "mandelbrot stitch INDEX [FILE]" }))
+        .mut_subcommand("view", |c| help(c, Text { en: "mandelbrot view [OPTIONS] [UPPERLEFT LOWERRIGHT]",
+                                                  zh: "mandelbrot view [选项] [UPPERLEFT LOWERRIGHT]" }))
+        .mut_subcommand("info", |c| help(c, Text { en: "mandelbrot info FILE", zh: "mandelbrot info FILE" }))
+}
+
+/// Exit, saying so, if the option `id` is on the command line with any option but the
+/// global ones and `but`.
+fn alone(matches: &ArgMatches, id: &str, but: &[&str]) {
+    let given = |other: &str| matches.value_source(other) == Some(ValueSource::CommandLine);
+    if !given(id) {
+        return;
+    }
+    let global = ["lang", "deterministic", "help"];
+    let mut others = matches.ids().map(|other| other.as_str())
+        .filter(|other| *other != id && !but.contains(other) && !global.contains(other));
+    if let Some(other) = others.find(|other| given(other)) {
+        exit(tr!("{}: not with {}", "{}: 不能和 {} 一起用", long(id), long(other)));
+    }
+}
+
+/// The option `id` as it's typed, `--name`; or a positional argument's name.
+fn long(id: &str) -> String {
+    let command = Cli::command();
+    let name = command.get_arguments().find(|arg| arg.get_id() == id)
+        .map(|arg| arg.get_long().map_or_else(|| "FILE".to_string(), |name| format!("--{}", name)));
+    name.unwrap_or_else(|| id.to_string())
+}
+
+/// Say what's wrong with the arguments, with the usage after it, and exit with status 1.
+pub fn exit<M: Display>(message: M) -> ! {
+    eprintln!("mandelbrot: {}\n{}\n{}", message, tr!("Usage: {}", "用法: {}", USAGE),
+              tr!("(--help for more)", "(更多请看 --help)"));
+    std::process::exit(1);
+}
+
+/// Exit for clap's error `e`, worded as exit() words ours: see 3.) above. --help is an
+/// error to clap too, and prints the help.
+fn fail(e: clap::Error) -> ! {
+    if e.kind() == ErrorKind::DisplayHelp {
+        e.exit();
+    }
+    let context = |kind| match e.get(kind) {
+        Some(ContextValue::String(s)) => s.clone(),
+        Some(ContextValue::Strings(s)) => s.join(" "),
+        _ => String::new(),
+    };
+    // "--tile-size <N>" is the option --tile-size, and "<INDEX>" the argument INDEX
+    let arg = context(ContextKind::InvalidArg);
+    let name = arg.split_whitespace().next().unwrap_or_default().trim_matches(['<', '>', '[', ']', '.']).to_string();
+    let value = context(ContextKind::InvalidValue);
+    exit(match e.kind() {
+        ErrorKind::InvalidValue if value.is_empty() => tr!("{} needs a value", "{} 需要一个值", name),
+        ErrorKind::InvalidValue | ErrorKind::ValueValidation => tr!("{}: can't parse {:?}", "{}: 无法解析 {:?}", name, value),
+        ErrorKind::UnknownArgument => tr!("unexpected argument {:?}", "多余的参数 {:?}", arg),
+        ErrorKind::MissingRequiredArgument => tr!("missing {}", "缺少 {}", name),
+        // a word past the positionals, taken for a subcommand that can't follow them
+        ErrorKind::ArgumentConflict if e.get(ContextKind::InvalidSubcommand).is_some() =>
+            tr!("unexpected argument {:?}", "多余的参数 {:?}", context(ContextKind::InvalidSubcommand)),
+        ErrorKind::ArgumentConflict => match context(ContextKind::PriorArg) {
+            prior if prior == arg => tr!("{}: given twice", "{}: 给了两次", name),
+            prior => tr!("{}: not with {}", "{}: 不能和 {} 一起用", name, prior.split_whitespace().next().unwrap_or_default()),
+        },
+        _ => e.kind().as_str().unwrap_or("can't read the arguments").to_string(),
+    })
+}
+
+/// Options that set the same thing, so that typing one overrides any of them behind it.
+const SAME_SETTING: &[&[&str]] = &[&["--coloring", "--smooth"], &["--palette", "--palette-file"]];
+
+/// `arguments` with `options` after them, each `--name=value` or a flag `--name`, but for
+/// those the arguments give already, by that name or another for the same setting:
+/// defaults, from a config file say, that the command line overrides.
+pub fn behind(arguments: &[String], options: Vec<String>) -> Vec<String> {
+    let typed = |name: &str| {
+        let prefix = format!("{}=", name);
+        arguments.iter().any(|a| a == name || a.starts_with(&prefix))
+    };
+    let mut all = arguments.to_vec();
+    for option in options {
+        let name = option.split('=').next().unwrap_or_default();
+        let same = SAME_SETTING.iter().find(|names| names.contains(&name)).copied().unwrap_or(&[]);
+        if !typed(name) && !same.iter().any(|other| typed(other)) {
+            all.push(option);
+        }
+    }
+    all
+}
+
+#[test]
+fn test_parse() {
+    let words = |s: &str| s.split(' ').map(String::from).collect::<Vec<_>>();
+    let cli = Cli::parse(&words("--palette fire mandel.png --julia -0.8,0.156 40x30 -1.20,0.35 --limit=100 -1,0.20"));
+    assert_eq!(cli.positional, words("mandel.png 40x30 -1.20,0.35 -1,0.20"));
+    assert_eq!((cli.set.palette, cli.set.julia, cli.set.limit),
+               (Some(Palette::Fire), Some(Complex { re: -0.8, im: 0.156 }), Some(100)));
+    assert_eq!(cli.arguments.len(), 9);
+    let cli = Cli::parse(&words("--distribute alpha:7878,beta:7878 --size 40x30 --stats -"));
+    assert_eq!(cli.distribute, Some(words("alpha:7878 beta:7878")));
+    assert_eq!((cli.size, cli.stats.as_deref()), (Some((40, 30)), Some("-")));
+    assert!(matches!(Cli::parse(&words("view --lang en -2,1 1,-1")).command,
+                     Some(Command::View(ViewArgs { upper_left: Some(_), lower_right: Some(_), .. }))));
+}
+
+#[test]
+fn test_behind() {
+    let words = |s: &str| s.split(' ').map(String::from).collect::<Vec<_>>();
+    assert_eq!(behind(&words("--palette fire out.png --zoom=3"), words("--palette=gray --zoom=2 --smooth")),
+               words("--palette fire out.png --zoom=3 --smooth"));
+    // --smooth is a coloring too
+    assert_eq!(behind(&words("--smooth"), words("--coloring=bands --limit=100")), words("--smooth --limit=100"));
+    assert_eq!(behind(&words("--coloring distance"), words("--smooth")), words("--coloring distance"));
+    // and a palette file a palette
+    assert_eq!(behind(&words("--palette-file=x.map"), words("--palette=gray")), words("--palette-file=x.map"));
+    assert_eq!(behind(&words("--palette fire"), words("--palette-file=x.map")), words("--palette fire"));
+}
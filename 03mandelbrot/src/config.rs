@@ -0,0 +1,170 @@
+// Render jobs in a file, behind `--config render.toml`: the options of a render written
+// down once, to keep, to put under version control, and to run again. The keys are the
+// long options without their dashes, and the positional arguments by their option names:
+//
+//   output = "mandel.png"
+//   size = "1000x750"
+//   upper-left = "-1.20,0.35"
+//   lower-right = "-1,0.20"
+//   palette = "fire"
+//   smooth = true
+//
+// A file ending in .json is read as JSON instead, with the same keys. Anything given on
+// the command line as well wins over the file. `--print-config` writes out the options
+// a command line comes to, defaults and all, in this form.
+//
+// Note:
+//
+// 1.) The file's options become arguments, `--key=value` for each, and cli::behind puts
+//     them after the typed ones that don't already say otherwise; the lot is read again
+//     by Cli::parse, so they're checked, and their mistakes reported, like the typed ones.
+// 2.) The corners, the Julia set's c and the size are strings, as on the command line:
+//     a TOML or JSON number is an f64, and would lose the digits of a deep zoom.
+// 3.) #[serde(deny_unknown_fields)] makes a misspelt key an error, rather than an
+//     option quietly left out.
+// 4.) palette-colors has no option of its own: the coordinator of a distributed render
+//     puts a palette file's colors in the options it sends, instead of the file's
+//     path, and a worker paints with them. A file of the user's can't have it.
+//
+use rustfun_error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// The options of a render, as a config file gives them.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Config {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upper_left: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lower_right: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub renderer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub julia: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub palette: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub palette_file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub palette_colors: Option<Vec<[u8; 3]>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coloring: Option<String>,
+    pub smooth: bool,
+    pub no_perturbation: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub precision: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backend: Option<String>,
+    pub no_period_check: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tile_size: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub antialias: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frames: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub zoom: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub split: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub power: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub escape_radius: Option<f64>,
+}
+
+impl Config {
+    /// Read the config file at `path`: JSON if its name ends in .json, TOML otherwise.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Config> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|e| Error::io_at(path, e))?;
+        let config: Config = if path.extension().is_some_and(|e| e.eq_ignore_ascii_case("json")) {
+            serde_json::from_str(&text).map_err(|e| Error::parse(format!("{}: {}", path.display(), e)))?
+        } else {
+            Config::from_toml(&text, path)?
+        };
+        // 4.) above
+        if config.palette_colors.is_some() {
+            return Err(Error::parse(format!("{}: palette-colors: only in a worker's tile request; use palette-file",
+                                            path.display())));
+        }
+        Ok(config)
+    }
+
+    /// The config in `text`, TOML, read from the file `path`.
+    pub fn from_toml<P: AsRef<Path>>(text: &str, path: P) -> Result<Config> {
+        toml::from_str(text).map_err(|e| Error::parse(format!("{}: {}", path.as_ref().display(), e)))
+    }
+
+    /// The options as command-line arguments, `--key=value`, or `--key` for a flag
+    /// that's set.
+    pub fn arguments(&self) -> Vec<String> {
+        // the keys and values come from the same serde attributes as the file's
+        let Ok(serde_json::Value::Object(options)) = serde_json::to_value(self) else {
+            unreachable!("a Config is a JSON object")
+        };
+        options.into_iter().filter_map(|(key, value)| match value {
+            serde_json::Value::Bool(true) => Some(format!("--{}", key)),
+            serde_json::Value::String(s) => Some(format!("--{}={}", key, s)),
+            serde_json::Value::Number(n) => Some(format!("--{}={}", key, n)),
+            _ => None,
+        }).collect()
+    }
+
+    /// The config as a TOML file.
+    pub fn to_toml(&self) -> String {
+        toml::to_string(self).expect("a Config has only strings, numbers and flags")
+    }
+}
+
+#[test]
+fn test_load() {
+    let dir = tempfile::tempdir().unwrap();
+    let toml = dir.path().join("render.toml");
+    std::fs::write(&toml, "output = \"mandel.png\"\nupper-left = \"-1.20,0.35\"\nsmooth = true\nframes = 3\n")
+        .unwrap();
+    let config = Config::load(&toml).unwrap();
+    assert_eq!(config, Config {
+        output: Some("mandel.png".to_string()),
+        upper_left: Some("-1.20,0.35".to_string()),
+        smooth: true,
+        frames: Some(3),
+        ..Config::default()
+    });
+    let json = dir.path().join("render.json");
+    std::fs::write(&json, r#"{"output": "mandel.png", "upper-left": "-1.20,0.35", "smooth": true, "frames": 3}"#)
+        .unwrap();
+    assert_eq!(Config::load(&json).unwrap(), config);
+    // and it goes back to a file that reads the same
+    std::fs::write(&toml, config.to_toml()).unwrap();
+    assert_eq!(Config::load(&toml).unwrap(), config);
+
+    std::fs::write(&toml, "pallete = \"fire\"\n").unwrap();
+    let error = Config::load(&toml).unwrap_err().to_string();
+    assert!(error.starts_with(&format!("{}: ", toml.display())) && error.contains("pallete"), "{}", error);
+    assert!(Config::load(dir.path().join("missing.toml")).is_err());
+    std::fs::write(&toml, "palette-colors = [[0, 0, 0], [255, 255, 255]]\n").unwrap();
+    let error = Config::load(&toml).unwrap_err().to_string();
+    assert!(error.ends_with(": palette-colors: only in a worker's tile request; use palette-file"), "{}", error);
+}
+
+#[test]
+fn test_arguments() {
+    let config = Config {
+        size: Some("40x30".to_string()),
+        no_period_check: true,
+        zoom: Some(1.5),
+        ..Config::default()
+    };
+    let mut arguments = config.arguments();
+    arguments.sort();
+    assert_eq!(arguments, ["--no-period-check", "--size=40x30", "--zoom=1.5"]);
+}
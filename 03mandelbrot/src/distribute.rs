@@ -0,0 +1,342 @@
+// Rendering on other machines, behind `--distribute HOST:PORT,...`: the picture is cut
+// into tiles, and each tile is sent over TCP to a worker, another mandelbrot started with
+// `--serve-worker PORT`, which renders it and sends back its pixels. The coordinator, the
+// mandelbrot that was given --distribute, puts them together and writes the picture:
+//
+//   alpha$ mandelbrot --serve-worker 7878 --bind 0.0.0.0
+//   beta$  mandelbrot --serve-worker 7878 --bind 0.0.0.0
+//   here$  mandelbrot --distribute alpha:7878,beta:7878 mandel.png 8000x6000 -1.20,0.35 -1,0.20
+//
+// Note:
+//
+// 1.) The wire format, a connection for each tile:
+//
+//       request:  "mandelbrot tile 1\n", "X Y WIDTH HEIGHT\n", "LENGTH\n", then LENGTH bytes
+//                 of options, the render's as --print-config prints them
+//       reply:    "ok LENGTH\n", then LENGTH bytes of pixels, a row at a time from the top;
+//                 or "error MESSAGE\n"
+//
+//     A worker is told everything it needs for each tile, so it keeps nothing between
+//     them, and renders a tile just as the coordinator would, palette, limit and all.
+//     A palette file is sent as its colors, palette-colors: a worker opens no file a
+//     request names.
+// 2.) Each host gets a thread of the coordinator's, taking tiles from a queue they share,
+//     so a fast worker renders more of the picture than a slow one.
+// 3.) A tile that fails on the way, a worker refusing the connection, hanging up or
+//     sending what isn't a reply, goes back on the queue, for whichever host is next; its
+//     host waits a moment before trying another. After TRIES failures in a row the host
+//     is given up on, and once they all are, the render is. An "error" reply is the
+//     options' fault, and would be the same anywhere: the render stops there. So a
+//     worker only sends one for a request it could read, and just hangs up on a
+//     coordinator whose request broke off, for the tile to be tried again.
+// 4.) A worker serves one connection at a time: a tile is rendered with all its cores.
+// 5.) Nothing waits on the other end for ever: a worker that stops answering in the
+//     middle of a tile is as good as one that hung up, and a coordinator that stops
+//     sending its request is hung up on, so that it can't hold up the others.
+// 6.) There's no telling who's asking: a worker listens on 127.0.0.1 unless --bind says
+//     otherwise, and turns away a tile bigger than TILE by TILE, or a limit above LIMIT,
+//     which no coordinator would send.
+//
+use crate::split::Rectangle;
+use rustfun_error::{Error, Result};
+use std::collections::VecDeque;
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Mutex};
+use std::time::Duration;
+
+/// The width and height of the tiles a distributed picture is cut into.
+pub const TILE: usize = 128;
+
+/// The highest limit a worker iterates to: with tiles no bigger than TILE, a request
+/// can't keep it busy for ever.
+pub const LIMIT: u32 = 1_000_000;
+
+/// The first line of a request, naming the protocol and its version.
+const HELLO: &str = "mandelbrot tile 1";
+
+/// How many times in a row a host can fail before it's given up on: see 3.) above.
+const TRIES: u32 = 3;
+
+/// How long a host that failed waits before trying again.
+const PAUSE: Duration = Duration::from_millis(200);
+
+/// How long a host has to accept a connection.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long a coordinator waits on a worker, for each read or write, rendering the tile
+/// included: see 5.) above.
+const TIMEOUT: Duration = Duration::from_secs(120);
+
+/// How long a worker waits on a coordinator, for each read or write.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The longest a line of the protocol, or the options, can be: a request with more is
+/// garbled, and isn't read into memory.
+const MOST: u64 = 1 << 16;
+
+/// Send the request for the pixels of `tile` of the render `options` describes.
+pub fn write_request<W: Write>(mut output: W, options: &str, tile: Rectangle) -> io::Result<()> {
+    write!(output, "{}\n{} {} {} {}\n{}\n{}", HELLO, tile.0, tile.1, tile.2, tile.3, options.len(), options)?;
+    output.flush()
+}
+
+/// Read a request: the render's options, and the tile of it wanted.
+pub fn read_request<R: BufRead>(mut input: R) -> Result<(String, Rectangle)> {
+    let hello = read_line(&mut input)?;
+    if hello != HELLO {
+        return Err(Error::Parse(format!("{:?}: not a tile request", hello)));
+    }
+    let line = read_line(&mut input)?;
+    let tile = match line.split(' ').map(str::parse).collect::<std::result::Result<Vec<usize>, _>>()?[..] {
+        [x, y, width, height] => (x, y, width, height),
+        _ => return Err(Error::Parse(format!("{:?}: not a tile", line))),
+    };
+    let len: u64 = read_line(&mut input)?.parse()?;
+    if len > MOST {
+        return Err(Error::Parse(format!("{} bytes of options is too many", len)));
+    }
+    let mut options = Vec::new();
+    input.take(len).read_to_end(&mut options)?;
+    if options.len() as u64 != len {
+        return Err(Error::Io(io::ErrorKind::UnexpectedEof.into()));
+    }
+    let options = String::from_utf8(options).map_err(|_| Error::Parse("the options aren't UTF-8".to_string()))?;
+    Ok((options, tile))
+}
+
+/// Send the reply to a request: the tile's pixels, or what was wrong with it.
+pub fn write_reply<W: Write>(mut output: W, reply: &std::result::Result<Vec<u8>, String>) -> io::Result<()> {
+    match reply {
+        Ok(pixels) => {
+            writeln!(output, "ok {}", pixels.len())?;
+            output.write_all(pixels)?;
+        }
+        // the message is a line of the protocol
+        Err(message) => writeln!(output, "error {}", message.replace('\n', " "))?,
+    }
+    output.flush()
+}
+
+/// Read the reply to a request for a tile of `len` bytes. Anything but the pixels or an
+/// error is an io::Error of its own, for the tile to be tried again.
+pub fn read_reply<R: BufRead>(mut input: R, len: usize) -> io::Result<std::result::Result<Vec<u8>, String>> {
+    let line = read_line(&mut input)?;
+    if let Some(message) = line.strip_prefix("error ") {
+        return Ok(Err(message.to_string()));
+    }
+    if line.strip_prefix("ok ").and_then(|n| n.parse().ok()) != Some(len) {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, format!("{:?}: not a tile of {} bytes", line, len)));
+    }
+    let mut pixels = vec![0; len];
+    input.read_exact(&mut pixels)?;
+    Ok(Ok(pixels))
+}
+
+/// A line of the protocol, without its newline.
+fn read_line<R: BufRead>(input: &mut R) -> io::Result<String> {
+    let mut line = String::new();
+    input.take(MOST).read_line(&mut line)?;
+    match line.strip_suffix('\n') {
+        Some(line) => Ok(line.to_string()),
+        None => Err(io::ErrorKind::UnexpectedEof.into()),
+    }
+}
+
+/// Serve tiles on `address` until killed: `render` gives the pixels of a tile of the
+/// render the options describe.
+pub fn serve(address: SocketAddr, render: impl Fn(&str, Rectangle) -> Result<Vec<u8>>) -> Result<()> {
+    let listener = TcpListener::bind(address)?;
+    eprintln!("mandelbrot: serving tiles on {}", listener.local_addr()?);
+    serve_on(listener, REQUEST_TIMEOUT, render);
+    Ok(())
+}
+
+/// Serve tiles to the connections `listener` takes, waiting on each at most `timeout`.
+fn serve_on(listener: TcpListener, timeout: Duration, render: impl Fn(&str, Rectangle) -> Result<Vec<u8>>) {
+    for stream in listener.incoming() {
+        // a coordinator that went away is its own problem, not the worker's
+        let served = stream.and_then(|stream| {
+            stream.set_read_timeout(Some(timeout))?;
+            stream.set_write_timeout(Some(timeout))?;
+            let reply = match read_request(BufReader::new(&stream)) {
+                Err(Error::Io(e)) => return Err(e),
+                request => request.and_then(|(options, tile)| render(&options, tile)).map_err(|e| e.to_string()),
+            };
+            if let Err(message) = &reply {
+                log::warn!("{}: {}", stream.peer_addr()?, message);
+            }
+            write_reply(BufWriter::new(&stream), &reply)
+        });
+        if let Err(e) = served {
+            log::warn!("{}", e);
+        }
+    }
+}
+
+/// Ask the worker at `host` for the pixels of `tile`, of `channels` bytes each, of the
+/// render `options` describes, waiting on it at most `timeout` at a time.
+fn fetch(host: &str, options: &str, tile: Rectangle, channels: usize, timeout: Duration)
+    -> io::Result<std::result::Result<Vec<u8>, String>>
+{
+    let address = host.to_socket_addrs()?.next()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no address"))?;
+    let stream = TcpStream::connect_timeout(&address, CONNECT_TIMEOUT.min(timeout))?;
+    stream.set_read_timeout(Some(timeout))?;
+    stream.set_write_timeout(Some(timeout))?;
+    write_request(BufWriter::new(&stream), options, tile)?;
+    read_reply(BufReader::new(&stream), tile.2 * tile.3 * channels)
+}
+
+/// Render `tiles` of a picture `width` pixels wide on the workers at `hosts`, as `options`
+/// say, into `pixels`, the picture's, `channels` bytes to a pixel.
+pub fn render(hosts: &[String], options: &str, tiles: Vec<Rectangle>, width: usize, channels: usize,
+              pixels: &mut [u8]) -> Result<()> {
+    render_within(hosts, options, tiles, width, channels, pixels, TIMEOUT)
+}
+
+/// `render`, waiting on each worker at most `timeout` at a time.
+fn render_within(hosts: &[String], options: &str, tiles: Vec<Rectangle>, width: usize, channels: usize,
+                 pixels: &mut [u8], timeout: Duration) -> Result<()> {
+    let left = AtomicUsize::new(tiles.len());
+    let queue = Mutex::new(VecDeque::from(tiles));
+    let hosts_up = AtomicUsize::new(hosts.len());
+    let (done, received) = mpsc::channel::<Result<(Rectangle, Vec<u8>)>>();
+    std::thread::scope(|scope| {
+        for host in hosts {
+            let done = done.clone();
+            let (left, queue, hosts_up) = (&left, &queue, &hosts_up);
+            scope.spawn(move || {
+                let mut failures = 0;
+                while left.load(Ordering::SeqCst) > 0 {
+                    // the queue can be empty while another host's tile might yet come back
+                    let Some(tile) = queue.lock().unwrap().pop_front() else {
+                        std::thread::sleep(PAUSE / 10);
+                        continue;
+                    };
+                    match fetch(host, options, tile, channels, timeout) {
+                        Ok(Ok(tile_pixels)) => {
+                            failures = 0;
+                            left.fetch_sub(1, Ordering::SeqCst);
+                            let _ = done.send(Ok((tile, tile_pixels)));
+                        }
+                        Ok(Err(message)) => {
+                            let _ = done.send(Err(Error::Check(format!("{}: {}", host, message))));
+                            return;
+                        }
+                        Err(e) => {
+                            queue.lock().unwrap().push_back(tile);
+                            failures += 1;
+                            log::warn!("{}: {}; the tile at {},{} goes back on the queue", host, e, tile.0, tile.1);
+                            if failures < TRIES {
+                                std::thread::sleep(PAUSE);
+                                continue;
+                            }
+                            let e = io::Error::new(e.kind(), format!("{}: {}", host, e));
+                            if hosts_up.fetch_sub(1, Ordering::SeqCst) == 1 {
+                                let _ = done.send(Err(Error::Io(e)));
+                            } else {
+                                log::warn!("{}; giving up on it", e);
+                            }
+                            return;
+                        }
+                    }
+                }
+            });
+        }
+        drop(done);
+        for tile in received {
+            let (tile, tile_pixels) = match tile {
+                Ok(tile) => tile,
+                Err(e) => {
+                    // the other hosts stop after the tile they're on
+                    left.store(0, Ordering::SeqCst);
+                    return Err(e);
+                }
+            };
+            for (row, line) in tile_pixels.chunks(tile.2 * channels).enumerate() {
+                let at = ((tile.1 + row) * width + tile.0) * channels;
+                pixels[at .. at + line.len()].copy_from_slice(line);
+            }
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn test_protocol() {
+    let mut request = Vec::new();
+    write_request(&mut request, "palette = \"fire\"\n", (128, 0, 64, 30)).unwrap();
+    assert_eq!(request, b"mandelbrot tile 1\n128 0 64 30\n17\npalette = \"fire\"\n");
+    assert_eq!(read_request(&request[..]).unwrap(), ("palette = \"fire\"\n".to_string(), (128, 0, 64, 30)));
+    for garbled in [&b"GET / HTTP/1.1\n"[..], b"mandelbrot tile 1\n1 2 3\n0\n", b"mandelbrot tile 1\n1 2 3 4\n9\nshort",
+                    b"mandelbrot tile 1\n1 2 3 4\n100000\n"] {
+        assert!(read_request(garbled).is_err(), "{:?}", String::from_utf8_lossy(garbled));
+    }
+
+    let mut reply = Vec::new();
+    write_reply(&mut reply, &Ok(vec![1, 2, 3])).unwrap();
+    assert_eq!(reply, b"ok 3\n\x01\x02\x03");
+    assert_eq!(read_reply(&reply[..], 3).unwrap(), Ok(vec![1, 2, 3]));
+    assert!(read_reply(&reply[..], 4).is_err());
+    assert!(read_reply(&reply[..5], 3).is_err());
+    reply.clear();
+    write_reply(&mut reply, &Err("--palette: no\nsuch".to_string())).unwrap();
+    assert_eq!(read_reply(&reply[..], 3).unwrap(), Err("--palette: no such".to_string()));
+}
+
+#[test]
+fn test_render() {
+    // a worker that makes every pixel of a tile the tile's column and row, with a queue
+    // of tiles for it, and a host alongside that's down
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let worker = listener.local_addr().unwrap().to_string();
+    let down = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().to_string();
+    std::thread::spawn(move || serve_on(listener, Duration::from_millis(200), |options, tile| match options {
+        "ok" => Ok(vec![(tile.0 / 2 + tile.1 / 2) as u8; tile.2 * tile.3]),
+        _ => Err(Error::Parse(format!("{}: no such options", options))),
+    }));
+    let tiles = || crate::split::Index::new("mandel.png", (5, 3), 2).tiles().into_iter().map(|(_, tile)| tile).collect();
+    let mut pixels = vec![9; 5 * 3];
+    render(&[down.clone(), worker.clone()], "ok", tiles(), 5, 1, &mut pixels).unwrap();
+    assert_eq!(pixels, [0, 0, 1, 1, 2,
+                        0, 0, 1, 1, 2,
+                        1, 1, 2, 2, 3]);
+
+    let error = render(std::slice::from_ref(&worker), "bad", vec![(0, 0, 5, 3)], 5, 1, &mut pixels).unwrap_err();
+    assert!(error.to_string().ends_with(": bad: no such options"), "{}", error);
+    let error = render(std::slice::from_ref(&down), "ok", vec![(0, 0, 5, 3)], 5, 1, &mut pixels).unwrap_err();
+    assert!(error.to_string().starts_with(&format!("{}: ", down)), "{}", error);
+
+    // a host that takes the connections and then says nothing times out, and its tiles
+    // go to the other
+    let stalled = TcpListener::bind("127.0.0.1:0").unwrap();
+    let silent = stalled.local_addr().unwrap().to_string();
+    std::thread::spawn(move || stalled.incoming().collect::<Vec<_>>());
+    pixels.fill(9);
+    render_within(&[silent.clone(), worker.clone()], "ok", tiles(), 5, 1, &mut pixels, Duration::from_millis(200))
+        .unwrap();
+    assert_eq!(pixels[14], 3);
+    let error = render_within(std::slice::from_ref(&silent), "ok", vec![(0, 0, 5, 3)], 5, 1, &mut pixels,
+                              Duration::from_millis(200)).unwrap_err();
+    assert!(error.to_string().starts_with(&format!("{}: ", silent)), "{}", error);
+
+    // and the worker doesn't wait for ever on a coordinator that connects and says
+    // nothing; one whose request breaks off is hung up on, not told its options are wrong
+    let idle = TcpStream::connect(&worker).unwrap();
+    let mut cut_off = TcpStream::connect(&worker).unwrap();
+    cut_off.write_all(b"mandelbrot tile 1\n0 0 5 3\n2\no").unwrap();
+    cut_off.shutdown(std::net::Shutdown::Write).unwrap();
+    let mut garbled = TcpStream::connect(&worker).unwrap();
+    garbled.write_all(b"GET / HTTP/1.1\n").unwrap();
+    render(std::slice::from_ref(&worker), "ok", tiles(), 5, 1, &mut pixels).unwrap();
+    let reply = |mut stream: TcpStream| {
+        let mut reply = String::new();
+        stream.read_to_string(&mut reply).unwrap();
+        reply
+    };
+    assert_eq!(reply(cut_off), "");
+    assert!(reply(garbled).starts_with("error \"GET / HTTP/1.1\": "));
+    drop(idle);
+}
@@ -0,0 +1,356 @@
+// Deep zooms. An f64 has 53 bits of mantissa, about 16 decimal digits; zoom in on a
+// point near 0.75 until the pixels are 1e-16 apart and neighbouring pixels round to the
+// same f64, so the picture turns into blocks. A double-double is two f64s, the second
+// holding what the first had to round off: 106 bits, about 32 digits, in software.
+//
+// render, pixel_to_point and the escape-time loop are generic over the number type,
+// any Real. main picks f64 when it's precise enough for the corners and the size, and
+// Dd when it isn't; Dd is about ten times slower. --precision f32 picks f32 throughout.
+//
+// Note:
+//
+// 1.) The arithmetic is the usual error-free transformations (Dekker, Knuth; the QD
+//     library): two_sum gives a + b and the exact error of that f64 addition, and
+//     two_prod gives a * b and the exact error, through a fused multiply-add.
+// 2.) Complex<T>'s operators need T: Num from num-traits, so Dd implements it: Zero,
+//     One, the five arithmetic operators and from_str_radix.
+// 3.) Display writes a Dd back out with 32 digits, for the viewer to print corners that
+//     read back as the same Dd, give or take its last bit or two.
+// 4.) Real has from_f64 rather than a From<f64> bound because f32 has no From<f64>, the
+//     conversion losing digits; f32 is a Real all the same, for --precision f32's quick
+//     previews.
+//
+use num::Complex;
+use num_traits::{Num, One, Zero};
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+use std::str::FromStr;
+
+/// A number type render can work in.
+pub trait Real: Num + Copy + PartialOrd + Send + Sync {
+    /// The nearest value to `x`.
+    fn from_f64(x: f64) -> Self;
+    /// The nearest f64, for coloring and for the test of whether the point escaped.
+    fn to_f64(self) -> f64;
+}
+
+impl Real for f32 {
+    fn from_f64(x: f64) -> f32 {
+        x as f32
+    }
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl Real for f64 {
+    fn from_f64(x: f64) -> f64 {
+        x
+    }
+
+    fn to_f64(self) -> f64 {
+        self
+    }
+}
+
+/// A double-double: the unevaluated sum `hi + lo`, where `lo` is at most half an ulp of
+/// `hi`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Dd {
+    hi: f64,
+    lo: f64,
+}
+
+/// `a + b`, and the error of rounding it to an f64.
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let s = a + b;
+    let bb = s - a;
+    (s, (a - (s - bb)) + (b - bb))
+}
+
+/// The same, when `|a| >= |b|`, which saves three operations.
+fn quick_two_sum(a: f64, b: f64) -> (f64, f64) {
+    let s = a + b;
+    (s, b - (s - a))
+}
+
+/// `a * b`, and the error of rounding it to an f64.
+fn two_prod(a: f64, b: f64) -> (f64, f64) {
+    let p = a * b;
+    (p, a.mul_add(b, -p))
+}
+
+impl Dd {
+    fn new(hi: f64, lo: f64) -> Dd {
+        let (hi, lo) = quick_two_sum(hi, lo);
+        Dd { hi, lo }
+    }
+
+    fn trunc(self) -> Dd {
+        let hi = self.hi.trunc();
+        if hi == self.hi { Dd::new(hi, self.lo.trunc()) } else { Dd::new(hi, 0.0) }
+    }
+}
+
+impl From<f64> for Dd {
+    fn from(x: f64) -> Dd {
+        Dd { hi: x, lo: 0.0 }
+    }
+}
+
+impl Real for Dd {
+    fn from_f64(x: f64) -> Dd {
+        Dd::from(x)
+    }
+
+    fn to_f64(self) -> f64 {
+        self.hi + self.lo
+    }
+}
+
+impl Neg for Dd {
+    type Output = Dd;
+    fn neg(self) -> Dd {
+        Dd { hi: -self.hi, lo: -self.lo }
+    }
+}
+
+impl Add for Dd {
+    type Output = Dd;
+    fn add(self, other: Dd) -> Dd {
+        let (s, e) = two_sum(self.hi, other.hi);
+        let (t, f) = two_sum(self.lo, other.lo);
+        let (s, e) = quick_two_sum(s, e + t);
+        Dd::new(s, e + f)
+    }
+}
+
+impl Sub for Dd {
+    type Output = Dd;
+    fn sub(self, other: Dd) -> Dd {
+        self + -other
+    }
+}
+
+impl Mul for Dd {
+    type Output = Dd;
+    fn mul(self, other: Dd) -> Dd {
+        let (p, e) = two_prod(self.hi, other.hi);
+        Dd::new(p, e + (self.hi * other.lo + self.lo * other.hi))
+    }
+}
+
+// Long division, one f64 of quotient at a time: each step divides what's left by the
+// leading part of the divisor.
+impl Div for Dd {
+    type Output = Dd;
+    fn div(self, other: Dd) -> Dd {
+        let q1 = self.hi / other.hi;
+        let r = self - other * Dd::from(q1);
+        let q2 = r.hi / other.hi;
+        let r = r - other * Dd::from(q2);
+        let q3 = r.hi / other.hi;
+        Dd::new(q1, q2) + Dd::from(q3)
+    }
+}
+
+impl Rem for Dd {
+    type Output = Dd;
+    fn rem(self, other: Dd) -> Dd {
+        self - (self / other).trunc() * other
+    }
+}
+
+impl PartialOrd for Dd {
+    fn partial_cmp(&self, other: &Dd) -> Option<Ordering> {
+        match self.hi.partial_cmp(&other.hi) {
+            Some(Ordering::Equal) => self.lo.partial_cmp(&other.lo),
+            ordering => ordering,
+        }
+    }
+}
+
+impl Zero for Dd {
+    fn zero() -> Dd {
+        Dd::from(0.0)
+    }
+    fn is_zero(&self) -> bool {
+        self.hi == 0.0
+    }
+}
+
+impl One for Dd {
+    fn one() -> Dd {
+        Dd::from(1.0)
+    }
+}
+
+/// Why a string isn't a Dd.
+#[derive(Debug, PartialEq)]
+pub struct ParseDdError;
+
+// A decimal number, with an optional sign, fraction and exponent: "-0.74364388703715870475",
+// "1e-30". The digits are added up in a Dd, 15 at a time, each group times its power of
+// ten, so that 32 or so of them count from the first one that isn't 0 to the last one
+// that isn't: 1.000...0001 keeps its 1e-40. f64's parse stops at 17. The f64 part is
+// f64's own parse, correctly rounded, so that a number read as a Dd and rounded to an
+// f64 is the number read as an f64.
+impl FromStr for Dd {
+    type Err = ParseDdError;
+    fn from_str(text: &str) -> Result<Dd, ParseDdError> {
+        let (negative, s) = match text.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, text.strip_prefix('+').unwrap_or(text)),
+        };
+        let (mantissa, exponent) = match s.find(['e', 'E']) {
+            Some(e) => (&s[..e], s[e + 1..].parse::<i32>().map_err(|_| ParseDdError)?),
+            None => (s, 0),
+        };
+        let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+        if whole.is_empty() && fraction.is_empty() {
+            return Err(ParseDdError);
+        }
+        let digits: Vec<u8> = whole.bytes().chain(fraction.bytes()).collect();
+        if !digits.iter().all(u8::is_ascii_digit) {
+            return Err(ParseDdError);
+        }
+        // the last digit is worth 10^shift
+        let shift = exponent - fraction.len() as i32;
+        let mut x = Dd::zero();
+        for (i, group) in digits.chunks(15).enumerate() {
+            let value = group.iter().fold(0.0, |v, d| v * 10.0 + (d - b'0') as f64);
+            if value != 0.0 {
+                let last = digits.len() - i * 15 - group.len();
+                x = x + Dd::from(value) * power_of_ten(shift + last as i32);
+            }
+        }
+        let x = if negative { -x } else { x };
+        let hi: f64 = text.parse().map_err(|_| ParseDdError)?;
+        let lo = (x - Dd::from(hi)).to_f64();
+        // past f64's range, or below it, there's nothing more to keep
+        Ok(Dd { hi, lo: if lo.is_finite() { lo } else { 0.0 } })
+    }
+}
+
+/// 10 to the power `n`, as a Dd.
+fn power_of_ten(n: i32) -> Dd {
+    let mut x = Dd::one();
+    for _ in 0..n.unsigned_abs() {
+        x = x * Dd::from(10.0);
+    }
+    if n < 0 { Dd::one() / x } else { x }
+}
+
+// An f64 is written as f64 writes it, the fewest digits that read back the same. Otherwise
+// the number is scaled to between 1 and 10 and its digits taken off the front one by one.
+impl fmt::Display for Dd {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.lo == 0.0 || !self.hi.is_finite() {
+            return write!(f, "{}", self.hi);
+        }
+        let (sign, mut x) = if self.hi < 0.0 { ("-", -*self) } else { ("", *self) };
+        let mut exponent = x.hi.abs().log10().floor() as i32;
+        x = x * power_of_ten(-exponent);
+        // log10 is rounded too, and can be one out
+        if x < Dd::one() {
+            x = x * Dd::from(10.0);
+            exponent -= 1;
+        } else if x >= Dd::from(10.0) {
+            x = x / Dd::from(10.0);
+            exponent += 1;
+        }
+        let mut digits = String::new();
+        for _ in 0..32 {
+            // hi can round up to the next whole number, with lo taking it back
+            let mut digit = x.hi.floor();
+            if Dd::from(digit) > x {
+                digit -= 1.0;
+            }
+            digits.push(char::from(b'0' + digit.clamp(0.0, 9.0) as u8));
+            x = (x - Dd::from(digit)) * Dd::from(10.0);
+        }
+        let digits = digits.trim_end_matches('0');
+        let (first, rest) = digits.split_at(1);
+        let point = if rest.is_empty() { "" } else { "." };
+        match exponent {
+            0 => write!(f, "{}{}{}{}", sign, first, point, rest),
+            e => write!(f, "{}{}{}{}e{}", sign, first, point, rest, e),
+        }
+    }
+}
+
+impl Num for Dd {
+    type FromStrRadixErr = ParseDdError;
+    fn from_str_radix(s: &str, radix: u32) -> Result<Dd, ParseDdError> {
+        if radix != 10 {
+            return Err(ParseDdError);
+        }
+        s.parse()
+    }
+}
+
+/// Parse a pair of decimal numbers separated by a comma as a complex number, with all
+/// the digits a Dd can hold.
+pub fn parse_complex(s: &str) -> Option<Complex<Dd>> {
+    rustfun_common::parse_pair(s, ',').map(|(re, im)| Complex { re, im })
+}
+
+#[test]
+fn test_arithmetic() {
+    let one = Dd::one();
+    let tiny = Dd::from(1e-20);
+    // an f64 loses 1e-20 next to 1; a Dd keeps it
+    assert_eq!((1.0 + 1e-20) - 1.0, 0.0);
+    assert_eq!((one + tiny) - one, tiny);
+    let third = one / Dd::from(3.0);
+    assert_eq!(third.hi, 1.0 / 3.0);
+    assert!(third.lo != 0.0);
+    assert!(((third * Dd::from(3.0)) - one).to_f64().abs() < 1e-31);
+    assert_eq!(Dd::from(7.5) % Dd::from(2.0), Dd::from(1.5));
+    assert!(Dd::from(1.0) + tiny > Dd::from(1.0));
+    assert!(-tiny < Dd::zero());
+}
+
+#[test]
+fn test_parse() {
+    assert_eq!("1.25".parse(), Ok(Dd::from(1.25)));
+    assert_eq!("-3".parse(), Ok(Dd::from(-3.0)));
+    assert_eq!("+.5e1".parse(), Ok(Dd::from(5.0)));
+    assert_eq!("2E-1".parse::<Dd>().unwrap().to_f64(), 0.2);
+    for bad in ["", "-", ".", "1.2.3", "1e", "0x10", "1,5"] {
+        assert_eq!(bad.parse::<Dd>(), Err(ParseDdError), "{:?}", bad);
+    }
+    // rounded to an f64, it's what f64 would have read
+    for text in ["-1.20", "0.35", "0.1", "-0.743643887037158704752191506114774", "1e-300",
+                 "1e-400", "2e400"] {
+        assert_eq!(text.parse::<Dd>().unwrap().to_f64(), text.parse::<f64>().unwrap());
+    }
+    // digits past the 17th still count
+    let a: Dd = "0.100000000000000000001".parse().unwrap();
+    let b: Dd = "0.1".parse().unwrap();
+    assert_eq!(a.to_f64(), b.to_f64());
+    assert!(a > b);
+    assert!(((a - b) - Dd::from(1e-21)).to_f64().abs() < 1e-32);
+    let c: Dd = "1.0000000000000000000000000000000000000001".parse().unwrap();
+    assert_eq!((c.hi, c.lo), (1.0, 1e-40));
+    assert_eq!(parse_complex("-0.75,1e-30").map(|c| (c.re, c.im.to_f64())), Some((Dd::from(-0.75), 1e-30)));
+    assert_eq!(parse_complex("-0.75;0"), None);
+}
+
+#[test]
+fn test_display() {
+    assert_eq!(Dd::from(-1.2).to_string(), "-1.2");
+    assert_eq!(Dd::from(1e-20).to_string(), "0.00000000000000000001");
+    // what it writes reads back the same, to within the last bit or two
+    for text in ["-0.743643887037158704752191506114774", "1.000000000000000000015", "0.1", "-1e-20",
+                 "123456.78901234567890123456789", "9.9999999999999999999999999"] {
+        let x: Dd = text.parse().unwrap();
+        let written = x.to_string();
+        let back: Dd = written.parse().unwrap();
+        assert!(((back - x) / x).to_f64().abs() < 1e-31, "{} came out as {}", text, written);
+    }
+    let x: Dd = "1.000000000000000000015".parse().unwrap();
+    assert!(x.to_string().starts_with("1.00000000000000000001"), "{}", x);
+}
@@ -0,0 +1,493 @@
+// The escape-time kernel: the loop that decides whether a point is in the set, in all its
+// variations, and the Fractal that picks which of them a picture is of. It's the hot loop
+// of every renderer, so it lives in the library, where the benches can time it directly
+// (benches/render.rs) and main.rs uses it like any other crate's.
+//
+// Note:
+//
+// 1.) Everything here is generic over the number type, T: Real, so the same loops run in
+//     f64 and, for deep zooms, in double-double (double_double.rs).
+// 2.) --power, --limit, --escape-radius and --no-period-check are a Settings, which every
+//     loop is handed along with the point; nothing here is process-wide.
+//
+use num::Complex;
+use crate::double_double::Real;
+#[cfg(test)]
+use crate::double_double::Dd;
+
+#[allow(dead_code)]
+fn complex_square_add_loop(c: Complex<f64>) {
+    let mut z = Complex { re: 0.0, im: 0.0 };
+    loop {
+        z = z * z + c;
+    }
+}
+
+//  use /// to mark the comment lines above the function definition; the comments above the members
+//  of the Complex structure start with /// as well. These are documentation comments; the rustdoc
+//  utility knows how to parse them, together with the code they describe, and produce online
+//  documentation. 
+/// Try to determine if `c` is in the Mandelbrot set, using at most `settings.limit`
+/// iterations to decide.
+///
+/// If `c` is not a member, return `Some(i)`, where `i` is the number of
+/// iterations it took for `c` to leave the circle of radius two centered on the
+/// origin, or `settings.escape_radius`. If `c` seems to be a member (more precisely,
+/// if we reached the iteration limit without being able to prove that `c` is not a
+/// member), return `None`.
+// 1. The function’s return value is an Option<u32>, for any type T, a value 
+//    of type Option<T> is either Some(v), where v is a value of type T;
+//    or None, indicating no T value is available.
+// 2. Option is a generic type: you can use Option<T> to represent an optional
+//    value of any type T you like.
+//
+// 2.1 escape_time is generic too, over the number type T it computes in: f64, or Dd
+//     for deep zooms (double_double.rs). T::zero() is T's 0, whatever T is.
+pub fn escape_time<T: Real>(c: Complex<T>, settings: Settings) -> Option<u32> {
+	if settings.power == Power::Two && in_main_bulbs(c) {
+		return None;
+	}
+	escape_time_from(Complex { re: T::zero(), im: T::zero() }, c, settings)
+}
+
+/// Whether `c` is inside the main cardioid of the Mandelbrot set, or the disk to its
+/// left, the period-2 bulb: points that never escape, however long we iterate.
+// 31.  In a default view most of the black pixels are in these two, and each of them
+//      took all 255 iterations to give up on. Both shapes have simple equations: with
+//      q = (x - 1/4)^2 + y^2, c = x + yi is in the cardioid when q (q + x - 1/4) is at
+//      most y^2 / 4, and in the bulb, a circle of radius 1/4 around -1, when
+//      (x + 1)^2 + y^2 is at most 1/16. A few multiplications instead of 255 rounds.
+fn in_main_bulbs<T: Real>(c: Complex<T>) -> bool {
+	let quarter = T::from_f64(0.25);
+	let (x, y2) = (c.re - quarter, c.im * c.im);
+	let q = x * x + y2;
+	let bulb = c.re + T::one();
+	q * (q + x) <= quarter * y2 || bulb * bulb + y2 <= quarter * quarter
+}
+
+#[test]
+fn test_in_main_bulbs() {
+	let at = |re, im| Complex { re, im };
+	for inside in [at(0.0, 0.0), at(-0.5, 0.5), at(0.24, 0.0), at(-1.0, 0.0), at(-1.2, 0.1)] {
+		assert!(in_main_bulbs(inside), "{}", inside);
+	}
+	for outside in [at(0.26, 0.0), at(-0.75, 0.1), at(-1.26, 0.0), at(-0.1, 1.0), at(2.0, 2.0)] {
+		assert!(!in_main_bulbs(outside), "{}", outside);
+	}
+	// and the shortcut changes nothing: every point of the usual view escapes, or
+	// doesn't, the same as when iterated from 0
+	let (zero, settings) = (Complex { re: 0.0, im: 0.0 }, Settings::default());
+	for row in 0..100 {
+		for column in 0..125 {
+			let c = at(-2.0 + column as f64 * 0.02, 1.0 - row as f64 * 0.02);
+			assert_eq!(escape_time(c, settings), escape_time_from(zero, c, settings), "{}", c);
+		}
+	}
+}
+
+/// The same loop as `escape_time`, started from `z` rather than from 0.
+// 25.  A Julia set iterates the same z = z*z + c, but the other way round: c is one
+//      number for the whole picture, and each pixel gives the starting z. With z
+//      starting at 0 it's the Mandelbrot set again, so the two share this loop.
+pub fn escape_time_from<T: Real>(mut z: Complex<T>, c: Complex<T>, settings: Settings) -> Option<u32> {
+	let mut cycle = settings.period_check.then(|| Cycle::new(z));
+	let (power, bailout) = (settings.power, T::from_f64(settings.bailout(2.0)));
+	for i in 0..settings.limit {
+		z = power.of(z) + c;
+        //3. The z.norm_sqr() method call returns the square of z’s distance from the origin.
+        //   instead of computing a square root, we just compare the squared distance with 4.0,
+        //   which is faster. The radius is 2 unless --escape-radius says otherwise.
+		if z.norm_sqr() > bailout {
+			return Some(i);
+		}
+		if cycle.as_mut().is_some_and(|cycle| cycle.repeats(z)) {
+			return None;
+		}
+	}
+
+	None
+}
+
+/// How the escape loops iterate a point: to which power, how many times, how far out, and
+/// whether they look for cycles.
+// 32.  The cycles are looked for unless --no-period-check turns it off, to compare with
+//      the plain loop.
+// 36.  The power d of a Multibrot set, z = z^d + c, from --power; 2 is the Mandelbrot
+//      set, and the only one the shortcut for the main bulbs is good for.
+// 39.  How many iterations a point gets before it's taken to be in the set, from --limit,
+//      and how far z goes before it's taken to have escaped, from --escape-radius. A deep
+//      zoom needs thousands of iterations to show its detail; a bigger radius makes
+//      smooth coloring smoother still. Any radius of 2 or more gives the same set, since
+//      a z past 2 never comes back.
+// 39.2 Every loop is handed them, rather than reading them from the process: two renders
+//      in one process, a library caller's say, can each have their own. Settings is
+//      Copy, like Fractal, so it's handed around by value.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Settings {
+	/// z^d + c's d.
+	pub power: Power,
+	/// The iterations a point gets before it's taken to be in the set.
+	pub limit: u32,
+	/// How far from 0 z gets before it's taken to have escaped: 2 or more.
+	pub escape_radius: f64,
+	/// Whether an orbit that's gone round a cycle is taken to be in the set then: see Cycle.
+	pub period_check: bool,
+}
+
+/// The book's: z*z + c, 255 iterations, and the circle of radius 2; with the period check.
+impl Default for Settings {
+	fn default() -> Settings {
+		Settings { power: Power::Two, limit: 255, escape_radius: 2.0, period_check: true }
+	}
+}
+
+impl Settings {
+	/// The norm_sqr past which a loop that needs z to get at least `radius` away takes
+	/// it to have escaped: the escape radius's, or `radius`'s if that's further.
+	// 39.1 The smooth and distance loops have radii of their own, 16 and 1000, that their
+	//      formulas need; the escape radius only moves them further out.
+	pub fn bailout(&self, radius: f64) -> f64 {
+		let radius = self.escape_radius.max(radius);
+		radius * radius
+	}
+}
+
+/// How the escape loops raise z to the power d.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Power {
+	/// z*z, the Mandelbrot set's own.
+	Two,
+	/// By squaring and multiplying: z^5 is (z^2)^2 * z.
+	Integer(u32),
+	/// Through polar form, in f64: |z|^d, at d times z's angle.
+	Fraction(f64),
+}
+
+impl Power {
+	/// The power d; a whole one too big for a u32 is done as a fraction would be.
+	pub fn new(d: f64) -> Power {
+		if d == 2.0 {
+			Power::Two
+		} else if d.fract() == 0.0 && d <= u32::MAX as f64 {
+			Power::Integer(d as u32)
+		} else {
+			Power::Fraction(d)
+		}
+	}
+
+	/// d itself.
+	pub fn degree(self) -> f64 {
+		match self {
+			Power::Two => 2.0,
+			Power::Integer(n) => n as f64,
+			Power::Fraction(d) => d,
+		}
+	}
+
+	/// `z` to the power d.
+	// 36.1 Squaring and multiplying takes about 2 log2(d) multiplications rather than d,
+	//      and works in any Real, Dd too. A fractional power needs logarithms and
+	//      trigonometry, which only f64 has, so it's only as precise as an f64.
+	pub fn of<T: Real>(self, z: Complex<T>) -> Complex<T> {
+		match self {
+			Power::Two => z * z,
+			Power::Integer(mut n) => {
+				let (mut result, mut square) = (Complex { re: T::one(), im: T::zero() }, z);
+				while n > 0 {
+					if n & 1 == 1 {
+						result = result * square;
+					}
+					square = square * square;
+					n >>= 1;
+				}
+				result
+			}
+			Power::Fraction(d) => widen(narrow(z).powf(d)),
+		}
+	}
+}
+
+#[test]
+fn test_power() {
+	let at = |re, im| Complex { re, im };
+	assert_eq!(Power::new(2.0), Power::Two);
+	assert_eq!(Power::new(3.0), Power::Integer(3));
+	assert_eq!(Power::new(2.5), Power::Fraction(2.5));
+	assert_eq!(Power::new(u32::MAX as f64), Power::Integer(u32::MAX));
+	assert_eq!(Power::new(u32::MAX as f64 + 1.0), Power::Fraction(u32::MAX as f64 + 1.0));
+	let z = at(1.0, 1.0);
+	assert_eq!(Power::Integer(2).of(z), Power::Two.of(z));
+	assert_eq!(Power::Integer(3).of(z), at(-2.0, 2.0));
+	assert_eq!(Power::Integer(8).of(z), at(16.0, 0.0));
+	// (1 + i)^2.5 is 2^1.25 at 2.5 times 45 degrees
+	let w = Power::Fraction(2.5).of(z);
+	let expected = Complex::from_polar(&2f64.powf(1.25), &(2.5 * std::f64::consts::FRAC_PI_4));
+	assert!((w - expected).norm() < 1e-12, "{}", w);
+	// and in double-double, an integer power is as exact as the squares are
+	let dd = Power::Integer(3).of(Complex { re: Dd::from(1.0), im: Dd::from(1.0) });
+	assert_eq!(narrow(dd), at(-2.0, 2.0));
+
+	// c = -1 goes round 0, -1 for ever squared, but cubed it goes 0, -1, -2, -9: out
+	let cubed = Settings { power: Power::new(3.0), ..Settings::default() };
+	assert_eq!(escape_time(at(-1.0, 0.0), Settings::default()), None);
+	assert_eq!(escape_time(at(-1.0, 0.0), cubed), Some(2));
+	// and z^3 + c's set is the same turned half way round, which z^2 + c's isn't
+	let grid = (0..30 * 30).map(|i| at(-1.5 + (i % 30) as f64 * 0.1, 1.5 - (i / 30) as f64 * 0.1));
+	assert!(grid.clone().all(|c| escape_time(c, cubed) == escape_time(-c, cubed)));
+	assert!(grid.clone().any(|c| escape_time(c, Settings::default()) != escape_time(-c, Settings::default())));
+	assert!(grid.clone().any(|c| escape_time(c, cubed) != escape_time(c, Settings::default())));
+}
+
+/// Brent's cycle detection, for an orbit that's settled into a loop: every point inside
+/// the set that isn't in the main cardioid or bulb ends up going round one, and would
+/// otherwise take all `limit` iterations to give up on.
+// 32.1 The orbit is compared with one point saved from it, and the saved point is
+//      replaced after 1, 2, 4, 8, ... steps, so a cycle of any length is caught within
+//      a few times its length of getting going. Only an exact repeat counts: an f64
+//      orbit that's found its cycle repeats exactly, and anything less would take
+//      points just outside the set, which follow a cycle closely for a long time, for
+//      points inside.
+struct Cycle<T> {
+	saved: Complex<T>,
+	steps: u32,
+	span: u32,
+}
+
+impl<T: Real> Cycle<T> {
+	fn new(z: Complex<T>) -> Cycle<T> {
+		Cycle { saved: z, steps: 0, span: 1 }
+	}
+
+	/// Whether `z`, the next point of the orbit, is one it's been at before.
+	fn repeats(&mut self, z: Complex<T>) -> bool {
+		if z == self.saved {
+			return true;
+		}
+		self.steps += 1;
+		if self.steps == self.span {
+			self.saved = z;
+			self.steps = 0;
+			self.span = self.span.saturating_mul(2);
+		}
+		false
+	}
+}
+
+#[test]
+fn test_period_check() {
+	let at = |re, im| Complex { re, im };
+	let zero = at(0.0, 0.0);
+	// c = -1 goes 0, -1, 0, -1, ...: -1 is saved after one step, and seen again two later
+	let mut cycle = Cycle::new(zero);
+	assert!(!cycle.repeats(at(-1.0, 0.0)));
+	assert!(!cycle.repeats(zero));
+	assert!(cycle.repeats(at(-1.0, 0.0)));
+	// a period-3 bulb, and the edge of the usual view, the same with or without it
+	let views = [(at(-0.13, 0.74), 0.0005), (at(-1.20, 0.35), 0.002)];
+	let grid = |period_check| {
+		let settings = Settings { limit: 1000, period_check, ..Settings::default() };
+		views.iter().flat_map(|&(corner, step)| (0..40 * 40).map(move |i| {
+			let c = corner + at((i % 40) as f64 * step, -((i / 40) as f64) * step);
+			escape_time_from(zero, c, settings)
+		})).collect::<Vec<_>>()
+	};
+	let checked = grid(true);
+	assert!(checked.iter().any(Option::is_none) && checked.iter().any(Option::is_some));
+	let plain = grid(false);
+	assert_eq!(checked, plain);
+}
+
+/// Like `escape_time_from`, but a fraction of an iteration more or less, for coloring
+/// without bands: two points that escape in the same iteration, one just barely and
+/// one with room to spare, get different times. Returns the iteration as well.
+// 27.  The log-log formula: once |z| is large, each iteration roughly squares it, so
+//      log2(log2 |z|) grows by one per iteration. Taking that away from the count
+//      leaves a number that changes smoothly from one pixel to the next. It's only
+//      that accurate well outside the circle of radius 2, so this loop runs on to 16,
+//      or to the escape radius if that's further.
+// 27.1 f64::ln is a method: (x).ln() is the natural log of x, and there's no log(x).
+pub fn smooth_escape_time_from<T: Real>(mut z: Complex<T>, c: Complex<T>, settings: Settings) -> Option<(u32, f64)> {
+	let mut cycle = settings.period_check.then(|| Cycle::new(z));
+	let (power, bailout) = (settings.power, T::from_f64(settings.bailout(16.0)));
+	for i in 0..settings.limit {
+		z = power.of(z) + c;
+		if z.norm_sqr() > bailout {
+			return Some((i, fractional_escape(i, z.norm_sqr().to_f64(), power.degree())));
+		}
+		if cycle.as_mut().is_some_and(|cycle| cycle.repeats(z)) {
+			return None;
+		}
+	}
+
+	None
+}
+
+/// The smooth escape time of a point that escaped in iteration `i`, to a z with a
+/// `norm_sqr` past 256 at least, iterating z to the power `degree`.
+// 36.2 Each iteration takes |z| to about its `degree`th power, so the log-log is to that
+//      base: for 2, the same as before.
+pub fn fractional_escape(i: u32, norm_sqr: f64, degree: f64) -> f64 {
+	let log_z = norm_sqr.ln() / 2.0;
+	let nu = (log_z / 2f64.ln()).ln() / degree.ln();
+	(i as f64 + 1.0 - nu).max(0.0)
+}
+
+#[test]
+fn test_smooth_escape_time() {
+	let (zero, settings) = (Complex { re: 0.0, im: 0.0 }, Settings::default());
+	let c = |re| Complex { re, im: 0.0 };
+	assert_eq!(smooth_escape_time_from(zero, c(0.0), settings), None);
+	assert_eq!(smooth_escape_time_from(zero, c(-1.0), settings), None);
+	// points that escape by the same iteration get different times, close to the count
+	let (a, b) = (smooth_escape_time_from(zero, c(0.7), settings).unwrap().1,
+	              smooth_escape_time_from(zero, c(0.8), settings).unwrap().1);
+	assert_eq!(escape_time(c(0.7), settings), escape_time(c(0.8), settings));
+	assert!(a > b && a - b < 1.0);
+	assert!((a - escape_time(c(0.7), settings).unwrap() as f64).abs() < 2.0);
+	// and it changes smoothly: a small step along the line makes a small difference
+	let mut last = smooth_escape_time_from(zero, c(0.5), settings).unwrap().1;
+	for step in 1..=100 {
+		let next = smooth_escape_time_from(zero, c(0.5 + step as f64 * 0.01), settings).unwrap().1;
+		assert!((next - last).abs() < 0.25, "at {}: {} then {}", step, last, next);
+		last = next;
+	}
+}
+
+/// The same loop again, carrying the derivative of z along: the iteration `z`, iterated
+/// with `c`, escapes past a radius of 1000, or the escape radius if that's further, in,
+/// with z and its derivative then. The derivative is by c for the Mandelbrot set, and by
+/// where z started for a Julia set.
+// 37.  The chain rule: z' = z^d + c has the derivative d * z^(d-1) * dz, plus 1 when
+//      it's by c. It starts at 0 for the Mandelbrot set, where z starts at 0 whatever
+//      c is, and at 1 for a Julia set, where z starts at the point itself. Anything
+//      that needs to know how fast the picture changes around a point, --coloring
+//      distance now, shading in 3D later, starts from this loop.
+pub fn escape_with_derivative<T: Real>(mut z: Complex<T>, c: Complex<T>, settings: Settings, julia: bool)
+	-> Option<(u32, Complex<T>, Complex<T>)>
+{
+	let mut cycle = settings.period_check.then(|| Cycle::new(z));
+	let power = settings.power;
+	let (lower, degree) = (Power::new(power.degree() - 1.0), T::from_f64(power.degree()));
+	let bailout = T::from_f64(settings.bailout(1000.0));
+	let (zero, one) = (Complex { re: T::zero(), im: T::zero() }, Complex { re: T::one(), im: T::zero() });
+	let (mut dz, step) = if julia { (one, zero) } else { (zero, one) };
+	for i in 0..settings.limit {
+		dz = lower.of(z) * dz * degree + step;
+		z = power.of(z) + c;
+		if z.norm_sqr() > bailout {
+			return Some((i, z, dz));
+		}
+		if cycle.as_mut().is_some_and(|cycle| cycle.repeats(z)) {
+			return None;
+		}
+	}
+
+	None
+}
+
+/// Roughly how far the point is from the set, given the z it escaped to and the
+/// derivative then: |z| ln |z| / |dz|, to within a factor of 4 or so.
+// 37.1 Near the set the derivative is huge, so the distance is tiny; a filament too thin
+//      for any pixel's point to land in still shows, as the pixels it passes close by.
+pub fn exterior_distance(z: Complex<f64>, dz: Complex<f64>) -> f64 {
+	let norm = z.norm();
+	norm * norm.ln() / dz.norm()
+}
+
+#[test]
+fn test_distance() {
+	let at = |re, im| Complex { re, im };
+	let zero = at(0.0, 0.0);
+	let settings = Settings { limit: 1000, ..Settings::default() };
+	let distance = |c: Complex<f64>| escape_with_derivative(zero, c, settings, false).map(|(_, z, dz)| exterior_distance(z, dz));
+	// the set reaches 1/4 on the real line, and -2: the estimate is within a factor of 4
+	for (c, actual) in [(at(0.35, 0.0), 0.1), (at(0.5, 0.0), 0.25), (at(-2.5, 0.0), 0.5), (at(0.0, 1.5), 0.5)] {
+		let d = distance(c).unwrap();
+		assert!(d > actual / 4.0 && d < actual * 4.0, "{}: {}", c, d);
+	}
+	assert_eq!(distance(at(-1.0, 0.0)), None);
+	// closer in, nearer: the estimate shrinks towards the edge
+	assert!(distance(at(0.26, 0.0)).unwrap() < distance(at(0.3, 0.0)).unwrap());
+	// the unit circle is c = 0's Julia set: from 2, it's 1 away
+	let d = escape_with_derivative(at(2.0, 0.0), zero, settings, true).map(|(_, z, dz)| exterior_distance(z, dz)).unwrap();
+	assert!(d > 0.25 && d < 4.0, "{}", d);
+}
+
+/// What render asks about each point: how long it takes to escape, computing in `T`.
+// 29.  A generic trait: Fractal answers for points of any Real type T, and the reference
+//      orbit in perturbation.rs for Dd points only. render takes either, as a &F where
+//      F: EscapeTime<T>. Sync, as a supertrait, lets the renderers share one between
+//      their threads.
+pub trait EscapeTime<T>: Sync {
+    /// How many iterations it takes `point` to escape, or None if it seems not to.
+    fn escape_time(&self, point: Complex<T>, settings: Settings) -> Option<u32>;
+
+    /// The same, and as a fraction: see `smooth_escape_time_from`.
+    fn smooth_escape_time(&self, point: Complex<T>, settings: Settings) -> Option<(u32, f64)>;
+
+    /// The same, and roughly how far `point` is from the set: see `exterior_distance`.
+    fn distance(&self, point: Complex<T>, settings: Settings) -> Option<(u32, f64)>;
+}
+
+/// Which set a picture is of: what each pixel's point stands for in z = z*z + c.
+// 25.1 An enum with data in one variant: Julia carries its c, Mandelbrot needs nothing.
+//      It's Copy, so it's easily handed around, and shared between threads.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Fractal {
+    /// The point is c, and z starts at 0.
+    Mandelbrot,
+    /// c is fixed, and the point is where z starts.
+    Julia(Complex<f64>),
+}
+
+impl<T: Real> EscapeTime<T> for Fractal {
+    fn escape_time(&self, point: Complex<T>, settings: Settings) -> Option<u32> {
+        match *self {
+            Fractal::Mandelbrot => escape_time(point, settings),
+            Fractal::Julia(c) => escape_time_from(point, widen(c), settings),
+        }
+    }
+
+    fn smooth_escape_time(&self, point: Complex<T>, settings: Settings) -> Option<(u32, f64)> {
+        match *self {
+            Fractal::Mandelbrot if settings.power == Power::Two && in_main_bulbs(point) => None,
+            Fractal::Mandelbrot => smooth_escape_time_from(Complex { re: T::zero(), im: T::zero() }, point, settings),
+            Fractal::Julia(c) => smooth_escape_time_from(point, widen(c), settings),
+        }
+    }
+
+    fn distance(&self, point: Complex<T>, settings: Settings) -> Option<(u32, f64)> {
+        let escaped = match *self {
+            Fractal::Mandelbrot if settings.power == Power::Two && in_main_bulbs(point) => None,
+            Fractal::Mandelbrot => escape_with_derivative(Complex { re: T::zero(), im: T::zero() }, point, settings, false),
+            Fractal::Julia(c) => escape_with_derivative(point, widen(c), settings, true),
+        };
+        escaped.map(|(i, z, dz)| (i, exterior_distance(narrow(z), narrow(dz))))
+    }
+}
+
+/// `c` in the number type `T`: Julia's c is an f64, even when the picture is rendered in Dd.
+pub fn widen<T: Real>(c: Complex<f64>) -> Complex<T> {
+    Complex { re: T::from_f64(c.re), im: T::from_f64(c.im) }
+}
+
+#[test]
+fn test_julia() {
+    let (at, settings) = (|re, im| Complex { re, im }, Settings::default());
+    // c = 0 squares z over and over: the unit disk stays, the rest escapes
+    let disk = Fractal::Julia(at(0.0, 0.0));
+    assert_eq!(disk.escape_time(at(0.5, 0.5), settings), None);
+    assert_eq!(disk.escape_time(at(0.0, -0.99), settings), None);
+    assert_eq!(disk.escape_time(at(1.2, 0.0), settings), Some(1));     // 1.44, then 2.07
+    assert_eq!(disk.escape_time(at(3.0, 0.0), settings), Some(0));
+    // starting from 0, any Julia set's orbit is c's orbit in the Mandelbrot set
+    for c in [at(-0.8, 0.156), at(0.3, 0.5), at(-1.0, 0.0), at(1.0, 1.0)] {
+        assert_eq!(Fractal::Julia(c).escape_time(at(0.0, 0.0), settings),
+                   Fractal::Mandelbrot.escape_time(c, settings));
+    }
+}
+
+/// The nearest f64 point to `c`.
+pub fn narrow<T: Real>(c: Complex<T>) -> Complex<f64> {
+    Complex { re: c.re.to_f64(), im: c.im.to_f64() }
+}
@@ -0,0 +1,96 @@
+// What `mandelbrot --explain` prints: the chapter's notes, in short.
+use rustfun_common::{Explanation, Text, Topic};
+
+pub const EXPLANATION: Explanation = Explanation {
+    chapter: "03mandelbrot",
+    summary: Text {
+        en: "plot the Mandelbrot set to a PNG, on every core",
+        zh: "用所有核心把 Mandelbrot 集画成 PNG",
+    },
+    topics: &[
+        Topic {
+            title: Text { en: "Option", zh: "Option" },
+            body: Text {
+                en: "escape_time returns Option<u32>: Some(i) if the point left the circle after\n\
+                     i iterations, None if it seems to be in the set. Option is generic: Option<T>\n\
+                     works for any T.",
+                zh: "escape_time 返回 Option<u32>: 点在 i 次迭代后离开圆时为 Some(i), 看起来属于\n\
+                     集合时为 None。Option 是泛型的: Option<T> 适用于任何 T。",
+            },
+        },
+        Topic {
+            title: Text { en: "Mandelbrot and Julia sets", zh: "Mandelbrot 集与 Julia 集" },
+            body: Text {
+                en: "Both iterate z = z*z + c. The Mandelbrot set takes c from the pixel and\n\
+                     starts z at 0; `--julia RE,IM` fixes c and starts z at the pixel. The enum\n\
+                     Fractal says which, and render asks it for each point's escape time.",
+                zh: "两者都迭代 z = z*z + c。Mandelbrot 集的 c 取自像素, z 从 0 开始;\n\
+                     `--julia RE,IM` 固定 c, z 从像素开始。枚举 Fractal 表示是哪一种,\n\
+                     render 向它询问每个点的逃逸时间。",
+            },
+        },
+        Topic {
+            title: Text { en: "Parsing and errors", zh: "解析与错误" },
+            body: Text {
+                en: "The arguments are parsed into a pair of sizes and two complex numbers; a bad\n\
+                     one is reported by name, with the usage. Writing the image returns a Result,\n\
+                     and ? passes an error up to the caller.",
+                zh: "参数被解析成一对尺寸和两个复数; 有问题的参数会连同用法一起按名字报告。\n\
+                     写图像返回 Result, ? 把错误交给调用者。",
+            },
+        },
+        Topic {
+            title: Text { en: "Borrowing the pixel buffer", zh: "借用像素缓冲区" },
+            body: Text {
+                en: "render takes &mut [u8], a mutable borrow of the buffer, and write_image\n\
+                     takes &[T], a shared one. The buffer itself stays owned by main.",
+                zh: "render 接受 &mut [u8], 即缓冲区的可变借用; write_image 接受 &[T],\n\
+                     即共享借用。缓冲区本身一直归 main 所有。",
+            },
+        },
+        Topic {
+            title: Text { en: "Palettes", zh: "调色板" },
+            body: Text {
+                en: "`--palette fire|ocean|rainbow` colors a pixel by mixing the two colors on\n\
+                     either side of its escape time, three bytes to a pixel; gray, the default,\n\
+                     takes one. write_image is generic over the pixel type, Luma<u8> or Rgb<u8>.\n\
+                     `--smooth` takes a fraction off each escape time, from how far past the\n\
+                     circle z got (log2 log2 |z|), so the colors don't come in bands.",
+                zh: "`--palette fire|ocean|rainbow` 按逃逸时间混合两侧的两种颜色给像素上色,\n\
+                     每个像素三个字节; 默认的 gray 只用一个。write_image 对像素类型是泛型的,\n\
+                     Luma<u8> 或 Rgb<u8>。`--smooth` 根据 z 离开圆后走了多远 (log2 log2 |z|)\n\
+                     从逃逸时间中减去一个小数, 颜色就不会分成一条条的。",
+            },
+        },
+        Topic {
+            title: Text { en: "Threads without data races", zh: "没有数据竞争的线程" },
+            body: Text {
+                en: "rayon's par_chunks_mut splits the buffer into rows that don't overlap, and\n\
+                     its threads share them out, stealing from each other when they run out. The\n\
+                     rows may borrow from main, because for_each waits for every one of them.\n\
+                     `tiles` does the same by hand: squares on a queue behind a Mutex, taken by\n\
+                     scoped threads that report how busy they were.",
+                zh: "rayon 的 par_chunks_mut 把缓冲区分成互不重叠的行, 由它的线程分着做,\n\
+                     做完的线程会从别的线程那里偷活。这些行可以借用 main 的数据,\n\
+                     因为 for_each 会等所有行都画完。`tiles` 手工做同样的事: 方块放在 Mutex\n\
+                     保护的队列里, 由作用域线程领取, 最后报告各线程有多忙。",
+            },
+        },
+        Topic {
+            title: Text { en: "Generic over the number type", zh: "对数值类型泛型" },
+            body: Text {
+                en: "render, pixel_to_point and escape_time take a type parameter T: Real, and\n\
+                     work in f64 or in Dd, a double-double of two f64s with twice the digits.\n\
+                     main picks Dd when the pixels are too close together for f64 to tell\n\
+                     apart; the compiler makes a copy of the code for each. Deeper still, only\n\
+                     the middle pixel is iterated in Dd, and the rest follow their difference\n\
+                     from it in f64: an Orbit is just another EscapeTime<f64>.",
+                zh: "render、pixel_to_point 和 escape_time 带一个类型参数 T: Real, 可以用 f64\n\
+                     计算, 也可以用 Dd, 即由两个 f64 组成、位数多一倍的 double-double。像素\n\
+                     靠得太近、f64 分不清时, main 就选 Dd; 编译器为每种类型各生成一份代码。\n\
+                     这时只有中间的像素用 Dd 迭代, 其余像素用 f64 跟踪自己与它的差 (摄动):\n\
+                     Orbit 不过是又一个 EscapeTime<f64>。",
+            },
+        },
+    ],
+};
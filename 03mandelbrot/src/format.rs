@@ -0,0 +1,72 @@
+// The kinds of file mandelbrot writes, picked by the output file's extension or by
+// `--format`. PNG is lossless and small for pictures with large flat areas, like ours;
+// JPEG is smaller still for smooth colors, at some cost in sharpness; BMP is the pixels
+// as they are, uncompressed; TIFF keeps 16-bit grays; GIF and PNG can be animations.
+// PPM, netpbm's, is the simplest of them all, and written without the image crate.
+
+/// A kind of image file.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Png,
+    Jpeg,
+    Bmp,
+    Tiff,
+    Gif,
+    /// A PGM for gray, a PPM for color: ppm.rs.
+    Ppm,
+}
+
+impl Format {
+    /// The format called `name`, on the command line or as a file extension, in any
+    /// case.
+    pub fn from_name(name: &str) -> Option<Format> {
+        match name.to_ascii_lowercase().as_str() {
+            "png" => Some(Format::Png),
+            "jpeg" | "jpg" => Some(Format::Jpeg),
+            "bmp" => Some(Format::Bmp),
+            "tiff" | "tif" => Some(Format::Tiff),
+            "gif" => Some(Format::Gif),
+            "ppm" | "pgm" | "pnm" => Some(Format::Ppm),
+            _ => None,
+        }
+    }
+
+    /// The name `--format` takes for this format.
+    pub fn name(self) -> &'static str {
+        match self {
+            Format::Png => "png",
+            Format::Jpeg => "jpeg",
+            Format::Bmp => "bmp",
+            Format::Tiff => "tiff",
+            Format::Gif => "gif",
+            Format::Ppm => "ppm",
+        }
+    }
+
+    /// The format `filename`'s extension names, if it names one.
+    pub fn from_filename(filename: &str) -> Option<Format> {
+        std::path::Path::new(filename).extension()
+            .and_then(|e| e.to_str())
+            .and_then(Format::from_name)
+    }
+
+    /// Whether a file of this format can hold more than one picture.
+    pub fn animates(self) -> bool {
+        matches!(self, Format::Png | Format::Gif)
+    }
+}
+
+#[test]
+fn test_formats() {
+    assert_eq!(Format::from_filename("mandel.png"), Some(Format::Png));
+    assert_eq!(Format::from_filename("dir.d/mandel.JPG"), Some(Format::Jpeg));
+    assert_eq!(Format::from_filename("mandel.tif"), Some(Format::Tiff));
+    assert_eq!(Format::from_filename("mandel.webp"), None);
+    assert_eq!(Format::from_filename("mandel"), None);
+    assert_eq!(Format::from_name("Jpeg"), Some(Format::Jpeg));
+    assert_eq!(Format::from_filename("mandel.pgm"), Some(Format::Ppm));
+    assert!(Format::Gif.animates() && !Format::Bmp.animates());
+    for format in [Format::Png, Format::Jpeg, Format::Bmp, Format::Tiff, Format::Gif, Format::Ppm] {
+        assert_eq!(Format::from_name(format.name()), Some(format));
+    }
+}
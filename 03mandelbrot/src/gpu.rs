@@ -0,0 +1,258 @@
+// The escape loop on the graphics card, behind the `gpu` feature: `--backend gpu` hands
+// the picture to a compute shader, gpu.wgsl, one invocation per pixel, and colors what
+// comes back with the palette. A big picture is done in seconds rather than minutes:
+//
+//   $ cargo run --release --features gpu -- --backend gpu mandel.png 7680x4320 -1.20,0.35 -1,0.20 fast
+//
+// Note:
+//
+// 1.) wgpu finds a card through whatever the system has, Vulkan, Metal, DirectX 12 or
+//     OpenGL, and compiles the WGSL for it. Its requests for a card and a device are
+//     async; pollster::block_on waits for them, since nothing else is going on.
+// 2.) The card and the compiled shader are set up the first time they're asked for and
+//     kept for the rest of the process, in a OnceLock.
+// 3.) The card works in f32, whatever --precision says: the picture is --precision f32's,
+//     a pixel on the edge here and there apart, and as blocky once zoomed in. Points
+//     in the set are iterated to the limit; there's no period check.
+// 4.) The shader writes each pixel's escape, the count and z's norm_sqr then, or a count
+//     of IN_THE_SET; coloring it is done here, as render::color_of does, so the
+//     palettes and the smooth coloring are the CPU's own. Distance coloring needs the
+//     derivative as well, and isn't done on the card.
+// 5.) The shader's output is in a buffer it can write, which the CPU can't read: each
+//     strip is copied to one that can be mapped, and read from there. A strip is as many
+//     rows as fit in STRIP pixels, so the buffers stay within any card's limits.
+//
+use crate::stats::{self, Stats};
+use bytemuck::{Pod, Zeroable};
+use mandelbrot::escape::{fractional_escape, Fractal, Power, Settings};
+use mandelbrot::palette::Palette;
+use mandelbrot::render::Coloring;
+use num::Complex;
+use rustfun_error::{Error, Result};
+use std::io;
+use std::sync::OnceLock;
+
+/// How many pixels the shader does at a go, at most.
+const STRIP: usize = 1 << 22;
+
+/// The shader's workgroups are this many pixels each way.
+const WORKGROUP: u32 = 16;
+
+/// What gpu.wgsl's count is for a point that didn't escape.
+const IN_THE_SET: u32 = u32::MAX;
+
+/// gpu.wgsl's Params, field for field.
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+struct Params {
+    upper_left: [f32; 2],
+    size: [f32; 2],
+    c: [f32; 2],
+    bounds: [u32; 2],
+    top: u32,
+    rows: u32,
+    limit: u32,
+    julia: u32,
+    power: u32,
+    n: u32,
+    degree: f32,
+    bailout: f32,
+}
+
+/// gpu.wgsl's Escape.
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+struct Escape {
+    count: u32,
+    norm_sqr: f32,
+}
+
+/// A card, and the shader compiled for it.
+struct Gpu {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    pipeline: wgpu::ComputePipeline,
+}
+
+impl Gpu {
+    async fn new() -> std::result::Result<Gpu, String> {
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+        let options = wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::HighPerformance,
+            ..wgpu::RequestAdapterOptions::default()
+        };
+        let adapter = instance.request_adapter(&options).await.ok_or("no graphics card wgpu can use")?;
+        let info = adapter.get_info();
+        if !adapter.get_downlevel_capabilities().flags.contains(wgpu::DownlevelFlags::COMPUTE_SHADERS) {
+            return Err(format!("{}: no compute shaders", info.name));
+        }
+        // the least any card with compute shaders has: STRIP's buffers fit in it
+        let descriptor = wgpu::DeviceDescriptor {
+            label: Some("mandelbrot"),
+            required_limits: wgpu::Limits::downlevel_defaults(),
+            ..wgpu::DeviceDescriptor::default()
+        };
+        let (device, queue) = adapter.request_device(&descriptor, None).await
+            .map_err(|e| format!("{}: {}", info.name, e))?;
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("escape"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("gpu.wgsl").into()),
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("escape"),
+            layout: None,
+            module: &module,
+            entry_point: Some("main"),
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+            cache: None,
+        });
+        log::debug!("rendering on {} ({:?})", info.name, info.backend);
+        Ok(Gpu { device, queue, pipeline })
+    }
+
+    /// The process's card: see 2.) above.
+    fn get() -> Result<&'static Gpu> {
+        static GPU: OnceLock<std::result::Result<Gpu, String>> = OnceLock::new();
+        GPU.get_or_init(|| pollster::block_on(Gpu::new())).as_ref()
+            .map_err(|e| Error::Io(io::Error::other(format!("--backend gpu: {}", e))))
+    }
+}
+
+/// Render the view between these corners into `pixels`, as render::render does, but
+/// with the escape loop on the card, and each point counted for --stats the same way.
+/// `coloring` is bands or smooth: see 4.) above.
+#[allow(clippy::too_many_arguments)]
+pub fn render(pixels: &mut [u8],
+              bounds: (usize, usize),
+              upper_left: Complex<f32>,
+              lower_right: Complex<f32>,
+              fractal: &Fractal,
+              palette: &Palette,
+              coloring: Coloring,
+              settings: Settings) -> Result<()> {
+    assert_ne!(coloring, Coloring::Distance, "main turns --coloring distance away");
+    let gpu = Gpu::get()?;
+    let width = u32::try_from(bounds.0).ok()
+        .filter(|&width| width.div_ceil(WORKGROUP) <= gpu.device.limits().max_compute_workgroups_per_dimension)
+        .ok_or_else(|| Error::Overflow(format!("--backend gpu: {} pixels is too wide", bounds.0)))?;
+    let rows = (STRIP / bounds.0).clamp(1, bounds.1);
+    let channels = palette.channels();
+    let strip_len = (rows * bounds.0 * size_of::<Escape>()) as u64;
+
+    let params = gpu.device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("params"),
+        size: size_of::<Params>() as u64,
+        usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    let escapes = gpu.device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("escapes"),
+        size: strip_len,
+        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+        mapped_at_creation: false,
+    });
+    let read_back = gpu.device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("read back"),
+        size: strip_len,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    let bind_group = gpu.device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("escape"),
+        layout: &gpu.pipeline.get_bind_group_layout(0),
+        entries: &[
+            wgpu::BindGroupEntry { binding: 0, resource: params.as_entire_binding() },
+            wgpu::BindGroupEntry { binding: 1, resource: escapes.as_entire_binding() },
+        ],
+    });
+
+    let c = match fractal {
+        Fractal::Julia(c) => [c.re as f32, c.im as f32],
+        Fractal::Mandelbrot => [0.0, 0.0],
+    };
+    let (power, n) = match settings.power {
+        Power::Two => (0, 2),
+        Power::Integer(n) => (1, n),
+        Power::Fraction(_) => (2, 0),
+    };
+    let bailout = settings.bailout(if coloring == Coloring::Smooth { 16.0 } else { 2.0 });
+    let (start, mut counted) = (std::time::Instant::now(), Stats::new());
+    for top in (0..bounds.1).step_by(rows) {
+        let this = rows.min(bounds.1 - top);
+        gpu.queue.write_buffer(&params, 0, bytemuck::bytes_of(&Params {
+            upper_left: [upper_left.re, upper_left.im],
+            size: [lower_right.re - upper_left.re, upper_left.im - lower_right.im],
+            c,
+            bounds: [width, bounds.1 as u32],
+            top: top as u32,
+            rows: this as u32,
+            limit: settings.limit,
+            julia: matches!(fractal, Fractal::Julia(_)) as u32,
+            power,
+            n,
+            degree: settings.power.degree() as f32,
+            bailout: bailout as f32,
+        }));
+        let len = (this * bounds.0 * size_of::<Escape>()) as u64;
+        let mut encoder = gpu.device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some("escape") });
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor { label: Some("escape"),
+                                                                                      timestamp_writes: None });
+            pass.set_pipeline(&gpu.pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(width.div_ceil(WORKGROUP), (this as u32).div_ceil(WORKGROUP), 1);
+        }
+        encoder.copy_buffer_to_buffer(&escapes, 0, &read_back, 0, len);
+        gpu.queue.submit([encoder.finish()]);
+
+        let slice = read_back.slice(..len);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |mapped| drop(sender.send(mapped)));
+        gpu.device.poll(wgpu::Maintain::Wait);
+        receiver.recv().expect("wgpu maps the buffer once it's waited for")
+            .map_err(|e| Error::Io(io::Error::other(format!("--backend gpu: {}", e))))?;
+        {
+            let mapped = slice.get_mapped_range();
+            let lines = &mut pixels[top * bounds.0 * channels..(top + this) * bounds.0 * channels];
+            for (escape, pixel) in bytemuck::cast_slice::<u8, Escape>(&mapped).iter().zip(lines.chunks_mut(channels)) {
+                let count = (escape.count != IN_THE_SET).then_some(escape.count);
+                let shade = count.map(|count| match coloring {
+                    Coloring::Smooth => fractional_escape(count, escape.norm_sqr as f64, settings.power.degree()),
+                    _ => count as f64,
+                });
+                pixel.copy_from_slice(&palette.color(shade, settings.limit)[..channels]);
+                counted.add(count);
+            }
+        }
+        read_back.unmap();
+    }
+    stats::gather(&counted, start.elapsed());
+    Ok(())
+}
+
+// Only on a machine with a card wgpu can use: `cargo test --features gpu -- --ignored`.
+#[test]
+#[ignore = "needs a graphics card"]
+fn test_render() {
+    // the CPU's picture in f32, a pixel on the edge here and there apart
+    let (bounds, upper_left, lower_right) = ((40, 30), Complex { re: -1.2f32, im: 0.35 }, Complex { re: -1.0, im: 0.2 });
+    let julia = Fractal::Julia(Complex { re: -0.8, im: 0.156 });
+    let power = |d| Settings { power: Power::new(d), ..Settings::default() };
+    for (fractal, palette, coloring, settings) in [
+        (Fractal::Mandelbrot, Palette::Gray, Coloring::Bands, Settings::default()),
+        (Fractal::Mandelbrot, Palette::Fire, Coloring::Smooth, Settings { limit: 1000, escape_radius: 4.0, ..Settings::default() }),
+        (julia, Palette::Ocean, Coloring::Bands, Settings::default()),
+        (Fractal::Mandelbrot, Palette::Gray16, Coloring::Bands, power(3.0)),
+        (Fractal::Mandelbrot, Palette::Gray, Coloring::Smooth, power(2.5)),
+    ] {
+        let len = bounds.0 * bounds.1 * palette.channels();
+        let (mut on_the_card, mut on_the_cpu) = (vec![0; len], vec![0; len]);
+        render(&mut on_the_card, bounds, upper_left, lower_right, &fractal, &palette, coloring, settings).unwrap();
+        mandelbrot::render::render(&mut on_the_cpu, bounds, upper_left, lower_right, &fractal, &palette, coloring,
+                                   settings, |_| ());
+        let differ = on_the_card.chunks(palette.channels()).zip(on_the_cpu.chunks(palette.channels()))
+            .filter(|(a, b)| a != b).count();
+        assert!(differ <= 4, "{:?} {:?}: {} pixels differ", fractal, settings, differ);
+        assert!(on_the_card.iter().any(|&byte| byte != on_the_card[0]));
+    }
+}
@@ -0,0 +1,94 @@
+// The escape loop of escape.rs, one invocation per pixel, in f32: see gpu.rs.
+
+struct Params {
+    // the picture's upper left corner, and its width and height in the plane
+    upper_left: vec2<f32>,
+    size: vec2<f32>,
+    // a Julia set's c
+    c: vec2<f32>,
+    // the picture's width and height in pixels
+    bounds: vec2<u32>,
+    // the strip of rows this pass does: the first, and how many
+    top: u32,
+    rows: u32,
+    limit: u32,
+    julia: u32,
+    // Power: 0 for Two, 1 for Integer(n), 2 for Fraction(degree)
+    power: u32,
+    n: u32,
+    degree: f32,
+    bailout: f32,
+}
+
+// What became of a point: the iteration it escaped in, and z's norm_sqr then, for the
+// smooth coloring; a count of IN_THE_SET if it never did.
+struct Escape {
+    count: u32,
+    norm_sqr: f32,
+}
+
+const IN_THE_SET: u32 = 0xffffffffu;
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read_write> escapes: array<Escape>;
+
+fn mul(a: vec2<f32>, b: vec2<f32>) -> vec2<f32> {
+    return vec2<f32>(a.x * b.x - a.y * b.y, a.x * b.y + a.y * b.x);
+}
+
+// Power::of: squaring and multiplying for a whole power, polar form for any other
+fn power(z: vec2<f32>) -> vec2<f32> {
+    switch params.power {
+        case 0u: {
+            return mul(z, z);
+        }
+        case 1u: {
+            var result = vec2<f32>(1.0, 0.0);
+            var square = z;
+            var n = params.n;
+            while n > 0u {
+                if (n & 1u) == 1u {
+                    result = mul(result, square);
+                }
+                square = mul(square, square);
+                n = n >> 1u;
+            }
+            return result;
+        }
+        default: {
+            let r = length(z);
+            if r == 0.0 {
+                return z;
+            }
+            let angle = atan2(z.y, z.x) * params.degree;
+            return pow(r, params.degree) * vec2<f32>(cos(angle), sin(angle));
+        }
+    }
+}
+
+@compute @workgroup_size(16, 16)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    if id.x >= params.bounds.x || id.y >= params.rows {
+        return;
+    }
+    // pixel_to_point
+    let row = params.top + id.y;
+    let point = vec2<f32>(params.upper_left.x + f32(id.x) * params.size.x / f32(params.bounds.x),
+                          params.upper_left.y - f32(row) * params.size.y / f32(params.bounds.y));
+    var z = point;
+    var c = params.c;
+    if params.julia == 0u {
+        z = vec2<f32>(0.0, 0.0);
+        c = point;
+    }
+    var escape = Escape(IN_THE_SET, 0.0);
+    for (var i = 0u; i < params.limit; i++) {
+        z = power(z) + c;
+        let norm_sqr = dot(z, z);
+        if norm_sqr > params.bailout {
+            escape = Escape(i, norm_sqr);
+            break;
+        }
+    }
+    escapes[id.y * params.bounds.x + id.x] = escape;
+}
@@ -0,0 +1,85 @@
+// Batch rendering, behind `--jobs FILE`: a list of renders, one after another, in one
+// run of the program, for a set of pictures made together, a tour of the set or the
+// frames of a poster. FILE has one render on each line, its arguments as they'd be typed:
+//
+//   # a tour: the whole set, then closer in
+//   whole.png 1200x900 -2.4,1.2 0.8,-1.2
+//   --palette fire valley.png 1200x900 -0.80,0.20 -0.70,0.125
+//   --palette ocean --smooth spiral.png 1200x900 -0.74366,0.13182 -0.74362,0.13179
+//
+// A FILE ending in .json is an array of renders instead, each an object with the keys of
+// a config file (see config.rs):
+//
+//   [ { "output": "whole.png", "size": "1200x900", "upper-left": "-2.4,1.2", "lower-right": "0.8,-1.2" },
+//     { "output": "valley.png", "palette": "fire", "size": "1200x900", ... } ]
+//
+// Note:
+//
+// 1.) Each job is checked and rendered just as a command line would be: main hands its
+//     arguments to the same function. A mistake in one stops the run there, with the
+//     jobs before it done.
+// 2.) The threads are the process's: the rayon pool the fast renderer uses, tiles::pool,
+//     starts with the first job and serves them all, where a run of the program for each
+//     picture would start it each time.
+// 3.) A line is split at spaces and tabs, without quotes, so a file name can't have a
+//     space in it; blank lines, and lines starting with #, are left out.
+//
+use crate::config::Config;
+use rustfun_error::{Error, Result};
+use std::path::Path;
+
+/// One render of a jobs file: where it is in the file, and its arguments.
+#[derive(Debug, PartialEq)]
+pub struct Job {
+    /// The line it's on, or for JSON, its place in the array, from 1.
+    pub number: usize,
+    pub arguments: Vec<String>,
+}
+
+/// Read the jobs file at `path`: JSON if its name ends in .json, lines of arguments
+/// otherwise.
+pub fn read<P: AsRef<Path>>(path: P) -> Result<Vec<Job>> {
+    let path = path.as_ref();
+    let text = std::fs::read_to_string(path).map_err(|e| Error::io_at(path, e))?;
+    if path.extension().is_some_and(|e| e.eq_ignore_ascii_case("json")) {
+        let configs: Vec<Config> = serde_json::from_str(&text)
+            .map_err(|e| Error::parse(format!("{}: {}", path.display(), e)))?;
+        Ok(configs.iter().enumerate()
+            .map(|(i, config)| Job { number: i + 1, arguments: config.arguments() })
+            .collect())
+    } else {
+        Ok(text.lines().enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+            .map(|(i, line)| Job { number: i + 1, arguments: line.split_whitespace().map(String::from).collect() })
+            .collect())
+    }
+}
+
+#[test]
+fn test_read() {
+    let dir = tempfile::tempdir().unwrap();
+    let txt = dir.path().join("jobs.txt");
+    std::fs::write(&txt, "# a tour\nwhole.png 40x30 -2.4,1.2 0.8,-1.2\n\n  --palette fire\tvalley.png 40x30 -0.8,0.2 -0.7,0.125 \n")
+        .unwrap();
+    let words = |s: &str| s.split(' ').map(String::from).collect::<Vec<_>>();
+    assert_eq!(read(&txt).unwrap(), [
+        Job { number: 2, arguments: words("whole.png 40x30 -2.4,1.2 0.8,-1.2") },
+        Job { number: 4, arguments: words("--palette fire valley.png 40x30 -0.8,0.2 -0.7,0.125") },
+    ]);
+
+    let json = dir.path().join("jobs.json");
+    std::fs::write(&json, r#"[{"output": "whole.png", "size": "40x30"}, {"palette": "fire", "smooth": true}]"#).unwrap();
+    let mut jobs = read(&json).unwrap();
+    jobs.iter_mut().for_each(|job| job.arguments.sort());
+    assert_eq!(jobs, [
+        Job { number: 1, arguments: words("--output=whole.png --size=40x30") },
+        Job { number: 2, arguments: words("--palette=fire --smooth") },
+    ]);
+
+    for bad in [r#"{"output": "whole.png"}"#, r#"[{"pallete": "fire"}]"#, "[{"] {
+        std::fs::write(&json, bad).unwrap();
+        let error = read(&json).unwrap_err().to_string();
+        assert!(error.starts_with(&format!("{}: ", json.display())), "{}", error);
+    }
+    assert!(read(dir.path().join("missing.txt")).is_err());
+}
@@ -0,0 +1,82 @@
+//
+// The parts of the mandelbrot program that other crates need too: the parser for its
+// command-line arguments, so that the fuzz targets in fuzz/ can reach it, the
+// escape-time kernel, pixel_to_point and the double-double numbers, so that the benches
+// in benches/ can time them, and the renderer and its palettes, so that anything can
+// draw a picture without running the program (render.rs). main.rs uses them from here
+// like any other crate: `use mandelbrot::parse_complex;`.
+// Nothing in here touches a file or a thread, so it builds for wasm32 too; whatever
+// moves in later has to keep it that way (see `cargo xtask wasm`).
+//
+use num::Complex;
+use rustfun_common::parse_pair;
+
+pub mod double_double;
+use double_double::Real;
+pub mod escape;
+pub mod palette;
+pub mod render;
+
+/// Parse a pair of floating-point numbers separated by a comma as a complex
+/// number.
+// 9. Complex { re, im } is a shorthand notation to build the Complex value. 
+//    aka. to initialize a struct’s fields with variables of the same name
+#[allow(clippy::manual_map)] // spelled out, as in the book
+pub fn parse_complex(s: &str) -> Option<Complex<f64>> {
+	match parse_pair(s, ',') {
+		Some((re, im)) => Some(Complex { re, im }),
+		None => None
+	}
+}
+
+#[test]
+fn test_parse_complex() {
+    assert_eq!(parse_complex("1.25,-0.0625"), Some(Complex { re: 1.25, im: -0.0625 }));
+    assert_eq!(parse_complex(",-0.0625"), None);
+}
+
+/// Given the row and column of a pixel in the output image, return the
+/// corresponding point on the complex plane.
+///
+/// `bounds` is a pair giving the width and height of the image in pixels.
+/// `pixel` is a (column, row) pair indicating a particular pixel in that image.
+/// The `upper_left` and `lower_right` parameters are points on the complex
+/// plane designating the area our image covers.
+pub fn pixel_to_point<T: Real>(bounds: (usize, usize),
+				  pixel: (usize, usize),
+				  upper_left: Complex<T>,
+				  lower_right: Complex<T>)
+	-> Complex<T>
+{
+	let (width, height) = (lower_right.re - upper_left.re,
+						   upper_left.im - lower_right.im);
+    // 10.  pixel.0 refers to the first element of the tuple pixel.
+    // 11.  `as f64` is Rust’s syntax for a type conversion: this converts
+    //      pixel.0 to an f64 value.
+    // 11.1 T::from_f64 turns that f64 into a T; for T = f64 it does nothing at all.
+	Complex {
+		re: upper_left.re + T::from_f64(pixel.0 as f64) * width  / T::from_f64(bounds.0 as f64),
+		im: upper_left.im - T::from_f64(pixel.1 as f64) * height / T::from_f64(bounds.1 as f64)
+			// Why subtraction here? pixel.1 increases as we go down,
+			// but the imaginary component increases as we go up.
+	}
+}
+
+#[test]
+fn test_pixel_to_point() {
+	// The point is computed, so it's compared within a few ULPs, not with ==:
+	// 0.1 steps, say, don't add up exactly (rustfun_common::float).
+	use rustfun_common::ulps_eq;
+	let near = |a: Complex<f64>, b: Complex<f64>| ulps_eq(a.re, b.re, 4) && ulps_eq(a.im, b.im, 4);
+	assert!(near(pixel_to_point((100, 100), (25, 75),
+                                Complex { re: -1.0, im:  1.0 },
+                                Complex { re:  1.0, im: -1.0 }),
+                 Complex { re: -0.5, im: -0.5 }));
+	assert!(near(pixel_to_point((30, 10), (8, 3),
+                                Complex { re: -1.2, im:  0.35 },
+                                Complex { re: -0.9, im:  0.25 }),
+                 Complex { re: -1.12, im: 0.32 }));
+	assert_ne!(pixel_to_point((30, 10), (8, 3),
+                              Complex { re: -1.2, im:  0.35 },
+                              Complex { re: -0.9, im:  0.25 }).re, -1.12);
+}
@@ -1,187 +1,72 @@
-extern crate num;
+// num-complex is the part of `num` we use; on its own it builds without the long
+// unmaintained rustc-serialize dependency. Cargo.toml renames it to `num`.
 use num::Complex;
 
-#[allow(dead_code)]
-fn complex_square_add_loop(c: Complex<f64>) {
-    let mut z = Complex { re: 0.0, im: 0.0 };
-    loop {
-        z = z * z + c;
-    }
-}
-
-//  use /// to mark the comment lines above the function definition; the comments above the members
-//  of the Complex structure start with /// as well. These are documentation comments; the rustdoc
-//  utility knows how to parse them, together with the code they describe, and produce online
-//  documentation. 
-/// Try to determine if `c` is in the Mandelbrot set, using at most `limit`
-/// iterations to decide.
-///
-/// If `c` is not a member, return `Some(i)`, where `i` is the number of
-/// iterations it took for `c` to leave the circle of radius two centered on the
-/// origin. If `c` seems to be a member (more precisely, if we reached the
-/// iteration limit without being able to prove that `c` is not a member),
-/// return `None`.
-// 1. The function’s return value is an Option<u32>, for any type T, a value 
-//    of type Option<T> is either Some(v), where v is a value of type T;
-//    or None, indicating no T value is available.
-// 2. Option is a generic type: you can use Option<T> to represent an optional
-//    value of any type T you like.
-//
-fn escape_time(c: Complex<f64>, limit: u32) -> Option<u32> {
-	let mut z = Complex { re: 0.0, im: 0.0 };
-	for i in 0..limit {
-		z = z*z + c;
-        //3. The z.norm_sqr() method call returns the square of z’s distance from the origin.
-        //   instead of computing a square root, we just compare the squared distance with 4.0,
-        //   which is faster.
-		if z.norm_sqr() > 4.0 {
-			return Some(i);
-		}
-	}
+// parse_pair, generic over any T: FromStr, lives in the shared helper crate (common/)
+// together with its notes and tests; it reads the PIXELS argument.
+use rustfun_common::{parse_pair, tr};
 
-	None
-}
-
-use std::str::FromStr;
-
-/// Parse the string `s` as a coordinate pair, like `"400x600"` or `"1.0,0.5"`.
-///
-/// Specifically, `s` should have the form <left><sep><right>, where <sep> is
-/// the character given by the `separator` argument, and <left> and <right> are both
-/// strings that can be parsed by `T::from_str`.
-///
-/// If `s` has the proper form, return `Some<(x, y)>`. If it doesn't parse
-/// correctly, return `None`.
-// 1.  The definition of parse_pair is a generic function
-// 1.1 When you use a generic function, Rust will often be able to infer type parameters for you
-// 1.2  <T: FromStr> means "For any type T that implements the FromStr trait..." 
-// 2.  return type is Option<(T, T)>: either None, or a value Some((v1, v2)), where (v1, v2) is a
-//     tuple of two values, both of type T.
-fn parse_pair<T: FromStr>(s: &str, separator: char) -> Option<(T, T)> {
-    // 3. no explicit return statement, so its return value is the value of the last
-    //    expression in the match body
-	match s.find(separator) {
-        //4. the entire match expression evaluates to None, indicating that the parse failed.
-		None => None,
-        //5. Otherwise, we take index to be the separator’s position in the string.
-		Some(index) => {
-            // The power of the match expression in the Rust.
-            // 6.  The argument to the match is this tuple expression
-            //     (T::from_str(&s[..index]), T::from_str(&s[index + 1..])) 
-            // 7.  This pattern matches only if both elements of the tuple are Ok variants of
-            //     the Result type, indicating that both parses succeeded.
-            // 8.  The wildcard pattern _ matches anything, and ignores its value. 
-			match (T::from_str(&s[..index]), T::from_str(&s[index + 1..])) {
-				(Ok(l), Ok(r)) => Some((l, r)),
-				_ => None
-			}
-		}
-	}
-}
+// parse_complex is in lib.rs, where the fuzz targets can get at it too; pixel_to_point,
+// the escape-time kernel, escape.rs, and render, render.rs, are there for the benches,
+// and for anything else that wants a picture without running this program.
+use mandelbrot::{parse_complex, pixel_to_point};
+use mandelbrot::palette::Palette;
+use mandelbrot::render::{buffer_len, color_of, Coloring, RenderOptions};
+use mandelbrot::escape::{narrow, widen, EscapeTime, Fractal, Power, Settings};
 
-#[test]
-fn test_parse_pair() {
-	assert_eq!(parse_pair::<i32>("",        ','), None);
-	assert_eq!(parse_pair::<i32>("10,",     ','), None);
-	assert_eq!(parse_pair::<i32>(",10",     ','), None);
-	assert_eq!(parse_pair::<i32>("10,20",   ','), Some((10, 20)));
-	assert_eq!(parse_pair::<i32>("10,20xy", ','), None);
-	assert_eq!(parse_pair::<f64>("0.5x",    'x'), None);
-	assert_eq!(parse_pair::<f64>("0.5x1.5", 'x'), Some((0.5, 1.5)));
-}
-
-/// Parse a pair of floating-point numbers separated by a comma as a complex
-/// number.
-// 9. Complex { re, im } is a shorthand notation to build the Complex value. 
-//    aka. to initialize a struct’s fields with variables of the same name
-fn parse_complex(s: &str) -> Option<Complex<f64>> {
-	match parse_pair(s, ',') {
-		Some((re, im)) => Some(Complex { re, im }),
-		None => None
-	}
-}
-
-#[test]
-fn test_parse_complex() {
-    assert_eq!(parse_complex("1.25,-0.0625"), Some(Complex { re: 1.25, im: -0.0625 }));
-    assert_eq!(parse_complex(",-0.0625"), None);
-}
-
-/// Given the row and column of a pixel in the output image, return the
-/// corresponding point on the complex plane.
-///
-/// `bounds` is a pair giving the width and height of the image in pixels.
-/// `pixel` is a (column, row) pair indicating a particular pixel in that image.
-/// The `upper_left` and `lower_right` parameters are points on the complex
-/// plane designating the area our image covers.
-fn pixel_to_point(bounds: (usize, usize),
-				  pixel: (usize, usize),
-				  upper_left: Complex<f64>,
-				  lower_right: Complex<f64>)
-	-> Complex<f64>
-{
-	let (width, height) = (lower_right.re - upper_left.re,
-						   upper_left.im - lower_right.im);
-    // 10.  pixel.0 refers to the first element of the tuple pixel.
-    // 11.  `as f64` is Rust’s syntax for a type conversion: this converts
-    //      pixel.0 to an f64 value.
-	Complex {
-		re: upper_left.re + pixel.0 as f64 * width  / bounds.0 as f64,
-		im: upper_left.im - pixel.1 as f64 * height / bounds.1 as f64
-			// Why subtraction here? pixel.1 increases as we go down,
-			// but the imaginary component increases as we go up.
-	}
-}
-
-#[test]
-fn test_pixel_to_point() {
-	assert_eq!(pixel_to_point((100, 100), (25, 75),
-                              Complex { re: -1.0, im:  1.0 }, 
-                              Complex { re:  1.0, im: -1.0 }),
-               Complex { re: -0.5, im: -0.5 });
-}
-
-/// Render a rectangle of the Mandelbrot set into a buffer of pixels.
-///
-/// The `bounds` argument gives the width and height of the buffer `pixels`,
-/// which holds one grayscale pixel per byte. The `upper_left` and `lower_right`
-/// arguments specify points on the complex plane corresponding to the upper-
-/// left and lower-right corners of the pixel buffer.
-fn render(pixels: &mut [u8],
+/// The library's render, counting each point's escape for --stats as it goes: see
+/// stats.rs.
+#[allow(clippy::too_many_arguments)]
+fn render<T: Real, F: EscapeTime<T>>(pixels: &mut [u8],
 		  bounds: (usize, usize),
-		  upper_left: Complex<f64>,
-		  lower_right: Complex<f64>)
+		  upper_left: Complex<T>,
+		  lower_right: Complex<T>,
+		  fractal: &F,
+		  palette: &Palette,
+		  coloring: Coloring,
+		  settings: Settings)
 {
-	assert!(pixels.len() == bounds.0 * bounds.1);
-
-	for row in 0 .. bounds.1 {
-		for column in 0 .. bounds.0 {
-			let point = pixel_to_point(bounds, (column, row),
-			upper_left, lower_right);
-			pixels[row * bounds.0 + column] =
-				match escape_time(point, 255) {
-					None => 0,
-					Some(count) => 255 - count as u8
-				};
-		}
-	}
+	let (start, mut counted) = (std::time::Instant::now(), Stats::new());
+	mandelbrot::render::render(pixels, bounds, upper_left, lower_right, fractal, palette, coloring, settings,
+	                           |escape| counted.add(escape));
+	stats::gather(&counted, start.elapsed());
 }
 
-extern crate image;
-
-use image::ColorType;
-use image::png::PNGEncoder;
+use image::codecs::bmp::BmpEncoder;
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::PngEncoder;
+use image::codecs::tiff::TiffEncoder;
+use std::io::BufWriter;
+use image::ImageError;
+use rustfun_error::{Error, Result};
+use std::io;
+use image::{EncodableLayout, ImageBuffer, ImageResult, Luma, PixelWithColorType, Rgb};
 use std::fs::File;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::net::Ipv4Addr;
+use rayon::prelude::*;
 
 /// Write the buffer `pixels`, whose dimensions are given by `bounds`, to the
-/// file named `filename`.
+/// file named `filename`, as a `format` image of `P` pixels: `Luma<u8>` or `Luma<u16>`
+/// for grayscale, `Rgb<u8>` for color. `pixels` holds their channels one after another.
+/// A GIF is written by animation.rs instead.
 // 12.  write_image function has no useful value to return, So its success type is
 //      the unit type (), so called because it has only one value. 
 // 12.1 The unit type is akin to void in C and C++.
 // 13.  we can use Result<()> shorthand for Result<T, std::io::Error>, if we bring it
 //      into scope with a use std::io::Result declaration
-fn write_image(filename: &str, pixels: &[u8], bounds: (usize, usize))
-	-> Result<(), std::io::Error>
+// 13.1 image has the same kind of shorthand: ImageResult<()> is Result<(), ImageError>.
+//      An ImageError can be made from an io::Error, so ? converts one into the other.
+// 13.2 The where clause lists what the image crate needs from the pixel type: a pixel
+//      it knows the PNG color type of (PixelWithColorType), made of numbers it can see
+//      as bytes (EncodableLayout). Luma<u8> and Luma<u16> become 8- and 16-bit
+//      grayscale, Rgb<u8> 8-bit RGB. P::Subpixel is the type of one channel.
+fn write_image<P>(filename: &str, format: Format, pixels: &[P::Subpixel], bounds: (usize, usize))
+    -> ImageResult<()>
+where
+    P: PixelWithColorType,
+    [P::Subpixel]: EncodableLayout,
 {
     // 12. The ? operator exists to make these checks convenient. 
     //     Instead of spelling everything out like:
@@ -189,98 +74,1129 @@ fn write_image(filename: &str, pixels: &[u8], bounds: (usize, usize))
     //          Ok(f) => { f }
     //          Err(e) => { return Err(e); }
     //      };
-	let output = File::create(filename)?;
+	let mut output = BufWriter::new(File::create(filename)?);
 
-	let encoder = PNGEncoder::new(output);
-    // the value ColorType::Gray(8) indicates that each byte is an eight-bit grayscale value.
-	encoder.encode(&pixels,
-				   bounds.0 as u32, bounds.1 as u32,
-				   ColorType::Gray(8))?;
+    // An ImageBuffer borrowing our pixels: from_raw checks that there are
+    // width * height of them.
+	let image = ImageBuffer::<P, &[P::Subpixel]>::from_raw(bounds.0 as u32, bounds.1 as u32, pixels)
+		.expect("pixel buffer doesn't match bounds");
+	// 13.3 One encoder type per format; they all implement image's ImageEncoder trait,
+	//      which is what write_with_encoder takes. Some formats can't hold some pixels,
+	//      like JPEG 16-bit gray; the encoder says so with an ImageError::Unsupported.
+	match format {
+		Format::Png => image.write_with_encoder(PngEncoder::new(output))?,
+		// 90 of 100: a file a third the size of the PNG, with no visible blur
+		Format::Jpeg => image.write_with_encoder(JpegEncoder::new_with_quality(output, 90))?,
+		Format::Bmp => image.write_with_encoder(BmpEncoder::new(&mut output))?,
+		Format::Tiff => image.write_with_encoder(TiffEncoder::new(output))?,
+		Format::Gif => unreachable!("GIFs are written as animations"),
+		Format::Ppm => unreachable!("PPMs are written by ppm.rs"),
+	}
 
 	Ok(())
 }
 
-use std::io::Write;
+#[test]
+fn test_write_image_gray_and_rgb() {
+	let dir = tempfile::tempdir().unwrap();
+
+	let narrow: Vec<u8> = (0..12).map(|i| i * 20).collect();
+	let file = dir.path().join("narrow.png");
+	write_image::<Luma<u8>>(file.to_str().unwrap(), Format::Png, &narrow, (4, 3)).unwrap();
+	let back = image::open(&file).unwrap();
+	assert_eq!(back.color(), image::ColorType::L8);
+	assert_eq!(back.into_luma8().into_raw(), narrow);
+
+	let wide: Vec<u16> = (0..12).map(|i| i * 5000 + 1).collect();
+	let file = dir.path().join("wide.png");
+	write_image::<Luma<u16>>(file.to_str().unwrap(), Format::Png, &wide, (4, 3)).unwrap();
+	let back = image::open(&file).unwrap();
+	assert_eq!(back.color(), image::ColorType::L16);
+	assert_eq!(back.into_luma16().into_raw(), wide);
+
+	let color: Vec<u8> = (0..36).map(|i| i * 7).collect();
+	let file = dir.path().join("color.png");
+	write_image::<Rgb<u8>>(file.to_str().unwrap(), Format::Png, &color, (4, 3)).unwrap();
+	let back = image::open(&file).unwrap();
+	assert_eq!(back.color(), image::ColorType::Rgb8);
+	assert_eq!(back.into_rgb8().into_raw(), color);
+
+	// the lossless formats give back the same pixels; JPEG nearly the same
+	for (name, format) in [("color.bmp", Format::Bmp), ("color.tiff", Format::Tiff), ("color.jpg", Format::Jpeg)] {
+		let file = dir.path().join(name);
+		write_image::<Rgb<u8>>(file.to_str().unwrap(), format, &color, (4, 3)).unwrap();
+		let back = image::open(&file).unwrap().into_rgb8().into_raw();
+		if format == Format::Jpeg {
+			assert!(back.iter().zip(&color).all(|(a, b)| a.abs_diff(*b) < 40), "{:?}", back);
+		} else {
+			assert_eq!(back, color, "{}", name);
+		}
+	}
+	let file = dir.path().join("wide.tiff");
+	write_image::<Luma<u16>>(file.to_str().unwrap(), Format::Tiff, &wide, (4, 3)).unwrap();
+	assert_eq!(image::open(&file).unwrap().into_luma16().into_raw(), wide);
+	let file = dir.path().join("wide.jpg");
+	assert!(matches!(write_image::<Luma<u16>>(file.to_str().unwrap(), Format::Jpeg, &wide, (4, 3)),
+	                 Err(ImageError::Unsupported(_))));
+}
+
+mod explain;
+mod cli;
+use cli::{Cli, Command, SetOptions, ViewArgs};
+mod tiles;
+#[cfg(feature = "gpu")]
+mod gpu;
+use mandelbrot::double_double::{self, Dd, Real};
+mod perturbation;
+use perturbation::Orbit;
+mod antialias;
+use antialias::Sides;
+mod animation;
+use animation::Animation;
+mod format;
+use format::Format;
+mod ppm;
+mod config;
+use config::Config;
+mod checkpoint;
+use checkpoint::Checkpoint;
+mod split;
+use split::Index;
+mod stream;
+use stream::PngStream;
+mod stats;
+use stats::Stats;
+mod jobs;
+mod metadata;
+mod distribute;
+#[cfg(feature = "viewer")]
+mod viewer;
+
+/// The palette of --palette NAME, or of --palette-file FILE, with FILE; gray if neither
+/// is given.
+fn palette_of(set: &SetOptions) -> (Palette, Option<String>) {
+    match (&set.palette, &set.palette_file) {
+        (Some(named), Some(_)) => cli::exit(tr!("--palette-file: not with --palette {}",
+                                                "--palette-file: 不能和 --palette {} 一起用", named.name())),
+        (_, Some(path)) => (load_palette(path).unwrap_or_else(|e| e.exit("mandelbrot")), Some(path.clone())),
+        (named, None) => (named.clone().unwrap_or(Palette::Gray), None),
+    }
+}
+
+/// The palette of the colors in the file at `path`.
+fn load_palette(path: &str) -> Result<Palette> {
+    let text = std::fs::read_to_string(path).map_err(|e| Error::io_at(path, e))?;
+    Palette::parse(&text).map_err(|e| Error::Parse(format!("{}: {}", path, e)))
+}
+
+/// The next of the `positional` arguments, which must be there. `name` is how the usage
+/// calls it.
+fn take(positional: &mut VecDeque<String>, name: &str) -> String {
+    positional.pop_front().unwrap_or_else(|| cli::exit(tr!("missing {}", "缺少 {}", name)))
+}
+
+/// A corner of the view, `text`, given as `name`. Returns it parsed, and as it was written.
+fn corner(text: String, name: &str) -> (Complex<Dd>, String) {
+    match double_double::parse_complex(&text) {
+        Some(corner) => (corner, text),
+        None => cli::exit(tr!("{}: can't parse {:?}", "{}: 无法解析 {:?}", name, text)),
+    }
+}
 
 fn main() {
-    let args: Vec<String> = std::env::args().collect();
-
-    if args.len() != 6 {
-        writeln!(std::io::stderr(),
-        "Usage: mandelbrot FILE PIXELS UPPERLEFT LOWERRIGHT CONCURRENT")
-            .unwrap();
-        writeln!(std::io::stderr(),
-        "Example: {} mandel.png 1000x750 -1.20,0.35 -1,0.20 fast",
-        args[0])
-            .unwrap();
-        std::process::exit(1);
-    }
-
-    let bounds = parse_pair(&args[2], 'x')
-        .expect("error parsing image dimensions");
-    let upper_left = parse_complex(&args[3])
-        .expect("error parsing upper left corner point");
-    let lower_right = parse_complex(&args[4])
-        .expect("error parsing lower right corner point");
+    rustfun_common::init_logging();
+    explain::EXPLANATION.on_request();
+
+    // 14.  clap reads the command line: see cli.rs. Where the book calls expect() and
+    //      panics, a mistake is named, the usage shown, and the program exits with status 1.
+    let mut cli = Cli::parse(&std::env::args().skip(1).collect::<Vec<_>>());
+    match cli.command.take() {
+        Some(Command::Stitch { index, file }) => return stitch(&index, file),
+        Some(Command::View(args)) => return view(args),
+        Some(Command::Info { file }) => return info(&file),
+        None => {}
+    }
+    // 14.12 --jobs FILE renders a list of pictures, each of its lines a command line of
+    //       its own; it goes with nothing else. See jobs.rs.
+    if let Some(path) = &cli.jobs {
+        return run_jobs(path);
+    }
+    // 14.13 --serve-worker PORT renders nothing of its own, but tiles for a coordinator
+    //       run with --distribute, until it's killed, on --bind's address, or only this
+    //       machine's own, 127.0.0.1. See distribute.rs.
+    if let Some(port) = cli.serve_worker {
+        let (address, threads) = (cli.bind.unwrap_or(Ipv4Addr::LOCALHOST.into()), cli.threads);
+        return distribute::serve((address, port).into(), |options, tile| render_tile(options, tile, threads))
+            .unwrap_or_else(|e| e.exit("mandelbrot"));
+    }
+    run(cli);
+}
 
+/// Render the picture the command line `cli` describes, and return the FILE it went to.
+fn run(mut cli: Cli) -> String {
+    // 14.9 A checkpoint to resume from brings the options of its render along too.
+    let resumed = cli.resume.as_ref().map(|path| {
+        let (options, pixels) = Checkpoint::read(path).unwrap_or_else(|e| e.exit("mandelbrot"));
+        let config = Config::from_toml(&options, path).unwrap_or_else(|e| e.exit("mandelbrot"));
+        (options, pixels, config.arguments())
+    });
+    // 14.7 A config file's options go in behind the command line's, as though they'd
+    //      been typed after them, so the typed ones win, and the command line is read
+    //      again with them; see config.rs.
+    let config = cli.config.as_ref().map(|path| Config::load(path).unwrap_or_else(|e| e.exit("mandelbrot")));
+    if resumed.is_some() || config.is_some() {
+        let mut arguments = cli.arguments.clone();
+        if let Some((_, _, options)) = &resumed {
+            arguments = cli::behind(&arguments, options.clone());
+        }
+        if let Some(config) = config {
+            arguments = cli::behind(&arguments, config.arguments());
+        }
+        cli = Cli::parse(&arguments);
+    }
+    let checkpoint = cli.checkpoint.or(cli.resume);
+    // 14.1 The options have been parsed, and their values checked, by clap; what's left
+    //      is what they come to together, and their defaults.
+    let fractal = match cli.set.julia {
+        Some(c) => Fractal::Julia(c),
+        None => Fractal::Mandelbrot,
+    };
+    let (palette, palette_file) = palette_of(&cli.set);
+    // 14.11 --smooth is the short way to say --coloring smooth, so it can't go with another.
+    let coloring = cli.set.coloring;
+    if let Some(other) = coloring.filter(|&c| cli.smooth && c != Coloring::Smooth) {
+        cli::exit(tr!("--smooth: not with --coloring {}", "--smooth: 不能和 --coloring {} 一起用", other.name()));
+    }
+    let coloring = coloring.unwrap_or(if cli.smooth { Coloring::Smooth } else { Coloring::Bands });
+    let no_perturbation = cli.no_perturbation;
+    let precision = cli.set.precision.unwrap_or(Precision::Double);
+    // 14.15 cfg!(feature = "gpu") is true or false when the program is compiled; built
+    //       without the feature, there's no gpu module to call.
+    let backend = cli.backend.unwrap_or(Backend::Cpu);
+    if backend == Backend::Gpu && !cfg!(feature = "gpu") {
+        cli::exit(tr!("--backend gpu: built without it; build with --features gpu",
+                      "--backend gpu: 构建时没有包含它; 请用 --features gpu 构建"));
+    }
+    if backend == Backend::Gpu && coloring == Coloring::Distance {
+        cli::exit(tr!("--backend gpu: not with --coloring distance", "--backend gpu: 不能和 --coloring distance 一起用"));
+    }
+    let power = cli.set.power.unwrap_or(2.0);
+    let limit = cli.set.limit.unwrap_or(coloring.default_limit());
+    let escape_radius = cli.set.escape_radius.unwrap_or(2.0);
+    let period_check = !cli.no_period_check;
+    let settings = Settings { power: Power::new(power), limit, escape_radius, period_check };
+    let tile_size = cli.tile_size.unwrap_or(32);
+    let threads = cli.threads;
+    let view = RenderOptions { fractal, palette: palette.clone(), coloring, settings, threads };
+    let antialias = cli.antialias;
+    let frames = cli.frames.unwrap_or(1);
+    let zoom = cli.zoom.unwrap_or(1.1);
+    // 14.5 Each positional argument can be given by name instead, in any order; the
+    //      positionals left fill in the ones that weren't, in the usage's order.
+    let mut positional = cli.positional;
+    let (format, split, stats, distribute) = (cli.format, cli.split, cli.stats, cli.distribute);
+    let filename = cli.output.unwrap_or_else(|| take(&mut positional, "FILE"));
+    // 14.3 Without --format, the file's extension says; and it has to say something.
+    //      A FILE of - is stdout, where only a PPM goes.
+    let to_stdout = filename == "-";
+    let Some(format) = format.or_else(|| if to_stdout { Some(Format::Ppm) } else { Format::from_filename(&filename) })
+    else {
+        cli::exit(tr!("{}: can't tell the format; end it in .png, .jpg, .bmp, .tiff, .gif or .ppm, or give --format",
+                      "{}: 无法判断格式; 请以 .png, .jpg, .bmp, .tiff, .gif 或 .ppm 结尾, 或者给出 --format",
+                      filename))
+    };
+    if to_stdout && format != Format::Ppm {
+        cli::exit(tr!("only a PPM can go to stdout", "只有 PPM 能写到标准输出"));
+    }
+    // 14.4 Some formats can't hold 16-bit gray. Anti-aliasing averages colors, which
+    //      the counts of gray16 aren't.
+    if palette == Palette::Gray16 && matches!(format, Format::Jpeg | Format::Bmp | Format::Gif) {
+        cli::exit(tr!("{}: 16-bit gray needs a PNG, a TIFF or a PPM", "{}: 16 位灰度只能存为 PNG, TIFF 或 PPM",
+                      filename));
+    }
+    if palette == Palette::Gray16 && antialias.is_some() {
+        cli::exit(tr!("--antialias: gray16 holds counts, not colors to average",
+                      "--antialias: gray16 存的是次数, 不是可以平均的颜色"));
+    }
+    if palette == Palette::Gray16 && coloring == Coloring::Distance {
+        cli::exit(tr!("--coloring distance: gray16 holds counts, not distances",
+                      "--coloring distance: gray16 存的是次数, 不是距离"));
+    }
+    if frames > 1 && checkpoint.is_some() {
+        cli::exit(tr!("--checkpoint: only a single picture can be checkpointed",
+                      "--checkpoint: 只有单张图片能保存检查点"));
+    }
+    // 14.10 A split picture's tiles are PNGs, written one by one, each complete in itself.
+    if split.is_some() && format != Format::Png {
+        cli::exit(tr!("--split: the tiles are PNGs, so FILE has to be one", "--split: 方块都是 PNG, 所以 FILE 也得是"));
+    }
+    if split.is_some() && (frames > 1 || checkpoint.is_some()) {
+        cli::exit(tr!("--split: the tiles can't be frames of an animation, or checkpointed",
+                      "--split: 方块不能是动画的帧, 也不能保存检查点"));
+    }
+    if distribute.is_some() && (frames > 1 || checkpoint.is_some() || split.is_some() || stats.is_some()) {
+        cli::exit(tr!("--distribute: only a single picture, without --checkpoint, --split or --stats",
+                      "--distribute: 只能是单张图片, 不能和 --checkpoint, --split 或 --stats 一起用"));
+    }
+    if distribute.is_some() && limit > distribute::LIMIT {
+        cli::exit(tr!("--limit: a worker iterates {} times at most", "--limit: 工作进程最多迭代 {} 次",
+                      distribute::LIMIT));
+    }
+    if frames > 1 && !format.animates() {
+        cli::exit(tr!("{}: only a GIF or a PNG can hold more than one frame",
+                      "{}: 只有 GIF 或 PNG 能存多帧", filename));
+    }
+    let bounds: (usize, usize) = cli.size.unwrap_or_else(|| {
+        let pixels = take(&mut positional, "PIXELS");
+        parse_pair(&pixels, 'x').unwrap_or_else(|| {
+            cli::exit(tr!("PIXELS: expected two values separated by {:?}, got {:?}",
+                          "PIXELS: 应为以 {:?} 分隔的两个值, 却是 {:?}", 'x', pixels))
+        })
+    });
+    if bounds.0 == 0 || bounds.1 == 0 {
+        cli::exit(tr!("{}x{}: an image needs at least one pixel each way", "{}x{}: 图像每边至少要有一个像素",
+                      bounds.0, bounds.1));
+    }
+    // 14.14 A thread needs a row at least to have anything to do.
+    if let Some(threads) = threads.filter(|&threads| threads > bounds.1) {
+        cli::exit(tr!("--threads {}: more threads than the picture's {} rows",
+                      "--threads {}: 线程比图片的 {} 行还多", threads, bounds.1));
+    }
+    // 14.2 The corners are read with all their digits, in case they're too close together
+    //      for f64 to tell the pixels between them apart.
+    //      Their text is kept too, for --print-config.
+    let (upper_left, upper_left_text) = match cli.upper_left {
+        Some(text) => corner(text, "--upper-left"),
+        None => corner(take(&mut positional, "UPPERLEFT"), "UPPERLEFT"),
+    };
+    let (lower_right, lower_right_text) = match cli.lower_right {
+        Some(text) => corner(text, "--lower-right"),
+        None => corner(take(&mut positional, "LOWERRIGHT"), "LOWERRIGHT"),
+    };
+    // 14.6 The renderer is fast unless it's named, and has to be one there is.
+    let concurrent = cli.renderer.or_else(|| positional.pop_front()).unwrap_or_else(|| "fast".to_string());
+    if !["fast", "tiles", "slow"].contains(&concurrent.as_str()) {
+        cli::exit(tr!("{:?}: the renderer is fast, tiles or slow", "{:?}: 渲染器只能是 fast, tiles 或 slow",
+                      concurrent));
+    }
+    if let Some(extra) = positional.front() {
+        cli::exit(tr!("unexpected argument {:?}", "多余的参数 {:?}", extra));
+    }
+    // 14.8 What all that came to, as a config file that renders the same picture:
+    //      --print-config writes it out and stops there, and a checkpoint starts with it.
+    let config = Config {
+        output: Some(filename.clone()),
+        size: Some(format!("{}x{}", bounds.0, bounds.1)),
+        upper_left: Some(upper_left_text),
+        lower_right: Some(lower_right_text),
+        renderer: Some(concurrent.clone()),
+        julia: match fractal {
+            Fractal::Julia(c) => Some(format!("{},{}", c.re, c.im)),
+            Fractal::Mandelbrot => None,
+        },
+        palette: palette_file.is_none().then(|| palette.name().to_string()),
+        palette_file,
+        palette_colors: None,
+        coloring: Some(coloring.name().to_string()),
+        smooth: false,
+        no_perturbation,
+        precision: Some(precision.name().to_string()),
+        backend: Some(backend.name().to_string()),
+        no_period_check: !settings.period_check,
+        tile_size: Some(tile_size),
+        antialias,
+        format: Some(format.name().to_string()),
+        frames: Some(frames),
+        zoom: Some(zoom),
+        split,
+        power: Some(power),
+        limit: Some(limit),
+        escape_radius: Some(escape_radius),
+    };
+    let options = config.to_toml();
+    if cli.print_config {
+        print!("{}", options);
+        return filename;
+    }
+    let kept = match resumed {
+        Some((saved, _, _)) if saved != options => {
+            Error::Parse(tr!("{}: the checkpoint is of a render with other options",
+                             "{}: 检查点属于选项不同的渲染", checkpoint.unwrap_or_default())).exit("mandelbrot")
+        }
+        Some((_, pixels, _)) => pixels,
+        None => Vec::new(),
+    };
+
+    // 16.8 A distributed picture's tiles are rendered by the workers, each told the
+    //      options the whole render has, and put together here. See distribute.rs.
+    //      A palette file goes to them as its colors: a worker reads no files.
+    if let Some(hosts) = distribute {
+        let len = buffer_len(bounds, palette.channels()).unwrap_or_else(|e| e.exit("mandelbrot"));
+        let mut pixels = vec![0; len];
+        let request = Config {
+            palette_file: None,
+            palette_colors: palette.colors().map(<[_]>::to_vec),
+            ..config
+        }.to_toml();
+        let tiles = Index::new(&filename, bounds, distribute::TILE).tiles().into_iter().map(|(_, tile)| tile).collect();
+        distribute::render(&hosts, &request, tiles, bounds.0, palette.channels(), &mut pixels)
+            .unwrap_or_else(|e| e.exit("mandelbrot"));
+        write_still(&filename, format, &palette, &pixels, bounds, &options).unwrap_or_else(|e| exit_writing(&filename, e));
+        return filename;
+    }
+
+    // 16.6 A split picture is rendered a tile at a time, each into a buffer of its own
+    //      size: the whole picture is never in memory at all. See split.rs.
+    if let Some(split) = split {
+        let start = std::time::Instant::now();
+        let index = Index::new(&filename, bounds, split);
+        let mut done = Vec::new();
+        for (file, (x, y, width, height)) in index.tiles() {
+            let len = buffer_len((width, height), palette.channels()).unwrap_or_else(|e| e.exit("mandelbrot"));
+            let mut pixels = vec![0; len];
+            let corners = (pixel_to_point(bounds, (x, y), upper_left, lower_right),
+                           pixel_to_point(bounds, (x + width, y + height), upper_left, lower_right));
+            add_up(&mut done, render_view(&concurrent, &mut pixels, (width, height), corners.0, corners.1, &view,
+                                          tile_size, antialias, Sides::of((x, y, width, height), bounds),
+                                          no_perturbation, precision, backend));
+            let path = Path::new(&filename).with_file_name(file).to_string_lossy().into_owned();
+            write_still(&path, Format::Png, &palette, &pixels, (width, height), &options)
+                .unwrap_or_else(|e| exit_writing(&path, e));
+        }
+        if !done.is_empty() {
+            report_utilization(&done, start.elapsed());
+        }
+        index.save(Index::path(&filename)).unwrap_or_else(|e| e.exit("mandelbrot"));
+        if let Some(path) = stats {
+            write_stats(&path, start.elapsed());
+        }
+        return filename;
+    }
+
+    // 16.5 With a checkpoint the picture is rendered in bands of rows, each added to the
+    //      checkpoint when it's done. A resumed render starts after the last whole band
+    //      the checkpoint has, and the new checkpoint starts with them.
+    // 16.7 Without one, a still PNG is streamed: rendered a band at a time into a buffer
+    //      of just that band, each written out as soon as it's done. See stream.rs.
+    let streamed = format == Format::Png && frames == 1 && checkpoint.is_none();
+    let row_len = bounds.0 * palette.channels();
+    let band = match checkpoint {
+        Some(_) => bounds.1.div_ceil(checkpoint::BANDS),
+        None if streamed => stream::ROWS.min(bounds.1),
+        None => bounds.1,
+    };
     // 15.  A macro call vec![v; n] creates a vector n elements long 
     //      whose elements are initialized to v
-    let mut pixels = vec![0; bounds.0 * bounds.1];
+    let len = buffer_len(bounds, palette.channels()).unwrap_or_else(|e| e.exit("mandelbrot"));
+    let mut pixels = vec![0; if streamed { band * row_len } else { len }];
+    let first_row = (kept.len() / row_len).min(bounds.1) / band * band;
+    pixels[.. first_row * row_len].copy_from_slice(&kept[.. first_row * row_len]);
+    let mut checkpoint = checkpoint.map(|path| {
+        Checkpoint::create(path, &options, &pixels[.. first_row * row_len])
+    }).transpose().unwrap_or_else(|e| e.exit("mandelbrot"));
     
-    // 16. The &mut pixels borrows a mutable reference to our pixel buffer, allowing
-    //     render to fill it with computed grayscale values.
-    match &args[5][..] {
-        "fast" => render_c(&mut pixels, bounds, upper_left, lower_right),
-             _ => render(&mut pixels, bounds, upper_left, lower_right)
+    let start = std::time::Instant::now();
+    // 16.4 An animation renders one view after another into the same buffer, and writes
+    //      each out before rendering the next; what the threads did adds up over all.
+    let mut animation = (frames > 1 || format == Format::Gif)
+        .then(|| Animation::create(&filename, format, bounds, palette.channels(), frames, &options))
+        .transpose()
+        .unwrap_or_else(|e| exit_writing(&filename, e));
+    let stream = streamed.then(|| PngStream::create(&filename, bounds, palette.channels(), &options))
+        .transpose()
+        .unwrap_or_else(|e| exit_writing(&filename, e));
+    let mut done: Vec<tiles::Utilization> = Vec::new();
+    for frame in 0..frames {
+        let (upper_left, lower_right) = zoom_in(upper_left, lower_right, zoom, frame);
+        for top in (first_row .. bounds.1).step_by(band) {
+            let rows = band.min(bounds.1 - top);
+            let corners = if rows == bounds.1 {
+                (upper_left, lower_right)
+            } else {
+                (pixel_to_point(bounds, (0, top), upper_left, lower_right),
+                 pixel_to_point(bounds, (bounds.0, top + rows), upper_left, lower_right))
+            };
+            // 16. The &mut pixels borrows a mutable reference to our pixel buffer, allowing
+            //     render to fill it with the palette's colors.
+            let at = if streamed { 0 } else { top * row_len };
+            let lines = &mut pixels[at .. at + rows * row_len];
+            let this = render_view(&concurrent, lines, (bounds.0, rows), corners.0, corners.1, &view, tile_size,
+                                   antialias, Sides::of((0, top, bounds.0, rows), bounds), no_perturbation, precision,
+                                   backend);
+            add_up(&mut done, this);
+            if let Some(checkpoint) = &mut checkpoint {
+                checkpoint.add(lines).unwrap_or_else(|e| e.exit("mandelbrot"));
+            }
+            if let Some(stream) = &stream {
+                stream.add(lines);
+            }
+        }
+        if let Some(animation) = &mut animation {
+            animation.add(&pixels).unwrap_or_else(|e| exit_writing(&filename, e));
+        }
+    }
+    if !done.is_empty() {
+        report_utilization(&done, start.elapsed());
+    }
+    if rustfun_common::deterministic::is_on() {
+        log::debug!("rendered {}x{}", bounds.0, bounds.1);
+    } else {
+        log::debug!("rendered {}x{} in {:?}", bounds.0, bounds.1, start.elapsed());
     }
         // 17. In this case, we pass a shared (nonmutable) reference &pixels , since 
     //     write_image should have no need to modify the buffer’s contents.
-    write_image(&args[1], &pixels, bounds)
-        .expect("error writing PNG file");
+    let written = match (animation, stream) {
+        (Some(animation), _) => animation.finish(),
+        (None, Some(stream)) => stream.finish(),
+        (None, None) => write_still(&filename, format, &palette, &pixels, bounds, &options),
+    };
+    if let Err(e) = written {
+        exit_writing(&filename, e);
+    }
+    if let Some(checkpoint) = checkpoint {
+        checkpoint.remove().unwrap_or_else(|e| e.exit("mandelbrot"));
+    }
+    if let Some(path) = stats {
+        write_stats(&path, start.elapsed());
+    }
+    filename
+}
+
+/// `mandelbrot --jobs FILE`: render each job of FILE in turn, as though it had been typed,
+/// and tell stderr how long each took. See jobs.rs.
+fn run_jobs(path: &str) {
+    let jobs = jobs::read(path).unwrap_or_else(|e| e.exit("mandelbrot"));
+    let deterministic = rustfun_common::deterministic::is_on();
+    let start = std::time::Instant::now();
+    for job in &jobs {
+        let started = std::time::Instant::now();
+        // one job's statistics aren't the next one's
+        stats::reset();
+        let filename = run(Cli::parse(&job.arguments));
+        match deterministic {
+            true => eprintln!("{}:{}: {}", path, job.number, filename),
+            false => eprintln!("{}:{}: {} in {:.1} ms", path, job.number, filename,
+                               started.elapsed().as_secs_f64() * 1000.0),
+        }
+    }
+    match deterministic {
+        true => eprintln!("{} jobs", jobs.len()),
+        false => eprintln!("{} jobs in {:.1} ms", jobs.len(), start.elapsed().as_secs_f64() * 1000.0),
+    }
+}
+
+/// The hosts of --distribute: HOST:PORT, separated by commas.
+fn parse_hosts(s: &str) -> Option<Vec<String>> {
+    s.split(',')
+     .map(|host| host.rsplit_once(':')
+                     .filter(|(name, port)| !name.is_empty() && port.parse::<u16>().is_ok())
+                     .map(|_| host.to_string()))
+     .collect()
+}
+
+#[test]
+fn test_parse_hosts() {
+    assert_eq!(parse_hosts("alpha:7878,10.0.0.2:7878,[::1]:80"),
+               Some(vec!["alpha:7878".to_string(), "10.0.0.2:7878".to_string(), "[::1]:80".to_string()]));
+    for bad in ["", "alpha", "alpha:7878,", ":7878", "alpha:http", "alpha:65536"] {
+        assert_eq!(parse_hosts(bad), None, "{:?}", bad);
+    }
 }
 
-extern crate crossbeam;
-fn render_c(pixels: &mut [u8],
+/// The pixels of `tile` of the render `options` describes, a config file's worth, as
+/// --print-config prints them, on the worker's `threads`: a worker's part of a
+/// distributed render.
+fn render_tile(options: &str, tile: split::Rectangle, threads: Option<usize>) -> Result<Vec<u8>> {
+    let config = Config::from_toml(options, "the options")?;
+    let given = |value: &Option<String>, name: &str| {
+        value.clone().ok_or_else(|| Error::Parse(format!("the options have no {}", name)))
+    };
+    let unparsed = |name: &str, value: &str| Error::Parse(format!("{}: can't parse {:?}", name, value));
+    let size = given(&config.size, "size")?;
+    let bounds = parse_pair(&size, 'x').filter(|&(width, height): &(usize, usize)| width > 0 && height > 0)
+        .ok_or_else(|| unparsed("size", &size))?;
+    let corner = |value: &Option<String>, name: &str| {
+        let text = given(value, name)?;
+        double_double::parse_complex(&text).ok_or_else(|| unparsed(name, &text))
+    };
+    let (upper_left, lower_right) = (corner(&config.upper_left, "upper-left")?, corner(&config.lower_right, "lower-right")?);
+    let fractal = match &config.julia {
+        Some(c) => Fractal::Julia(parse_complex(c).ok_or_else(|| unparsed("julia", c))?),
+        None => Fractal::Mandelbrot,
+    };
+    // the coordinator sends a palette file's colors; a worker doesn't open files it's named
+    if config.palette_file.is_some() {
+        return Err(Error::Parse("palette-file: a worker reads no files; send palette-colors".to_string()));
+    }
+    let palette = match (&config.palette, &config.palette_colors) {
+        (_, Some(colors)) => Palette::custom(colors.clone()).map_err(|e| Error::Parse(format!("palette-colors: {}", e)))?,
+        (palette, None) => {
+            let palette = palette.as_deref().unwrap_or("gray");
+            Palette::from_name(palette).ok_or_else(|| unparsed("palette", palette))?
+        }
+    };
+    let coloring = config.coloring.as_deref().unwrap_or("bands");
+    let coloring = Coloring::from_name(coloring).ok_or_else(|| unparsed("coloring", coloring))?;
+    let precision = config.precision.as_deref().unwrap_or("f64");
+    let precision = Precision::from_name(precision).ok_or_else(|| unparsed("precision", precision))?;
+    let renderer = config.renderer.clone().unwrap_or_else(|| "fast".to_string());
+    if !["fast", "tiles", "slow"].contains(&renderer.as_str()) {
+        return Err(unparsed("renderer", &renderer));
+    }
+    let backend = config.backend.as_deref().unwrap_or("cpu");
+    let backend = Backend::from_name(backend).ok_or_else(|| unparsed("backend", backend))?;
+    if backend == Backend::Gpu && !cfg!(feature = "gpu") {
+        return Err(Error::Parse("backend: gpu: this worker was built without it".to_string()));
+    }
+    if backend == Backend::Gpu && coloring == Coloring::Distance {
+        return Err(Error::Parse("backend: gpu: not with distance coloring".to_string()));
+    }
+    let power = config.power.unwrap_or(2.0);
+    let limit = config.limit.unwrap_or(coloring.default_limit());
+    let escape_radius = config.escape_radius.unwrap_or(2.0);
+    let tile_size = config.tile_size.unwrap_or(32);
+    if !(power.is_finite() && power >= 2.0 && power <= u32::MAX as f64 && limit > 0 && escape_radius.is_finite() && escape_radius >= 2.0
+         && tile_size > 0 && config.antialias.is_none_or(|n| (1..=antialias::MOST).contains(&n))) {
+        return Err(Error::Parse("the options are out of range".to_string()));
+    }
+    let (x, y, width, height) = tile;
+    let inside = |at: usize, len: usize, whole: usize| len > 0 && at.checked_add(len).is_some_and(|end| end <= whole);
+    if !(inside(x, width, bounds.0) && inside(y, height, bounds.1)) {
+        return Err(Error::Parse(format!("{},{} {}x{}: not a tile of a {}x{} picture", x, y, width, height,
+                                        bounds.0, bounds.1)));
+    }
+    // no more than a coordinator asks for: a request can't keep the worker busy for ever
+    if width * height > distribute::TILE * distribute::TILE {
+        return Err(Error::Parse(format!("{}x{}: a tile is {}x{} at most", width, height,
+                                        distribute::TILE, distribute::TILE)));
+    }
+    if limit > distribute::LIMIT {
+        return Err(Error::Parse(format!("limit: {} is more than a worker's {}", limit, distribute::LIMIT)));
+    }
+    let period_check = !config.no_period_check;
+    let settings = Settings { power: Power::new(power), limit, escape_radius, period_check };
+    let mut pixels = vec![0; buffer_len((width, height), palette.channels())?];
+    let corners = (pixel_to_point(bounds, (x, y), upper_left, lower_right),
+                   pixel_to_point(bounds, (x + width, y + height), upper_left, lower_right));
+    let view = RenderOptions { fractal, palette, coloring, settings, threads };
+    render_view(&renderer, &mut pixels, (width, height), corners.0, corners.1, &view, tile_size, config.antialias,
+                Sides::of(tile, bounds), config.no_perturbation, precision, backend);
+    Ok(pixels)
+}
+
+/// Write the statistics of the pixels rendered, which took `elapsed`, to `path`, or to
+/// stderr for a `path` of -. With --deterministic, without the times.
+fn write_stats(path: &str, elapsed: std::time::Duration) {
+    let elapsed = (!rustfun_common::deterministic::is_on()).then_some(elapsed);
+    stats::gathered().report(elapsed).write(path).unwrap_or_else(|e| e.exit("mandelbrot"));
+}
+
+/// Write `pixels`, of `bounds`, colored by `palette`, to `filename`, as a picture in
+/// `format`, one that doesn't animate. A PNG says it was rendered with `options`.
+fn write_still(filename: &str, format: Format, palette: &Palette, pixels: &[u8], bounds: (usize, usize),
+               options: &str)
+    -> ImageResult<()>
+{
+    match palette {
+        _ if format == Format::Ppm => write_ppm(filename, pixels, bounds, palette.channels()),
+        // the same PNG a streamed render would have written
+        _ if format == Format::Png => {
+            let stream = PngStream::create(filename, bounds, palette.channels(), options)?;
+            stream.add(pixels);
+            stream.finish()
+        }
+        Palette::Gray => write_image::<Luma<u8>>(filename, format, pixels, bounds),
+        Palette::Gray16 => {
+            let counts: Vec<u16> = pixels.chunks(2).map(|count| u16::from_be_bytes([count[0], count[1]])).collect();
+            write_image::<Luma<u16>>(filename, format, &counts, bounds)
+        }
+        _ => write_image::<Rgb<u8>>(filename, format, pixels, bounds),
+    }
+}
+
+/// Count what the threads of the tiles renderer did, `this`, if it was that, into `done`.
+fn add_up(done: &mut Vec<tiles::Utilization>, this: Option<Vec<tiles::Utilization>>) {
+    match this {
+        Some(this) if done.is_empty() => *done = this,
+        Some(this) => for (all, this) in done.iter_mut().zip(this) {
+            all.tiles += this.tiles;
+            all.busy += this.busy;
+        },
+        None => {}
+    }
+}
+
+/// `mandelbrot stitch INDEX [FILE]`: put the tiles of a split picture together, into
+/// FILE, or the picture the index is of.
+fn stitch(index: &Path, output: Option<PathBuf>) {
+    if output.as_ref().is_some_and(|file| Format::from_filename(&file.to_string_lossy()) != Some(Format::Png)) {
+        cli::exit(tr!("stitch: FILE has to be a PNG", "stitch: FILE 必须是 PNG"));
+    }
+    split::stitch(index, output.as_deref()).unwrap_or_else(|e| e.exit("mandelbrot"));
+}
+
+/// `mandelbrot info FILE`: print the options the PNG FILE was rendered with, as a config
+/// file. See metadata.rs.
+fn info(file: &Path) {
+    print!("{}", metadata::options(file).unwrap_or_else(|e| e.exit("mandelbrot")));
+}
+
+/// `mandelbrot view [OPTIONS] [UPPERLEFT LOWERRIGHT]`: a window on the set to pan and
+/// zoom in, printing the corners of what it shows, and the options it was opened with
+/// that change the picture. See viewer.rs.
+#[cfg(feature = "viewer")]
+fn view(args: ViewArgs) {
+    let bounds = args.size.unwrap_or((800, 600));
+    let mut given = vec![];
+    let fractal = match args.set.julia {
+        Some(c) => {
+            given.push(format!("--julia {},{}", c.re, c.im));
+            Fractal::Julia(c)
+        }
+        None => Fractal::Mandelbrot,
+    };
+    let (palette, palette_file) = palette_of(&args.set);
+    if palette == Palette::Gray16 {
+        cli::exit(tr!("view: gray16 holds counts, not colors to show", "view: gray16 存的是次数, 不是可以显示的颜色"));
+    }
+    match palette_file {
+        Some(path) => given.push(format!("--palette-file {}", path)),
+        None if palette != Palette::Gray => given.push(format!("--palette {}", palette.name())),
+        None => {}
+    }
+    let coloring = args.set.coloring.unwrap_or(Coloring::Bands);
+    if coloring != Coloring::Bands {
+        given.push(format!("--coloring {}", coloring.name()));
+    }
+    let precision = args.set.precision.unwrap_or(Precision::Double);
+    if precision != Precision::Double {
+        given.push(format!("--precision {}", precision.name()));
+    }
+    let power = args.set.power.unwrap_or(2.0);
+    if power != 2.0 {
+        given.push(format!("--power {}", power));
+    }
+    let limit = args.set.limit.unwrap_or(coloring.default_limit());
+    if limit != coloring.default_limit() {
+        given.push(format!("--limit {}", limit));
+    }
+    let escape_radius = args.set.escape_radius.unwrap_or(2.0);
+    if escape_radius != 2.0 {
+        given.push(format!("--escape-radius {}", escape_radius));
+    }
+    let settings = Settings { power: Power::new(power), limit, escape_radius, ..Settings::default() };
+    // the whole set, or all of a Julia set that's in the circle of radius 2
+    let (upper_left, lower_right) = match (args.upper_left, args.lower_right, fractal) {
+        (Some(upper_left), Some(lower_right), _) => (corner(upper_left, "UPPERLEFT").0, corner(lower_right, "LOWERRIGHT").0),
+        (_, _, Fractal::Mandelbrot) => (double_double::parse_complex("-2.4,1.2").unwrap(),
+                                        double_double::parse_complex("0.8,-1.2").unwrap()),
+        (_, _, Fractal::Julia(_)) => (double_double::parse_complex("-2,1.5").unwrap(),
+                                      double_double::parse_complex("2,-1.5").unwrap()),
+    };
+    viewer::run(viewer::View { upper_left, lower_right }, bounds, fractal, &palette, coloring, settings, precision,
+                &given.join(" "))
+        .unwrap_or_else(|e| e.exit("mandelbrot"));
+}
+
+#[cfg(not(feature = "viewer"))]
+fn view(_: ViewArgs) {
+    cli::exit(tr!("view: built without the viewer; build with --features viewer",
+                  "view: 构建时没有包含查看器; 请用 --features viewer 构建"));
+}
+
+/// Write `pixels` to `filename`, or to stdout if it's `-`, as a PGM or a PPM.
+fn write_ppm(filename: &str, pixels: &[u8], bounds: (usize, usize), channels: usize) -> ImageResult<()> {
+    if filename == "-" {
+        ppm::write(io::stdout().lock(), pixels, bounds, channels)?;
+    } else {
+        ppm::write(BufWriter::new(File::create(filename)?), pixels, bounds, channels)?;
+    }
+    Ok(())
+}
+
+/// Report an error writing `filename`, and exit.
+// 17.1 An ImageError from writing a file is an I/O error, whatever the encoder
+//      thinks; the file name goes into the message.
+fn exit_writing(filename: &str, e: ImageError) -> ! {
+    let e = match e {
+        ImageError::IoError(e) => e,
+        e => io::Error::other(e),
+    };
+    Error::io_at(filename, e).exit("mandelbrot");
+}
+
+/// The number type a render is worked out in, as --precision names it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Precision {
+    /// f32 throughout: a quick preview, blocky once zoomed in a little.
+    Single,
+    /// f64, or double-double once the pixels are too close together for it.
+    Double,
+}
+
+impl Precision {
+    fn from_name(name: &str) -> Option<Precision> {
+        match name {
+            "f32" => Some(Precision::Single),
+            "f64" => Some(Precision::Double),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Precision::Single => "f32",
+            Precision::Double => "f64",
+        }
+    }
+}
+
+/// Render the view between these corners into `pixels`, as `view` says, threads and
+/// all, on the card if `backend` says, or else in f32 if `precision` says, or else in
+/// f64, in double-double, or by perturbation, whichever it needs, with the renderer
+/// named by `concurrent`. The view is a part of a picture that goes on past its
+/// `sides`, for anti-aliasing. The tiles renderer returns what its threads did.
+// 16.1 draw::<Dd> and draw::<f64> are two copies of the same code, one for each
+//      number type; the compiler makes them both, and this picks one at run time.
+// 16.9 draw::<f32> is a third copy, for --precision f32. f32 has 24 bits of mantissa to
+//      f64's 53: twice as many numbers fit in a vector register, and its arithmetic is
+//      no slower, but the pixels run together about a thousand times sooner.
+// 16.2 A deep zoom iterates one point, the middle, in double-double, and every pixel
+//      as an f64 difference from it (perturbation.rs). --no-perturbation iterates
+//      every pixel in double-double instead, which is slower, and can't go as deep.
+// 16.10 The card works in f32 too, and gets the same warning. The renderer's name makes
+//       no difference there: the card has threads of its own. Its edges are smoothed
+//       here afterwards, on the CPU. See gpu.rs.
+#[allow(clippy::too_many_arguments)]
+fn render_view(concurrent: &str,
+               pixels: &mut [u8],
+               bounds: (usize, usize),
+               upper_left: Complex<Dd>,
+               lower_right: Complex<Dd>,
+               view: &RenderOptions,
+               tile_size: usize,
+               antialias: Option<usize>,
+               sides: Sides,
+               no_perturbation: bool,
+               precision: Precision,
+               backend: Backend) -> Option<Vec<tiles::Utilization>> {
+    let (fractal, palette, coloring, settings) = (view.fractal, &view.palette, view.coloring, view.settings);
+    let threads = tiles::threads(view.threads);
+    if (precision == Precision::Single || backend == Backend::Gpu)
+        && too_close(bounds, upper_left, lower_right, f32::EPSILON as f64) {
+        log::warn!("the pixels are too close together for f32: the picture comes out blocky");
+    }
+    if backend == Backend::Gpu {
+        let (upper_left, lower_right) = (widen::<f32>(narrow(upper_left)), widen::<f32>(narrow(lower_right)));
+        on_the_card(pixels, bounds, upper_left, lower_right, &fractal, palette, coloring, settings)
+            .unwrap_or_else(|e| e.exit("mandelbrot"));
+        if let Some(n) = antialias {
+            tiles::pool(threads).install(|| {
+                smooth_edges(pixels, bounds, upper_left, lower_right, &fractal, palette, coloring, settings, n, sides)
+            });
+        }
+        None
+    } else if precision == Precision::Single {
+        draw(concurrent, pixels, bounds, widen::<f32>(narrow(upper_left)), widen::<f32>(narrow(lower_right)),
+             &fractal, palette, coloring, settings, tile_size, threads, antialias, sides)
+    } else if !needs_double_double(bounds, upper_left, lower_right) {
+        draw(concurrent, pixels, bounds, narrow(upper_left), narrow(lower_right),
+             &fractal, palette, coloring, settings, tile_size, threads, antialias, sides)
+    } else if let Power::Fraction(d) = settings.power {
+        log::warn!("a power of {} is only worked out in f64: a zoom this deep comes out blocky", d);
+        draw(concurrent, pixels, bounds, narrow(upper_left), narrow(lower_right),
+             &fractal, palette, coloring, settings, tile_size, threads, antialias, sides)
+    } else if no_perturbation || settings.power != Power::Two {
+        log::debug!("the pixels are too close together for f64: rendering in double-double");
+        draw(concurrent, pixels, bounds, upper_left, lower_right,
+             &fractal, palette, coloring, settings, tile_size, threads, antialias, sides)
+    } else {
+        log::debug!("the pixels are too close together for f64: rendering by perturbation");
+        let middle = pixel_to_point(bounds, (bounds.0 / 2, bounds.1 / 2), upper_left, lower_right);
+        let orbit = Orbit::new(fractal, middle, settings);
+        draw(concurrent, pixels, bounds, narrow(upper_left - middle), narrow(lower_right - middle),
+             &orbit, palette, coloring, settings, tile_size, threads, antialias, sides)
+    }
+}
+
+/// The corners of frame `frame` of a zoom into the middle of the view between
+/// `upper_left` and `lower_right`, each frame `zoom` times closer than the one before.
+/// Frame 0 is the view itself.
+fn zoom_in(upper_left: Complex<Dd>, lower_right: Complex<Dd>, zoom: f64, frame: u32) -> (Complex<Dd>, Complex<Dd>) {
+    if frame == 0 {
+        return (upper_left, lower_right);
+    }
+    let two = Dd::from(2.0);
+    let middle = Complex { re: (upper_left.re + lower_right.re) / two, im: (upper_left.im + lower_right.im) / two };
+    // 33.  zoom^frame as an f64 is rounded in its 16th digit, which moves the corners
+    //      by a sixteenth-digit fraction of the view: nothing a pixel would show.
+    let scale = Dd::from(zoom.powi(frame as i32));
+    let half = Complex { re: (lower_right.re - upper_left.re) / two / scale,
+                         im: (upper_left.im - lower_right.im) / two / scale };
+    (Complex { re: middle.re - half.re, im: middle.im + half.im },
+     Complex { re: middle.re + half.re, im: middle.im - half.im })
+}
+
+#[test]
+fn test_zoom_in() {
+    let corners = |s: &str| double_double::parse_complex(s).unwrap();
+    let (upper_left, lower_right) = (corners("-2,1.5"), corners("1,-1.5"));
+    assert_eq!(zoom_in(upper_left, lower_right, 2.0, 0), (upper_left, lower_right));
+    let (a, b) = zoom_in(upper_left, lower_right, 2.0, 1);
+    assert_eq!((narrow(a), narrow(b)), (Complex { re: -1.25, im: 0.75 }, Complex { re: 0.25, im: -0.75 }));
+    // forty frames, ten times closer each, and the view is 3e-40 across, still around
+    // the same middle
+    let (a, b) = zoom_in(upper_left, lower_right, 10.0, 40);
+    assert!(((b.re - a.re).to_f64() - 3e-40).abs() < 1e-54);
+    assert_eq!(narrow(a).re, -0.5);
+}
+
+#[allow(clippy::too_many_arguments)]
+fn render_c<T: Real, F: EscapeTime<T>>(pixels: &mut [u8],
             bounds: (usize, usize),
-            upper_left: Complex<f64>,
-            lower_right: Complex<f64>){
-    let threads = 8;
-    let rows_per_band = bounds.1 / threads + 1;
-    // 18.  buffer’s chunks_mut() method returns an iterator producing mutable, 
-    //      nonoverlapping slices of the buffer
-    // 19.  the iterator’s collect() method builds a vector holding these mutable,
-    //      nonoverlapping slices
-    let bands: Vec<&mut [u8]> = pixels.chunks_mut(rows_per_band * bounds.0).collect();
-    // 20.  The argument |spawner| { ... } is a Rust closure expression. 
-    //      |spawner| is the argument list, and { ... } is the body of the function. 
+            upper_left: Complex<T>,
+            lower_right: Complex<T>,
+            fractal: &F,
+            palette: &Palette,
+            coloring: Coloring,
+            settings: Settings){
+    let row_len = bounds.0 * palette.channels();
+    // 18.  The work isn't even: rows near the set take far longer than rows far from it.
+    //      Cut into 8 fixed bands, the threads with easy bands finish early and wait for
+    //      the one with the hard band. Here every row is a piece of work of its own.
+    // 19.  par_chunks_mut() is rayon's parallel chunks_mut(): it splits the buffer into
+    //      mutable, nonoverlapping slices, one row each, and rayon's threads (--threads
+    //      of them, see tiles.rs) share them out. A thread that runs out of rows steals
+    //      some from one that hasn't, so they all finish at about the same time.
+    // 20.  The argument |(row, line)| { ... } is a Rust closure expression. 
+    //      |(row, line)| is the argument list, and { ... } is the body of the function. 
     //      unlike functions declared with fn, we don’t need to declare the types of a
     //      closure’s arguments
-    // 21.  crossbeam::scope calls the closure, passing as the spawner argument a value the
-    //      closure can use to create new threads
-    // 21.1 crossbeam::scope waits for all such threads to finish execution before 
-    //      returning itself. when crossbeam::scope returns, the computation of the 
-    //      image is complete.
-    crossbeam::scope(|spawner| {
-        // 22.  The into_iter() iterator gives each iteration of the loop body exclusive
-        //      ownership of one band, ensuring that only one thread can write to it at a time. 
-        // 22.1 the enumerate adapter produces tuples pairing each vector element with its index.
-        for (i, band) in bands.into_iter().enumerate() {
-            let top = rows_per_band * i;
-            let height = band.len() / bounds.0;
-            let band_bounds = (bounds.0, height);
-            let band_upper_left =
-                pixel_to_point(bounds, (0, top), upper_left, lower_right);
-            let band_lower_right =
-                pixel_to_point(bounds, (bounds.0, top + height), upper_left, lower_right);
-
-            // 23.   create a thread, running the closure move || { ... }.
-            //       a closure of no arguments whose body is the { ... } form.
-            // 24.   move keyword indicates that this closure takes ownership of the 
-            //       variables it uses. 
-            // 24.1  in particular, only the closure may use the mutable slice band.
-            spawner.spawn(move || {
-                render(band, band_bounds, band_upper_left, band_lower_right);
-            });
+    // 20.1 the enumerate adapter produces tuples pairing each row with its index, and the
+    //      closure's argument takes the tuple apart.
+    // 21.  for_each returns once every row is rendered. Until then the rows borrow from
+    //      pixels, which is why a row can be written from another thread at all: the
+    //      borrow checker sees that each slice goes to one call of the closure only.
+    pixels.par_chunks_mut(row_len).enumerate().for_each(|(row, line)| {
+        let row_upper_left = pixel_to_point(bounds, (0, row), upper_left, lower_right);
+        let row_lower_right = pixel_to_point(bounds, (bounds.0, row + 1), upper_left, lower_right);
+        render(line, (bounds.0, 1), row_upper_left, row_lower_right, fractal, palette, coloring, settings);
+    });
+}
+
+/// Like `render_c`, but on the tile scheduler in tiles.rs, with tiles of `tile_size`
+/// pixels, on `threads` threads; returns what each thread did.
+// 22.  The threads are --threads N of them, or else one per core: see tiles::threads.
+// 22.1 The closure borrows bounds, the corners and the rest from here, and the
+//      scheduler calls it from every thread at once. That's allowed because all it
+//      does with them is read.
+#[allow(clippy::too_many_arguments)]
+fn render_tiles<T: Real, F: EscapeTime<T>>(pixels: &mut [u8],
+                bounds: (usize, usize),
+                upper_left: Complex<T>,
+                lower_right: Complex<T>,
+                fractal: &F,
+                palette: &Palette,
+                coloring: Coloring,
+                settings: Settings,
+                tile_size: usize,
+                threads: usize) -> Vec<tiles::Utilization> {
+    tiles::render(pixels, bounds, palette.channels(), tile_size, threads, |tile, buffer| {
+        let tile_upper_left = pixel_to_point(bounds, (tile.x, tile.y), upper_left, lower_right);
+        let tile_lower_right = pixel_to_point(bounds, (tile.x + tile.width, tile.y + tile.height),
+                                              upper_left, lower_right);
+        render(buffer, (tile.width, tile.height), tile_upper_left, tile_lower_right,
+               fractal, palette, coloring, settings);
+    })
+}
+
+/// Where the escape loop runs, as --backend names it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Backend {
+    /// The CPU's threads, in whatever number type --precision and the zoom say.
+    Cpu,
+    /// The graphics card, in f32, with the gpu feature: see gpu.rs.
+    Gpu,
+}
+
+impl Backend {
+    fn from_name(name: &str) -> Option<Backend> {
+        match name {
+            "cpu" => Some(Backend::Cpu),
+            "gpu" => Some(Backend::Gpu),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Backend::Cpu => "cpu",
+            Backend::Gpu => "gpu",
         }
+    }
+}
+
+#[cfg(feature = "gpu")]
+use gpu::render as on_the_card;
+
+/// Built without the gpu feature there's no card to render on; run and render_tile turn
+/// --backend gpu away before it gets here.
+#[cfg(not(feature = "gpu"))]
+#[allow(clippy::too_many_arguments)]
+fn on_the_card(_: &mut [u8], _: (usize, usize), _: Complex<f32>, _: Complex<f32>,
+               _: &Fractal, _: &Palette, _: Coloring, _: Settings) -> Result<()> {
+    Err(Error::Io(io::Error::other("--backend gpu: built without it")))
+}
+
+/// Print, to stderr, how many tiles each thread rendered and how much of `elapsed`
+/// it spent on them. With --deterministic only the total, which doesn't change.
+fn report_utilization(done: &[tiles::Utilization], elapsed: std::time::Duration) {
+    if rustfun_common::deterministic::is_on() {
+        let total: usize = done.iter().map(|d| d.tiles).sum();
+        eprintln!("{} tiles on {} threads", total, done.len());
+        return;
+    }
+    for (i, d) in done.iter().enumerate() {
+        eprintln!("thread {}: {} tiles, busy {:.0}% of {:.2?}",
+                  i, d.tiles, 100.0 * d.busy.as_secs_f64() / elapsed.as_secs_f64(), elapsed);
+    }
+}
+
+/// Render with the renderer named by `concurrent`, in the number type `T`, on `threads`
+/// threads, then, given `antialias`, smooth the edges, those along the `sides` the
+/// picture goes on past included. The tiles renderer returns what its threads did.
+// 30.4 Anti-aliasing is a second pass over the finished image, whichever renderer
+//      made it, in the same number type.
+#[allow(clippy::too_many_arguments)]
+fn draw<T: Real, F: EscapeTime<T>>(concurrent: &str,
+                 pixels: &mut [u8],
+                 bounds: (usize, usize),
+                 upper_left: Complex<T>,
+                 lower_right: Complex<T>,
+                 fractal: &F,
+                 palette: &Palette,
+                 coloring: Coloring,
+                 settings: Settings,
+                 tile_size: usize,
+                 threads: usize,
+                 antialias: Option<usize>,
+                 sides: Sides) -> Option<Vec<tiles::Utilization>> {
+    // rayon's par_ functions run on the pool of --threads threads they're called in
+    tiles::pool(threads).install(|| {
+        let done = match concurrent {
+            "fast" => { render_c(pixels, bounds, upper_left, lower_right, fractal, palette, coloring, settings); None }
+            "tiles" => Some(render_tiles(pixels, bounds, upper_left, lower_right,
+                                         fractal, palette, coloring, settings, tile_size, threads)),
+                 _ => { render(pixels, bounds, upper_left, lower_right, fractal, palette, coloring, settings); None }
+        };
+        if let Some(n) = antialias {
+            smooth_edges(pixels, bounds, upper_left, lower_right, fractal, palette, coloring, settings, n, sides);
+        }
+        done
+    })
+}
+
+/// Anti-alias `pixels`, already rendered between these corners: repaint the pixels on an
+/// edge with the average of `n` by `n` points spread over each. Past the `sides` given
+/// is more of the picture, whose pixels next to ours count for the edges too.
+// 30.3 The points of an image n times as wide and high, between the same corners, are
+//      n by n to each of our pixels, the first of them the pixel's own point.
+// 30.6 The pixels past the sides are colored here, by their points, as their own part
+//      colors them; outside the renderers, so --stats doesn't count them twice.
+#[allow(clippy::too_many_arguments)]
+fn smooth_edges<T: Real, F: EscapeTime<T>>(pixels: &mut [u8],
+                 bounds: (usize, usize),
+                 upper_left: Complex<T>,
+                 lower_right: Complex<T>,
+                 fractal: &F,
+                 palette: &Palette,
+                 coloring: Coloring,
+                 settings: Settings,
+                 n: usize,
+                 sides: Sides) {
+    let fine = (bounds.0 * n, bounds.1 * n);
+    let pixel = ((lower_right.re - upper_left.re) / T::from_f64(bounds.0 as f64)).to_f64().abs();
+    let channels = palette.channels();
+    // pixel_to_point, for the rows and columns just outside the view as well
+    let (width, height) = (lower_right.re - upper_left.re, upper_left.im - lower_right.im);
+    let beyond = |on: bool, len: usize, at: &(dyn Fn(usize) -> (f64, f64) + Sync)| -> Vec<u8> {
+        if !on {
+            return Vec::new();
+        }
+        (0 .. len).into_par_iter().flat_map_iter(|i| {
+            let (column, row) = at(i);
+            let point = Complex { re: upper_left.re + T::from_f64(column) * width / T::from_f64(bounds.0 as f64),
+                                  im: upper_left.im - T::from_f64(row) * height / T::from_f64(bounds.1 as f64) };
+            color_of(point, fractal, palette, coloring, settings, pixel).0.into_iter().take(channels)
+        }).collect()
+    };
+    let around = antialias::Around {
+        left: beyond(sides.left, bounds.1, &|row| (-1.0, row as f64)),
+        top: beyond(sides.top, bounds.0, &|column| (column as f64, -1.0)),
+        right: beyond(sides.right, bounds.1, &|row| (bounds.0 as f64, row as f64)),
+        bottom: beyond(sides.bottom, bounds.0, &|column| (column as f64, bounds.1 as f64)),
+    };
+    let refined = antialias::refine(pixels, bounds, channels, &around, n * n, |column, row, i| {
+        let point = pixel_to_point(fine, (column * n + i % n, row * n + i / n), upper_left, lower_right);
+        color_of(point, fractal, palette, coloring, settings, pixel).0
     });
-} 
+    log::debug!("antialiased {} of {} pixels", refined, bounds.0 * bounds.1);
+}
+
+/// Whether neighbouring pixels of an image of `bounds` between these corners are too
+/// close together for f64: less than 16 of its steps apart, at the size of the corners.
+// 28.  f64::EPSILON is the gap between 1.0 and the next f64 up, so x * EPSILON is about
+//      the gap at x. Even a few gaps per pixel would do for pixel_to_point, but the
+//      escape-time loop makes the rounding errors grow, and the picture gets blocky well
+//      before neighbouring pixels round to the same point.
+fn needs_double_double(bounds: (usize, usize), upper_left: Complex<Dd>, lower_right: Complex<Dd>) -> bool {
+    too_close(bounds, upper_left, lower_right, f64::EPSILON)
+}
+
+/// Whether the pixels between the corners are too close together to tell apart in a
+/// number type whose `epsilon` is that.
+fn too_close(bounds: (usize, usize), upper_left: Complex<Dd>, lower_right: Complex<Dd>, epsilon: f64) -> bool {
+    let step = f64::min((lower_right.re - upper_left.re).to_f64().abs() / bounds.0 as f64,
+                        (upper_left.im - lower_right.im).to_f64().abs() / bounds.1 as f64);
+    let size = [upper_left.re, upper_left.im, lower_right.re, lower_right.im].iter()
+        .map(|x| x.to_f64().abs())
+        .fold(0.0, f64::max);
+    step < 16.0 * size * epsilon
+}
+
+#[test]
+fn test_deep_zoom() {
+    // 2e-20 across, around i: a point on the edge of the set, where nearby points escape
+    // after fifty or so iterations, a different number for each
+    let corners = |s: &str| double_double::parse_complex(s).unwrap();
+    let (upper_left, lower_right) = (corners("-1e-20,1.00000000000000000001"),
+                                     corners("1e-20,0.99999999999999999999"));
+    let bounds = (16, 16);
+    assert!(needs_double_double(bounds, upper_left, lower_right));
+    assert!(!needs_double_double(bounds, corners("-1.20,0.35"), corners("-1,0.20")));
+    assert!(!needs_double_double(bounds, corners("-1e-20,1e-20"), corners("1e-20,-1e-20")));
+
+    let mut deep = vec![0; 16 * 16];
+    render(&mut deep, bounds, upper_left, lower_right, &Fractal::Mandelbrot, &Palette::Gray, Coloring::Bands, Settings::default());
+    let mut shallow = vec![0; 16 * 16];
+    render(&mut shallow, bounds, narrow(upper_left), narrow(lower_right),
+           &Fractal::Mandelbrot, &Palette::Gray, Coloring::Bands, Settings::default());
+    // in f64, every row is at im = 1, and they're all the same; in Dd they aren't
+    let rows_differ = |pixels: &[u8]| pixels.chunks(16).any(|row| row != &pixels[..16]);
+    assert!(!rows_differ(&shallow));
+    assert!(rows_differ(&deep));
+    // and the Dd image is the edge of the set: nearly everything escapes, late
+    assert!(deep.iter().filter(|&&p| p > 180 && p < 210).count() > 250);
+
+    // f32 runs out of digits far sooner: 1e-6 across is too close for it, not for f64
+    let (upper_left, lower_right) = (corners("-0.7436,0.1318"), corners("-0.743599,0.131799"));
+    assert!(too_close(bounds, upper_left, lower_right, f32::EPSILON as f64));
+    assert!(!needs_double_double(bounds, upper_left, lower_right));
+    // and where it isn't, the picture is f64's, a pixel here and there on the edge apart
+    let (upper_left, lower_right) = (Complex { re: -1.2f32, im: 0.35 }, Complex { re: -1.0f32, im: 0.2 });
+    let mut single = vec![0; 16 * 16];
+    render(&mut single, bounds, upper_left, lower_right, &Fractal::Mandelbrot, &Palette::Gray, Coloring::Bands, Settings::default());
+    render(&mut shallow, bounds, Complex { re: -1.2, im: 0.35 }, Complex { re: -1.0, im: 0.2 },
+           &Fractal::Mandelbrot, &Palette::Gray, Coloring::Bands, Settings::default());
+    assert!(single.iter().zip(&shallow).filter(|(a, b)| a != b).count() <= 8);
+}
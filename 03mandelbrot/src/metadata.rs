@@ -0,0 +1,96 @@
+// Where a PNG came from. Each PNG mandelbrot writes carries the options it was rendered
+// with, the viewport, the limit, the palette and the rest, as --print-config prints them,
+// and the version of the program, in text chunks of its own, for `info` to read back:
+//
+//   $ mandelbrot info mandel.png > render.toml
+//   $ mandelbrot --config render.toml --output again.png
+//
+// renders it again, and editing the corners in render.toml zooms in from there.
+//
+// Note:
+//
+// 1.) A PNG's text chunks are a keyword and some text each, which viewers and tools like
+//     exiftool show along with the picture. Software is one of the keywords the PNG
+//     standard suggests; the options go under a keyword of ours, "mandelbrot options".
+// 2.) tEXt chunks are Latin-1. Options with anything else in them, a file name in Chinese,
+//     say, go in an iTXt chunk instead, which is UTF-8; `info` reads either.
+// 3.) The text is small and written before the pixels, in the header, so the picture can
+//     still be streamed after it.
+//
+use rustfun_error::{Error, Result};
+use std::fs::File;
+use std::io::{BufReader, Write};
+use std::path::Path;
+
+/// The keyword of the text chunk the options are in.
+pub const OPTIONS: &str = "mandelbrot options";
+
+/// What the Software chunk says: the program, and its version.
+pub fn software() -> String {
+    format!("mandelbrot {}", env!("CARGO_PKG_VERSION"))
+}
+
+/// Have `encoder` write the text chunks of a render with `options`.
+pub fn add<W: Write>(encoder: &mut png::Encoder<W>, options: &str) -> std::result::Result<(), png::EncodingError> {
+    encoder.add_text_chunk("Software".to_string(), software())?;
+    if options.chars().all(|c| (c as u32) < 0x100) {
+        encoder.add_text_chunk(OPTIONS.to_string(), options.to_string())
+    } else {
+        encoder.add_itxt_chunk(OPTIONS.to_string(), options.to_string())
+    }
+}
+
+/// The text chunks of the PNG `info` has read, keyword and text, tEXt and iTXt alike.
+pub fn text(info: &png::Info) -> Vec<(String, String)> {
+    let latin1 = info.uncompressed_latin1_text.iter().map(|chunk| (chunk.keyword.clone(), chunk.text.clone()));
+    let utf8 = info.utf8_text.iter().filter_map(|chunk| Some((chunk.keyword.clone(), chunk.get_text().ok()?)));
+    latin1.chain(utf8).collect()
+}
+
+/// The text chunks of the PNG at `path`.
+pub fn read<P: AsRef<Path>>(path: P) -> Result<Vec<(String, String)>> {
+    let path = path.as_ref();
+    let file = File::open(path).map_err(|e| Error::io_at(path, e))?;
+    let reader = png::Decoder::new(BufReader::new(file)).read_info()
+        .map_err(|e| Error::Parse(format!("{}: {}", path.display(), e)))?;
+    Ok(text(reader.info()))
+}
+
+/// The options the PNG at `path` was rendered with, as a config file, with a comment
+/// saying which version of the program rendered it.
+pub fn options<P: AsRef<Path>>(path: P) -> Result<String> {
+    let path = path.as_ref();
+    let text = read(path)?;
+    let find = |keyword: &str| text.iter().find(|(k, _)| k == keyword).map(|(_, text)| text.as_str());
+    match find(OPTIONS) {
+        Some(options) => Ok(format!("# {}\n{}", find("Software").unwrap_or("mandelbrot"), options)),
+        None => Err(Error::Parse(format!("{}: no render options in it; was it made by mandelbrot?", path.display()))),
+    }
+}
+
+#[test]
+fn test_metadata() {
+    let dir = tempfile::tempdir().unwrap();
+    let write = |name: &str, options: &str| {
+        let path = dir.path().join(name);
+        let mut encoder = png::Encoder::new(File::create(&path).unwrap(), 2, 1);
+        encoder.set_color(png::ColorType::Grayscale);
+        add(&mut encoder, options).unwrap();
+        encoder.write_header().unwrap().write_image_data(&[0, 255]).unwrap();
+        path
+    };
+    let path = write("mandel.png", "output = \"mandel.png\"\nlimit = 255\n");
+    assert_eq!(read(&path).unwrap(), [("Software".to_string(), software()),
+                                      (OPTIONS.to_string(), "output = \"mandel.png\"\nlimit = 255\n".to_string())]);
+    assert_eq!(options(&path).unwrap(), format!("# {}\noutput = \"mandel.png\"\nlimit = 255\n", software()));
+    // and what isn't Latin-1, in UTF-8
+    let path = write("曼德博.png", "output = \"曼德博.png\"\n");
+    assert!(options(&path).unwrap().ends_with("\noutput = \"曼德博.png\"\n"));
+
+    // a PNG made by something else
+    let other = dir.path().join("other.png");
+    image::GrayImage::new(2, 1).save(&other).unwrap();
+    assert!(options(&other).unwrap_err().to_string().ends_with("no render options in it; was it made by mandelbrot?"));
+    std::fs::write(&other, "not a PNG").unwrap();
+    assert!(options(&other).unwrap_err().to_string().starts_with(&format!("{}: ", other.display())));
+}
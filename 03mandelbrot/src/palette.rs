@@ -0,0 +1,200 @@
+// The colors render paints with. Gray is the book's picture, one byte per pixel, white
+// where a point escapes at once and darker the longer it takes. The others spread the
+// escape time over a handful of colors, three bytes per pixel. Points that never
+// escape are black in all of them.
+//
+// Gray16 isn't colors at all: each pixel is the escape time itself, in two bytes, for
+// other tools to color as they like.
+//
+// A palette can come from a file too, `--palette-file`: a Fractint .map, a color on each
+// line as three numbers and maybe a comment,
+//
+//   0 0 0        black
+//   128 0 0
+//   255 200 0    gold
+//
+// or a CSV of the same, `128,0,0`; `parse` reads either.
+
+use std::sync::Arc;
+
+/// How an escape time becomes the color of a pixel.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Palette {
+    Gray,
+    /// 16-bit gray: the escape count, or 65535 for a point that doesn't escape.
+    Gray16,
+    Fire,
+    Ocean,
+    Rainbow,
+    /// Colors read from a file, by `parse`.
+    Custom(Arc<[[u8; 3]]>),
+}
+
+// 26.  Each palette is a few colors, evenly spaced from escaping at once (the first)
+//      to escaping at the last moment (the last); the ones in between are mixed from
+//      the two on either side.
+const GRAY: &[[u8; 3]] = &[[255, 255, 255], [0, 0, 0]];
+const FIRE: &[[u8; 3]] = &[[0, 0, 0], [128, 0, 0], [255, 64, 0], [255, 200, 0], [255, 255, 255]];
+const OCEAN: &[[u8; 3]] = &[[0, 7, 100], [32, 107, 203], [237, 255, 255], [255, 170, 0], [0, 2, 0]];
+const RAINBOW: &[[u8; 3]] = &[[255, 0, 0], [255, 255, 0], [0, 255, 0], [0, 255, 255],
+                              [0, 0, 255], [255, 0, 255]];
+
+impl Palette {
+    /// The palette called `name` on the command line, if there is one.
+    pub fn from_name(name: &str) -> Option<Palette> {
+        match name {
+            "gray" => Some(Palette::Gray),
+            "gray16" => Some(Palette::Gray16),
+            "fire" => Some(Palette::Fire),
+            "ocean" => Some(Palette::Ocean),
+            "rainbow" => Some(Palette::Rainbow),
+            _ => None,
+        }
+    }
+
+    /// The name `from_name` knows this palette by.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Palette::Gray => "gray",
+            Palette::Gray16 => "gray16",
+            Palette::Fire => "fire",
+            Palette::Ocean => "ocean",
+            Palette::Rainbow => "rainbow",
+            Palette::Custom(_) => "custom",
+        }
+    }
+
+    /// How many bytes a pixel takes: 1 for gray, 2 for 16-bit gray, 3 for red, green and
+    /// blue.
+    pub fn channels(&self) -> usize {
+        match self {
+            Palette::Gray => 1,
+            Palette::Gray16 => 2,
+            _ => 3,
+        }
+    }
+
+    fn stops(&self) -> &[[u8; 3]] {
+        match self {
+            Palette::Gray | Palette::Gray16 => GRAY,
+            Palette::Fire => FIRE,
+            Palette::Ocean => OCEAN,
+            Palette::Rainbow => RAINBOW,
+            Palette::Custom(stops) => stops,
+        }
+    }
+
+    /// The palette of the colors in `text`, a Fractint .map or a CSV, in order from
+    /// escaping at once to escaping at the last moment, or what's wrong with it.
+    // 26.3 The colors are shared, behind an Arc, so a palette clones without copying
+    //      them; the renderers borrow it.
+    //      Blank lines and lines starting with # are left out, and whatever follows a
+    //      color on its line, a .map's comment say, with them. What's wrong names the
+    //      line by its number alone: a worker's reply goes over the wire, and the file's
+    //      text has no business there.
+    pub fn parse(text: &str) -> Result<Palette, String> {
+        let mut stops = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut numbers = line.split(|c: char| c == ',' || c.is_whitespace()).filter(|n| !n.is_empty())
+                .map(|n| n.parse::<u8>().ok());
+            match [numbers.next(), numbers.next(), numbers.next()] {
+                [Some(Some(red)), Some(Some(green)), Some(Some(blue))] => stops.push([red, green, blue]),
+                _ => return Err(format!("line {}: not a color, three numbers from 0 to 255", i + 1)),
+            }
+        }
+        Palette::custom(stops)
+    }
+
+    /// The palette of `stops`, colors as `parse` reads them, or what's wrong with them.
+    pub fn custom(stops: Vec<[u8; 3]>) -> Result<Palette, String> {
+        if stops.len() < 2 {
+            return Err("a palette needs two colors at least".to_string());
+        }
+        Ok(Palette::Custom(stops.into()))
+    }
+
+    /// The colors of a palette that came from a file; None for the built-in ones.
+    pub fn colors(&self) -> Option<&[[u8; 3]]> {
+        match self {
+            Palette::Custom(stops) => Some(stops),
+            _ => None,
+        }
+    }
+
+    /// The color of a point that escaped after `escape` of `limit` iterations, or that
+    /// didn't. `escape` may be a fraction, from smooth coloring. A gray pixel is the
+    /// first of the three bytes, a 16-bit gray one the first two.
+    pub fn color(&self, escape: Option<f64>, limit: u32) -> [u8; 3] {
+        // 26.2 The count, rounded, most significant byte first, the order PNG, TIFF and
+        //      PPM all keep 16-bit numbers in; 65535 is kept for the points inside.
+        if *self == Palette::Gray16 {
+            let count = escape.map_or(u16::MAX, |count| count.round().clamp(0.0, (u16::MAX - 1) as f64) as u16);
+            let [high, low] = count.to_be_bytes();
+            return [high, low, 0];
+        }
+        let Some(count) = escape else {
+            return [0, 0, 0];
+        };
+        let stops = self.stops();
+        let at = (count / limit as f64).clamp(0.0, 1.0) * (stops.len() - 1) as f64;
+        let i = (at as usize).min(stops.len() - 2);
+        let (from, to, t) = (stops[i], stops[i + 1], at - i as f64);
+        // 26.1 The mix is rounded, so that gray comes out as the book's 255 - count.
+        let mix = |c: usize| (from[c] as f64 + (to[c] as f64 - from[c] as f64) * t).round() as u8;
+        [mix(0), mix(1), mix(2)]
+    }
+}
+
+#[test]
+fn test_palettes() {
+    for name in ["gray", "fire", "ocean", "rainbow"] {
+        let palette = Palette::from_name(name).unwrap();
+        assert_eq!(palette.color(None, 255), [0, 0, 0]);
+        assert_eq!(palette.color(Some(0.0), 255), palette.stops()[0]);
+        assert_eq!(palette.name(), name);
+    }
+    assert_eq!(Palette::from_name("pink"), None);
+    // gray16 keeps the count as it is
+    let gray16 = Palette::from_name("gray16").unwrap();
+    assert_eq!(gray16.color(Some(300.0), 1000), [1, 44, 0]);
+    assert_eq!(gray16.color(Some(2.4), 255), [0, 2, 0]);
+    assert_eq!(gray16.color(None, 255), [255, 255, 0]);
+    assert_eq!(gray16.color(Some(1e9), 255), [255, 254, 0]);
+    for count in 0..255 {
+        assert_eq!(Palette::Gray.color(Some(count as f64), 255)[0], 255 - count as u8);
+    }
+    // halfway through fire is its middle stop; a quarter of the way, its second
+    assert_eq!(Palette::Fire.color(Some(50.0), 100), [255, 64, 0]);
+    assert_eq!(Palette::Fire.color(Some(25.0), 100), [128, 0, 0]);
+    assert_eq!(Palette::Fire.color(Some(99.0), 100), [255, 253, 245]);
+    // fractions fall in between, and anything past the ends gets the end's color
+    assert_eq!(Palette::Gray.color(Some(10.25), 255)[0], 245);
+    assert_eq!(Palette::Fire.color(Some(-1.0), 100), [0, 0, 0]);
+    assert_eq!(Palette::Fire.color(Some(250.0), 100), [255, 255, 255]);
+}
+
+#[test]
+fn test_parse() {
+    // a .map, comments and all, and the same colors as CSV
+    let map = Palette::parse("0 0 0  black\n128 0 0\n\n\t255 255 255 white\n").unwrap();
+    assert_eq!(map, Palette::Custom(Arc::from([[0, 0, 0], [128, 0, 0], [255, 255, 255]])));
+    assert_eq!(Palette::parse("# red, green, blue\n0,0,0\n128, 0, 0\n255,255,255\n").unwrap(), map);
+    assert_eq!(map.channels(), 3);
+    // the stops are spread out and mixed, like fire's
+    assert_eq!(map.color(Some(25.0), 100), [64, 0, 0]);
+    assert_eq!(map.color(Some(50.0), 100), [128, 0, 0]);
+    assert_eq!(map.color(Some(75.0), 100), [192, 128, 128]);
+    assert_eq!(map.color(None, 100), [0, 0, 0]);
+    assert_eq!(Palette::parse("0 0 0\n1 2\n").unwrap_err(), "line 2: not a color, three numbers from 0 to 255");
+    assert_eq!(Palette::parse("0,0,0\n0,0,256\n").unwrap_err(), "line 2: not a color, three numbers from 0 to 255");
+    // what's wrong never has the line's text in it
+    assert_eq!(Palette::parse("root:x:0:0\n").unwrap_err(), "line 1: not a color, three numbers from 0 to 255");
+    assert_eq!(Palette::parse("# nothing\n255 0 0\n").unwrap_err(), "a palette needs two colors at least");
+    assert_eq!(map.colors(), Some(&[[0, 0, 0], [128, 0, 0], [255, 255, 255]][..]));
+    assert_eq!(Palette::custom(vec![[1, 2, 3]]).unwrap_err(), "a palette needs two colors at least");
+    assert_eq!(Palette::Fire.colors(), None);
+}
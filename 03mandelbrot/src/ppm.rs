@@ -0,0 +1,46 @@
+// The netpbm formats, written by hand: `--format ppm`, or a FILE ending in .ppm, .pgm or
+// .pnm. A PGM (gray) or PPM (color) file is a line of text saying what follows, then
+// the pixels, byte for byte as render left them. No compression and no checksums, so
+// nearly any image tool reads it, and it's easy to pipe: a FILE of `-` is stdout.
+//
+//   $ mandelbrot - 1000x750 -1.20,0.35 -1,0.20 fast | pnmtopng > mandel.png
+//
+// Note:
+//
+// 1.) The header is "P5" for gray or "P6" for RGB, the width, the height, and the
+//     largest value a channel can have, all separated by whitespace, with exactly one
+//     whitespace byte after the last. The pixels follow, row by row from the top. With
+//     a largest value over 255 a channel takes two bytes, most significant first.
+// 2.) write! on a Write, like println! on stdout, formats the text straight into it;
+//     write_all then hands over the pixel bytes in one go.
+// 3.) The output is generic, W: Write, so the same function writes a File, stdout,
+//     or a Vec<u8> in the test.
+//
+use std::io::{self, Write};
+
+/// Write the pixels of an image of `bounds`, `channels` bytes each (1 for gray, 2 for
+/// 16-bit gray, 3 for RGB), to `out` as a PGM or a PPM.
+pub fn write<W: Write>(mut out: W, pixels: &[u8], bounds: (usize, usize), channels: usize) -> io::Result<()> {
+    assert!(pixels.len() == bounds.0 * bounds.1 * channels);
+    let (magic, max) = match channels {
+        1 => ("P5", 255),
+        2 => ("P5", 65535),
+        _ => ("P6", 255),
+    };
+    write!(out, "{}\n{} {}\n{}\n", magic, bounds.0, bounds.1, max)?;
+    out.write_all(pixels)?;
+    out.flush()
+}
+
+#[test]
+fn test_write() {
+    let mut gray = Vec::new();
+    write(&mut gray, &[0, 128, 255, 7, 8, 9], (3, 2), 1).unwrap();
+    assert_eq!(gray, b"P5\n3 2\n255\n\x00\x80\xff\x07\x08\x09");
+    let mut color = Vec::new();
+    write(&mut color, &[1, 2, 3, 4, 5, 6], (2, 1), 3).unwrap();
+    assert_eq!(color, b"P6\n2 1\n255\n\x01\x02\x03\x04\x05\x06");
+    let mut wide = Vec::new();
+    write(&mut wide, &[1, 44, 255, 255], (1, 2), 2).unwrap();
+    assert_eq!(wide, b"P5\n1 2\n65535\n\x01\x2c\xff\xff");
+}
@@ -0,0 +1,256 @@
+// The renderer, as a library: what the mandelbrot program does with each pixel, for
+// other crates to draw pictures with, the web server chapter's, say, or a test, without
+// running the program and reading back its file.
+//
+//   use mandelbrot::render::{render_into, RenderOptions, Viewport};
+//
+//   let viewport = Viewport::parse("400x300", "-1.20,0.35", "-1,0.20").unwrap();
+//   let options = RenderOptions::default();
+//   let mut pixels = vec![0; options.buffer_len(&viewport)?];
+//   render_into(&mut pixels, &viewport, &options);
+//
+// Note:
+//
+// 1.) render_into is one thread, in f64, for the picture the viewport says; the program
+//     adds the threads, as many as the options' threads say, the deep zooms in
+//     double-double, and writing the file. The options' settings are the program's
+//     --power, --limit, --escape-radius and --no-period-check.
+// 2.) render itself, the loop under both, is generic over the number type and over the
+//     EscapeTime, so the program can hand it a perturbation orbit instead of a Fractal.
+//     It tells `counted` each point's escape, for the program's --stats.
+//
+use num::Complex;
+use crate::double_double::Real;
+use crate::escape::{EscapeTime, Fractal, Settings};
+use crate::palette::Palette;
+use crate::{parse_complex, pixel_to_point};
+use rustfun_error::{Error, Result};
+
+/// A rectangle of the complex plane, and the size of its picture in pixels.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Viewport {
+    /// The picture's width and height.
+    pub bounds: (usize, usize),
+    pub upper_left: Complex<f64>,
+    pub lower_right: Complex<f64>,
+}
+
+impl Viewport {
+    /// The viewport of the program's PIXELS, UPPERLEFT and LOWERRIGHT arguments, written
+    /// the same way: "400x300", "-1.20,0.35", "-1,0.20". None if any of them doesn't
+    /// parse, or the picture has no pixels.
+    pub fn parse(size: &str, upper_left: &str, lower_right: &str) -> Option<Viewport> {
+        let bounds = rustfun_common::parse_pair(size, 'x').filter(|&(width, height)| width > 0 && height > 0)?;
+        Some(Viewport { bounds, upper_left: parse_complex(upper_left)?, lower_right: parse_complex(lower_right)? })
+    }
+
+    /// The point of the plane the pixel at (column, row) `pixel` is of.
+    pub fn point(&self, pixel: (usize, usize)) -> Complex<f64> {
+        pixel_to_point(self.bounds, pixel, self.upper_left, self.lower_right)
+    }
+}
+
+/// What the picture is of, how it's colored, and how its points are iterated.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RenderOptions {
+    pub fractal: Fractal,
+    pub palette: Palette,
+    pub coloring: Coloring,
+    pub settings: Settings,
+    /// How many threads the program renders on, its --threads N; None for one per core.
+    /// render_into is one thread whatever it says: see 1.) above.
+    pub threads: Option<usize>,
+}
+
+/// The program's defaults: the Mandelbrot set, in gray bands, as Settings::default()
+/// iterates it, on one thread per core.
+impl Default for RenderOptions {
+    fn default() -> RenderOptions {
+        RenderOptions { fractal: Fractal::Mandelbrot, palette: Palette::Gray, coloring: Coloring::Bands,
+                        settings: Settings::default(), threads: None }
+    }
+}
+
+impl RenderOptions {
+    /// How many bytes the pixels of `viewport` take, in these options' palette; an
+    /// Overflow error if that's more than buffer_len allows.
+    pub fn buffer_len(&self, viewport: &Viewport) -> Result<usize> {
+        buffer_len(viewport.bounds, self.palette.channels())
+    }
+}
+
+/// The number of bytes in an image of `bounds` with `channels` bytes per pixel, if a
+/// PNG can be that big and the count fits in a usize.
+// 15.1 A plain `bounds.0 * bounds.1` panics on overflow in a debug build and wraps
+//      around in a release build; checked_mul returns None instead.
+pub fn buffer_len(bounds: (usize, usize), channels: usize) -> Result<usize> {
+    let too_big = || Error::Overflow(format!("{}x{} pixels is too many", bounds.0, bounds.1));
+    if u32::try_from(bounds.0).is_err() || u32::try_from(bounds.1).is_err() {
+        return Err(too_big());
+    }
+    bounds.0.checked_mul(bounds.1)
+        .and_then(|pixels| pixels.checked_mul(channels))
+        .ok_or_else(too_big)
+}
+
+#[test]
+fn test_buffer_len() {
+	assert_eq!(buffer_len((1000, 750), 1).unwrap(), 750_000);
+	assert_eq!(buffer_len((1000, 750), 3).unwrap(), 2_250_000);
+	assert!(matches!(buffer_len((usize::MAX, 2), 1), Err(Error::Overflow(_))));
+	assert!(matches!(buffer_len((1 << 32, 1), 1), Err(Error::Overflow(_))));
+	let huge = Viewport { bounds: (usize::MAX, 2), ..Viewport::parse("1x1", "0,0", "1,1").unwrap() };
+	assert!(matches!(RenderOptions::default().buffer_len(&huge), Err(Error::Overflow(_))));
+}
+
+/// Render `viewport` into `pixels`, as `options` say, a row at a time from the top, each
+/// pixel `options.palette.channels()` bytes. `pixels` has to be `options.buffer_len`
+/// long.
+pub fn render_into(pixels: &mut [u8], viewport: &Viewport, options: &RenderOptions) {
+    render(pixels, viewport.bounds, viewport.upper_left, viewport.lower_right,
+           &options.fractal, &options.palette, options.coloring, options.settings, |_| ());
+}
+
+/// Render a rectangle of the Mandelbrot set, or of a Julia set, into a buffer of pixels.
+///
+/// The `bounds` argument gives the width and height of the buffer `pixels`,
+/// which holds one pixel of `palette` per `palette.channels()` bytes. The
+/// `upper_left` and `lower_right` arguments specify points on the complex plane
+/// corresponding to the upper-left and lower-right corners of the pixel buffer.
+/// `coloring` says what the palette is given for each point: see `Coloring`. `fractal`
+/// works out how long each point takes to escape, iterating as `settings` says, and
+/// `counted` is told.
+#[allow(clippy::too_many_arguments)]
+pub fn render<T: Real, F: EscapeTime<T>>(pixels: &mut [u8],
+		  bounds: (usize, usize),
+		  upper_left: Complex<T>,
+		  lower_right: Complex<T>,
+		  fractal: &F,
+		  palette: &Palette,
+		  coloring: Coloring,
+		  settings: Settings,
+		  mut counted: impl FnMut(Option<u32>))
+{
+	let channels = palette.channels();
+	assert!(pixels.len() == bounds.0 * bounds.1 * channels);
+	let pixel = ((lower_right.re - upper_left.re) / T::from_f64(bounds.0 as f64)).to_f64().abs();
+
+	for row in 0 .. bounds.1 {
+		for column in 0 .. bounds.0 {
+			let point = pixel_to_point(bounds, (column, row),
+			upper_left, lower_right);
+			let (color, escape) = color_of(point, fractal, palette, coloring, settings, pixel);
+			counted(escape);
+			let at = (row * bounds.0 + column) * channels;
+			pixels[at .. at + channels].copy_from_slice(&color[.. channels]);
+		}
+	}
+}
+
+/// How the palette colors a point that escapes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Coloring {
+	/// By the iteration it escaped in, in bands of color.
+	Bands,
+	/// By a fractional iteration: the colors run into each other instead.
+	Smooth,
+	/// By how far it is from the set: the edge, and the filaments too thin for any
+	/// pixel to land on, in thin dark lines, and everything further out the same.
+	Distance,
+}
+
+impl Coloring {
+	pub fn from_name(name: &str) -> Option<Coloring> {
+		match name {
+			"bands" => Some(Coloring::Bands),
+			"smooth" => Some(Coloring::Smooth),
+			"distance" => Some(Coloring::Distance),
+			_ => None,
+		}
+	}
+
+	pub fn name(self) -> &'static str {
+		match self {
+			Coloring::Bands => "bands",
+			Coloring::Smooth => "smooth",
+			Coloring::Distance => "distance",
+		}
+	}
+
+	/// The iteration limit without --limit: the filaments distance coloring shows need
+	/// more iterations to get away from the set than the bands do.
+	pub fn default_limit(self) -> u32 {
+		match self {
+			Coloring::Bands | Coloring::Smooth => 255,
+			Coloring::Distance => 1000,
+		}
+	}
+}
+
+/// The color of the point `point` of `fractal`, iterated as `settings` says, in
+/// `palette`, in an image whose pixels are `pixel` apart, and the iteration it escaped in.
+// 37.2 A point a pixel or more from the set gets the palette's color for 0, and one
+//      right on the edge its color for 255: a pixel that's half covered by a filament
+//      is as dark as that.
+// 37.3 The counts run through the palette from 0 to the iteration limit, so a longer
+//      limit spreads the colors out further: the bands get narrower.
+pub fn color_of<T: Real, F: EscapeTime<T>>(point: Complex<T>, fractal: &F, palette: &Palette,
+                                           coloring: Coloring, settings: Settings, pixel: f64)
+	-> ([u8; 3], Option<u32>)
+{
+	let limit = settings.limit;
+	let (escape, scale) = match coloring {
+		Coloring::Bands => (fractal.escape_time(point, settings).map(|count| (count, count as f64)), limit),
+		Coloring::Smooth => (fractal.smooth_escape_time(point, settings), limit),
+		Coloring::Distance => (fractal.distance(point, settings)
+			.map(|(count, distance)| (count, 255.0 * (1.0 - (distance / pixel).min(1.0)))), 255),
+	};
+	(palette.color(escape.map(|(_, shade)| shade), scale), escape.map(|(count, _)| count))
+}
+
+#[test]
+fn test_render_into() {
+	assert_eq!(Viewport::parse("40x30", "-1.20,0.35", "-1,0.20"),
+	           Some(Viewport { bounds: (40, 30), upper_left: Complex { re: -1.2, im: 0.35 },
+	                           lower_right: Complex { re: -1.0, im: 0.2 } }));
+	for bad in [("40x0", "-1.20,0.35", "-1,0.20"), ("40x30", "-1.20", "-1,0.20"), ("40x30", "-1.20,0.35", "")] {
+		assert_eq!(Viewport::parse(bad.0, bad.1, bad.2), None);
+	}
+
+	// the book's gray: 255 - the count, or black inside the set
+	let viewport = Viewport::parse("40x30", "-1.20,0.35", "-1,0.20").unwrap();
+	let options = RenderOptions::default();
+	let mut pixels = vec![0; options.buffer_len(&viewport).unwrap()];
+	render_into(&mut pixels, &viewport, &options);
+	for (i, &gray) in pixels.iter().enumerate() {
+		let escape = crate::escape::escape_time(viewport.point((i % 40, i / 40)), Settings::default());
+		assert_eq!(gray, escape.map_or(0, |count| 255 - count as u8));
+	}
+	assert!(pixels.contains(&0) && pixels.iter().any(|&gray| gray > 200));
+
+	// in color, three bytes a pixel, and a Julia set isn't the Mandelbrot set
+	let fire = RenderOptions { palette: Palette::Fire, ..options.clone() };
+	let mut colors = vec![0; fire.buffer_len(&viewport).unwrap()];
+	render_into(&mut colors, &viewport, &fire);
+	assert_eq!(colors.len(), 3 * pixels.len());
+	let julia = RenderOptions { fractal: Fractal::Julia(Complex { re: -0.8, im: 0.156 }), ..options.clone() };
+	let mut other = vec![0; julia.buffer_len(&viewport).unwrap()];
+	render_into(&mut other, &viewport, &julia);
+	assert_ne!(other, pixels);
+
+	// the settings go through to the escape loops: cubed, and iterated longer
+	let settings = Settings { power: crate::escape::Power::new(3.0), limit: 1000, ..Settings::default() };
+	let cubed = RenderOptions { settings, ..options.clone() };
+	render_into(&mut other, &viewport, &cubed);
+	for (i, &gray) in other.iter().enumerate() {
+		let escape = crate::escape::escape_time(viewport.point((i % 40, i / 40)), settings);
+		assert_eq!(gray, Palette::Gray.color(escape.map(|count| count as f64), 1000)[0]);
+	}
+	assert_ne!(other, pixels);
+
+	// render tells what it counted
+	let mut interior = 0;
+	render(&mut pixels, viewport.bounds, viewport.upper_left, viewport.lower_right, &options.fractal,
+	       &options.palette, options.coloring, Settings::default(), |escape| interior += escape.is_none() as usize);
+	assert_eq!(interior, pixels.iter().filter(|&&gray| gray == 0).count());
+}
@@ -0,0 +1,199 @@
+// Pictures bigger than memory, behind `--split N`: instead of one picture, N by N pixel
+// tiles of it, each rendered into a buffer of its own size and written to a PNG of its
+// own, next to FILE, with an index saying how they fit together. Then
+//
+//   $ mandelbrot --split 4096 mandel.png 100000x75000 -1.20,0.35 -1,0.20
+//   $ mandelbrot stitch mandel.tiles.toml
+//
+// puts the tiles together into mandel.png, reading and writing a row of pixels at a time,
+// so that neither step ever holds more than a tile, or a row of tiles' worth of rows.
+//
+// Note:
+//
+// 1.) The tiles of mandel.png are mandel-ROW-COLUMN.png, counting tiles from 0 at the
+//     top left, and the index is mandel.tiles.toml. The index names the tiles and the
+//     picture relative to its own directory, so the lot can be moved together.
+// 2.) The tiles on the right and at the bottom are narrower and shorter when N doesn't
+//     divide the picture's width and height.
+// 3.) stitch uses the png crate's Reader::next_row to read each tile of a row of tiles a
+//     row of pixels at a time, and its StreamWriter to write the picture the same way.
+//
+use rustfun_error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// How a picture is split into tiles, and the files they are in.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Index {
+    /// The picture the tiles make up.
+    pub output: String,
+    pub width: usize,
+    pub height: usize,
+    pub tile_size: usize,
+    /// The tiles' files, a row of them at a time, from the top left.
+    pub tiles: Vec<Vec<String>>,
+}
+
+/// Where a tile is in the picture: its left, top, width and height, in pixels.
+pub type Rectangle = (usize, usize, usize, usize);
+
+impl Index {
+    /// The index of the picture `filename`, of `bounds`, split into tiles of `tile_size`.
+    pub fn new(filename: &str, bounds: (usize, usize), tile_size: usize) -> Index {
+        let path = Path::new(filename);
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        let (columns, rows) = (bounds.0.div_ceil(tile_size), bounds.1.div_ceil(tile_size));
+        Index {
+            output: path.file_name().unwrap_or_default().to_string_lossy().into_owned(),
+            width: bounds.0,
+            height: bounds.1,
+            tile_size,
+            tiles: (0..rows).map(|row| (0..columns).map(|column| format!("{}-{}-{}.png", stem, row, column))
+                                                   .collect())
+                            .collect(),
+        }
+    }
+
+    /// Where the index of the picture `filename` goes.
+    pub fn path(filename: &str) -> PathBuf {
+        Path::new(filename).with_extension("tiles.toml")
+    }
+
+    /// The tiles' files, each with where it is in the picture, row by row.
+    pub fn tiles(&self) -> Vec<(&str, Rectangle)> {
+        let size = |at: usize, whole: usize| self.tile_size.min(whole - at);
+        self.tiles.iter().enumerate().flat_map(|(row, files)| {
+            files.iter().enumerate().map(move |(column, file)| {
+                let (x, y) = (column * self.tile_size, row * self.tile_size);
+                (file.as_str(), (x, y, size(x, self.width), size(y, self.height)))
+            })
+        }).collect()
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let text = toml::to_string(self).map_err(Error::parse)?;
+        std::fs::write(path, text).map_err(|e| Error::io_at(path, e))
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Index> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|e| Error::io_at(path, e))?;
+        let index: Index = toml::from_str(&text).map_err(|e| Error::parse(format!("{}: {}", path.display(), e)))?;
+        let tiles_fit = index.tile_size > 0 && index.width > 0 && index.height > 0
+            && index.tiles.len() == index.height.div_ceil(index.tile_size)
+            && index.tiles.iter().all(|row| row.len() == index.width.div_ceil(index.tile_size));
+        if !tiles_fit {
+            return Err(Error::Parse(format!("{}: the tiles don't make up a {}x{} picture",
+                                            path.display(), index.width, index.height)));
+        }
+        Ok(index)
+    }
+}
+
+/// Put together the tiles of the index at `index`, into `output`, or else the picture the
+/// index names, as a PNG.
+pub fn stitch(index: &Path, output: Option<&Path>) -> Result<()> {
+    let dir = index.parent().unwrap_or(Path::new(""));
+    let index = Index::load(index)?;
+    let output = output.map_or_else(|| dir.join(&index.output), Path::to_path_buf);
+    let tiles = index.tiles();
+
+    // the first tile says what kind of pixels they all have
+    let first = open_tile(&dir.join(tiles[0].0), tiles[0].1)?;
+    let (color, depth) = (first.info().color_type, first.info().bit_depth);
+    let file = File::create(&output).map_err(|e| Error::io_at(&output, e))?;
+    let mut encoder = png::Encoder::new(BufWriter::new(file), index.width as u32, index.height as u32);
+    encoder.set_color(color);
+    encoder.set_depth(depth);
+    let encoding = |e: png::EncodingError| Error::Parse(format!("{}: {}", output.display(), e));
+    // the tiles say what the whole picture was rendered with
+    let text = crate::metadata::text(first.info());
+    if let Some((_, options)) = text.iter().find(|(keyword, _)| keyword == crate::metadata::OPTIONS) {
+        crate::metadata::add(&mut encoder, options).map_err(encoding)?;
+    }
+    let mut writer = encoder.write_header().map_err(encoding)?.into_stream_writer().map_err(encoding)?;
+
+    let mut line = Vec::new();
+    for row in tiles.chunks(index.tiles[0].len()) {
+        let mut readers = Vec::new();
+        for &(file, rectangle) in row {
+            let path = dir.join(file);
+            let reader = open_tile(&path, rectangle)?;
+            if (reader.info().color_type, reader.info().bit_depth) != (color, depth) {
+                return Err(Error::Parse(format!("{}: not the same kind of PNG as {}",
+                                                path.display(), tiles[0].0)));
+            }
+            readers.push((path, reader));
+        }
+        let (_, (_, _, _, height)) = row[0];
+        for _ in 0..height {
+            line.clear();
+            for (path, reader) in &mut readers {
+                match reader.next_row() {
+                    Ok(Some(pixels)) => line.extend_from_slice(pixels.data()),
+                    Ok(None) => return Err(Error::Parse(format!("{}: too few rows", path.display()))),
+                    Err(e) => return Err(Error::Parse(format!("{}: {}", path.display(), e))),
+                }
+            }
+            writer.write_all(&line).map_err(|e| Error::io_at(&output, e))?;
+        }
+    }
+    writer.finish().map_err(encoding)
+}
+
+/// Start reading the tile at `path`, which should be the size of `rectangle`.
+fn open_tile(path: &Path, rectangle: Rectangle) -> Result<png::Reader<BufReader<File>>> {
+    let file = File::open(path).map_err(|e| Error::io_at(path, e))?;
+    let mut decoder = png::Decoder::new(BufReader::new(file));
+    // the pixels as they are in the file: 16-bit grays stay 16-bit, big-endian
+    decoder.set_transformations(png::Transformations::IDENTITY);
+    let reader = decoder.read_info().map_err(|e| Error::Parse(format!("{}: {}", path.display(), e)))?;
+    let size = (reader.info().width as usize, reader.info().height as usize);
+    if size != (rectangle.2, rectangle.3) || reader.info().interlaced {
+        return Err(Error::Parse(format!("{}: expected a {}x{} tile, got {}x{}",
+                                        path.display(), rectangle.2, rectangle.3, size.0, size.1)));
+    }
+    Ok(reader)
+}
+
+#[test]
+fn test_index() {
+    let index = Index::new("out/mandel.png", (10, 7), 4);
+    assert_eq!(index.output, "mandel.png");
+    assert_eq!(index.tiles, [["mandel-0-0.png", "mandel-0-1.png", "mandel-0-2.png"],
+                             ["mandel-1-0.png", "mandel-1-1.png", "mandel-1-2.png"]]);
+    let tiles = index.tiles();
+    assert_eq!(tiles[0], ("mandel-0-0.png", (0, 0, 4, 4)));
+    assert_eq!(tiles[2], ("mandel-0-2.png", (8, 0, 2, 4)));
+    assert_eq!(tiles[5], ("mandel-1-2.png", (8, 4, 2, 3)));
+    assert_eq!(Index::path("out/mandel.png"), Path::new("out/mandel.tiles.toml"));
+}
+
+#[test]
+fn test_stitch() {
+    // a 5x3 picture of 16-bit grays, in tiles of 2: each pixel its own number
+    let dir = tempfile::tempdir().unwrap();
+    let filename = dir.path().join("counts.png");
+    let index = Index::new(filename.to_str().unwrap(), (5, 3), 2);
+    for (file, (x, y, width, height)) in index.tiles() {
+        let counts: Vec<u16> = (y..y + height).flat_map(|row| (x..x + width).map(move |column| (row * 5 + column) as u16))
+                                              .collect();
+        image::ImageBuffer::<image::Luma<u16>, _>::from_raw(width as u32, height as u32, counts).unwrap()
+            .save(dir.path().join(file)).unwrap();
+    }
+    let path = Index::path(filename.to_str().unwrap());
+    index.save(&path).unwrap();
+    assert_eq!(Index::load(&path).unwrap(), index);
+    stitch(&path, None).unwrap();
+    let stitched = image::open(&filename).unwrap().into_luma16().into_raw();
+    assert_eq!(stitched, (0..15).collect::<Vec<u16>>());
+
+    // a tile of the wrong size
+    image::GrayImage::new(2, 2).save(dir.path().join("counts-1-2.png")).unwrap();
+    let error = stitch(&path, None).unwrap_err().to_string();
+    assert!(error.ends_with("counts-1-2.png: expected a 1x1 tile, got 2x2"), "{}", error);
+}
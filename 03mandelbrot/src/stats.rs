@@ -0,0 +1,263 @@
+// Iteration statistics, behind `--stats FILE`: once the picture is written, how its
+// points came out, for tuning the iteration limit and for comparing one way of rendering
+// with another. FILE gets them as JSON; a FILE of - prints them to stderr instead.
+//
+//   {
+//     "pixels": 1200,
+//     "interior": 187,
+//     "iterations": { "min": 3, "max": 254, "mean": 24.6 },
+//     "histogram": [ { "from": 0, "to": 0, "pixels": 0 }, ..., { "from": 128, "to": 255, "pixels": 9 } ],
+//     "elapsed-ms": 1.9,
+//     "threads": [ { "thread": "rayon 0", "pixels": 640, "busy-ms": 0.9 }, ... ]
+//   }
+//
+// Note:
+//
+// 1.) render, the loop every renderer ends up in, counts the points of each piece of work
+//     it's given, a row, a tile or a band, in a Stats of its own, and adds that to the
+//     process's total when it's done: a lock for each row or tile, not for each pixel.
+// 2.) The histogram's buckets double in width, 0, 1, 2-3, 4-7, ..., so a handful of them
+//     cover any limit, and the last ones show how many points only just escaped.
+// 3.) The pixels are the ones rendered: every frame's, with --frames, and none of the
+//     rows a resumed checkpoint kept. Anti-aliasing's extra points aren't counted.
+// 4.) With --deterministic there are no times, which differ from run to run.
+// 5.) The same thread is counted as one, whatever band or frame it's at. The tiles
+//     renderer starts its threads again for each band of a streamed picture, and names
+//     them after their place in the line-up; rayon's threads, the fast renderer's, stay
+//     for the whole render, and have numbers of their own.
+//
+use rustfun_error::{Error, Result};
+use serde::Serialize;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// The points of some part of the picture: how many, and how long they took to escape.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Stats {
+    pixels: u64,
+    interior: u64,
+    min: Option<u32>,
+    max: u32,
+    sum: u64,
+    /// How many points escaped in 0 iterations, in 1, in 2 or 3, in 4 to 7, ...
+    histogram: Vec<u64>,
+    /// How many points each thread did, and how long it took over them.
+    threads: Vec<(String, u64, Duration)>,
+}
+
+// 38.  The process's total, which render adds to: process-wide, like
+//      rustfun_common::deterministic, rather than one more argument for every function
+//      between main and render.
+static GATHERED: Mutex<Stats> = Mutex::new(Stats::new());
+
+/// Add `part`, points that took the current thread `busy`, to the process's total.
+pub fn gather(part: &Stats, busy: Duration) {
+    GATHERED.lock().unwrap().add_up(part, busy);
+}
+
+/// The process's total so far.
+pub fn gathered() -> Stats {
+    GATHERED.lock().unwrap().clone()
+}
+
+/// Start the process's total again from nothing, for the next job of --jobs.
+pub fn reset() {
+    *GATHERED.lock().unwrap() = Stats::new();
+}
+
+/// What to call the current thread in the report: see 5.) above.
+fn thread_name() -> String {
+    let thread = std::thread::current();
+    match (rayon::current_thread_index(), thread.name()) {
+        (Some(i), _) => format!("rayon {}", i),
+        (None, Some(name)) => name.to_string(),
+        (None, None) => format!("{:?}", thread.id()),
+    }
+}
+
+impl Stats {
+    pub const fn new() -> Stats {
+        Stats { pixels: 0, interior: 0, min: None, max: 0, sum: 0, histogram: Vec::new(), threads: Vec::new() }
+    }
+
+    /// Count a point that escaped in iteration `escape`, or that didn't escape: None.
+    pub fn add(&mut self, escape: Option<u32>) {
+        self.pixels += 1;
+        let Some(i) = escape else {
+            self.interior += 1;
+            return;
+        };
+        self.min = Some(self.min.map_or(i, |min| min.min(i)));
+        self.max = self.max.max(i);
+        self.sum += i as u64;
+        // the bucket is the number of bits i takes: 0 for 0, 1 for 1, 2 for 2 and 3, ...
+        let bucket = (u32::BITS - i.leading_zeros()) as usize;
+        if self.histogram.len() <= bucket {
+            self.histogram.resize(bucket + 1, 0);
+        }
+        self.histogram[bucket] += 1;
+    }
+
+    /// Add `part`, points that took the current thread `busy`.
+    pub fn add_up(&mut self, part: &Stats, busy: Duration) {
+        self.pixels += part.pixels;
+        self.interior += part.interior;
+        self.min = match (self.min, part.min) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        self.max = self.max.max(part.max);
+        self.sum += part.sum;
+        if self.histogram.len() < part.histogram.len() {
+            self.histogram.resize(part.histogram.len(), 0);
+        }
+        for (bucket, count) in self.histogram.iter_mut().zip(&part.histogram) {
+            *bucket += count;
+        }
+        let name = thread_name();
+        match self.threads.iter_mut().find(|(thread, _, _)| *thread == name) {
+            Some((_, pixels, time)) => {
+                *pixels += part.pixels;
+                *time += busy;
+            }
+            None => self.threads.push((name, part.pixels, busy)),
+        }
+    }
+
+    /// The statistics to write out, for a render that took `elapsed`, if it's to say.
+    pub fn report(&self, elapsed: Option<Duration>) -> Report {
+        let escaped = self.pixels - self.interior;
+        let ms = |time: Duration| time.as_secs_f64() * 1000.0;
+        Report {
+            pixels: self.pixels,
+            interior: self.interior,
+            iterations: self.min.map(|min| Iterations { min, max: self.max, mean: self.sum as f64 / escaped as f64 }),
+            histogram: self.histogram.iter().enumerate().map(|(bucket, &pixels)| {
+                let (from, to) = match bucket {
+                    0 => (0, 0),
+                    b => (1 << (b - 1), u32::MAX >> (32 - b)),
+                };
+                Bucket { from, to, pixels }
+            }).collect(),
+            elapsed_ms: elapsed.map(ms),
+            threads: match elapsed {
+                Some(_) => self.threads.iter()
+                    .map(|(thread, pixels, busy)| Thread { thread: thread.clone(), pixels: *pixels, busy_ms: ms(*busy) })
+                    .collect(),
+                None => Vec::new(),
+            },
+        }
+    }
+}
+
+/// The statistics of a render, as `--stats` writes them.
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Report {
+    pub pixels: u64,
+    /// How many of them didn't escape.
+    pub interior: u64,
+    /// None if none of them escaped.
+    pub iterations: Option<Iterations>,
+    pub histogram: Vec<Bucket>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub elapsed_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub threads: Vec<Thread>,
+}
+
+/// How many iterations the points that escaped took.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Iterations {
+    pub min: u32,
+    pub max: u32,
+    pub mean: f64,
+}
+
+/// How many points escaped in `from` to `to` iterations.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Bucket {
+    pub from: u32,
+    pub to: u32,
+    pub pixels: u64,
+}
+
+/// What one thread did.
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Thread {
+    pub thread: String,
+    pub pixels: u64,
+    pub busy_ms: f64,
+}
+
+impl Report {
+    /// Write the report to `path` as JSON, or to stderr as text if `path` is -.
+    pub fn write(&self, path: &str) -> Result<()> {
+        if path == "-" {
+            eprint!("{}", self);
+            return Ok(());
+        }
+        let json = serde_json::to_string_pretty(self).map_err(Error::parse)?;
+        std::fs::write(path, json + "\n").map_err(|e| Error::io_at(path, e))
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{} pixels, {} inside the set", self.pixels, self.interior)?;
+        if let Some(i) = &self.iterations {
+            writeln!(f, "iterations: min {}, max {}, mean {:.1}", i.min, i.max, i.mean)?;
+        }
+        for bucket in &self.histogram {
+            let range = format!("{}-{}", bucket.from, bucket.to);
+            writeln!(f, "  {:>11} {}", range, bucket.pixels)?;
+        }
+        if let Some(elapsed) = self.elapsed_ms {
+            writeln!(f, "rendered in {:.1} ms", elapsed)?;
+        }
+        for thread in &self.threads {
+            writeln!(f, "{}: {} pixels, busy {:.1} ms", thread.thread, thread.pixels, thread.busy_ms)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_stats() {
+    let mut stats = Stats::new();
+    for escape in [Some(0), Some(1), Some(3), Some(2), None, Some(200), None] {
+        stats.add(escape);
+    }
+    let report = stats.report(None);
+    assert_eq!((report.pixels, report.interior), (7, 2));
+    assert_eq!(report.iterations, Some(Iterations { min: 0, max: 200, mean: 41.2 }));
+    let buckets: Vec<_> = report.histogram.iter().map(|b| (b.from, b.to, b.pixels)).collect();
+    assert_eq!(buckets, [(0, 0, 1), (1, 1, 1), (2, 3, 2), (4, 7, 0), (8, 15, 0), (16, 31, 0), (32, 63, 0),
+                         (64, 127, 0), (128, 255, 1)]);
+    assert_eq!(Stats::new().report(None).iterations, None);
+
+    // added up from two threads, it's the same
+    let total = Mutex::new(Stats::new());
+    std::thread::scope(|s| {
+        for escapes in [[Some(0), Some(1), Some(3)], [Some(2), None, Some(200)]] {
+            let total = &total;
+            s.spawn(move || {
+                let mut part = Stats::new();
+                escapes.into_iter().for_each(|escape| part.add(escape));
+                total.lock().unwrap().add_up(&part, Duration::from_millis(5));
+            });
+        }
+    });
+    let mut total = total.into_inner().unwrap();
+    assert_eq!(total.threads.len(), 2);
+    total.add(None);
+    total.threads.clear();
+    assert_eq!(total, stats);
+    let report = stats.report(Some(Duration::from_millis(10)));
+    assert_eq!(report.elapsed_ms, Some(10.0));
+    let json = serde_json::to_value(&report).unwrap();
+    assert_eq!(json["iterations"]["max"], 200);
+    assert_eq!(json["elapsed-ms"], 10.0);
+}
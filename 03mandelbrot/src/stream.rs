@@ -0,0 +1,85 @@
+// Streaming PNGs: a still PNG is rendered ROWS rows at a time, and each band of rows is
+// compressed and written out while the next is rendered, instead of the whole picture
+// being rendered into memory and then compressed in one go at the end. However tall
+// the picture, only a few bands of it are ever in memory.
+//
+// Note:
+//
+// 1.) The png crate's StreamWriter takes the rows of a picture through io::Write, as
+//     many bytes at a time as we like, and compresses them as they come.
+// 2.) The writing happens on a thread of its own. The bands go to it through a
+//     sync_channel, which holds at most one band waiting, so a renderer faster than the
+//     compression waits for it rather than filling memory with bands.
+//
+use image::ImageResult;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::sync::mpsc::{sync_channel, SyncSender};
+use std::thread::JoinHandle;
+use crate::animation::png_error;
+use crate::metadata;
+
+/// How many rows of a streamed picture are rendered at a time.
+// 35.  Enough that every thread has rows, or a row of tiles, to be getting on with.
+pub const ROWS: usize = 64;
+
+/// A PNG being written a band of rows at a time.
+pub struct PngStream {
+    bands: SyncSender<Vec<u8>>,
+    writer: JoinHandle<ImageResult<()>>,
+}
+
+impl PngStream {
+    /// Start writing a PNG of `bounds`, with `channels` bytes per pixel (1 for gray, 2 for
+    /// 16-bit gray, 3 for RGB), to `filename`, rendered with `options`: see metadata.rs.
+    pub fn create(filename: &str, bounds: (usize, usize), channels: usize, options: &str)
+        -> ImageResult<PngStream>
+    {
+        let output = BufWriter::new(File::create(filename)?);
+        let mut encoder = png::Encoder::new(output, bounds.0 as u32, bounds.1 as u32);
+        encoder.set_color(if channels == 3 { png::ColorType::Rgb } else { png::ColorType::Grayscale });
+        encoder.set_depth(if channels == 2 { png::BitDepth::Sixteen } else { png::BitDepth::Eight });
+        metadata::add(&mut encoder, options).map_err(png_error)?;
+        let mut stream = encoder.write_header().map_err(png_error)?.into_stream_writer().map_err(png_error)?;
+        let (bands, received) = sync_channel::<Vec<u8>>(1);
+        let writer = std::thread::spawn(move || {
+            for band in received {
+                stream.write_all(&band)?;
+            }
+            stream.finish().map_err(png_error)
+        });
+        Ok(PngStream { bands, writer })
+    }
+
+    /// Write the next rows. If writing has failed, this does nothing: finish says why.
+    pub fn add(&self, rows: &[u8]) {
+        let _ = self.bands.send(rows.to_vec());
+    }
+
+    /// Wait for the last rows to be written, and finish the file. It must have had all the
+    /// rows of the picture.
+    pub fn finish(self) -> ImageResult<()> {
+        drop(self.bands);
+        self.writer.join().expect("the PNG writer panicked")
+    }
+}
+
+#[test]
+fn test_stream() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("stream.png");
+    let rows: Vec<u8> = (0..=255).collect();
+    let stream = PngStream::create(file.to_str().unwrap(), (16, 8), 2, "limit = 255\n").unwrap();
+    for band in rows.chunks(3 * 16 * 2) {
+        stream.add(band);
+    }
+    stream.finish().unwrap();
+    let counts = image::open(&file).unwrap().into_luma16().into_raw();
+    assert_eq!(counts, rows.chunks(2).map(|c| u16::from_be_bytes([c[0], c[1]])).collect::<Vec<_>>());
+    assert!(metadata::options(&file).unwrap().ends_with("\nlimit = 255\n"));
+
+    // too few rows
+    let stream = PngStream::create(file.to_str().unwrap(), (16, 8), 1, "").unwrap();
+    stream.add(&rows[.. 16 * 3]);
+    assert!(stream.finish().is_err());
+}
@@ -0,0 +1,157 @@
+// The tile scheduler behind `CONCURRENT` = tiles. The image is cut into small squares,
+// all put on one queue; each thread takes the next square off it, renders it into a
+// buffer of its own, and copies that into the image. A thread that got cheap squares
+// far from the set just comes back for more, so the expensive squares near it end up
+// shared among everybody instead of holding up whoever got them.
+//
+// At the end every thread says how many squares it rendered and how much of the time
+// it spent rendering them, rather than waiting for the queue or the image.
+//
+// How many threads there are, for the tiles and for rayon's rows alike, is up to
+// `--threads N`, which comes in the render's options: see threads() below.
+//
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A rectangle of the image, in pixels: a square, except along the right and bottom
+/// edges, where it's cut short.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tile {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+/// What one thread did.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Utilization {
+    /// How many tiles it rendered.
+    pub tiles: usize,
+    /// How long it spent rendering them.
+    pub busy: Duration,
+}
+
+/// How many threads render: `given`, --threads N, or else the machine's, one per core,
+/// unless the output has to be the same every time; then 8, as many as the book's bands.
+// 4.  --threads N comes with the rest of a render's options, RenderOptions::threads,
+//     rather than being set for the whole process: a worker's and a job's are their own.
+pub fn threads(given: Option<usize>) -> usize {
+    match given {
+        Some(n) => n,
+        None if rustfun_common::deterministic::is_on() => 8,
+        None => std::thread::available_parallelism().map_or(8, |n| n.get()),
+    }
+}
+
+/// A rayon pool of `threads` threads, for the fast renderer's rows.
+// 5.  rayon's global pool has as many threads as cores, and can't be changed once it's
+//     started, so the rows go to a pool of our own instead, started the first time a
+//     number of threads is asked for and kept for the next render that wants as many.
+pub fn pool(threads: usize) -> Arc<ThreadPool> {
+    static POOLS: Mutex<Vec<Arc<ThreadPool>>> = Mutex::new(Vec::new());
+    let mut pools = POOLS.lock().unwrap();
+    if let Some(pool) = pools.iter().find(|pool| pool.current_num_threads() == threads) {
+        return pool.clone();
+    }
+    let pool = Arc::new(ThreadPoolBuilder::new().num_threads(threads).build()
+                            .expect("rayon couldn't start its threads"));
+    pools.push(pool.clone());
+    pool
+}
+
+/// The tiles of `size` by `size` pixels that cover an image of `bounds`, row by row.
+pub fn tiles(bounds: (usize, usize), size: usize) -> VecDeque<Tile> {
+    let mut tiles = VecDeque::new();
+    for y in (0..bounds.1).step_by(size) {
+        for x in (0..bounds.0).step_by(size) {
+            tiles.push_back(Tile { x, y, width: size.min(bounds.0 - x), height: size.min(bounds.1 - y) });
+        }
+    }
+    tiles
+}
+
+/// Fill `pixels`, an image of `bounds` with `channels` bytes per pixel, on `threads`
+/// threads that take tiles of `size` pixels from a shared queue. `paint` renders one
+/// tile into a buffer just big enough for it. Returns what each thread did.
+// 1.  The queue and the image are each behind a Mutex. A thread holds the queue's
+//     lock only long enough to pop a tile, and the image's only to copy one in, so
+//     they spend nearly all their time in paint, where nothing is shared.
+// 2.  std::thread::scope, like rayon's for_each, waits for its threads before it
+//     returns, so they can borrow pixels, paint and the queue from this function.
+pub fn render<F>(pixels: &mut [u8],
+                 bounds: (usize, usize),
+                 channels: usize,
+                 size: usize,
+                 threads: usize,
+                 paint: F) -> Vec<Utilization>
+    where F: Fn(Tile, &mut [u8]) + Sync
+{
+    assert!(pixels.len() == bounds.0 * bounds.1 * channels);
+    let queue = Mutex::new(tiles(bounds, size));
+    let image = Mutex::new(pixels);
+
+    std::thread::scope(|scope| {
+        // named, so that --stats can tell them apart from the fast renderer's
+        let workers: Vec<_> = (0..threads).map(|i| std::thread::Builder::new().name(format!("tiles {}", i))
+                                                   .spawn_scoped(scope, || {
+            let mut done = Utilization::default();
+            let mut buffer = Vec::new();
+            // 3. Not `while let Some(tile) = queue.lock()...`: the guard, a temporary
+            //    of the condition, would live until the end of the loop body, and the
+            //    queue would stay locked while the tile renders. A let statement drops
+            //    its temporaries at the semicolon.
+            loop {
+                let Some(tile) = queue.lock().unwrap().pop_front() else {
+                    break;
+                };
+                let start = Instant::now();
+                let row_len = tile.width * channels;
+                buffer.clear();
+                buffer.resize(row_len * tile.height, 0);
+                paint(tile, &mut buffer);
+                done.busy += start.elapsed();
+                done.tiles += 1;
+
+                let mut image = image.lock().unwrap();
+                for (row, line) in buffer.chunks(row_len).enumerate() {
+                    let at = ((tile.y + row) * bounds.0 + tile.x) * channels;
+                    image[at .. at + row_len].copy_from_slice(line);
+                }
+            }
+            done
+        }).expect("can't start a thread")).collect();
+        workers.into_iter().map(|worker| worker.join().unwrap()).collect()
+    })
+}
+
+#[test]
+fn test_tiles() {
+    let all = tiles((5, 3), 2);
+    assert_eq!(all.len(), 6);
+    assert_eq!(all[0], Tile { x: 0, y: 0, width: 2, height: 2 });
+    assert_eq!(all[2], Tile { x: 4, y: 0, width: 1, height: 2 });
+    assert_eq!(all[5], Tile { x: 4, y: 2, width: 1, height: 1 });
+    assert_eq!(all.iter().map(|t| t.width * t.height).sum::<usize>(), 15);
+    assert_eq!(tiles((4, 4), 10), [Tile { x: 0, y: 0, width: 4, height: 4 }]);
+}
+
+#[test]
+fn test_render() {
+    // each pixel painted with its own coordinates, two bytes of it, lands where it belongs
+    let bounds = (7, 5);
+    let mut pixels = vec![0; 7 * 5 * 2];
+    let done = render(&mut pixels, bounds, 2, 3, 4, |tile, buffer| {
+        for (i, pixel) in buffer.chunks_mut(2).enumerate() {
+            pixel[0] = (tile.x + i % tile.width) as u8;
+            pixel[1] = (tile.y + i / tile.width) as u8;
+        }
+    });
+    for (i, pixel) in pixels.chunks(2).enumerate() {
+        assert_eq!(pixel, [(i % 7) as u8, (i / 7) as u8]);
+    }
+    assert_eq!(done.len(), 4);
+    assert_eq!(done.iter().map(|d| d.tiles).sum::<usize>(), 6);
+}
@@ -0,0 +1,186 @@
+// A window on the set, behind the `viewer` feature: `mandelbrot view` opens one, renders
+// the view into it, and lets you look around, dragging with the mouse to pan and turning
+// the scroll wheel to zoom in and out, about the point under the mouse.
+//
+//   $ cargo run --release --features viewer -- view
+//   $ cargo run --release --features viewer -- view --palette fire --julia -0.8,0.156
+//   --upper-left -0.93203125,0.3265625 --lower-right -0.49453125,0.0015625 --julia -0.8,0.156 --palette fire
+//
+// Each view it settles on is printed as the options that render it again from the command
+// line, its corners and the options the window was opened with that change the picture
+// (--julia, --palette, --coloring, --precision, --power, --limit, --escape-radius): the
+// line above put in front of `mandelbrot julia.png 1000x750` makes a picture of what the
+// window showed.
+//
+// Note:
+//
+// 1.) minifb gives a window and a buffer of u32 pixels, 0x00RRGGBB, and is polled for the
+//     mouse rather than calling back, so the render loop stays ours: look at the mouse,
+//     render a pass, show it, and round again.
+// 2.) Progressive rendering: a view is rendered first at an eighth of the window's width,
+//     each pixel drawn as an 8 by 8 block, then at a quarter, a half, and whole. A drag
+//     or a scroll in between starts the new view from an eighth again, so the window
+//     keeps up with the mouse however long a whole view takes.
+// 3.) The corners are Dd, as on the command line, and each pass goes through render_view,
+//     so the window zooms as deep as the renderer does, by perturbation past f64. Dd's
+//     Display writes them with all their digits.
+// 4.) It prints the corners, not a center and a zoom: the command line takes a view as
+//     its corners, and --zoom is already each frame's step in an animation. A center and
+//     a zoom would need the window's shape as well to come back to the same view; the
+//     corners carry it.
+//
+use crate::{render_view, Backend, Precision, Sides};
+use mandelbrot::double_double::Dd;
+use mandelbrot::escape::{Fractal, Settings};
+use mandelbrot::palette::Palette;
+use mandelbrot::render::{Coloring, RenderOptions};
+use mandelbrot::pixel_to_point;
+use minifb::{Key, MouseButton, MouseMode, Window, WindowOptions};
+use num::Complex;
+use rustfun_error::{Error, Result};
+use std::io;
+#[cfg(test)]
+use mandelbrot::double_double::Real;
+
+/// What the window shows: the points at its corners.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct View {
+    pub upper_left: Complex<Dd>,
+    pub lower_right: Complex<Dd>,
+}
+
+/// How much closer one notch of the scroll wheel zooms in.
+const ZOOM: f64 = 1.25;
+
+/// The widths of a view's passes, as fractions of the window's: see 2.) above.
+const PASSES: [usize; 4] = [8, 4, 2, 1];
+
+impl View {
+    /// The view dragged `by` pixels, right and down, in a window of `bounds` pixels: what
+    /// was under the mouse stays under it.
+    pub fn pan(self, bounds: (usize, usize), by: (f64, f64)) -> View {
+        let width = self.lower_right.re - self.upper_left.re;
+        let height = self.upper_left.im - self.lower_right.im;
+        let shift = Complex { re: -(width * Dd::from(by.0) / Dd::from(bounds.0 as f64)),
+                              im: height * Dd::from(by.1) / Dd::from(bounds.1 as f64) };
+        View { upper_left: self.upper_left + shift, lower_right: self.lower_right + shift }
+    }
+
+    /// The view `factor` times closer in, about the point at `pixel` of a window of `bounds`
+    /// pixels, which stays where it is: a factor under 1 zooms out.
+    pub fn zoom(self, bounds: (usize, usize), pixel: (usize, usize), factor: f64) -> View {
+        let at = pixel_to_point(bounds, pixel, self.upper_left, self.lower_right);
+        let factor = Dd::from(factor);
+        let toward = |corner: Complex<Dd>| Complex { re: at.re + (corner.re - at.re) / factor,
+                                                     im: at.im + (corner.im - at.im) / factor };
+        View { upper_left: toward(self.upper_left), lower_right: toward(self.lower_right) }
+    }
+
+    /// The corners of this view, as the options that render it from the command line.
+    pub fn options(&self) -> String {
+        format!("--upper-left {},{} --lower-right {},{}",
+                self.upper_left.re, self.upper_left.im, self.lower_right.re, self.lower_right.im)
+    }
+}
+
+/// Open a window of `bounds` pixels on `view` of `fractal`, and render into it, iterating
+/// as `settings` say, in `precision` until it's closed, or Escape is pressed. Each view is printed with `given`,
+/// the other options that render it again.
+#[allow(clippy::too_many_arguments)]
+pub fn run(mut view: View, bounds: (usize, usize), fractal: Fractal, palette: &Palette, coloring: Coloring,
+           settings: Settings, precision: Precision, given: &str) -> Result<()>
+{
+    let print = |view: View| match given {
+        "" => println!("{}", view.options()),
+        _ => println!("{} {}", view.options(), given),
+    };
+    let mut window = Window::new("mandelbrot", bounds.0, bounds.1, WindowOptions::default())
+        .map_err(|e| Error::Io(io::Error::other(e.to_string())))?;
+    window.set_target_fps(60);
+    let mut shown = vec![0u32; bounds.0 * bounds.1];
+    let mut pass = Some(0);
+    let mut dragged_from: Option<(f32, f32)> = None;
+    print(view);
+
+    while window.is_open() && !window.is_key_down(Key::Escape) {
+        let mut moved = view;
+        if let Some((x, y)) = window.get_mouse_pos(MouseMode::Discard) {
+            if window.get_mouse_down(MouseButton::Left) {
+                if let Some((from_x, from_y)) = dragged_from {
+                    moved = moved.pan(bounds, ((x - from_x) as f64, (y - from_y) as f64));
+                }
+                dragged_from = Some((x, y));
+            } else {
+                dragged_from = None;
+            }
+            // a notch is 1 on some systems, more on others: only its direction counts
+            if let Some((_, notches)) = window.get_scroll_wheel().filter(|&(_, y)| y != 0.0) {
+                let factor = if notches > 0.0 { ZOOM } else { 1.0 / ZOOM };
+                moved = moved.zoom(bounds, (x as usize, y as usize), factor);
+            }
+        }
+        if moved != view {
+            view = moved;
+            pass = Some(0);
+        }
+
+        let Some(this) = pass else {
+            window.update();
+            continue;
+        };
+        draw_pass(&mut shown, bounds, view, PASSES[this], fractal, palette, coloring, settings, precision);
+        window.update_with_buffer(&shown, bounds.0, bounds.1)
+            .map_err(|e| Error::Io(io::Error::other(e.to_string())))?;
+        pass = (this + 1 < PASSES.len()).then_some(this + 1);
+        if pass.is_none() {
+            print(view);
+        }
+    }
+    Ok(())
+}
+
+/// Render `view` into `shown`, a window of `bounds` pixels, at a `step`th of its width
+/// and height, each pixel rendered drawn as a `step` by `step` block.
+#[allow(clippy::too_many_arguments)]
+fn draw_pass(shown: &mut [u32], bounds: (usize, usize), view: View, step: usize,
+             fractal: Fractal, palette: &Palette, coloring: Coloring, settings: Settings, precision: Precision) {
+    let small = (bounds.0.div_ceil(step), bounds.1.div_ceil(step));
+    // the blocks on the right and at the bottom can stick out past the window
+    let lower_right = pixel_to_point(bounds, (small.0 * step, small.1 * step), view.upper_left, view.lower_right);
+    let channels = palette.channels();
+    let mut pixels = vec![0; small.0 * small.1 * channels];
+    let options = RenderOptions { fractal, palette: palette.clone(), coloring, settings, threads: None };
+    render_view("fast", &mut pixels, small, view.upper_left, lower_right,
+                &options, 32, None, Sides::default(), false, precision, Backend::Cpu);
+    for (row, line) in shown.chunks_mut(bounds.0).enumerate() {
+        for (column, pixel) in line.iter_mut().enumerate() {
+            let at = ((row / step) * small.0 + column / step) * channels;
+            let color = &pixels[at .. at + channels];
+            *pixel = match channels {
+                1 => u32::from_be_bytes([0, color[0], color[0], color[0]]),
+                _ => u32::from_be_bytes([0, color[0], color[1], color[2]]),
+            };
+        }
+    }
+}
+
+#[test]
+fn test_view() {
+    let corner = |s: &str| mandelbrot::double_double::parse_complex(s).unwrap();
+    // to Dd's 32 digits: a tenth isn't exact in binary
+    let near = |a: View, b: View| {
+        let d = [a.upper_left - b.upper_left, a.lower_right - b.lower_right];
+        assert!(d.iter().all(|d| d.re.to_f64().abs() < 1e-30 && d.im.to_f64().abs() < 1e-30), "{:?} {:?}", a, b);
+    };
+    let view = View { upper_left: corner("-2,1"), lower_right: corner("1,-1") };
+    let bounds = (300, 200);
+    // a drag of 30 pixels right and 20 down shows what was a tenth of the view up and left
+    near(view.pan(bounds, (30.0, 20.0)), View { upper_left: corner("-2.3,1.2"), lower_right: corner("0.7,-0.8") });
+    near(view.pan(bounds, (30.0, 20.0)).pan(bounds, (-30.0, -20.0)), view);
+    // the point under the mouse stays put, and the view is half as wide
+    let zoomed = view.zoom(bounds, (100, 50), 2.0);
+    assert_eq!(pixel_to_point(bounds, (100, 50), zoomed.upper_left, zoomed.lower_right), corner("-1,0.5"));
+    near(zoomed, View { upper_left: corner("-1.5,0.75"), lower_right: corner("0,-0.25") });
+    near(zoomed.zoom(bounds, (100, 50), 0.5), view);
+    assert_eq!(view.options(), "--upper-left -2,1 --lower-right 1,-1");
+}
@@ -0,0 +1,883 @@
+// Render small images through the real binary, with the single-threaded renderer and
+// the two parallel ones, and check the usage error.
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+
+fn mandelbrot() -> Command {
+    Command::cargo_bin("mandelbrot").unwrap()
+}
+
+fn render(mode: &str) -> Vec<u8> {
+    render_with(&[], mode)
+}
+
+fn render_with(options: &[&str], mode: &str) -> Vec<u8> {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("mandel.png");
+    mandelbrot()
+        .args(options)
+        .args([file.to_str().unwrap(), "40x30", "-1.20,0.35", "-1,0.20", mode])
+        .assert()
+        .success()
+        .stdout("");
+    picture(&file)
+}
+
+// The PNG at `path` without its text chunks, which say what options it was rendered with,
+// so that two renders of the same picture compare equal however they were asked for.
+fn picture<P: AsRef<std::path::Path>>(path: P) -> Vec<u8> {
+    let png = fs::read(path).unwrap();
+    let (mut picture, mut at) = (png[..8].to_vec(), 8);
+    while at < png.len() {
+        let len = u32::from_be_bytes(png[at .. at + 4].try_into().unwrap()) as usize;
+        let chunk = &png[at .. at + 12 + len];
+        if &chunk[4..8] != b"tEXt" && &chunk[4..8] != b"iTXt" {
+            picture.extend(chunk);
+        }
+        at += chunk.len();
+    }
+    picture
+}
+
+#[test]
+fn writes_a_png() {
+    let png = render("slow");
+    assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+}
+
+#[test]
+fn fast_and_slow_renderers_agree() {
+    assert_eq!(render("fast"), render("slow"));
+}
+
+#[test]
+fn tiles_agree_with_the_other_renderers() {
+    assert_eq!(render("tiles"), render("slow"));
+    assert_eq!(render_with(&["--tile-size", "7", "--palette", "fire"], "tiles"),
+               render_with(&["--palette", "fire"], "fast"));
+}
+
+#[test]
+fn reports_what_the_tile_threads_did() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("mandel.png");
+    let args = [file.to_str().unwrap(), "40x30", "-1.20,0.35", "-1,0.20", "tiles"];
+    mandelbrot().args(["--deterministic", "--tile-size", "8"]).args(args).assert()
+        .success()
+        .stderr("20 tiles on 8 threads\n");
+    mandelbrot().args(args).assert()
+        .success()
+        .stderr(predicate::str::is_match("^thread 0: [0-9]+ tiles, busy [0-9]+% of ").unwrap());
+    mandelbrot().args(["--tile-size", "0"]).args(args).assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("mandelbrot: --tile-size: can't parse \"0\"\n"));
+
+    // as many threads as told, but no more than there are rows
+    mandelbrot().args(["--deterministic", "--tile-size", "8", "--threads", "3"]).args(args).assert()
+        .success()
+        .stderr("20 tiles on 3 threads\n");
+    assert_eq!(render_with(&["--threads", "2"], "fast"), render("fast"));
+    mandelbrot().args(["--threads", "0"]).args(args).assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("mandelbrot: --threads: can't parse \"0\"\n"));
+    mandelbrot().args(["--threads", "31"]).args(args).assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("mandelbrot: --threads 31: more threads than the picture's 30 rows\n"));
+}
+
+// 4e-20 across, around i: f64 can't tell the rows apart, so this renders in double-double
+#[test]
+fn renders_deep_zooms() {
+    let deep_with = |options: &[&str], mode: &str| {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("deep.png");
+        mandelbrot()
+            .args(options)
+            .args([file.to_str().unwrap(), "40x30", "-0.00000000000000000002,1.000000000000000000015",
+                   "0.00000000000000000002,0.999999999999999999985", mode])
+            .assert()
+            .success();
+        image::open(&file).unwrap().into_luma8().into_raw()
+    };
+    let deep = |mode: &str| deep_with(&[], mode);
+    let slow = deep("slow");
+    assert_ne!(slow[..40], slow[40 * 29..]);
+    assert_eq!(deep("fast"), slow);
+    assert_eq!(deep("tiles"), slow);
+    // by perturbation, the default, or iterating every pixel in double-double, the
+    // picture is the same, give or take a pixel on the edge of the set
+    let direct = deep_with(&["--no-perturbation"], "slow");
+    assert!(direct.iter().zip(&slow).filter(|(a, b)| a != b).count() <= 12);
+}
+
+#[test]
+fn previews_in_single_precision() {
+    // much the same picture as in f64, a pixel here and there on the edge apart, with the
+    // fast renderer or the tiles one
+    let double = image::load_from_memory(&render("fast")).unwrap().into_luma8().into_raw();
+    for mode in ["fast", "tiles"] {
+        let single = image::load_from_memory(&render_with(&["--precision", "f32"], mode)).unwrap().into_luma8();
+        let differ = single.iter().zip(&double).filter(|(a, b)| a != b).count();
+        assert!(differ <= 40, "{} pixels differ", differ);
+    }
+    // but zoomed in, f32 can't tell the pixels apart
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("zoomed.png");
+    mandelbrot().args(["--precision", "f32"]).arg(&file).args(["40x30", "-0.7436,0.1318", "-0.743599,0.131799"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("too close together for f32"));
+    mandelbrot().args(["--precision", "f16", "mandel.png", "40x30", "-1.20,0.35", "-1,0.20"]).assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("mandelbrot: --precision: can't parse \"f16\"\n"));
+}
+
+#[test]
+fn renders_julia_sets() {
+    let julia = render_with(&["--julia", "-0.8,0.156"], "slow");
+    assert_eq!(&julia[..8], b"\x89PNG\r\n\x1a\n");
+    assert_ne!(julia, render("slow"));
+    assert_eq!(render_with(&["--julia=-0.8,0.156"], "fast"), julia);
+}
+
+#[test]
+fn renders_multibrot_sets() {
+    // z^3 + c, around 0: the same turned upside down, since -c's orbit is c's negated.
+    // The pixels' points go from -1.5 to 1.5 both ways, 0.075 and 0.1 apart.
+    let render_power = |power: &str| {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("multibrot.png");
+        mandelbrot().args(["--power", power]).arg(&file).args(["41x31", "-1.5,1.5", "1.575,-1.6"])
+            .assert().success();
+        image::open(&file).unwrap().into_luma8()
+    };
+    let cubed = render_power("3");
+    assert_eq!(cubed.get_pixel(20, 15).0, [0]);
+    assert_ne!(cubed.get_pixel(0, 0).0, [0]);
+    let turned = image::imageops::rotate180(&cubed);
+    let differ = cubed.pixels().zip(turned.pixels()).filter(|(a, b)| a != b).count();
+    assert!(differ < 41 * 31 / 20, "{} pixels differ", differ);
+    assert_ne!(cubed, image::load_from_memory(&render("fast")).unwrap().into_luma8());
+    // a fractional power, and 2, which is the Mandelbrot set
+    assert_ne!(render_power("2.5"), cubed);
+    assert_eq!(render_with(&["--power", "2"], "fast"), render("fast"));
+    mandelbrot().args(["--power", "1.5", "mandel.png", "40x30", "-1.20,0.35", "-1,0.20"]).assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("mandelbrot: --power: can't parse \"1.5\"\n"));
+    // as far as a u32 goes
+    mandelbrot().args(["--power", "4294967295", "mandel.png", "40x30", "-1.20,0.35", "-1,0.20", "--print-config"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("power = 4294967295.0\n"));
+    mandelbrot().args(["--power", "4294967296", "mandel.png", "40x30", "-1.20,0.35", "-1,0.20"]).assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("mandelbrot: --power: can't parse \"4294967296\"\n"));
+}
+
+// white a pixel or more away from the set, black on its edge, and gray in between
+#[test]
+fn colors_by_distance() {
+    let distance = render_with(&["--coloring", "distance"], "slow");
+    let gray = image::load_from_memory(&distance).unwrap().into_luma8().into_raw();
+    assert!(gray.contains(&255) && gray.contains(&0) && gray.iter().any(|&g| g > 0 && g < 255));
+    assert_ne!(distance, render("slow"));
+    assert_eq!(render_with(&["--coloring=distance"], "fast"), distance);
+    assert_eq!(render_with(&["--coloring", "distance"], "tiles"), distance);
+    assert_eq!(render_with(&["--coloring", "smooth"], "fast"), render_with(&["--smooth"], "fast"));
+    assert_eq!(render_with(&["--coloring", "bands"], "fast"), render("fast"));
+    for (options, error) in [(&["--coloring", "distance", "--smooth"][..], "--smooth: not with --coloring distance"),
+                             (&["--coloring", "distance", "--palette", "gray16"],
+                              "--coloring distance: gray16 holds counts, not distances"),
+                             (&["--coloring", "sharp"], "--coloring: can't parse \"sharp\"")] {
+        mandelbrot().args(options).args(["mandel.png", "40x30", "-1.20,0.35", "-1,0.20"]).assert()
+            .code(1)
+            .stderr(predicate::str::starts_with(format!("mandelbrot: {}\n", error)));
+    }
+}
+
+// byte 25 of a PNG, in its IHDR chunk, is the color type: 0 for gray, 2 for RGB
+#[test]
+fn renders_with_a_palette() {
+    assert_eq!(render("slow")[25], 0);
+    let fire = render_with(&["--palette", "fire"], "slow");
+    assert_eq!(fire[25], 2);
+    assert_eq!(render_with(&["--palette", "fire"], "fast"), fire);
+    assert_ne!(render_with(&["--palette", "rainbow"], "slow"), fire);
+    assert_eq!(render_with(&["--palette=gray"], "fast"), render("slow"));
+}
+
+// fire's colors, from a file, are fire
+#[test]
+fn renders_with_a_palette_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let (map, csv) = (dir.path().join("fire.map"), dir.path().join("fire.csv"));
+    fs::write(&map, "0 0 0    black\n128 0 0\n255 64 0\n255 200 0\n255 255 255  white\n").unwrap();
+    fs::write(&csv, "# red,green,blue\n0,0,0\n128,0,0\n255,64,0\n255,200,0\n255,255,255\n").unwrap();
+    let fire = render_with(&["--palette", "fire"], "fast");
+    assert_eq!(render_with(&["--palette-file", map.to_str().unwrap()], "slow"), fire);
+    assert_eq!(render_with(&["--palette-file", csv.to_str().unwrap(), "--tile-size", "7"], "tiles"), fire);
+
+    mandelbrot().args(["--palette", "fire", "--palette-file"]).arg(&map)
+        .args(["mandel.png", "40x30", "-1.20,0.35", "-1,0.20"]).assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("mandelbrot: --palette-file: not with --palette fire\n"));
+    // but a typed one replaces a config file's other
+    let (config, file) = (dir.path().join("render.toml"), dir.path().join("mandel.png"));
+    let saved = mandelbrot().arg(&file).args(["40x30", "-1.20,0.35", "-1,0.20", "--print-config"]).assert()
+        .success()
+        .get_output().stdout.clone();
+    assert!(String::from_utf8_lossy(&saved).contains("palette = \"gray\"\n"));
+    fs::write(&config, saved).unwrap();
+    mandelbrot().arg("--config").arg(&config).arg("--palette-file").arg(&map).assert().success();
+    assert_eq!(picture(&file), fire);
+    let saved = mandelbrot().arg("--config").arg(&config).arg("--palette-file").arg(&map).arg("--print-config")
+        .assert()
+        .success()
+        .get_output().stdout.clone();
+    fs::write(&config, saved).unwrap();
+    mandelbrot().arg("--config").arg(&config).args(["--palette", "ocean", "--print-config"]).assert()
+        .success()
+        .stdout(predicate::str::contains("palette = \"ocean\"\n").and(predicate::str::contains("palette-file").not()));
+    fs::write(&csv, "0,0,0\n128,0\n").unwrap();
+    mandelbrot().arg("--palette-file").arg(&csv).args(["mandel.png", "40x30", "-1.20,0.35", "-1,0.20"]).assert()
+        .code(1)
+        .stderr(format!("mandelbrot: {}: line 2: not a color, three numbers from 0 to 255\n", csv.display()));
+}
+
+// gray16 keeps the counts that gray turns into 255 - count
+#[test]
+fn writes_the_counts_in_16_bit_gray() {
+    let png = render_with(&["--palette", "gray16"], "fast");
+    assert_eq!((png[24], png[25]), (16, 0));
+    let counts = image::load_from_memory(&png).unwrap().into_luma16().into_raw();
+    let gray = image::load_from_memory(&render("fast")).unwrap().into_luma8().into_raw();
+    for (&count, &gray) in counts.iter().zip(&gray) {
+        if count == u16::MAX {
+            assert_eq!(gray, 0);
+        } else {
+            assert_eq!(gray as u16, 255 - count);
+        }
+    }
+    assert!(counts.contains(&u16::MAX) && counts.iter().any(|&c| c > 0 && c < 255));
+    mandelbrot().args(["--palette", "gray16", "mandel.jpg", "40x30", "-1.20,0.35", "-1,0.20", "fast"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("mandelbrot: mandel.jpg: 16-bit gray needs a PNG, a TIFF or a PPM\n"));
+}
+
+#[test]
+fn renders_smooth_colors() {
+    let smooth = render_with(&["--smooth", "--palette", "ocean"], "slow");
+    assert_ne!(smooth, render_with(&["--palette", "ocean"], "slow"));
+    assert_eq!(render_with(&["--palette", "ocean", "--smooth"], "fast"), smooth);
+}
+
+// looking for cycles only saves time: the picture is the same without
+#[test]
+fn period_check_changes_nothing() {
+    assert_eq!(render_with(&["--no-period-check"], "fast"), render("fast"));
+    assert_eq!(render_with(&["--no-period-check", "--julia", "-0.8,0.156", "--smooth"], "fast"),
+               render_with(&["--julia", "-0.8,0.156", "--smooth"], "fast"));
+}
+
+// rendered in parts, the bands of a streamed PNG or of a checkpoint, or tiles to stitch,
+// a picture is anti-aliased just as it is rendered whole, along the seams too
+#[test]
+fn antialiases_across_the_seams() {
+    let dir = tempfile::tempdir().unwrap();
+    let view = ["200x200", "-1.20,0.35", "-1,0.20"];
+    let whole = mandelbrot().args(["--antialias", "3", "-"]).args(view).assert().success().get_output().stdout.clone();
+    let whole = &whole[b"P5\n200 200\n255\n".len() ..];
+    let gray = |name: &str| image::open(dir.path().join(name)).unwrap().into_luma8().into_raw();
+    let file = |name: &str| dir.path().join(name).to_str().unwrap().to_string();
+    mandelbrot().args(["--antialias", "3", &file("streamed.png")]).args(view).assert().success();
+    assert_eq!(gray("streamed.png"), whole);
+    mandelbrot().args(["--antialias", "3", "--checkpoint", &file("mandel.ckpt"), &file("checkpointed.png")]).args(view)
+        .assert().success();
+    assert_eq!(gray("checkpointed.png"), whole);
+    mandelbrot().args(["--antialias", "3", "--split", "64", &file("split.png")]).args(view).assert().success();
+    mandelbrot().args(["stitch", &file("split.tiles.toml")]).assert().success();
+    assert_eq!(gray("split.png"), whole);
+}
+
+#[test]
+fn antialiases_the_edges() {
+    let plain = image::load_from_memory(&render("slow")).unwrap().into_luma8().into_raw();
+    let png = render_with(&["--antialias", "3"], "slow");
+    let smoothed = image::load_from_memory(&png).unwrap().into_luma8().into_raw();
+    // only some of the pixels change, and every renderer changes the same ones
+    let changed = plain.iter().zip(&smoothed).filter(|(a, b)| a != b).count();
+    assert!(changed > 0 && changed < plain.len() / 2, "{} changed", changed);
+    assert_eq!(render_with(&["--antialias", "3"], "fast"), png);
+    assert_eq!(render_with(&["--antialias", "3", "--tile-size", "7"], "tiles"), png);
+    assert_eq!(render_with(&["--antialias", "1"], "slow"), render("slow"));
+    mandelbrot().args(["--antialias", "0", "mandel.png", "40x30", "-1.20,0.35", "-1,0.20", "fast"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("mandelbrot: --antialias: can't parse \"0\"\n"));
+    // nor more than 16 across, whose points would outnumber what an image's size can count
+    for n in ["17", "4294967296"] {
+        mandelbrot().args(["--antialias", n, "mandel.png", "40x30", "-1.20,0.35", "-1,0.20", "fast"])
+            .assert()
+            .code(1)
+            .stderr(predicate::str::starts_with(format!("mandelbrot: --antialias: can't parse \"{}\"\n", n)));
+    }
+}
+
+#[test]
+fn writes_zoom_animations() {
+    let dir = tempfile::tempdir().unwrap();
+    let animate = |name: &str, options: &[&str]| {
+        let file = dir.path().join(name);
+        mandelbrot().args(["--frames", "4", "--zoom", "2"]).args(options)
+            .args([file.to_str().unwrap(), "40x30", "-1.20,0.35", "-1,0.20", "fast"])
+            .assert()
+            .success();
+        fs::read(&file).unwrap()
+    };
+    // an APNG's first frame is the plain picture, and the image crate reads it as one
+    let apng = animate("zoom.png", &[]);
+    assert_eq!(&apng[37..45], b"acTL\0\0\0\x04");
+    assert_eq!(image::load_from_memory(&apng).unwrap().into_luma8(),
+               image::load_from_memory(&render("fast")).unwrap().into_luma8());
+    let gif = animate("zoom.gif", &["--palette", "fire"]);
+    assert_eq!(&gif[..6], b"GIF89a");
+    mandelbrot().args(["--frames", "4", "zoom.bmp", "40x30", "-1.20,0.35", "-1,0.20", "fast"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("mandelbrot: zoom.bmp: only a GIF or a PNG can hold more than one frame\n"));
+}
+
+// the extension picks the format, unless --format says otherwise
+#[test]
+fn writes_other_formats() {
+    let dir = tempfile::tempdir().unwrap();
+    let write = |name: &str, options: &[&str]| {
+        let file = dir.path().join(name);
+        mandelbrot().args(options)
+            .args([file.to_str().unwrap(), "40x30", "-1.20,0.35", "-1,0.20", "fast"])
+            .assert()
+            .success();
+        fs::read(&file).unwrap()
+    };
+    let png = image::load_from_memory(&render("fast")).unwrap().into_luma8();
+    let bmp = write("mandel.bmp", &[]);
+    assert_eq!(&bmp[..2], b"BM");
+    assert_eq!(image::load_from_memory(&bmp).unwrap().into_luma8(), png);
+    assert_eq!(&write("mandel.TIF", &[])[..4], b"II*\0");
+    assert_eq!(&write("mandel.jpg", &["--palette", "fire"])[..3], b"\xff\xd8\xff");
+    assert_eq!(&write("mandel.gif", &[])[..6], b"GIF89a");
+    assert_eq!(&write("mandel.out", &["--format", "bmp"])[..2], b"BM");
+    // a PGM is a header and the gray bytes; a PPM, three bytes a pixel
+    let pgm = write("mandel.pgm", &[]);
+    assert_eq!(pgm, [&b"P5\n40 30\n255\n"[..], &png.into_raw()].concat());
+    assert_eq!(write("mandel.pnm", &["--palette", "fire"]).len(), 13 + 40 * 30 * 3);
+    mandelbrot().args(["mandel.webp", "40x30", "-1.20,0.35", "-1,0.20", "fast"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("mandelbrot: mandel.webp: can't tell the format; "));
+    mandelbrot().args(["--format", "webp", "mandel.png", "40x30", "-1.20,0.35", "-1,0.20", "fast"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("mandelbrot: --format: can't parse \"webp\"\n"));
+}
+
+#[test]
+fn pipes_a_ppm_to_stdout() {
+    let output = mandelbrot().args(["-", "40x30", "-1.20,0.35", "-1,0.20", "fast"]).assert().success();
+    let pgm = output.get_output().stdout.clone();
+    assert_eq!(&pgm[..13], b"P5\n40 30\n255\n");
+    assert_eq!(pgm[13..], image::load_from_memory(&render("fast")).unwrap().into_luma8().into_raw());
+    mandelbrot().args(["--format", "png", "-", "40x30", "-1.20,0.35", "-1,0.20", "fast"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("mandelbrot: only a PPM can go to stdout\n"));
+}
+
+#[test]
+fn rejects_an_unknown_palette() {
+    mandelbrot().args(["--palette", "pink", "mandel.png", "40x30", "-1.20,0.35", "-1,0.20", "fast"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("mandelbrot: --palette: can't parse \"pink\"\n"));
+}
+
+#[test]
+fn rejects_a_bad_julia_constant() {
+    mandelbrot().args(["--julia", "-0.8", "mandel.png", "40x30", "-1.20,0.35", "-1,0.20", "fast"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("mandelbrot: --julia: can't parse \"-0.8\"\n"));
+}
+
+#[test]
+fn picks_the_backend() {
+    assert_eq!(render_with(&["--backend", "cpu"], "fast"), render("fast"));
+    mandelbrot().args(["--backend", "tpu", "mandel.png", "40x30", "-1.20,0.35", "-1,0.20", "fast"]).assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("mandelbrot: --backend: can't parse \"tpu\"\n"));
+}
+
+#[cfg(not(feature = "gpu"))]
+#[test]
+fn says_when_built_without_the_gpu() {
+    mandelbrot().args(["--backend", "gpu", "mandel.png", "40x30", "-1.20,0.35", "-1,0.20", "fast"]).assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("mandelbrot: --backend gpu: built without it; build with --features gpu\n"));
+}
+
+// on the card, the picture is --precision f32's, but for the odd pixel on the edge;
+// only on a machine with a card wgpu can use:
+// `cargo test --features gpu -- --ignored`
+#[cfg(feature = "gpu")]
+#[test]
+#[ignore = "needs a graphics card"]
+fn renders_on_the_gpu() {
+    mandelbrot().args(["--backend", "gpu", "--coloring", "distance", "mandel.png", "40x30", "-1.20,0.35", "-1,0.20"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("mandelbrot: --backend gpu: not with --coloring distance\n"));
+    for options in [&["--smooth", "--palette", "fire"][..], &["--power", "3"],
+                    &["--smooth", "--limit", "1000", "--escape-radius", "4"]] {
+        let on_the_card = render_with(&[&["--backend", "gpu"], options].concat(), "fast");
+        let on_the_cpu = render_with(&[&["--precision", "f32"], options].concat(), "fast");
+        let (on_the_cpu, on_the_card) = (image::load_from_memory(&on_the_cpu).unwrap().into_rgb8(),
+                                         image::load_from_memory(&on_the_card).unwrap().into_rgb8());
+        let differ = on_the_cpu.pixels().zip(on_the_card.pixels()).filter(|(a, b)| a != b).count();
+        assert!(differ <= 4, "{:?}: {} pixels differ", options, differ);
+    }
+}
+
+#[test]
+fn takes_an_iteration_limit_and_an_escape_radius() {
+    // the defaults, given
+    assert_eq!(render_with(&["--limit", "255", "--escape-radius", "2"], "fast"), render("fast"));
+    assert_eq!(render_with(&["--coloring", "distance", "--limit", "1000"], "fast"),
+               render_with(&["--coloring", "distance"], "fast"));
+    // more iterations spread the counts over more of the palette: points that escape get
+    // lighter, and fewer are taken for inside the set
+    let gray = |png: Vec<u8>| image::load_from_memory(&png).unwrap().into_luma8().into_raw();
+    let (short, long) = (gray(render("fast")), gray(render_with(&["--limit", "2000"], "fast")));
+    assert!(short.iter().zip(&long).all(|(s, l)| s <= l || *l == 0 && *s == 0));
+    assert!(long.iter().filter(|&&g| g == 0).count() <= short.iter().filter(|&&g| g == 0).count());
+    assert_eq!(render_with(&["--limit", "2000"], "tiles"), render_with(&["--limit", "2000"], "slow"));
+    // a further radius takes a step or two more to get to, and bands move
+    assert_ne!(render_with(&["--escape-radius", "100"], "fast"), render("fast"));
+    for (option, value) in [("--limit", "0"), ("--escape-radius", "1.5"), ("--escape-radius", "inf")] {
+        mandelbrot().args([option, value, "mandel.png", "40x30", "-1.20,0.35", "-1,0.20"]).assert()
+            .code(1)
+            .stderr(predicate::str::starts_with(format!("mandelbrot: {}: can't parse \"{}\"\n", option, value)));
+    }
+}
+
+// the window itself needs a display, which the tests don't have
+#[cfg(not(feature = "viewer"))]
+#[test]
+fn says_when_built_without_the_viewer() {
+    mandelbrot().args(["view", "-2,1", "1,-1"]).assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("mandelbrot: view: built without the viewer; build with --features viewer\n"));
+}
+
+#[test]
+fn usage_with_wrong_argument_count() {
+    mandelbrot().args(["mandel.png", "40x30"]).assert()
+        .code(1)
+        .stderr("mandelbrot: missing UPPERLEFT\n\
+                 Usage: mandelbrot [OPTIONS] FILE PIXELS UPPERLEFT LOWERRIGHT [fast|tiles|slow]\n   \
+                 or: mandelbrot stitch INDEX [FILE]\n   \
+                 or: mandelbrot view [OPTIONS] [UPPERLEFT LOWERRIGHT]\n   \
+                 or: mandelbrot info FILE\n\
+                 (--help for more)\n");
+}
+
+#[test]
+fn rejects_bad_dimensions() {
+    mandelbrot().args(["mandel.png", "40by30", "-1.20,0.35", "-1,0.20", "fast"]).assert()
+        .code(1)
+        .stderr(predicate::str::starts_with(
+            "mandelbrot: PIXELS: expected two values separated by 'x', got \"40by30\"\n"));
+}
+
+#[test]
+fn rejects_bad_corners_and_extra_arguments() {
+    mandelbrot().args(["mandel.png", "40x30", "-1.20;0.35", "-1,0.20", "fast"]).assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("mandelbrot: UPPERLEFT: can't parse \"-1.20;0.35\"\n"));
+    mandelbrot().args(["mandel.png", "40x30", "-1.20,0.35", "-1,0.20", "fast", "now"]).assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("mandelbrot: unexpected argument \"now\"\n"));
+}
+
+#[test]
+fn prints_help() {
+    mandelbrot().arg("--help").assert()
+        .success()
+        .stdout(predicate::str::starts_with("Usage: mandelbrot [OPTIONS] FILE PIXELS"))
+        .stderr("");
+}
+
+#[test]
+fn takes_the_arguments_by_name() {
+    let dir = tempfile::tempdir().unwrap();
+    let (positional, named) = (dir.path().join("positional.png"), dir.path().join("named.png"));
+    mandelbrot().arg(&positional).args(["40x30", "-1.20,0.35", "-1,0.20", "fast"]).assert().success();
+    // in any order, with the renderer left to its default
+    mandelbrot().args(["--lower-right", "-1,0.20", "--size=40x30", "--upper-left", "-1.20,0.35", "--output"])
+        .arg(&named).assert().success();
+    assert_eq!(picture(&positional), picture(&named));
+    // or some by name and the rest in their places
+    mandelbrot().args(["--size", "40x30"]).arg(&named).args(["-1.20,0.35", "-1,0.20", "tiles"]).assert().success();
+    assert_eq!(picture(&positional), picture(&named));
+}
+
+#[test]
+fn rejects_empty_images_and_unknown_renderers() {
+    mandelbrot().args(["mandel.png", "0x30", "-1.20,0.35", "-1,0.20"]).assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("mandelbrot: 0x30: an image needs at least one pixel each way\n"));
+    mandelbrot().args(["mandel.png", "40x30", "-1.20,0.35", "-1,0.20", "quick"]).assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("mandelbrot: \"quick\": the renderer is fast, tiles or slow\n"));
+}
+
+#[test]
+fn says_what_a_png_was_rendered_with() {
+    let dir = tempfile::tempdir().unwrap();
+    let (file, again) = (dir.path().join("mandel.png"), dir.path().join("again.png"));
+    mandelbrot().args(["--palette", "fire", "--limit", "100"]).arg(&file).args(["40x30", "-1.20,0.35", "-1,0.20"])
+        .assert().success();
+    let info = mandelbrot().arg("info").arg(&file).assert().success().get_output().stdout.clone();
+    let info = String::from_utf8(info).unwrap();
+    assert!(info.starts_with(&format!("# mandelbrot {}\n", env!("CARGO_PKG_VERSION"))), "{}", info);
+    for line in ["upper-left = \"-1.20,0.35\"\n", "lower-right = \"-1,0.20\"\n", "limit = 100\n", "palette = \"fire\"\n"] {
+        assert!(info.contains(line), "{}", info);
+    }
+    // it renders the same picture again
+    let config = dir.path().join("render.toml");
+    fs::write(&config, &info).unwrap();
+    mandelbrot().arg("--config").arg(&config).arg("--output").arg(&again).assert().success();
+    assert_eq!(image::open(&again).unwrap().into_rgb8(), image::open(&file).unwrap().into_rgb8());
+
+    // an animation says so too, and so does a split picture once it's stitched
+    mandelbrot().args(["--frames", "2"]).arg(&again).args(["40x30", "-1.20,0.35", "-1,0.20"]).assert().success();
+    mandelbrot().arg("info").arg(&again).assert().success().stdout(predicate::str::contains("frames = 2\n"));
+    mandelbrot().args(["--split", "16"]).arg(&again).args(["40x30", "-1.20,0.35", "-1,0.20"]).assert().success();
+    mandelbrot().arg("stitch").arg(dir.path().join("again.tiles.toml")).assert().success();
+    mandelbrot().arg("info").arg(&again).assert().success().stdout(predicate::str::contains("split = 16\n"));
+
+    // but a picture from elsewhere doesn't
+    image::GrayImage::new(4, 3).save(&again).unwrap();
+    mandelbrot().arg("info").arg(&again).assert()
+        .code(1)
+        .stderr(format!("mandelbrot: {}: no render options in it; was it made by mandelbrot?\n", again.display()));
+    mandelbrot().arg("info").assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("mandelbrot: missing FILE\n"));
+}
+
+#[test]
+fn renders_a_config_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let (typed, configured) = (dir.path().join("typed.png"), dir.path().join("configured.png"));
+    mandelbrot().args(["--palette", "fire", "--smooth"]).arg(&typed)
+        .args(["40x30", "-1.20,0.35", "-1,0.20", "tiles"]).assert().success();
+    let config = dir.path().join("render.toml");
+    std::fs::write(&config, format!("output = {:?}\nsize = \"40x30\"\nupper-left = \"-1.20,0.35\"\n\
+                                     lower-right = \"-1,0.20\"\nrenderer = \"tiles\"\n\
+                                     palette = \"fire\"\nsmooth = true\n", configured.to_str().unwrap()))
+        .unwrap();
+    mandelbrot().arg("--config").arg(&config).assert().success();
+    assert_eq!(picture(&typed), picture(&configured));
+
+    // what --print-config prints renders the same again
+    let printed = mandelbrot().arg("--config").arg(&config).arg("--print-config").assert().success()
+        .get_output().stdout.clone();
+    let printed = String::from_utf8(printed).unwrap();
+    assert!(printed.contains("palette = \"fire\"\n") && printed.contains("tile-size = 32\n"), "{}", printed);
+    std::fs::remove_file(&configured).unwrap();
+    std::fs::write(&config, printed).unwrap();
+    mandelbrot().arg("--config").arg(&config).assert().success().stdout("");
+    assert_eq!(picture(&typed), picture(&configured));
+
+    // the command line wins over the file
+    mandelbrot().arg("--config").arg(&config).args(["--palette", "ocean", "--print-config"]).assert()
+        .success()
+        .stdout(predicate::str::contains("palette = \"ocean\"\n"));
+
+    // even where it says the same thing another way: a saved config of bands, and --smooth
+    let bands = mandelbrot().arg(&typed).args(["40x30", "-1.20,0.35", "-1,0.20", "--print-config"]).assert()
+        .success()
+        .get_output().stdout.clone();
+    assert!(String::from_utf8_lossy(&bands).contains("coloring = \"bands\"\n"));
+    std::fs::write(&config, bands).unwrap();
+    mandelbrot().arg("--config").arg(&config).args(["--smooth", "--print-config"]).assert()
+        .success()
+        .stdout(predicate::str::contains("coloring = \"smooth\"\n"));
+    mandelbrot().arg("--config").arg(&config).arg("--smooth").arg("--output").arg(&configured).assert().success();
+    mandelbrot().args(["--smooth", "--palette", "gray"]).arg(&typed)
+        .args(["40x30", "-1.20,0.35", "-1,0.20"]).assert().success();
+    assert_eq!(picture(&typed), picture(&configured));
+
+    std::fs::write(&config, "pallete = \"fire\"\n").unwrap();
+    mandelbrot().arg("--config").arg(&config).assert()
+        .code(1)
+        .stderr(predicate::str::contains("unknown field `pallete`"));
+}
+
+#[test]
+fn renders_a_list_of_jobs() {
+    let dir = tempfile::tempdir().unwrap();
+    let (plain, fire) = (dir.path().join("plain.png"), dir.path().join("fire.png"));
+    let jobs = dir.path().join("jobs.txt");
+    fs::write(&jobs, format!("# two renders, in one run\n{} 40x30 -1.20,0.35 -1,0.20\n\n\
+                              --palette fire --limit 100 {} 40x30 -1.20,0.35 -1,0.20\n",
+                             plain.display(), fire.display())).unwrap();
+    // each as it would be on its own, and each job's settings gone by the next
+    mandelbrot().arg("--deterministic").arg("--jobs").arg(&jobs).assert()
+        .success()
+        .stdout("")
+        .stderr(format!("{0}:2: {1}\n{0}:4: {2}\n2 jobs\n", jobs.display(), plain.display(), fire.display()));
+    assert_eq!(picture(&plain), render("fast"));
+    assert_eq!(picture(&fire), render_with(&["--palette", "fire", "--limit", "100"], "fast"));
+
+    // or a JSON list, with the keys of a config file
+    let json = dir.path().join("jobs.json");
+    fs::write(&json, format!(r#"[{{"output": {:?}, "size": "40x30", "upper-left": "-1.20,0.35",
+                                   "lower-right": "-1,0.20", "palette": "fire"}}]"#, fire.to_str().unwrap()))
+        .unwrap();
+    mandelbrot().arg("--jobs").arg(&json).assert()
+        .success()
+        .stderr(predicate::str::starts_with(format!("{}:1: {} in ", json.display(), fire.display())));
+    assert_eq!(picture(&fire), render_with(&["--palette", "fire"], "fast"));
+
+    // on its own, though, and a job's mistakes are the command line's
+    mandelbrot().arg("--jobs").arg(&jobs).arg("--palette").arg("fire").assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("mandelbrot: --jobs: not with --palette\n"));
+    fs::write(&jobs, "mandel.png 40x30 -1.20,0.35\n").unwrap();
+    mandelbrot().arg("--jobs").arg(&jobs).assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("mandelbrot: missing LOWERRIGHT\n"));
+}
+
+#[test]
+fn resumes_from_a_checkpoint() {
+    let dir = tempfile::tempdir().unwrap();
+    let (file, checkpoint) = (dir.path().join("mandel.png"), dir.path().join("mandel.ckpt"));
+    let view = ["40x30", "-1.20,0.35", "-1,0.20"];
+    // rendered in bands, it's the same picture, and the checkpoint is gone at the end
+    mandelbrot().arg("--checkpoint").arg(&checkpoint).arg(&file).args(view).assert().success().stdout("");
+    assert_eq!(picture(&file), render("fast"));
+    assert!(!checkpoint.exists());
+
+    // a checkpoint with the first ten rows done, made up so that they stand out
+    let options = mandelbrot().arg("--print-config").arg(&file).args(view).assert().success()
+        .get_output().stdout.clone();
+    let mut saved = format!("mandelbrot checkpoint\n{}\n", options.len()).into_bytes();
+    saved.extend(options);
+    saved.extend([7; 40 * 10]);
+    fs::write(&checkpoint, &saved).unwrap();
+    mandelbrot().arg("--resume").arg(&checkpoint).assert().success().stdout("");
+    let whole = image::load_from_memory(&render("fast")).unwrap().into_luma8().into_raw();
+    let resumed = image::open(&file).unwrap().into_luma8().into_raw();
+    assert!(resumed[.. 40 * 10].iter().all(|&p| p == 7));
+    assert_eq!(resumed[40 * 10 ..], whole[40 * 10 ..]);
+    assert!(!checkpoint.exists());
+
+    // not with other options, though
+    fs::write(&checkpoint, &saved).unwrap();
+    mandelbrot().arg("--resume").arg(&checkpoint).args(["--palette", "fire"]).assert()
+        .code(1)
+        .stderr(format!("mandelbrot: {}: the checkpoint is of a render with other options\n",
+                        checkpoint.display()));
+}
+
+/// A worker, `mandelbrot --serve-worker`, killed when it's dropped, the test passing or not.
+struct Worker(std::process::Child);
+
+impl Drop for Worker {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+#[test]
+fn distributes_tiles_to_workers() {
+    use std::io::{BufRead, Read, Write};
+    let mut worker = Worker(std::process::Command::new(assert_cmd::cargo::cargo_bin("mandelbrot"))
+        .args(["--serve-worker", "0"])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .unwrap());
+    // it says where it is once it's listening, on this machine alone without --bind
+    let mut line = String::new();
+    std::io::BufReader::new(worker.0.stderr.take().unwrap()).read_line(&mut line).unwrap();
+    assert!(line.starts_with("mandelbrot: serving tiles on 127.0.0.1:"), "{}", line);
+    let port = line.trim_end().rsplit(':').next().unwrap().to_string();
+    let down = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+
+    let dir = tempfile::tempdir().unwrap();
+    let (local, distributed) = (dir.path().join("local.png"), dir.path().join("distributed.png"));
+    let view = ["300x200", "-1.20,0.35", "-1,0.20"];
+    let options = ["--palette", "fire", "--julia", "-0.8,0.156", "--limit", "100"];
+    mandelbrot().args(options).arg(&local).args(view).assert().success();
+    // the tiles the host that's down fails on go to the worker
+    mandelbrot().args(options).arg("--distribute").arg(format!("{},127.0.0.1:{}", down, port))
+        .arg(&distributed).args(view).assert()
+        .success()
+        .stdout("");
+    assert_eq!(image::open(&distributed).unwrap().into_rgb8(), image::open(&local).unwrap().into_rgb8());
+    // a palette file's colors go with the request; the worker never sees the file
+    let map = dir.path().join("fire.map");
+    fs::write(&map, "0 0 0\n128 0 0\n255 64 0\n255 200 0\n255 255 255\n").unwrap();
+    std::fs::remove_file(&distributed).unwrap();
+    mandelbrot().arg("--palette-file").arg(&map).args(&options[2..]).arg("--distribute")
+        .arg(format!("127.0.0.1:{}", port)).arg(&distributed).args(view).assert()
+        .success();
+    assert_eq!(image::open(&distributed).unwrap().into_rgb8(), image::open(&local).unwrap().into_rgb8());
+    // and won't open one it's named, or render more than a coordinator would ask for
+    let ask = |tile: &str, options: &str| {
+        let request = format!("size = \"1000x1000\"\nupper-left = \"-1,1\"\nlower-right = \"1,-1\"\n{}", options);
+        let mut stream = std::net::TcpStream::connect(format!("127.0.0.1:{}", port)).unwrap();
+        write!(stream, "mandelbrot tile 1\n{}\n{}\n{}", tile, request.len(), request).unwrap();
+        let mut reply = Vec::new();
+        stream.read_to_end(&mut reply).unwrap();
+        String::from_utf8_lossy(&reply).into_owned()
+    };
+    assert_eq!(ask("0 0 4 4", &format!("palette-file = {:?}\n", map.to_str().unwrap())),
+               "error palette-file: a worker reads no files; send palette-colors\n");
+    assert_eq!(ask("0 0 129 128", ""), "error 129x128: a tile is 128x128 at most\n");
+    assert_eq!(ask("0 0 4 4", "limit = 1000001\n"), "error limit: 1000001 is more than a worker's 1000000\n");
+    assert!(ask("0 0 128 128", "limit = 10\n").starts_with("ok 16384\n"));
+    assert!(ask("0 0 4 4", "power = 4294967295.0\n").starts_with("ok 16\n"));
+    assert_eq!(ask("0 0 4 4", "power = 4294967296.0\n"), "error the options are out of range\n");
+    drop(worker);
+    // and with no worker up at all, there's no picture
+    std::fs::remove_file(&distributed).unwrap();
+    mandelbrot().args(options).arg("--distribute").arg(format!("127.0.0.1:{}", port))
+        .arg(&distributed).args(view).assert()
+        .code(1)
+        .stderr(predicate::str::contains(format!("\nmandelbrot: 127.0.0.1:{}: ", port)));
+    assert!(!distributed.exists());
+
+    mandelbrot().args(["--distribute", "alpha"]).arg(&distributed).args(view).assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("mandelbrot: --distribute: can't parse \"alpha\""));
+    mandelbrot().args(["--distribute", "alpha:7878", "--split", "16"]).arg(&distributed).args(view).assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("mandelbrot: --distribute: only a single picture"));
+    mandelbrot().args(["--distribute", "alpha:7878", "--limit", "1000001"]).arg(&distributed).args(view).assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("mandelbrot: --limit: a worker iterates 1000000 times at most\n"));
+    mandelbrot().args(["--bind", "0.0.0.0"]).arg(&distributed).args(view).assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("mandelbrot: missing --serve-worker"));
+    mandelbrot().args(["--serve-worker", "0", "--bind", "localhost"]).assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("mandelbrot: --bind: can't parse \"localhost\"\n"));
+}
+
+#[test]
+fn splits_into_tiles_and_stitches_them() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("mandel.png");
+    mandelbrot().args(["--split", "16", "--palette", "fire"]).arg(&file).args(["40x30", "-1.20,0.35", "-1,0.20"])
+        .assert().success().stdout("");
+    assert!(!file.exists());
+    for tile in ["mandel-0-0.png", "mandel-0-2.png", "mandel-1-2.png"] {
+        assert!(dir.path().join(tile).exists(), "{}", tile);
+    }
+    assert_eq!(image::image_dimensions(dir.path().join("mandel-1-2.png")).unwrap(), (8, 14));
+
+    mandelbrot().arg("stitch").arg(dir.path().join("mandel.tiles.toml")).assert().success().stdout("");
+    let whole = image::load_from_memory(&render_with(&["--palette", "fire"], "fast")).unwrap().into_rgb8();
+    assert_eq!(image::open(&file).unwrap().into_rgb8(), whole);
+    // or into another file
+    let other = dir.path().join("other.png");
+    mandelbrot().arg("stitch").arg(dir.path().join("mandel.tiles.toml")).arg(&other).assert().success();
+    assert_eq!(image::open(&other).unwrap().into_rgb8(), whole);
+
+    mandelbrot().args(["--split", "16", "mandel.jpg", "40x30", "-1.20,0.35", "-1,0.20"]).assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("mandelbrot: --split: the tiles are PNGs, so FILE has to be one\n"));
+}
+
+#[test]
+fn streams_tall_pngs() {
+    // a PNG is written a band of rows at a time, a PPM all at once: the same pixels
+    let dir = tempfile::tempdir().unwrap();
+    let (png, ppm) = (dir.path().join("tall.png"), dir.path().join("tall.ppm"));
+    for file in [&png, &ppm] {
+        mandelbrot().args(["--palette", "ocean", "--smooth"]).arg(file).args(["40x300", "-1.20,0.35", "-1,0.20"])
+            .assert().success();
+    }
+    let ppm = fs::read(&ppm).unwrap();
+    assert_eq!(image::open(&png).unwrap().into_rgb8().into_raw(), ppm[ppm.len() - 40 * 300 * 3 ..]);
+}
+
+#[test]
+fn writes_iteration_statistics() {
+    // the same points, whichever renderer, and whether streamed in bands or not
+    let dir = tempfile::tempdir().unwrap();
+    let (file, json) = (dir.path().join("mandel.png"), dir.path().join("stats.json"));
+    let stats = |file: &std::path::Path, mode: &str| {
+        mandelbrot().args(["--stats", json.to_str().unwrap(), file.to_str().unwrap(), "40x30", "-1.20,0.35", "-1,0.20", mode])
+            .assert().success();
+        let mut stats: serde_json::Value = serde_json::from_str(&fs::read_to_string(&json).unwrap()).unwrap();
+        let threads = stats.as_object_mut().unwrap().remove("threads").unwrap();
+        let pixels = threads.as_array().unwrap().iter().map(|t| t["pixels"].as_u64().unwrap()).sum::<u64>();
+        assert_eq!(pixels, 1200);
+        stats.as_object_mut().unwrap().remove("elapsed-ms").unwrap();
+        stats
+    };
+    let slow = stats(&file, "slow");
+    assert_eq!((&slow["pixels"], &slow["interior"], &slow["iterations"]["max"]), (&1200.into(), &323.into(), &253.into()));
+    let histogram = slow["histogram"].as_array().unwrap();
+    assert_eq!(histogram.iter().map(|b| b["pixels"].as_u64().unwrap()).sum::<u64>(), 1200 - 323);
+    assert_eq!(stats(&file, "fast"), slow);
+    assert_eq!(stats(&file, "tiles"), slow);
+    assert_eq!(stats(&dir.path().join("mandel.ppm"), "fast"), slow);
+
+    // to stderr, and with --deterministic, without the times
+    mandelbrot().args(["--deterministic", "--stats", "-", file.to_str().unwrap(), "40x30", "-1.20,0.35", "-1,0.20"])
+        .assert()
+        .success()
+        .stderr("1200 pixels, 323 inside the set\n\
+                 iterations: min 7, max 253, mean 25.5\n          \
+                 0-0 0\n          1-1 0\n          2-3 0\n          4-7 10\n         8-15 408\n        \
+                 16-31 284\n        32-63 119\n       64-127 37\n      128-255 19\n");
+}
+
+#[test]
+fn reports_unwritable_output() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("no-such-dir").join("mandel.png");
+    mandelbrot().args([file.to_str().unwrap(), "4x3", "-1.20,0.35", "-1,0.20", "fast"]).assert()
+        .code(1)
+        .stderr(predicate::str::starts_with(format!("mandelbrot: {}: ", file.display())));
+}
+
+#[test]
+fn rejects_images_too_big_to_allocate() {
+    mandelbrot().args(["mandel.png", "5000000000x5000000000", "-1.20,0.35", "-1,0.20", "fast"])
+        .assert()
+        .code(1)
+        .stderr("mandelbrot: 5000000000x5000000000 pixels is too many\n");
+}
+
+// --explain wins over everything else on the command line, and renders nothing
+#[test]
+fn explains_instead_of_rendering() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("mandel.png");
+    mandelbrot().args([file.to_str().unwrap(), "40x30", "-1.20,0.35", "-1,0.20", "fast", "--explain"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("03mandelbrot: ")
+            .and(predicate::str::contains("\n6. Threads without data races\n")));
+    assert!(!file.exists());
+}
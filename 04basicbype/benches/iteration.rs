@@ -0,0 +1,119 @@
+// The same loop, a sum of squares, over an array, a Vec and a slice, written three ways:
+// with an index, as an iterator chain, and as a fold. The chapter says iterators cost
+// nothing over the loop you'd write by hand, and that an array, a Vec and a slice are
+// all read the same way once they're borrowed; this puts numbers on both.
+//
+//   $ cargo bench -p basictype
+//   basictype::sum_of_squares/array indexed/1024    time:   [...]
+//   basictype::sum_of_squares/array chain/1024      time:   [...]
+//   ...
+//
+// What to expect: the chain and the fold compile to the same code, and match or beat the
+// indexed loop, which has a bounds check per element unless the compiler can see it away.
+// Over an array it can, since the length is in the type; over a Vec or a slice it often
+// can too, from the `0..v.len()` right there, so the gaps are small. Where they aren't,
+// it's the bounds checks.
+//
+// The indexed loops are the point of the comparison, and the &Vec parameters keep the
+// Vec from turning into a slice before the loop; clippy would rewrite both.
+#![allow(clippy::needless_range_loop, clippy::ptr_arg)]
+use criterion::{criterion_group, criterion_main, BenchmarkGroup, BenchmarkId, Criterion, Throughput};
+use criterion::measurement::WallTime;
+use std::hint::black_box;
+
+fn array_indexed<const N: usize>(a: &[u64; N]) -> u64 {
+    let mut sum = 0;
+    for i in 0..N {
+        sum += a[i] * a[i];
+    }
+    sum
+}
+
+fn array_chain<const N: usize>(a: &[u64; N]) -> u64 {
+    a.iter().map(|x| x * x).sum()
+}
+
+fn array_fold<const N: usize>(a: &[u64; N]) -> u64 {
+    a.iter().fold(0, |sum, x| sum + x * x)
+}
+
+fn vec_indexed(v: &Vec<u64>) -> u64 {
+    let mut sum = 0;
+    for i in 0..v.len() {
+        sum += v[i] * v[i];
+    }
+    sum
+}
+
+fn vec_chain(v: &Vec<u64>) -> u64 {
+    v.iter().map(|x| x * x).sum()
+}
+
+fn vec_fold(v: &Vec<u64>) -> u64 {
+    v.iter().fold(0, |sum, x| sum + x * x)
+}
+
+fn slice_indexed(s: &[u64]) -> u64 {
+    let mut sum = 0;
+    for i in 0..s.len() {
+        sum += s[i] * s[i];
+    }
+    sum
+}
+
+fn slice_chain(s: &[u64]) -> u64 {
+    s.iter().map(|x| x * x).sum()
+}
+
+fn slice_fold(s: &[u64]) -> u64 {
+    s.iter().fold(0, |sum, x| sum + x * x)
+}
+
+// The array size is a const generic, so each size is its own function, with its length
+// known at compile time, as it would be in a program that used an array.
+fn bench_size<const N: usize>(group: &mut BenchmarkGroup<WallTime>) {
+    let array: [u64; N] = std::array::from_fn(|i| (i % 1000) as u64);
+    let vec = array.to_vec();
+    // black_box hides where the slice came from, so its length is only known at run time
+    let slice: &[u64] = black_box(&vec[..]);
+    let expected = array_indexed(&array);
+    for sum in [array_chain(&array), array_fold(&array), vec_indexed(&vec), vec_chain(&vec),
+                vec_fold(&vec), slice_indexed(slice), slice_chain(slice), slice_fold(slice)] {
+        assert_eq!(sum, expected);
+    }
+
+    group.throughput(Throughput::Elements(N as u64));
+    group.bench_with_input(BenchmarkId::new("array indexed", N), &array,
+                           |b, a| b.iter(|| array_indexed(black_box(a))));
+    group.bench_with_input(BenchmarkId::new("array chain", N), &array,
+                           |b, a| b.iter(|| array_chain(black_box(a))));
+    group.bench_with_input(BenchmarkId::new("array fold", N), &array,
+                           |b, a| b.iter(|| array_fold(black_box(a))));
+    group.bench_with_input(BenchmarkId::new("vec indexed", N), &vec,
+                           |b, v| b.iter(|| vec_indexed(black_box(v))));
+    group.bench_with_input(BenchmarkId::new("vec chain", N), &vec,
+                           |b, v| b.iter(|| vec_chain(black_box(v))));
+    group.bench_with_input(BenchmarkId::new("vec fold", N), &vec,
+                           |b, v| b.iter(|| vec_fold(black_box(v))));
+    group.bench_with_input(BenchmarkId::new("slice indexed", N), slice,
+                           |b, s| b.iter(|| slice_indexed(black_box(s))));
+    group.bench_with_input(BenchmarkId::new("slice chain", N), slice,
+                           |b, s| b.iter(|| slice_chain(black_box(s))));
+    group.bench_with_input(BenchmarkId::new("slice fold", N), slice,
+                           |b, s| b.iter(|| slice_fold(black_box(s))));
+}
+
+fn bench_sum_of_squares(c: &mut Criterion) {
+    let mut group = c.benchmark_group("basictype::sum_of_squares");
+    bench_size::<16>(&mut group);
+    bench_size::<1024>(&mut group);
+    bench_size::<16384>(&mut group);
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = rustfun_benches::criterion();
+    targets = bench_sum_of_squares
+}
+criterion_main!(benches);
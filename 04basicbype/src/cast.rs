@@ -0,0 +1,260 @@
+// The cast explorer: `basictype VALUE CHAIN` follows a value through a chain of `as`
+// casts between integer types, and shows the bits at every step.
+//
+//   $ cargo run -q -p basictype -- 1000 'i16->u8->i32'
+//   1000_i16                      0000_0011_1110_1000
+//   as u8: truncated, keeping the low 8 of 16 bits
+//     232_u8                                1110_1000
+//   as i32: zero-extended, with 24 new bits of 0
+//    232_i32  0000_0000_0000_0000_0000_0000_1110_1000
+//
+// Note 13 in main.rs states the rules: an integer cast keeps the bits, truncating to a
+// narrower type, sign-extending a signed value to a wider one, zero-extending an
+// unsigned one. Here they're worked out on the bits themselves, and the tests check the
+// result against what `as` does.
+//
+use std::fmt;
+use std::str::FromStr;
+
+/// An integer type: its name, its width in bits, and whether it's signed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntType {
+    pub name: &'static str,
+    pub bits: u32,
+    pub signed: bool,
+}
+
+const fn int(name: &'static str, bits: u32, signed: bool) -> IntType {
+    IntType { name, bits, signed }
+}
+
+/// Every integer type. isize and usize are as wide as an address on this machine.
+pub const TYPES: [IntType; 12] = [
+    int("i8", 8, true), int("i16", 16, true), int("i32", 32, true), int("i64", 64, true),
+    int("i128", 128, true), int("isize", isize::BITS, true),
+    int("u8", 8, false), int("u16", 16, false), int("u32", 32, false), int("u64", 64, false),
+    int("u128", 128, false), int("usize", usize::BITS, false),
+];
+
+impl FromStr for IntType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<IntType, String> {
+        TYPES.iter().find(|t| t.name == s).copied()
+            .ok_or_else(|| format!("{:?} isn't an integer type, like i16 or u8", s))
+    }
+}
+
+impl IntType {
+    /// All of the type's bits set: the bits a value of it can use.
+    fn mask(self) -> u128 {
+        u128::MAX >> (128 - self.bits)
+    }
+}
+
+/// A value of an integer type, kept as its bit pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Value {
+    pub ty: IntType,
+    bits: u128,
+}
+
+impl Value {
+    /// Parse `s` as a value of `ty`, if it's a number in the type's range.
+    pub fn parse(s: &str, ty: IntType) -> Result<Value, String> {
+        // 1.  Negative numbers go through i128 and the rest through u128, so that every
+        //     value of every type, down to i128::MIN and up to u128::MAX, can be read.
+        let not_a_number = |_| format!("{:?} isn't a whole number", s);
+        let (bits, fits) = match s.strip_prefix('-') {
+            Some(_) => {
+                let n = s.parse::<i128>().map_err(not_a_number)?;
+                (n as u128, ty.signed && (ty.bits == 128 || n >= -(1 << (ty.bits - 1))))
+            }
+            None => {
+                let n = s.parse::<u128>().map_err(not_a_number)?;
+                (n, n <= if ty.signed { ty.mask() >> 1 } else { ty.mask() })
+            }
+        };
+        if !fits {
+            return Err(format!("{} doesn't fit in {}", s, ty.name));
+        }
+        Ok(Value { ty, bits: bits & ty.mask() })
+    }
+
+    /// The value of `ty` with the low bits of `bits`.
+    pub fn from_bits(ty: IntType, bits: u128) -> Value {
+        Value { ty, bits: bits & ty.mask() }
+    }
+
+    /// The bits, most significant first, in groups of four as in `0b0000_0011`.
+    pub fn bit_string(&self) -> String {
+        let digits = format!("{:0width$b}", self.bits, width = self.ty.bits as usize);
+        let groups: Vec<&str> = digits.as_bytes().chunks(4)
+            .map(|group| std::str::from_utf8(group).unwrap())
+            .collect();
+        groups.join("_")
+    }
+
+    fn sign_bit(&self) -> bool {
+        self.bits >> (self.ty.bits - 1) & 1 == 1
+    }
+}
+
+// 2.  A signed value is read in two's complement: with the sign bit set, it's negative.
+//     Shifting the pattern up to bit 127 and back down as an i128 is an arithmetic shift,
+//     which copies the sign bit into the bits above: sign extension again.
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.ty.signed {
+            let shift = 128 - self.ty.bits;
+            write!(f, "{}", ((self.bits << shift) as i128) >> shift)
+        } else {
+            write!(f, "{}", self.bits)
+        }
+    }
+}
+
+/// What a cast did to the bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    /// The same type: nothing at all.
+    Nothing,
+    /// The same width: the same bits, read the other type's way.
+    Reinterpreted,
+    /// Narrower: the high bits are dropped, and `kept` remain.
+    Truncated { kept: u32, of: u32 },
+    /// Wider, from a signed type: the new high bits are copies of the sign bit.
+    SignExtended { added: u32, bit: bool },
+    /// Wider, from an unsigned type: the new high bits are zeros.
+    ZeroExtended { added: u32 },
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Change::Nothing => write!(f, "the same type, so nothing changes"),
+            Change::Reinterpreted => write!(f, "the same width, so the same bits, read anew"),
+            Change::Truncated { kept, of } =>
+                write!(f, "truncated, keeping the low {} of {} bits", kept, of),
+            Change::SignExtended { added, bit } =>
+                write!(f, "sign-extended, with {} new bits copied from the sign bit, {}",
+                       added, bit as u8),
+            Change::ZeroExtended { added } =>
+                write!(f, "zero-extended, with {} new bits of 0", added),
+        }
+    }
+}
+
+/// `value as to`, and what it did.
+pub fn cast(value: Value, to: IntType) -> (Value, Change) {
+    let from = value.ty;
+    let (bits, change) = if to == from {
+        (value.bits, Change::Nothing)
+    } else if to.bits == from.bits {
+        (value.bits, Change::Reinterpreted)
+    } else if to.bits < from.bits {
+        (value.bits & to.mask(), Change::Truncated { kept: to.bits, of: from.bits })
+    } else if from.signed {
+        let bit = value.sign_bit();
+        let high = if bit { to.mask() & !from.mask() } else { 0 };
+        (value.bits | high, Change::SignExtended { added: to.bits - from.bits, bit })
+    } else {
+        (value.bits, Change::ZeroExtended { added: to.bits - from.bits })
+    };
+    (Value { ty: to, bits }, change)
+}
+
+#[cfg(test)]
+fn ty(name: &str) -> IntType {
+    name.parse().unwrap()
+}
+
+#[test]
+fn test_cast_matches_as() {
+    let to = |s: &str, from: &str, into: &str| {
+        cast(Value::parse(s, ty(from)).unwrap(), ty(into)).0.to_string()
+    };
+    // The casts of note 13, and what `as` makes of them.
+    assert_eq!(to("10", "i8", "u16"), (10_i8 as u16).to_string());
+    assert_eq!(to("2525", "u16", "i16"), (2525_u16 as i16).to_string());
+    assert_eq!(to("-1", "i16", "i32"), (-1_i16 as i32).to_string());
+    assert_eq!(to("65535", "u16", "i32"), (65535_u16 as i32).to_string());
+    assert_eq!(to("1000", "i16", "u8"), (1000_i16 as u8).to_string());
+    assert_eq!(to("65535", "u32", "i16"), (65535_u32 as i16).to_string());
+    assert_eq!(to("-1", "i8", "u8"), (-1_i8 as u8).to_string());
+    assert_eq!(to("255", "u8", "i8"), (255_u8 as i8).to_string());
+    assert_eq!(to("-2", "i64", "u128"), (-2_i64 as u128).to_string());
+    assert_eq!(to("-128", "i8", "i128"), (-128_i8 as i128).to_string());
+
+    let (wide, change) = cast(Value::parse("-1", ty("i8")).unwrap(), ty("i32"));
+    assert_eq!(change, Change::SignExtended { added: 24, bit: true });
+    assert_eq!(wide.bit_string(), "1111_1111_1111_1111_1111_1111_1111_1111");
+    assert_eq!(cast(Value::parse("1000", ty("i16")).unwrap(), ty("u8")).1,
+               Change::Truncated { kept: 8, of: 16 });
+    assert_eq!(cast(Value::parse("255", ty("u8")).unwrap(), ty("i32")).1,
+               Change::ZeroExtended { added: 24 });
+}
+
+#[test]
+fn test_parse_value() {
+    assert_eq!(Value::parse("-128", ty("i8")).unwrap().bit_string(), "1000_0000");
+    assert_eq!(Value::parse("127", ty("i8")).unwrap().to_string(), "127");
+    assert_eq!(Value::parse("128", ty("i8")), Err("128 doesn't fit in i8".to_string()));
+    assert_eq!(Value::parse("-1", ty("u8")), Err("-1 doesn't fit in u8".to_string()));
+    assert_eq!(Value::parse("x", ty("i8")), Err("\"x\" isn't a whole number".to_string()));
+    assert_eq!(Value::parse("340282366920938463463374607431768211455", ty("u128"))
+                   .unwrap().to_string(), u128::MAX.to_string());
+    assert_eq!(Value::parse("-170141183460469231731687303715884105728", ty("i128"))
+                   .unwrap().to_string(), i128::MIN.to_string());
+    assert_eq!("f32".parse::<IntType>(), Err("\"f32\" isn't an integer type, like i16 or u8".to_string()));
+}
+
+/// Parse a chain like `i16->u8->i32`: the value's own type, then each type it's cast to.
+pub fn parse_chain(s: &str) -> Result<Vec<IntType>, String> {
+    let types = s.split("->").map(|t| t.trim().parse()).collect::<Result<Vec<IntType>, _>>()?;
+    if types.len() < 2 {
+        return Err(format!("{:?}: expected a type and what to cast it to, like i16->u8", s));
+    }
+    Ok(types)
+}
+
+/// Follow `value` through `chain`, one line for each value and one for each cast, with the
+/// values and bit patterns right-aligned, so that the low bits line up.
+pub fn explore(value: &str, chain: &[IntType]) -> Result<String, String> {
+    let mut values = vec![Value::parse(value, chain[0])?];
+    let mut changes = Vec::new();
+    for &to in &chain[1..] {
+        let (next, change) = cast(*values.last().unwrap(), to);
+        values.push(next);
+        changes.push(change);
+    }
+
+    let labels: Vec<String> = values.iter().map(|v| format!("{}_{}", v, v.ty.name)).collect();
+    let label_width = labels.iter().map(String::len).max().unwrap_or(0);
+    let bits_width = values.iter().map(|v| v.bit_string().len()).max().unwrap_or(0);
+    let mut out = String::new();
+    for (i, value) in values.iter().enumerate() {
+        if i > 0 {
+            out.push_str(&format!("as {}: {}\n", value.ty.name, changes[i - 1]));
+        }
+        out.push_str(&format!("{:>lw$}  {:>bw$}\n", labels[i], value.bit_string(),
+                              lw = label_width, bw = bits_width));
+    }
+    Ok(out)
+}
+
+#[test]
+fn test_explore() {
+    let chain = parse_chain("i16->u8->i8").unwrap();
+    assert_eq!(explore("1000", &chain).unwrap(), "\
+1000_i16  0000_0011_1110_1000
+as u8: truncated, keeping the low 8 of 16 bits
+  232_u8            1110_1000
+as i8: the same width, so the same bits, read anew
+  -24_i8            1110_1000
+");
+    assert_eq!(explore("1000", &parse_chain("i8->u8").unwrap()),
+               Err("1000 doesn't fit in i8".to_string()));
+    assert!(parse_chain("i16").is_err());
+    assert!(parse_chain("i16->f32").is_err());
+}
@@ -0,0 +1,75 @@
+// What `basictype --explain` prints: the chapter's notes, in short.
+use rustfun_common::{Explanation, Text, Topic};
+
+pub const EXPLANATION: Explanation = Explanation {
+    chapter: "04basicbype",
+    summary: Text {
+        en: "integers, floats, chars, tuples, arrays, vectors and strings",
+        zh: "整数、浮点数、字符、元组、数组、向量和字符串",
+    },
+    topics: &[
+        Topic {
+            title: Text { en: "Numbers", zh: "数字" },
+            body: Text {
+                en: "Integer types say their size and sign: i8 to i128, u8 to u128, and isize and\n\
+                     usize for sizes and indices. Overflow panics in debug builds; the checked_,\n\
+                     saturating_, overflowing_ and wrapping_ methods, and Wrapping<T>, say what\n\
+                     to do instead. NonZeroU32 and the like can't be 0, so an Option of one is\n\
+                     no bigger than the integer. f32 and f64 are IEEE floats, and a computed one\n\
+                     is compared with approx_eq or ulps_eq, not ==: 0.1 + 0.2 != 0.3. Bits are\n\
+                     set, cleared, masked and rotated with | & ^ and shifts, and rustfun_common's\n\
+                     Bits trait names each of those; Perms keeps flags in a u8, joined with |.",
+                zh: "整数类型写明大小和符号: i8 到 i128, u8 到 u128, 以及用于大小和下标的 isize\n\
+                     和 usize。debug 构建中溢出会 panic; checked_、saturating_、overflowing_、\n\
+                     wrapping_ 方法和 Wrapping<T> 说明该怎么处理。NonZeroU32 等类型不能为 0,\n\
+                     所以它们的 Option 不比整数本身大。f32 和 f64 是 IEEE 浮点数, 算出来的浮点数\n\
+                     用 approx_eq 或 ulps_eq 比较, 而不是 ==: 0.1 + 0.2 != 0.3。位的置位、清除、掩码\n\
+                     和循环移位用 | & ^ 和移位完成, rustfun_common 的 Bits trait 给每种操作起了\n\
+                     名字; Perms 把标志位存在一个 u8 里, 用 | 组合。",
+            },
+        },
+        Topic {
+            title: Text { en: "Casts", zh: "类型转换" },
+            body: Text {
+                en: "Conversions between numeric types are always explicit, with as; a cast to a\n\
+                     narrower integer truncates. bool converts to an integer, but not back.\n\
+                     `basictype 1000 i16->u8` shows the bits of each step, and `basictype quiz`\n\
+                     asks what casts make of random values.",
+                zh: "数值类型之间的转换一律显式地用 as; 转换成更窄的整数会截断。bool 可以转成\n\
+                     整数, 反过来不行。`basictype 1000 i16->u8` 显示每一步的二进制位,\n\
+                     `basictype quiz` 则考你随机的值转换后是多少。",
+            },
+        },
+        Topic {
+            title: Text { en: "char and strings", zh: "char 与字符串" },
+            body: Text {
+                en: "A char is a Unicode scalar value, four bytes. A String owns UTF-8 text on the\n\
+                     heap; a &str borrows it. Raw strings and byte strings skip the escapes.",
+                zh: "char 是一个 Unicode 标量值, 占四个字节。String 在堆上拥有 UTF-8 文本; &str\n\
+                     借用它。原始字符串和字节串不处理转义。",
+            },
+        },
+        Topic {
+            title: Text { en: "Tuples, arrays, vectors, slices", zh: "元组、数组、向量、切片" },
+            body: Text {
+                en: "A tuple groups values of different types; an array has a fixed length known at\n\
+                     compile time; a Vec grows on the heap. A slice, &[T], borrows a run of either\n\
+                     one's elements. Indexing, iterator chains and fold read all three at the\n\
+                     same speed: `cargo bench -p basictype` times them.",
+                zh: "元组把不同类型的值组合在一起; 数组的长度在编译时确定; Vec 在堆上增长。\n\
+                     切片 &[T] 借用数组或向量中连续的一段元素。下标、迭代器链和 fold 读取三者的\n\
+                     速度相同: `cargo bench -p basictype` 会测出来。",
+            },
+        },
+        Topic {
+            title: Text { en: "Random numbers", zh: "随机数" },
+            body: Text {
+                en: "The rand crate: a StdRng seeded with seed_from_u64 gives the same numbers\n\
+                     every run, for tests and for repeating a run. random_range, shuffle, and\n\
+                     distributions like rand_distr's Normal draw from it.",
+                zh: "rand crate: 用 seed_from_u64 播种的 StdRng 每次运行给出相同的数, 便于测试\n\
+                     和重现。random_range、shuffle 以及 rand_distr 的 Normal 等分布都从它取数。",
+            },
+        },
+    ],
+};
@@ -1,5 +1,14 @@
-extern crate regex;
+// This chapter spells things out on purpose: asserts that compare a value with itself
+// written another way, push after Vec::new().
+#![allow(clippy::eq_op, clippy::unnecessary_fold,
+         clippy::vec_init_then_push, clippy::bool_assert_comparison, clippy::approx_constant,
+         clippy::useless_vec, clippy::needless_borrow, clippy::byte_char_slices,
+         clippy::get_first)]
 use regex::Regex;
+use rustfun_common::{tr, Args, Text};
+#[cfg(test)]
+use rustfun_common::{approx_eq, ulps_between, ulps_eq, Bits, Perms};
+use std::num::{NonZeroU32, NonZeroUsize, Wrapping};
 //
 // Rust is a statically typed language: without actually running the program, the compiler checks
 // that every possible path of execution will use values only in ways consistent with their types.
@@ -40,14 +49,12 @@ fn test_build_vector(){
     assert_eq!(Some(&10i16),build_vector2().get(0));
 }
 
-
-fn main() {
-    println!("basic types!");
-
+#[test]
+fn test_integers() {
     // 6. arithmetic operation overflowed
     //    let x = big_val + 1;
     //    wrap to a negative number
-    let big_val = std::i32::MAX;
+    let big_val = i32::MAX;
     let x = big_val.wrapping_add(1);  // ok
     assert_eq!( big_val,   2147483647);
     assert_eq!(       x,  -2147483648);
@@ -72,27 +79,29 @@ fn main() {
     assert_eq!( 0xffff_fc00usize, 4_294_966_272);
 
     // 11.  limitations
-    assert_eq!(    std::i8::MAX,                  127);
-    assert_eq!(    std::i8::MIN,                 -128);
-    assert_eq!(   std::i16::MAX,                32767);
-    assert_eq!(   std::i16::MIN,               -32768);
-    assert_eq!(   std::i32::MAX,           2147483647);
-    assert_eq!(   std::i32::MIN,          -2147483648);
-    assert_eq!(   std::i64::MAX,  9223372036854775807);
-    assert_eq!(   std::i64::MIN, -9223372036854775808);
-    assert_eq!( std::isize::MAX,  9223372036854775807);
-    assert_eq!( std::isize::MIN, -9223372036854775808);
-
-    assert_eq!(    std::u8::MAX,           (127<<1)+1); //255
-    assert_eq!(    std::u8::MIN,                    0);
-    assert_eq!(   std::u16::MAX,         (32767<<1)+1); //65,535
-    assert_eq!(   std::u16::MIN,                    0);
-    assert_eq!(   std::u32::MAX,    (2147483647<<1)+1); //4,294,967,295
-    assert_eq!(   std::u32::MIN,                    0);
-    assert_eq!(   std::u64::MAX, 18446744073709551615);
-    assert_eq!(   std::u64::MIN,                    0);
-    assert_eq!( std::usize::MAX, 18446744073709551615);
-    assert_eq!( std::usize::MIN,                    0);
+    // 11.1 MAX and MIN are associated constants of each type; the older std::i32::MAX
+    //      module constants are deprecated.
+    assert_eq!(    i8::MAX,                  127);
+    assert_eq!(    i8::MIN,                 -128);
+    assert_eq!(   i16::MAX,                32767);
+    assert_eq!(   i16::MIN,               -32768);
+    assert_eq!(   i32::MAX,           2147483647);
+    assert_eq!(   i32::MIN,          -2147483648);
+    assert_eq!(   i64::MAX,  9223372036854775807);
+    assert_eq!(   i64::MIN, -9223372036854775808);
+    assert_eq!( isize::MAX,  9223372036854775807);
+    assert_eq!( isize::MIN, -9223372036854775808);
+
+    assert_eq!(    u8::MAX,           (127<<1)+1); //255
+    assert_eq!(    u8::MIN,                    0);
+    assert_eq!(   u16::MAX,         (32767<<1)+1); //65,535
+    assert_eq!(   u16::MIN,                    0);
+    assert_eq!(   u32::MAX,    (2147483647<<1)+1); //4,294,967,295
+    assert_eq!(   u32::MIN,                    0);
+    assert_eq!(   u64::MAX, 18446744073709551615);
+    assert_eq!(   u64::MIN,                    0);
+    assert_eq!( usize::MAX, 18446744073709551615);
+    assert_eq!( usize::MIN,                    0);
 
 
     // 12.  Characters require a backslash
@@ -136,15 +145,210 @@ fn main() {
 
     assert_eq!(2_usize.pow(32), 4294967296);
     assert_eq!(2_usize.pow(32), 2_usize<<31);
-    assert_eq!(std::usize::MAX, (((2_usize<<62)-1)<<1)+1);
+    assert_eq!(usize::MAX, (((2_usize<<62)-1)<<1)+1);
     assert_eq!(2_u64.pow(32),   2_u64<<31);
 
-    assert_eq!(std::u64::MAX.count_ones(),64);
-    assert_eq!(std::i64::MAX.count_ones(),63);
-    assert_eq!(std::i64::MAX, 0x7fff_ffff_ffff_ffff_i64);
+    assert_eq!(u64::MAX.count_ones(),64);
+    assert_eq!(i64::MAX.count_ones(),63);
+    assert_eq!(i64::MAX, 0x7fff_ffff_ffff_ffff_i64);
     assert_eq!(0xff,0b1111_1111);
     assert_eq!(0x7f,0b0111_1111);
+}
+
+#[test]
+fn test_bits() {
+    // 14.1 count_ones counts the bits that are set; single bits, masks and rotations
+    //      are | & ^ and shifts (rustfun_common::bits), and say what they do by name.
+    let x = 0b101101u8;
+    assert_eq!(x.set_bit(1), 0b101111);
+    assert_eq!(x.set_bit(1).count_ones(), x.count_ones() + 1);
+    assert_eq!(x.clear_bit(0), 0b101100);
+    assert!(x.test_bit(5) && !x.test_bit(4));
+    assert_eq!(x & u8::mask(0..4), 0b1101);       // the low four bits
+    assert_eq!(x.rotl(4), 0b1101_0010);           // the two halves swapped
+    assert_eq!(x.rotl(4).count_ones(), 4);
+    assert_eq!(x.rotl(3), x.rotate_left(3));
+    assert_eq!(u64::mask(0..63), i64::MAX as u64);
+    // 14.2 A set of flags is an integer whose bits each mean one thing; Perms keeps
+    //      read, write and execute in a u8, and | and & combine them.
+    let perms = Perms::READ | Perms::EXECUTE;
+    assert_eq!(perms.to_string(), "r-x");
+    assert_eq!(perms.bits().count_ones(), 2);
+    assert_eq!(perms & Perms::all(), perms);
+}
+
+// 6.1 Overflow on purpose. Note 6 used wrapping_add; each family of methods says what
+//     to do instead of panicking, and the name says which:
+//     checked_     None on overflow, so the caller decides
+//     saturating_  stop at MAX (or MIN)
+//     overflowing_ the wrapped result, and a bool that says whether it wrapped
+//     wrapping_    the wrapped result alone; Wrapping<T> makes that the type's + - *
+// 6.2 checked_add with ? inside a fold: the first overflow makes the whole total None.
+/// The sum of `prices`, or None if it doesn't fit in a u32.
+#[allow(dead_code)]
+fn checked_total(prices: &[u32]) -> Option<u32> {
+    prices.iter().try_fold(0u32, |total, &price| total.checked_add(price))
+}
+
+#[test]
+fn test_checked_total() {
+    assert_eq!(checked_total(&[]), Some(0));
+    assert_eq!(checked_total(&[1_000, 2_000, 3_000]), Some(6_000));
+    assert_eq!(checked_total(&[u32::MAX, 0]), Some(u32::MAX));
+    assert_eq!(checked_total(&[u32::MAX, 1]), None);
+    assert_eq!(200u8.checked_add(56), None);
+    assert_eq!(5u8.checked_sub(6), None);
+    assert_eq!(i32::MIN.checked_div(-1), None); // the one division that overflows
+}
+
+// 6.3 saturating_mul for a value that only needs to be "big enough": a screen area
+//     can't be more than u16::MAX pixels here, so anything larger is just the maximum.
+/// The area of a `width` x `height` rectangle, at most u16::MAX.
+#[allow(dead_code)]
+fn saturating_area(width: u16, height: u16) -> u16 {
+    width.saturating_mul(height)
+}
+
+#[test]
+fn test_saturating_area() {
+    assert_eq!(saturating_area(200, 300), 60_000);
+    assert_eq!(saturating_area(300, 300), u16::MAX);
+    assert_eq!(saturating_area(0, u16::MAX), 0);
+    assert_eq!(100i8.saturating_add(100), i8::MAX);
+    assert_eq!((-100i8).saturating_sub(100), i8::MIN);
+    assert_eq!(0u8.saturating_sub(1), 0);
+}
+
+// 6.4 overflowing_sub returns the wrapped difference and whether it borrowed, which is
+//     how subtraction is done digit by digit on numbers wider than any one integer:
+//     here two-byte numbers, low byte first, the borrow carried into the high byte.
+/// `a - b` for two-byte numbers stored low byte first, and whether it wrapped.
+#[allow(dead_code)]
+fn overflowing_sub_bytes(a: [u8; 2], b: [u8; 2]) -> ([u8; 2], bool) {
+    let (low, borrow) = a[0].overflowing_sub(b[0]);
+    let (high, wrapped) = a[1].overflowing_sub(b[1]);
+    let (high, wrapped_again) = high.overflowing_sub(borrow as u8);
+    ([low, high], wrapped || wrapped_again)
+}
+
+#[test]
+fn test_overflowing_sub_bytes() {
+    let bytes = |n: u16| n.to_le_bytes();
+    assert_eq!(overflowing_sub_bytes(bytes(1000), bytes(1)), (bytes(999), false));
+    assert_eq!(overflowing_sub_bytes(bytes(256), bytes(1)), (bytes(255), false)); // a borrow
+    assert_eq!(overflowing_sub_bytes(bytes(0), bytes(1)), (bytes(u16::MAX), true));
+    // the same answers as u16's own overflowing_sub
+    for (a, b) in [(1000u16, 1u16), (256, 1), (0, 1), (1, 1000), (40_000, 39_999)] {
+        let (difference, wrapped) = a.overflowing_sub(b);
+        assert_eq!(overflowing_sub_bytes(bytes(a), bytes(b)), (bytes(difference), wrapped));
+    }
+    assert_eq!(i8::MIN.overflowing_sub(1), (i8::MAX, true));
+}
+
+// 6.5 std::num::Wrapping<T> for arithmetic that is meant to wrap, like a hash: its
+//     operators wrap in debug and release builds alike, with no method names to forget.
+//     This is djb2: start at 5381, and for each byte, multiply by 33 and add it.
+/// The djb2 hash of `bytes`.
+#[allow(dead_code)]
+fn djb2(bytes: &[u8]) -> u32 {
+    let mut hash = Wrapping(5381u32);
+    for &b in bytes {
+        hash = hash * Wrapping(33) + Wrapping(b as u32);
+    }
+    hash.0
+}
+
+#[test]
+fn test_djb2() {
+    assert_eq!(djb2(b""), 5381);
+    assert_eq!(djb2(b"a"), 5381 * 33 + 97);
+    // long enough to wrap many times over; the same as spelling out wrapping_mul/add
+    let text = b"The quick brown fox jumps over the lazy dog";
+    let spelled_out = text.iter()
+        .fold(5381u32, |hash, &b| hash.wrapping_mul(33).wrapping_add(b as u32));
+    assert_eq!(djb2(text), spelled_out);
+    assert_eq!(Wrapping(u8::MAX) + Wrapping(1), Wrapping(0));
+    assert_eq!(Wrapping(0u8) - Wrapping(1), Wrapping(u8::MAX));
+}
+
+// 11.2 i128 and u128, left out of note 11.1: 16 bytes, with MAX near 1.7e38 and 3.4e38.
+//      They're the wider type to reach for when a u64 product or sum might not fit.
+/// n!, or None once it no longer fits in a u128: the last that does is 34!.
+#[allow(dead_code)]
+fn factorial(n: u32) -> Option<u128> {
+    (1..=n as u128).try_fold(1u128, |product, k| product.checked_mul(k))
+}
+
+/// The full product of two u64s, which always fits in a u128.
+#[allow(dead_code)]
+fn mul_wide(a: u64, b: u64) -> u128 {
+    a as u128 * b as u128
+}
 
+#[test]
+fn test_128_bit_integers() {
+    assert_eq!( i128::MAX,  170_141_183_460_469_231_731_687_303_715_884_105_727);
+    assert_eq!( i128::MIN, -170_141_183_460_469_231_731_687_303_715_884_105_728);
+    assert_eq!( u128::MAX,  340_282_366_920_938_463_463_374_607_431_768_211_455);
+    assert_eq!( u128::MAX,  ((1u128 << 127) - 1) * 2 + 1);
+    assert_eq!( std::mem::size_of::<u128>(), 16);
+
+    // 20! is the last factorial a u64 holds; u128 goes on to 34!
+    assert_eq!(factorial(20), Some(2_432_902_008_176_640_000));
+    assert_eq!(factorial(20), Some((1..=20u64).product::<u64>() as u128));
+    assert_eq!(factorial(21).map(|f| f > u64::MAX as u128), Some(true));
+    assert_eq!(factorial(34), Some(295_232_799_039_604_140_847_618_609_643_520_000_000));
+    assert_eq!(factorial(35), None);
+
+    // (2^64 - 1)^2 = 2^128 - 2^65 + 1: the high half of the product is u64::MAX - 1
+    let product = mul_wide(u64::MAX, u64::MAX);
+    assert_eq!(product, u128::MAX - (1 << 65) + 2);
+    assert_eq!(((product >> 64) as u64, product as u64), (u64::MAX - 1, 1));
+    assert_eq!(u64::MAX.checked_mul(u64::MAX), None);
+    assert_eq!(mul_wide(1 << 32, 1 << 32), 1 << 64);
+}
+
+// 11.3 NonZeroU32, NonZeroUsize and the rest can't hold 0, so Option uses 0 for None:
+//      Option<NonZeroU32> is as small as a u32, where Option<u32> needs a tag beside it.
+//      That unused value is called a niche; references and Box have one too, null.
+//      Dividing by a NonZero can't panic, so the check for 0 moves to where it's made.
+/// The average of `total` over `count` things, which can't be no things.
+#[allow(dead_code)]
+fn average(total: u32, count: NonZeroU32) -> u32 {
+    total / count
+}
+
+/// How many chunks of `size` it takes to hold `len` items.
+#[allow(dead_code)]
+fn chunks_needed(len: usize, size: NonZeroUsize) -> usize {
+    len.div_ceil(size.get())
+}
+
+#[test]
+fn test_non_zero() {
+    use std::mem::size_of;
+    assert_eq!(size_of::<NonZeroU32>(), size_of::<u32>());
+    assert_eq!(size_of::<Option<NonZeroU32>>(), size_of::<u32>());
+    assert_eq!(size_of::<Option<u32>>(), 2 * size_of::<u32>());
+    assert_eq!(size_of::<Option<NonZeroUsize>>(), size_of::<usize>());
+    assert_eq!(size_of::<Option<usize>>(), 2 * size_of::<usize>());
+    assert_eq!(size_of::<Option<&u8>>(), size_of::<&u8>());
+    assert_eq!(size_of::<Option<Box<u8>>>(), size_of::<Box<u8>>());
+
+    assert_eq!(NonZeroU32::new(0), None);
+    let three = NonZeroU32::new(3).unwrap();
+    assert_eq!(three.get(), 3);
+    assert_eq!(average(10, three), 3);
+    assert_eq!(NonZeroU32::new(5).map(|n| average(10, n)), Some(2));
+
+    let four = NonZeroUsize::new(4).unwrap();
+    assert_eq!(chunks_needed(0, four), 0);
+    assert_eq!(chunks_needed(8, four), 2);
+    assert_eq!(chunks_needed(9, four), 3);
+}
+
+#[test]
+fn test_floats() {
     // 15.  Foating-Point Type
     //      Rust provides IEEE single/double-precision floating-point types.
     //      Following the IEEE 754-2008 specification
@@ -156,21 +360,33 @@ fn main() {
     assert_eq!(1.61803f32, 1.61803);
     assert_eq!(6.0221e23f64, 6.0221e23);
 
-    assert_eq!(5f32.sqrt() * 5f32.sqrt(), 5.); // exactly 5.0, per IEEE
+    // 15.4 Arithmetic rounds, so what it computes is compared allowing for that, not with
+    //      ==: within an eps, or within a number of ULPs, the floats in between
+    //      (rustfun_common::float). 5f32.sqrt() squared happens to round back to exactly
+    //      5.0, but nothing promises that of a computation in general: 0.1 + 0.2 doesn't
+    //      round to 0.3.
+    assert!(ulps_eq(5f32.sqrt() * 5f32.sqrt(), 5., 1));
+    assert_ne!(0.1 + 0.2, 0.3);
+    assert!(approx_eq(0.1 + 0.2, 0.3, 1e-12));
+    assert_eq!(ulps_between(0.1 + 0.2, 0.3), Some(1));
     assert_eq!(-1.01f64.floor(), -1.0);
-    assert!((-1. / std::f32::INFINITY).is_sign_negative());
+    assert!((-1. / f32::INFINITY).is_sign_negative());
 
     // The standard library’s std::f32 and std::f64 modules define constants for
     // the IEEE-required special values like INFINITY,
     // NEG_INFINITY (negative infinity), NAN (the not-a-number value),
     // and MIN and MAX (the largest and smallest finite values).
-    assert_eq!(std::f32::MIN, -3.4028235_e38_f32);
-    assert_eq!(std::f32::MAX,  3.4028235_e38_f32);
-    assert_eq!(1./std::f32::INFINITY, 0.);
-    assert_eq!(1./std::f32::NEG_INFINITY, -0.);
-    assert_eq!((2.0_f32).sqrt(),1.4142135);
-    assert_eq!(f64::sqrt(2.0),1.4142135623730951);
+    assert_eq!(f32::MIN, -3.4028235_e38_f32);
+    assert_eq!(f32::MAX,  3.4028235_e38_f32);
+    assert_eq!(1./f32::INFINITY, 0.);
+    assert_eq!(1./f32::NEG_INFINITY, -0.);
+    assert!(approx_eq((2.0_f32).sqrt(), 1.4142135, 1e-7));
+    assert!(approx_eq(f64::sqrt(2.0), 1.4142135623730951, 1e-15));
+    assert!(ulps_eq(f64::sqrt(2.0) * f64::sqrt(2.0), 2.0, 1));
+}
 
+#[test]
+fn test_bools_and_chars() {
     // 16.  bool
     // 16.1 as operator can convert bool values to integer types
     // 16.2 However, as won’t convert in the other direction, from numeric types to bool.
@@ -194,7 +410,10 @@ fn main() {
     assert_eq!('8'.to_digit(10), Some(8));
     assert_eq!('ಠ'.len_utf8(), 3);
     assert_eq!(std::char::from_digit(2, 10), Some('2'));
+}
 
+#[test]
+fn test_tuples() {
     // 18.  A tuple is a pair, or triple, or quadruple, ... of values of assorted types.
     // 18.1 Tuples aren’t much like arrays: for one thing, each element of a tuple can have a
     //      different type,
@@ -235,7 +454,10 @@ fn main() {
     // missing the comma caused a mismatched type &str
     // assert_eq!(("lonely hearts",),("lonely hearts")); //mismatched types
     assert_eq!(("lonely hearts",),("lonely hearts",));
+}
 
+#[test]
+fn test_pointers_and_boxes() {
     // 19.  Pointer Types
     // 19.1 A value of type &String (pronounced “ref String”) is a reference to a String
     //      value, a &i32 is a reference to an i32, and so on.
@@ -259,7 +481,10 @@ fn main() {
     let t = (12, "eggs");
     let b = Box::new(t);                   // allocate a tuple in the heap
     assert_eq!(b,Box::new((12,"eggs")));
+}
 
+#[test]
+fn test_arrays() {
     // 21.  Array
     //      The type [T; N] represents an array of N values, each of type T. An array’s
     //      size is a constant determined at compile time, and is part of the type; you
@@ -271,32 +496,16 @@ fn main() {
     assert_eq!(taxonomy.len(), 3);
     // 21.1 For the common case of a long array filled with some value, you can write
     //      [V; N], where V is the value each element should have, and N is the length.
-    let mut sieve = [true; 10000];
-    for i in 2..100 {
-        if sieve[i] {
-            let mut j = i * i;
-            while j < 10000 {
-                sieve[j] = false;
-                j += i;
-            }
-        }
-    }
+    let filled = [true; 10000];
+    assert!(filled.iter().all(|&b| b));
+    // 21.2 The sieve of Eratosthenes that used to be built here on a [true; 10000] array
+    //      is now rustfun_common::sieve, which takes its size as an argument (and so
+    //      uses a Vec, since an array's length must be a constant).
+    let sieve = rustfun_common::sieve(9999);
     assert!(sieve[211]);
     assert!(!sieve[9867]);
-
-    /* print out the primes in the range from 2..10000
-       let mut count=0;
-       for i in 2..10000 {
-       if sieve[i] {
-       count+=1;
-       print!("{:5},",i);
-       if count % 10 == 0 {
-       println!()
-       }
-       }
-       }
-       println!("\n {} primes exist in the range of 2..10000",count);
-       */
+    // the segmented sieve of note 21.3 finds the same primes, a range at a time
+    assert_eq!(rustfun_common::primes_between(9900, 9999).last(), Some(&9973));
 
     // 22.2 use slice method on an array directly
     //      Rust implicitly converts a reference to an array to a slice when searching
@@ -307,7 +516,19 @@ fn main() {
     let mut chaos = [3, 5, 4, 1, 2];
     chaos.sort();
     assert_eq!(chaos, [1, 2, 3, 4, 5]);
+    // 22.3 And once borrowed as a slice, an array, a Vec and a slice are read at the same
+    //      speed, whether by index, by iterator chain or by fold: `cargo bench -p
+    //      basictype` (benches/iteration.rs) times a sum of squares all nine ways.
+}
 
+// build a vector by repeating a given value a certain number of times
+#[allow(dead_code)]
+fn new_pixel_buffer(rows: usize, cols: usize) -> Vec<u8> {
+    vec![0; rows * cols]
+}
+
+#[test]
+fn test_vectors() {
     // 23.  A vector Vec<T> is a resizable array of elements of type T, allocated on the
     //      heap.
     // 23.1 create vectors. the simplest is to use the vec! macro
@@ -321,7 +542,6 @@ fn main() {
     assert_eq!(v.iter().fold(1, |a, b|{a * b}), 30030);
     // 2*((2*(1+2)-1)+3)-1 = 15
     assert_eq!([2,3].iter().fold(1, |a, b|{let i = a + b; 2*i-1}), 15);
-    // build a vector by repeating a given value a certain number of times
     assert_eq!(new_pixel_buffer(2,3),vec![0,0,0,0,0,0]);
 
     // 23.2 Vec::new is equivalent to calling vec! macro
@@ -419,70 +639,10 @@ fn main() {
     let b:Vec<String> = s.iter().map(|s|s.to_string()).collect();
     assert_eq!(a,["1","2"]);
     assert_eq!(b,["1","2"]);
+}
 
-
-    // 23.6  use a for loop to iterate over a vector
-    let v = ["input","1","2","3","4","5"];
-    let numbers: Vec<String> = v.iter().skip(1).map(|s|s.to_string()).collect();
-    for num_str in numbers {
-        let num = num_str.parse::<i32>().unwrap();
-        println!("{}: {}", num,
-                 if num % 2 == 0 {
-                     "even"
-                 } else {
-                     "odd"
-                 });
-    }
-
-    // 24.  Slices
-    // 24.1
-    // automatically converts the &Vec<f64> reference and the &[f64; 4] reference to slice
-    // references that point directly to the data.
-    //
-    //    v                     a                 sa       sv
-    // ------------------------------------------------------------
-    // |*|4|4|      |0.0|0.456|1.0|0.456|        |&|4|    |&|4|
-    // -|-------------|---------------------------|--------|-------  stack
-    //  |             |                           |        |
-    //  +-(1)-+       +---------(2)---------------+        |
-    //        | +-----------------------(3)----------------+
-    //        | |
-    // -------|-|--------------------------------------------------  heap
-    //       |0.0|0.123|1.0|0.123|
-    // ------------------------------------------------------------
-    //
-    // (1) Pointer to vector v in heap (owned by itself)
-    // (2) Reference to array a in stack (non-owning)
-    // (3) Reference to vector v in heap (non-owning)
-
-    let v: Vec<f64> = vec![0.0,  0.123,  1.0,  0.123];
-    let a: [f64; 4] =     [0.0,  0.456,  1.0,  0.456];
-    let sa: &[f64] = &a;
-    let sv: &[f64] = &v;
-    print(&sv);
-    print(&sa);
-
-    // 25.  String
-    // 25.1 String Literals
-    //      If one line of a string ends with a backslash, then the newline character and the
-    //      leading whitespace on the next line are dropped:
-    println!("On the 24th of February, 1815, the look-out at Notre-Dame de\
-        la Garde signalled the three-\
-        master, the Pharaon from Smyrna, Trieste, and Naples.");
-    // 25.2 raw strings need not warry about escape sequences
-    //  raw string using r
-    let default_mac_install_path = r"~/Library/Application Support/";
-    let pattern = Regex::new(r"\d+(\.\d+)*");
-    println!("{}",default_mac_install_path);
-    println!("{:?}",pattern);
-
-    //  raw string using ###
-    println!(r###"
-         This raw string started with 'r###"'.
-         Therefore it does not end until we reach a quote mark ('"')
-         followed immediately by three pound signs ('###'):
-    "###);
-
+#[test]
+fn test_strings() {
     // 25.2 Byte Strings
     let abc = b"ABC";
     assert_eq!(abc, &[b'A', b'B', b'C']);
@@ -578,10 +738,6 @@ fn main() {
     }
 }
 
-fn new_pixel_buffer(rows: usize, cols: usize) -> Vec<u8> {
-    vec![0; rows * cols]
-}
-
 fn print(n: &[f64]) {
     for elt in n {
         print!("{:.3} ", elt);
@@ -589,5 +745,176 @@ fn print(n: &[f64]) {
     println!()
 }
 
+mod cast;
+mod explain;
+mod quiz;
+mod random;
+
+const USAGE: Text = Text {
+    en: "basictype [VALUE CASTS | quiz [COUNT] | primes [LOW] HIGH | random [SEED]]
+With no arguments, the chapter's demo. With them, VALUE is followed through CASTS: its
+type and the types it's cast to, like i16->u8. quiz asks COUNT questions about casts
+and primes, 5 unless given, and reads the answers from stdin. primes lists the primes
+from LOW, or 2, up to HIGH. random draws dice, cards and more from SEED, or a new seed.
+Example: basictype 1000 i16->u8->i32",
+    zh: "basictype [VALUE CASTS | quiz [COUNT] | primes [LOW] HIGH | random [SEED]]
+不带参数时运行本章的演示。带参数时, 显示 VALUE 经过 CASTS 的每一步: 它的类型和
+依次转换成的类型, 如 i16->u8。quiz 出 COUNT 道类型转换和素数的题目, 默认 5 道, 从
+标准输入读取答案。primes 列出从 LOW (默认 2) 到 HIGH 的素数。random 用 SEED (或新的
+种子) 掷骰子、洗牌等。
+示例: basictype 1000 i16->u8->i32",
+};
+
+// The rest only prints, so it stays in main: `cargo run` to see it, `cargo test` for
+// everything the test functions above check.
+fn main() {
+    rustfun_common::init_logging();
+    explain::EXPLANATION.on_request();
+
+    let mut args = Args::from_env(USAGE);
+    match args.optional() {
+        Some(command) if command == "quiz" => return run_quiz(args),
+        Some(command) if command == "primes" => return list_primes(args),
+        Some(command) if command == "random" => return draw_random(args),
+        Some(value) => return explore_casts(value, args),
+        None => {}
+    }
 
+    println!("basic types!");
 
+    // 23.6  use a for loop to iterate over a vector
+    let v = ["input","1","2","3","4","5"];
+    let numbers: Vec<String> = v.iter().skip(1).map(|s|s.to_string()).collect();
+    for num_str in numbers {
+        let num = num_str.parse::<i32>().unwrap();
+        println!("{}: {}", num,
+                 if num % 2 == 0 {
+                     "even"
+                 } else {
+                     "odd"
+                 });
+    }
+
+    // 24.  Slices
+    // 24.1
+    // automatically converts the &Vec<f64> reference and the &[f64; 4] reference to slice
+    // references that point directly to the data.
+    //
+    //    v                     a                 sa       sv
+    // ------------------------------------------------------------
+    // |*|4|4|      |0.0|0.456|1.0|0.456|        |&|4|    |&|4|
+    // -|-------------|---------------------------|--------|-------  stack
+    //  |             |                           |        |
+    //  +-(1)-+       +---------(2)---------------+        |
+    //        | +-----------------------(3)----------------+
+    //        | |
+    // -------|-|--------------------------------------------------  heap
+    //       |0.0|0.123|1.0|0.123|
+    // ------------------------------------------------------------
+    //
+    // (1) Pointer to vector v in heap (owned by itself)
+    // (2) Reference to array a in stack (non-owning)
+    // (3) Reference to vector v in heap (non-owning)
+
+    let v: Vec<f64> = vec![0.0,  0.123,  1.0,  0.123];
+    let a: [f64; 4] =     [0.0,  0.456,  1.0,  0.456];
+    let sa: &[f64] = &a;
+    let sv: &[f64] = &v;
+    print(&sv);
+    print(&sa);
+
+    // 25.  String
+    // 25.1 String Literals
+    //      If one line of a string ends with a backslash, then the newline character and the
+    //      leading whitespace on the next line are dropped:
+    println!("On the 24th of February, 1815, the look-out at Notre-Dame de\
+        la Garde signalled the three-\
+        master, the Pharaon from Smyrna, Trieste, and Naples.");
+    // 25.2 raw strings need not warry about escape sequences
+    //  raw string using r
+    let default_mac_install_path = r"~/Library/Application Support/";
+    let pattern = Regex::new(r"\d+(\.\d+)*");
+    println!("{}",default_mac_install_path);
+    println!("{:?}",pattern);
+
+    //  raw string using ###
+    println!(r###"
+         This raw string started with 'r###"'.
+         Therefore it does not end until we reach a quote mark ('"')
+         followed immediately by three pound signs ('###'):
+    "###);
+}
+
+// 13.3 `basictype 1000 i16->u8` turns note 13 into a tool: the value's bits, and
+//      what each cast in the chain does to them. See cast.rs.
+fn explore_casts(value: String, mut args: Args) {
+    let casts = args.required("CASTS").unwrap_or_else(|e| e.exit());
+    let chain = cast::parse_chain(&casts)
+        .unwrap_or_else(|message| args.error(format!("CASTS: {}", message)).exit());
+    let steps = cast::explore(&value, &chain)
+        .unwrap_or_else(|message| args.error(format!("VALUE: {}", message)).exit());
+    args.finish().unwrap_or_else(|e| e.exit());
+    print!("{}", steps);
+}
+
+// 13.4 `basictype quiz` asks what casts make of random values, and checks the answers
+//      with the same code. See quiz.rs.
+fn run_quiz(mut args: Args) {
+    let count = match args.optional() {
+        Some(count) => count.parse::<usize>().unwrap_or_else(|_| {
+            args.error(tr!("{}: can't parse {:?}", "{}: 无法解析 {:?}", "COUNT", count)).exit()
+        }),
+        None => 5,
+    };
+    args.finish().unwrap_or_else(|e| e.exit());
+    // A fixed seed whose first questions have casts of both kinds and primes.
+    let seed = if rustfun_common::deterministic::is_on() {
+        6
+    } else {
+        std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |since| since.as_nanos() as u64)
+    };
+    let stdin = std::io::stdin();
+    if let Err(e) = quiz::run(seed, count, stdin.lock(), std::io::stdout()) {
+        eprintln!("basictype: {}", e);
+        std::process::exit(1);
+    }
+}
+
+// 21.3 `basictype primes 2 9999` prints the primes note 21.2 finds, ten to a line. The
+//      segmented rustfun_common::primes_between keeps a range of big numbers cheap:
+//      `basictype primes 1000000000000 1000000000100` sieves 101 numbers, not 10^12.
+fn list_primes(mut args: Args) {
+    let first = args.required("HIGH").unwrap_or_else(|e| e.exit());
+    let second = args.optional();
+    let number = |name: &str, arg: &str| arg.parse::<usize>().unwrap_or_else(|_| {
+        args.error(tr!("{}: can't parse {:?}", "{}: 无法解析 {:?}", name, arg)).exit()
+    });
+    let (low, high) = match second {
+        Some(high) => (number("LOW", &first), number("HIGH", &high)),
+        None => (2, number("HIGH", &first)),
+    };
+    args.finish().unwrap_or_else(|e| e.exit());
+    let primes = rustfun_common::primes_between(low, high);
+    let width = high.to_string().len();
+    for line in primes.chunks(10) {
+        let line: Vec<String> = line.iter().map(|p| format!("{:>width$}", p)).collect();
+        println!("{}", line.join(" "));
+    }
+    println!("{} primes from {} to {}", primes.len(), low, high);
+}
+
+// 26.  Random numbers: `basictype random 42` draws the same dice, cards and heights every
+//      time, and `basictype random` a new seed each run, printed so the run can be had
+//      again. See random.rs.
+fn draw_random(mut args: Args) {
+    let seed = match args.optional() {
+        Some(seed) => seed.parse::<u64>().unwrap_or_else(|_| {
+            args.error(tr!("{}: can't parse {:?}", "{}: 无法解析 {:?}", "SEED", seed)).exit()
+        }),
+        None if rustfun_common::deterministic::is_on() => 42,
+        None => random::fresh_seed(),
+    };
+    args.finish().unwrap_or_else(|e| e.exit());
+    print!("{}", random::report(seed));
+}
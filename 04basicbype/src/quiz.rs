@@ -0,0 +1,212 @@
+// The cast quiz: `basictype quiz [COUNT]` asks COUNT questions, 5 unless given, mostly
+// about what `as` makes of a value, reads an answer for each from stdin, and keeps score.
+//
+//   $ cargo run -q -p basictype -- quiz 2 --deterministic
+//   1. Is 871 prime? no
+//   Right.
+//   2. What is -12_i8 as u8? -12
+//   No: -12_i8 as u8 is 244_u8, the same width, so the same bits, read anew.
+//   Score: 1 of 2
+//
+// The answers are worked out by cast.rs, which the tests there check against `as`, and
+// by rustfun_common's sieve. A cast's answer can be written with or without the type, as
+// 244 or 244_u8; a prime's is yes or no. With --deterministic the questions are the same
+// on every run.
+//
+use std::io::{self, BufRead, Write};
+
+use crate::cast::{self, IntType, Value, TYPES};
+
+// 1.  No rand crate for a quiz: splitmix64 is a few lines, and random enough. Its state
+//     just counts up by a large odd number; the output mixes it with shifts and
+//     multiplies that are meant to wrap, so they're wrapping_ (note 6.1).
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number below `n`. Taking the remainder favours the small numbers a little, by
+    /// far too little to matter for a dozen choices.
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+}
+
+#[test]
+fn test_rng() {
+    // splitmix64's first outputs from 0, as published with it.
+    let mut rng = Rng(0);
+    assert_eq!(rng.next(), 0xe220_a839_7b1d_cdaf);
+    assert_eq!(rng.next(), 0x6e78_9e6a_a1b9_65f4);
+    assert!((0..1000).all(|_| rng.below(6) < 6));
+}
+
+/// A question: "What is `value` as `to`?", or "Is `n` prime?"
+#[derive(Debug, Clone, Copy)]
+pub enum Question {
+    Cast { value: Value, to: IntType },
+    Prime(usize),
+}
+
+impl Question {
+    // 2.  Cast questions use the 8-, 16- and 32-bit types, whose values are short enough
+    //     to type. The value has between half and all of its type's bits, so that most
+    //     casts to a narrower type lose some, and a signed one is negative half the time.
+    // 3.  One question in four is about a number below 10000 instead, from the sieve of
+    //     note 21.
+    fn random(rng: &mut Rng) -> Question {
+        if rng.below(4) == 0 {
+            return Question::Prime(2 + rng.below(9998) as usize);
+        }
+        let types: Vec<IntType> = TYPES.iter().copied().filter(|t| t.bits <= 32).collect();
+        let from = types[rng.below(types.len() as u64) as usize];
+        let others: Vec<IntType> = types.iter().copied().filter(|&t| t != from).collect();
+        let to = others[rng.below(others.len() as u64) as usize];
+        let width = from.bits / 2 + 1 + rng.below(from.bits as u64 / 2) as u32;
+        let mut bits = rng.next() as u128 & ((1 << width) - 1);
+        if from.signed && rng.below(2) == 0 {
+            bits |= !0 << width;
+        }
+        Question::Cast { value: Value::from_bits(from, bits), to }
+    }
+
+    /// Whether `answer` is right: the value the cast makes, with or without its type, or
+    /// yes or no.
+    fn is_right(&self, answer: &str) -> bool {
+        let answer = answer.trim();
+        match *self {
+            Question::Cast { value, to } => {
+                let number = answer.strip_suffix(to.name)
+                    .map(|n| n.strip_suffix('_').unwrap_or(n))
+                    .unwrap_or(answer);
+                Value::parse(number, to) == Ok(cast::cast(value, to).0)
+            }
+            Question::Prime(n) => match answer.to_lowercase().as_str() {
+                "y" | "yes" => is_prime(n),
+                "n" | "no" => !is_prime(n),
+                _ => false,
+            },
+        }
+    }
+
+    /// The right answer, and why.
+    fn explain(&self) -> String {
+        match *self {
+            Question::Cast { value, to } => {
+                let (result, change) = cast::cast(value, to);
+                format!("{}_{} as {} is {}_{}, {}", value, value.ty.name, to.name,
+                        result, to.name, change)
+            }
+            Question::Prime(n) => match smallest_factor(n) {
+                None => format!("{} is a prime", n),
+                Some(factor) => format!("{} is {} x {}", n, factor, n / factor),
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for Question {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            Question::Cast { value, to } =>
+                write!(f, "What is {}_{} as {}?", value, value.ty.name, to.name),
+            Question::Prime(n) => write!(f, "Is {} prime?", n),
+        }
+    }
+}
+
+fn is_prime(n: usize) -> bool {
+    rustfun_common::primes_between(n, n) == [n]
+}
+
+/// The smallest prime factor of `n`, if it isn't a prime itself.
+fn smallest_factor(n: usize) -> Option<usize> {
+    rustfun_common::primes_up_to(n.isqrt()).into_iter().find(|&p| n.is_multiple_of(p))
+}
+
+#[test]
+fn test_question() {
+    let question = Question::Cast { value: Value::parse("65535", "u32".parse().unwrap()).unwrap(),
+                                    to: "i16".parse().unwrap() };
+    assert_eq!(question.to_string(), "What is 65535_u32 as i16?");
+    for right in ["-1", " -1\n", "-1_i16", "-1i16"] {
+        assert!(question.is_right(right), "{:?}", right);
+    }
+    for wrong in ["65535", "1", "-1_u16", "", "minus one"] {
+        assert!(!question.is_right(wrong), "{:?}", wrong);
+    }
+    assert_eq!(question.explain(),
+               "65535_u32 as i16 is -1_i16, truncated, keeping the low 16 of 32 bits");
+
+    assert_eq!(Question::Prime(211).to_string(), "Is 211 prime?");
+    assert!(Question::Prime(211).is_right("yes") && Question::Prime(211).is_right("Y"));
+    assert!(!Question::Prime(211).is_right("no") && !Question::Prime(211).is_right("211"));
+    assert!(Question::Prime(9867).is_right("n"));
+    assert_eq!(Question::Prime(9867).explain(), "9867 is 3 x 3289");
+    assert_eq!(Question::Prime(9973).explain(), "9973 is a prime");
+
+    let mut rng = Rng(7);
+    for _ in 0..100 {
+        match Question::random(&mut rng) {
+            Question::Cast { value, to } => {
+                assert_ne!(value.ty, to);
+                assert!(value.ty.bits <= 32 && to.bits <= 32);
+            }
+            Question::Prime(n) => assert!((2..10000).contains(&n)),
+        }
+    }
+}
+
+/// Ask `count` questions, reading answers from `input`, and return how many were right
+/// out of how many were answered: the quiz ends early at the end of the input.
+pub fn run(seed: u64, count: usize, input: impl BufRead, mut output: impl Write)
+           -> io::Result<(usize, usize)> {
+    let mut rng = Rng(seed);
+    let mut answers = input.lines();
+    let (mut right, mut asked) = (0, 0);
+    for n in 1..=count {
+        let question = Question::random(&mut rng);
+        write!(output, "{}. {} ", n, question)?;
+        output.flush()?;
+        let answer = match answers.next() {
+            Some(answer) => answer?,
+            None => {
+                writeln!(output)?;
+                break;
+            }
+        };
+        asked += 1;
+        if question.is_right(&answer) {
+            right += 1;
+            writeln!(output, "Right.")?;
+        } else {
+            writeln!(output, "No: {}.", question.explain())?;
+        }
+    }
+    writeln!(output, "Score: {} of {}", right, asked)?;
+    Ok((right, asked))
+}
+
+#[test]
+fn test_run() {
+    // Answer the first question right and the second wrong, whatever they are.
+    let mut rng = Rng(42);
+    let first = Question::random(&mut rng);
+    let answer = match first {
+        Question::Cast { value, to } => cast::cast(value, to).0.to_string(),
+        Question::Prime(n) => if is_prime(n) { "yes" } else { "no" }.to_string(),
+    };
+    let mut output = Vec::new();
+    let score = run(42, 3, format!("{}\nno idea\n", answer).as_bytes(), &mut output).unwrap();
+    assert_eq!(score, (1, 2));
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.starts_with(&format!("1. {} Right.\n2. ", first)));
+    assert!(output.contains("? No: "));
+    assert!(output.ends_with("Score: 1 of 2\n"));
+}
@@ -0,0 +1,165 @@
+// Random numbers with the rand crate: `basictype random [SEED]` rolls dice, shuffles a
+// deck of cards, draws heights from a normal distribution and weather by weight, all
+// from one seed.
+//
+//   $ cargo run -q -p basictype -- random 42
+//   seed 42
+//   dice: 1 4 2 4 6 4 6 3 6 1
+//   hand: 2♠ 10♦ 3♣ 3♠ 2♦
+//   heights: 185.5 174.1 186.2 178.4 185.4
+//   week: sun rain sun rain sun sun sun
+//   any card: 2♣
+//
+// The quiz (quiz.rs) gets by with a dozen lines of splitmix64; this is what a program
+// uses when the numbers matter: generators that are fast or secure, ranges without the
+// bias of `% n`, and distributions beyond the uniform one.
+//
+// Note:
+//
+// 1.) A generator made with seed_from_u64(seed) gives the same numbers on every run, so a
+//     test can pin them, and a run with a surprising result can be repeated: the seed is
+//     printed first. rand::rng(), the thread's own generator, seeds itself from the
+//     operating system instead, and is never the same twice.
+// 2.) StdRng is the same from run to run, but not promised to be the same from one
+//     version of rand to the next; its algorithm has changed with the 0.x versions
+//     before. rand = "0.9" in Cargo.toml keeps to one, and test_pinned_to_seed will say
+//     if that ever isn't enough. Numbers that must last, in a file format say, want a
+//     named algorithm, like rand_chacha's ChaCha8Rng.
+//
+use rand::distr::weighted::WeightedIndex;
+use rand::rngs::StdRng;
+use rand::seq::{IndexedRandom, SliceRandom};
+use rand::{Rng, SeedableRng};
+use rand_distr::{Distribution, Normal};
+
+/// A generator that gives the same numbers for the same `seed`.
+pub fn seeded(seed: u64) -> StdRng {
+    StdRng::seed_from_u64(seed)
+}
+
+/// A seed to start from, when none is given: itself random.
+pub fn fresh_seed() -> u64 {
+    rand::rng().random()
+}
+
+// 1.  random_range takes a range, as in the for loops of chapter 7, and its numbers are
+//     spread evenly across it: no value of 1..=6 comes up more often than another.
+/// `count` rolls of a six-sided die.
+pub fn roll_dice(rng: &mut impl Rng, count: usize) -> Vec<u8> {
+    (0..count).map(|_| rng.random_range(1..=6)).collect()
+}
+
+/// A deck of 52 cards, as "A♠" to "K♣", shuffled.
+pub fn shuffled_deck(rng: &mut impl Rng) -> Vec<String> {
+    let mut deck: Vec<String> = ["♠", "♥", "♦", "♣"].iter()
+        .flat_map(|suit| {
+            ["A", "2", "3", "4", "5", "6", "7", "8", "9", "10", "J", "Q", "K"].iter()
+                .map(move |rank| format!("{}{}", rank, suit))
+        })
+        .collect();
+    // 2.  shuffle is a Fisher-Yates shuffle, in place: every order equally likely.
+    deck.shuffle(rng);
+    deck
+}
+
+// 3.  A Distribution says how likely each value is; sample draws one. Normal is the bell
+//     curve, from rand_distr: most values near the mean, fewer the further away.
+/// `count` values from the normal distribution around `mean`, `sd` wide.
+pub fn normal_samples(rng: &mut impl Rng, mean: f64, sd: f64, count: usize) -> Vec<f64> {
+    let normal = Normal::new(mean, sd).expect("the standard deviation is finite and >= 0");
+    normal.sample_iter(rng).take(count).collect()
+}
+
+/// One of `choices`, each as likely as its weight says.
+pub fn weighted_choice<'a, T>(rng: &mut impl Rng, choices: &'a [(T, u32)]) -> Option<&'a T> {
+    let weights = WeightedIndex::new(choices.iter().map(|(_, weight)| *weight)).ok()?;
+    Some(&choices[weights.sample(rng)].0)
+}
+
+/// What `basictype random SEED` prints.
+pub fn report(seed: u64) -> String {
+    let mut rng = seeded(seed);
+    let dice = roll_dice(&mut rng, 10);
+    let deck = shuffled_deck(&mut rng);
+    let heights = normal_samples(&mut rng, 170.0, 10.0, 5);
+    let weather = [("sun", 6), ("rain", 3), ("snow", 1)];
+    let forecast: Vec<&str> = (0..7)
+        .map(|_| *weighted_choice(&mut rng, &weather).unwrap())
+        .collect();
+    let dice: Vec<String> = dice.iter().map(u8::to_string).collect();
+    let heights: Vec<String> = heights.iter().map(|h| format!("{:.1}", h)).collect();
+    format!("seed {}\ndice: {}\nhand: {}\nheights: {}\nweek: {}\nany card: {}\n",
+            seed, dice.join(" "), deck[..5].join(" "), heights.join(" "), forecast.join(" "),
+            deck.choose(&mut rng).unwrap())
+}
+
+#[test]
+fn test_same_seed_same_numbers() {
+    assert_eq!(roll_dice(&mut seeded(7), 20), roll_dice(&mut seeded(7), 20));
+    assert_ne!(roll_dice(&mut seeded(7), 20), roll_dice(&mut seeded(8), 20));
+    assert_eq!(report(42), report(42));
+    // One generator goes on from where it is: the second ten rolls aren't the first ten.
+    let mut rng = seeded(7);
+    assert_ne!(roll_dice(&mut rng, 10), roll_dice(&mut rng, 10));
+}
+
+#[test]
+fn test_pinned_to_seed() {
+    // What seed 42 gives with rand 0.9 (note 2): a change here means rand changed its
+    // StdRng, not that this code is wrong.
+    assert_eq!(roll_dice(&mut seeded(42), 10), [1, 4, 2, 4, 6, 4, 6, 3, 6, 1]);
+    assert_eq!(report(42), "\
+seed 42
+dice: 1 4 2 4 6 4 6 3 6 1
+hand: 2♠ 10♦ 3♣ 3♠ 2♦
+heights: 185.5 174.1 186.2 178.4 185.4
+week: sun rain sun rain sun sun sun
+any card: 2♣
+");
+}
+
+#[test]
+fn test_ranges() {
+    let mut rng = seeded(1);
+    let rolls = roll_dice(&mut rng, 6000);
+    assert!(rolls.iter().all(|r| (1..=6).contains(r)));
+    for face in 1..=6 {
+        let count = rolls.iter().filter(|&&r| r == face).count();
+        assert!((800..1200).contains(&count), "{} came up {} times", face, count);
+    }
+    let x: f64 = rng.random_range(-1.0..1.0);
+    assert!((-1.0..1.0).contains(&x));
+}
+
+#[test]
+fn test_shuffle() {
+    let deck = shuffled_deck(&mut seeded(3));
+    assert_eq!(deck.len(), 52);
+    let mut sorted = deck.clone();
+    sorted.sort();
+    sorted.dedup();
+    assert_eq!(sorted.len(), 52);   // every card once
+    assert!(deck.contains(&"10♥".to_string()));
+    assert_ne!(deck, shuffled_deck(&mut seeded(4)));
+}
+
+#[test]
+fn test_distributions() {
+    let heights = normal_samples(&mut seeded(5), 170.0, 10.0, 10_000);
+    let mean = heights.iter().sum::<f64>() / heights.len() as f64;
+    let var = heights.iter().map(|h| (h - mean).powi(2)).sum::<f64>() / heights.len() as f64;
+    assert!((mean - 170.0).abs() < 0.5, "mean {}", mean);
+    assert!((var.sqrt() - 10.0).abs() < 0.5, "sd {}", var.sqrt());
+    // about 68% within one standard deviation
+    let within = heights.iter().filter(|h| (160.0..180.0).contains(*h)).count();
+    assert!((6600..7000).contains(&within), "{}", within);
+
+    let mut rng = seeded(6);
+    let choices = [("often", 9), ("rarely", 1), ("never", 0)];
+    let picks: Vec<&str> = (0..1000).map(|_| *weighted_choice(&mut rng, &choices).unwrap()).collect();
+    let often = picks.iter().filter(|&&p| p == "often").count();
+    assert!((850..950).contains(&often), "{}", often);
+    assert!(!picks.contains(&"never"));
+    assert_eq!(weighted_choice(&mut rng, &[("none", 0)]), None);
+    assert_eq!(weighted_choice::<&str>(&mut rng, &[]), None);
+}
@@ -0,0 +1,74 @@
+// Run the chapter's demo binary and check the parts of its output that are deterministic.
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+#[test]
+fn prints_the_demo() {
+    Command::cargo_bin("basictype").unwrap().assert()
+        .success()
+        .stdout(predicate::str::starts_with("basic types!\n1: odd\n2: even\n3: odd\n4: even\n5: odd\n")
+            .and(predicate::str::contains("0.000 0.456 1.000 0.456 \n"))
+            .and(predicate::str::contains("Notre-Dame dela Garde"))
+            .and(predicate::str::contains("This raw string started with 'r###\"'.")));
+}
+
+#[test]
+fn follows_a_value_through_casts() {
+    Command::cargo_bin("basictype").unwrap().args(["-1", "i8->u16->i32"]).assert()
+        .success()
+        .stdout("    -1_i8                                1111_1111
+as u16: sign-extended, with 8 new bits copied from the sign bit, 1
+65535_u16                      1111_1111_1111_1111
+as i32: zero-extended, with 16 new bits of 0
+65535_i32  0000_0000_0000_0000_1111_1111_1111_1111
+");
+}
+
+#[test]
+fn says_which_value_does_not_fit() {
+    Command::cargo_bin("basictype").unwrap().args(["1000", "i8->u8"]).assert()
+        .failure()
+        .stderr(predicate::str::starts_with("basictype: VALUE: 1000 doesn't fit in i8\nUsage: basictype [VALUE CASTS | quiz [COUNT] | primes [LOW] HIGH | random [SEED]]\n"));
+}
+
+#[test]
+fn quizzes_on_casts() {
+    Command::cargo_bin("basictype").unwrap().args(["quiz", "4", "--deterministic"])
+        .write_stdin("no\n244\n5\n")
+        .assert()
+        .success()
+        .stdout("1. Is 871 prime? Right.
+2. What is -12_i8 as u8? Right.
+3. What is 212_u16 as i32? No: 212_u16 as i32 is 212_i32, zero-extended, with 16 new bits of 0.
+4. What is -1424_i16 as i8? 
+Score: 2 of 3
+");
+}
+
+#[test]
+fn lists_primes() {
+    Command::cargo_bin("basictype").unwrap().args(["primes", "30"]).assert()
+        .success()
+        .stdout(" 2  3  5  7 11 13 17 19 23 29\n10 primes from 2 to 30\n");
+    Command::cargo_bin("basictype").unwrap().args(["primes", "1000000000000", "1000000000100"])
+        .assert()
+        .success()
+        .stdout("1000000000039 1000000000061 1000000000063 1000000000091\n\
+                 4 primes from 1000000000000 to 1000000000100\n");
+}
+
+#[test]
+fn draws_the_same_from_a_seed() {
+    let draw = |args: &[&str]| {
+        let output = Command::cargo_bin("basictype").unwrap().args(args).output().unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+    let drawn = draw(&["random", "42"]);
+    assert!(drawn.starts_with("seed 42\ndice: 1 4 2 4 6 4 6 3 6 1\n"));
+    assert_eq!(draw(&["random", "--deterministic"]), drawn);
+    // A new seed each run, printed so that it can be given back.
+    let fresh = draw(&["random"]);
+    let seed = fresh.lines().next().unwrap().strip_prefix("seed ").unwrap();
+    assert_eq!(draw(&["random", seed]), fresh);
+}
@@ -0,0 +1,341 @@
+// An arena: one Vec that owns every node of a tree, and Ids that stand for the nodes.
+//
+//   let mut tree = Tree::new();
+//   let home = tree.add(tree.root(), "home");
+//   let alice = tree.add(home, "alice");
+//   tree.path(alice)  ->  "/home/alice"
+//
+// Note:
+//
+// 1.) The arena is the one owner. Nodes point at each other with Ids, plain indices that
+//     own nothing, so a child can name its parent without Rc, RefCell or Weak (compare
+//     link_a_and_b in main.rs), and there's no cycle to leak: dropping the arena drops
+//     every node in it, once.
+// 2.) get hands out a &T that borrows the arena. The borrow checker keeps it from
+//     outliving the arena, or being held across an alloc or remove that might move or
+//     drop what it points to (tests/ui/alloc_while_borrowed.rs).
+// 3.) An Id can outlive its value, being only a number. Each slot counts the values it
+//     has held, and an Id keeps the count it was made with, so an old Id to a slot that
+//     has been reused gets None, not whatever lives there now.
+//
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{Index, IndexMut};
+
+/// Where a value lives in an `Arena<T>`: an index, and which of the slot's values.
+pub struct Id<T> {
+    index: usize,
+    generation: u32,
+    // 1.  An Id<T> only fits an Arena<T>, though it holds no T: PhantomData says which T.
+    //     fn() -> T, rather than T, keeps an Id Copy, Send and Sync whatever T is.
+    _type: PhantomData<fn() -> T>,
+}
+
+// 2.  #[derive] would ask for T: Copy, T: PartialEq and so on; an Id is a pair of
+//     numbers whatever T is, so these are written out.
+impl<T> Clone for Id<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Id<T> {}
+
+impl<T> PartialEq for Id<T> {
+    fn eq(&self, other: &Self) -> bool {
+        (self.index, self.generation) == (other.index, other.generation)
+    }
+}
+
+impl<T> Eq for Id<T> {}
+
+impl<T> fmt::Debug for Id<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Id({}#{})", self.index, self.generation)
+    }
+}
+
+enum Slot<T> {
+    Full { generation: u32, value: T },
+    // 3.  A free slot is a link in the list of free slots, so alloc finds one without a
+    //     search, and remembers its generation for the next value (note 3).
+    Free { generation: u32, next_free: Option<usize> },
+}
+
+/// Values of one type, owned together, and reached by `Id`.
+pub struct Arena<T> {
+    slots: Vec<Slot<T>>,
+    free: Option<usize>,
+    len: usize,
+}
+
+impl<T> Default for Arena<T> {
+    fn default() -> Self {
+        Arena { slots: Vec::new(), free: None, len: 0 }
+    }
+}
+
+impl<T> Arena<T> {
+    pub fn new() -> Arena<T> {
+        Arena::default()
+    }
+
+    /// How many values the arena holds.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// How many slots it has, full or free: what its Vec has grown to.
+    pub fn slots(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Move `value` into the arena, into a free slot if there is one.
+    pub fn alloc(&mut self, value: T) -> Id<T> {
+        self.len += 1;
+        let Some(index) = self.free else {
+            self.slots.push(Slot::Full { generation: 0, value });
+            return Id { index: self.slots.len() - 1, generation: 0, _type: PhantomData };
+        };
+        let Slot::Free { generation, next_free } = self.slots[index] else {
+            unreachable!("slot {} is on the free list but full", index);
+        };
+        let generation = generation + 1;
+        self.free = next_free;
+        self.slots[index] = Slot::Full { generation, value };
+        Id { index, generation, _type: PhantomData }
+    }
+
+    /// The value `id` stands for, if it's still here.
+    pub fn get(&self, id: Id<T>) -> Option<&T> {
+        match self.slots.get(id.index)? {
+            Slot::Full { generation, value } if *generation == id.generation => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, id: Id<T>) -> Option<&mut T> {
+        match self.slots.get_mut(id.index)? {
+            Slot::Full { generation, value } if *generation == id.generation => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Move the value `id` stands for back out, and free its slot for the next alloc.
+    pub fn remove(&mut self, id: Id<T>) -> Option<T> {
+        self.get(id)?;
+        let free = Slot::Free { generation: id.generation, next_free: self.free };
+        let Slot::Full { value, .. } = std::mem::replace(&mut self.slots[id.index], free) else {
+            unreachable!("get found slot {} full", id.index);
+        };
+        self.free = Some(id.index);
+        self.len -= 1;
+        Some(value)
+    }
+}
+
+impl<T> Index<Id<T>> for Arena<T> {
+    type Output = T;
+
+    fn index(&self, id: Id<T>) -> &T {
+        self.get(id).unwrap_or_else(|| panic!("{:?} is no longer in the arena", id))
+    }
+}
+
+impl<T> IndexMut<Id<T>> for Arena<T> {
+    fn index_mut(&mut self, id: Id<T>) -> &mut T {
+        self.get_mut(id).unwrap_or_else(|| panic!("{:?} is no longer in the arena", id))
+    }
+}
+
+/// A node of a `Tree`: its name, and the Ids of the nodes around it.
+pub struct TreeNode {
+    pub name: String,
+    pub parent: Option<Id<TreeNode>>,
+    pub children: Vec<Id<TreeNode>>,
+}
+
+/// A tree of names, like a file system's, kept in an arena.
+pub struct Tree {
+    nodes: Arena<TreeNode>,
+    root: Id<TreeNode>,
+}
+
+impl Tree {
+    /// A tree of one node, the root, called "/".
+    pub fn new() -> Tree {
+        let mut nodes = Arena::new();
+        let root = nodes.alloc(TreeNode { name: "/".to_string(), parent: None, children: Vec::new() });
+        Tree { nodes, root }
+    }
+
+    pub fn root(&self) -> Id<TreeNode> {
+        self.root
+    }
+
+    pub fn nodes(&self) -> &Arena<TreeNode> {
+        &self.nodes
+    }
+
+    /// Add a node called `name` below `parent`.
+    pub fn add(&mut self, parent: Id<TreeNode>, name: &str) -> Id<TreeNode> {
+        let child = self.nodes.alloc(TreeNode {
+            name: name.to_string(),
+            parent: Some(parent),
+            children: Vec::new(),
+        });
+        self.nodes[parent].children.push(child);
+        child
+    }
+
+    /// The names from below the root down to `id`, each after a '/'.
+    pub fn path(&self, id: Id<TreeNode>) -> String {
+        let mut names = Vec::new();
+        let mut at = id;
+        while let Some(parent) = self.nodes[at].parent {
+            names.push(self.nodes[at].name.as_str());
+            at = parent;
+        }
+        names.reverse();
+        format!("/{}", names.join("/"))
+    }
+
+    /// Remove `id` and everything below it, and say how many nodes that was. None for
+    /// the root, which the tree can't be without, or an Id that's already gone.
+    pub fn remove(&mut self, id: Id<TreeNode>) -> Option<usize> {
+        let parent = self.nodes.get(id)?.parent?;
+        self.nodes[parent].children.retain(|&child| child != id);
+        let mut removed = 0;
+        let mut doomed = vec![id];
+        // 4.  remove moves each node out of the arena; it's dropped at the end of the
+        //     loop body, once its children's Ids have been taken out of it.
+        while let Some(id) = doomed.pop() {
+            let node = self.nodes.remove(id).expect("a child of a node in the tree");
+            doomed.extend(node.children);
+            removed += 1;
+        }
+        Some(removed)
+    }
+
+    /// The tree, one node a line, indented by depth.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let mut stack = vec![(self.root, 0)];
+        while let Some((id, depth)) = stack.pop() {
+            let node = &self.nodes[id];
+            out += &format!("{}{}\n", "  ".repeat(depth), node.name);
+            stack.extend(node.children.iter().rev().map(|&child| (child, depth + 1)));
+        }
+        out
+    }
+}
+
+#[test]
+fn test_alloc_and_get() {
+    let mut arena = Arena::new();
+    let a = arena.alloc("a".to_string());
+    let b = arena.alloc("b".to_string());
+    assert_eq!((arena.len(), arena.slots()), (2, 2));
+    assert_eq!(arena.get(a).map(String::as_str), Some("a"));
+    assert_eq!(arena[b], "b");
+    arena[b].push('!');
+    arena.get_mut(a).unwrap().push('?');
+    assert_eq!((arena[a].as_str(), arena[b].as_str()), ("a?", "b!"));
+    assert_ne!(a, b);
+}
+
+#[test]
+fn test_reuse() {
+    let mut arena = Arena::new();
+    let ids: Vec<Id<i32>> = (0..4).map(|n| arena.alloc(n)).collect();
+    assert_eq!(arena.remove(ids[1]), Some(1));
+    assert_eq!(arena.remove(ids[1]), None);
+    assert_eq!(arena.remove(ids[2]), Some(2));
+    assert_eq!((arena.len(), arena.slots()), (2, 4));
+
+    // The last slot freed is the first reused, and the Vec doesn't grow.
+    let x = arena.alloc(20);
+    let y = arena.alloc(10);
+    let z = arena.alloc(40);
+    assert_eq!((x.index, y.index, z.index), (2, 1, 4));
+    assert_eq!((arena.len(), arena.slots()), (5, 5));
+
+    // Note 3: the old Ids don't reach the new values in their slots.
+    assert_eq!(arena.get(ids[1]), None);
+    assert_eq!(arena.get(ids[2]), None);
+    assert_eq!(arena.get(y), Some(&10));
+    assert_ne!(ids[1], y);
+    assert_eq!(format!("{:?} {:?}", ids[1], y), "Id(1#0) Id(1#1)");
+}
+
+#[test]
+#[should_panic(expected = "Id(0#0) is no longer in the arena")]
+fn test_index_with_a_stale_id() {
+    let mut arena = Arena::new();
+    let id = arena.alloc('x');
+    arena.remove(id);
+    arena.alloc('y');
+    let _ = arena[id];
+}
+
+#[test]
+fn test_drops() {
+    let log = crate::drop_order::Log::new();
+    let mut arena = Arena::new();
+    let a = arena.alloc(log.noisy("a"));
+    arena.alloc(log.noisy("b"));
+    arena.alloc(log.noisy("c"));
+    // remove gives the value back to be owned again; it's dropped when that owner goes
+    let a = arena.remove(a).unwrap();
+    assert!(log.take().is_empty());
+    drop(a);
+    assert_eq!(log.take(), ["a"]);
+    // reusing a's slot drops nothing: it was already empty
+    arena.alloc(log.noisy("d"));
+    assert!(log.take().is_empty());
+    // and the arena drops what it still holds, each once, in the order of its slots
+    drop(arena);
+    assert_eq!(log.take(), ["d", "b", "c"]);
+}
+
+#[cfg(test)]
+fn sample_tree() -> (Tree, Id<TreeNode>, Id<TreeNode>) {
+    let mut tree = Tree::new();
+    let usr = tree.add(tree.root(), "usr");
+    tree.add(usr, "bin");
+    tree.add(usr, "lib");
+    let home = tree.add(tree.root(), "home");
+    let alice = tree.add(home, "alice");
+    (tree, usr, alice)
+}
+
+#[test]
+fn test_tree() {
+    let (tree, usr, alice) = sample_tree();
+    assert_eq!(tree.render(), "/\n  usr\n    bin\n    lib\n  home\n    alice\n");
+    assert_eq!(tree.path(alice), "/home/alice");
+    assert_eq!(tree.path(usr), "/usr");
+    assert_eq!(tree.path(tree.root()), "/");
+    // Note 1: a child names its parent, and the parent its children, with no cycle of
+    // owners: the arena is the only one.
+    let home = tree.nodes()[alice].parent.unwrap();
+    assert_eq!(tree.nodes()[home].children, [alice]);
+}
+
+#[test]
+fn test_tree_remove() {
+    let (mut tree, usr, alice) = sample_tree();
+    let bin = tree.nodes()[usr].children[0];
+    assert_eq!(tree.remove(usr), Some(3));
+    assert_eq!(tree.remove(usr), None);
+    assert_eq!(tree.remove(bin), None);
+    assert_eq!(tree.remove(tree.root()), None);
+    assert_eq!(tree.render(), "/\n  home\n    alice\n");
+    assert_eq!((tree.nodes().len(), tree.nodes().slots()), (3, 6));
+    // The freed slots are reused, and the Ids from before stay dead.
+    let tmp = tree.add(tree.root(), "tmp");
+    assert_eq!(tree.nodes().slots(), 6);
+    assert!(tree.nodes().get(usr).is_none() && tree.nodes().get(bin).is_none());
+    assert_eq!(tree.path(tmp), "/tmp");
+    assert_eq!(tree.path(alice), "/home/alice");
+}
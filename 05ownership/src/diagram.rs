@@ -0,0 +1,272 @@
+// The diagrams of `ownership draw`: the stack and the heap as the demos run, drawn from
+// the values themselves rather than by hand like the ones in the comments of main.rs.
+//
+//   --- let t = s;
+//   stack  s   (moved)
+//          t   Vec -> @1 (len 3, cap 3)
+//   heap   @1  [0] String -> @2 (len 5, cap 5)
+//              [1] String -> @3 (len 3, cap 3)
+//              [2] String -> @4 (len 5, cap 5)
+//          @2  "alice"
+//          @3  "bob"
+//          @4  "molly"
+//
+// Note:
+//
+// 1.) A heap block is named by its real address: the first one a Diagram sees is @1,
+//     the next @2. So when a move leaves s's buffer in t, t points at the same @1; a
+//     clone gets new blocks, with new names.
+// 2.) The names are kept from one step of a demo to the next, but each demo starts a
+//     Diagram of its own: a block freed in one demo and its address reused in the next
+//     would otherwise look like the same block.
+// 3.) A Vec or String with no capacity has no block: its pointer is dangling, and never
+//     read.
+//
+use std::rc::{Rc, Weak};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// 1.  As with --deterministic, a process-wide setting; main turns it on for `draw`.
+static ON: AtomicBool = AtomicBool::new(false);
+
+pub fn turn_on() {
+    ON.store(true, Ordering::Relaxed);
+}
+
+/// The variables on the stack, and the heap blocks they own, at one step of a demo.
+pub struct Diagram {
+    on: bool,
+    addresses: Vec<usize>,
+    stack: Vec<(String, String)>,
+    heap: Vec<(String, Vec<String>)>,
+}
+
+/// How a value looks where it's stored: on the stack, or inside a block that owns it.
+pub trait Draw {
+    /// Draw the value, adding the heap blocks it owns to `diagram`.
+    fn draw(&self, diagram: &mut Diagram) -> String;
+}
+
+impl Diagram {
+    pub fn new() -> Diagram {
+        Diagram { on: ON.load(Ordering::Relaxed), addresses: Vec::new(), stack: Vec::new(),
+                  heap: Vec::new() }
+    }
+
+    /// The variable `name`, holding `value`.
+    pub fn var<T: Draw>(&mut self, name: &str, value: &T) -> &mut Diagram {
+        let drawn = value.draw(self);
+        self.stack.push((name.to_string(), drawn));
+        self
+    }
+
+    /// The variable `name`, whose value has been moved out.
+    pub fn moved(&mut self, name: &str) -> &mut Diagram {
+        self.stack.push((name.to_string(), "(moved)".to_string()));
+        self
+    }
+
+    /// The block at `address`, whose lines `contents` draws, and its name. A block that
+    /// several pointers lead to is drawn once.
+    pub fn block<F>(&mut self, address: usize, contents: F) -> String
+        where F: FnOnce(&mut Diagram) -> Vec<String>
+    {
+        let label = match self.addresses.iter().position(|&a| a == address) {
+            Some(i) => format!("@{}", i + 1),
+            None => {
+                self.addresses.push(address);
+                format!("@{}", self.addresses.len())
+            }
+        };
+        if !self.heap.iter().any(|(l, _)| *l == label) {
+            let lines = contents(self);
+            self.heap.push((label.clone(), lines));
+        }
+        label
+    }
+
+    /// The diagram so far, under `caption`; the next step starts from an empty one.
+    pub fn render(&mut self, caption: &str) -> String {
+        let width = self.stack.iter().map(|(name, _)| name.len())
+            .chain(self.heap.iter().map(|(label, _)| label.len()))
+            .max().unwrap_or(0);
+        let mut out = format!("--- {}\n", caption);
+        for (i, (name, drawn)) in self.stack.drain(..).enumerate() {
+            let section = if i == 0 { "stack" } else { "" };
+            out.push_str(&format!("{:5}  {:width$}  {}\n", section, name, drawn));
+        }
+        // 2.  Blocks in the order of their names, which is the order the Diagram first
+        //     saw them in, whatever moved where since.
+        self.heap.sort_by_key(|(label, _)| label[1..].parse::<usize>().unwrap_or(0));
+        let mut section = "heap";
+        for (label, lines) in self.heap.drain(..) {
+            for (i, line) in lines.iter().enumerate() {
+                let label = if i == 0 { label.as_str() } else { "" };
+                out.push_str(&format!("{:5}  {:width$}  {}\n", section, label, line));
+                section = "";
+            }
+        }
+        out
+    }
+
+    /// Print the diagram, if `draw` asked for diagrams.
+    pub fn show(&mut self, caption: &str) {
+        let drawn = self.render(caption);
+        if self.on {
+            print!("{}", drawn);
+        }
+    }
+}
+
+impl Default for Diagram {
+    fn default() -> Diagram {
+        Diagram::new()
+    }
+}
+
+macro_rules! draw_as_display {
+    ($($t:ty),*) => {
+        $(impl Draw for $t {
+            fn draw(&self, _: &mut Diagram) -> String {
+                self.to_string()
+            }
+        })*
+    };
+}
+draw_as_display!(i32, u32, char);
+
+impl Draw for String {
+    fn draw(&self, diagram: &mut Diagram) -> String {
+        if self.capacity() == 0 {
+            return "String (len 0, cap 0)".to_string();
+        }
+        let label = diagram.block(self.as_ptr() as usize, |_| vec![format!("{:?}", self)]);
+        format!("String -> {} (len {}, cap {})", label, self.len(), self.capacity())
+    }
+}
+
+// 3.  Elements that own blocks of their own get a line each; plain values share one,
+//     with a _ for each slot of capacity that isn't used yet.
+impl<T: Draw> Draw for Vec<T> {
+    fn draw(&self, diagram: &mut Diagram) -> String {
+        if self.capacity() == 0 {
+            return "Vec (len 0, cap 0)".to_string();
+        }
+        let label = diagram.block(self.as_ptr() as usize, |diagram| {
+            let items: Vec<String> = self.iter().map(|item| item.draw(diagram)).collect();
+            let spare = self.capacity() - self.len();
+            if items.iter().any(|item| item.contains(" -> ")) {
+                let mut lines: Vec<String> = items.iter().enumerate()
+                    .map(|(i, item)| format!("[{}] {}", i, item))
+                    .collect();
+                if spare > 0 {
+                    lines.push(format!("[{}..{}] unused", self.len(), self.capacity()));
+                }
+                lines
+            } else {
+                let slots: Vec<String> = items.into_iter()
+                    .chain(std::iter::repeat_n("_".to_string(), spare))
+                    .collect();
+                vec![format!("[{}]", slots.join(", "))]
+            }
+        });
+        format!("Vec -> {} (len {}, cap {})", label, self.len(), self.capacity())
+    }
+}
+
+// 4.  An Rc points at one block holding the counts and the value, however many Rcs and
+//     Weaks share it. Drawing a Weak reads the counts before upgrading it, since the
+//     upgrade is one more strong reference while it lasts. Once the value is dropped,
+//     weak_count says 0 too, but the block itself stays until the last Weak goes.
+impl<T: Draw> Draw for Rc<T> {
+    fn draw(&self, diagram: &mut Diagram) -> String {
+        let label = diagram.block(Rc::as_ptr(self) as *const u8 as usize, |diagram| {
+            vec![format!("strong {}, weak {}", Rc::strong_count(self), Rc::weak_count(self)),
+                 (**self).draw(diagram)]
+        });
+        format!("Rc -> {}", label)
+    }
+}
+
+impl<T: Draw> Draw for Weak<T> {
+    fn draw(&self, diagram: &mut Diagram) -> String {
+        let label = diagram.block(self.as_ptr() as *const u8 as usize, |diagram| {
+            let counts = format!("strong {}, weak {}", self.strong_count(), self.weak_count());
+            match self.upgrade() {
+                Some(rc) => vec![counts, (*rc).draw(diagram)],
+                None => vec!["strong 0".to_string(), "(value dropped)".to_string()],
+            }
+        });
+        format!("Weak -> {}", label)
+    }
+}
+
+#[test]
+fn test_move_keeps_the_blocks() {
+    let mut diagram = Diagram::new();
+    let s = vec!["alice".to_string(), "bob".to_string()];
+    diagram.var("s", &s);
+    assert_eq!(diagram.render("let s"), "\
+--- let s
+stack  s   Vec -> @1 (len 2, cap 2)
+heap   @1  [0] String -> @2 (len 5, cap 5)
+           [1] String -> @3 (len 3, cap 3)
+       @2  \"alice\"
+       @3  \"bob\"
+");
+    let t = s;
+    let u = t.clone();
+    diagram.moved("s").var("t", &t).var("u", &u);
+    let drawn = diagram.render("let t = s; let u = t.clone();");
+    assert!(drawn.contains("stack  s   (moved)\n       t   Vec -> @1 (len 2, cap 2)\n"));
+    assert!(drawn.contains("       u   Vec -> @4 (len 2, cap 2)\n"));
+    assert!(drawn.contains("       @4  [0] String -> @5 (len 5, cap 5)\n"));
+    // @1 is drawn once, for t, however many times it's pointed to
+    assert_eq!(drawn.matches("@1  [0]").count(), 1);
+}
+
+#[test]
+fn test_spare_capacity() {
+    let mut diagram = Diagram::new();
+    let mut numbers: Vec<i32> = Vec::with_capacity(4);
+    numbers.extend([1, 2]);
+    let mut names: Vec<String> = Vec::with_capacity(2);
+    names.push("x".to_string());
+    diagram.var("numbers", &numbers).var("names", &names).var("empty", &Vec::<i32>::new());
+    assert_eq!(diagram.render("spare"), "\
+--- spare
+stack  numbers  Vec -> @1 (len 2, cap 4)
+       names    Vec -> @2 (len 1, cap 2)
+       empty    Vec (len 0, cap 0)
+heap   @1       [1, 2, _, _]
+       @2       [0] String -> @3 (len 1, cap 1)
+                [1..2] unused
+       @3       \"x\"
+");
+}
+
+#[test]
+fn test_shared_block() {
+    let mut diagram = Diagram::new();
+    let s = Rc::new("shirataki".to_string());
+    let t = s.clone();
+    let w = Rc::downgrade(&s);
+    diagram.var("s", &s).var("t", &t).var("w", &w);
+    assert_eq!(diagram.render("shared"), "\
+--- shared
+stack  s   Rc -> @1
+       t   Rc -> @1
+       w   Weak -> @1
+heap   @1  strong 2, weak 1
+           String -> @2 (len 9, cap 9)
+       @2  \"shirataki\"
+");
+    drop(s);
+    drop(t);
+    diagram.var("w", &w);
+    assert_eq!(diagram.render("dropped"), "\
+--- dropped
+stack  w   Weak -> @1
+heap   @1  strong 0
+           (value dropped)
+");
+}
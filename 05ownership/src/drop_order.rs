@@ -0,0 +1,231 @@
+// When each value is dropped, written down rather than described: every Noisy adds its
+// name to a Log as it's dropped, and each scenario below gives back the Log's order.
+//
+//   $ cargo run -q -p ownership
+//   ...
+//   locals: c, b, a
+//   shadowing: x2, x1
+//   ...
+//
+// Note:
+//
+// 1.) Locals are dropped at the end of their block, last declared first: a later value
+//     may borrow an earlier one, never the other way round, so it has to go first.
+// 2.) The parts of a value go after the value's own Drop, first to last: struct fields
+//     in the order they're declared, tuple and array and Vec elements by index.
+// 3.) A value goes early when its owner is overwritten (the old value, right then), or
+//     moves it away (the new owner drops it), and never if it's handed to mem::forget.
+// 4.) A temporary, the value of an expression that isn't bound to a name, lasts to the
+//     end of its statement; `let _ = ...` binds nothing, so its value is a temporary too.
+//
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Where Noisy values write their names as they're dropped. Clones share one list.
+#[derive(Clone, Default)]
+pub struct Log(Rc<RefCell<Vec<&'static str>>>);
+
+impl Log {
+    pub fn new() -> Log {
+        Log::default()
+    }
+
+    /// A value that writes `name` here when it's dropped.
+    pub fn noisy(&self, name: &'static str) -> Noisy {
+        Noisy { name, log: self.clone() }
+    }
+
+    /// The names written so far, in order; the log starts again empty.
+    pub fn take(&self) -> Vec<&'static str> {
+        self.0.take()
+    }
+}
+
+/// A value with nothing in it but a name, which it writes to its Log when dropped.
+pub struct Noisy {
+    pub name: &'static str,
+    log: Log,
+}
+
+impl Drop for Noisy {
+    fn drop(&mut self) {
+        self.log.0.borrow_mut().push(self.name);
+    }
+}
+
+/// Two Noisy fields, and a Drop of its own that runs before theirs (note 2).
+#[allow(dead_code)] // first and second are there to be dropped, not read
+struct Pair {
+    first: Noisy,
+    second: Noisy,
+    log: Log,
+}
+
+impl Drop for Pair {
+    fn drop(&mut self) {
+        self.log.0.borrow_mut().push("pair");
+    }
+}
+
+pub fn locals() -> Vec<&'static str> {
+    let log = Log::new();
+    {
+        let _a = log.noisy("a");
+        let _b = log.noisy("b");
+        let _c = log.noisy("c");
+    }
+    log.take()
+}
+
+pub fn shadowing() -> Vec<&'static str> {
+    let log = Log::new();
+    {
+        // The second x hides the first, but doesn't drop it: the first still lives,
+        // nameless, to the end of the block, and goes after the second, as note 1 says.
+        let x = log.noisy("x1");
+        assert_eq!(x.name, "x1");
+        let x = log.noisy("x2");
+        assert_eq!(x.name, "x2");
+    }
+    log.take()
+}
+
+pub fn nested_blocks() -> Vec<&'static str> {
+    let log = Log::new();
+    {
+        let _outer = log.noisy("outer");
+        {
+            let _inner = log.noisy("inner");
+        }
+        log.noisy("marker: inner block done");
+    }
+    log.take()
+}
+
+pub fn fields() -> Vec<&'static str> {
+    let log = Log::new();
+    // initialized second field first, but dropped in declaration order
+    let second = log.noisy("second");
+    let first = log.noisy("first");
+    drop(Pair { first, second, log: log.clone() });
+    log.take()
+}
+
+#[allow(clippy::useless_vec)] // a Vec, not an array, is the point here
+pub fn collections() -> Vec<&'static str> {
+    let log = Log::new();
+    {
+        let _v = vec![log.noisy("v0"), log.noisy("v1"), log.noisy("v2")];
+        let _t = (log.noisy("t0"), log.noisy("t1"));
+        let _boxed = Box::new([log.noisy("b0"), log.noisy("b1")]);
+    }
+    log.take()
+}
+
+pub fn reassignment() -> Vec<&'static str> {
+    let log = Log::new();
+    {
+        let mut x = log.noisy("old");
+        assert_eq!(x.name, "old");
+        log.noisy("marker: before");
+        x = log.noisy("new");           // the old value is dropped here, not at the end
+        log.noisy("marker: after");
+        assert_eq!(x.name, "new");
+    }
+    log.take()
+}
+
+pub fn moves() -> Vec<&'static str> {
+    fn consume(_n: Noisy) {}
+    let log = Log::new();
+    {
+        let a = log.noisy("a (into a function)");
+        let b = log.noisy("b (into a closure)");
+        let _c = log.noisy("c (stays)");
+        consume(a);                     // dropped at the end of consume
+        let give_back = move || b;      // owns b until it's called
+        log.noisy("marker: closure made");
+        drop(give_back);                // never called: b goes with the closure
+    }
+    log.take()
+}
+
+pub fn temporaries() -> Vec<&'static str> {
+    let log = Log::new();
+    {
+        let _named = log.noisy("named");
+        let _ = log.noisy("let _");   // never bound: dropped before the next statement
+        let len = log.noisy("temporary").name.len();
+        assert_eq!(len, 9);
+        log.noisy("marker: statements done");
+    }
+    log.take()
+}
+
+pub fn forget() -> Vec<&'static str> {
+    let log = Log::new();
+    {
+        let _kept = log.noisy("kept");
+        std::mem::forget(log.noisy("forgotten"));
+        let mut slot = Some(log.noisy("taken"));
+        drop(slot.take());
+        let _ = slot.replace(log.noisy("replacement"));
+    }
+    log.take()
+}
+
+/// A scenario: it drops some Noisy values, and gives back their names in drop order.
+pub type Scenario = fn() -> Vec<&'static str>;
+
+/// The scenarios, with their names, as main prints them.
+pub const SCENARIOS: [(&str, Scenario); 9] = [
+    ("locals", locals),
+    ("shadowing", shadowing),
+    ("nested blocks", nested_blocks),
+    ("fields", fields),
+    ("collections", collections),
+    ("reassignment", reassignment),
+    ("moves", moves),
+    ("temporaries", temporaries),
+    ("forget", forget),
+];
+
+#[test]
+fn test_locals() {
+    assert_eq!(locals(), ["c", "b", "a"]);
+    assert_eq!(shadowing(), ["x2", "x1"]);
+    assert_eq!(nested_blocks(), ["inner", "marker: inner block done", "outer"]);
+}
+
+#[test]
+fn test_parts() {
+    assert_eq!(fields(), ["pair", "first", "second"]);
+    // the box is last declared, so first to go; inside each, the elements go in order
+    assert_eq!(collections(), ["b0", "b1", "t0", "t1", "v0", "v1", "v2"]);
+}
+
+#[test]
+fn test_early_drops() {
+    assert_eq!(reassignment(), ["marker: before", "old", "marker: after", "new"]);
+    assert_eq!(moves(), ["a (into a function)", "marker: closure made",
+                         "b (into a closure)", "c (stays)"]);
+    assert_eq!(temporaries(), ["let _", "temporary", "marker: statements done", "named"]);
+}
+
+#[test]
+fn test_forget() {
+    // forgotten is never dropped; the Option's old value goes with take, and its new
+    // one at the end of the block, after replace hands back the None it held.
+    assert_eq!(forget(), ["taken", "replacement", "kept"]);
+}
+
+#[test]
+fn test_log() {
+    let log = Log::new();
+    drop(log.noisy("one"));
+    assert_eq!(log.take(), ["one"]);
+    assert!(log.take().is_empty());
+    let clone = log.clone();
+    drop(clone.noisy("two"));
+    assert_eq!(log.take(), ["two"]);
+}
@@ -0,0 +1,88 @@
+// What `ownership --explain` prints: the chapter's notes, in short.
+use rustfun_common::{Explanation, Text, Topic};
+
+pub const EXPLANATION: Explanation = Explanation {
+    chapter: "05ownership",
+    summary: Text {
+        en: "owners, moves, and what the compiler says about them",
+        zh: "所有者、移动, 以及编译器对它们的检查",
+    },
+    topics: &[
+        Topic {
+            title: Text { en: "One owner", zh: "唯一的所有者" },
+            body: Text {
+                en: "Every value has a single owner, and is dropped when its owner goes out of\n\
+                     scope. A Vec on the stack owns its buffer on the heap, and the buffer owns\n\
+                     its elements: ownership forms a tree.",
+                zh: "每个值只有一个所有者, 所有者离开作用域时值就被释放。栈上的 Vec 拥有堆上的\n\
+                     缓冲区, 缓冲区拥有其中的元素: 所有权构成一棵树。",
+            },
+        },
+        Topic {
+            title: Text { en: "Moves", zh: "移动" },
+            body: Text {
+                en: "Assigning a value, passing it to a function or returning it moves it: the\n\
+                     old variable can no longer be used. Moving is cheap, only the few words on\n\
+                     the stack are copied. `ownership draw` shows the heap blocks staying put.",
+                zh: "赋值、传参或返回一个值都会移动它: 原来的变量不能再使用。移动很便宜,\n\
+                     只复制栈上的几个字。`ownership draw` 会显示堆上的内存块原地不动。",
+            },
+        },
+        Topic {
+            title: Text { en: "Copy types", zh: "Copy 类型" },
+            body: Text {
+                en: "Integers, floats, chars, bools, and tuples and arrays of them are Copy:\n\
+                     assignment copies them and leaves the original usable. A struct of your own\n\
+                     is Copy only with #[derive(Clone, Copy)], and only if every field is Copy\n\
+                     and it has no Drop.",
+                zh: "整数、浮点数、char、bool, 以及由它们组成的元组和数组是 Copy 的: 赋值会复制,\n\
+                     原值仍然可用。自定义的结构体要写 #[derive(Clone, Copy)] 才是 Copy 的, 而且\n\
+                     每个字段都必须是 Copy 的, 也不能实现 Drop。",
+            },
+        },
+        Topic {
+            title: Text { en: "Rc for shared ownership", zh: "用 Rc 共享所有权" },
+            body: Text {
+                en: "Rc<T> counts its owners and drops the value when the last one goes. What an\n\
+                     Rc points to is immutable. Two values that hold Rcs to each other are never\n\
+                     dropped, a leak; making one direction a Weak, which doesn't count, fixes it.",
+                zh: "Rc<T> 记录所有者的数量, 最后一个所有者消失时释放值。Rc 指向的值不可修改。\n\
+                     两个值互相持有对方的 Rc 就永远不会被释放, 造成泄漏; 把其中一个方向改成\n\
+                     不计数的 Weak 就能解决。",
+            },
+        },
+        Topic {
+            title: Text { en: "Arenas", zh: "arena" },
+            body: Text {
+                en: "An arena owns many values of one type in one Vec and hands out Ids, plain\n\
+                     indices, to reach them. Nodes that name each other by Id need no Rc, and\n\
+                     can't leak: dropping the arena drops them all. A removed value's slot is\n\
+                     reused, and a generation count keeps old Ids from reaching the new value.",
+                zh: "arena 在一个 Vec 里拥有同一类型的许多值, 并发出 Id (普通的下标) 来访问它们。\n\
+                     用 Id 互相指向的节点不需要 Rc, 也不会泄漏: 释放 arena 就释放了全部节点。\n\
+                     删除的值空出的槽位会被重用, 代数计数让旧的 Id 访问不到新值。",
+            },
+        },
+        Topic {
+            title: Text { en: "Drop order", zh: "释放顺序" },
+            body: Text {
+                en: "Locals are dropped at the end of their block, last declared first; a\n\
+                     value's fields and elements after its own Drop, first to last. Shadowing\n\
+                     drops nothing, assigning drops the old value there and then, and a\n\
+                     temporary, or `let _ = ...`, lasts to the end of its statement.",
+                zh: "局部变量在块结束时释放, 后声明的先释放; 值的字段和元素在它自己的 Drop\n\
+                     之后按从前到后的顺序释放。遮蔽不会释放任何值, 赋值会当场释放旧值, 临时值\n\
+                     以及 `let _ = ...` 的值只活到语句结束。",
+            },
+        },
+        Topic {
+            title: Text { en: "The compiler's view", zh: "编译器的视角" },
+            body: Text {
+                en: "The lines that would break these rules are compile-fail tests in tests/ui/,\n\
+                     checked against the compiler's actual error messages.",
+                zh: "违反这些规则的代码行放在 tests/ui/ 里作为编译失败测试, 并与编译器的实际\n\
+                     错误信息对照。",
+            },
+        },
+    ],
+};
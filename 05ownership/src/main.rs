@@ -4,6 +4,10 @@
 // immediately and it will probably feel uncomfortable at first.
 // -- Mitchell Nordine
 //
+// The lines marked "error" below are commented out so the chapter builds; each one also
+// lives in tests/ui/ as a compile-fail test, checked against the compiler's actual
+// message by `cargo test`.
+//
 // Two Promise: 
 //
 // * You (need to) decide the lifetime of each value in your program.
@@ -20,13 +24,57 @@
 // in C and C++. The difference is that your code has been proven to use them safely.
 //
 //
+// 4.) `ownership draw` runs the same demos, drawing the stack and the heap at each step
+// from the values themselves, so a move can be watched as it happens. See diagram.rs.
+//
+// 5.) An arena owns many values of one type in a single Vec, and hands out Ids to them:
+// a tree whose nodes point both ways, with one owner and no Rc. See arena.rs.
+//
+// 6.) When each value is dropped, checked rather than claimed: drop_order.rs logs the
+// drops of locals, fields, elements, temporaries and moved values, and its tests pin
+// the order.
+//
+mod arena;
+mod diagram;
+mod drop_order;
+mod explain;
+
+use diagram::{Diagram, Draw};
+use rustfun_common::{tr, Args, Text};
+
+const USAGE: Text = Text {
+    en: "ownership [draw]
+With draw, each demo also draws the stack and the heap as it goes: which variable owns
+which heap block, and what a move leaves behind.",
+    zh: "ownership [draw]
+带 draw 时, 每个演示还会随着运行画出栈和堆: 哪个变量拥有哪块堆内存, 以及移动之后
+留下了什么。",
+};
+
 fn main() {
+    rustfun_common::init_logging();
+    explain::EXPLANATION.on_request();
+    let mut args = Args::from_env(USAGE);
+    match args.optional() {
+        Some(mode) if mode == "draw" => diagram::turn_on(),
+        Some(mode) => args.error(tr!("unknown mode {:?}", "未知模式 {:?}", mode)).exit(),
+        None => {}
+    }
+    args.finish().unwrap_or_else(|e| e.exit());
+
     println!("Hello, Ownership!");
 	print_padovan();
     print_person();
+    err_moved_value();
+    clone_moved_value();
     err_move_indexed();
     move_indexed();
     err_use_after_move();
+    copy_and_move();
+    using_rc();
+    rc_cycle();
+    arena_tree();
+    print_drop_orders();
 }
 
 // In Rust, every value has a single owner that determines its lifetime.
@@ -53,13 +101,15 @@ fn main() {
 //   (A Vec 32 on the stack, pointing to its buffer in the heap)
 //
 fn print_padovan() {
+    let mut diagram = Diagram::new();
     let mut padovan = vec![1,1,1];  // allocated here
     for i in 3..10 {
         let next = padovan[i-3] + padovan[i-2];
         padovan.push(next);
     }
+    diagram.var("padovan", &padovan).show("print_padovan: after pushing P(3) to P(9)");
     println!("P(1..10) = {:?}", padovan);
-}                                   // dropped here
+}                                   // dropped here (drop_order.rs, note 1)
 
 //       persons 
 // -------------------------- stack
@@ -76,12 +126,20 @@ fn print_padovan() {
 //      |alice|            |bob|            |molly|
 // ----------------     ----------       ---------------
 //
+#[allow(clippy::vec_init_then_push)] // pushing one at a time is what leaves room for a 4th
 fn print_person() {
     struct Person { name: String, birth: i32 }
+    impl Draw for Person {
+        fn draw(&self, diagram: &mut Diagram) -> String {
+            format!("Person {{ name: {}, birth: {} }}", self.name.draw(diagram), self.birth)
+        }
+    }
+    let mut diagram = Diagram::new();
     let mut persons = Vec::new();
     persons.push(Person { name: "alice".to_string(), birth: 1988 });
     persons.push(Person { name:   "bob".to_string(), birth: 1984 });
     persons.push(Person { name: "molly".to_string(), birth: 1990 });
+    diagram.var("persons", &persons).show("print_person: three pushes");
     for p in &persons {
         println!("{}, born {}", p.name, p.birth);
     }
@@ -120,11 +178,13 @@ fn print_person() {
 //        |alice|   |bob|     |molly|
 //  --------------- --------  -------------
 //
-#[allow(dead_code,unused_variables)]
 fn err_moved_value() {
+   let mut diagram = Diagram::new();
    let s = vec!["alice".to_string(), "bob".to_string(), "molly".to_string()];
+   diagram.var("s", &s).show("err_moved_value: before initializing t");
    let t = s;  
-   //let u = s;  // error : use of moved value 
+   //let u = s;  // error : use of moved value (tests/ui/use_after_move.rs)
+   diagram.moved("s").var("t", &t).show("let t = s;");
 }
 
 //
@@ -143,13 +203,16 @@ fn err_moved_value() {
 //   |alice| |bob| |molly|  |alice| |bob| |molly|
 //  --------------------------------------------------
 //
-#[allow(dead_code,unused_variables)]
 fn clone_moved_value() {
+   let mut diagram = Diagram::new();
    let s = vec!["alice".to_string(), "bob".to_string(), "molly".to_string()];
    let t = s.clone();
    let u = s.clone();
+   diagram.var("s", &s).var("t", &t).var("u", &u)
+       .show("clone_moved_value: let t = s.clone(); let u = s.clone();");
 }
 
+#[allow(clippy::useless_vec)] // indexing a Vec is the point here
 fn err_move_indexed() {
     let x = vec![10, 20, 30];
     let x1 = x[1];
@@ -160,46 +223,131 @@ fn err_move_indexed() {
 		v.push(i.to_string());
 	}
 	// Pull out random elements from the vector.
-	//let third = v[2];  //error : cannot move out of indexed content
+	//let third = v[2];  //error : cannot move out of indexed content (tests/ui/move_out_of_index.rs)
 }
 
 fn move_indexed(){
+	let mut diagram = Diagram::new();
 	// Build a vector of the strings "101", "102", ... "105"
 	let mut v = Vec::new();
 	for i in 101 .. 106 {
 		v.push(i.to_string());
 	}
+	diagram.var("v", &v).show("move_indexed: the strings \"101\" to \"105\"");
 
 	// 1. Pop a value off the end of the vector:
 	let fifth = v.pop().unwrap();
 	assert_eq!(fifth, "105");
+	diagram.var("v", &v).var("fifth", &fifth).show("let fifth = v.pop().unwrap();");
 
 	// 2. Move a value out of the middle of the vector, and move the last
 	// element into its spot:
 	let second = v.swap_remove(1);
 	assert_eq!(second, "102");
+	diagram.var("v", &v).var("fifth", &fifth).var("second", &second)
+	    .show("let second = v.swap_remove(1);");
 
 	// 3. Swap in another value for the one we're taking out:
 	let third = std::mem::replace(&mut v[2], "substitute".to_string());
 	assert_eq!(third, "103");
+	diagram.var("v", &v).var("fifth", &fifth).var("second", &second).var("third", &third)
+	    .show("let third = std::mem::replace(&mut v[2], \"substitute\".to_string());");
 
 	// Let's see what's left of our vector.
 	assert_eq!(v, vec!["101", "104", "substitute"]);
 }
 
 fn err_use_after_move(){
+	let mut diagram = Diagram::new();
 
 	let v = vec!["liberté".to_string(),
 	"égalité".to_string(),
 	"fraternité".to_string()];
+	diagram.var("v", &v).show("err_use_after_move: before the loop");
 
 	for mut s in v {  //value moved here
 		s.push('!');
 		println!("{}", s);
 	}
-	//v.push("a".to_string()); //error: value used after move
+	//v.push("a".to_string()); //error: value used after move (tests/ui/use_after_loop_move.rs)
+	diagram.moved("v").show("after the loop, which took each String out of v and dropped it");
+
+}
+//  Copy types: assigning a value of a Copy type copies it, and the source stays usable.
+//  1. A struct of your own is not Copy until it says so, even when every field is: the
+//     type promises to stay copyable, and adding a String later would break that.
+//  2. #[derive(Clone, Copy)] says so, and needs both: Copy is a marker on top of Clone,
+//     meaning "clone by copying the bits" (tests/ui/copy_without_clone.rs).
+//  3. Copy is only possible when every field is Copy: a struct with a String can't be,
+//     since copying its bits would give two owners of one heap buffer
+//     (tests/ui/copy_with_string_field.rs). Nor can a type with a Drop impl
+//     (tests/ui/copy_with_drop.rs): a copy would be dropped twice.
+//
+//          l               (copied)  print_label's l
+//  ------------------------------------------------ stack
+//        |3|                         |3|
+//  ------------------------------------------------
+//
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Label { number: u32 }
+
+#[derive(Clone, Debug, PartialEq)]
+struct StringLabel { name: String }
+
+impl Draw for Label {
+    fn draw(&self, _: &mut Diagram) -> String {
+        format!("Label {{ number: {} }}", self.number)
+    }
+}
+
+impl Draw for StringLabel {
+    fn draw(&self, diagram: &mut Diagram) -> String {
+        format!("StringLabel {{ name: {} }}", self.name.draw(diagram))
+    }
+}
+
+fn print_label(l: Label) { println!("STAMP: {}", l.number); }
+
+fn print_string_label(l: StringLabel) { println!("STAMP: {}", l.name); }
+
+fn copy_and_move() {
+    let mut diagram = Diagram::new();
+    let l = Label { number: 3 };
+    print_label(l);
+    println!("My label number is: {}", l.number);  // l was copied into print_label
+    let m = l;
+    diagram.var("l", &l).var("m", &m).show("copy_and_move: let m = l; two Labels");
+
+    let s = StringLabel { name: "shirataki".to_string() };
+    let t = s;
+    diagram.moved("s").var("t", &t).show("let t = s; one StringLabel");
+    print_string_label(t.clone());
+    print_string_label(t);        // t moves into the function, and is dropped there
+    //println!("My label name is: {}", t.name); // error: borrow of moved value (tests/ui/use_after_move_into_fn.rs)
+}
+
+#[test]
+fn test_copy_and_move() {
+    // a copy is a value of its own: changing one leaves the other as it was
+    let a = Label { number: 3 };
+    let mut b = a;
+    b.number += 1;
+    assert_eq!((a.number, b.number), (3, 4));
+
+    // a move hands over the same heap buffer; a clone makes a new one
+    let s = StringLabel { name: "shirataki".to_string() };
+    let buffer = s.name.as_ptr();
+    let t = s;
+    assert_eq!(t.name.as_ptr(), buffer);
+    let u = t.clone();
+    assert_ne!(u.name.as_ptr(), buffer);
+    assert_eq!(u, t);
 
+    // what the compiler checks for #[derive(Copy)]: a Copy type has nothing to drop
+    assert!(!std::mem::needs_drop::<Label>());
+    assert!(std::mem::needs_drop::<StringLabel>());
 }
+
 //  Using Rc, which holds a reference count and space for the String.
 //
 //           s    t    u
@@ -215,14 +363,154 @@ fn err_use_after_move(){
 //          |shirataki|
 //  -------------------------
 //
-use std::rc::Rc;
-fn _using_rc(){
+//  1. Cloning an Rc copies the pointer and adds one to the strong count; dropping one
+//     takes one away, and the last drop frees the String.
+//  2. Rc::downgrade makes a Weak, which counts separately and doesn't keep the value
+//     alive: upgrade gives back an Rc while there is one, and None after.
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+/// Print what each step does to the counts, and return them: the step, then strong and
+/// weak.
+fn using_rc() -> Vec<(&'static str, usize, usize)> {
+    let mut diagram = Diagram::new();
+    let counts = |rc: &Rc<String>| (Rc::strong_count(rc), Rc::weak_count(rc));
+    let mut steps = Vec::new();
+    let mut step = |name, (strong, weak)| {
+        println!("{}: strong {}, weak {}", name, strong, weak);
+        steps.push((name, strong, weak));
+    };
+
     // Rust can infer all these types; written out for clarity
     let s: Rc<String> = Rc::new("shirataki".to_string());
-    let _t: Rc<String> = s.clone();
-    let _u: Rc<String> = s.clone();
+    step("let s = Rc::new(..)", counts(&s));
+    let t: Rc<String> = s.clone();
+    step("let t = s.clone()", counts(&s));
+    let u: Rc<String> = s.clone();
+    step("let u = s.clone()", counts(&s));
+    diagram.var("s", &s).var("t", &t).var("u", &u).show("using_rc: three Rcs, one String");
+
+    drop(t);
+    step("drop(t)", counts(&s));
+    let w: Weak<String> = Rc::downgrade(&s);
+    step("let w = Rc::downgrade(&s)", counts(&s));
+    drop(s);
+    drop(u);
+    assert!(w.upgrade().is_none());
+    step("drop(s); drop(u)", (w.strong_count(), w.weak_count()));
+    diagram.var("w", &w).show("drop(s); drop(u); only w is left");
+    steps
+}
+
+#[test]
+fn test_using_rc() {
+    assert_eq!(using_rc(), vec![
+        ("let s = Rc::new(..)", 1, 0),
+        ("let t = s.clone()", 2, 0),
+        ("let u = s.clone()", 3, 0),
+        ("drop(t)", 2, 0),
+        ("let w = Rc::downgrade(&s)", 2, 1),
+        ("drop(s); drop(u)", 0, 0), // a Weak reports no weak count once the value is gone
+    ]);
+}
 
+//  3. Rc values are immutable, so a node that points at another needs a RefCell to be
+//     pointed somewhere after it's made. That is all it takes to build a cycle:
+//
+//           a          b
+//  ------------------------------ stack
+//          |*|        |*|
+//  ---------|----------|---------
+//         +-+        +-+
+//  -------|----------|----------- heap
+//        |2|next:*-->|2|next:*-+
+//         ^----------------------+
+//
+//  4. Each node holds the other's strong count at 1 after a and b are gone, so neither
+//     is ever dropped: the memory leaks, safely, but for good. Rust's promises are
+//     about never using freed memory; a leak breaks none of them.
+//  5. The way out is for one direction to be Weak: b's prev doesn't count, so dropping
+//     a drops a, and with it a's next, the last strong reference to b.
+struct Node {
+    name: &'static str,
+    next: RefCell<Option<Rc<Node>>>,
+    prev: RefCell<Weak<Node>>,
 }
+
+impl Node {
+    fn new(name: &'static str) -> Rc<Node> {
+        Rc::new(Node { name, next: RefCell::new(None), prev: RefCell::new(Weak::new()) })
+    }
+}
+
+impl Drop for Node {
+    fn drop(&mut self) {
+        println!("dropping {}", self.name);
+    }
+}
+
+/// Link a to b, and b back to a, weakly if `weak_back`; return what's left of a and b
+/// once their variables are gone.
+fn link_a_and_b(weak_back: bool) -> (Weak<Node>, Weak<Node>) {
+    let a = Node::new("a");
+    let b = Node::new("b");
+    *a.next.borrow_mut() = Some(b.clone());
+    if weak_back {
+        *b.prev.borrow_mut() = Rc::downgrade(&a);
+    } else {
+        *b.next.borrow_mut() = Some(a.clone());
+    }
+    println!("a: strong {}, weak {}; b: strong {}, weak {}",
+             Rc::strong_count(&a), Rc::weak_count(&a), Rc::strong_count(&b), Rc::weak_count(&b));
+    (Rc::downgrade(&a), Rc::downgrade(&b))
+}
+
+fn rc_cycle() {
+    println!("a.next = b, b.next = a:");
+    let (a, b) = link_a_and_b(false);
+    println!("after a and b go: strong {} and {}, so both leak", a.strong_count(), b.strong_count());
+    println!("a.next = b, b.prev = a, weakly:");
+    let (a, b) = link_a_and_b(true);
+    println!("after a and b go: strong {} and {}", a.strong_count(), b.strong_count());
+}
+
+#[test]
+fn test_rc_cycle() {
+    let (a, b) = link_a_and_b(false);
+    assert_eq!((a.strong_count(), b.strong_count()), (1, 1));
+    assert_eq!(a.upgrade().map(|a| a.name), Some("a")); // still there, with no way to reach it
+    let (a, b) = link_a_and_b(true);
+    assert_eq!((a.strong_count(), b.strong_count()), (0, 0));
+    assert!(a.upgrade().is_none() && b.upgrade().is_none());
+}
+//  6. Or let neither node own the other: put both in an arena, which owns them all, and
+//     let them name each other by Id. Removing a branch drops its nodes there and then,
+//     and their slots go to the next nodes added (arena.rs).
+fn arena_tree() {
+    let mut tree = arena::Tree::new();
+    let usr = tree.add(tree.root(), "usr");
+    tree.add(usr, "bin");
+    tree.add(usr, "lib");
+    let home = tree.add(tree.root(), "home");
+    let alice = tree.add(home, "alice");
+    print!("a tree in an arena:\n{}", tree.render());
+    println!("alice's path: {}", tree.path(alice));
+    let removed = tree.remove(usr).unwrap_or(0);
+    println!("remove /usr: {} nodes gone, {} of {} slots in use",
+             removed, tree.nodes().len(), tree.nodes().slots());
+    let tmp = tree.add(tree.root(), "tmp");
+    println!("add {}: {} of {} slots in use; the old Id of /usr finds {}",
+             tree.path(tmp), tree.nodes().len(), tree.nodes().slots(),
+             tree.nodes().get(usr).map_or("nothing", |node| &node.name));
+}
+
+// Which value goes first, in each of drop_order.rs's scenarios.
+fn print_drop_orders() {
+    for (name, scenario) in drop_order::SCENARIOS {
+        println!("{}: {}", name, scenario().join(", "));
+    }
+}
+
 //  Once you have become comfortable with both ownership and borrowing, you will have climbed the
 //  steepest part of Rust’s learning curve, and you’ll be ready to take advantage of Rust’s unique
 //  strengths.
@@ -0,0 +1,81 @@
+// Run the chapter's demo binary and check the parts of its output that are deterministic.
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+#[test]
+fn prints_the_demo() {
+    Command::cargo_bin("ownership").unwrap().assert()
+        .success()
+        .stdout("\
+Hello, Ownership!
+P(1..10) = [1, 1, 1, 2, 2, 3, 4, 5, 7, 9]
+alice, born 1988
+bob, born 1984
+molly, born 1990
+liberté!
+égalité!
+fraternité!
+STAMP: 3
+My label number is: 3
+STAMP: shirataki
+STAMP: shirataki
+let s = Rc::new(..): strong 1, weak 0
+let t = s.clone(): strong 2, weak 0
+let u = s.clone(): strong 3, weak 0
+drop(t): strong 2, weak 0
+let w = Rc::downgrade(&s): strong 2, weak 1
+drop(s); drop(u): strong 0, weak 0
+a.next = b, b.next = a:
+a: strong 2, weak 0; b: strong 2, weak 0
+after a and b go: strong 1 and 1, so both leak
+a.next = b, b.prev = a, weakly:
+a: strong 1, weak 1; b: strong 2, weak 0
+dropping a
+dropping b
+after a and b go: strong 0 and 0
+a tree in an arena:
+/
+  usr
+    bin
+    lib
+  home
+    alice
+alice's path: /home/alice
+remove /usr: 3 nodes gone, 3 of 6 slots in use
+add /tmp: 4 of 6 slots in use; the old Id of /usr finds nothing
+locals: c, b, a
+shadowing: x2, x1
+nested blocks: inner, marker: inner block done, outer
+fields: pair, first, second
+collections: b0, b1, t0, t1, v0, v1, v2
+reassignment: marker: before, old, marker: after, new
+moves: a (into a function), marker: closure made, b (into a closure), c (stays)
+temporaries: let _, temporary, marker: statements done, named
+forget: taken, replacement, kept
+");
+}
+
+#[test]
+fn draws_a_move() {
+    Command::cargo_bin("ownership").unwrap().arg("draw").assert()
+        .success()
+        .stdout(predicate::str::contains("\
+--- let t = s;
+stack  s   (moved)
+       t   Vec -> @1 (len 3, cap 3)
+heap   @1  [0] String -> @2 (len 5, cap 5)
+           [1] String -> @3 (len 3, cap 3)
+           [2] String -> @4 (len 5, cap 5)
+       @2  \"alice\"
+")
+            .and(predicate::str::contains("       u    Vec -> @9 (len 3, cap 3)\n"))
+            .and(predicate::str::contains("--- let fifth = v.pop().unwrap();\n"))
+            .and(predicate::str::contains("stack  v  (moved)\n")));
+}
+
+#[test]
+fn says_which_mode_is_unknown() {
+    Command::cargo_bin("ownership").unwrap().arg("paint").assert()
+        .failure()
+        .stderr(predicate::str::starts_with("ownership: unknown mode \"paint\"\nUsage: ownership [draw]\n"));
+}
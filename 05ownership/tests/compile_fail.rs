@@ -0,0 +1,12 @@
+// The move and Copy errors that src/main.rs shows as commented-out lines or names in its
+// notes, and the borrow that arena.rs's note 2 rules out, compiled for real.
+// Each tests/ui/*.rs must fail to build with exactly the error in the .stderr file next
+// to it. After a toolchain update changes the wording, review and refresh them with:
+//
+//   $ TRYBUILD=overwrite cargo test --test compile_fail
+//
+#[test]
+fn move_and_copy_errors() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,13 @@
+// arena.rs, note 2: get lends out a reference into the arena's Vec. alloc may grow the
+// Vec and move everything in it, so the arena can't be changed while the reference lives.
+#[path = "../../src/arena.rs"]
+#[allow(dead_code)]
+mod arena;
+
+fn main() {
+    let mut names = arena::Arena::new();
+    let alice = names.alloc("alice".to_string());
+    let first = names.get(alice).unwrap();
+    names.alloc("bob".to_string());
+    println!("{}", first);
+}
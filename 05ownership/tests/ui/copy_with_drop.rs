@@ -0,0 +1,17 @@
+// A Copy type can't implement Drop: every copy would be dropped.
+#[derive(Clone, Copy)]
+struct Label {
+    number: u32,
+}
+
+impl Drop for Label {
+    fn drop(&mut self) {
+        println!("dropping label {}", self.number);
+    }
+}
+
+fn main() {
+    let l = Label { number: 3 };
+    let m = l;
+    println!("{} {}", l.number, m.number);
+}
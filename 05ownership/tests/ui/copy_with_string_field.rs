@@ -0,0 +1,11 @@
+// StringLabel: a struct can only be Copy if all of its fields are, and a String isn't.
+#[derive(Clone, Copy)]
+struct StringLabel {
+    name: String,
+}
+
+fn main() {
+    let l = StringLabel { name: "shirataki".to_string() };
+    let m = l;
+    println!("{} {}", l.name, m.name);
+}
@@ -0,0 +1,11 @@
+// Label: Copy is a marker on top of Clone, so deriving it alone isn't enough.
+#[derive(Copy)]
+struct Label {
+    number: u32,
+}
+
+fn main() {
+    let l = Label { number: 3 };
+    let m = l;
+    println!("{} {}", l.number, m.number);
+}
@@ -0,0 +1,10 @@
+// err_move_indexed: v[2] is a place inside the vector; moving the String out of it would
+// leave a hole. Use pop, swap_remove or mem::replace instead (see move_indexed).
+fn main() {
+    let mut v = Vec::new();
+    for i in 101 .. 106 {
+        v.push(i.to_string());
+    }
+    let third = v[2];
+    println!("{}", third);
+}
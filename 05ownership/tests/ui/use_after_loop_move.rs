@@ -0,0 +1,12 @@
+// err_use_after_move: `for s in v` takes ownership of the vector and moves each element
+// out of it, so `v` is gone after the loop.
+fn main() {
+    let mut v = vec!["liberté".to_string(),
+                 "égalité".to_string(),
+                 "fraternité".to_string()];
+    for mut s in v {
+        s.push('!');
+        println!("{}", s);
+    }
+    v.push("a".to_string());
+}
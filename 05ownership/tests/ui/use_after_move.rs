@@ -0,0 +1,7 @@
+// err_moved_value: `let t = s;` moves the vector, so `s` can't be used again.
+fn main() {
+    let s = vec!["alice".to_string(), "bob".to_string(), "molly".to_string()];
+    let t = s;
+    let u = s;
+    println!("{:?} {:?}", t, u);
+}
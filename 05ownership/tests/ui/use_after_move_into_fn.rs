@@ -0,0 +1,15 @@
+// copy_and_move: passing a StringLabel to a function moves it, so it can't be used after;
+// a Label would have been copied.
+struct StringLabel {
+    name: String,
+}
+
+fn print_string_label(l: StringLabel) {
+    println!("STAMP: {}", l.name);
+}
+
+fn main() {
+    let l = StringLabel { name: "shirataki".to_string() };
+    print_string_label(l);
+    println!("My label name is: {}", l.name);
+}
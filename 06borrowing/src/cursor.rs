@@ -0,0 +1,207 @@
+// Ends: a cursor over a mutable slice that takes elements off either end, built on a
+// raw pointer and a length instead of a &mut [T].
+//
+//   let mut v = [1, 2, 3, 4];
+//   let mut ends = Ends::new(&mut v);
+//   let (first, last) = (ends.take_front().unwrap(), ends.take_back().unwrap());
+//   std::mem::swap(first, last);        // v is now [4, 2, 3, 1]
+//
+// It's what slice::iter_mut is inside: first and last are two &mut into the same slice,
+// alive at once, which a single &mut [T] could never lend out by indexing.
+//
+// Note:
+//
+// 1.) A raw pointer, *const T or *mut T, is an address with none of a reference's
+//     promises: it can be null, dangle, or point where a &mut also points. Making one is
+//     safe; reading or writing through one is `unsafe`, a block where we, not the
+//     compiler, answer for those promises.
+// 2.) What this file promises, and each unsafe block relies on: `front` points to the
+//     first of `len` elements of the slice `new` was given, all of them still there for
+//     'a, and no element is handed out twice, since taking one moves front past it or
+//     shortens len before it.
+// 3.) The public API is safe: nothing a caller does with Ends can break note 2. That's
+//     the point of unsafe code in Rust, a small part checked by hand, wrapped in types
+//     the compiler checks everywhere else.
+// 4.) Miri runs the tests on an interpreter of Rust's abstract machine, and reports any
+//     read out of bounds, or any &mut that a raw pointer is used behind the back of:
+//
+//       $ cargo +nightly miri test -p borrowing cursor
+//
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+
+/// Takes elements off both ends of a mutable slice: see the top of cursor.rs.
+pub struct Ends<'a, T> {
+    front: NonNull<T>,
+    len: usize,
+    // 1.  Ends borrows the slice mutably for 'a, though it holds no reference to say so:
+    //     PhantomData does, so the slice can't be used behind its back, and &'a mut T is
+    //     what the compiler checks take_front's results against.
+    _borrow: PhantomData<&'a mut [T]>,
+}
+
+impl<'a, T> Ends<'a, T> {
+    pub fn new(slice: &'a mut [T]) -> Ends<'a, T> {
+        // 2.  A slice's pointer is never null, even for an empty one; as_mut_ptr on the
+        //     whole slice, rather than &mut slice[0], gives a pointer that may reach
+        //     every element.
+        let len = slice.len();
+        let front = NonNull::new(slice.as_mut_ptr()).expect("a slice's pointer isn't null");
+        Ends { front, len, _borrow: PhantomData }
+    }
+
+    /// How many elements are left between the ends.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// A look at the first element left, without taking it.
+    pub fn front(&self) -> Option<&T> {
+        if self.len == 0 {
+            return None;
+        }
+        // SAFETY: front points to the first of len > 0 elements (note 2); the &T borrows
+        // self, so no take can hand out the same element while it lives.
+        Some(unsafe { self.front.as_ref() })
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        if self.len == 0 {
+            return None;
+        }
+        // SAFETY: front + len - 1 is the last element, within the slice, as above.
+        Some(unsafe { self.front.add(self.len - 1).as_ref() })
+    }
+
+    /// Take the first element, for as long as the slice is borrowed.
+    pub fn take_front(&mut self) -> Option<&'a mut T> {
+        if self.len == 0 {
+            return None;
+        }
+        let mut taken = self.front;
+        // SAFETY: len > 0, so front + 1 is at most one past the end, which a pointer may
+        // point to. The taken element is now outside front..front + len, and can't be
+        // reached through self again.
+        unsafe {
+            self.front = self.front.add(1);
+            self.len -= 1;
+            Some(taken.as_mut())
+        }
+    }
+
+    /// Take the last element, for as long as the slice is borrowed.
+    pub fn take_back(&mut self) -> Option<&'a mut T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // SAFETY: front + len, with len already shortened, is the element just left out
+        // of front..front + len: in the slice, and no longer reachable through self.
+        unsafe { Some(self.front.add(self.len).as_mut()) }
+    }
+
+    /// Swap the first and last elements left, in place, without taking them.
+    pub fn swap_ends(&mut self) {
+        if self.len < 2 {
+            return;
+        }
+        // SAFETY: both pointers are within the slice and different, as len >= 2, and
+        // nothing else holds a reference to either: they're not taken yet.
+        unsafe {
+            std::ptr::swap(self.front.as_ptr(), self.front.add(self.len - 1).as_ptr());
+        }
+    }
+}
+
+// 3.  With take_front and take_back, Ends is an iterator from both ends, as
+//     slice::iter_mut is: the same pointer and length, with next and next_back.
+impl<'a, T> Iterator for Ends<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        self.take_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Ends<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.take_back()
+    }
+}
+
+impl<T> ExactSizeIterator for Ends<'_, T> {}
+
+/// Reverse `v` in place, a pair of ends at a time.
+pub fn reverse<T>(v: &mut [T]) {
+    let mut ends = Ends::new(v);
+    while let (Some(front), Some(back)) = (ends.take_front(), ends.take_back()) {
+        std::mem::swap(front, back);
+    }
+}
+
+#[test]
+fn test_take_from_both_ends() {
+    let mut v = [1, 2, 3, 4, 5];
+    let mut ends = Ends::new(&mut v);
+    assert_eq!((ends.front(), ends.back(), ends.len()), (Some(&1), Some(&5), 5));
+    let first = ends.take_front().unwrap();
+    let last = ends.take_back().unwrap();
+    let second = ends.take_front().unwrap();
+    // three &mut into one array, all alive at once, none the same element
+    std::mem::swap(first, last);
+    *second *= 10;
+    assert_eq!((ends.front(), ends.back(), ends.len()), (Some(&3), Some(&4), 2));
+    ends.swap_ends();
+    assert_eq!(ends.take_back(), Some(&mut 3));
+    assert_eq!(ends.take_back(), Some(&mut 4));
+    assert_eq!((ends.take_front(), ends.take_back(), ends.front()), (None, None, None));
+    assert_eq!(v, [5, 20, 4, 3, 1]);
+}
+
+#[test]
+fn test_iterator() {
+    let mut v = vec![1, 2, 3, 4];
+    for x in Ends::new(&mut v).rev().step_by(2) {
+        *x = 0;
+    }
+    assert_eq!(v, [1, 0, 3, 0]);
+    let mut ends = Ends::new(&mut v);
+    assert_eq!(ends.len(), 4);
+    assert_eq!(ends.next_back(), Some(&mut 0));
+    assert_eq!(ends.map(|x| *x).collect::<Vec<_>>(), [1, 0, 3]);
+    let mut copy = v.clone();
+    assert!(Ends::new(&mut v).eq(copy.iter_mut()));
+}
+
+#[test]
+fn test_reverse() {
+    for n in 0..6 {
+        let mut v: Vec<usize> = (0..n).collect();
+        reverse(&mut v);
+        assert_eq!(v, (0..n).rev().collect::<Vec<_>>());
+    }
+    let mut words = ["tea".to_string(), "for".to_string(), "two".to_string()];
+    reverse(&mut words);
+    assert_eq!(words, ["two", "for", "tea"]);
+}
+
+#[test]
+fn test_zero_sized() {
+    // Every () is at the same address, so front never moves: it's the len that counts
+    // the elements down, and the cursor still ends.
+    let mut units = [(); 3];
+    let mut ends = Ends::new(&mut units);
+    assert_eq!(ends.take_back(), Some(&mut ()));
+    assert_eq!(ends.by_ref().count(), 2);
+    assert!(ends.is_empty());
+    let mut nothing: [(); 0] = [];
+    assert_eq!(Ends::new(&mut nothing).next(), None);
+}
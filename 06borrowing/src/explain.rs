@@ -0,0 +1,89 @@
+// What `borrowing --explain` prints: the chapter's notes, in short.
+use rustfun_common::{Explanation, Text, Topic};
+
+pub const EXPLANATION: Explanation = Explanation {
+    chapter: "06borrowing",
+    summary: Text {
+        en: "shared and mutable references, and lifetimes",
+        zh: "共享引用、可变引用和生命周期",
+    },
+    topics: &[
+        Topic {
+            title: Text { en: "Two kinds of reference", zh: "两种引用" },
+            body: Text {
+                en: "&T is a shared reference: read-only, and Copy. &mut T is a mutable one:\n\
+                     exclusive, so while it lives nothing else may use the value.",
+                zh: "&T 是共享引用: 只读, 并且是 Copy 的。&mut T 是可变引用: 独占, 它存在期间\n\
+                     别的代码都不能使用这个值。",
+            },
+        },
+        Topic {
+            title: Text { en: "Implicit borrowing", zh: "隐式借用" },
+            body: Text {
+                en: "The . operator borrows and dereferences as a method call needs, so\n\
+                     v.sort() means (&mut v).sort().",
+                zh: "点运算符会按方法调用的需要自动借用和解引用, 所以 v.sort() 就是\n\
+                     (&mut v).sort()。",
+            },
+        },
+        Topic {
+            title: Text { en: "Lifetimes", zh: "生命周期" },
+            body: Text {
+                en: "No reference may outlive what it points to. A function that returns a\n\
+                     reference says which argument it borrows from with a lifetime parameter:\n\
+                     fn smallest(v: &[i32]) -> &i32 is short for\n\
+                     fn smallest<'a>(v: &'a [i32]) -> &'a i32. A method's return borrows from\n\
+                     self: the matches StringTable::find_by_prefix hands out keep the table\n\
+                     from changing while they're in use. An iterator's Item names its lifetime\n\
+                     outright: Windows<'a, T> yields &'a [T], windows that borrow the buffer,\n\
+                     so they can outlive the iterator but not the buffer.",
+                zh: "引用不能比它指向的值活得更久。返回引用的函数用生命周期参数说明它借自哪个\n\
+                     参数: fn smallest(v: &[i32]) -> &i32 是\n\
+                     fn smallest<'a>(v: &'a [i32]) -> &'a i32 的简写。方法返回的引用借自 self:\n\
+                     StringTable::find_by_prefix 给出的匹配还在使用时, 表不能被修改。迭代器的\n\
+                     Item 要直接写出生命周期: Windows<'a, T> 产出借自缓冲区的 &'a [T], 所以\n\
+                     这些窗口可以比迭代器活得久, 但不能比缓冲区久。",
+            },
+        },
+        Topic {
+            title: Text { en: "References are never null", zh: "引用永远不为空" },
+            body: Text {
+                en: "A reference always points at a live value; Option<&T> says \"maybe none\"\n\
+                     instead, and costs nothing extra.",
+                zh: "引用总是指向一个活着的值; 需要表示\"可能没有\"时用 Option<&T>, 不需要额外开销。",
+            },
+        },
+        Topic {
+            title: Text { en: "Interior mutability", zh: "内部可变性" },
+            body: Text {
+                en: "Cell and RefCell change their contents through a shared &. Cell only copies\n\
+                     values in and out; RefCell hands out borrows and checks the rules as it\n\
+                     runs, panicking where plain references wouldn't compile.",
+                zh: "Cell 和 RefCell 可以通过共享引用 & 修改其中的内容。Cell 只把值复制进出;\n\
+                     RefCell 借出引用并在运行时检查借用规则, 普通引用编译不过的地方它会 panic。",
+            },
+        },
+        Topic {
+            title: Text { en: "Splitting borrows", zh: "拆分借用" },
+            body: Text {
+                en: "Two &mut into one slice are fine if they can't overlap, but the compiler\n\
+                     has to be told: split_at_mut gives both halves at once, and iter_mut a\n\
+                     &mut to each element.",
+                zh: "指向同一切片的两个 &mut, 只要不重叠就没问题, 但要告诉编译器:\n\
+                     split_at_mut 同时给出两半, iter_mut 给出每个元素的 &mut。",
+            },
+        },
+        Topic {
+            title: Text { en: "Raw pointers", zh: "裸指针" },
+            body: Text {
+                en: "*const T and *mut T are addresses with none of a reference's promises:\n\
+                     they can be null, dangle or alias. Using one takes an unsafe block, whose\n\
+                     soundness is argued by hand. Ends (cursor.rs) wraps one in a safe type that\n\
+                     takes &mut from both ends of a slice; its tests also run under Miri.",
+                zh: "*const T 和 *mut T 是没有引用那些保证的地址: 可以为空、悬垂或别名。\n\
+                     使用它们需要 unsafe 块, 其正确性要靠人工论证。Ends (cursor.rs) 把裸指针\n\
+                     包进一个安全的类型, 从切片两端取出 &mut; 它的测试也在 Miri 下运行。",
+            },
+        },
+    ],
+};
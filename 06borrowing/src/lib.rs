@@ -0,0 +1,28 @@
+//
+// The parts of the borrowing chapter that are worth using from other code: the
+// StringTable of note 9.10, grown from a Vec searched front to back into a prefix tree;
+// Windows, an iterator over a borrowed buffer; and Ends, a cursor over a mutable slice
+// built on a raw pointer. main.rs uses them from here like any other crate:
+// `use borrowing::StringTable;`.
+//
+// Note:
+//
+// 1.) Every string the table hands out, from find_by_prefix or longest_common_prefix, is
+//     a &str into the table itself. The elision rules of note 9.10 give it self's
+//     lifetime, so while one is held the table can't be changed: insert and remove take
+//     &mut self (tests/ui/insert_while_matching.rs).
+// 2.) find_by_prefix returns an iterator, not a Vec: Matches holds references into the
+//     tree, so it has a lifetime parameter of its own, like the structs of note 9.5.
+// 3.) Windows is the same pattern at its smallest: an iterator whose items are references
+//     with the lifetime of what it borrows, written out by hand (windows.rs).
+// 4.) Ends hands out &mut from both ends of one slice at once, which references alone
+//     can't do; underneath it's a raw pointer, and the unsafe blocks that use it are
+//     checked under Miri (cursor.rs).
+//
+pub mod cursor;
+pub mod string_table;
+pub mod windows;
+
+pub use crate::cursor::Ends;
+pub use crate::string_table::{Matches, StringTable};
+pub use crate::windows::{moving_average, windows, Windows};
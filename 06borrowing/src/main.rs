@@ -16,9 +16,17 @@
 //      Shared references are Copy.
 //  2.) A mutable reference lets you both read and modify the value.  &mut T
 //      Mutable references are not Copy.
+//  The borrow-checker errors below are commented out so the chapter builds; the main ones
+//  also live in tests/ui/ as compile-fail tests, checked against the compiler's actual
+//  message by `cargo test`.
 //
-//
-use std::collections::HashMap;
+// Several lines spell out what Rust would do implicitly (explicit derefs, `(&mut v).sort()`,
+// lifetimes that could be elided); that's the point of them, so clippy is told to let them be.
+#![allow(clippy::bool_assert_comparison, clippy::explicit_auto_deref, clippy::for_kv_map,
+         clippy::needless_borrow, clippy::needless_lifetimes, clippy::no_effect, clippy::op_ref,
+         clippy::unnecessary_fold, clippy::vec_init_then_push)]
+use std::collections::{BTreeMap, HashMap};
+use borrowing::{moving_average, windows, Ends, StringTable};
 type Table = HashMap<String, Vec<String>>;
 
 // 1.  Reference Rules
@@ -35,6 +43,18 @@ fn show(table: &Table) {
         }
     }
 }
+// 1.4 A HashMap iterates in an order of its own, which changes from run to run. With
+//     --deterministic the table is shown by artist: collecting the (&String, &Vec) pairs
+//     into a BTreeMap sorts them by key, and borrows the table, as show() does.
+fn show_sorted(table: &Table) {
+    let sorted: BTreeMap<&String, &Vec<String>> = table.iter().collect();
+    for (artist, works) in sorted {
+        println!("works by {}:", artist);
+        for work in works {
+            println!("  {}", work);
+        }
+    }
+}
 // 1.3 The mutable borrow required by the vectors’ sort method.
 fn sort_works(table: &mut Table) {
     for (_artist, works) in table {
@@ -42,7 +62,11 @@ fn sort_works(table: &mut Table) {
     }
 }
 
+mod explain;
+
 fn main() {
+    rustfun_common::init_logging();
+    explain::EXPLANATION.on_request();
     println!("Hello, Borrowing!");
 
     let mut table = Table::new();
@@ -58,7 +82,11 @@ fn main() {
     // need a &mut
     sort_works(&mut table);
     assert_eq!(table["Gesualdo"][1], "many madrigals");
-    show(&table);
+    if rustfun_common::deterministic::is_on() {
+        show_sorted(&table);
+    } else {
+        show(&table);
+    }
 
     // 2.  Implicity in Rust ref and de-ref
     //     Since references are so widely used in Rust, the . operator implicitly dereferences
@@ -76,7 +104,7 @@ fn main() {
     assert!(*m == 64);     // and to see y's new value
     //assert!(y==64);      // error: can't use y, it was mutably borrowed
 
-    struct Anime { name: &'static str, bechdel_pass: bool };
+    struct Anime { name: &'static str, bechdel_pass: bool }
     let aria = Anime { name: "Aria: The Animation", bechdel_pass: true };
     let anime_ref = &aria;
     assert_eq!(anime_ref.bechdel_pass, true);
@@ -178,6 +206,7 @@ fn main() {
     //    assert_eq!(*r, 1);  // bad: reads memory `x` used to occupy
     //}
     // 3.) r reference to x with contradictory constraints on its lifetime
+    //     (tests/ui/dangling_reference.rs)
 
     // 9.2 Receiving References as Parameters
     unsafe { //you may access a mutable static only within an unsafe block.
@@ -254,14 +283,33 @@ fn main() {
     //   Rust assumes that self’s lifetime is the one to give everything in your return value.
     
 
-    let x = Vec::new();
-    let t = StringTable{ elements:x };
-    assert_eq!(t.find_by_prefix("t"),None);
-
-    let mut x = Vec::new();
-    x.push("test".to_string());
-    let t = StringTable{ elements:x };
-    assert_eq!(t.find_by_prefix("t"),Some(&"test".to_string()));
+    //   StringTable (src/string_table.rs) is a method-heavy example: find_by_prefix(&self,
+    //   prefix: &str) -> Matches<'_> hands out matches that borrow from self, not prefix.
+    let mut t = StringTable::new();
+    assert_eq!(t.find_by_prefix("t").next(), None);
+
+    t.insert("test");
+    t.insert("tesla");
+    t.insert("rust");
+    let found: Vec<&str> = t.find_by_prefix("t").collect();
+    assert_eq!(found, ["tesla", "test"]);
+    assert_eq!(t.longest_common_prefix("t"), Some("tes"));
+    // t.insert("tea");        // bad: found still borrows t (tests/ui/insert_while_matching.rs)
+    assert_eq!(found.len(), 2);
+    t.insert("tea");           // ok, found is no longer used
+
+    //   Windows (src/windows.rs) is an iterator with a lifetime parameter, and no elision
+    //   to lean on: its Item is written &'a [T], the lifetime of the buffer it walks. So
+    //   the windows it hands out can outlive the iterator, but not the buffer.
+    let readings: Vec<i32> = vec![3, 1, 4, 1, 5, 9];
+    let steepest = windows(&readings, 2)
+        .max_by_key(|pair| (pair[1] - pair[0]).abs())
+        .unwrap();
+    assert_eq!(steepest, [5, 9]);
+    assert_eq!(moving_average(&[1.0, 2.0, 4.0, 8.0], 2), [1.5, 3.0, 6.0]);
+    // (kept past the end of readings' scope, steepest wouldn't compile:
+    //  tests/ui/window_outlives_buffer.rs)
+    assert_eq!(steepest.len(), 2);
 
     // 9.11 Sharing Versus Mutation
     //
@@ -270,13 +318,15 @@ fn main() {
     let r = &v;
     let aside = v;  // move vector to aside
     r[0];           // bad: uses `v`, which is now uninitialized
-    */
+    */                 // (tests/ui/move_while_borrowed.rs)
     /*
     let v = vec![4, 8, 19, 27, 34, 10];
     let r = &v;
     r[0];              
     let aside = v;  // bad: can't move, because it borrow by r 
     */
+    // (Since non-lexical lifetimes, this second version compiles: a borrow ends at its
+    //  last use, and r[0] comes before the move.)
     let v = vec![4, 8, 19, 27, 34, 10];
     {
         let r = &v;
@@ -296,12 +346,77 @@ fn main() {
     assert_eq!(wave, vec![0.0, 1.0, 0.0, -1.0]);
     assert_eq!(head, vec![0.0, 1.0]);
     assert_eq!(tail, [0.0, -1.0]);
+    // (tests/ui/aliasing_extend.rs)
     /*
     extend(&mut wave, &wave);
     assert_eq!(wave, vec![0.0, 1.0, 0.0, -1.0,
                           0.0, 1.0, 0.0, -1.0]);
 */
 
+    // 10.  Interior Mutability (Counter and Log, below)
+    //      Everything above is checked at compile time: many & or one &mut, never both.
+    //      Cell and RefCell change what's inside them through a plain &, which the
+    //      compiler allows; the rules hold all the same, by other means.
+    let counter = Counter::new();
+    let (a, b) = (&counter, &counter);  // two shared references, both counting
+    a.hit();
+    b.hit();
+    assert_eq!(counter.hits(), 2);
+
+    let log = Log::new();
+    log.write("started");
+    log.write("still going");
+    {
+        let reading = log.lines.borrow();
+        assert!(log.try_write("while reading").is_err());  // 9.11's rule, at run time
+        assert_eq!(reading.len(), 2);
+    }
+
+    // 11.  Splitting Borrows (fold_halves, pair_mut and partition, below)
+    //      One &mut at a time is the rule for a value, but a slice's halves, or its
+    //      elements, are values of their own. split_at_mut and iter_mut hand out &muts to
+    //      parts that can't overlap, all usable at once.
+    let mut v = [1, 2, 3, 10, 20, 30];
+    fold_halves(&mut v);
+    assert_eq!(v, [11, 22, 33, 10, 20, 30]);
+    /*
+    let (front, back) = (&mut v[..3], &mut v[3..]);   // bad: v borrowed mutably twice
+    front[0] += back[0];                                // (tests/ui/two_mutable_halves.rs)
+    */
+    let (a, b) = pair_mut(&mut v, 0, 5);
+    std::mem::swap(a, b);
+    assert_eq!(v, [30, 22, 33, 10, 20, 11]);
+
+    let mut v = [5, 8, 1, 4, 7, 2];
+    let evens = partition(&mut v, |n| n % 2 == 0);
+    let (even, odd) = v.split_at_mut(evens);
+    even.sort();
+    odd.sort();
+    assert_eq!(v, [2, 4, 8, 1, 5, 7]);
+
+    // 12.  Raw Pointers (Ends, in src/cursor.rs)
+    // 12.1 *const T and *mut T are addresses without a reference's promises. Making one
+    //      is safe, from a reference with `as` or from &raw; using one is unsafe.
+    let mut x = 10;
+    let p = &raw mut x;
+    let q = p;                  // two *mut to x at once: fine, they promise nothing
+    unsafe {
+        *p += 1;
+        *q += 1;
+    }
+    assert_eq!(x, 12);
+    let null: *const i32 = std::ptr::null();
+    assert!(null.is_null());    // a reference can't be null; a raw pointer can
+    // 12.2 Built into a type with a safe API, they do what references can't say: Ends
+    //      lends out &mut from both ends of one slice at once.
+    let mut v = [1, 2, 3, 4, 5];
+    let mut ends = Ends::new(&mut v);
+    let (first, last) = (ends.take_front().unwrap(), ends.take_back().unwrap());
+    std::mem::swap(first, last);
+    assert_eq!(ends.len(), 3);
+    borrowing::cursor::reverse(&mut v[1..4]);
+    assert_eq!(v, [5, 4, 3, 2, 1]);
+
     //Rust is all about transferring the pain of understanding your program from the future to the present. It works unreasonably well: not only can Rust force you to understand why your program is thread-safe, it can even require some amount of high-level architectural design.
 
 }
@@ -312,21 +427,6 @@ fn extend(vec: &mut Vec<f64>, slice: &[f64]) {
     }
 }
 
-struct StringTable {
-    elements: Vec<String>,
-}
-
-impl StringTable {
-    fn find_by_prefix(&self, prefix: &str) -> Option<&String> {
-        for i in 0 .. self.elements.len() {
-            if self.elements[i].starts_with(prefix) {
-                return Some(&self.elements[i]);
-            }
-        }
-        None
-    }
-}
-
 
 // 1.) STASH lives for the program’s entire execution, the reference type it holds must have a
 // lifetime of the same length; Rust calls this the 'static lifetime.'
@@ -364,3 +464,204 @@ fn smallest(v: &[i32]) -> &i32 {
 fn factorial(n: usize) -> usize {
     (1..n+1).fold(1, |a, b| a * b)
 }
+
+// 10.1 Cell<T> never hands out a reference to what's inside: get copies the value out and
+//      set replaces it, so there's nothing for the borrow rules to check. That makes it
+//      for small Copy values, like a count.
+use std::cell::{Cell, RefCell};
+
+struct Counter {
+    hits: Cell<u32>,
+}
+
+impl Counter {
+    fn new() -> Counter {
+        Counter { hits: Cell::new(0) }
+    }
+
+    /// Count one more, through a shared reference.
+    fn hit(&self) {
+        self.hits.set(self.hits.get() + 1);
+    }
+
+    fn hits(&self) -> u32 {
+        self.hits.get()
+    }
+}
+
+#[test]
+fn test_counter() {
+    let counter = Counter::new();
+    let count_words = |text: &str, counter: &Counter| {
+        for _ in text.split_whitespace() {
+            counter.hit();
+        }
+    };
+    count_words("many madrigals", &counter);
+    count_words("The Calling of St. Matthew", &counter);
+    assert_eq!(counter.hits(), 7);
+}
+
+// 10.2 RefCell<T> does hand out references, as guards: borrow() gives a Ref, which reads,
+//      and borrow_mut() a RefMut, which writes. It counts them as they come and go, and
+//      enforces note 9.11's rule at run time: a borrow_mut while any other borrow lives
+//      panics, where the same thing with plain references doesn't compile
+//      (tests/ui/mutate_while_shared.rs).
+// 10.3 try_borrow_mut asks instead of insisting, and returns an Err rather than panic.
+struct Log {
+    lines: RefCell<Vec<String>>,
+}
+
+impl Log {
+    fn new() -> Log {
+        Log { lines: RefCell::new(Vec::new()) }
+    }
+
+    /// Add a line, through a shared reference. Panics if the lines are borrowed.
+    fn write(&self, line: &str) {
+        self.lines.borrow_mut().push(line.to_string());
+    }
+
+    /// Add a line if nothing is reading the lines right now.
+    fn try_write(&self, line: &str) -> Result<(), std::cell::BorrowMutError> {
+        self.lines.try_borrow_mut()?.push(line.to_string());
+        Ok(())
+    }
+}
+
+#[test]
+fn test_log() {
+    let log = Log::new();
+    log.write("one");
+    {
+        let lines = log.lines.borrow();
+        let again = log.lines.borrow();   // many readers at once are fine
+        assert_eq!((lines.len(), again.len()), (1, 1));
+        assert!(log.try_write("two").is_err());
+    }
+    assert!(log.try_write("two").is_ok());  // the Refs are gone
+    assert_eq!(*log.lines.borrow(), ["one", "two"]);
+}
+
+#[test]
+#[should_panic(expected = "already borrowed")]
+fn test_write_while_reading() {
+    let log = Log::new();
+    let lines = log.lines.borrow();
+    log.write("oops");  // panics: lines is still reading
+    drop(lines);
+}
+
+#[test]
+#[should_panic(expected = "already mutably borrowed")]
+fn test_read_while_writing() {
+    let log = Log::new();
+    let mut lines = log.lines.borrow_mut();
+    lines.push("one".to_string());
+    let _count = log.lines.borrow().len();  // panics: lines is still writing
+}
+
+// 11.1 split_at_mut(mid) gives two &mut slices, v[..mid] and v[mid..], borrowed from v
+//      at once. Neither can reach into the other, so it's safe; the compiler can't see
+//      that from two index expressions, so it takes a function (with unsafe inside) to
+//      say it.
+/// Add each element of the back half of `v` to the one at the same place in the front
+/// half. With an odd length, the middle element is the back half's first.
+fn fold_halves(v: &mut [i32]) {
+    let mid = v.len() / 2;
+    let (front, back) = v.split_at_mut(mid);
+    // 11.2 iter_mut gives a &mut to each element in turn; zip walks both halves at once.
+    for (f, b) in front.iter_mut().zip(back.iter()) {
+        *f += *b;
+    }
+}
+
+#[test]
+fn test_fold_halves() {
+    let mut v = [1, 2, 3, 10, 20, 30];
+    fold_halves(&mut v);
+    assert_eq!(v, [11, 22, 33, 10, 20, 30]);
+    let mut odd = [1, 2, 100, 10, 20];
+    fold_halves(&mut odd);
+    assert_eq!(odd, [101, 12, 100, 10, 20]);
+    let mut empty: [i32; 0] = [];
+    fold_halves(&mut empty);
+}
+
+// 11.3 Two &muts into one slice, at any two different places: split between them, and
+//      take one from each side. (The standard library has this as get_disjoint_mut.)
+/// Mutable references to `v[i]` and `v[j]`, together. Panics if `i == j`, which would be
+/// two &muts to one element, or if either is out of bounds.
+fn pair_mut<T>(v: &mut [T], i: usize, j: usize) -> (&mut T, &mut T) {
+    assert!(i != j, "pair_mut: {} twice", i);
+    let (low, high) = (i.min(j), i.max(j));
+    let (front, back) = v.split_at_mut(high);
+    let (first, second) = (&mut front[low], &mut back[0]);
+    if i < j { (first, second) } else { (second, first) }
+}
+
+#[test]
+fn test_pair_mut() {
+    let mut v = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+    let (x, y) = pair_mut(&mut v, 2, 0);
+    x.push('!');
+    y.push('?');
+    assert_eq!(v, ["a?", "b", "c!"]);
+    let (x, y) = pair_mut(&mut v, 1, 2);
+    std::mem::swap(x, y);
+    assert_eq!(v, ["a?", "c!", "b"]);
+}
+
+#[test]
+#[should_panic(expected = "pair_mut: 1 twice")]
+fn test_pair_mut_same_place() {
+    let mut v = [1, 2, 3];
+    pair_mut(&mut v, 1, 1);
+}
+
+// 11.4 Partition in place: the elements for which `pred` holds move to the front, the
+//      rest to the back, by swapping; the count of the first kind is where to split.
+//      `done` marks the end of the front part. Each swap needs two places in v mutably,
+//      which swap(i, j) on the slice does inside, like pair_mut.
+/// Reorder `v` so that the elements for which `pred` is true come first, and return how
+/// many they are. The order within each part isn't kept.
+fn partition<T, F>(v: &mut [T], pred: F) -> usize
+    where F: Fn(&T) -> bool
+{
+    let mut done = 0;
+    for i in 0..v.len() {
+        if pred(&v[i]) {
+            v.swap(done, i);
+            done += 1;
+        }
+    }
+    done
+}
+
+#[test]
+fn test_partition() {
+    let mut v = [5, 8, 1, 4, 7, 2];
+    let n = partition(&mut v, |&x| x % 2 == 0);
+    assert_eq!(n, 3);
+    assert!(v[..n].iter().all(|x| x % 2 == 0));
+    assert!(v[n..].iter().all(|x| x % 2 == 1));
+    let mut sorted = v;
+    sorted.sort();
+    assert_eq!(sorted, [1, 2, 4, 5, 7, 8]);  // nothing lost, nothing doubled
+
+    let mut none = [1, 3];
+    assert_eq!(partition(&mut none, |&x| x > 5), 0);
+    assert_eq!(none, [1, 3]);
+    let mut all = [6, 7];
+    assert_eq!(partition(&mut all, |&x| x > 5), 2);
+    assert_eq!(all, [6, 7]);
+    let mut empty: [i32; 0] = [];
+    assert_eq!(partition(&mut empty, |_| true), 0);
+
+    let mut words = ["tea", "Rust", "toast", "Ferris"];
+    let capitals = partition(&mut words, |w| w.starts_with(char::is_uppercase));
+    let (caps, rest) = words.split_at_mut(capitals);
+    caps.sort();
+    rest.sort();
+    assert_eq!(words, ["Ferris", "Rust", "tea", "toast"]);
+}
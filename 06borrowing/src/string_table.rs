@@ -0,0 +1,253 @@
+// StringTable: a set of strings, searched by prefix.
+//
+//   let mut table = StringTable::new();
+//   table.insert("tea");  table.insert("team");  table.insert("toast");
+//   table.find_by_prefix("te")  ->  "tea", "team"
+//   table.longest_common_prefix("t")  ->  Some("t")
+//   table.longest_common_prefix("te")  ->  Some("tea")
+//
+// The strings are kept in a trie: one node per prefix, each with a child for every char
+// that extends it. "tea" and "team" share the nodes t, te and tea; finding what starts
+// with "te" is a walk of two steps down, then a walk of what's below, however many
+// other strings the table holds.
+//
+use std::collections::BTreeMap;
+
+#[derive(Debug, Default, Clone)]
+struct Node {
+    // 1.  A node owns its children, and the children are kept in char order, so a walk
+    //     of the tree visits the strings sorted.
+    children: BTreeMap<char, Node>,
+    // 2.  The string that ends here, if one does. Keeping the whole String at its node,
+    //     rather than rebuilding it from the path, is what lets the table lend out &str.
+    word: Option<String>,
+}
+
+impl Node {
+    fn is_empty(&self) -> bool {
+        self.word.is_none() && self.children.is_empty()
+    }
+
+    /// The node for `prefix`, if any string in the tree starts with it.
+    fn find(&self, prefix: &str) -> Option<&Node> {
+        let mut node = self;
+        for c in prefix.chars() {
+            node = node.children.get(&c)?;
+        }
+        Some(node)
+    }
+
+    /// Remove the string of which `rest` is what's left below this node, and drop the
+    /// nodes that lead to nothing any more on the way back up.
+    fn remove(&mut self, mut rest: std::str::Chars) -> Option<String> {
+        match rest.next() {
+            None => self.word.take(),
+            Some(c) => {
+                let child = self.children.get_mut(&c)?;
+                let removed = child.remove(rest);
+                if child.is_empty() {
+                    self.children.remove(&c);
+                }
+                removed
+            }
+        }
+    }
+}
+
+/// A set of strings, searched by prefix.
+#[derive(Debug, Default, Clone)]
+pub struct StringTable {
+    root: Node,
+    len: usize,
+}
+
+impl StringTable {
+    pub fn new() -> StringTable {
+        StringTable::default()
+    }
+
+    /// How many strings the table holds.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Add `s`, and say whether it's new: a table holds each string once.
+    pub fn insert(&mut self, s: &str) -> bool {
+        let mut node = &mut self.root;
+        for c in s.chars() {
+            node = node.children.entry(c).or_default();
+        }
+        if node.word.is_some() {
+            return false;
+        }
+        node.word = Some(s.to_string());
+        self.len += 1;
+        true
+    }
+
+    pub fn contains(&self, s: &str) -> bool {
+        self.root.find(s).is_some_and(|node| node.word.is_some())
+    }
+
+    /// Take `s` out, giving back the String the table kept, if it held `s`.
+    pub fn remove(&mut self, s: &str) -> Option<String> {
+        let removed = self.root.remove(s.chars());
+        if removed.is_some() {
+            self.len -= 1;
+        }
+        removed
+    }
+
+    /// The strings that start with `prefix`, in order. Each is borrowed from the table
+    /// (note 1 in lib.rs), and so is the iterator: nothing is copied or collected.
+    pub fn find_by_prefix(&self, prefix: &str) -> Matches<'_> {
+        Matches { stack: self.root.find(prefix).into_iter().collect() }
+    }
+
+    /// What all the strings that start with `prefix` have in common, which is `prefix`
+    /// itself or more: what a shell would complete `prefix` to. None if no string
+    /// starts with it; with "", the prefix that the whole table shares.
+    pub fn longest_common_prefix(&self, prefix: &str) -> Option<&str> {
+        let mut node = self.root.find(prefix)?;
+        let mut len = prefix.len();
+        // 3.  Below `prefix`, the strings agree for as long as the path doesn't branch:
+        //     one child, and no string ending on the way.
+        while node.word.is_none() && node.children.len() == 1 {
+            let (c, child) = node.children.iter().next().unwrap();
+            len += c.len_utf8();
+            node = child;
+        }
+        // 4.  The common prefix is the start of any string below `node`; taking it as a
+        //     slice of one of them, not building a new String, keeps it a borrow.
+        let first = Matches { stack: vec![node] }.next()?;
+        Some(&first[..len])
+    }
+}
+
+impl<S: AsRef<str>> FromIterator<S> for StringTable {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> StringTable {
+        let mut table = StringTable::new();
+        for s in iter {
+            table.insert(s.as_ref());
+        }
+        table
+    }
+}
+
+/// The strings below a node of a StringTable, in order: see `find_by_prefix`.
+// 5.  A walk with a stack of the nodes still to visit, instead of recursion, so that it
+//     can stop after any string and go on when next is called again. Pushing each node's
+//     children last to first pops them first to last; and a node's own string comes
+//     before everything below it, as "tea" comes before "team".
+#[derive(Debug, Clone)]
+pub struct Matches<'a> {
+    stack: Vec<&'a Node>,
+}
+
+impl<'a> Iterator for Matches<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        while let Some(node) = self.stack.pop() {
+            self.stack.extend(node.children.values().rev());
+            if let Some(word) = &node.word {
+                return Some(word);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+fn table(words: &[&str]) -> StringTable {
+    words.iter().collect()
+}
+
+#[test]
+fn test_insert() {
+    let mut t = StringTable::new();
+    assert!(t.is_empty());
+    assert!(t.insert("tea"));
+    assert!(t.insert("team"));
+    assert!(!t.insert("tea"));
+    assert!(t.insert(""));
+    assert_eq!(t.len(), 3);
+    assert!(t.contains("tea") && t.contains("team") && t.contains(""));
+    assert!(!t.contains("te") && !t.contains("teams") && !t.contains("x"));
+}
+
+#[test]
+fn test_find_by_prefix() {
+    let t = table(&["toast", "team", "tea", "ten", "a", "tease"]);
+    let found: Vec<&str> = t.find_by_prefix("te").collect();
+    assert_eq!(found, ["tea", "team", "tease", "ten"]);
+    let all: Vec<&str> = t.find_by_prefix("").collect();
+    assert_eq!(all, ["a", "tea", "team", "tease", "ten", "toast"]);
+    assert_eq!(t.find_by_prefix("tea").next(), Some("tea"));
+    assert_eq!(t.find_by_prefix("toast").collect::<Vec<_>>(), ["toast"]);
+    assert_eq!(t.find_by_prefix("toasts").next(), None);
+    assert_eq!(t.find_by_prefix("b").next(), None);
+    assert_eq!(StringTable::new().find_by_prefix("").next(), None);
+    // Only as much of the tree is walked as is asked for.
+    assert_eq!(t.find_by_prefix("t").nth(2), Some("tease"));
+}
+
+#[test]
+fn test_find_by_prefix_unicode() {
+    let t = table(&["naïve", "naive", "日本", "日本語", "日曜"]);
+    assert_eq!(t.find_by_prefix("na").collect::<Vec<_>>(), ["naive", "naïve"]);
+    assert_eq!(t.find_by_prefix("日本").collect::<Vec<_>>(), ["日本", "日本語"]);
+    assert_eq!(t.longest_common_prefix("日"), Some("日"));
+    assert_eq!(t.longest_common_prefix("日本"), Some("日本"));
+    assert_eq!(t.longest_common_prefix("日曜"), Some("日曜"));
+    assert_eq!(table(&["日本語", "日本人"]).longest_common_prefix(""), Some("日本"));
+}
+
+#[test]
+fn test_remove() {
+    let mut t = table(&["tea", "team", "ten"]);
+    assert_eq!(t.remove("te"), None);
+    assert_eq!(t.remove("teams"), None);
+    assert_eq!(t.remove("tea"), Some("tea".to_string()));
+    assert_eq!(t.remove("tea"), None);
+    assert_eq!(t.len(), 2);
+    assert!(!t.contains("tea") && t.contains("team"));
+    assert_eq!(t.find_by_prefix("tea").collect::<Vec<_>>(), ["team"]);
+
+    // Removing the last string below a node removes the node: nothing is left to find
+    // by its prefix, and no common prefix is stuck on a dead branch.
+    assert_eq!(t.remove("team"), Some("team".to_string()));
+    assert_eq!(t.find_by_prefix("tea").next(), None);
+    assert_eq!(t.longest_common_prefix(""), Some("ten"));
+    assert_eq!(t.remove("ten"), Some("ten".to_string()));
+    assert!(t.is_empty());
+    assert!(t.root.is_empty());
+}
+
+#[test]
+fn test_longest_common_prefix() {
+    let t = table(&["interior", "interval", "internal", "into"]);
+    assert_eq!(t.longest_common_prefix(""), Some("int"));
+    assert_eq!(t.longest_common_prefix("inte"), Some("inter"));
+    assert_eq!(t.longest_common_prefix("interv"), Some("interval"));
+    assert_eq!(t.longest_common_prefix("into"), Some("into"));
+    assert_eq!(t.longest_common_prefix("x"), None);
+    assert_eq!(StringTable::new().longest_common_prefix(""), None);
+    // A string that ends on the way stops the prefix there: "tea" is all that "tea" and
+    // "team" have in common.
+    assert_eq!(table(&["tea", "team"]).longest_common_prefix("t"), Some("tea"));
+    assert_eq!(table(&["", "a"]).longest_common_prefix(""), Some(""));
+}
+
+#[test]
+fn test_matches_borrow_the_table() {
+    // The &strs are the table's own Strings, not copies.
+    let t = table(&["tea"]);
+    let found = t.find_by_prefix("t").next().unwrap();
+    let kept = t.root.find("tea").unwrap().word.as_ref().unwrap();
+    assert_eq!(found.as_ptr(), kept.as_ptr());
+}
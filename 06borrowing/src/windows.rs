@@ -0,0 +1,123 @@
+// Windows: every run of `size` neighbours in a slice, each a slice itself, what the
+// standard library's slice::windows gives, written out by hand to show the lifetimes.
+//
+//   let readings = vec![3, 1, 4, 1, 5];
+//   windows(&readings, 3)  ->  [3, 1, 4], [1, 4, 1], [4, 1, 5]
+//
+// Note:
+//
+// 1.) Windows<'a, T> holds a &'a [T] borrowed from whoever owns the buffer, and hands out
+//     &'a [T] too: pieces of that same buffer, not of the iterator. So a window can be
+//     kept after the iterator is gone, or after next has been called again, for as long
+//     as the buffer is there; and not a moment longer (tests/ui/window_outlives_buffer.rs).
+// 2.) That's the Iterator trait's own rule: next(&mut self) -> Option<Self::Item>, and
+//     Item can't mention the lifetime of that &mut self. An iterator can only lend out
+//     what it borrowed from elsewhere, for as long as it borrowed it, never a reference
+//     into itself.
+//
+
+/// An iterator over the windows of a slice: see `windows`.
+#[derive(Debug, Clone)]
+pub struct Windows<'a, T> {
+    // 1.  What's left to look at: the next window starts at rest[0]. Each call to next
+    //     takes one element off the front, so the slice shrinks, but the buffer it
+    //     points into doesn't change.
+    rest: &'a [T],
+    size: usize,
+}
+
+/// The windows of `size` neighbours in `buf`, from the front: `buf.len() - size + 1` of
+/// them, or none if `buf` is shorter than `size`. Panics if `size` is 0, as there's no
+/// end to windows of nothing.
+pub fn windows<T>(buf: &[T], size: usize) -> Windows<'_, T> {
+    assert!(size != 0, "windows of size 0");
+    Windows { rest: buf, size }
+}
+
+impl<'a, T> Iterator for Windows<'a, T> {
+    // 2.  The item's lifetime is the buffer's 'a, spelled out. With `&[T]` alone the
+    //     compiler would have no lifetime to give it: an associated type isn't a
+    //     function signature, and there's nothing to elide.
+    type Item = &'a [T];
+
+    fn next(&mut self) -> Option<&'a [T]> {
+        let window = self.rest.get(..self.size)?;
+        // 3.  self.rest is a &'a [T]: slicing it makes another &'a [T], borrowed from the
+        //     buffer, not from self, though it's reached through self. A borrow through a
+        //     shared reference can last as long as that reference does; with a &'a mut [T]
+        //     it couldn't, which is why a hand-written windows_mut doesn't compile.
+        self.rest = &self.rest[1..];
+        Some(window)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = (self.rest.len() + 1).saturating_sub(self.size);
+        (n, Some(n))
+    }
+}
+
+impl<T> ExactSizeIterator for Windows<'_, T> {}
+
+/// The mean of each window of `size` in `samples`.
+pub fn moving_average(samples: &[f64], size: usize) -> Vec<f64> {
+    windows(samples, size)
+        .map(|window| window.iter().sum::<f64>() / size as f64)
+        .collect()
+}
+
+#[test]
+fn test_windows() {
+    let readings = vec![3, 1, 4, 1, 5];
+    let all: Vec<&[i32]> = windows(&readings, 3).collect();
+    assert_eq!(all, [&[3, 1, 4][..], &[1, 4, 1], &[4, 1, 5]]);
+    assert_eq!(windows(&readings, 5).collect::<Vec<_>>(), [&readings[..]]);
+    assert_eq!(windows(&readings, 6).next(), None);
+    assert_eq!(windows(&readings, 1).count(), 5);
+    assert_eq!(windows::<u8>(&[], 1).next(), None);
+    // the same as the standard library's
+    for size in 1..=6 {
+        assert!(windows(&readings, size).eq(readings.windows(size)));
+    }
+}
+
+#[test]
+#[should_panic(expected = "windows of size 0")]
+fn test_windows_of_nothing() {
+    windows(&[1, 2, 3], 0);
+}
+
+#[test]
+fn test_len() {
+    let readings = [3, 1, 4, 1, 5];
+    let mut w = windows(&readings, 2);
+    assert_eq!(w.len(), 4);
+    w.next();
+    assert_eq!(w.len(), 3);
+    assert_eq!(windows(&readings, 9).len(), 0);
+    assert_eq!(w.by_ref().count(), 3);
+    assert_eq!(w.len(), 0);
+}
+
+#[test]
+fn test_windows_outlive_the_iterator() {
+    // Note 1: the windows borrow the buffer, not the iterator, so they can all be held
+    // at once, and kept once the iterator is dropped.
+    let buf = String::from("rust");
+    let bytes = buf.as_bytes();
+    let (first, second, last) = {
+        let mut w = windows(bytes, 2);
+        let first = w.next().unwrap();
+        let second = w.next().unwrap();
+        (first, second, w.last().unwrap())
+    };
+    assert_eq!((first, second, last), (&b"ru"[..], &b"us"[..], &b"st"[..]));
+    // and they are the buffer itself, not copies of it
+    assert_eq!(second.as_ptr(), bytes[1..].as_ptr());
+}
+
+#[test]
+fn test_moving_average() {
+    assert_eq!(moving_average(&[1.0, 2.0, 3.0, 4.0, 5.0], 2), [1.5, 2.5, 3.5, 4.5]);
+    assert_eq!(moving_average(&[2.0, 4.0, 6.0], 3), [4.0]);
+    assert!(moving_average(&[2.0], 3).is_empty());
+}
@@ -0,0 +1,32 @@
+// Run the chapter's demo binary and check the parts of its output that are deterministic.
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+// The works are listed from a HashMap, so artists come out in no particular order.
+#[test]
+fn prints_the_demo() {
+    Command::cargo_bin("borrowing").unwrap().assert()
+        .success()
+        .stdout(predicate::str::starts_with("Hello, Borrowing!\n")
+            .and(predicate::str::contains("works by Gesualdo:\n  Tenebrae Responsoria\n  many madrigals\n"))
+            .and(predicate::str::contains("works by Caravaggio:\n  The Calling of St. Matthew\n  The Musicians\n"))
+            .and(predicate::str::ends_with("720\n100\n1000\n")));
+}
+
+// With --deterministic the artists are sorted.
+#[test]
+fn sorts_the_table_when_deterministic() {
+    Command::cargo_bin("borrowing").unwrap().arg("--deterministic").assert()
+        .success()
+        .stdout(predicate::str::starts_with("Hello, Borrowing!
+works by Caravaggio:
+  The Calling of St. Matthew
+  The Musicians
+works by Cellini:
+  Perseus with the head of Medusa
+  a salt cellar
+works by Gesualdo:
+  Tenebrae Responsoria
+  many madrigals
+"));
+}
@@ -0,0 +1,13 @@
+// 9.12 `extend(&mut wave, &wave)`: a mutable and a shared reference to the same vector at
+// once. If it were allowed, pushing could reallocate the buffer `slice` is reading.
+fn extend(vec: &mut Vec<f64>, slice: &[f64]) {
+    for elt in slice {
+        vec.push(*elt);
+    }
+}
+
+fn main() {
+    let mut wave = vec![0.0, 1.0, 0.0, -1.0];
+    extend(&mut wave, &wave);
+    println!("{:?}", wave);
+}
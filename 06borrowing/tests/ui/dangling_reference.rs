@@ -0,0 +1,10 @@
+// 9.1 Borrowing a Local Variable: `x` is dropped at the end of the inner block, but `r`
+// still points at it afterwards.
+fn main() {
+    let r;
+    {
+        let x = 1;
+        r = &x;
+    }
+    assert_eq!(*r, 1);
+}
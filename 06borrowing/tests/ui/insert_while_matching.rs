@@ -0,0 +1,11 @@
+// StringTable (note 9.10): the matches find_by_prefix hands out are borrowed from the
+// table, so it can't be changed while they're in use.
+use borrowing::StringTable;
+
+fn main() {
+    let mut table = StringTable::new();
+    table.insert("test");
+    let found: Vec<&str> = table.find_by_prefix("t").collect();
+    table.insert("tea");
+    println!("{:?}", found);
+}
@@ -0,0 +1,8 @@
+// 9.11 Sharing Versus Mutation: `v` can't be moved while the shared reference `r` is
+// still going to be used.
+fn main() {
+    let v = vec![4, 8, 19, 27, 34, 10];
+    let r = &v;
+    let aside = v;
+    println!("{} {:?}", r[0], aside);
+}
@@ -0,0 +1,8 @@
+// Interior mutability (note 10.2): the rule RefCell checks at run time, checked here at
+// compile time. While `lines` reads the Vec, nothing may change it.
+fn main() {
+    let mut log = vec!["one".to_string()];
+    let lines = &log;
+    log.push("oops".to_string());
+    println!("{}", lines.len());
+}
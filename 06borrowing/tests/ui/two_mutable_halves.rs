@@ -0,0 +1,8 @@
+// Splitting borrows (note 11.1): two &mut slices of one array, written as two index
+// expressions, are two mutable borrows of the whole array as far as the compiler can
+// see. split_at_mut is how to say they don't overlap.
+fn main() {
+    let mut v = [1, 2, 3, 10, 20, 30];
+    let (front, back) = (&mut v[..3], &mut v[3..]);
+    front[0] += back[0];
+}
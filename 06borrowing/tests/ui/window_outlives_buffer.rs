@@ -0,0 +1,14 @@
+// Windows (windows.rs, note 1): the windows borrow the buffer the iterator walks, so they
+// can outlive the iterator, but not the buffer.
+use borrowing::windows;
+
+fn main() {
+    let steepest;
+    {
+        let readings: Vec<i32> = vec![3, 1, 4, 1, 5, 9];
+        steepest = windows(&readings, 2)
+            .max_by_key(|pair| (pair[1] - pair[0]).abs())
+            .unwrap();
+    }
+    println!("{:?}", steepest);
+}
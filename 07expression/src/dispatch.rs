@@ -0,0 +1,192 @@
+// A command dispatcher: a table from command names to closures, and a loop that looks up
+// the first word of each line and calls what it finds with the rest.
+//
+//   $ printf 'echo hi there\nadd 1 2 3\ngcd 12 18\nmul 2 3\n' | cargo run -q -p expression -- dispatch
+//   hi there
+//   6
+//   6
+//   error: unknown command "mul"; try help
+//
+// Note:
+//
+// 1.) Each command is a different closure, so a different type; what they share is the
+//     trait Fn(&[&str]) -> Result<String, String>. Box<dyn Fn(...)> puts each one on the
+//     heap behind a pointer of one size, so they fit in one HashMap.
+// 2.) Dispatching is a match on what the lookup gives: the command, or None, which is
+//     an error of its own and not a panic. Every command answers with a Result, and the
+//     loop prints either side of it the same way.
+// 3.) A closure can bring its own state: `count` keeps its total in a Cell it owns,
+//     which it can change through the & that calling a Fn gives it.
+//
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+
+/// What a command is: its arguments in, a line of output or an error out.
+pub type Handler = Box<dyn Fn(&[&str]) -> Result<String, String>>;
+
+/// Commands by name, and how to run them.
+#[derive(Default)]
+pub struct Dispatcher {
+    commands: HashMap<String, Handler>,
+}
+
+impl Dispatcher {
+    pub fn new() -> Dispatcher {
+        Dispatcher::default()
+    }
+
+    /// The dispatcher with echo, add, gcd and count.
+    pub fn with_builtins() -> Dispatcher {
+        let mut dispatcher = Dispatcher::new();
+        dispatcher.register("echo", |args| Ok(args.join(" ")));
+        dispatcher.register("add", |args| {
+            let numbers = parse_numbers("add", args)?;
+            numbers.iter().try_fold(0i64, |sum, &n| sum.checked_add(n))
+                .map(|sum| sum.to_string())
+                .ok_or_else(|| "add: the sum overflows".to_string())
+        });
+        dispatcher.register("gcd", |args| {
+            let numbers = parse_numbers("gcd", args)?;
+            // rustfun_common::gcd asserts that neither number is 0: check first, so a
+            // typo is an error message and not a panic
+            match numbers.iter().map(|&n| u64::try_from(n)).collect::<Result<Vec<u64>, _>>() {
+                Ok(numbers) if numbers.len() >= 2 && !numbers.contains(&0) =>
+                    Ok(rustfun_common::gcd_all(&numbers).to_string()),
+                _ => Err("gcd: expected two or more positive numbers".to_string()),
+            }
+        });
+        let calls = Cell::new(0);
+        dispatcher.register("count", move |_| {
+            calls.set(calls.get() + 1);
+            Ok(calls.get().to_string())
+        });
+        dispatcher
+    }
+
+    /// Add a command called `name`, or replace the one that had that name.
+    pub fn register<F>(&mut self, name: &str, handler: F)
+        where F: Fn(&[&str]) -> Result<String, String> + 'static
+    {
+        self.commands.insert(name.to_string(), Box::new(handler));
+    }
+
+    /// The commands' names, sorted.
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.commands.keys().map(String::as_str).collect();
+        names.sort();
+        names
+    }
+
+    /// Run the command that `line` starts with, on the words after it. `help`, which
+    /// lists the commands, is always there.
+    pub fn dispatch(&self, line: &str) -> Result<String, String> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let Some((&name, args)) = words.split_first() else {
+            return Err("no command".to_string());
+        };
+        match (name, self.commands.get(name)) {
+            (_, Some(handler)) => handler(args),
+            ("help", None) => {
+                let names: Vec<&str> = std::iter::once("help").chain(self.names()).collect();
+                Ok(format!("commands: {}", names.join(" ")))
+            }
+            (_, None) => Err(format!("unknown command {:?}; try help", name)),
+        }
+    }
+
+    /// Write a line to `output` for each line of `input`, as soon as it's read: the
+    /// command's output, or its error. Blank lines are skipped.
+    pub fn run(&self, input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+        for line in input.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match self.dispatch(&line) {
+                Ok(text) => writeln!(output, "{}", text)?,
+                Err(e) => writeln!(output, "error: {}", e)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `args` as numbers, or an error that names `command` and the word that isn't one.
+fn parse_numbers(command: &str, args: &[&str]) -> Result<Vec<i64>, String> {
+    args.iter()
+        .map(|arg| arg.parse().map_err(|_| format!("{}: {:?} isn't a number", command, arg)))
+        .collect()
+}
+
+#[test]
+fn test_builtins() {
+    let d = Dispatcher::with_builtins();
+    assert_eq!(d.dispatch("echo  hi   there"), Ok("hi there".to_string()));
+    assert_eq!(d.dispatch("echo"), Ok("".to_string()));
+    assert_eq!(d.dispatch("add 1 2 3"), Ok("6".to_string()));
+    assert_eq!(d.dispatch("add -5 2"), Ok("-3".to_string()));
+    assert_eq!(d.dispatch("add"), Ok("0".to_string()));
+    assert_eq!(d.dispatch("gcd 12 18"), Ok("6".to_string()));
+    assert_eq!(d.dispatch("gcd 12 18 8"), Ok("2".to_string()));
+    assert_eq!(d.dispatch("help"), Ok("commands: help add count echo gcd".to_string()));
+}
+
+#[test]
+fn test_argument_errors() {
+    let d = Dispatcher::with_builtins();
+    assert_eq!(d.dispatch("add 1 two"), Err("add: \"two\" isn't a number".to_string()));
+    assert_eq!(d.dispatch("gcd 1.5 3"), Err("gcd: \"1.5\" isn't a number".to_string()));
+    let positive = Err("gcd: expected two or more positive numbers".to_string());
+    assert_eq!(d.dispatch("gcd 0 3"), positive);
+    assert_eq!(d.dispatch("gcd -4 6"), positive);
+    assert_eq!(d.dispatch("gcd 12"), positive);
+    assert_eq!(d.dispatch(&format!("add {} 1", i64::MAX)), Err("add: the sum overflows".to_string()));
+}
+
+#[test]
+fn test_unknown_commands() {
+    let d = Dispatcher::with_builtins();
+    assert_eq!(d.dispatch("mul 2 3"), Err("unknown command \"mul\"; try help".to_string()));
+    assert_eq!(d.dispatch("ECHO hi"), Err("unknown command \"ECHO\"; try help".to_string()));
+    assert_eq!(d.dispatch("   "), Err("no command".to_string()));
+    let empty = Dispatcher::new();
+    assert_eq!(empty.dispatch("echo hi"), Err("unknown command \"echo\"; try help".to_string()));
+    assert_eq!(empty.dispatch("help"), Ok("commands: help".to_string()));
+}
+
+#[test]
+fn test_register() {
+    let mut d = Dispatcher::with_builtins();
+    // a closure that captures a value of its own, moved in
+    let greeting = String::from("hello");
+    d.register("greet", move |args| match args {
+        [name] => Ok(format!("{}, {}", greeting, name)),
+        _ => Err("greet: one name, please".to_string()),
+    });
+    assert_eq!(d.dispatch("greet ferris"), Ok("hello, ferris".to_string()));
+    assert_eq!(d.dispatch("greet"), Err("greet: one name, please".to_string()));
+    // registering a name again replaces the command
+    d.register("echo", |args| Ok(args.join("-")));
+    assert_eq!(d.dispatch("echo a b"), Ok("a-b".to_string()));
+    assert_eq!(d.names(), ["add", "count", "echo", "gcd", "greet"]);
+}
+
+#[test]
+fn test_state() {
+    // Note 3: each dispatcher's count has a Cell of its own.
+    let (a, b) = (Dispatcher::with_builtins(), Dispatcher::with_builtins());
+    assert_eq!(a.dispatch("count"), Ok("1".to_string()));
+    assert_eq!(a.dispatch("count"), Ok("2".to_string()));
+    assert_eq!(b.dispatch("count"), Ok("1".to_string()));
+}
+
+#[test]
+fn test_run() {
+    let mut output = Vec::new();
+    Dispatcher::with_builtins()
+        .run("echo hi\n\nadd 1 x\nnope\ncount\ncount\n".as_bytes(), &mut output)
+        .unwrap();
+    assert_eq!(String::from_utf8(output).unwrap(),
+               "hi\nerror: add: \"x\" isn't a number\nerror: unknown command \"nope\"; try help\n1\n2\n");
+}
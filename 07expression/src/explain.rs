@@ -0,0 +1,62 @@
+// What `expression --explain` prints: the chapter's notes, in short.
+use rustfun_common::{Explanation, Text, Topic};
+
+pub const EXPLANATION: Explanation = Explanation {
+    chapter: "07expression",
+    summary: Text {
+        en: "if, match and loops as expressions",
+        zh: "作为表达式的 if、match 和循环",
+    },
+    topics: &[
+        Topic {
+            title: Text { en: "Everything is an expression", zh: "一切皆表达式" },
+            body: Text {
+                en: "if, match and blocks produce values. A block's value is its last expression,\n\
+                     the one without a semicolon. `expression rpn 3 4 + 2 '*'` is a calculator\n\
+                     built almost entirely from such values.",
+                zh: "if、match 和代码块都会产生值。代码块的值是它最后一个不带分号的表达式。\n\
+                     `expression rpn 3 4 + 2 '*'` 是一个几乎全由这样的值构成的计算器。",
+            },
+        },
+        Topic {
+            title: Text { en: "match and if let", zh: "match 与 if let" },
+            body: Text {
+                en: "match must cover every possible value, and all its arms have one type. if let\n\
+                     is a match with a single pattern and an else. An enum, like ErrorCode, lets\n\
+                     the compiler check that every case is handled. Patterns nest into enums,\n\
+                     structs and slices, bind with name @ pattern, and take |, ranges and guards.\n\
+                     A state machine is one match on (state, event) with no `_` arm: the traffic\n\
+                     light of traffic.rs won't compile until every pair has a next state.",
+                zh: "match 必须覆盖所有可能的值, 各个分支的类型相同。if let 是只有一个模式加上\n\
+                     else 的 match。像 ErrorCode 这样的枚举, 让编译器检查每种情况都处理了。\n\
+                     模式可以深入枚举、结构体和切片, 用 name @ pattern 绑定, 还支持 |、范围和守卫。\n\
+                     状态机就是对 (状态, 事件) 的一个没有 `_` 分支的 match: traffic.rs 的红绿灯\n\
+                     要每一对都给出下一个状态才能编译。",
+            },
+        },
+        Topic {
+            title: Text { en: "Closures in a table", zh: "放进表里的闭包" },
+            body: Text {
+                en: "Every closure has a type of its own; Box<dyn Fn(&[&str]) -> Result<..>> gives\n\
+                     them one, so `expression dispatch` keeps its commands in a HashMap by name.\n\
+                     Looking one up is a match on Some or None, and an unknown command is an\n\
+                     error line, not a panic.",
+                zh: "每个闭包都有自己的类型; Box<dyn Fn(&[&str]) -> Result<..>> 让它们成为同一种\n\
+                     类型, 所以 `expression dispatch` 按名字把命令存在 HashMap 里。查找命令就是\n\
+                     对 Some 或 None 的 match, 未知命令输出一行错误, 而不是 panic。",
+            },
+        },
+        Topic {
+            title: Text { en: "Loops", zh: "循环" },
+            body: Text {
+                en: "while, while let, loop and for. A for loop over a collection moves it; loop\n\
+                     over &collection to borrow it instead. 0..20 is a Range value. loop can\n\
+                     produce one with break value, and a 'label lets break and continue leave\n\
+                     more than the innermost loop.",
+                zh: "while、while let、loop 和 for。for 循环遍历集合会移动它; 要借用就遍历\n\
+                     &collection。0..20 是一个 Range 值。loop 可以用 break value 产生值,\n\
+                     'label 让 break 和 continue 跳出的不只是最内层的循环。",
+            },
+        },
+    ],
+};
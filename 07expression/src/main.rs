@@ -1,4 +1,25 @@
+// The Vec is built push by push to show the loop below, as in the book.
+#![allow(clippy::vec_init_then_push)]
+use std::fmt;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+mod dispatch;
+mod explain;
+mod patterns;
+mod rpn;
+mod traffic;
+
 fn main() {
+    rustfun_common::init_logging();
+    explain::EXPLANATION.on_request();
+    // `expression rpn ...` is the calculator of rpn.rs, and `expression dispatch` the
+    // command loop of dispatch.rs, and nothing else
+    match std::env::args().nth(1).as_deref() {
+        Some("rpn") => return calculate(std::env::args().skip(2).collect()),
+        Some("dispatch") => return run_commands(),
+        _ => {}
+    }
     println!("Hello, expression!");
     // 1. Expression Language
     // 1.1 In Rust, if and match can produce values.
@@ -20,29 +41,13 @@ fn main() {
     // 4. match & if
     // match expressions are something like the C switch statement, but more flexible.
     let mut args:Vec<String> = std::env::args().skip(1).collect();
-    match args.pop() {
-        Some( code_str) => {
-            let code:i32 = match code_str.parse::<i32>()
-            {
-                Ok(n) => n,
-                Err(err) => {
-                    println!("err {}",err);
-                    -1
-                }, 
-                
-            };
-            match code {
-                0 => println!("OK"),
-                1 => println!("Wires Tangled"),
-                2 => println!("User Asleep"),
-                _ => println!("Unrecognized Error {}", code)
-            }
-        },
-        None => {
-            println!("None input");
-        }
-    }
+    // a --deterministic anywhere isn't the code; the output is the same every run anyway
+    rustfun_common::deterministic::take_flag(&mut args);
+    // (status_message, below, does the matching, and gives back the line to print)
+    println!("{}", status_message(args.pop().as_deref()));
     // 4.1 Rust prohibits match expressions that do not cover all possible values:
+    //     (ErrorCode, below: every match on it names every variant, and a new one
+    //     wouldn't compile until each match says what to do with it)
     // 4.2 All blocks of an if expression must produce values of the same type
     // 4.3 all arms of a match expression must have the same type 
     //
@@ -54,6 +59,38 @@ fn main() {
     //     _ => { block2 }
     // }
     //
+    // 5.1 Patterns go deeper than numbers: into enums inside structs, slices and ranges,
+    //     binding parts as they match (patterns.rs)
+    use patterns::{Command, Direction, Event, Key, Point};
+    let events = [
+        Event::Key { key: Key::Char('s'), ctrl: true },
+        Event::Key { key: Key::Enter, ctrl: false },
+        Event::Key { key: Key::Backspace, ctrl: false },
+        Event::Key { key: Key::Arrow(Direction::Left), ctrl: false },
+        Event::Click { at: Point { x: 0, y: 3 }, button: 1 },
+        Event::Resize(100, 30),
+        Event::Quit,
+    ];
+    let described: Vec<String> = events.iter().map(patterns::describe).collect();
+    assert_eq!(described, ["command S", "new line", "delete", "move a character",
+                           "select line 3", "redraw at 100x30", "save and quit"]);
+    assert_eq!(patterns::status_class(418), "client error");
+    assert_eq!(patterns::parse_command(&["take", "lamp"]), Ok(Command::Take(vec!["lamp"])));
+    assert_eq!(patterns::shape(&[3, 1, 3]), "from 3 and back");
+    assert_eq!(patterns::quadrants(&[(1, 1), (0, 2)]), ([1, 0, 0, 0], 1));
+    assert_eq!(patterns::score_table(&[("ann", 7)]), "1. ann 7 *\n");
+    // 5.2 A state machine is a match on (state, event): every pair named, and the value
+    //     of the match the next state (traffic.rs)
+    use traffic::{Event as Signal, Light};
+    let lights = traffic::run(Light::Red, &[Signal::Timer, Signal::Timer, Signal::Button,
+                                            Signal::Fault, Signal::Repaired]);
+    assert_eq!(lights, [Light::Red, Light::RedAmber, Light::Green { waiting: false },
+                        Light::Green { waiting: true }, Light::Flashing, Light::Red]);
+    assert!(traffic::step(Light::RedAmber, Signal::Timer).go());
+    assert!(Light::ALL.iter().all(|&light| {
+        Signal::ALL.iter().all(|&event| Light::ALL.contains(&traffic::step(light, event)))
+    }));
+    //
     // 6. loop
     // 6.1 A while loop behaves exactly like the C equivalent, except that again, the condition
     //   must be of the exact type bool
@@ -80,5 +117,235 @@ fn main() {
     }
     println!("{} error(s)", strings.len());
 
+    // 6.5 - 6.7 Loops that produce values, break out of more than one loop, and empty a
+    //     queue (collatz_steps, retry, find_in_grid, rows_without_negatives and
+    //     round_robin, below)
+    assert_eq!(collatz_steps(27), 111);
+    assert_eq!(retry(3, || "42".parse::<i32>()), Ok(42));
+    assert_eq!(find_in_grid(&[&[1, 2], &[3, 4]], 3), Some((1, 0)));
+    assert_eq!(rows_without_negatives(&[&[1, -2], &[3, 4]]), [1]);
+    assert_eq!(round_robin(&[("a", 3), ("b", 1)], 2), ["b", "a"]);
 }
 
+// 7.  `expression rpn 3 4 + 2 '*'` calculates what its arguments say, and
+//     `expression rpn` each line of stdin. if let, of note 5, takes the one case that
+//     needs something done: a failed calculation exits with status 1.
+fn calculate(mut args: Vec<String>) {
+    rustfun_common::deterministic::take_flag(&mut args);
+    let result = if args.is_empty() {
+        rpn::run(std::io::stdin().lock(), std::io::stdout()).map_err(|e| e.to_string())
+    } else {
+        rpn::evaluate(&args.join(" ")).map(|value| println!("{}", value)).map_err(|e| e.to_string())
+    };
+    if let Err(message) = result {
+        eprintln!("expression: {}", message);
+        std::process::exit(1);
+    }
+}
+
+// 8.  `expression dispatch` runs each line of stdin as a command: echo, add, gcd, count
+//     or help. The commands are closures in a HashMap, looked up by name (dispatch.rs);
+//     a line that fails prints an error and the loop goes on.
+fn run_commands() {
+    let dispatcher = dispatch::Dispatcher::with_builtins();
+    if let Err(e) = dispatcher.run(std::io::stdin().lock(), std::io::stdout()) {
+        eprintln!("expression: {}", e);
+        std::process::exit(1);
+    }
+}
+
+// 4.4 The status codes the match in main reads, as an enum: 0, 1 and 2 get names, and
+//     every other number is kept in Unrecognized, so that no i32 is lost on the way.
+/// A status code, read from the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ErrorCode {
+    Ok,
+    WiresTangled,
+    UserAsleep,
+    Unrecognized(i32),
+}
+
+impl ErrorCode {
+    /// The number the code was read from.
+    #[allow(dead_code)]
+    fn code(self) -> i32 {
+        match self {
+            ErrorCode::Ok => 0,
+            ErrorCode::WiresTangled => 1,
+            ErrorCode::UserAsleep => 2,
+            ErrorCode::Unrecognized(n) => n,
+        }
+    }
+}
+
+// 4.5 The match produces the value of the whole function: the parse's Err is passed on
+//     by ?, and each arm is an ErrorCode.
+impl FromStr for ErrorCode {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<ErrorCode, ParseIntError> {
+        Ok(match s.parse::<i32>()? {
+            0 => ErrorCode::Ok,
+            1 => ErrorCode::WiresTangled,
+            2 => ErrorCode::UserAsleep,
+            n => ErrorCode::Unrecognized(n),
+        })
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ErrorCode::Ok => write!(f, "OK"),
+            ErrorCode::WiresTangled => write!(f, "Wires Tangled"),
+            ErrorCode::UserAsleep => write!(f, "User Asleep"),
+            ErrorCode::Unrecognized(n) => write!(f, "Unrecognized Error {}", n),
+        }
+    }
+}
+
+#[test]
+fn test_error_code() {
+    assert_eq!("0".parse(), Ok(ErrorCode::Ok));
+    assert_eq!("1".parse(), Ok(ErrorCode::WiresTangled));
+    assert_eq!("2".parse(), Ok(ErrorCode::UserAsleep));
+    assert_eq!("-7".parse(), Ok(ErrorCode::Unrecognized(-7)));
+    assert!("x".parse::<ErrorCode>().is_err());
+    assert!("99999999999".parse::<ErrorCode>().is_err());
+    for n in [0, 1, 2, 3, -1, i32::MAX] {
+        assert_eq!(n.to_string().parse::<ErrorCode>().unwrap().code(), n);
+    }
+    assert_eq!(ErrorCode::WiresTangled.to_string(), "Wires Tangled");
+    assert_eq!(ErrorCode::Unrecognized(7).to_string(), "Unrecognized Error 7");
+}
+
+/// What main prints for the last command-line argument, if there is one.
+fn status_message(arg: Option<&str>) -> String {
+    match arg {
+        Some(code) => match code.parse::<ErrorCode>() {
+            Ok(code) => code.to_string(),
+            Err(err) => format!("err {}", err),
+        },
+        None => "None input".to_string(),
+    }
+}
+
+#[test]
+fn test_status_message() {
+    assert_eq!(status_message(Some("0")), "OK");
+    assert_eq!(status_message(Some("7")), "Unrecognized Error 7");
+    assert_eq!(status_message(Some("x")), "err invalid digit found in string");
+    assert_eq!(status_message(None), "None input");
+}
+
+// 6.5 A loop is an expression too, and `break value` is how it gets a value: the loop's
+//     type is the type of what its breaks carry. (A while or for loop might run zero
+//     times, so only loop can break with a value.)
+/// How many steps of the Collatz rule, n/2 if even and 3n+1 if odd, take `n` to 1.
+fn collatz_steps(mut n: u64) -> u32 {
+    assert!(n > 0, "collatz_steps(0) never reaches 1");
+    let mut steps = 0;
+    loop {
+        if n == 1 {
+            break steps;
+        }
+        n = if n.is_multiple_of(2) { n / 2 } else { 3 * n + 1 };
+        steps += 1;
+    }
+}
+
+/// Call `op` until it succeeds, `attempts` times at most, and give its last result.
+fn retry<T, E>(mut attempts: u32, mut op: impl FnMut() -> Result<T, E>) -> Result<T, E> {
+    loop {
+        // each arm either breaks out with the loop's value, or counts and goes round
+        match op() {
+            Ok(value) => break Ok(value),
+            Err(e) if attempts <= 1 => break Err(e),
+            Err(_) => attempts -= 1,
+        }
+    }
+}
+
+#[test]
+fn test_loop_values() {
+    assert_eq!(collatz_steps(1), 0);
+    assert_eq!(collatz_steps(6), 8);   // 6 3 10 5 16 8 4 2 1
+    assert_eq!(collatz_steps(27), 111);
+
+    let mut calls = 0;
+    let flaky = |calls: &mut u32| { *calls += 1; if *calls < 3 { Err(*calls) } else { Ok("done") } };
+    assert_eq!(retry(5, || flaky(&mut calls)), Ok("done"));
+    assert_eq!(calls, 3);
+    calls = 0;
+    assert_eq!(retry(2, || flaky(&mut calls)), Err(2));
+    assert_eq!(calls, 2);
+}
+
+// 6.6 A label names a loop, 'like_this, so that break and continue can say which one
+//     they mean from inside another. A labeled block can be broken out of with a value
+//     too, which a for loop can't.
+/// Where `target` first is in `grid`, row by row, as (row, column).
+fn find_in_grid(grid: &[&[i32]], target: i32) -> Option<(usize, usize)> {
+    'search: {
+        for (r, row) in grid.iter().enumerate() {
+            for (c, &value) in row.iter().enumerate() {
+                if value == target {
+                    break 'search Some((r, c));  // out of both loops, with a value
+                }
+            }
+        }
+        None
+    }
+}
+
+/// The rows of `grid` without a negative number.
+fn rows_without_negatives(grid: &[&[i32]]) -> Vec<usize> {
+    let mut rows = Vec::new();
+    'rows: for (r, row) in grid.iter().enumerate() {
+        for &value in row.iter() {
+            if value < 0 {
+                continue 'rows;  // the next row, not just the next value
+            }
+        }
+        rows.push(r);
+    }
+    rows
+}
+
+#[test]
+fn test_labeled_breaks() {
+    let grid: &[&[i32]] = &[&[1, 2, 3], &[4, -5, 6], &[7, 8, 4]];
+    assert_eq!(find_in_grid(grid, 4), Some((1, 0)));  // the first, not the one in row 2
+    assert_eq!(find_in_grid(grid, 8), Some((2, 1)));
+    assert_eq!(find_in_grid(grid, 9), None);
+    assert_eq!(find_in_grid(&[], 1), None);
+    assert_eq!(rows_without_negatives(grid), [0, 2]);
+    assert_eq!(rows_without_negatives(&[&[], &[-1]]), [0]);
+}
+
+// 6.7 while let runs for as long as its pattern matches: here, for as long as pop_front
+//     finds something in the queue, even as the loop puts work back on it.
+/// Run `jobs`, each a name and how much work it needs, `slice` of work at a time in
+/// turn, and give the names in the order they finish.
+fn round_robin<'a>(jobs: &[(&'a str, u32)], slice: u32) -> Vec<&'a str> {
+    use std::collections::VecDeque;
+    let mut queue: VecDeque<(&str, u32)> = jobs.iter().copied().collect();
+    let mut finished = Vec::new();
+    while let Some((name, left)) = queue.pop_front() {
+        if left > slice {
+            queue.push_back((name, left - slice));
+        } else {
+            finished.push(name);
+        }
+    }
+    finished
+}
+
+#[test]
+fn test_round_robin() {
+    let jobs = [("compile", 5), ("lint", 1), ("test", 3), ("doc", 2)];
+    assert_eq!(round_robin(&jobs, 2), ["lint", "doc", "test", "compile"]);
+    assert_eq!(round_robin(&jobs, 10), ["compile", "lint", "test", "doc"]);
+    assert_eq!(round_robin(&[("zero", 0)], 1), ["zero"]);
+    assert!(round_robin(&[], 1).is_empty());
+}
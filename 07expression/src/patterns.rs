@@ -0,0 +1,248 @@
+// Patterns beyond `0 => ...`: matching into enums inside structs inside enums, into
+// slices, and on ranges, binding parts as they match.
+//
+// Note:
+//
+// 1.) A pattern has the shape of the value it matches, and can go as deep as the value
+//     does: `Event::Key { key: Key::Arrow(Direction::Up), .. }`.
+// 2.) The arms are tried in order, and the first that matches wins; so a general arm has
+//     to come after the particular ones it would swallow.
+// 3.) A match must still cover every value (note 4.1). Leaving out a case doesn't
+//     compile, and the error names one it missed (tests/ui/missing_arm.rs). The tests
+//     below go through each arm, so none of them is there only to satisfy the checker.
+//
+use std::fmt::Write;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Char(char),
+    Enter,
+    Backspace,
+    Arrow(Direction),
+}
+
+/// What a terminal program might be told happened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    Key { key: Key, ctrl: bool },
+    Click { at: Point, button: u8 },
+    Resize(u32, u32),
+    Quit,
+}
+
+/// What `event` means to an editor.
+pub fn describe(event: &Event) -> String {
+    match *event {
+        // 1.  A literal inside a pattern inside a pattern: only ctrl-c.
+        Event::Key { key: Key::Char('c'), ctrl: true } => "interrupt".to_string(),
+        // 2.  `name @ pattern` checks the pattern and keeps the value: here the letter,
+        //     once it's known to be one.
+        Event::Key { key: Key::Char(c @ 'a'..='z'), ctrl: true } =>
+            format!("command {}", c.to_ascii_uppercase()),
+        Event::Key { key: Key::Char(c @ '0'..='9'), ctrl: false } =>
+            format!("digit {}", c),
+        Event::Key { key: Key::Char(c), ctrl: false } => format!("typed {:?}", c),
+        // 3.  A ctrl with anything else, and Enter and Backspace with or without: `_` and
+        //     `..` say which parts don't matter.
+        Event::Key { key: Key::Char(_), ctrl: true } => "nothing".to_string(),
+        Event::Key { key: Key::Enter, .. } => "new line".to_string(),
+        Event::Key { key: Key::Backspace, .. } => "delete".to_string(),
+        // 4.  Or-patterns, |, nest too: one arm for both of the up-and-down arrows.
+        Event::Key { key: Key::Arrow(Direction::Up | Direction::Down), .. } =>
+            "move a line".to_string(),
+        Event::Key { key: Key::Arrow(Direction::Left | Direction::Right), .. } =>
+            "move a character".to_string(),
+        // 5.  A struct pattern binds fields by name; `y: row` renames one as it binds it.
+        Event::Click { at: Point { x: 0, y: row }, button: 1 } =>
+            format!("select line {}", row),
+        Event::Click { at: Point { x, y }, button: 1 } => format!("cursor to {},{}", x, y),
+        Event::Click { button: 2 | 3, .. } => "menu".to_string(),
+        Event::Click { button, .. } => format!("ignored button {}", button),
+        // 6.  A guard, `if ...`, tests what a pattern can't: here, two fields together.
+        Event::Resize(w, h) if w < 80 || h < 24 => format!("too small at {}x{}", w, h),
+        Event::Resize(w, h) => format!("redraw at {}x{}", w, h),
+        Event::Quit => "save and quit".to_string(),
+    }
+}
+
+#[test]
+fn test_describe() {
+    let key = |key, ctrl| describe(&Event::Key { key, ctrl });
+    assert_eq!(key(Key::Char('c'), true), "interrupt");
+    assert_eq!(key(Key::Char('s'), true), "command S");
+    assert_eq!(key(Key::Char('7'), false), "digit 7");
+    assert_eq!(key(Key::Char('s'), false), "typed 's'");
+    assert_eq!(key(Key::Char('7'), true), "nothing");
+    assert_eq!(key(Key::Char('S'), true), "nothing");
+    assert_eq!(key(Key::Enter, true), "new line");
+    assert_eq!(key(Key::Backspace, false), "delete");
+    for (direction, moves) in [(Direction::Up, "move a line"), (Direction::Down, "move a line"),
+                               (Direction::Left, "move a character"),
+                               (Direction::Right, "move a character")] {
+        assert_eq!(key(Key::Arrow(direction), false), moves);
+    }
+
+    let click = |x, y, button| describe(&Event::Click { at: Point { x, y }, button });
+    assert_eq!(click(0, 12, 1), "select line 12");
+    assert_eq!(click(5, 12, 1), "cursor to 5,12");
+    assert_eq!(click(0, 12, 3), "menu");
+    assert_eq!(click(0, 12, 9), "ignored button 9");
+
+    assert_eq!(describe(&Event::Resize(79, 40)), "too small at 79x40");
+    assert_eq!(describe(&Event::Resize(120, 23)), "too small at 120x23");
+    assert_eq!(describe(&Event::Resize(80, 24)), "redraw at 80x24");
+    assert_eq!(describe(&Event::Quit), "save and quit");
+}
+
+// 7.  Range patterns: `a..=b` includes b, and `a..` runs to the top of the type. They're
+//     checked for exhaustiveness like any others, so the last arm has to be there, for
+//     the numbers below 100 and above 599: u16 goes up to 65535.
+/// The class of an HTTP status code.
+pub fn status_class(status: u16) -> &'static str {
+    match status {
+        100..=199 => "informational",
+        200 | 204 => "success",
+        201..=299 => "success, with a note",
+        300..=399 => "redirection",
+        404 | 410 => "not found",
+        400..=499 => "client error",
+        500..=599 => "server error",
+        0..=99 | 600.. => "not a status",
+    }
+}
+
+#[test]
+fn test_status_class() {
+    let classes: Vec<&str> = [100, 200, 204, 201, 301, 404, 410, 418, 503, 99, 600, 65535]
+        .iter().map(|&s| status_class(s)).collect();
+    assert_eq!(classes, ["informational", "success", "success", "success, with a note",
+                         "redirection", "not found", "not found", "client error",
+                         "server error", "not a status", "not a status", "not a status"]);
+}
+
+// 8.  Slice patterns match on length and elements at once. `..` stands for any number of
+//     elements, and `rest @ ..` keeps them as a slice. Matching on *words, the slice
+//     itself, the words are copied out as they're bound, but the rest can't be: ref
+//     borrows it instead.
+/// A command typed at an adventure game, already split into words.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command<'a> {
+    Go(Direction),
+    Take(Vec<&'a str>),
+    Look,
+    Quit,
+}
+
+pub fn parse_command<'a>(words: &[&'a str]) -> Result<Command<'a>, String> {
+    Ok(match *words {
+        ["go", "north"] | ["n"] => Command::Go(Direction::Up),
+        ["go", "south"] | ["s"] => Command::Go(Direction::Down),
+        ["go", "west"] | ["w"] => Command::Go(Direction::Left),
+        ["go", "east"] | ["e"] => Command::Go(Direction::Right),
+        ["go", place] => return Err(format!("can't go {}", place)),
+        ["go", ..] => return Err("go where? one direction at a time".to_string()),
+        ["take"] => return Err("take what?".to_string()),
+        ["take", "all", ..] => return Err("take them one by one".to_string()),
+        ["take", ref items @ ..] => Command::Take(items.to_vec()),
+        ["look"] | ["l"] | [] => Command::Look,
+        ["quit" | "exit"] => Command::Quit,
+        [word, ..] => return Err(format!("I don't know how to {}", word)),
+    })
+}
+
+#[test]
+fn test_parse_command() {
+    let parse = |line: &'static str| {
+        parse_command(&line.split_whitespace().collect::<Vec<_>>())
+    };
+    assert_eq!(parse("go north"), Ok(Command::Go(Direction::Up)));
+    assert_eq!(parse("e"), Ok(Command::Go(Direction::Right)));
+    assert_eq!(parse("go up"), Err("can't go up".to_string()));
+    assert_eq!(parse("go north east"), Err("go where? one direction at a time".to_string()));
+    assert_eq!(parse("go"), Err("go where? one direction at a time".to_string()));
+    assert_eq!(parse("take lamp"), Ok(Command::Take(vec!["lamp"])));
+    assert_eq!(parse("take lamp key"), Ok(Command::Take(vec!["lamp", "key"])));
+    assert_eq!(parse("take"), Err("take what?".to_string()));
+    assert_eq!(parse("take all lamps"), Err("take them one by one".to_string()));
+    assert_eq!(parse(""), Ok(Command::Look));
+    assert_eq!(parse("l"), Ok(Command::Look));
+    assert_eq!(parse("exit"), Ok(Command::Quit));
+    assert_eq!(parse("quit now"), Err("I don't know how to quit".to_string()));
+    assert_eq!(parse("dance"), Err("I don't know how to dance".to_string()));
+}
+
+/// How `numbers` run, from the shape of the slice.
+pub fn shape(numbers: &[i32]) -> String {
+    match numbers {
+        [] => "nothing".to_string(),
+        [one] => format!("just {}", one),
+        [first, .., last] if first == last => format!("from {} and back", first),
+        [first, second, ..] if first == second => format!("starts flat at {}", first),
+        [first, rest @ ..] => format!("{} then {} more", first, rest.len()),
+    }
+}
+
+#[test]
+fn test_shape() {
+    assert_eq!(shape(&[]), "nothing");
+    assert_eq!(shape(&[4]), "just 4");
+    assert_eq!(shape(&[4, 5, 4]), "from 4 and back");
+    assert_eq!(shape(&[4, 4]), "from 4 and back");  // the first arm that fits wins
+    assert_eq!(shape(&[4, 4, 5]), "starts flat at 4");
+    assert_eq!(shape(&[1, 2, 3]), "1 then 2 more");
+}
+
+// 9.  A for loop's variable is a pattern as well: it can take each tuple apart as it
+//     comes, with & to copy out of the reference, and with a nested tuple for
+//     enumerate's (index, item) when the item is itself a pair.
+/// How many `points` are in each quadrant, counter-clockwise from the top right, and how
+/// many are on an axis.
+pub fn quadrants(points: &[(i32, i32)]) -> ([usize; 4], usize) {
+    let (mut counts, mut on_axis) = ([0; 4], 0);
+    for &(x, y) in points {
+        match (x.signum(), y.signum()) {
+            (0, _) | (_, 0) => on_axis += 1,
+            (1, 1) => counts[0] += 1,
+            (-1, 1) => counts[1] += 1,
+            (-1, -1) => counts[2] += 1,
+            (1, -1) => counts[3] += 1,
+            (sx, sy) => unreachable!("signum gave {} and {}", sx, sy),
+        }
+    }
+    (counts, on_axis)
+}
+
+/// A table of `scores`, numbered from 1, with the best marked.
+pub fn score_table(scores: &[(&str, u32)]) -> String {
+    let best = scores.iter().map(|&(_, score)| score).max();
+    let mut table = String::new();
+    for (i, &(name, score)) in scores.iter().enumerate() {
+        let mark = if Some(score) == best { " *" } else { "" };
+        writeln!(table, "{}. {} {}{}", i + 1, name, score, mark).unwrap();
+    }
+    table
+}
+
+#[test]
+fn test_tuples_in_for_loops() {
+    let points = [(1, 2), (-3, 4), (-1, -1), (5, -5), (0, 3), (2, 0), (7, 7)];
+    assert_eq!(quadrants(&points), ([2, 1, 1, 1], 2));
+    assert_eq!(quadrants(&[]), ([0; 4], 0));
+    assert_eq!(score_table(&[("ann", 7), ("bo", 9), ("cy", 9)]),
+               "1. ann 7\n2. bo 9 *\n3. cy 9 *\n");
+    assert_eq!(score_table(&[]), "");
+}
@@ -0,0 +1,250 @@
+// A reverse-Polish calculator: `expression rpn 3 4 + 2 '*'` prints 14. Each number is
+// pushed on a stack, and each operator pops what it works on and pushes the result.
+//
+//   $ cargo run -q -p expression -- rpn 3 4 + 2 '*'
+//   14
+//   $ printf '1 2 /\n2 0.5 ^\n1 +\n' | cargo run -q -p expression -- rpn
+//   0.5
+//   1.4142135623730951
+//   error: + needs 2 numbers, but the stack has 1
+//
+// With no arguments after rpn, it reads one calculation per line from stdin. It's here
+// for the expressions more than the arithmetic: almost everything below is a match or
+// an if whose value is the point, not a statement that sets a variable (main.rs, notes
+// 1 and 4).
+//
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+/// An operator, and how many numbers it takes from the stack.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Pow,
+    Neg,
+    Sqrt,
+}
+
+impl Op {
+    fn arity(self) -> usize {
+        match self {
+            Op::Neg | Op::Sqrt => 1,
+            Op::Add | Op::Sub | Op::Mul | Op::Div | Op::Rem | Op::Pow => 2,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Op::Add => "+",
+            Op::Sub => "-",
+            Op::Mul => "*",
+            Op::Div => "/",
+            Op::Rem => "%",
+            Op::Pow => "^",
+            Op::Neg => "neg",
+            Op::Sqrt => "sqrt",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Token {
+    Number(f64),
+    Op(Op),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    /// A word that is neither a number nor an operator.
+    UnknownToken(String),
+    /// An operator with fewer numbers on the stack than it takes.
+    Underflow { op: Op, have: usize },
+    DivideByZero,
+    SqrtOfNegative(f64),
+    /// Nothing to calculate.
+    Empty,
+    /// More than one number left at the end: an operator is missing.
+    Leftover(usize),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::UnknownToken(word) => write!(f, "{:?} isn't a number or an operator", word),
+            Error::Underflow { op, have } =>
+                write!(f, "{} needs {} numbers, but the stack has {}", op.name(), op.arity(), have),
+            Error::DivideByZero => write!(f, "division by zero"),
+            Error::SqrtOfNegative(x) => write!(f, "sqrt of a negative number, {}", x),
+            Error::Empty => write!(f, "nothing to calculate"),
+            Error::Leftover(n) => write!(f, "{} numbers left on the stack; an operator is missing", n),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Split `line` into tokens at whitespace.
+pub fn tokenize(line: &str) -> Result<Vec<Token>, Error> {
+    line.split_whitespace()
+        .map(|word| {
+            // 1.  Every arm of the match is an Op but the last, which has none to give:
+            //     it returns from the closure instead, with the value of a match of its
+            //     own, a Result<Token, Error> like the closure's last expression.
+            let op = match word {
+                "+" => Op::Add,
+                "-" => Op::Sub,
+                "*" | "x" => Op::Mul,
+                "/" => Op::Div,
+                "%" => Op::Rem,
+                "^" => Op::Pow,
+                "neg" => Op::Neg,
+                "sqrt" => Op::Sqrt,
+                _ => return match word.parse::<f64>() {
+                    // "inf" and "NaN" parse as f64 too, but aren't numbers to type in
+                    Ok(n) if n.is_finite() => Ok(Token::Number(n)),
+                    _ => Err(Error::UnknownToken(word.to_string())),
+                },
+            };
+            Ok(Token::Op(op))
+        })
+        .collect()
+}
+
+#[test]
+fn test_tokenize() {
+    assert_eq!(tokenize(" 3 4.5\t+ -2 neg ").unwrap(),
+               [Token::Number(3.0), Token::Number(4.5), Token::Op(Op::Add),
+                Token::Number(-2.0), Token::Op(Op::Neg)]);
+    assert_eq!(tokenize("2 x 3").unwrap()[1], Token::Op(Op::Mul));
+    assert_eq!(tokenize("").unwrap(), []);
+    assert_eq!(tokenize("1 2 plus"), Err(Error::UnknownToken("plus".to_string())));
+    assert_eq!(tokenize("inf"), Err(Error::UnknownToken("inf".to_string())));
+    assert_eq!(tokenize("3+4"), Err(Error::UnknownToken("3+4".to_string())));
+}
+
+/// What `op` makes of `args`, which are as many numbers as it takes, in stack order.
+fn apply(op: Op, args: &[f64]) -> Result<f64, Error> {
+    // 2.  Every arm produces an f64, so the match is an f64 too; the arms that can fail
+    //     leave the function early with return, whose type, !, fits any arm.
+    Ok(match (op, args) {
+        (Op::Add, &[a, b]) => a + b,
+        (Op::Sub, &[a, b]) => a - b,
+        (Op::Mul, &[a, b]) => a * b,
+        (Op::Div, &[a, b]) | (Op::Rem, &[a, b]) => {
+            if b == 0.0 {
+                return Err(Error::DivideByZero);
+            }
+            if op == Op::Div { a / b } else { a % b }
+        }
+        (Op::Pow, &[a, b]) => a.powf(b),
+        (Op::Neg, &[a]) => -a,
+        (Op::Sqrt, &[a]) => if a < 0.0 { return Err(Error::SqrtOfNegative(a)) } else { a.sqrt() },
+        _ => unreachable!("{:?} given {} numbers", op, args.len()),
+    })
+}
+
+/// Run `tokens`, and give the one number they leave on the stack.
+pub fn eval(tokens: &[Token]) -> Result<f64, Error> {
+    let mut stack: Vec<f64> = Vec::new();
+    for &token in tokens {
+        let value = match token {
+            Token::Number(n) => n,
+            Token::Op(op) => {
+                // 3.  A block's value is its last expression: the result of apply, or
+                //     the error that ? returns early with.
+                let have = stack.len();
+                let Some(start) = have.checked_sub(op.arity()) else {
+                    return Err(Error::Underflow { op, have });
+                };
+                let result = apply(op, &stack[start..])?;
+                stack.truncate(start);
+                result
+            }
+        };
+        stack.push(value);
+    }
+    match stack[..] {
+        [] => Err(Error::Empty),
+        [result] => Ok(result),
+        _ => Err(Error::Leftover(stack.len())),
+    }
+}
+
+/// Tokenize and run `line`.
+pub fn evaluate(line: &str) -> Result<f64, Error> {
+    eval(&tokenize(line)?)
+}
+
+#[test]
+fn test_operators() {
+    assert_eq!(evaluate("3 4 +"), Ok(7.0));
+    assert_eq!(evaluate("3 4 -"), Ok(-1.0));   // the first pushed is the left operand
+    assert_eq!(evaluate("3 4 *"), Ok(12.0));
+    assert_eq!(evaluate("3 4 /"), Ok(0.75));
+    assert_eq!(evaluate("7 4 %"), Ok(3.0));
+    assert_eq!(evaluate("-7 4 %"), Ok(-3.0));  // the sign of the left, as with Rust's %
+    assert_eq!(evaluate("2 10 ^"), Ok(1024.0));
+    assert_eq!(evaluate("5 neg"), Ok(-5.0));
+    assert_eq!(evaluate("16 sqrt"), Ok(4.0));
+    assert_eq!(evaluate("42"), Ok(42.0));
+}
+
+#[test]
+fn test_longer_calculations() {
+    // (3 + 4) * 2, and 3 + 4 * 2: the order of the tokens is the order of the work, so
+    // there's no precedence and no parentheses.
+    assert_eq!(evaluate("3 4 + 2 *"), Ok(14.0));
+    assert_eq!(evaluate("3 4 2 * +"), Ok(11.0));
+    // the hypotenuse of a 3-4-5 triangle
+    assert_eq!(evaluate("3 2 ^ 4 2 ^ + sqrt"), Ok(5.0));
+    assert_eq!(evaluate("1 2 3 4 5 + + + +"), Ok(15.0));
+    assert_eq!(evaluate("0.1 0.2 +"), Ok(0.1 + 0.2));
+}
+
+#[test]
+fn test_errors() {
+    assert_eq!(evaluate(""), Err(Error::Empty));
+    assert_eq!(evaluate("+"), Err(Error::Underflow { op: Op::Add, have: 0 }));
+    assert_eq!(evaluate("1 +"), Err(Error::Underflow { op: Op::Add, have: 1 }));
+    assert_eq!(evaluate("neg"), Err(Error::Underflow { op: Op::Neg, have: 0 }));
+    assert_eq!(evaluate("1 2"), Err(Error::Leftover(2)));
+    assert_eq!(evaluate("1 0 /"), Err(Error::DivideByZero));
+    assert_eq!(evaluate("1 0 %"), Err(Error::DivideByZero));
+    assert_eq!(evaluate("2 neg sqrt"), Err(Error::SqrtOfNegative(-2.0)));
+    assert_eq!(evaluate("1 2 ?"), Err(Error::UnknownToken("?".to_string())));
+    // an error stops the calculation where it is, whatever comes after
+    assert_eq!(evaluate("1 0 / 2 +"), Err(Error::DivideByZero));
+
+    assert_eq!(Error::Underflow { op: Op::Mul, have: 1 }.to_string(),
+               "* needs 2 numbers, but the stack has 1");
+    assert_eq!(Error::Leftover(2).to_string(), "2 numbers left on the stack; an operator is missing");
+    assert_eq!(Error::UnknownToken("?".to_string()).to_string(), "\"?\" isn't a number or an operator");
+}
+
+/// Write a line to `output` for each line of `input`, the result or the error, as soon
+/// as it's read; blank lines are skipped.
+pub fn run(input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+    for line in input.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match evaluate(&line) {
+            Ok(value) => writeln!(output, "{}", value)?,
+            Err(e) => writeln!(output, "error: {}", e)?,
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_run() {
+    let mut output = Vec::new();
+    run("3 4 +\n\n1 +\n  2 0.5 ^  \n".as_bytes(), &mut output).unwrap();
+    assert_eq!(String::from_utf8(output).unwrap(),
+               "7\nerror: + needs 2 numbers, but the stack has 1\n1.4142135623730951\n");
+}
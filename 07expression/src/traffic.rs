@@ -0,0 +1,160 @@
+// A pedestrian crossing's traffic light, as a state machine: a Light, an Event, and
+// step(light, event), one match that gives the next Light.
+//
+//   Red --timer--> RedAmber --timer--> Green --button--> Green (waiting)
+//    ^                                                         |
+//    +-------------------- Amber <----------timer--------------+
+//
+// and from anywhere, a fault sets it flashing until it's repaired.
+//
+// Note:
+//
+// 1.) The match in step is the whole table of transitions, on (light, event) pairs. It has
+//     no `_` arm: each light is named in the arms for each event, so a light or an event
+//     added later won't compile until step says what it does with it
+//     (tests/ui/missing_transition.rs). A `_` would quietly give the new one whatever
+//     the wildcard gives.
+// 2.) The data a state needs goes in the variant: Green { waiting } is two states, one
+//     type. A separate bool beside the enum could be true on a red light, where it means
+//     nothing; inside Green, it can't be.
+//
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Light {
+    Red,
+    RedAmber,
+    /// Green, and whether someone has pressed the button to cross.
+    Green { waiting: bool },
+    Amber,
+    /// Flashing amber: out of order, give way.
+    Flashing,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    /// The time for the current light is up.
+    Timer,
+    /// A pedestrian pressed the button.
+    Button,
+    Fault,
+    Repaired,
+}
+
+impl Light {
+    pub const ALL: [Light; 6] = [Light::Red, Light::RedAmber, Light::Green { waiting: false },
+                                 Light::Green { waiting: true }, Light::Amber, Light::Flashing];
+
+    /// Whether traffic may go.
+    pub fn go(self) -> bool {
+        matches!(self, Light::Green { .. })
+    }
+}
+
+impl Event {
+    pub const ALL: [Event; 4] = [Event::Timer, Event::Button, Event::Fault, Event::Repaired];
+}
+
+impl fmt::Display for Light {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Light::Red => "red",
+            Light::RedAmber => "red and amber",
+            Light::Green { waiting: false } => "green",
+            Light::Green { waiting: true } => "green, waiting",
+            Light::Amber => "amber",
+            Light::Flashing => "flashing amber",
+        })
+    }
+}
+
+/// The light after `event`, when it was `light`.
+pub fn step(light: Light, event: Event) -> Light {
+    use Event::*;
+    use Light::*;
+    match (light, event) {
+        (Red, Timer) => RedAmber,
+        (RedAmber, Timer) => Green { waiting: false },
+        // 1.  Green stays green for the traffic until someone wants to cross.
+        (Green { waiting: false }, Timer) => light,
+        (Green { waiting: true }, Timer) => Amber,
+        (Amber, Timer) => Red,
+        (Flashing, Timer) => Flashing,
+
+        (Green { .. }, Button) => Green { waiting: true },
+        // 2.  Pressing when the traffic has stopped, or is stopping, or when the light is
+        //     out of order, changes nothing.
+        (Red | RedAmber | Amber | Flashing, Button) => light,
+
+        (Red | RedAmber | Green { .. } | Amber | Flashing, Fault) => Flashing,
+
+        // 3.  Back from a fault, the light starts at red, where it's safe to start.
+        (Flashing, Repaired) => Red,
+        (Red | RedAmber | Green { .. } | Amber, Repaired) => light,
+    }
+}
+
+/// Each light `start` goes through for `events`, `start` first.
+pub fn run(start: Light, events: &[Event]) -> Vec<Light> {
+    let mut lights = vec![start];
+    lights.extend(events.iter().scan(start, |light, &event| {
+        *light = step(*light, event);
+        Some(*light)
+    }));
+    lights
+}
+
+#[test]
+fn test_cycle() {
+    use Event::*;
+    let lights = run(Light::Red, &[Timer, Timer, Timer, Button, Timer, Timer]);
+    let names: Vec<String> = lights.iter().map(Light::to_string).collect();
+    assert_eq!(names, ["red", "red and amber", "green", "green", "green, waiting", "amber",
+                       "red"]);
+}
+
+#[test]
+fn test_faults() {
+    use Event::*;
+    for light in Light::ALL {
+        assert_eq!(step(light, Fault), Light::Flashing);
+        assert_eq!(run(light, &[Fault, Timer, Button, Repaired]).last(), Some(&Light::Red));
+    }
+    assert_eq!(step(Light::Green { waiting: true }, Repaired), Light::Green { waiting: true });
+}
+
+#[test]
+fn test_every_transition() {
+    // The table as a table: every light against every event, each answer written down.
+    let table: Vec<String> = Light::ALL.iter()
+        .map(|&light| {
+            let next: Vec<String> = Event::ALL.iter().map(|&e| step(light, e).to_string()).collect();
+            format!("{}: {}", light, next.join(" / "))
+        })
+        .collect();
+    assert_eq!(table, [
+        "red: red and amber / red / flashing amber / red",
+        "red and amber: green / red and amber / flashing amber / red and amber",
+        "green: green / green, waiting / flashing amber / green",
+        "green, waiting: amber / green, waiting / flashing amber / green, waiting",
+        "amber: red / amber / flashing amber / amber",
+        "flashing amber: flashing amber / flashing amber / flashing amber / red",
+    ]);
+}
+
+#[test]
+fn test_safety() {
+    // Whatever happens, traffic is never told to go straight after being told to stop,
+    // nor to stop with no amber first: green only follows red and amber, or green.
+    for light in Light::ALL {
+        for event in Event::ALL {
+            let next = step(light, event);
+            if next.go() {
+                assert!(matches!(light, Light::RedAmber | Light::Green { .. }), "{} to {}", light, next);
+            }
+            if light.go() && next == Light::Red {
+                panic!("{} to red on {:?}", light, event);
+            }
+        }
+    }
+}
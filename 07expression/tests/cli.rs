@@ -0,0 +1,64 @@
+// The match on the last command-line argument, seen from outside.
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+fn expression(args: &[&str]) -> String {
+    let output = Command::cargo_bin("expression").unwrap().args(args).output().unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn matches_error_codes() {
+    let lines = |args: &[&str]| expression(args).lines().nth(1).unwrap().to_string();
+    assert_eq!(lines(&["0"]), "OK");
+    assert_eq!(lines(&["1"]), "Wires Tangled");
+    assert_eq!(lines(&["2"]), "User Asleep");
+    assert_eq!(lines(&["7"]), "Unrecognized Error 7");
+    assert_eq!(lines(&[]), "None input");
+    // only the last argument counts
+    assert_eq!(lines(&["2", "0"]), "OK");
+}
+
+#[test]
+fn reports_unparsable_codes() {
+    Command::cargo_bin("expression").unwrap().arg("x").assert()
+        .success()
+        .stdout(predicate::str::contains("err invalid digit found in string\n0\n"));
+}
+
+#[test]
+fn calculates_from_arguments() {
+    Command::cargo_bin("expression").unwrap().args(["rpn", "3", "4", "+", "2", "*"]).assert()
+        .success()
+        .stdout("14\n");
+    Command::cargo_bin("expression").unwrap().args(["rpn", "1", "0", "/"]).assert()
+        .failure()
+        .stdout("")
+        .stderr("expression: division by zero\n");
+}
+
+#[test]
+fn calculates_each_line_of_stdin() {
+    Command::cargo_bin("expression").unwrap().arg("rpn")
+        .write_stdin("1 2 /\n2 0.5 ^\n1 +\n")
+        .assert()
+        .success()
+        .stdout("0.5\n1.4142135623730951\nerror: + needs 2 numbers, but the stack has 1\n");
+}
+
+#[test]
+fn dispatches_each_line_of_stdin() {
+    Command::cargo_bin("expression").unwrap().arg("dispatch")
+        .write_stdin("echo hi there\nadd 1 2 3\ngcd 12 18\nmul 2 3\ngcd 0 4\nhelp\n")
+        .assert()
+        .success()
+        .stdout("\
+hi there
+6
+6
+error: unknown command \"mul\"; try help
+error: gcd: expected two or more positive numbers
+commands: help add count echo gcd
+");
+}
@@ -0,0 +1,11 @@
+// The matches that src/patterns.rs and src/traffic.rs say won't compile, compiled for
+// real: tests/ui/*.rs must each fail to build with exactly the error in the .stderr file
+// next to it. After a toolchain update changes the wording, review and refresh them with:
+//
+//   $ TRYBUILD=overwrite cargo test --test compile_fail
+//
+#[test]
+fn non_exhaustive_matches() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,26 @@
+// Snapshots of the messages the match on the status code prints, with insta; see
+// 01hello/tests/snapshots.rs for how to review and accept a change.
+use assert_cmd::Command;
+
+// Every run's command line, exit status and what the match printed, one after another.
+// The rest of the output, from the for loop on (its first line is "0"), is the same for
+// every code, and left out.
+fn transcript(runs: &[&[&str]]) -> String {
+    let mut text = String::new();
+    for args in runs {
+        let output = Command::cargo_bin("expression").unwrap().args(*args).output().unwrap();
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let command: Vec<&str> = std::iter::once("expression").chain(args.iter().copied()).collect();
+        text += &format!("$ {}\nstatus: {}\n", command.join(" "), output.status.code().unwrap());
+        for line in stdout.lines().skip(1).take_while(|&line| line != "0") {
+            text += line;
+            text += "\n";
+        }
+    }
+    text
+}
+
+#[test]
+fn status_code_messages() {
+    insta::assert_snapshot!(transcript(&[&["0"], &["1"], &["2"], &["7"], &["x"], &[]]));
+}
@@ -0,0 +1,27 @@
+// Patterns (patterns.rs, note 3): a match that leaves out a case doesn't compile. The
+// arrows are matched two by two, and Right was forgotten; the error says which value
+// gets through.
+#[allow(dead_code)]
+enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+enum Key {
+    Char(char),
+    Arrow(Direction),
+}
+
+fn describe(key: Key) -> &'static str {
+    match key {
+        Key::Char(_) => "typed",
+        Key::Arrow(Direction::Up | Direction::Down) => "move a line",
+        Key::Arrow(Direction::Left) => "move a character",
+    }
+}
+
+fn main() {
+    println!("{}", describe(Key::Char('x')));
+}
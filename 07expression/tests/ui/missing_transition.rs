@@ -0,0 +1,34 @@
+// The traffic light (traffic.rs, note 1) with a state for a crossing that's closed at
+// night. step has no `_` arm, so it doesn't compile until it says what each event does
+// to the new state; the error names the light that's missing.
+#[allow(dead_code)]
+#[derive(Clone, Copy)]
+enum Light {
+    Red,
+    Green,
+    Amber,
+    Off,
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy)]
+enum Event {
+    Timer,
+    Button,
+}
+
+fn step(light: Light, event: Event) -> Light {
+    use Event::*;
+    use Light::*;
+    match (light, event) {
+        (Red, Timer) => Green,
+        (Green, Timer) => Amber,
+        (Amber, Timer) => Red,
+        (Green, Button) => Amber,
+        (Red | Amber, Button) => light,
+    }
+}
+
+fn main() {
+    step(Light::Off, Event::Timer);
+}
@@ -0,0 +1,48 @@
+// What `associated-types --explain` prints: the chapter's notes, in short.
+use rustfun_common::{Explanation, Text, Topic};
+
+pub const EXPLANATION: Explanation = Explanation {
+    chapter: "25associated-types",
+    summary: Text {
+        en: "a Graph trait with associated types, and generic shortest paths",
+        zh: "带关联类型的 Graph trait, 以及泛型最短路径",
+    },
+    topics: &[
+        Topic {
+            title: Text { en: "Associated types", zh: "关联类型" },
+            body: Text {
+                en: "trait Graph { type Node; type Edge; ... } lets each implementation choose its\n\
+                     node and edge types, once. A generic parameter, GraphOf<N, E>, would let the\n\
+                     user choose, and one type could implement it many times.",
+                zh: "trait Graph { type Node; type Edge; ... } 让每个实现各自确定一次节点和边的类型。\n\
+                     泛型参数 GraphOf<N, E> 则由使用者选择, 同一个类型可以实现多次。",
+            },
+        },
+        Topic {
+            title: Text { en: "Bounds on associated types", zh: "关联类型上的约束" },
+            body: Text {
+                en: "type Node: Copy + Eq + Hash + Ord requires every implementation's Node to\n\
+                     have those traits, so generic code can rely on them without saying so.",
+                zh: "type Node: Copy + Eq + Hash + Ord 要求每个实现的 Node 都具备这些 trait,\n\
+                     泛型代码不必重复声明就能依赖它们。",
+            },
+        },
+        Topic {
+            title: Text { en: "Two implementations", zh: "两种实现" },
+            body: Text {
+                en: "An adjacency list with usize nodes, and a grid whose nodes are (row, column)\n\
+                     pairs and whose edges are computed on demand.",
+                zh: "一个以 usize 为节点的邻接表, 以及一个以 (行, 列) 为节点、边按需计算的网格。",
+            },
+        },
+        Topic {
+            title: Text { en: "Generic Dijkstra", zh: "泛型的 Dijkstra" },
+            body: Text {
+                en: "shortest_path is written once, for any G: Graph, and runs on both. Reverse\n\
+                     turns BinaryHeap, a max-heap, into a min-heap.",
+                zh: "shortest_path 只写一次, 适用于任何 G: Graph, 在两种图上都能运行。Reverse\n\
+                     把最大堆 BinaryHeap 变成最小堆。",
+            },
+        },
+    ],
+};
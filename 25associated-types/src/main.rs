@@ -0,0 +1,334 @@
+//
+// Associated types vs. generic parameters
+//
+// A trait can leave some types open in two different ways:
+//
+// * generic parameter:  trait GraphOf<N, E> { ... }
+//   the *user* of the trait picks N and E, so one type may implement GraphOf<u8, ()>
+//   and GraphOf<String, f64> at the same time.
+//
+// * associated type:    trait Graph { type Node; type Edge; ... }
+//   the *implementor* picks Node and Edge, exactly once. Given a graph type, its node
+//   type is fully determined, the same way Iterator::Item is determined by the iterator.
+//
+// Note:
+//
+// 1.) Use an associated type when there is one natural answer per implementing type.
+// 2.) Use a generic parameter when it makes sense to implement the trait many times
+//     for the same type (like From<T>, Add<Rhs>).
+// 3.) Associated types keep the signatures of generic code short: `G: Graph` instead of
+//     `G: GraphOf<N, E>, N: ..., E: ...` repeated in every function that touches a graph.
+//
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+// 1.  The Graph trait declares two associated types.
+// 1.1 `type Node: Copy + Eq + Hash + Ord;` puts bounds on the associated type: every
+//     implementation must choose a Node type that satisfies them, and generic code can
+//     rely on the bounds without restating them.
+// 1.2 Self::Node and Self::Edge refer to whatever the implementation picked.
+trait Graph {
+    type Node: Copy + Eq + Hash + Ord;
+    type Edge;
+
+    /// The edges leaving `node`.
+    fn edges(&self, node: Self::Node) -> Vec<Self::Edge>;
+
+    /// The node at the far end of `edge`.
+    fn target(&self, edge: &Self::Edge) -> Self::Node;
+
+    /// The cost of travelling along `edge`.
+    fn weight(&self, edge: &Self::Edge) -> u64;
+}
+
+// 2.  An adjacency list: nodes are indices, edges are (target, weight) pairs.
+struct AdjacencyList {
+    adjacent: Vec<Vec<(usize, u64)>>,
+}
+
+impl AdjacencyList {
+    fn new(nodes: usize) -> AdjacencyList {
+        AdjacencyList { adjacent: vec![Vec::new(); nodes] }
+    }
+
+    fn add_edge(&mut self, from: usize, to: usize, weight: u64) {
+        self.adjacent[from].push((to, weight));
+    }
+}
+
+// 3.  The implementation fixes the associated types with `type Node = usize;`.
+impl Graph for AdjacencyList {
+    type Node = usize;
+    type Edge = (usize, u64);
+
+    fn edges(&self, node: usize) -> Vec<(usize, u64)> {
+        self.adjacent[node].clone()
+    }
+
+    fn target(&self, edge: &(usize, u64)) -> usize {
+        edge.0
+    }
+
+    fn weight(&self, edge: &(usize, u64)) -> u64 {
+        edge.1
+    }
+}
+
+// 4.  A completely different representation: a grid of walkable cells, where a node is a
+//     (row, column) pair and an edge is a Step struct. Nothing about the grid is stored as
+//     explicit edges, they are computed on demand.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Step {
+    to: (usize, usize),
+    cost: u64,
+}
+
+struct Grid {
+    // b'#' is a wall, a digit is the cost of entering that cell.
+    cells: Vec<Vec<u8>>,
+}
+
+impl Grid {
+    fn parse(text: &str) -> Grid {
+        let lines = text.lines().map(|line| line.trim()).filter(|line| !line.is_empty());
+        Grid { cells: lines.map(|line| line.bytes().collect()).collect() }
+    }
+}
+
+impl Graph for Grid {
+    type Node = (usize, usize);
+    type Edge = Step;
+
+    fn edges(&self, (row, col): (usize, usize)) -> Vec<Step> {
+        let mut steps = Vec::new();
+        let candidates = [
+            (row.wrapping_sub(1), col),
+            (row + 1, col),
+            (row, col.wrapping_sub(1)),
+            (row, col + 1),
+        ];
+        for &(r, c) in &candidates {
+            // wrapping_sub turns "one before zero" into usize::MAX, which `get` rejects.
+            if let Some(&cell) = self.cells.get(r).and_then(|line| line.get(c)) {
+                if cell.is_ascii_digit() {
+                    steps.push(Step { to: (r, c), cost: u64::from(cell - b'0') });
+                }
+            }
+        }
+        steps
+    }
+
+    fn target(&self, edge: &Step) -> (usize, usize) {
+        edge.to
+    }
+
+    fn weight(&self, edge: &Step) -> u64 {
+        edge.cost
+    }
+}
+
+// 5.  Dijkstra's shortest path, written once for every Graph.
+// 5.1 The signature only needs `G: Graph`; G::Node is known to be Copy + Eq + Hash + Ord
+//     because the trait says so.
+// 5.2 Reverse turns std's max-heap BinaryHeap into a min-heap on the distance.
+/// Find the cheapest path from `start` to `goal` in `graph`.
+///
+/// Return `Some((cost, path))`, where `path` begins with `start` and ends with `goal`,
+/// or `None` if `goal` can't be reached.
+fn shortest_path<G: Graph>(graph: &G, start: G::Node, goal: G::Node)
+    -> Option<(u64, Vec<G::Node>)>
+{
+    let mut best: HashMap<G::Node, u64> = HashMap::new();
+    let mut previous: HashMap<G::Node, G::Node> = HashMap::new();
+    let mut queue = BinaryHeap::new();
+
+    best.insert(start, 0);
+    queue.push(Reverse((0, start)));
+
+    while let Some(Reverse((cost, node))) = queue.pop() {
+        if node == goal {
+            let mut path = vec![goal];
+            let mut current = goal;
+            while let Some(&prev) = previous.get(&current) {
+                path.push(prev);
+                current = prev;
+            }
+            path.reverse();
+            return Some((cost, path));
+        }
+        // a stale queue entry: we already found a cheaper way here
+        if cost > best[&node] {
+            continue;
+        }
+        for edge in graph.edges(node) {
+            let next = graph.target(&edge);
+            let next_cost = cost + graph.weight(&edge);
+            if best.get(&next).is_none_or(|&known| next_cost < known) {
+                best.insert(next, next_cost);
+                previous.insert(next, node);
+                queue.push(Reverse((next_cost, next)));
+            }
+        }
+    }
+    None
+}
+
+#[test]
+fn test_shortest_path_adjacency_list() {
+    //   0 --1--> 1 --1--> 2
+    //   |                 ^
+    //   +--------5--------+
+    let mut g = AdjacencyList::new(4);
+    g.add_edge(0, 1, 1);
+    g.add_edge(1, 2, 1);
+    g.add_edge(0, 2, 5);
+    assert_eq!(shortest_path(&g, 0, 2), Some((2, vec![0, 1, 2])));
+    assert_eq!(shortest_path(&g, 0, 0), Some((0, vec![0])));
+    // node 3 has no incoming edges; edges are one-way
+    assert_eq!(shortest_path(&g, 0, 3), None);
+    assert_eq!(shortest_path(&g, 2, 0), None);
+}
+
+#[test]
+fn test_shortest_path_grid() {
+    let grid = Grid::parse("
+        1111
+        1##1
+        1#91
+        1111");
+    let (cost, path) = shortest_path(&grid, (0, 0), (2, 2)).unwrap();
+    // both ways around the wall take 5 steps, then 9 to enter the target cell
+    assert_eq!(cost, 5 + 9);
+    assert_eq!(path.first(), Some(&(0, 0)));
+    assert_eq!(path.last(), Some(&(2, 2)));
+    assert_eq!(path.len(), 7);
+
+    // walls are never entered
+    assert!(path.iter().all(|&(r, c)| grid.cells[r][c] != b'#'));
+    assert_eq!(shortest_path(&grid, (0, 0), (1, 1)), None);
+}
+
+// 6.  Associated types can be named from outside the trait with the `G::Node` syntax, or
+//     fully qualified as `<G as Graph>::Node` when there could be ambiguity.
+fn reachable<G: Graph>(graph: &G, start: <G as Graph>::Node) -> Vec<G::Node> {
+    let mut seen = vec![start];
+    let mut stack = vec![start];
+    while let Some(node) = stack.pop() {
+        for edge in graph.edges(node) {
+            let next = graph.target(&edge);
+            if !seen.contains(&next) {
+                seen.push(next);
+                stack.push(next);
+            }
+        }
+    }
+    seen.sort();
+    seen
+}
+
+#[test]
+fn test_reachable() {
+    let mut g = AdjacencyList::new(5);
+    g.add_edge(0, 1, 1);
+    g.add_edge(1, 3, 1);
+    g.add_edge(4, 0, 1);
+    assert_eq!(reachable(&g, 0), vec![0, 1, 3]);
+    assert_eq!(reachable(&g, 4), vec![0, 1, 3, 4]);
+}
+
+// 7.  The same idea as a generic trait, for comparison.
+// 7.1 Every function that uses it must carry N and E as extra type parameters, plus their
+//     bounds, even when it doesn't care about edges at all.
+// 7.2 Nothing stops a type from implementing GraphOf<usize, u8> *and* GraphOf<char, ()>,
+//     so calls may need turbofish annotations to pick one.
+trait GraphOf<N, E> {
+    fn edges_of(&self, node: N) -> Vec<E>;
+}
+
+impl GraphOf<usize, (usize, u64)> for AdjacencyList {
+    fn edges_of(&self, node: usize) -> Vec<(usize, u64)> {
+        self.adjacent[node].clone()
+    }
+}
+
+// An AdjacencyList is also a graph of "just the targets", with no weights.
+impl GraphOf<usize, usize> for AdjacencyList {
+    fn edges_of(&self, node: usize) -> Vec<usize> {
+        self.adjacent[node].iter().map(|&(to, _)| to).collect()
+    }
+}
+
+fn out_degree<N, E, G: GraphOf<N, E>>(graph: &G, node: N) -> usize {
+    graph.edges_of(node).len()
+}
+
+#[test]
+fn test_generic_parameter_version() {
+    let mut g = AdjacencyList::new(3);
+    g.add_edge(0, 1, 7);
+    g.add_edge(0, 2, 9);
+    // two impls exist, so the edge type has to be spelled out
+    assert_eq!(out_degree::<usize, usize, _>(&g, 0), 2);
+    assert_eq!(out_degree::<usize, (usize, u64), _>(&g, 0), 2);
+    assert_eq!(GraphOf::<usize, usize>::edges_of(&g, 0), vec![1, 2]);
+}
+
+// 8.  The standard library's most famous associated type: Iterator::Item.
+//     An iterator yields exactly one kind of item, so Item is associated, not generic.
+struct Countdown(u32);
+
+impl Iterator for Countdown {
+    type Item = u32;
+    fn next(&mut self) -> Option<u32> {
+        if self.0 == 0 {
+            None
+        } else {
+            self.0 -= 1;
+            Some(self.0 + 1)
+        }
+    }
+}
+
+// 8.1 Bounds can constrain an associated type in place: `I: Iterator<Item = u32>`.
+fn total<I: Iterator<Item = u32>>(iter: I) -> u32 {
+    iter.sum()
+}
+
+#[test]
+fn test_iterator_item() {
+    assert_eq!(Countdown(3).collect::<Vec<_>>(), vec![3, 2, 1]);
+    assert_eq!(total(Countdown(4)), 10);
+}
+
+mod explain;
+
+fn main() {
+    rustfun_common::init_logging();
+    explain::EXPLANATION.on_request();
+    println!("Hello, associated types!");
+
+    let mut g = AdjacencyList::new(4);
+    g.add_edge(0, 1, 4);
+    g.add_edge(0, 2, 1);
+    g.add_edge(2, 1, 2);
+    g.add_edge(1, 3, 1);
+    match shortest_path(&g, 0, 3) {
+        Some((cost, path)) => println!("adjacency list: 0 -> 3 costs {} via {:?}", cost, path),
+        None => println!("adjacency list: 3 is unreachable"),
+    }
+
+    let grid = Grid::parse("
+        11111
+        9###1
+        11911");
+    match shortest_path(&grid, (0, 0), (2, 0)) {
+        Some((cost, path)) => println!("grid: (0,0) -> (2,0) costs {} via {:?}", cost, path),
+        None => println!("grid: (2,0) is unreachable"),
+    }
+    println!("reachable from 2: {:?}", reachable(&g, 2));
+
+    // the generic-parameter flavour needs the edge type spelled out
+    println!("out degree of 0: {}", out_degree::<usize, usize, _>(&g, 0));
+    println!("countdown total: {}", total(Countdown(10)));
+}
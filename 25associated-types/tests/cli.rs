@@ -0,0 +1,16 @@
+// Run the chapter's demo binary and check the parts of its output that are deterministic.
+use assert_cmd::Command;
+
+#[test]
+fn prints_the_demo() {
+    Command::cargo_bin("associated-types").unwrap().assert()
+        .success()
+        .stdout("\
+Hello, associated types!
+adjacency list: 0 -> 3 costs 4 via [0, 2, 1, 3]
+grid: (0,0) -> (2,0) costs 10 via [(0, 0), (1, 0), (2, 0)]
+reachable from 2: [1, 2, 3]
+out degree of 0: 2
+countdown total: 55
+");
+}
@@ -0,0 +1,48 @@
+// What `processes-and-signals --explain` prints: the chapter's notes, in short.
+use rustfun_common::{Explanation, Text, Topic};
+
+pub const EXPLANATION: Explanation = Explanation {
+    chapter: "26processes-and-signals",
+    summary: Text {
+        en: "spawning child processes, pipes, exit codes and signals",
+        zh: "启动子进程、管道、退出码和信号",
+    },
+    topics: &[
+        Topic {
+            title: Text { en: "Command", zh: "Command" },
+            body: Text {
+                en: "std::process::Command is a builder: program, arguments, environment and stdio,\n\
+                     then status(), output() or spawn(). No shell is involved unless you ask.",
+                zh: "std::process::Command 是一个构建器: 程序、参数、环境和标准输入输出, 然后调用\n\
+                     status()、output() 或 spawn()。除非你要求, 否则不经过 shell。",
+            },
+        },
+        Topic {
+            title: Text { en: "Pipes", zh: "管道" },
+            body: Text {
+                en: "Stdio::piped() connects the child's stdin or stdout to us. Dropping the stdin\n\
+                     handle closes the pipe, which is how the child sees end of input.",
+                zh: "Stdio::piped() 把子进程的标准输入或输出连到我们这边。丢弃 stdin 句柄会关闭\n\
+                     管道, 子进程由此看到输入结束。",
+            },
+        },
+        Topic {
+            title: Text { en: "Exit statuses", zh: "退出状态" },
+            body: Text {
+                en: "ExitStatus::code() is Some(n) after exit(n), and None when the process was\n\
+                     killed by a signal. Always wait() for a child, or it lingers as a zombie.",
+                zh: "调用 exit(n) 后 ExitStatus::code() 是 Some(n), 被信号杀死时为 None。一定要\n\
+                     wait() 子进程, 否则它会变成僵尸进程。",
+            },
+        },
+        Topic {
+            title: Text { en: "Signals", zh: "信号" },
+            body: Text {
+                en: "Signals have no portable std API; the Unix-only parts use libc directly,\n\
+                     behind #[cfg(unix)] and the `signals` feature, which is on by default.",
+                zh: "信号没有可移植的标准库 API; 只适用于 Unix 的部分直接调用 libc, 并放在\n\
+                     #[cfg(unix)] 和默认开启的 `signals` 特性后面。",
+            },
+        },
+    ],
+};
@@ -0,0 +1,234 @@
+//
+// Processes and signals
+//
+// std::process::Command is a builder for starting other programs. You describe the program,
+// its arguments, its environment and what to do with its stdin/stdout/stderr, then either:
+//
+// * .status()  run it to completion, inheriting our stdio, and get the ExitStatus
+// * .output()  run it to completion, capturing stdout/stderr into Vec<u8>s
+// * .spawn()   start it and get a Child handle back, to talk to it while it runs
+//
+// Note:
+//
+// 1.) Nothing goes through a shell unless you ask for one: Command::new("sort") runs the
+//     `sort` executable directly, so arguments are never re-split or glob-expanded.
+// 2.) Every step can fail (program not found, pipe closed, ...), so everything returns
+//     io::Result.
+// 3.) Signals are a Unix concept with no portable std API. The unix-only parts below are
+//     behind #[cfg(unix)] and use the libc crate directly, to show what's under the hood.
+//     They're also behind the `signals` feature, on by default, so a build for a target
+//     whose libc lacks them can leave them out: `cargo build --no-default-features`.
+//
+use std::io;
+use std::io::{Read, Write};
+use std::process::{Command, ExitStatus, Stdio};
+
+// 1.  Piping data through a child process.
+// 1.1 Stdio::piped() connects the child's stdin/stdout to pipes we hold the other end of.
+// 1.2 child.stdin is an Option<ChildStdin>; take() moves it out of the Child, so that it is
+//     dropped (and the pipe closed) at the end of the block. `sort` can't print anything
+//     before it has seen end-of-file on its input, so forgetting this would deadlock.
+// 1.3 LC_ALL=C makes `sort` compare plain bytes instead of following the user's locale, so
+//     the result doesn't depend on who runs it.
+/// Sort `lines` by running them through the system `sort` command.
+fn sort_lines(lines: &[&str]) -> io::Result<Vec<String>> {
+    let mut child = Command::new("sort")
+        .env("LC_ALL", "C")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    {
+        let stdin = child.stdin.take().expect("stdin was piped");
+        let mut stdin = io::BufWriter::new(stdin);
+        for line in lines {
+            writeln!(stdin, "{}", line)?;
+        }
+    } // stdin dropped here: the child sees EOF
+
+    let mut sorted = String::new();
+    child.stdout.take().expect("stdout was piped").read_to_string(&mut sorted)?;
+
+    // 1.4 Always wait() for a spawned child, otherwise it lingers as a zombie process
+    //     until we exit.
+    let status = child.wait()?;
+    if !status.success() {
+        return Err(io::Error::other(format!("sort failed: {}", status)));
+    }
+    Ok(sorted.lines().map(|line| line.to_string()).collect())
+}
+
+#[test]
+fn test_sort_lines() {
+    assert_eq!(sort_lines(&["pear", "apple", "fig"]).unwrap(),
+               vec!["apple", "fig", "pear"]);
+    // with LC_ALL=C, uppercase sorts before lowercase
+    assert_eq!(sort_lines(&["b", "a", "B", "A"]).unwrap(),
+               vec!["A", "B", "a", "b"]);
+    assert_eq!(sort_lines(&[]).unwrap(), Vec::<String>::new());
+}
+
+// 2.  Exit statuses.
+// 2.1 ExitStatus::code() is Some(n) when the process called exit(n), and None when it
+//     was terminated some other way (on Unix: killed by a signal).
+// 2.2 success() is just `code() == Some(0)`.
+/// Run `script` with `sh -c` and report its exit code.
+fn exit_code(script: &str) -> io::Result<Option<i32>> {
+    let status: ExitStatus = Command::new("sh")
+        .arg("-c")
+        .arg(script)
+        .stdout(Stdio::null())
+        .status()?;
+    Ok(status.code())
+}
+
+#[test]
+fn test_exit_code() {
+    assert_eq!(exit_code("true").unwrap(), Some(0));
+    assert_eq!(exit_code("exit 3").unwrap(), Some(3));
+    assert_eq!(exit_code("false").unwrap(), Some(1));
+}
+
+#[test]
+fn test_missing_program() {
+    // spawning a program that doesn't exist is an io::Error, not an exit status
+    let err = Command::new("no-such-program-rustfun").status().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::NotFound);
+}
+
+// 3.  The child's environment.
+// 3.1 By default a child inherits our whole environment. env() adds or overrides one
+//     variable, env_remove() deletes one, and env_clear() starts from nothing.
+// 3.2 None of these touch our own environment, only the child's.
+/// Run `printenv name` in a child whose environment is changed by `setup`, and return
+/// what it printed, or `None` if the variable wasn't set in the child.
+fn child_sees<F>(name: &str, setup: F) -> io::Result<Option<String>>
+    where F: FnOnce(&mut Command)
+{
+    let mut command = Command::new("printenv");
+    command.arg(name);
+    setup(&mut command);
+    let output = command.output()?;
+    // printenv exits with status 1 when the variable is unset
+    if output.status.success() {
+        Ok(Some(String::from_utf8_lossy(&output.stdout).trim_end().to_string()))
+    } else {
+        Ok(None)
+    }
+}
+
+#[test]
+fn test_child_environment() {
+    let set = child_sees("RUSTFUN_GREETING", |c| { c.env("RUSTFUN_GREETING", "hello"); });
+    assert_eq!(set.unwrap(), Some("hello".to_string()));
+
+    let unset = child_sees("RUSTFUN_GREETING", |_| {});
+    assert_eq!(unset.unwrap(), None);
+
+    // PATH is inherited unless we clear the environment; printenv itself is found through
+    // *our* PATH, because the program lookup happens before the child starts
+    assert!(child_sees("PATH", |_| {}).unwrap().is_some());
+    assert_eq!(child_sees("PATH", |c| { c.env_clear(); }).unwrap(), None);
+    assert_eq!(child_sees("PATH", |c| { c.env_remove("PATH"); }).unwrap(), None);
+}
+
+// 4.  Signals (Unix only).
+// 4.1 A signal handler runs in the middle of whatever the thread was doing, so it may
+//     only do "async-signal-safe" things. Storing to an atomic is the classic one: the
+//     handler sets a flag and the normal code looks at it later.
+// 4.2 libc::signal takes the handler as a plain address (sighandler_t), hence the cast
+//     from the extern "C" fn.
+#[cfg(all(unix, feature = "signals"))]
+mod signals {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static USR1_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+    extern "C" fn on_usr1(_signal: libc::c_int) {
+        USR1_COUNT.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Install a SIGUSR1 handler that counts deliveries.
+    pub fn install_usr1_handler() {
+        let handler = on_usr1 as extern "C" fn(libc::c_int);
+        unsafe {
+            libc::signal(libc::SIGUSR1, handler as libc::sighandler_t);
+        }
+    }
+
+    /// Send SIGUSR1 to our own process.
+    pub fn raise_usr1() {
+        unsafe {
+            libc::raise(libc::SIGUSR1);
+        }
+    }
+
+    /// How many SIGUSR1s the handler has seen so far.
+    pub fn usr1_count() -> usize {
+        USR1_COUNT.load(Ordering::SeqCst)
+    }
+
+    // 4.3 Killing a child: Child::kill() sends SIGKILL. The status then has no exit code,
+    //     and the Unix-specific ExitStatusExt trait recovers the signal number instead.
+    /// Start a long `sleep`, kill it, and return the signal that terminated it.
+    pub fn kill_child() -> ::std::io::Result<(Option<i32>, Option<i32>)> {
+        use std::os::unix::process::ExitStatusExt;
+        use std::process::Command;
+
+        let mut child = Command::new("sleep").arg("30").spawn()?;
+        child.kill()?;
+        let status = child.wait()?;
+        Ok((status.code(), status.signal()))
+    }
+
+    #[test]
+    fn test_usr1_handler() {
+        install_usr1_handler();
+        let before = usr1_count();
+        raise_usr1();
+        raise_usr1();
+        // raise() delivers to the calling thread before returning
+        assert_eq!(usr1_count(), before + 2);
+    }
+
+    #[test]
+    fn test_kill_child() {
+        assert_eq!(kill_child().unwrap(), (None, Some(libc::SIGKILL)));
+    }
+}
+
+mod explain;
+
+fn main() {
+    rustfun_common::init_logging();
+    explain::EXPLANATION.on_request();
+    println!("Hello, processes!");
+
+    match sort_lines(&["liberté", "égalité", "fraternité"]) {
+        Ok(sorted) => println!("sorted by `sort`: {:?}", sorted),
+        Err(e) => println!("couldn't run sort: {}", e),
+    }
+
+    for script in &["true", "exit 42"] {
+        match exit_code(script) {
+            Ok(code) => println!("sh -c {:?} exited with {:?}", script, code),
+            Err(e) => println!("couldn't run sh: {}", e),
+        }
+    }
+
+    match child_sees("RUSTFUN_GREETING", |c| { c.env("RUSTFUN_GREETING", "bonjour"); }) {
+        Ok(value) => println!("child saw RUSTFUN_GREETING={:?}", value),
+        Err(e) => println!("couldn't run printenv: {}", e),
+    }
+
+    #[cfg(all(unix, feature = "signals"))]
+    {
+        signals::install_usr1_handler();
+        signals::raise_usr1();
+        println!("SIGUSR1 received {} time(s)", signals::usr1_count());
+        match signals::kill_child() {
+            Ok((code, signal)) => println!("killed child: code {:?}, signal {:?}", code, signal),
+            Err(e) => println!("couldn't run sleep: {}", e),
+        }
+    }
+}
@@ -0,0 +1,23 @@
+// Run the chapter's demo binary and check the parts of its output that are deterministic.
+use assert_cmd::Command;
+
+#[test]
+fn prints_the_demo() {
+    let mut expected = "\
+Hello, processes!
+sorted by `sort`: [\"fraternité\", \"liberté\", \"égalité\"]
+sh -c \"true\" exited with Some(0)
+sh -c \"exit 42\" exited with Some(42)
+child saw RUSTFUN_GREETING=Some(\"bonjour\")
+".to_string();
+    // without the `signals` feature the demo stops there
+    if cfg!(feature = "signals") {
+        expected.push_str("\
+SIGUSR1 received 1 time(s)
+killed child: code None, signal Some(9)
+");
+    }
+    Command::cargo_bin("processes-and-signals").unwrap().assert()
+        .success()
+        .stdout(expected);
+}
@@ -0,0 +1,46 @@
+// What `tcp-chat --explain` prints: the chapter's notes, in short.
+use rustfun_common::{Explanation, Text, Topic};
+
+pub const EXPLANATION: Explanation = Explanation {
+    chapter: "27tcp-chat",
+    summary: Text {
+        en: "a threaded TCP chat server and client",
+        zh: "多线程的 TCP 聊天服务器和客户端",
+    },
+    topics: &[
+        Topic {
+            title: Text { en: "A thread per connection", zh: "每个连接一个线程" },
+            body: Text {
+                en: "Each client gets a reader thread that blocks on its own socket, and never\n\
+                     needs to know about any other client.",
+                zh: "每个客户端有一个读线程, 阻塞在自己的套接字上, 不需要知道其他客户端。",
+            },
+        },
+        Topic {
+            title: Text { en: "One owner for shared state", zh: "共享状态只有一个所有者" },
+            body: Text {
+                en: "Only the broadcaster thread holds the list of clients. Reader threads send it\n\
+                     events over an mpsc channel, so the list needs no Mutex.",
+                zh: "只有广播线程持有客户端列表。读线程通过 mpsc 通道向它发送事件, 所以列表\n\
+                     不需要 Mutex。",
+            },
+        },
+        Topic {
+            title: Text { en: "Cloning a socket", zh: "复制套接字" },
+            body: Text {
+                en: "TcpStream::try_clone() gives a second handle to the same connection: one\n\
+                     thread reads from it while another writes.",
+                zh: "TcpStream::try_clone() 给出同一连接的第二个句柄: 一个线程读, 另一个线程写。",
+            },
+        },
+        Topic {
+            title: Text { en: "A line protocol", zh: "按行的协议" },
+            body: Text {
+                en: "The first line a client sends is its name, every line after it a message.\n\
+                     The server's welcome line tells the client it has joined.",
+                zh: "客户端发送的第一行是名字, 之后每一行都是消息。服务器的欢迎行告诉客户端\n\
+                     它已经加入。",
+            },
+        },
+    ],
+};
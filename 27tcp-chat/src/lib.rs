@@ -0,0 +1,186 @@
+//
+// A multi-client TCP chat server, with nothing but std::net and threads.
+//
+//   client ---TcpStream---> [reader thread] --+
+//   client ---TcpStream---> [reader thread] --+--Event--> [broadcaster thread] --> every client
+//   client ---TcpStream---> [reader thread] --+  (mpsc)
+//
+// Note:
+//
+// 1.) One thread per connection: each thread blocks on its own socket and never has to
+//     know about any other client.
+// 2.) Exactly one thread, the broadcaster, owns the list of connected clients. The reader
+//     threads talk to it over an mpsc channel, so the list needs no Mutex at all.
+// 3.) The protocol is line based: the first line a client sends is its name, every line
+//     after that is a message. The server answers the name with a "* welcome" line once
+//     the client is registered, so from then on it sees everything said.
+//
+use std::collections::HashMap;
+use std::io;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread;
+
+// 1.  Everything the reader threads can tell the broadcaster.
+// 1.1 Join carries a clone of the client's TcpStream: try_clone() gives a second handle to
+//     the same socket, so the reader thread keeps reading while the broadcaster writes.
+enum Event {
+    Join { id: usize, name: String, stream: TcpStream },
+    Message { id: usize, text: String },
+    Leave { id: usize },
+}
+
+/// A chat server bound to a local address.
+pub struct Server {
+    listener: TcpListener,
+}
+
+impl Server {
+    /// Bind to `addr`. Use port 0 to let the OS pick a free port, then ask `local_addr`.
+    pub fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<Server> {
+        Ok(Server { listener: TcpListener::bind(addr)? })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Accept clients forever. Only returns if accepting fails.
+    pub fn run(self) -> io::Result<()> {
+        let (sender, receiver) = channel();
+        thread::spawn(move || broadcast(receiver));
+
+        // 2.  incoming() is an endless iterator of connection attempts.
+        for (id, stream) in self.listener.incoming().enumerate() {
+            let stream = stream?;
+            let sender = sender.clone();
+            // 2.1 A misbehaving client only takes down its own thread.
+            thread::spawn(move || {
+                let _ = handle_client(id, stream, sender);
+            });
+        }
+        Ok(())
+    }
+
+    /// Run the server on a background thread.
+    pub fn spawn(self) -> thread::JoinHandle<io::Result<()>> {
+        thread::spawn(move || self.run())
+    }
+}
+
+// 3.  A reader thread: announce the client, forward every line, then say goodbye.
+// 3.1 A client that hangs up (or errors) before sending its name never joined, so there
+//     is nothing to announce. After the Join, any read error just ends the loop and the
+//     Leave still goes out.
+fn handle_client(id: usize, stream: TcpStream, events: Sender<Event>) -> io::Result<()> {
+    let mut lines = BufReader::new(stream.try_clone()?).lines();
+    let name = match lines.next() {
+        Some(name) => name?.trim().to_string(),
+        None => return Ok(()), // hung up before saying who they are
+    };
+    if events.send(Event::Join { id, name, stream }).is_err() {
+        return Ok(()); // broadcaster is gone, the server is shutting down
+    }
+
+    for line in lines {
+        let text = match line {
+            Ok(text) => text,
+            Err(_) => break,
+        };
+        if events.send(Event::Message { id, text }).is_err() {
+            break;
+        }
+    }
+    let _ = events.send(Event::Leave { id });
+    Ok(())
+}
+
+// 4.  The broadcaster owns the map from client id to (name, stream).
+// 4.1 `for event in receiver` ends when every Sender has been dropped.
+// 4.2 A write that fails means that client is gone; we drop it from the map right away
+//     rather than waiting for its reader thread to notice.
+fn broadcast(events: Receiver<Event>) {
+    let mut clients: HashMap<usize, (String, TcpStream)> = HashMap::new();
+
+    for event in events {
+        let (from, line) = match event {
+            Event::Join { id, name, mut stream } => {
+                let line = format!("* {} joined", name);
+                if writeln!(stream, "* welcome, {}", name).is_err() {
+                    continue; // gone already; nobody needs to hear about it
+                }
+                log::info!("client {} joined as {:?}", id, name);
+                clients.insert(id, (name, stream));
+                (id, line)
+            }
+            Event::Message { id, text } => match clients.get(&id) {
+                Some((name, _)) => (id, format!("{}: {}", name, text)),
+                None => continue,
+            },
+            Event::Leave { id } => match clients.remove(&id) {
+                Some((name, _)) => {
+                    log::info!("client {} ({:?}) left", id, name);
+                    (id, format!("* {} left", name))
+                }
+                None => continue,
+            },
+        };
+
+        let mut dead = Vec::new();
+        for (&id, &mut (_, ref mut stream)) in &mut clients {
+            if id != from && writeln!(stream, "{}", line).is_err() {
+                dead.push(id);
+            }
+        }
+        for id in dead {
+            log::debug!("client {} dropped after a failed write", id);
+            clients.remove(&id);
+        }
+    }
+}
+
+/// A connected chat client.
+pub struct Client {
+    stream: TcpStream,
+    lines: io::Lines<BufReader<TcpStream>>,
+}
+
+impl Client {
+    /// Connect to the server at `addr` and introduce ourselves as `name`.
+    // 5.  Wait for the welcome: until it arrives the broadcaster may not know about us
+    //     yet, and a client that joins right after us would be announced to everyone else.
+    pub fn connect<A: ToSocketAddrs>(addr: A, name: &str) -> io::Result<Client> {
+        let mut stream = TcpStream::connect(addr)?;
+        writeln!(stream, "{}", name)?;
+        let mut lines = BufReader::new(stream.try_clone()?).lines();
+        match lines.next() {
+            Some(Ok(ref line)) if line.starts_with("* welcome") => {}
+            Some(Err(e)) => return Err(e),
+            _ => return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                           "server did not welcome us")),
+        }
+        Ok(Client { stream, lines })
+    }
+
+    /// Send one chat message.
+    pub fn send(&mut self, text: &str) -> io::Result<()> {
+        writeln!(self.stream, "{}", text)
+    }
+
+    /// Wait for the next line from the server; `None` once the server hangs up.
+    pub fn recv(&mut self) -> Option<io::Result<String>> {
+        self.lines.next()
+    }
+
+    /// The underlying socket, e.g. to set a read timeout.
+    pub fn stream(&self) -> &TcpStream {
+        &self.stream
+    }
+
+    /// Split into a sending half and a receiving iterator, so that two threads can use
+    /// the connection at the same time.
+    pub fn split(self) -> (TcpStream, io::Lines<BufReader<TcpStream>>) {
+        (self.stream, self.lines)
+    }
+}
@@ -0,0 +1,80 @@
+use std::io;
+use std::io::{BufRead, Write};
+use std::thread;
+use rustfun_common::{tr, ArgError, Args, Text};
+use tcp_chat::{Client, Server};
+
+const USAGE: Text = Text {
+    en: "tcp-chat server [ADDR]
+       tcp-chat client ADDR NAME
+Example: tcp-chat server 127.0.0.1:7878",
+    zh: "tcp-chat server [ADDR]
+      tcp-chat client ADDR NAME
+示例: tcp-chat server 127.0.0.1:7878",
+};
+
+fn run_server(addr: &str) -> io::Result<()> {
+    let server = Server::bind(addr)?;
+    println!("Chatting on {}...", server.local_addr()?);
+    server.run()
+}
+
+// 2.  The client needs to wait on two things at once: the keyboard and the socket.
+//     With blocking I/O that means two threads, one for each.
+fn run_client(addr: &str, name: &str) -> io::Result<()> {
+    let (mut to_server, from_server) = Client::connect(addr, name)?.split();
+
+    thread::spawn(move || {
+        for line in from_server {
+            match line {
+                Ok(line) => println!("{}", line),
+                Err(_) => break,
+            }
+        }
+        println!("* disconnected");
+        std::process::exit(0);
+    });
+
+    let stdin = io::stdin();
+    for line in stdin.lock().lines() {
+        writeln!(to_server, "{}", line?)?;
+    }
+    Ok(())
+}
+
+// 1.  The binary is a thin shell over the library: `server` runs the broadcaster, `client`
+//     wires stdin/stdout to a connection.
+enum Mode {
+    Server { addr: String },
+    Client { addr: String, name: String },
+}
+
+fn parse_args(mut args: Args) -> Result<Mode, ArgError> {
+    let mode = match args.required("server or client")?.as_str() {
+        "server" => Mode::Server {
+            addr: args.optional().unwrap_or_else(|| "127.0.0.1:7878".to_string()),
+        },
+        "client" => Mode::Client { addr: args.required("ADDR")?, name: args.required("NAME")? },
+        other => return Err(args.error(tr!("unknown mode {:?}", "未知的模式 {:?}", other))),
+    };
+    args.finish()?;
+    Ok(mode)
+}
+
+mod explain;
+
+fn main() {
+    rustfun_common::init_logging();
+    explain::EXPLANATION.on_request();
+    let mode = parse_args(Args::from_env(USAGE)).unwrap_or_else(|e| e.exit());
+
+    let result = match mode {
+        Mode::Server { addr } => run_server(&addr),
+        Mode::Client { addr, name } => run_client(&addr, &name),
+    };
+
+    if let Err(e) = result {
+        eprintln!("tcp-chat: {}", e);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,55 @@
+use std::time::Duration;
+use tcp_chat::{Client, Server};
+
+// Connect with a read timeout, so a lost message fails the test instead of hanging it.
+fn connect(addr: std::net::SocketAddr, name: &str) -> Client {
+    let client = Client::connect(addr, name).expect("connect");
+    client.stream().set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+    client
+}
+
+fn next_line(client: &mut Client) -> String {
+    client.recv().expect("server hung up").expect("read failed")
+}
+
+#[test]
+fn two_clients_exchange_messages() {
+    let server = Server::bind("127.0.0.1:0").unwrap();
+    let addr = server.local_addr().unwrap();
+    server.spawn();
+
+    let mut alice = connect(addr, "alice");
+    let mut bob = connect(addr, "bob");
+
+    // alice sees bob arrive; once she has, bob is registered with the broadcaster
+    assert_eq!(next_line(&mut alice), "* bob joined");
+
+    alice.send("hello bob").unwrap();
+    assert_eq!(next_line(&mut bob), "alice: hello bob");
+
+    bob.send("hi alice").unwrap();
+    assert_eq!(next_line(&mut alice), "bob: hi alice");
+
+    // messages are not echoed back to their sender: bob's next line is carol's arrival
+    let mut carol = connect(addr, "carol");
+    assert_eq!(next_line(&mut bob), "* carol joined");
+    assert_eq!(next_line(&mut alice), "* carol joined");
+
+    carol.send("bye").unwrap();
+    assert_eq!(next_line(&mut alice), "carol: bye");
+    assert_eq!(next_line(&mut bob), "carol: bye");
+}
+
+#[test]
+fn leaving_is_announced() {
+    let server = Server::bind("127.0.0.1:0").unwrap();
+    let addr = server.local_addr().unwrap();
+    server.spawn();
+
+    let mut alice = connect(addr, "alice");
+    let bob = connect(addr, "bob");
+    assert_eq!(next_line(&mut alice), "* bob joined");
+
+    drop(bob);
+    assert_eq!(next_line(&mut alice), "* bob left");
+}
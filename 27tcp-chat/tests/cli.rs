@@ -0,0 +1,81 @@
+// The tcp-chat binary end to end: a real server process, a real client process fed from
+// stdin, and a library Client on the other side to watch what arrives.
+use assert_cmd::cargo::CommandCargoExt;
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::io::{BufRead, BufReader};
+use std::process::{self, Child, Stdio};
+use std::time::Duration;
+use tcp_chat::Client;
+
+fn tcp_chat() -> Command {
+    Command::cargo_bin("tcp-chat").unwrap()
+}
+
+// Kill the server even when an assertion fails halfway.
+struct KillOnDrop(Child);
+
+impl Drop for KillOnDrop {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+/// Start `tcp-chat server` on a free port and return it with the address it printed.
+fn start_server() -> (KillOnDrop, String) {
+    // a long-running child, so a std Command rather than assert_cmd's run-to-completion one
+    let mut child = process::Command::cargo_bin("tcp-chat").unwrap()
+        .args(["server", "127.0.0.1:0"])
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut first_line = String::new();
+    BufReader::new(child.stdout.take().unwrap()).read_line(&mut first_line).unwrap();
+    let addr = first_line.trim()
+        .trim_start_matches("Chatting on ")
+        .trim_end_matches("...")
+        .to_string();
+    (KillOnDrop(child), addr)
+}
+
+#[test]
+fn client_messages_reach_other_clients() {
+    let (_server, addr) = start_server();
+
+    let mut bob = Client::connect(&*addr, "bob").unwrap();
+    bob.stream().set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+    let mut next = move || bob.recv().expect("server hung up").expect("read failed");
+
+    tcp_chat().args(["client", &addr, "carol"])
+        .write_stdin("hello from the command line\n")
+        .assert()
+        .success();
+
+    assert_eq!(next(), "* carol joined");
+    assert_eq!(next(), "carol: hello from the command line");
+    assert_eq!(next(), "* carol left");
+}
+
+#[test]
+fn usage_on_bad_arguments() {
+    for (args, error) in [(&[][..], "missing server or client"),
+                          (&["client", "127.0.0.1:7878"][..], "missing NAME"),
+                          (&["serve"][..], "unknown mode \"serve\""),
+                          (&["server", "127.0.0.1:0", "now"][..], "unexpected argument \"now\"")] {
+        tcp_chat().args(args).assert()
+            .code(1)
+            .stderr(format!("tcp-chat: {}\nUsage: tcp-chat server [ADDR]\n       \
+                             tcp-chat client ADDR NAME\nExample: tcp-chat server 127.0.0.1:7878\n",
+                            error));
+    }
+}
+
+#[test]
+fn client_reports_connection_errors() {
+    // Bind and drop a listener to find a port that (almost certainly) has nobody on it.
+    let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+    tcp_chat().args(["client", &format!("127.0.0.1:{}", port), "dave"]).assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("tcp-chat: "));
+}
@@ -0,0 +1,47 @@
+// What `threadpool --explain` prints: the chapter's notes, in short.
+use rustfun_common::{Explanation, Text, Topic};
+
+pub const EXPLANATION: Explanation = Explanation {
+    chapter: "28threadpool",
+    summary: Text {
+        en: "a thread pool, rendering the Mandelbrot set (slow in debug builds)",
+        zh: "线程池, 用来渲染 Mandelbrot 集 (debug 构建较慢)",
+    },
+    topics: &[
+        Topic {
+            title: Text { en: "Jobs are boxed closures", zh: "任务是装箱的闭包" },
+            body: Text {
+                en: "A job is Box<dyn FnOnce() + Send + 'static>: code that runs once, can move to\n\
+                     another thread, and borrows nothing that might die before it runs.",
+                zh: "任务是 Box<dyn FnOnce() + Send + 'static>: 只运行一次、可以移到另一个线程、\n\
+                     不借用任何可能在它运行前消失的东西。",
+            },
+        },
+        Topic {
+            title: Text { en: "Sharing one receiver", zh: "共享一个接收端" },
+            body: Text {
+                en: "An mpsc channel has a single consumer, so the workers share the Receiver\n\
+                     through Arc<Mutex<...>>. The lock is released before the job runs.",
+                zh: "mpsc 通道只有一个消费者, 所以工作线程通过 Arc<Mutex<...>> 共享 Receiver。\n\
+                     锁在任务运行之前就释放了。",
+            },
+        },
+        Topic {
+            title: Text { en: "Shutdown is Drop", zh: "关闭就是 Drop" },
+            body: Text {
+                en: "Dropping the pool drops the Sender, so each worker's recv() fails once the\n\
+                     queue is empty; then Drop joins them. Every job handed in has run.",
+                zh: "丢弃线程池会丢弃 Sender, 队列清空后每个工作线程的 recv() 都会失败; 随后\n\
+                     Drop 等待它们结束。交进来的每个任务都已运行。",
+            },
+        },
+        Topic {
+            title: Text { en: "Surviving panics", zh: "挺过 panic" },
+            body: Text {
+                en: "catch_unwind stops a panicking job at the worker, so the pool doesn't lose\n\
+                     threads.",
+                zh: "catch_unwind 在工作线程处拦住 panic 的任务, 线程池不会因此失去线程。",
+            },
+        },
+    ],
+};
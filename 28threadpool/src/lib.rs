@@ -0,0 +1,192 @@
+//
+// A thread pool from scratch.
+//
+//                       +--> [worker 0] --+
+//   execute(job) --Job--+--> [worker 1] --+--> jobs run, results go wherever the job sends them
+//          (mpsc)       +--> [worker 2] --+
+//
+// Note:
+//
+// 1.) A job is a boxed closure, Box<dyn FnOnce() + Send + 'static>: any code at all, run once,
+//     movable to another thread, borrowing nothing that might die before it runs.
+// 2.) mpsc is "multiple producer, single consumer", but a pool has many consumers. The
+//     workers share the one Receiver through Arc<Mutex<..>> and take turns pulling from it.
+// 3.) Shutdown is Drop: dropping the Sender makes every recv() fail once the queue is empty,
+//     which is each worker's signal to exit, and then Drop joins all of them. So when a
+//     ThreadPool goes out of scope, every job that was handed to it has finished.
+//
+use std::panic;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+pub mod mandelbrot;
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// A fixed number of threads running submitted jobs in FIFO order.
+pub struct ThreadPool {
+    workers: Vec<Worker>,
+    // 1.  Option only so that Drop can take() the Sender and drop it *before* joining.
+    sender: Option<Sender<Job>>,
+}
+
+struct Worker {
+    id: usize,
+    thread: Option<thread::JoinHandle<()>>,
+}
+
+impl Worker {
+    fn new(id: usize, receiver: Arc<Mutex<Receiver<Job>>>) -> Worker {
+        let thread = thread::Builder::new()
+            .name(format!("pool-worker-{}", id))
+            .spawn(move || loop {
+                // 2.  The lock guard is a temporary of this `let` statement, so the Mutex is
+                //     released as soon as recv() returns, *before* the job runs. Holding it
+                //     while running the job would let only one job run at a time.
+                let message = receiver.lock().unwrap().recv();
+                match message {
+                    // 3.  A panicking job must not take its worker down with it, or the pool
+                    //     would slowly lose threads. catch_unwind stops the unwinding here.
+                    Ok(job) => {
+                        if panic::catch_unwind(panic::AssertUnwindSafe(job)).is_err() {
+                            log::warn!("worker {} carries on after a panicking job", id);
+                        }
+                    }
+                    // the Sender is gone and the queue is drained
+                    Err(_) => {
+                        log::debug!("worker {} exiting", id);
+                        break;
+                    }
+                }
+            })
+            .expect("failed to spawn worker thread");
+        Worker { id, thread: Some(thread) }
+    }
+}
+
+impl ThreadPool {
+    /// Create a pool with `size` worker threads.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero: such a pool would accept jobs and never run them.
+    pub fn new(size: usize) -> ThreadPool {
+        assert!(size > 0, "a thread pool needs at least one worker");
+
+        let (sender, receiver) = channel();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let workers = (0..size).map(|id| Worker::new(id, Arc::clone(&receiver))).collect();
+
+        ThreadPool { workers, sender: Some(sender) }
+    }
+
+    /// The number of worker threads.
+    pub fn size(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Queue `job` to run on the next free worker.
+    // 4.  The bounds mirror thread::spawn's: FnOnce because each job runs once, Send to cross
+    //     to the worker thread, 'static because we can't know when it will run.
+    pub fn execute<F>(&self, job: F)
+        where F: FnOnce() + Send + 'static
+    {
+        self.sender.as_ref()
+            .expect("sender only taken in drop")
+            .send(Box::new(job))
+            .expect("all workers have exited");
+    }
+}
+
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        // 5.  Close the channel first, otherwise the workers would wait for more jobs
+        //     forever and the joins below would never return.
+        drop(self.sender.take());
+
+        for worker in &mut self.workers {
+            if let Some(thread) = worker.thread.take() {
+                if thread.join().is_err() {
+                    log::error!("worker {} panicked outside a job", worker.id);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(test)]
+use std::time::Duration;
+
+#[test]
+fn test_runs_all_jobs() {
+    let counter = Arc::new(AtomicUsize::new(0));
+    let pool = ThreadPool::new(4);
+    assert_eq!(pool.size(), 4);
+    for _ in 0..100 {
+        let counter = Arc::clone(&counter);
+        pool.execute(move || { counter.fetch_add(1, Ordering::SeqCst); });
+    }
+    drop(pool);
+    assert_eq!(counter.load(Ordering::SeqCst), 100);
+}
+
+#[test]
+fn test_drop_waits_for_queued_jobs() {
+    // one worker, several slow jobs: at drop time most of them are still in the queue,
+    // and a graceful shutdown must run them all rather than throwing them away
+    let finished = Arc::new(Mutex::new(Vec::new()));
+    {
+        let pool = ThreadPool::new(1);
+        for i in 0..5 {
+            let finished = Arc::clone(&finished);
+            pool.execute(move || {
+                thread::sleep(Duration::from_millis(10));
+                finished.lock().unwrap().push(i);
+            });
+        }
+    } // pool dropped here
+    // a single worker also means FIFO order is observable
+    assert_eq!(*finished.lock().unwrap(), vec![0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn test_drop_joins_workers() {
+    let alive = Arc::new(AtomicUsize::new(0));
+    let pool = ThreadPool::new(3);
+    for _ in 0..3 {
+        let alive = Arc::clone(&alive);
+        pool.execute(move || { alive.fetch_add(1, Ordering::SeqCst); });
+    }
+    drop(pool);
+    // every job, and so every clone of `alive`, is gone once drop returns
+    assert_eq!(Arc::strong_count(&alive), 1);
+    assert_eq!(alive.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn test_survives_panicking_job() {
+    let counter = Arc::new(AtomicUsize::new(0));
+    let pool = ThreadPool::new(2);
+    // silence the default "thread panicked" message for the deliberate panics
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    for _ in 0..4 {
+        pool.execute(|| panic!("job failed"));
+    }
+    for _ in 0..10 {
+        let counter = Arc::clone(&counter);
+        pool.execute(move || { counter.fetch_add(1, Ordering::SeqCst); });
+    }
+    drop(pool);
+    panic::set_hook(hook);
+    assert_eq!(counter.load(Ordering::SeqCst), 10);
+}
+
+#[test]
+#[should_panic(expected = "at least one worker")]
+fn test_zero_workers() {
+    ThreadPool::new(0);
+}
@@ -0,0 +1,48 @@
+use num::Complex;
+use std::time::Instant;
+use threadpool::mandelbrot::{render, render_on_pool};
+use threadpool::ThreadPool;
+
+// 1.  Print a small render as ASCII art: darker pixels (slower to escape) get denser
+//     characters, and the set itself is '@'.
+fn print_ascii(pixels: &[u8], bounds: (usize, usize)) {
+    let shades = b" .:-=+*#%@";
+    for row in pixels.chunks(bounds.0) {
+        let line: String = row.iter()
+            .map(|&p| shades[(255 - p as usize) * (shades.len() - 1) / 255] as char)
+            .collect();
+        println!("{}", line);
+    }
+}
+
+mod explain;
+
+fn main() {
+    rustfun_common::init_logging();
+    explain::EXPLANATION.on_request();
+    let upper_left = Complex { re: -2.2, im: 1.2 };
+    let lower_right = Complex { re: 0.8, im: -1.2 };
+
+    let preview = (72, 28);
+    let pool = ThreadPool::new(4);
+    print_ascii(&render_on_pool(&pool, preview, upper_left, lower_right), preview);
+
+    // 2.  The same image, serially and on pools of several sizes. With --deterministic
+    //     the timings are left out: they're different every run.
+    let deterministic = rustfun_common::deterministic::is_on();
+    let took = |start: Instant| {
+        if deterministic { "done".to_string() } else { format!("{:?}", start.elapsed()) }
+    };
+    let bounds = (1000, 750);
+    let start = Instant::now();
+    let serial = render(bounds, upper_left, lower_right);
+    println!("serial:            {}", took(start));
+
+    for &threads in &[2, 4, 8] {
+        let pool = ThreadPool::new(threads);
+        let start = Instant::now();
+        let pixels = render_on_pool(&pool, bounds, upper_left, lower_right);
+        println!("pool of {} workers: {}", threads, took(start));
+        assert!(pixels == serial);
+    }
+}
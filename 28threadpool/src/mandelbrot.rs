@@ -0,0 +1,136 @@
+// The practical payoff: the Mandelbrot renderer from 03mandelbrot, parallelized on the pool.
+//
+// 03mandelbrot's render_c lets rayon share the rows out, and rayon's for_each waits for
+// them all, so the rows can borrow the pixel buffer. A pool job must be 'static, so
+// it can't borrow anything; instead each job owns the parameters for one row, renders it
+// into its own Vec<u8>, and sends the finished row back over a channel.
+//
+// Note:
+//
+// 1.) Rows near the set take far longer than rows far from it. With one job per row, a
+//     worker that got cheap rows simply takes the next job, so nobody sits idle while one
+//     thread grinds through the expensive band.
+// 2.) The pool outlives a single render: the same threads can serve many frames.
+//
+use num::Complex;
+use std::sync::mpsc::channel;
+use crate::ThreadPool;
+
+/// Try to determine if `c` is in the Mandelbrot set, using at most `limit`
+/// iterations to decide.
+///
+/// Return `Some(i)` if `c` left the circle of radius two after `i` iterations,
+/// or `None` if it was still inside after `limit` iterations.
+pub fn escape_time(c: Complex<f64>, limit: u32) -> Option<u32> {
+    let mut z = Complex { re: 0.0, im: 0.0 };
+    for i in 0..limit {
+        z = z * z + c;
+        if z.norm_sqr() > 4.0 {
+            return Some(i);
+        }
+    }
+    None
+}
+
+/// Given the row and column of a pixel in the output image, return the
+/// corresponding point on the complex plane.
+pub fn pixel_to_point(bounds: (usize, usize),
+                      pixel: (usize, usize),
+                      upper_left: Complex<f64>,
+                      lower_right: Complex<f64>)
+    -> Complex<f64>
+{
+    let (width, height) = (lower_right.re - upper_left.re,
+                           upper_left.im - lower_right.im);
+    Complex {
+        re: upper_left.re + pixel.0 as f64 * width  / bounds.0 as f64,
+        im: upper_left.im - pixel.1 as f64 * height / bounds.1 as f64,
+    }
+}
+
+/// Render one row of the image, one grayscale byte per pixel.
+pub fn render_row(bounds: (usize, usize),
+                  row: usize,
+                  upper_left: Complex<f64>,
+                  lower_right: Complex<f64>)
+    -> Vec<u8>
+{
+    (0..bounds.0).map(|column| {
+        let point = pixel_to_point(bounds, (column, row), upper_left, lower_right);
+        match escape_time(point, 255) {
+            None => 0,
+            Some(count) => 255 - count as u8,
+        }
+    }).collect()
+}
+
+/// Render the whole image on the calling thread.
+pub fn render(bounds: (usize, usize),
+              upper_left: Complex<f64>,
+              lower_right: Complex<f64>)
+    -> Vec<u8>
+{
+    let mut pixels = Vec::with_capacity(bounds.0 * bounds.1);
+    for row in 0..bounds.1 {
+        pixels.extend(render_row(bounds, row, upper_left, lower_right));
+    }
+    pixels
+}
+
+/// Render the whole image with one pool job per row.
+pub fn render_on_pool(pool: &ThreadPool,
+                      bounds: (usize, usize),
+                      upper_left: Complex<f64>,
+                      lower_right: Complex<f64>)
+    -> Vec<u8>
+{
+    // 1.  Every job gets its own clone of the Sender. Complex<f64> is Copy, so the move
+    //     closure copies the corners rather than borrowing them.
+    let (sender, receiver) = channel();
+    for row in 0..bounds.1 {
+        let sender = sender.clone();
+        pool.execute(move || {
+            let pixels = render_row(bounds, row, upper_left, lower_right);
+            // the receiver only goes away if render_on_pool itself panicked
+            let _ = sender.send((row, pixels));
+        });
+    }
+    // 2.  Drop our own Sender, so the loop below ends once every job's clone is gone too.
+    drop(sender);
+
+    // 3.  Rows arrive in whatever order they finish; the row number says where each goes.
+    let mut pixels = vec![0; bounds.0 * bounds.1];
+    for (row, row_pixels) in receiver {
+        let start = row * bounds.0;
+        pixels[start..start + bounds.0].copy_from_slice(&row_pixels);
+    }
+    pixels
+}
+
+#[test]
+fn test_pixel_to_point() {
+    assert_eq!(pixel_to_point((100, 100), (25, 75),
+                              Complex { re: -1.0, im:  1.0 },
+                              Complex { re:  1.0, im: -1.0 }),
+               Complex { re: -0.5, im: -0.5 });
+}
+
+#[test]
+fn test_escape_time() {
+    assert_eq!(escape_time(Complex { re: 0.0, im: 0.0 }, 255), None);
+    assert_eq!(escape_time(Complex { re: 2.0, im: 2.0 }, 255), Some(0));
+}
+
+#[test]
+fn test_pool_render_matches_serial_render() {
+    let bounds = (64, 48);
+    let upper_left = Complex { re: -1.20, im: 0.35 };
+    let lower_right = Complex { re: -1.0, im: 0.20 };
+    let pool = ThreadPool::new(4);
+    assert_eq!(render_on_pool(&pool, bounds, upper_left, lower_right),
+               render(bounds, upper_left, lower_right));
+    // the pool is reusable for a second frame
+    let lower_right = Complex { re: -0.5, im: -0.5 };
+    assert_eq!(render_on_pool(&pool, bounds, upper_left, lower_right),
+               render(bounds, upper_left, lower_right));
+}
@@ -0,0 +1,29 @@
+// Run the chapter's demo binary and check the parts of its output that are deterministic.
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+// The timings vary; the ASCII preview and the fact that every pool's image matched the
+// serial one (main asserts it) don't.
+#[test]
+fn prints_the_demo() {
+    Command::cargo_bin("threadpool").unwrap().assert()
+        .success()
+        .stdout(predicate::str::contains("@@@")
+            .and(predicate::str::contains("serial:            "))
+            .and(predicate::str::contains("pool of 8 workers: ")));
+}
+
+// With --deterministic the output is the same on every run: the timings are left out.
+#[test]
+fn prints_the_same_every_run_when_deterministic() {
+    let run = || Command::cargo_bin("threadpool").unwrap().arg("--deterministic").output().unwrap();
+    let (first, second) = (run(), run());
+    assert!(first.status.success());
+    assert_eq!(first.stdout, second.stdout);
+    assert!(String::from_utf8(first.stdout).unwrap().ends_with("\
+serial:            done
+pool of 2 workers: done
+pool of 4 workers: done
+pool of 8 workers: done
+"));
+}
@@ -0,0 +1,47 @@
+// What `binary-search-tree --explain` prints: the chapter's notes, in short.
+use rustfun_common::{Explanation, Text, Topic};
+
+pub const EXPLANATION: Explanation = Explanation {
+    chapter: "29binary-search-tree",
+    summary: Text {
+        en: "a binary search tree with Option<Box<Node>>",
+        zh: "用 Option<Box<Node>> 实现的二叉搜索树",
+    },
+    topics: &[
+        Topic {
+            title: Text { en: "Option<Box<Node<T>>>", zh: "Option<Box<Node<T>>>" },
+            body: Text {
+                en: "Each node owns its subtrees. Box, because a struct can't contain itself\n\
+                     directly; Option, because a subtree may be empty.",
+                zh: "每个节点拥有自己的子树。用 Box, 因为结构体不能直接包含自身; 用 Option,\n\
+                     因为子树可能为空。",
+            },
+        },
+        Topic {
+            title: Text { en: "A &mut cursor", zh: "&mut 游标" },
+            body: Text {
+                en: "insert walks a &mut to the link where the value belongs, re-borrowing one\n\
+                     level deeper each step; the empty link it ends at is where the node goes.",
+                zh: "insert 沿着指向值应在位置的 &mut 链接前进, 每一步重新借用更深一层;\n\
+                     最后停在的空链接就是放新节点的地方。",
+            },
+        },
+        Topic {
+            title: Text { en: "Loops, not recursion", zh: "用循环而不是递归" },
+            body: Text {
+                en: "A tree built from sorted input is as deep as it is long, so every walk is\n\
+                     a loop, including Drop: the compiler's drop glue is recursive and could\n\
+                     overflow the stack.",
+                zh: "由有序输入建成的树和它的长度一样深, 所以所有遍历都用循环, 包括 Drop:\n\
+                     编译器生成的释放代码是递归的, 可能导致栈溢出。",
+            },
+        },
+        Topic {
+            title: Text { en: "Iterators", zh: "迭代器" },
+            body: Text {
+                en: "The in-order iterator keeps its own stack of the nodes still to visit.",
+                zh: "中序迭代器自己维护一个待访问节点的栈。",
+            },
+        },
+    ],
+};
@@ -0,0 +1,297 @@
+//
+// A generic binary search tree built from Box<Node<T>>.
+//
+//                 Tree { root, len: 5 }
+//                        |
+//                      [ 40 ]
+//                      /    \
+//                [ 20 ]      [ 60 ]
+//                /    \           \
+//           [ 10 ]    [ 30 ]      (None)...
+//
+// Note:
+//
+// 1.) Every node owns its two subtrees through Option<Box<Node<T>>>: Box because a struct
+//     can't contain itself directly (its size would be infinite), Option because a
+//     subtree may be empty. Ownership is a tree already, so a tree fits it perfectly.
+// 2.) Recursion is the natural way to write tree code, but a tree built from sorted input
+//     degenerates into a list as deep as it is long. Everything below walks the tree with
+//     a loop instead, including Drop: the compiler-generated drop glue *is* recursive, and
+//     would overflow the stack on a deep enough tree.
+//
+#[cfg(test)]
+use proptest::prelude::*;
+use std::cmp::Ordering;
+use std::iter::FromIterator;
+
+type Link<T> = Option<Box<Node<T>>>;
+
+struct Node<T> {
+    value: T,
+    left: Link<T>,
+    right: Link<T>,
+}
+
+/// An unbalanced binary search tree holding a set of `T`s.
+pub struct Tree<T> {
+    root: Link<T>,
+    len: usize,
+}
+
+impl<T: Ord> Tree<T> {
+    pub fn new() -> Tree<T> {
+        Tree { root: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // 1.  Insertion walks a *cursor*: `link` is a &mut to the Option where the value
+    //     belongs. Each step re-borrows one level deeper; when the loop finds an empty
+    //     link, that is exactly the place to write the new node.
+    // 1.1 `while let Some(node) = link` moves the &mut out of `link` into `node` for the
+    //     body, and the body gives `link` a new value before the next round, so the
+    //     borrow checker can see that no two &muts to the same Option are ever live.
+    /// Add `value` to the tree. Return false if it was already present.
+    pub fn insert(&mut self, value: T) -> bool {
+        let mut link = &mut self.root;
+        while let Some(node) = link {
+            link = match value.cmp(&node.value) {
+                Ordering::Less => &mut node.left,
+                Ordering::Greater => &mut node.right,
+                Ordering::Equal => return false,
+            };
+        }
+        *link = Some(Box::new(Node { value, left: None, right: None }));
+        self.len += 1;
+        true
+    }
+
+    // 2.  Lookup only needs shared references: `Some(ref node)` borrows the Box inside the
+    //     Option instead of moving it out, and we just keep stepping down.
+    pub fn contains(&self, value: &T) -> bool {
+        let mut link = &self.root;
+        while let Some(ref node) = *link {
+            link = match value.cmp(&node.value) {
+                Ordering::Less => &node.left,
+                Ordering::Greater => &node.right,
+                Ordering::Equal => return true,
+            };
+        }
+        false
+    }
+
+    /// The number of nodes on the longest path from the root to a leaf.
+    pub fn height(&self) -> usize {
+        let mut height = 0;
+        let mut level: Vec<&Node<T>> = self.root.iter().map(|n| &**n).collect();
+        while !level.is_empty() {
+            height += 1;
+            level = level.iter()
+                .flat_map(|n| n.left.iter().chain(n.right.iter()))
+                .map(|n| &**n)
+                .collect();
+        }
+        height
+    }
+
+    /// Visit the values in ascending order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        let mut iter = Iter { stack: Vec::new() };
+        iter.push_left_spine(&self.root);
+        iter
+    }
+}
+
+impl<T: Ord> Default for Tree<T> {
+    fn default() -> Tree<T> {
+        Tree::new()
+    }
+}
+
+// 3.  The in-order iterator keeps an explicit stack of the nodes whose left subtree is
+//     being visited. Invariant: the top of the stack is the next smallest value.
+// 3.1 Iter<'a, T> borrows the tree for 'a and hands out &'a T, so the values can outlive
+//     the iterator but not the tree.
+pub struct Iter<'a, T: 'a> {
+    stack: Vec<&'a Node<T>>,
+}
+
+impl<'a, T> Iter<'a, T> {
+    fn push_left_spine(&mut self, mut link: &'a Link<T>) {
+        while let Some(ref node) = *link {
+            self.stack.push(node);
+            link = &node.left;
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let node = self.stack.pop()?;
+        self.push_left_spine(&node.right);
+        Some(&node.value)
+    }
+}
+
+impl<'a, T: Ord> IntoIterator for &'a Tree<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<T: Ord> Extend<T> for Tree<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, values: I) {
+        for value in values {
+            self.insert(value);
+        }
+    }
+}
+
+impl<T: Ord> FromIterator<T> for Tree<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(values: I) -> Tree<T> {
+        let mut tree = Tree::new();
+        tree.extend(values);
+        tree
+    }
+}
+
+// 4.  A hand-written Drop. Detach the children of each node before the node itself is
+//     dropped, so the built-in drop of a Box<Node> never finds a subtree to recurse into.
+//     The pending subtrees wait on a Vec, which lives on the heap and can grow freely.
+impl<T> Drop for Tree<T> {
+    fn drop(&mut self) {
+        let mut pending: Vec<Box<Node<T>>> = self.root.take().into_iter().collect();
+        while let Some(mut node) = pending.pop() {
+            pending.extend(node.left.take());
+            pending.extend(node.right.take());
+            // `node` is dropped here, with both links already None
+        }
+    }
+}
+
+#[test]
+fn test_insert_and_contains() {
+    let mut tree = Tree::new();
+    assert!(tree.is_empty());
+    assert!(tree.insert(40));
+    assert!(tree.insert(20));
+    assert!(tree.insert(60));
+    assert!(tree.insert(30));
+    assert!(!tree.insert(20)); // already there
+    assert_eq!(tree.len(), 4);
+    assert!(tree.contains(&30));
+    assert!(!tree.contains(&50));
+    assert_eq!(tree.height(), 3);
+}
+
+#[test]
+fn test_in_order_iteration() {
+    let tree: Tree<&str> = vec!["pear", "apple", "fig", "kiwi", "apple"].into_iter().collect();
+    assert_eq!(tree.iter().cloned().collect::<Vec<_>>(), vec!["apple", "fig", "kiwi", "pear"]);
+    let mut lengths = Vec::new();
+    for fruit in &tree {
+        lengths.push(fruit.len());
+    }
+    assert_eq!(lengths, vec![5, 3, 4, 4]);
+}
+
+#[cfg(test)]
+fn degenerate_tree(len: usize) -> Tree<usize> {
+    // build the chain 0 -> 1 -> 2 -> ... bottom up: far faster than `len` inserts,
+    // each of which would walk the whole chain
+    let mut root = None;
+    for value in (0..len).rev() {
+        root = Some(Box::new(Node { value, left: None, right: root }));
+    }
+    Tree { root, len }
+}
+
+#[test]
+fn test_deep_tree_does_not_overflow_the_stack() {
+    // deep enough to overflow a test thread's stack with recursive drop glue
+    let tree = degenerate_tree(1_000_000);
+    assert_eq!(tree.height(), 1_000_000);
+    assert!(tree.contains(&999_999));
+    assert_eq!(tree.iter().count(), 1_000_000);
+    drop(tree);
+}
+
+#[test]
+fn test_sorted_inserts_degenerate() {
+    let tree: Tree<u32> = (0..1000).collect();
+    assert_eq!(tree.height(), 1000);
+    let shuffled: Tree<u32> = (0..1000).map(|i| (i * 7919) % 1000).collect();
+    assert_eq!(shuffled.len(), 1000);
+    assert!(shuffled.height() < 1000);
+}
+
+// 5.  Property tests: for any sequence of operations, the tree must answer exactly like
+//     std's BTreeSet, which is the specification we are implementing.
+#[cfg(test)]
+#[derive(Debug, Clone)]
+enum Op {
+    Insert(i16),
+    Contains(i16),
+}
+
+#[cfg(test)]
+fn op_strategy() -> impl Strategy<Value = Op> {
+    // a small value range, so the same values come up again and duplicates get tested
+    prop_oneof![
+        (-50i16..50).prop_map(Op::Insert),
+        (-50i16..50).prop_map(Op::Contains),
+    ]
+}
+
+#[cfg(test)]
+proptest! {
+    #[test]
+    fn prop_behaves_like_btreeset(ops in proptest::collection::vec(op_strategy(), 0..200)) {
+        let mut tree = Tree::new();
+        let mut set = std::collections::BTreeSet::new();
+        for op in ops {
+            match op {
+                Op::Insert(v) => prop_assert_eq!(tree.insert(v), set.insert(v)),
+                Op::Contains(v) => prop_assert_eq!(tree.contains(&v), set.contains(&v)),
+            }
+            prop_assert_eq!(tree.len(), set.len());
+        }
+        prop_assert!(tree.iter().eq(set.iter()));
+    }
+
+    #[test]
+    fn prop_iteration_is_sorted_and_unique(values in proptest::collection::vec(any::<u32>(), 0..300)) {
+        let tree: Tree<u32> = values.iter().cloned().collect();
+        let mut expected = values.clone();
+        expected.sort();
+        expected.dedup();
+        prop_assert_eq!(tree.iter().cloned().collect::<Vec<_>>(), expected);
+    }
+}
+
+mod explain;
+
+fn main() {
+    rustfun_common::init_logging();
+    explain::EXPLANATION.on_request();
+    println!("Hello, binary search tree!");
+
+    let tree: Tree<i32> = vec![40, 20, 60, 10, 30, 50, 70].into_iter().collect();
+    println!("in order: {:?}", tree.iter().collect::<Vec<_>>());
+    println!("len {}, height {}, contains 30? {}, contains 35? {}",
+             tree.len(), tree.height(), tree.contains(&30), tree.contains(&35));
+
+    let sorted: Tree<i32> = (0..1000).collect();
+    println!("1000 sorted inserts give a tree of height {}", sorted.height());
+}
@@ -0,0 +1,14 @@
+// Run the chapter's demo binary and check the parts of its output that are deterministic.
+use assert_cmd::Command;
+
+#[test]
+fn prints_the_demo() {
+    Command::cargo_bin("binary-search-tree").unwrap().assert()
+        .success()
+        .stdout("\
+Hello, binary search tree!
+in order: [10, 20, 30, 40, 50, 60, 70]
+len 7, height 3, contains 30? true, contains 35? false
+1000 sorted inserts give a tree of height 1000
+");
+}
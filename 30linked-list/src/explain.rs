@@ -0,0 +1,45 @@
+// What `linked-list --explain` prints: the chapter's notes, in short.
+use rustfun_common::{Explanation, Text, Topic};
+
+pub const EXPLANATION: Explanation = Explanation {
+    chapter: "30linked-list",
+    summary: Text {
+        en: "doubly linked lists, with Rc/Weak and with raw pointers",
+        zh: "双向链表, 分别用 Rc/Weak 和裸指针实现",
+    },
+    topics: &[
+        Topic {
+            title: Text { en: "Why it's hard", zh: "为什么难" },
+            body: Text {
+                en: "In a doubly linked list each node is reachable from two neighbours, but a Box\n\
+                     has exactly one owner, and a plain reference would freeze the node.",
+                zh: "双向链表中每个节点都能从两个邻居到达, 但 Box 只有一个所有者, 普通引用又会\n\
+                     让节点无法修改。",
+            },
+        },
+        Topic {
+            title: Text { en: "Rc, RefCell and Weak", zh: "Rc、RefCell 与 Weak" },
+            body: Text {
+                en: "rc_list shares nodes with Rc, mutates them through RefCell, and points back\n\
+                     with Weak so the links don't form a cycle that's never freed.",
+                zh: "rc_list 用 Rc 共享节点, 通过 RefCell 修改, 用 Weak 指回前一个节点,\n\
+                     这样链接不会形成永远无法释放的环。",
+            },
+        },
+        Topic {
+            title: Text { en: "Raw pointers", zh: "裸指针" },
+            body: Text {
+                en: "raw_list uses *mut pointers in unsafe blocks, whose soundness is argued by\n\
+                     hand and checked under Miri.",
+                zh: "raw_list 在 unsafe 块中使用 *mut 指针, 其正确性靠人工论证, 并在 Miri 下检查。",
+            },
+        },
+        Topic {
+            title: Text { en: "In practice", zh: "实际使用" },
+            body: Text {
+                en: "VecDeque is almost always the better choice.",
+                zh: "实际上 VecDeque 几乎总是更好的选择。",
+            },
+        },
+    ],
+};
@@ -0,0 +1,33 @@
+//
+// Why is a doubly linked list hard in Rust?
+//
+// A singly linked list is easy: each node owns the next one through a Box, and ownership
+// runs in a straight line from the head.
+//
+//     struct Node<T> { value: T, next: Option<Box<Node<T>>> }
+//
+// A doubly linked list breaks that line. Node 2 must be reachable from node 1 *and* from
+// node 3, but a Box has exactly one owner:
+//
+//     struct Node<T> {
+//         value: T,
+//         next: Option<Box<Node<T>>>,
+//         prev: Option<Box<Node<T>>>,   // node 1 can't be owned by node 2, it's already
+//     }                                 // owned by the list's head
+//
+// and a plain reference doesn't work either:
+//
+//     prev: Option<&'a Node<T>>         // borrows the node, so nothing may mutate it,
+//                                       // including the next push_back
+//
+// Note:
+//
+// 1.) rc_list.rs gives up compile-time checking: shared ownership with Rc, interior
+//     mutability with RefCell, and Weak for the back pointers so they don't form cycles.
+// 2.) raw_list.rs gives up safety instead: raw pointers, and unsafe blocks whose
+//     correctness we argue for by hand and test under Miri.
+// 3.) For real programs, std::collections::VecDeque is almost always the better choice;
+//     std::collections::LinkedList exists and is written like raw_list.rs.
+//
+pub mod raw_list;
+pub mod rc_list;
@@ -0,0 +1,29 @@
+use linked_list::{raw_list, rc_list};
+
+mod explain;
+
+fn main() {
+    rustfun_common::init_logging();
+    explain::EXPLANATION.on_request();
+    println!("Hello, linked lists!");
+
+    let mut safe = rc_list::List::new();
+    for word in &["liberté", "égalité", "fraternité"] {
+        safe.push_back(word.to_string());
+    }
+    safe.push_front("vive".to_string());
+    println!("Rc/Weak list, forwards:  {:?}", safe.to_vec());
+    println!("Rc/Weak list, backwards: {:?}", safe.to_vec_reversed());
+    if let Some(front) = safe.peek_front() {
+        println!("peek_front hands out a Ref guard: {}", *front);
+    }
+
+    let mut raw = raw_list::List::new();
+    for i in 1..6 {
+        raw.push_back(i * i);
+    }
+    println!("raw pointer list, forwards:  {:?}", raw.iter().collect::<Vec<_>>());
+    println!("raw pointer list, backwards: {:?}", raw.iter().rev().collect::<Vec<_>>());
+    println!("pop_front {:?}, pop_back {:?}, {} left",
+             raw.pop_front(), raw.pop_back(), raw.len());
+}
@@ -0,0 +1,249 @@
+// The same doubly linked list with raw pointers and unsafe code.
+//
+//   head                                               tail
+//    |                                                  |
+//    v       next(*mut)          next(*mut)             v
+//   [Node 1] ---------> [Node 2] ---------> [Node 3] ---> null
+//   null <--- [Node 1] <--------- [Node 2] <--------- [Node 3]
+//              prev(*mut)          prev(*mut)
+//
+// Note:
+//
+// 1.) This is how the list looks in C, and how std::collections::LinkedList is written.
+//     No reference counts, no RefCell flags, and iter() can hand out plain &T again.
+// 2.) The compiler no longer checks anything about the links. Each `unsafe` block below
+//     is a promise we keep by hand:
+//     - every non-null pointer came from Box::into_raw and is freed exactly once, with
+//       Box::from_raw, when its node is popped;
+//     - head.prev and tail.next are null, and `len` counts the nodes between them.
+// 3.) Miri, the interpreter for Rust's abstract machine, can check those promises at run
+//     time: `cargo +nightly miri test` reports any use-after-free, double free, leak, or
+//     aliasing violation the tests trigger.
+//
+use std::marker::PhantomData;
+use std::ptr;
+
+struct Node<T> {
+    value: T,
+    next: *mut Node<T>,
+    prev: *mut Node<T>,
+}
+
+pub struct List<T> {
+    head: *mut Node<T>,
+    tail: *mut Node<T>,
+    len: usize,
+    // 1.  Raw pointers say nothing about ownership. PhantomData tells the compiler (for
+    //     drop checking and auto traits) that a List<T> owns Box<Node<T>>s.
+    _owns: PhantomData<Box<Node<T>>>,
+}
+
+impl<T> List<T> {
+    pub fn new() -> List<T> {
+        List { head: ptr::null_mut(), tail: ptr::null_mut(), len: 0, _owns: PhantomData }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn push_front(&mut self, value: T) {
+        // 2.  Box::into_raw gives up the Box's ownership and leaves us a pointer to free
+        //     later, by hand.
+        let node = Box::into_raw(Box::new(Node { value, next: self.head, prev: ptr::null_mut() }));
+        if self.head.is_null() {
+            self.tail = node;
+        } else {
+            unsafe { (*self.head).prev = node; }
+        }
+        self.head = node;
+        self.len += 1;
+    }
+
+    pub fn push_back(&mut self, value: T) {
+        let node = Box::into_raw(Box::new(Node { value, next: ptr::null_mut(), prev: self.tail }));
+        if self.tail.is_null() {
+            self.head = node;
+        } else {
+            unsafe { (*self.tail).next = node; }
+        }
+        self.tail = node;
+        self.len += 1;
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.head.is_null() {
+            return None;
+        }
+        // 3.  Box::from_raw takes ownership back; the node is freed when `node` is dropped
+        //     at the end of this function, after its value has been moved out.
+        let node = unsafe { Box::from_raw(self.head) };
+        self.head = node.next;
+        if self.head.is_null() {
+            self.tail = ptr::null_mut();
+        } else {
+            unsafe { (*self.head).prev = ptr::null_mut(); }
+        }
+        self.len -= 1;
+        Some(node.value)
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.tail.is_null() {
+            return None;
+        }
+        let node = unsafe { Box::from_raw(self.tail) };
+        self.tail = node.prev;
+        if self.tail.is_null() {
+            self.head = ptr::null_mut();
+        } else {
+            unsafe { (*self.tail).next = ptr::null_mut(); }
+        }
+        self.len -= 1;
+        Some(node.value)
+    }
+
+    // 4.  Converting a raw pointer to a reference is where we pick the lifetime: tying it
+    //     to &self means the borrow checker is back in charge from here on.
+    pub fn front(&self) -> Option<&T> {
+        unsafe { self.head.as_ref().map(|node| &node.value) }
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        unsafe { self.tail.as_ref().map(|node| &node.value) }
+    }
+
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        unsafe { self.head.as_mut().map(|node| &mut node.value) }
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { front: self.head, back: self.tail, remaining: self.len, _list: PhantomData }
+    }
+}
+
+impl<T> Default for List<T> {
+    fn default() -> List<T> {
+        List::new()
+    }
+}
+
+impl<T> Drop for List<T> {
+    fn drop(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+}
+
+// 5.  A double-ended iterator. `remaining` keeps the two ends from walking past each other
+//     when they meet in the middle.
+pub struct Iter<'a, T: 'a> {
+    front: *mut Node<T>,
+    back: *mut Node<T>,
+    remaining: usize,
+    _list: PhantomData<&'a T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        unsafe {
+            let node = &*self.front;
+            self.front = node.next;
+            Some(&node.value)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        unsafe {
+            let node = &*self.back;
+            self.back = node.prev;
+            Some(&node.value)
+        }
+    }
+}
+
+#[test]
+fn test_push_pop_both_ends() {
+    let mut list = List::new();
+    assert_eq!(list.pop_back(), None);
+    list.push_back(2);
+    list.push_back(3);
+    list.push_front(1);
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.front(), Some(&1));
+    assert_eq!(list.back(), Some(&3));
+
+    assert_eq!(list.pop_back(), Some(3));
+    assert_eq!(list.pop_front(), Some(1));
+    assert_eq!(list.pop_front(), Some(2));
+    assert_eq!(list.pop_front(), None);
+    assert!(list.is_empty());
+
+    list.push_back(4);
+    assert_eq!(list.pop_front(), Some(4));
+}
+
+#[test]
+fn test_iter_both_directions() {
+    let mut list = List::new();
+    for i in 1..6 {
+        list.push_back(i);
+    }
+    assert_eq!(list.iter().cloned().collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+    assert_eq!(list.iter().rev().cloned().collect::<Vec<_>>(), vec![5, 4, 3, 2, 1]);
+
+    // the two ends meet in the middle and stop
+    let mut iter = list.iter();
+    assert_eq!(iter.next(), Some(&1));
+    assert_eq!(iter.next_back(), Some(&5));
+    assert_eq!(iter.next(), Some(&2));
+    assert_eq!(iter.next_back(), Some(&4));
+    assert_eq!(iter.next(), Some(&3));
+    assert_eq!(iter.next_back(), None);
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn test_front_mut() {
+    let mut list = List::new();
+    list.push_back(String::from("hello"));
+    if let Some(s) = list.front_mut() {
+        s.push_str(", world");
+    }
+    assert_eq!(list.front().map(|s| s.as_str()), Some("hello, world"));
+}
+
+#[test]
+fn test_drops_every_value() {
+    use std::rc::Rc;
+    // each value holds a clone of `marker`; once the list is gone, only ours is left
+    let marker = Rc::new(());
+    {
+        let mut list = List::new();
+        for _ in 0..10 {
+            list.push_front(Rc::clone(&marker));
+        }
+        list.pop_back();
+        assert_eq!(Rc::strong_count(&marker), 10);
+    }
+    assert_eq!(Rc::strong_count(&marker), 1);
+}
@@ -0,0 +1,259 @@
+// A doubly linked list in safe Rust: Rc<RefCell<Node>> forward, Weak backward.
+//
+//          head                                        tail
+//           |                                           |
+//           v                                           v
+//   +----------------+  next(Rc)   +----------------+  next(Rc)   +----------------+
+//   | RefCell<Node 1>| ----------> | RefCell<Node 2>| ----------> | RefCell<Node 3>|
+//   |                | <---------- |                | <---------- |                |
+//   +----------------+  prev(Weak) +----------------+  prev(Weak) +----------------+
+//
+// Note:
+//
+// 1.) Every middle node is pointed at by two neighbours, so no single Box can own it.
+//     Rc gives shared ownership; RefCell gives the mutation that shared ownership forbids,
+//     with the borrow rules checked at run time instead of compile time.
+// 2.) If prev were an Rc too, each pair of neighbours would keep each other alive and the
+//     list would never be freed: a reference cycle. Weak pointers don't count towards
+//     keeping a node alive, so ownership flows strictly head -> tail.
+// 3.) The price: no method can return a plain &T into the list, because the value lives
+//     inside a RefCell that some other handle might borrow mutably. We can hand out a Ref<T>
+//     guard, or copies, but an ordinary `iter()` yielding &T is not possible.
+//
+use std::cell::{Ref, RefCell};
+use std::rc::{Rc, Weak};
+
+type Link<T> = Option<Rc<RefCell<Node<T>>>>;
+
+struct Node<T> {
+    value: T,
+    next: Link<T>,
+    prev: Option<Weak<RefCell<Node<T>>>>,
+}
+
+pub struct List<T> {
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+}
+
+impl<T> List<T> {
+    pub fn new() -> List<T> {
+        List { head: None, tail: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn push_front(&mut self, value: T) {
+        let node = Rc::new(RefCell::new(Node { value, next: None, prev: None }));
+        match self.head.take() {
+            Some(old_head) => {
+                // 1.  borrow_mut() panics if the cell is already borrowed; here nobody else
+                //     can be looking at old_head, so it always succeeds.
+                old_head.borrow_mut().prev = Some(Rc::downgrade(&node));
+                node.borrow_mut().next = Some(old_head);
+            }
+            None => self.tail = Some(Rc::clone(&node)),
+        }
+        self.head = Some(node);
+        self.len += 1;
+    }
+
+    pub fn push_back(&mut self, value: T) {
+        let node = Rc::new(RefCell::new(Node { value, next: None, prev: None }));
+        match self.tail.take() {
+            Some(old_tail) => {
+                node.borrow_mut().prev = Some(Rc::downgrade(&old_tail));
+                old_tail.borrow_mut().next = Some(Rc::clone(&node));
+            }
+            None => self.head = Some(Rc::clone(&node)),
+        }
+        self.tail = Some(node);
+        self.len += 1;
+    }
+
+    // 2.  To move the value out, the node must have exactly one owner left. Once the list
+    //     has unlinked it, Rc::try_unwrap gives back the RefCell, and into_inner() the Node.
+    //     If some other Rc still pointed here, try_unwrap would fail; the `ok().expect` is
+    //     our claim that the list's bookkeeping is right.
+    fn unwrap_node(node: Rc<RefCell<Node<T>>>) -> T {
+        Rc::try_unwrap(node).ok().expect("list nodes have one owner").into_inner().value
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.head.take().map(|old_head| {
+            match old_head.borrow_mut().next.take() {
+                Some(new_head) => {
+                    new_head.borrow_mut().prev = None;
+                    self.head = Some(new_head);
+                }
+                None => self.tail = None,
+            }
+            self.len -= 1;
+            Self::unwrap_node(old_head)
+        })
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.tail.take().map(|old_tail| {
+            // 3.  Following a Weak pointer means upgrading it to an Rc first, which fails
+            //     if the target is already gone. Here the previous node is owned by the list.
+            let prev = old_tail.borrow_mut().prev.take().and_then(|weak| weak.upgrade());
+            match prev {
+                Some(new_tail) => {
+                    new_tail.borrow_mut().next = None;
+                    self.tail = Some(new_tail);
+                }
+                None => self.head = None,
+            }
+            self.len -= 1;
+            Self::unwrap_node(old_tail)
+        })
+    }
+
+    // 4.  The best we can do for "look at the first value": a Ref guard that keeps the
+    //     RefCell borrowed until it is dropped. Ref::map narrows it from the Node to
+    //     the value.
+    pub fn peek_front(&self) -> Option<Ref<'_, T>> {
+        self.head.as_ref().map(|node| Ref::map(node.borrow(), |node| &node.value))
+    }
+
+    pub fn peek_back(&self) -> Option<Ref<'_, T>> {
+        self.tail.as_ref().map(|node| Ref::map(node.borrow(), |node| &node.value))
+    }
+
+    /// Copy the values out, front to back.
+    pub fn to_vec(&self) -> Vec<T> where T: Clone {
+        let mut values = Vec::with_capacity(self.len);
+        let mut link = self.head.clone();
+        while let Some(node) = link {
+            values.push(node.borrow().value.clone());
+            link = node.borrow().next.clone();
+        }
+        values
+    }
+
+    /// Copy the values out, back to front, by following the Weak prev pointers.
+    pub fn to_vec_reversed(&self) -> Vec<T> where T: Clone {
+        let mut values = Vec::with_capacity(self.len);
+        let mut link = self.tail.clone();
+        while let Some(node) = link {
+            values.push(node.borrow().value.clone());
+            link = node.borrow().prev.as_ref().and_then(|weak| weak.upgrade());
+        }
+        values
+    }
+}
+
+impl<T> Default for List<T> {
+    fn default() -> List<T> {
+        List::new()
+    }
+}
+
+// 5.  Dropping the head Rc would drop the next node, which drops the next... recursively,
+//     one stack frame per node. Popping in a loop frees the nodes one at a time instead.
+impl<T> Drop for List<T> {
+    fn drop(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+}
+
+#[cfg(test)]
+use std::cell::Cell;
+
+// Counts how many values have been dropped, to prove that nothing leaks.
+#[cfg(test)]
+struct Tracked<'a>(u32, &'a Cell<u32>);
+
+#[cfg(test)]
+impl<'a> Drop for Tracked<'a> {
+    fn drop(&mut self) {
+        self.1.set(self.1.get() + 1);
+    }
+}
+
+#[test]
+fn test_push_pop_both_ends() {
+    let mut list = List::new();
+    assert_eq!(list.pop_front(), None);
+    list.push_back(2);
+    list.push_back(3);
+    list.push_front(1);
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.to_vec(), vec![1, 2, 3]);
+    assert_eq!(list.to_vec_reversed(), vec![3, 2, 1]);
+
+    assert_eq!(*list.peek_front().unwrap(), 1);
+    assert_eq!(*list.peek_back().unwrap(), 3);
+
+    assert_eq!(list.pop_back(), Some(3));
+    assert_eq!(list.pop_front(), Some(1));
+    assert_eq!(list.pop_front(), Some(2));
+    assert_eq!(list.pop_back(), None);
+    assert!(list.is_empty());
+
+    // the list still works after being emptied
+    list.push_front(4);
+    assert_eq!(list.pop_back(), Some(4));
+}
+
+#[test]
+fn test_reference_counts() {
+    let mut list = List::new();
+    list.push_back("a");
+    list.push_back("b");
+    list.push_back("c");
+    let middle = Rc::clone(list.head.as_ref().unwrap().borrow().next.as_ref().unwrap());
+    // owned by node "a" and by our clone; node "c" only points back weakly
+    assert_eq!(Rc::strong_count(&middle), 2);
+    assert_eq!(Rc::weak_count(&middle), 1);
+    drop(middle);
+    // head and tail each hold one extra strong reference to their node
+    assert_eq!(Rc::strong_count(list.head.as_ref().unwrap()), 1);
+    assert_eq!(Rc::strong_count(list.tail.as_ref().unwrap()), 2);
+}
+
+#[test]
+fn test_no_leaks() {
+    let dropped = Cell::new(0);
+    {
+        let mut list = List::new();
+        for i in 0..10 {
+            list.push_back(Tracked(i, &dropped));
+        }
+        let first = list.pop_front().unwrap();
+        assert_eq!(first.0, 0);
+        assert_eq!(dropped.get(), 0);
+        drop(first);
+        assert_eq!(dropped.get(), 1);
+    }
+    // without Weak back pointers, the remaining nine would never be dropped
+    assert_eq!(dropped.get(), 10);
+}
+
+#[test]
+#[should_panic(expected = "already borrowed")]
+fn test_runtime_borrow_check() {
+    let mut list = List::new();
+    list.push_back(1);
+    let _looking = list.peek_front().unwrap();
+    // the Ref guard is still alive, so mutating the node must panic
+    list.head.as_ref().unwrap().borrow_mut().value = 2;
+}
+
+#[test]
+fn test_long_list_drop() {
+    let mut list = List::new();
+    let n = if cfg!(miri) { 1_000 } else { 200_000 };
+    for i in 0..n {
+        list.push_back(i);
+    }
+    drop(list);
+}
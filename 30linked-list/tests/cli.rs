@@ -0,0 +1,17 @@
+// Run the chapter's demo binary and check the parts of its output that are deterministic.
+use assert_cmd::Command;
+
+#[test]
+fn prints_the_demo() {
+    Command::cargo_bin("linked-list").unwrap().assert()
+        .success()
+        .stdout("\
+Hello, linked lists!
+Rc/Weak list, forwards:  [\"vive\", \"liberté\", \"égalité\", \"fraternité\"]
+Rc/Weak list, backwards: [\"fraternité\", \"égalité\", \"liberté\", \"vive\"]
+peek_front hands out a Ref guard: vive
+raw pointer list, forwards:  [1, 4, 9, 16, 25]
+raw pointer list, backwards: [25, 16, 9, 4, 1]
+pop_front Some(1), pop_back Some(25), 3 left
+");
+}
@@ -0,0 +1,82 @@
+// Our Mutex + Condvar channels against std::sync::mpsc, with several producers sending
+// to one consumer.
+//
+//   $ cargo bench
+//
+// std's channels are lock-free on the fast path, so expect them to win; the interesting
+// part is by how much, and how the bounded variants compare when producers must wait.
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use std::sync::mpsc;
+use std::thread;
+
+const PRODUCERS: usize = 4;
+const MESSAGES: usize = 10_000;
+
+// Each benchmark is the same shape: spawn producers, drain on this thread, join.
+fn ours(capacity: Option<usize>) -> usize {
+    let (sender, receiver) = match capacity {
+        Some(capacity) => channels_from_scratch::bounded(capacity),
+        None => channels_from_scratch::channel(),
+    };
+    let producers: Vec<_> = (0..PRODUCERS).map(|_| {
+        let sender = sender.clone();
+        thread::spawn(move || for i in 0..MESSAGES { sender.send(i).unwrap(); })
+    }).collect();
+    drop(sender);
+    let count = receiver.iter().count();
+    for producer in producers {
+        producer.join().unwrap();
+    }
+    count
+}
+
+fn std_unbounded() -> usize {
+    let (sender, receiver) = mpsc::channel();
+    let producers: Vec<_> = (0..PRODUCERS).map(|_| {
+        let sender = sender.clone();
+        thread::spawn(move || for i in 0..MESSAGES { sender.send(i).unwrap(); })
+    }).collect();
+    drop(sender);
+    let count = receiver.iter().count();
+    for producer in producers {
+        producer.join().unwrap();
+    }
+    count
+}
+
+fn std_bounded(capacity: usize) -> usize {
+    let (sender, receiver) = mpsc::sync_channel(capacity);
+    let producers: Vec<_> = (0..PRODUCERS).map(|_| {
+        let sender = sender.clone();
+        thread::spawn(move || for i in 0..MESSAGES { sender.send(i).unwrap(); })
+    }).collect();
+    drop(sender);
+    let count = receiver.iter().count();
+    for producer in producers {
+        producer.join().unwrap();
+    }
+    count
+}
+
+fn bench_channels(c: &mut Criterion) {
+    let mut group = c.benchmark_group("mpsc");
+    group.throughput(Throughput::Elements((PRODUCERS * MESSAGES) as u64));
+    group.sample_size(20);
+
+    group.bench_function("ours/unbounded", |b| b.iter(|| ours(None)));
+    group.bench_function("std/unbounded", |b| b.iter(std_unbounded));
+    for &capacity in &[1, 64] {
+        group.bench_with_input(BenchmarkId::new("ours/bounded", capacity), &capacity,
+                               |b, &capacity| b.iter(|| ours(Some(capacity))));
+        group.bench_with_input(BenchmarkId::new("std/bounded", capacity), &capacity,
+                               |b, &capacity| b.iter(|| std_bounded(capacity)));
+    }
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = rustfun_benches::criterion();
+    targets = bench_channels
+}
+criterion_main!(benches);
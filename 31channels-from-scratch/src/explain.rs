@@ -0,0 +1,46 @@
+// What `channels-from-scratch --explain` prints: the chapter's notes, in short.
+use rustfun_common::{Explanation, Text, Topic};
+
+pub const EXPLANATION: Explanation = Explanation {
+    chapter: "31channels-from-scratch",
+    summary: Text {
+        en: "channels built from a Mutex and a Condvar",
+        zh: "用 Mutex 和 Condvar 搭建的通道",
+    },
+    topics: &[
+        Topic {
+            title: Text { en: "Mutex and Condvar", zh: "Mutex 与 Condvar" },
+            body: Text {
+                en: "The Mutex protects the queue; a Condvar lets a thread sleep until it changes.\n\
+                     wait() releases the lock while asleep and takes it back before returning.",
+                zh: "Mutex 保护队列; Condvar 让线程睡眠直到队列变化。wait() 睡眠时释放锁,\n\
+                     返回前重新获得。",
+            },
+        },
+        Topic {
+            title: Text { en: "Spurious wakeups", zh: "虚假唤醒" },
+            body: Text {
+                en: "wait() may return for no reason, so each wait sits in a loop that checks its\n\
+                     condition again.",
+                zh: "wait() 可能无故返回, 所以每次等待都放在一个重新检查条件的循环里。",
+            },
+        },
+        Topic {
+            title: Text { en: "Disconnection", zh: "断开连接" },
+            body: Text {
+                en: "recv() fails once every Sender is gone and the queue is empty; send() fails\n\
+                     once the Receiver is gone, and hands the value back.",
+                zh: "所有 Sender 都消失且队列为空时 recv() 失败; Receiver 消失后 send() 失败,\n\
+                     并把值交还。",
+            },
+        },
+        Topic {
+            title: Text { en: "Backpressure", zh: "背压" },
+            body: Text {
+                en: "The bounded channel makes send() wait while the queue is full, slowing a fast\n\
+                     producer to the consumer's pace.",
+                zh: "有界通道在队列满时让 send() 等待, 把快速的生产者放慢到消费者的节奏。",
+            },
+        },
+    ],
+};
@@ -0,0 +1,336 @@
+//
+// A multi-producer, single-consumer channel from a Mutex and two Condvars.
+//
+//   Sender ---+                 Arc<Shared<T>>
+//   Sender ---+--> Mutex<State { items: VecDeque<T>, senders, receiver_alive }>
+//   Sender ---+         |  Condvar `ready`: "there is an item, or no senders are left"
+//                       |  Condvar `space`: "there is room, or the receiver is gone"
+//                       +--> Receiver
+//
+// Note:
+//
+// 1.) The Mutex protects the data; a Condvar lets a thread sleep until the data changes,
+//     instead of spinning on the lock. Condvar::wait releases the lock while asleep and
+//     takes it back before returning.
+// 2.) wait() may return for no reason at all (a "spurious wakeup"), so every wait sits in
+//     a loop that rechecks its condition.
+// 3.) A channel must also tell each side when the other side is gone: recv() fails once
+//     every Sender is dropped and the queue is empty, send() fails once the Receiver is
+//     dropped. That's what `senders` and `receiver_alive` are for.
+// 4.) The bounded variant adds one rule: send() waits while the queue holds `capacity`
+//     items. That is backpressure: a fast producer is slowed to the consumer's pace
+//     instead of filling memory.
+//
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+
+struct State<T> {
+    items: VecDeque<T>,
+    senders: usize,
+    receiver_alive: bool,
+}
+
+struct Shared<T> {
+    state: Mutex<State<T>>,
+    ready: Condvar,
+    space: Condvar,
+    capacity: Option<usize>,
+}
+
+impl<T> Shared<T> {
+    // 1.  A thread that panics while holding the lock "poisons" the Mutex. Our state is
+    //     only ever changed by short, panic-free sections, so it is still consistent, and
+    //     we just take the guard out of the PoisonError.
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// The sending half of a channel. Clone it to get more producers.
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+/// The receiving half of a channel.
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+/// send() failed because the Receiver is gone. The value is handed back.
+#[derive(PartialEq, Eq)]
+pub struct SendError<T>(pub T);
+
+/// recv() failed because every Sender is gone and the channel is empty.
+#[derive(Debug, PartialEq, Eq)]
+pub struct RecvError;
+
+// 2.  A manual Debug, so that SendError<T> is Debug even when T isn't, and `.unwrap()`
+//     works on any send() result.
+impl<T> fmt::Debug for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("SendError(..)")
+    }
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("sending on a channel whose receiver is gone")
+    }
+}
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("receiving on an empty channel whose senders are gone")
+    }
+}
+
+impl<T> std::error::Error for SendError<T> {}
+impl std::error::Error for RecvError {}
+
+fn new_channel<T>(capacity: Option<usize>) -> (Sender<T>, Receiver<T>) {
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State { items: VecDeque::new(), senders: 1, receiver_alive: true }),
+        ready: Condvar::new(),
+        space: Condvar::new(),
+        capacity,
+    });
+    (Sender { shared: Arc::clone(&shared) }, Receiver { shared })
+}
+
+/// Create an unbounded channel: send() never blocks.
+pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
+    new_channel(None)
+}
+
+/// Create a channel holding at most `capacity` items: send() blocks while it is full.
+///
+/// # Panics
+///
+/// Panics if `capacity` is zero.
+pub fn bounded<T>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(capacity > 0, "a bounded channel needs room for at least one item");
+    new_channel(Some(capacity))
+}
+
+impl<T> Sender<T> {
+    pub fn send(&self, value: T) -> Result<(), SendError<T>> {
+        let mut state = self.shared.lock();
+        loop {
+            if !state.receiver_alive {
+                return Err(SendError(value));
+            }
+            match self.shared.capacity {
+                Some(capacity) if state.items.len() >= capacity => {
+                    // 3.  wait() consumes the guard and gives back a new one, with the lock
+                    //     held again, once someone notifies `space`.
+                    state = self.shared.space.wait(state).unwrap_or_else(|p| p.into_inner());
+                }
+                _ => break,
+            }
+        }
+        state.items.push_back(value);
+        // 4.  There is only one receiver, so waking one waiter is enough.
+        self.shared.ready.notify_one();
+        Ok(())
+    }
+}
+
+// 5.  Cloning and dropping Senders keeps the count that lets recv() detect the end.
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Sender<T> {
+        self.shared.lock().senders += 1;
+        Sender { shared: Arc::clone(&self.shared) }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.senders -= 1;
+        if state.senders == 0 {
+            // the receiver may be asleep waiting for an item that will never come
+            self.shared.ready.notify_all();
+        }
+    }
+}
+
+impl<T> Receiver<T> {
+    /// Block until an item arrives, or fail once all Senders are gone.
+    pub fn recv(&self) -> Result<T, RecvError> {
+        let mut state = self.shared.lock();
+        loop {
+            if let Some(value) = state.items.pop_front() {
+                self.shared.space.notify_one();
+                return Ok(value);
+            }
+            if state.senders == 0 {
+                return Err(RecvError);
+            }
+            state = self.shared.ready.wait(state).unwrap_or_else(|p| p.into_inner());
+        }
+    }
+
+    /// Take an item if one is waiting, without blocking.
+    pub fn try_recv(&self) -> Option<T> {
+        let value = self.shared.lock().items.pop_front();
+        if value.is_some() {
+            self.shared.space.notify_one();
+        }
+        value
+    }
+
+    /// Iterate over received items until every Sender is gone.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { receiver: self }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.shared.lock().receiver_alive = false;
+        // every blocked sender has to wake up and see that nobody is listening
+        self.shared.space.notify_all();
+    }
+}
+
+pub struct Iter<'a, T: 'a> {
+    receiver: &'a Receiver<T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = T;
+    fn next(&mut self) -> Option<T> {
+        self.receiver.recv().ok()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Receiver<T> {
+    type Item = T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(test)]
+use std::thread;
+#[cfg(test)]
+use std::time::Duration;
+
+#[test]
+fn test_fifo_order() {
+    let (sender, receiver) = channel();
+    for i in 0..5 {
+        sender.send(i).unwrap();
+    }
+    drop(sender);
+    assert_eq!(receiver.iter().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+    assert_eq!(receiver.recv(), Err(RecvError));
+}
+
+#[test]
+fn test_many_producers() {
+    let (sender, receiver) = channel();
+    let producers: Vec<_> = (0..8u64).map(|p| {
+        let sender = sender.clone();
+        thread::spawn(move || {
+            for i in 0..1000 {
+                sender.send(p * 1000 + i).unwrap();
+            }
+        })
+    }).collect();
+    drop(sender);
+
+    // the loop ends by itself once the last producer's Sender is dropped
+    let mut received: Vec<u64> = receiver.iter().collect();
+    for producer in producers {
+        producer.join().unwrap();
+    }
+    received.sort();
+    assert_eq!(received, (0..8000).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_recv_wakes_on_disconnect() {
+    let (sender, receiver) = channel::<i32>();
+    let waiter = thread::spawn(move || receiver.recv());
+    thread::sleep(Duration::from_millis(20));
+    drop(sender);
+    assert_eq!(waiter.join().unwrap(), Err(RecvError));
+}
+
+#[test]
+fn test_send_fails_without_receiver() {
+    let (sender, receiver) = channel();
+    drop(receiver);
+    assert_eq!(sender.send("lost"), Err(SendError("lost")));
+}
+
+#[test]
+fn test_try_recv() {
+    let (sender, receiver) = channel();
+    assert_eq!(receiver.try_recv(), None);
+    sender.send('x').unwrap();
+    assert_eq!(receiver.try_recv(), Some('x'));
+}
+
+#[test]
+fn test_bounded_blocks_when_full() {
+    let (sender, receiver) = bounded(2);
+    sender.send(1).unwrap();
+    sender.send(2).unwrap();
+
+    let sent_third = std::sync::Arc::new(AtomicBool::new(false));
+    let flag = std::sync::Arc::clone(&sent_third);
+    let producer = thread::spawn(move || {
+        sender.send(3).unwrap();
+        flag.store(true, Ordering::SeqCst);
+    });
+
+    thread::sleep(Duration::from_millis(50));
+    assert!(!sent_third.load(Ordering::SeqCst), "send should wait while the channel is full");
+
+    assert_eq!(receiver.recv(), Ok(1)); // makes room
+    producer.join().unwrap();
+    assert!(sent_third.load(Ordering::SeqCst));
+    assert_eq!(receiver.iter().collect::<Vec<_>>(), vec![2, 3]);
+}
+
+#[test]
+fn test_bounded_sender_wakes_when_receiver_drops() {
+    let (sender, receiver) = bounded(1);
+    sender.send(1).unwrap();
+    let producer = thread::spawn(move || sender.send(2));
+    thread::sleep(Duration::from_millis(20));
+    drop(receiver);
+    assert_eq!(producer.join().unwrap(), Err(SendError(2)));
+}
+
+#[test]
+fn test_bounded_stress() {
+    // a tiny buffer forces producers and the consumer to hand off constantly
+    let (sender, receiver) = bounded(1);
+    let producers: Vec<_> = (0..4u32).map(|_| {
+        let sender = sender.clone();
+        thread::spawn(move || {
+            for i in 0..500 {
+                sender.send(i).unwrap();
+            }
+        })
+    }).collect();
+    drop(sender);
+    let total: u32 = receiver.iter().sum();
+    for producer in producers {
+        producer.join().unwrap();
+    }
+    assert_eq!(total, 4 * (0..500).sum::<u32>());
+}
+
+#[test]
+#[should_panic(expected = "at least one item")]
+fn test_zero_capacity() {
+    bounded::<()>(0);
+}
@@ -0,0 +1,54 @@
+use channels_from_scratch::{bounded, channel};
+use std::thread;
+use std::time::{Duration, Instant};
+
+mod explain;
+
+fn main() {
+    rustfun_common::init_logging();
+    explain::EXPLANATION.on_request();
+    println!("Hello, channels!");
+    let deterministic = rustfun_common::deterministic::is_on();
+
+    // 1.  Three producers, one consumer. The consumer's loop ends on its own when the
+    //     last Sender is dropped.
+    // 1.1 The producers' messages arrive interleaved however the threads were scheduled;
+    //     --deterministic sorts them before printing.
+    let (sender, receiver) = channel();
+    for id in 0..3 {
+        let sender = sender.clone();
+        thread::spawn(move || {
+            for i in 0..3 {
+                sender.send(format!("producer {} says {}", id, i)).unwrap();
+            }
+        });
+    }
+    drop(sender);
+    let mut messages: Vec<String> = receiver.into_iter().collect();
+    if deterministic {
+        messages.sort();
+    }
+    for message in messages {
+        println!("{}", message);
+    }
+
+    // 2.  Backpressure: with room for only 2 items, a producer that is much faster than
+    //     its consumer spends most of its time waiting in send(). --deterministic leaves
+    //     the times out; the consumer's 100ms naps keep the order of the lines.
+    let (sender, receiver) = bounded(2);
+    let start = Instant::now();
+    let stamp = move || {
+        if deterministic { String::new() } else { format!("{:>4}ms  ", start.elapsed().as_millis()) }
+    };
+    let producer = thread::spawn(move || {
+        for i in 0..5 {
+            sender.send(i).unwrap();
+            println!("{}sent {}", stamp(), i);
+        }
+    });
+    for value in &receiver {
+        thread::sleep(Duration::from_millis(100));
+        println!("{}consumed {}", stamp(), value);
+    }
+    producer.join().unwrap();
+}
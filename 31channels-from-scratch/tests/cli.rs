@@ -0,0 +1,48 @@
+// Run the chapter's demo binary and check the parts of its output that are deterministic.
+use assert_cmd::Command;
+
+// Producers run concurrently, so only each producer's own order is fixed.
+#[test]
+fn prints_the_demo() {
+    let output = Command::cargo_bin("channels-from-scratch").unwrap().output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("Hello, channels!\n"));
+    for producer in 0..3 {
+        let said: Vec<&str> = stdout.lines()
+            .filter(|line| line.starts_with(&format!("producer {} says", producer)))
+            .collect();
+        assert_eq!(said.len(), 3, "{}", stdout);
+        assert!(said.windows(2).all(|w| w[0] < w[1]), "{}", stdout);
+    }
+    assert!(stdout.trim_end().ends_with("consumed 4"), "{}", stdout);
+}
+
+// --deterministic sorts the producers' messages and leaves out the times.
+#[test]
+fn prints_the_same_every_run_when_deterministic() {
+    Command::cargo_bin("channels-from-scratch").unwrap().arg("--deterministic").assert()
+        .success()
+        .stdout("\
+Hello, channels!
+producer 0 says 0
+producer 0 says 1
+producer 0 says 2
+producer 1 says 0
+producer 1 says 1
+producer 1 says 2
+producer 2 says 0
+producer 2 says 1
+producer 2 says 2
+sent 0
+sent 1
+sent 2
+consumed 0
+sent 3
+consumed 1
+sent 4
+consumed 2
+consumed 3
+consumed 4
+");
+}
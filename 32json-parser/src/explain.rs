@@ -0,0 +1,44 @@
+// What `json-parser --explain` prints: the chapter's notes, in short.
+use rustfun_common::{Explanation, Text, Topic};
+
+pub const EXPLANATION: Explanation = Explanation {
+    chapter: "32json-parser",
+    summary: Text {
+        en: "a recursive-descent JSON parser and pretty-printer",
+        zh: "递归下降的 JSON 解析器和格式化输出",
+    },
+    topics: &[
+        Topic {
+            title: Text { en: "One enum for the data model", zh: "一个枚举表示数据模型" },
+            body: Text {
+                en: "Value has a variant for each JSON type; arrays and objects hold more Values,\n\
+                     so a document is a tree of them.",
+                zh: "Value 为每种 JSON 类型提供一个变体; 数组和对象包含更多 Value, 所以文档\n\
+                     就是一棵 Value 树。",
+            },
+        },
+        Topic {
+            title: Text { en: "Recursive descent", zh: "递归下降" },
+            body: Text {
+                en: "One function per grammar rule, each calling the others as the grammar says;\n\
+                     the call stack follows the nesting of the document.",
+                zh: "每条语法规则对应一个函数, 按语法相互调用; 调用栈跟随文档的嵌套。",
+            },
+        },
+        Topic {
+            title: Text { en: "Errors with positions", zh: "带位置的错误" },
+            body: Text {
+                en: "Anything outside RFC 8259 is an error that says the line and column.",
+                zh: "任何不符合 RFC 8259 的内容都是错误, 并给出行号和列号。",
+            },
+        },
+        Topic {
+            title: Text { en: "Display", zh: "Display" },
+            body: Text {
+                en: "Display prints compact JSON, so to_string() is a minimal document;\n\
+                     to_pretty() indents it.",
+                zh: "Display 打印紧凑的 JSON, 所以 to_string() 就是最小的文档; to_pretty() 负责缩进。",
+            },
+        },
+    ],
+};
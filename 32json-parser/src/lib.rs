@@ -0,0 +1,120 @@
+//
+// JSON without serde: a value enum, a recursive-descent parser, and a pretty-printer.
+//
+// Note:
+//
+// 1.) The whole JSON data model fits in one enum. Arrays and objects hold more Values,
+//     so the enum is recursive, and a parsed document is simply a tree of Values.
+// 2.) A recursive-descent parser has one function per grammar rule (value, object,
+//     array, string, number), each calling the others as the grammar says. The call
+//     stack mirrors the nesting of the document.
+// 3.) The grammar is RFC 8259, strictly: no trailing commas, no comments, no single
+//     quotes, no leading zeros, no NaN. Anything else is an error with a line and column.
+//
+use std::fmt;
+
+mod parser;
+mod printer;
+
+pub use crate::parser::{parse, ParseError};
+
+/// A JSON value.
+// 1.  Objects keep their members in document order, as a Vec of pairs. JSON doesn't forbid
+//     duplicate keys, and a Vec doesn't have to decide what they mean; `get` returns the
+//     last one, which is what most parsers do.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// Look up `key` in an object. Returns None for missing keys and non-objects.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match *self {
+            Value::Object(ref members) => {
+                members.iter().rev().find(|m| m.0 == key).map(|m| &m.1)
+            }
+            _ => None,
+        }
+    }
+
+    /// Index into an array. Returns None when out of range or not an array.
+    pub fn at(&self, index: usize) -> Option<&Value> {
+        match *self {
+            Value::Array(ref items) => items.get(index),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match *self {
+            Value::String(ref s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            Value::Number(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            Value::Bool(b) => Some(b),
+            _ => None,
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        *self == Value::Null
+    }
+
+    /// Render with newlines and `indent` spaces per nesting level.
+    pub fn to_pretty(&self, indent: usize) -> String {
+        let mut out = String::new();
+        printer::write_pretty(&mut out, self, indent, 0);
+        out
+    }
+}
+
+// 2.  Display is the compact form, so `value.to_string()` is a minimal JSON document.
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut out = String::new();
+        printer::write_compact(&mut out, self);
+        f.write_str(&out)
+    }
+}
+
+#[test]
+fn test_accessors() {
+    let doc = parse(r#"{"name": "Ferris", "legs": 10, "tags": ["crab", true], "x": null}"#)
+        .unwrap();
+    assert_eq!(doc.get("name").and_then(Value::as_str), Some("Ferris"));
+    assert_eq!(doc.get("legs").and_then(Value::as_f64), Some(10.0));
+    assert_eq!(doc.get("tags").and_then(|t| t.at(1)).and_then(Value::as_bool), Some(true));
+    assert!(doc.get("x").unwrap().is_null());
+    assert_eq!(doc.get("missing"), None);
+    assert_eq!(doc.at(0), None);
+}
+
+#[test]
+fn test_duplicate_keys_last_wins() {
+    let doc = parse(r#"{"a": 1, "a": 2}"#).unwrap();
+    assert_eq!(doc.get("a"), Some(&Value::Number(2.0)));
+}
+
+#[test]
+fn test_compact_round_trip() {
+    let text = r#"{"a":[1,2.5,-3e-7,true,false,null],"b":{"c":"line\nbreak \"quoted\""}}"#;
+    let doc = parse(text).unwrap();
+    assert_eq!(doc.to_string(), text);
+    assert_eq!(parse(&doc.to_string()).unwrap(), doc);
+}
@@ -0,0 +1,40 @@
+use rustfun_common::Args;
+use std::fs;
+use std::io::{self, Read};
+use std::process;
+
+// Pretty-print a JSON file, or stdin when no file is given.
+//
+mod explain;
+
+//   $ echo '{"a":[1,2,{"b":null}]}' | cargo run
+//   $ cargo run -- tests/fixtures/y_nested.json
+fn main() {
+    rustfun_common::init_logging();
+    explain::EXPLANATION.on_request();
+    let mut args = Args::from_env("json-parser [FILE]");
+    let file = args.optional();
+    args.finish().unwrap_or_else(|e| e.exit());
+    let text = match file {
+        None => {
+            let mut text = String::new();
+            io::stdin().read_to_string(&mut text).unwrap_or_else(|e| {
+                eprintln!("error reading stdin: {}", e);
+                process::exit(1);
+            });
+            text
+        }
+        Some(path) => fs::read_to_string(&path).unwrap_or_else(|e| {
+            eprintln!("error reading {}: {}", path, e);
+            process::exit(1);
+        }),
+    };
+
+    match json_parser::parse(&text) {
+        Ok(value) => println!("{}", value.to_pretty(2)),
+        Err(e) => {
+            eprintln!("invalid JSON: {}", e);
+            process::exit(2);
+        }
+    }
+}
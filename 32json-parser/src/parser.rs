@@ -0,0 +1,339 @@
+// The recursive-descent parser.
+//
+//   value  = ws ( object | array | string | number | "true" | "false" | "null" ) ws
+//   object = "{" ws [ string ws ":" value *( "," ws string ws ":" value ) ] "}"
+//   array  = "[" [ value *( "," value ) ] "]"
+//
+// Each rule below is one method on Parser. They all work on the input as bytes: every
+// character that means something to the JSON grammar is ASCII, and the only place
+// multi-byte UTF-8 can appear is inside strings, where we copy it through untouched.
+use std::error::Error;
+use std::fmt;
+use crate::Value;
+
+/// Where and why parsing failed. `line` and `column` count from 1.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at line {}, column {}", self.message, self.line, self.column)
+    }
+}
+
+impl Error for ParseError {}
+
+// 1.  Deeply nested input like "[[[[[[..." would recurse once per bracket and could
+//     overflow the stack, so nesting is capped. Real documents never come close.
+const MAX_DEPTH: usize = 256;
+
+struct Parser<'a> {
+    text: &'a str,
+    bytes: &'a [u8],
+    pos: usize,
+    depth: usize,
+}
+
+/// Parse a complete JSON document. Anything but whitespace after the value is an error.
+pub fn parse(text: &str) -> Result<Value, ParseError> {
+    let mut parser = Parser { text, bytes: text.as_bytes(), pos: 0, depth: 0 };
+    let value = parser.parse_value()?;
+    if parser.pos < parser.bytes.len() {
+        return Err(parser.error("unexpected trailing characters"));
+    }
+    Ok(value)
+}
+
+impl<'a> Parser<'a> {
+    // 2.  Errors carry a position. Counting lines only when an error happens keeps the
+    //     happy path free of bookkeeping.
+    fn error(&self, message: &str) -> ParseError {
+        let before = &self.text[..self.pos.min(self.text.len())];
+        let line = before.matches('\n').count() + 1;
+        let column = before.rsplit('\n').next().map_or(0, |l| l.chars().count()) + 1;
+        ParseError { message: message.to_string(), line, column }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).cloned()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ') | Some(b'\t') | Some(b'\n') | Some(b'\r') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), ParseError> {
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{}'", byte as char)))
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<Value, ParseError> {
+        self.skip_whitespace();
+        // 3.  One byte of lookahead decides which rule applies; that's what makes JSON
+        //     so easy to parse.
+        let value = match self.peek() {
+            None => return Err(self.error("unexpected end of input")),
+            Some(b'{') => self.parse_object()?,
+            Some(b'[') => self.parse_array()?,
+            Some(b'"') => Value::String(self.parse_string()?),
+            Some(b'-') | Some(b'0'..=b'9') => self.parse_number()?,
+            Some(b't') => self.parse_literal("true", Value::Bool(true))?,
+            Some(b'f') => self.parse_literal("false", Value::Bool(false))?,
+            Some(b'n') => self.parse_literal("null", Value::Null)?,
+            Some(_) => return Err(self.error("expected a value")),
+        };
+        self.skip_whitespace();
+        Ok(value)
+    }
+
+    fn parse_literal(&mut self, word: &str, value: Value) -> Result<Value, ParseError> {
+        if self.bytes[self.pos..].starts_with(word.as_bytes()) {
+            self.pos += word.len();
+            Ok(value)
+        } else {
+            Err(self.error("expected a value"))
+        }
+    }
+
+    fn enter(&mut self) -> Result<(), ParseError> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            Err(self.error("nesting too deep"))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<Value, ParseError> {
+        self.enter()?;
+        self.expect(b'[')?;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+        } else {
+            loop {
+                items.push(self.parse_value()?);
+                match self.peek() {
+                    Some(b',') => self.pos += 1,
+                    Some(b']') => { self.pos += 1; break; }
+                    _ => return Err(self.error("expected ',' or ']'")),
+                }
+            }
+        }
+        self.depth -= 1;
+        Ok(Value::Array(items))
+    }
+
+    fn parse_object(&mut self) -> Result<Value, ParseError> {
+        self.enter()?;
+        self.expect(b'{')?;
+        let mut members = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+        } else {
+            loop {
+                self.skip_whitespace();
+                if self.peek() != Some(b'"') {
+                    return Err(self.error("expected a string key"));
+                }
+                let key = self.parse_string()?;
+                self.skip_whitespace();
+                self.expect(b':')?;
+                let value = self.parse_value()?;
+                members.push((key, value));
+                match self.peek() {
+                    Some(b',') => self.pos += 1,
+                    Some(b'}') => { self.pos += 1; break; }
+                    _ => return Err(self.error("expected ',' or '}'")),
+                }
+            }
+        }
+        self.depth -= 1;
+        Ok(Value::Object(members))
+    }
+
+    // 4.  Numbers are checked against the JSON grammar by hand, since Rust's f64 parser
+    //     accepts things JSON doesn't ("+1", ".5", "1.", "inf", "007"). Once the text is
+    //     known to be valid, str::parse does the actual conversion.
+    fn parse_number(&mut self) -> Result<Value, ParseError> {
+        let start = self.pos;
+        if self.peek() == Some(b'-') {
+            self.pos += 1;
+        }
+        match self.peek() {
+            Some(b'0') => self.pos += 1,
+            Some(b'1'..=b'9') => self.skip_digits(),
+            _ => return Err(self.error("expected a digit")),
+        }
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            if !self.peek().is_some_and(|b| b.is_ascii_digit()) {
+                return Err(self.error("expected a digit after '.'"));
+            }
+            self.skip_digits();
+        }
+        if let Some(b'e') | Some(b'E') = self.peek() {
+            self.pos += 1;
+            if let Some(b'+') | Some(b'-') = self.peek() {
+                self.pos += 1;
+            }
+            if !self.peek().is_some_and(|b| b.is_ascii_digit()) {
+                return Err(self.error("expected a digit in the exponent"));
+            }
+            self.skip_digits();
+        }
+        let number: f64 = self.text[start..self.pos].parse()
+            .map_err(|_| self.error("invalid number"))?;
+        // "1e400" is valid JSON, but not representable as an f64
+        if number.is_infinite() {
+            return Err(self.error("number out of range"));
+        }
+        Ok(Value::Number(number))
+    }
+
+    fn skip_digits(&mut self) {
+        while self.peek().is_some_and(|b| b.is_ascii_digit()) {
+            self.pos += 1;
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, ParseError> {
+        self.expect(b'"')?;
+        let mut out = String::new();
+        loop {
+            // 5.  Copy the longest run of ordinary characters in one go. Slicing the &str
+            //     at these positions is safe: '"' and '\\' are ASCII, so they can never
+            //     be in the middle of a multi-byte UTF-8 sequence.
+            let run_start = self.pos;
+            while let Some(b) = self.peek() {
+                if b == b'"' || b == b'\\' || b < 0x20 {
+                    break;
+                }
+                self.pos += 1;
+            }
+            out.push_str(&self.text[run_start..self.pos]);
+
+            match self.peek() {
+                None => return Err(self.error("unterminated string")),
+                Some(b'"') => {
+                    self.pos += 1;
+                    return Ok(out);
+                }
+                Some(b'\\') => {
+                    self.pos += 1;
+                    let c = self.parse_escape()?;
+                    out.push(c);
+                }
+                Some(_) => return Err(self.error("control character in string")),
+            }
+        }
+    }
+
+    fn parse_escape(&mut self) -> Result<char, ParseError> {
+        let c = match self.peek() {
+            Some(b'"') => '"',
+            Some(b'\\') => '\\',
+            Some(b'/') => '/',
+            Some(b'b') => '\u{8}',
+            Some(b'f') => '\u{c}',
+            Some(b'n') => '\n',
+            Some(b'r') => '\r',
+            Some(b't') => '\t',
+            Some(b'u') => {
+                self.pos += 1;
+                return self.parse_unicode_escape();
+            }
+            _ => return Err(self.error("invalid escape")),
+        };
+        self.pos += 1;
+        Ok(c)
+    }
+
+    // 6.  \uXXXX escapes are UTF-16 code units. Characters outside the Basic Multilingual
+    //     Plane, like emoji, arrive as a surrogate pair "\ud83d\ude00" that has to be
+    //     combined; a lone surrogate is not a character at all, and char rejects it.
+    fn parse_unicode_escape(&mut self) -> Result<char, ParseError> {
+        let high = self.parse_hex4()?;
+        let code = if (0xD800..0xDC00).contains(&high) {
+            if !self.bytes[self.pos..].starts_with(b"\\u") {
+                return Err(self.error("unpaired surrogate in \\u escape"));
+            }
+            self.pos += 2;
+            let low = self.parse_hex4()?;
+            if !(0xDC00..0xE000).contains(&low) {
+                return Err(self.error("unpaired surrogate in \\u escape"));
+            }
+            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+        } else {
+            high
+        };
+        std::char::from_u32(code).ok_or_else(|| self.error("unpaired surrogate in \\u escape"))
+    }
+
+    fn parse_hex4(&mut self) -> Result<u32, ParseError> {
+        let digits = self.text.get(self.pos..self.pos + 4)
+            .filter(|d| d.bytes().all(|b| b.is_ascii_hexdigit()))
+            .ok_or_else(|| self.error("expected four hex digits"))?;
+        self.pos += 4;
+        Ok(u32::from_str_radix(digits, 16).expect("checked hex digits"))
+    }
+}
+
+#[test]
+fn test_scalars() {
+    assert_eq!(parse("null"), Ok(Value::Null));
+    assert_eq!(parse(" true "), Ok(Value::Bool(true)));
+    assert_eq!(parse("false"), Ok(Value::Bool(false)));
+    assert_eq!(parse("-0.5e2"), Ok(Value::Number(-50.0)));
+    assert_eq!(parse("\"hi\""), Ok(Value::String("hi".to_string())));
+}
+
+#[test]
+fn test_number_grammar() {
+    for bad in &["01", "+1", ".5", "1.", "1e", "1e+", "-", "0x10", "NaN", "Infinity", "1e400"] {
+        assert!(parse(bad).is_err(), "{:?} should be rejected", bad);
+    }
+    assert_eq!(parse("0"), Ok(Value::Number(0.0)));
+    assert_eq!(parse("1E+2"), Ok(Value::Number(100.0)));
+    assert_eq!(parse("123.456e-3"), Ok(Value::Number(0.123456)));
+}
+
+#[test]
+fn test_string_escapes() {
+    assert_eq!(parse(r#""a\"b\\c\/d\n\t""#), Ok(Value::String("a\"b\\c/d\n\t".to_string())));
+    assert_eq!(parse(r#""é字""#), Ok(Value::String("é字".to_string())));
+    assert_eq!(parse(r#""\ud83e\udd80""#), Ok(Value::String("🦀".to_string())));
+    assert_eq!(parse(r#""\u0041\u00e9""#), Ok(Value::String("Aé".to_string())));
+    assert!(parse(r#""\ud83e""#).is_err());
+    assert!(parse(r#""\udd80""#).is_err());
+    assert!(parse(r#""\x41""#).is_err());
+    assert!(parse("\"tab\there\"").is_err());
+}
+
+#[test]
+fn test_error_position() {
+    let err = parse("{\n  \"a\": [1, 2,]\n}").unwrap_err();
+    assert_eq!(err.message, "expected a value");
+    assert_eq!((err.line, err.column), (2, 14));
+    assert_eq!(err.to_string(), "expected a value at line 2, column 14");
+}
+
+#[test]
+fn test_nesting_limit() {
+    let deep = "[".repeat(MAX_DEPTH) + &"]".repeat(MAX_DEPTH);
+    assert!(parse(&deep).is_ok());
+    let too_deep = "[".repeat(MAX_DEPTH + 1) + &"]".repeat(MAX_DEPTH + 1);
+    assert_eq!(parse(&too_deep).unwrap_err().message, "nesting too deep");
+}
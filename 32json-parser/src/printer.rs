@@ -0,0 +1,139 @@
+// The printers: compact (used by Display) and pretty (newlines and indentation).
+//
+// Both are plain recursive functions appending to one String, following the shape of
+// the Value tree the same way the parser followed the shape of the text.
+use std::fmt::Write;
+use crate::Value;
+
+pub fn write_compact(out: &mut String, value: &Value) {
+    match *value {
+        Value::Array(ref items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_compact(out, item);
+            }
+            out.push(']');
+        }
+        Value::Object(ref members) => {
+            out.push('{');
+            for (i, (key, item)) in members.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_string(out, key);
+                out.push(':');
+                write_compact(out, item);
+            }
+            out.push('}');
+        }
+        ref scalar => write_scalar(out, scalar),
+    }
+}
+
+// 1.  Empty arrays and objects stay on one line; anything else puts each element on
+//     its own line, one level deeper.
+pub fn write_pretty(out: &mut String, value: &Value, indent: usize, level: usize) {
+    let pad = |out: &mut String, level: usize| {
+        for _ in 0..indent * level {
+            out.push(' ');
+        }
+    };
+    match *value {
+        Value::Array(ref items) if !items.is_empty() => {
+            out.push_str("[\n");
+            for (i, item) in items.iter().enumerate() {
+                pad(out, level + 1);
+                write_pretty(out, item, indent, level + 1);
+                out.push_str(if i + 1 < items.len() { ",\n" } else { "\n" });
+            }
+            pad(out, level);
+            out.push(']');
+        }
+        Value::Object(ref members) if !members.is_empty() => {
+            out.push_str("{\n");
+            for (i, (key, item)) in members.iter().enumerate() {
+                pad(out, level + 1);
+                write_string(out, key);
+                out.push_str(": ");
+                write_pretty(out, item, indent, level + 1);
+                out.push_str(if i + 1 < members.len() { ",\n" } else { "\n" });
+            }
+            pad(out, level);
+            out.push('}');
+        }
+        ref other => write_compact(out, other),
+    }
+}
+
+fn write_scalar(out: &mut String, value: &Value) {
+    match *value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if b { "true" } else { "false" }),
+        Value::Number(n) => write_number(out, n),
+        Value::String(ref s) => write_string(out, s),
+        Value::Array(_) | Value::Object(_) => unreachable!("not a scalar"),
+    }
+}
+
+// 2.  Whole numbers print without a fraction ("10", not "10.0"). Everything else uses
+//     f64's Debug format, which is the shortest text that parses back to the same f64
+//     and switches to exponent notation for very large or small magnitudes.
+fn write_number(out: &mut String, n: f64) {
+    if n.fract() == 0.0 && n.abs() < 1e15 {
+        write!(out, "{}", n as i64).unwrap();
+    } else {
+        write!(out, "{:?}", n).unwrap();
+    }
+}
+
+// 3.  Only '"', '\\' and control characters need escaping; all other Unicode is written
+//     as-is, since JSON text is UTF-8.
+fn write_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+#[test]
+fn test_pretty() {
+    let doc = crate::parse(r#"{"name":"rustfun","chapters":[1,2],"meta":{},"list":[]}"#).unwrap();
+    assert_eq!(doc.to_pretty(2), r#"{
+  "name": "rustfun",
+  "chapters": [
+    1,
+    2
+  ],
+  "meta": {},
+  "list": []
+}"#);
+}
+
+#[test]
+fn test_numbers() {
+    let print = |n| Value::Number(n).to_string();
+    assert_eq!(print(10.0), "10");
+    assert_eq!(print(-0.25), "-0.25");
+    assert_eq!(print(1e300), "1e300");
+    assert_eq!(print(1.5e-9), "1.5e-9");
+}
+
+#[test]
+fn test_string_escaping() {
+    let s = Value::String("tab\t \"q\" \\ \u{1} é".to_string());
+    assert_eq!(s.to_string(), r#""tab\t \"q\" \\ \u0001 é""#);
+}
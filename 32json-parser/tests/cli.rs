@@ -0,0 +1,52 @@
+// The pretty-printer binary: stdin or a file in, pretty JSON out, and an exit code that
+// tells "couldn't read it" (1) apart from "read it, but it isn't JSON" (2).
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+fn json_parser() -> Command {
+    let mut cmd = Command::cargo_bin("json-parser").unwrap();
+    cmd.current_dir(env!("CARGO_MANIFEST_DIR"));
+    cmd
+}
+
+#[test]
+fn pretty_prints_stdin() {
+    json_parser().write_stdin(r#"{"a":[1,2,{"b":null}]}"#).assert()
+        .success()
+        .stdout("{\n  \"a\": [\n    1,\n    2,\n    {\n      \"b\": null\n    }\n  ]\n}\n");
+}
+
+#[test]
+fn pretty_prints_a_file() {
+    json_parser().arg("tests/fixtures/y_literals.json").assert()
+        .success()
+        .stdout(predicate::str::contains("true"));
+}
+
+#[test]
+fn invalid_json_exits_2() {
+    json_parser().arg("tests/fixtures/n_missing_colon.json").assert()
+        .code(2)
+        .stdout("")
+        .stderr("invalid JSON: expected ':' at line 1, column 6\n");
+    json_parser().write_stdin("[1, 2,]").assert()
+        .code(2)
+        .stderr(predicate::str::starts_with("invalid JSON: "));
+}
+
+#[test]
+fn unreadable_input_exits_1() {
+    json_parser().arg("tests/fixtures/no-such-file.json").assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("error reading tests/fixtures/no-such-file.json: "));
+    json_parser().arg("tests/fixtures/n_invalid_utf8.json").assert()
+        .code(1)
+        .stderr(predicate::str::contains("stream did not contain valid UTF-8"));
+}
+
+#[test]
+fn usage_with_too_many_arguments() {
+    json_parser().args(["a.json", "b.json"]).assert()
+        .code(1)
+        .stderr("json-parser: unexpected argument \"b.json\"\nUsage: json-parser [FILE]\n");
+}
@@ -0,0 +1,67 @@
+// Conformance suite: every file in tests/fixtures is a test case, named by convention
+// (borrowed from the JSONTestSuite project):
+//
+//   y_*.json   must parse
+//   n_*.json   must be rejected
+//
+// Accepted documents must also survive a round trip through both printers.
+// To add a case, drop a file into the directory; no code changes needed.
+use json_parser::parse;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn fixtures(prefix: &str) -> Vec<PathBuf> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let mut paths: Vec<PathBuf> = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| {
+            let name = path.file_name().unwrap().to_str().unwrap();
+            name.starts_with(prefix) && name.ends_with(".json")
+        })
+        .collect();
+    paths.sort();
+    assert!(!paths.is_empty(), "no {}*.json fixtures in {}", prefix, dir.display());
+    paths
+}
+
+// Read as bytes and let a UTF-8 failure count as a rejection, so fixtures can hold
+// invalid encodings too.
+fn parse_file(path: &Path) -> Result<json_parser::Value, String> {
+    let bytes = fs::read(path).unwrap();
+    let text = String::from_utf8(bytes).map_err(|e| e.to_string())?;
+    parse(&text).map_err(|e| e.to_string())
+}
+
+#[test]
+fn accepts_valid_documents() {
+    let mut failures = Vec::new();
+    for path in fixtures("y_") {
+        if let Err(e) = parse_file(&path) {
+            failures.push(format!("{}: {}", path.display(), e));
+        }
+    }
+    assert!(failures.is_empty(), "valid documents rejected:\n{}", failures.join("\n"));
+}
+
+#[test]
+fn rejects_invalid_documents() {
+    let mut failures = Vec::new();
+    for path in fixtures("n_") {
+        if let Ok(value) = parse_file(&path) {
+            failures.push(format!("{}: parsed as {}", path.display(), value));
+        }
+    }
+    assert!(failures.is_empty(), "invalid documents accepted:\n{}", failures.join("\n"));
+}
+
+#[test]
+fn round_trips() {
+    for path in fixtures("y_") {
+        let value = parse_file(&path).unwrap();
+        let compact = value.to_string();
+        let pretty = value.to_pretty(4);
+        assert_eq!(parse(&compact).as_ref(), Ok(&value), "compact: {}", path.display());
+        assert_eq!(parse(&pretty).as_ref(), Ok(&value), "pretty: {}", path.display());
+    }
+}
@@ -0,0 +1,163 @@
+// The evaluator: walk the tree, looking variables up in an environment.
+use crate::parser::{BinOp, Expr, Statement};
+use std::collections::BTreeMap;
+use std::f64::consts;
+use crate::Error;
+
+/// Variables, plus `ans`, the result of the last line.
+pub struct Env {
+    vars: BTreeMap<String, f64>,
+}
+
+impl Default for Env {
+    fn default() -> Env {
+        Env::new()
+    }
+}
+
+impl Env {
+    /// A fresh environment holding only the constants `pi` and `e`.
+    pub fn new() -> Env {
+        let mut vars = BTreeMap::new();
+        vars.insert("pi".to_string(), consts::PI);
+        vars.insert("e".to_string(), consts::E);
+        Env { vars }
+    }
+
+    pub fn get(&self, name: &str) -> Option<f64> {
+        self.vars.get(name).cloned()
+    }
+
+    pub fn set(&mut self, name: &str, value: f64) {
+        self.vars.insert(name.to_string(), value);
+    }
+
+    /// All variables in name order.
+    pub fn vars(&self) -> impl Iterator<Item = (&str, f64)> {
+        self.vars.iter().map(|(k, v)| (k.as_str(), *v))
+    }
+
+    pub fn execute(&mut self, statement: &Statement) -> Result<f64, Error> {
+        let value = match *statement {
+            Statement::Assign(ref name, ref expr) => {
+                let value = self.eval(expr)?;
+                self.set(name, value);
+                value
+            }
+            Statement::Expr(ref expr) => self.eval(expr)?,
+        };
+        self.set("ans", value);
+        Ok(value)
+    }
+
+    // 1.  Evaluation is one match with a case per node type, recursing into children:
+    //     the tree's shape already encodes precedence, so there's nothing left to decide.
+    pub fn eval(&self, expr: &Expr) -> Result<f64, Error> {
+        match *expr {
+            Expr::Number(n) => Ok(n),
+            Expr::Var(ref name) => self.get(name).ok_or_else(|| Error::UnknownVariable(name.clone())),
+            Expr::Neg(ref e) => Ok(-self.eval(e)?),
+            Expr::Binary(op, ref l, ref r) => {
+                let (l, r) = (self.eval(l)?, self.eval(r)?);
+                match op {
+                    BinOp::Add => Ok(l + r),
+                    BinOp::Sub => Ok(l - r),
+                    BinOp::Mul => Ok(l * r),
+                    // 2.  f64 would happily return inf or NaN here; a calculator user
+                    //     would rather be told.
+                    BinOp::Div | BinOp::Rem if r == 0.0 => Err(Error::DivisionByZero),
+                    BinOp::Div => Ok(l / r),
+                    BinOp::Rem => Ok(l % r),
+                    BinOp::Pow => Ok(l.powf(r)),
+                }
+            }
+            Expr::Call(ref name, ref args) => {
+                let args = args.iter().map(|a| self.eval(a)).collect::<Result<Vec<f64>, Error>>()?;
+                call(name, &args)
+            }
+        }
+    }
+}
+
+fn call(name: &str, args: &[f64]) -> Result<f64, Error> {
+    let unary: Option<fn(f64) -> f64> = match name {
+        "sqrt" => Some(f64::sqrt),
+        "abs" => Some(f64::abs),
+        "ln" => Some(f64::ln),
+        "log10" => Some(f64::log10),
+        "exp" => Some(f64::exp),
+        "sin" => Some(f64::sin),
+        "cos" => Some(f64::cos),
+        "tan" => Some(f64::tan),
+        "floor" => Some(f64::floor),
+        "ceil" => Some(f64::ceil),
+        "round" => Some(f64::round),
+        _ => None,
+    };
+    if let Some(f) = unary {
+        return match *args {
+            [x] => Ok(f(x)),
+            _ => Err(Error::WrongArity { name: name.to_string(), expected: 1, got: args.len() }),
+        };
+    }
+    // 3.  min and max take any number of arguments, but at least one.
+    let fold: fn(f64, f64) -> f64 = match name {
+        "min" => f64::min,
+        "max" => f64::max,
+        _ => return Err(Error::UnknownFunction(name.to_string())),
+    };
+    match args.split_first() {
+        Some((&first, rest)) => Ok(rest.iter().fold(first, |acc, &x| fold(acc, x))),
+        None => Err(Error::WrongArity { name: name.to_string(), expected: 1, got: 0 }),
+    }
+}
+
+#[cfg(test)]
+fn eval_str(env: &Env, input: &str) -> Result<f64, Error> {
+    match crate::parse(input)? {
+        Statement::Expr(e) => env.eval(&e),
+        Statement::Assign(..) => panic!("not an expression"),
+    }
+}
+
+#[test]
+fn test_arithmetic() {
+    let env = Env::new();
+    assert_eq!(eval_str(&env, "1 + 2 * 3"), Ok(7.0));
+    assert_eq!(eval_str(&env, "(1 + 2) * 3"), Ok(9.0));
+    assert_eq!(eval_str(&env, "2 ^ 3 ^ 2"), Ok(512.0));
+    assert_eq!(eval_str(&env, "-2 ^ 2"), Ok(-4.0));
+    assert_eq!(eval_str(&env, "10 - 4 - 3"), Ok(3.0));
+    assert_eq!(eval_str(&env, "7 % 4"), Ok(3.0));
+    assert_eq!(eval_str(&env, "1e3 / 8"), Ok(125.0));
+}
+
+#[test]
+fn test_functions() {
+    let env = Env::new();
+    assert_eq!(eval_str(&env, "sqrt(16)"), Ok(4.0));
+    assert_eq!(eval_str(&env, "round(cos(pi))"), Ok(-1.0));
+    assert_eq!(eval_str(&env, "min(4, -2, 9)"), Ok(-2.0));
+    assert_eq!(eval_str(&env, "ln(e)"), Ok(1.0));
+    assert_eq!(eval_str(&env, "max()"),
+               Err(Error::WrongArity { name: "max".to_string(), expected: 1, got: 0 }));
+}
+
+#[test]
+fn test_variables_and_ans() {
+    let mut env = Env::new();
+    assert_eq!(env.execute(&crate::parse("x = 3").unwrap()), Ok(3.0));
+    assert_eq!(env.execute(&crate::parse("x * x").unwrap()), Ok(9.0));
+    assert_eq!(env.get("ans"), Some(9.0));
+    assert_eq!(env.execute(&crate::parse("x = x + ans").unwrap()), Ok(12.0));
+    assert_eq!(env.vars().map(|(name, _)| name).collect::<Vec<_>>(), vec!["ans", "e", "pi", "x"]);
+}
+
+#[test]
+fn test_failed_line_keeps_state() {
+    let mut env = Env::new();
+    env.execute(&crate::parse("x = 1").unwrap()).unwrap();
+    assert_eq!(env.execute(&crate::parse("x = 1 / 0").unwrap()), Err(Error::DivisionByZero));
+    assert_eq!(env.get("x"), Some(1.0));
+    assert_eq!(env.get("ans"), Some(1.0));
+}
@@ -0,0 +1,37 @@
+// What `calculator-repl --explain` prints: the chapter's notes, in short.
+use rustfun_common::{Explanation, Text, Topic};
+
+pub const EXPLANATION: Explanation = Explanation {
+    chapter: "33calculator-repl",
+    summary: Text {
+        en: "a calculator REPL with variables and functions",
+        zh: "支持变量和函数的计算器 REPL",
+    },
+    topics: &[
+        Topic {
+            title: Text { en: "Three stages", zh: "三个阶段" },
+            body: Text {
+                en: "The lexer turns text into tokens, the parser turns tokens into an Expr tree,\n\
+                     and the evaluator computes the tree with an environment of variables.",
+                zh: "词法分析器把文本变成记号, 语法分析器把记号变成 Expr 树, 求值器在变量环境中\n\
+                     计算这棵树。",
+            },
+        },
+        Topic {
+            title: Text { en: "A Pratt parser", zh: "Pratt 解析器" },
+            body: Text {
+                en: "Each operator has a binding power, and one loop handles every precedence\n\
+                     level.",
+                zh: "每个运算符有一个结合力, 一个循环就能处理所有优先级。",
+            },
+        },
+        Topic {
+            title: Text { en: "One error type", zh: "一个错误类型" },
+            body: Text {
+                en: "A single Error enum covers all three stages; syntax errors carry a byte\n\
+                     offset, so the REPL can point at the spot.",
+                zh: "一个 Error 枚举覆盖三个阶段; 语法错误带有字节偏移, REPL 可以指出出错的位置。",
+            },
+        },
+    ],
+};
@@ -0,0 +1,112 @@
+// The lexer: split the input into tokens, each tagged with its byte offset.
+use crate::Error;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token {
+    Number(f64),
+    Ident(String),
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Percent,
+    Caret,
+    LParen,
+    RParen,
+    Comma,
+    Equals,
+}
+
+pub fn tokenize(input: &str) -> Result<Vec<(Token, usize)>, Error> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+
+    while let Some(&(pos, c)) = chars.peek() {
+        // 1.  Single-character tokens are a table lookup; numbers and identifiers keep
+        //     consuming while the next character still belongs to them.
+        let simple = match c {
+            '+' => Some(Token::Plus),
+            '-' => Some(Token::Minus),
+            '*' => Some(Token::Star),
+            '/' => Some(Token::Slash),
+            '%' => Some(Token::Percent),
+            '^' => Some(Token::Caret),
+            '(' => Some(Token::LParen),
+            ')' => Some(Token::RParen),
+            ',' => Some(Token::Comma),
+            '=' => Some(Token::Equals),
+            _ => None,
+        };
+        if let Some(token) = simple {
+            tokens.push((token, pos));
+            chars.next();
+        } else if c.is_whitespace() {
+            chars.next();
+        } else if c.is_ascii_digit() || c == '.' {
+            let mut end = pos;
+            while let Some(&(i, c)) = chars.peek() {
+                if !(c.is_ascii_digit() || c == '.') {
+                    break;
+                }
+                end = i + 1;
+                chars.next();
+            }
+            // 2.  Scientific notation: "1e3", "2.5E-4". Only taken when the 'e' is
+            //     followed by digits, so "2e" lexes as the number 2 and the name e.
+            let rest = &input[end..];
+            let exponent = rest.strip_prefix(['e', 'E']).map(|r| {
+                let sign = if r.starts_with(['+', '-']) { 1 } else { 0 };
+                let digits = r[sign..].bytes().take_while(u8::is_ascii_digit).count();
+                if digits > 0 { 1 + sign + digits } else { 0 }
+            }).unwrap_or(0);
+            for _ in 0..exponent {
+                chars.next();
+            }
+            end += exponent;
+
+            let text = &input[pos..end];
+            let number = text.parse().map_err(|_| Error::syntax("malformed number", pos))?;
+            tokens.push((Token::Number(number), pos));
+        } else if c.is_alphabetic() || c == '_' {
+            let mut end = pos;
+            while let Some(&(i, c)) = chars.peek() {
+                if !(c.is_alphanumeric() || c == '_') {
+                    break;
+                }
+                end = i + c.len_utf8();
+                chars.next();
+            }
+            tokens.push((Token::Ident(input[pos..end].to_string()), pos));
+        } else {
+            return Err(Error::syntax(&format!("unexpected character '{}'", c), pos));
+        }
+    }
+    Ok(tokens)
+}
+
+#[test]
+fn test_tokenize() {
+    let tokens: Vec<Token> = tokenize("x1 = 2.5*(y_max - 1e3)").unwrap()
+        .into_iter().map(|(t, _)| t).collect();
+    assert_eq!(tokens, vec![
+        Token::Ident("x1".to_string()), Token::Equals, Token::Number(2.5), Token::Star,
+        Token::LParen, Token::Ident("y_max".to_string()), Token::Minus,
+        Token::Number(1000.0), Token::RParen,
+    ]);
+}
+
+#[test]
+fn test_tokenize_positions_and_errors() {
+    let positions: Vec<usize> = tokenize(" 12 +  ab").unwrap().into_iter().map(|(_, p)| p).collect();
+    assert_eq!(positions, vec![1, 4, 7]);
+    assert_eq!(tokenize("1 # 2"), Err(Error::syntax("unexpected character '#'", 2)));
+    assert_eq!(tokenize("1.2.3"), Err(Error::syntax("malformed number", 0)));
+}
+
+#[test]
+fn test_tokenize_exponent() {
+    let tokens = |s| tokenize(s).unwrap().into_iter().map(|(t, _)| t).collect::<Vec<_>>();
+    assert_eq!(tokens("2.5E-2"), vec![Token::Number(0.025)]);
+    assert_eq!(tokens("2e"), vec![Token::Number(2.0), Token::Ident("e".to_string())]);
+    assert_eq!(tokens("3e+"), vec![Token::Number(3.0), Token::Ident("e".to_string()), Token::Plus]);
+}
@@ -0,0 +1,90 @@
+//
+// A calculator in three stages: text -> tokens -> syntax tree -> number.
+//
+// Note:
+//
+// 1.) Each stage is its own module and only knows about the one before it. The lexer
+//     turns "2*(x+1)" into [Number(2), Star, LParen, Ident("x"), ...]; the parser turns
+//     tokens into an Expr tree; the evaluator walks the tree with an environment of
+//     variables.
+// 2.) The parser is a Pratt parser ("top-down operator precedence"): each operator has
+//     a binding power, and one loop handles every precedence level, instead of one
+//     recursive function per level as in a textbook recursive-descent grammar.
+// 3.) One Error enum covers all three stages. Syntax errors carry a byte offset, so the
+//     REPL can point at the spot with a caret.
+//
+use std::error::Error as StdError;
+use std::fmt;
+
+pub mod eval;
+pub mod lexer;
+pub mod parser;
+
+pub use crate::eval::Env;
+pub use crate::parser::{parse, Expr, Statement};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    Syntax { message: String, pos: usize },
+    UnknownVariable(String),
+    UnknownFunction(String),
+    WrongArity { name: String, expected: usize, got: usize },
+    DivisionByZero,
+}
+
+impl Error {
+    pub fn syntax(message: &str, pos: usize) -> Error {
+        Error::Syntax { message: message.to_string(), pos }
+    }
+
+    /// The byte offset in the input, for errors found before evaluation.
+    pub fn position(&self) -> Option<usize> {
+        match *self {
+            Error::Syntax { pos, .. } => Some(pos),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Syntax { ref message, pos } => write!(f, "{} at column {}", message, pos + 1),
+            Error::UnknownVariable(ref name) => write!(f, "unknown variable '{}'", name),
+            Error::UnknownFunction(ref name) => write!(f, "unknown function '{}'", name),
+            Error::WrongArity { ref name, expected, got } => {
+                write!(f, "{}() takes {} argument(s), got {}", name, expected, got)
+            }
+            Error::DivisionByZero => write!(f, "division by zero"),
+        }
+    }
+}
+
+impl StdError for Error {}
+
+/// Parse and run one line of input: an expression, or an assignment `name = expr`.
+/// Returns the value, which is also stored in `ans`.
+pub fn run_line(env: &mut Env, line: &str) -> Result<f64, Error> {
+    let statement = parse(line)?;
+    env.execute(&statement)
+}
+
+#[test]
+fn test_run_line() {
+    let mut env = Env::new();
+    assert_eq!(run_line(&mut env, "r = 2"), Ok(2.0));
+    assert_eq!(run_line(&mut env, "area = pi * r^2"), Ok(std::f64::consts::PI * 4.0));
+    assert_eq!(run_line(&mut env, "ans / pi"), Ok(4.0));
+    assert_eq!(run_line(&mut env, "max(r, 3, 1)"), Ok(3.0));
+}
+
+#[test]
+fn test_error_messages() {
+    let mut env = Env::new();
+    let message = |env: &mut Env, line| run_line(env, line).unwrap_err().to_string();
+    assert_eq!(message(&mut env, "1 +"), "expected an expression at column 4");
+    assert_eq!(message(&mut env, "y * 2"), "unknown variable 'y'");
+    assert_eq!(message(&mut env, "foo(1)"), "unknown function 'foo'");
+    assert_eq!(message(&mut env, "sqrt(1, 2)"), "sqrt() takes 1 argument(s), got 2");
+    assert_eq!(message(&mut env, "1 / (2 - 2)"), "division by zero");
+}
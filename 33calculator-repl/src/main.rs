@@ -0,0 +1,57 @@
+use calculator_repl::{run_line, Env};
+use std::io::{self, BufRead, Write};
+
+// A read-eval-print loop.
+//
+//   $ cargo run
+//   > r = 2
+//   2
+//   > pi * r ^ 2
+//   12.566370614359172
+//   > 2 * (r +
+//            ^ expected an expression at column 9
+//
+mod explain;
+
+// Commands: `:vars` lists variables, `:quit` (or end of input) exits.
+fn main() {
+    rustfun_common::init_logging();
+    explain::EXPLANATION.on_request();
+    let mut env = Env::new();
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+
+    loop {
+        print!("> ");
+        io::stdout().flush().unwrap();
+        let line = match lines.next() {
+            Some(Ok(line)) => line,
+            Some(Err(e)) => {
+                eprintln!("error reading input: {}", e);
+                break;
+            }
+            None => break,
+        };
+
+        match line.trim() {
+            "" => continue,
+            ":quit" | ":q" => break,
+            ":vars" => {
+                for (name, value) in env.vars() {
+                    println!("{} = {}", name, value);
+                }
+                continue;
+            }
+            _ => {}
+        }
+
+        match run_line(&mut env, &line) {
+            Ok(value) => println!("{}", value),
+            // 1.  Point at syntax errors, lined up under the prompt's "> ".
+            Err(e) => match e.position() {
+                Some(pos) => println!("  {}^ {}", " ".repeat(line[..pos].chars().count()), e),
+                None => println!("error: {}", e),
+            },
+        }
+    }
+}
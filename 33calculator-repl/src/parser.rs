@@ -0,0 +1,314 @@
+// The parser: tokens -> syntax tree, by Pratt parsing.
+//
+// Every binary operator gets a pair of binding powers (left, right). To parse an
+// expression at minimum power `min_bp`, read one operand, then keep absorbing operators
+// whose left power is at least `min_bp`, parsing each right-hand side at the operator's
+// right power. Left associativity is right > left; right associativity is left > right.
+//
+//   operator      left  right
+//   + -            1     2
+//   * / %          3     4
+//   unary -        -     5
+//   ^              8     7      (right-assoc, and tighter than unary minus: -2^2 = -4)
+use crate::lexer::{tokenize, Token};
+use std::fmt;
+use crate::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Pow,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Number(f64),
+    Var(String),
+    Neg(Box<Expr>),
+    Binary(BinOp, Box<Expr>, Box<Expr>),
+    Call(String, Vec<Expr>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Statement {
+    Assign(String, Expr),
+    Expr(Expr),
+}
+
+impl BinOp {
+    fn binding_power(self) -> (u8, u8) {
+        match self {
+            BinOp::Add | BinOp::Sub => (1, 2),
+            BinOp::Mul | BinOp::Div | BinOp::Rem => (3, 4),
+            BinOp::Pow => (8, 7),
+        }
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            BinOp::Add => "+",
+            BinOp::Sub => "-",
+            BinOp::Mul => "*",
+            BinOp::Div => "/",
+            BinOp::Rem => "%",
+            BinOp::Pow => "^",
+        }
+    }
+}
+
+const PREFIX_MINUS_BP: u8 = 5;
+
+// 4.  Every "(", unary minus and right-hand side of ^ parses its operand with a call of
+//     its own, so "((((((..." would recurse once per bracket and overflow the stack.
+//     Nesting is capped instead, as the JSON parser does; real lines never come close.
+const MAX_DEPTH: usize = 256;
+
+fn infix(token: &Token) -> Option<BinOp> {
+    match *token {
+        Token::Plus => Some(BinOp::Add),
+        Token::Minus => Some(BinOp::Sub),
+        Token::Star => Some(BinOp::Mul),
+        Token::Slash => Some(BinOp::Div),
+        Token::Percent => Some(BinOp::Rem),
+        Token::Caret => Some(BinOp::Pow),
+        _ => None,
+    }
+}
+
+// 1.  Display prints the tree fully parenthesized, which makes the precedence decisions
+//     visible: "1 + 2 * 3" shows as "(1 + (2 * 3))". The tests lean on this heavily.
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Expr::Number(n) => write!(f, "{}", n),
+            Expr::Var(ref name) => write!(f, "{}", name),
+            Expr::Neg(ref e) => write!(f, "(-{})", e),
+            Expr::Binary(op, ref l, ref r) => write!(f, "({} {} {})", l, op.symbol(), r),
+            Expr::Call(ref name, ref args) => {
+                write!(f, "{}(", name)?;
+                for (i, arg) in args.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", arg)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+impl fmt::Display for Statement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Statement::Assign(ref name, ref e) => write!(f, "{} = {}", name, e),
+            Statement::Expr(ref e) => write!(f, "{}", e),
+        }
+    }
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    index: usize,
+    end: usize,
+    depth: usize,
+}
+
+/// Parse one line: either `name = expr` or a bare expression.
+pub fn parse(input: &str) -> Result<Statement, Error> {
+    let mut parser = Parser { tokens: tokenize(input)?, index: 0, end: input.len(), depth: 0 };
+
+    // 2.  Assignment is recognised up front with two tokens of lookahead, rather than
+    //     being an operator: it only makes sense once, at the start of a line.
+    let statement = match (parser.peek(), parser.tokens.get(1).map(|t| &t.0)) {
+        (Some(Token::Ident(name)), Some(Token::Equals)) => {
+            let name = name.clone();
+            parser.index = 2;
+            Statement::Assign(name, parser.expression(0)?)
+        }
+        _ => Statement::Expr(parser.expression(0)?),
+    };
+
+    match parser.peek() {
+        None => Ok(statement),
+        Some(&Token::RParen) => Err(Error::syntax("unmatched ')'", parser.pos())),
+        Some(_) => Err(Error::syntax("expected an operator", parser.pos())),
+    }
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index).map(|t| &t.0)
+    }
+
+    fn pos(&self) -> usize {
+        self.tokens.get(self.index).map_or(self.end, |t| t.1)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.index).map(|t| t.0.clone());
+        self.index += 1;
+        token
+    }
+
+    fn expect(&mut self, token: Token, message: &str) -> Result<(), Error> {
+        if self.peek() == Some(&token) {
+            self.index += 1;
+            Ok(())
+        } else {
+            Err(Error::syntax(message, self.pos()))
+        }
+    }
+
+    fn expression(&mut self, min_bp: u8) -> Result<Expr, Error> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(Error::syntax("nested too deeply", self.pos()));
+        }
+        let mut lhs = self.operand()?;
+
+        // 3.  The heart of the Pratt parser. A weaker operator than `min_bp` belongs to
+        //     a caller further up the stack, so stop and hand `lhs` back to it.
+        while let Some(op) = self.peek().and_then(infix) {
+            let (left_bp, right_bp) = op.binding_power();
+            if left_bp < min_bp {
+                break;
+            }
+            self.index += 1;
+            let rhs = self.expression(right_bp)?;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs));
+        }
+        self.depth -= 1;
+        Ok(lhs)
+    }
+
+    fn operand(&mut self) -> Result<Expr, Error> {
+        let pos = self.pos();
+        match self.next() {
+            Some(Token::Number(n)) => Ok(Expr::Number(n)),
+            Some(Token::Minus) => {
+                let operand = self.expression(PREFIX_MINUS_BP)?;
+                Ok(Expr::Neg(Box::new(operand)))
+            }
+            Some(Token::LParen) => {
+                let inner = self.expression(0)?;
+                self.expect(Token::RParen, "expected ')'")?;
+                Ok(inner)
+            }
+            Some(Token::Ident(name)) => {
+                if self.peek() == Some(&Token::LParen) {
+                    self.index += 1;
+                    let args = self.arguments()?;
+                    Ok(Expr::Call(name, args))
+                } else {
+                    Ok(Expr::Var(name))
+                }
+            }
+            _ => Err(Error::syntax("expected an expression", pos)),
+        }
+    }
+
+    fn arguments(&mut self) -> Result<Vec<Expr>, Error> {
+        let mut args = Vec::new();
+        if self.peek() == Some(&Token::RParen) {
+            self.index += 1;
+            return Ok(args);
+        }
+        loop {
+            args.push(self.expression(0)?);
+            match self.peek() {
+                Some(&Token::Comma) => self.index += 1,
+                Some(&Token::RParen) => {
+                    self.index += 1;
+                    return Ok(args);
+                }
+                _ => return Err(Error::syntax("expected ',' or ')'", self.pos())),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+fn shape(input: &str) -> String {
+    parse(input).unwrap().to_string()
+}
+
+#[test]
+fn test_precedence() {
+    assert_eq!(shape("1 + 2 * 3"), "(1 + (2 * 3))");
+    assert_eq!(shape("1 * 2 + 3"), "((1 * 2) + 3)");
+    assert_eq!(shape("1 + 2 % 3 - 4 / 5"), "((1 + (2 % 3)) - (4 / 5))");
+    assert_eq!(shape("2 * 3 ^ 2"), "(2 * (3 ^ 2))");
+}
+
+#[test]
+fn test_associativity() {
+    assert_eq!(shape("1 - 2 - 3"), "((1 - 2) - 3)");
+    assert_eq!(shape("8 / 4 / 2"), "((8 / 4) / 2)");
+    assert_eq!(shape("2 ^ 3 ^ 2"), "(2 ^ (3 ^ 2))");
+}
+
+#[test]
+fn test_unary_minus() {
+    assert_eq!(shape("-2 ^ 2"), "(-(2 ^ 2))");
+    assert_eq!(shape("2 ^ -1"), "(2 ^ (-1))");
+    assert_eq!(shape("--x"), "(-(-x))");
+    assert_eq!(shape("-x * y"), "((-x) * y)");
+    assert_eq!(shape("1 - -1"), "(1 - (-1))");
+}
+
+#[test]
+fn test_parentheses() {
+    assert_eq!(shape("(1 + 2) * 3"), "((1 + 2) * 3)");
+    assert_eq!(shape("((((x))))"), "x");
+    assert_eq!(shape("-(1 + 2)"), "(-(1 + 2))");
+}
+
+#[test]
+fn test_calls() {
+    assert_eq!(shape("sqrt(2)"), "sqrt(2)");
+    assert_eq!(shape("max(1, 2 + 3, -x)"), "max(1, (2 + 3), (-x))");
+    assert_eq!(shape("f()"), "f()");
+    assert_eq!(shape("sin(x) ^ 2 + cos(x) ^ 2"), "((sin(x) ^ 2) + (cos(x) ^ 2))");
+    assert_eq!(shape("f(g(1), h(2, 3))"), "f(g(1), h(2, 3))");
+}
+
+#[test]
+fn test_assignment() {
+    assert_eq!(parse("x = 1 + 2"), Ok(Statement::Assign("x".to_string(), Expr::Binary(
+        BinOp::Add, Box::new(Expr::Number(1.0)), Box::new(Expr::Number(2.0))))));
+    assert_eq!(shape("x"), "x");
+    assert_eq!(shape("x + y"), "(x + y)");
+    assert_eq!(parse("x = y = 1").unwrap_err(), Error::syntax("expected an operator", 6));
+    assert_eq!(parse("1 = 2").unwrap_err(), Error::syntax("expected an operator", 2));
+}
+
+#[test]
+fn test_syntax_errors() {
+    let error = |input| parse(input).unwrap_err();
+    assert_eq!(error(""), Error::syntax("expected an expression", 0));
+    assert_eq!(error("1 +"), Error::syntax("expected an expression", 3));
+    assert_eq!(error("* 2"), Error::syntax("expected an expression", 0));
+    assert_eq!(error("(1 + 2"), Error::syntax("expected ')'", 6));
+    assert_eq!(error("1 + 2)"), Error::syntax("unmatched ')'", 5));
+    assert_eq!(error("1 2"), Error::syntax("expected an operator", 2));
+    assert_eq!(error("max(1 2)"), Error::syntax("expected ',' or ')'", 6));
+    assert_eq!(error("max(1,)"), Error::syntax("expected an expression", 6));
+    assert_eq!(error("()"), Error::syntax("expected an expression", 1));
+}
+
+#[test]
+fn test_nesting() {
+    let nested = |open: &str, n: usize| format!("{}1", open.repeat(n));
+    assert_eq!(parse(&format!("{}{}", nested("(", 200), ")".repeat(200))), Ok(Statement::Expr(Expr::Number(1.0))));
+    assert_eq!(parse(&nested("(", 10_000)).unwrap_err(), Error::syntax("nested too deeply", 256));
+    assert!(parse(&nested("-", 200)).is_ok());
+    assert_eq!(parse(&nested("-", 10_000)).unwrap_err(), Error::syntax("nested too deeply", 256));
+    assert_eq!(parse(&nested("2 ^ ", 10_000)).unwrap_err().position(), Some(1024));
+    // depth is how deep, not how many: a long line of siblings is fine
+    assert!(parse(&vec!["(1)"; 1000].join(" + ")).is_ok());
+}
@@ -0,0 +1,52 @@
+// Drive the REPL through stdin, as if typed, and check the whole transcript.
+use assert_cmd::Command;
+
+fn session(input: &str) -> String {
+    let output = Command::cargo_bin("calculator-repl").unwrap().write_stdin(input).output().unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn evaluates_lines_and_keeps_variables() {
+    // every prompt is "> " with no newline, so answers follow on the same line
+    assert_eq!(session("r = 2\npi * r ^ 2\nans / pi\n"),
+               "> 2\n> 12.566370614359172\n> 4\n> ");
+}
+
+#[test]
+fn reports_errors_and_carries_on() {
+    assert_eq!(session("2 * (r +\nfoo(1)\n1 / 0\n6 * 7\n"), "\
+>           ^ expected an expression at column 9
+> error: unknown function 'foo'
+> error: division by zero
+> 42
+> ");
+}
+
+#[test]
+fn lists_variables_and_quits() {
+    assert_eq!(session("x = 1\n:vars\n:quit\n2 + 2\n"), "\
+> 1
+> ans = 1
+e = 2.718281828459045
+pi = 3.141592653589793
+x = 1
+> ");
+}
+
+#[test]
+fn ends_at_end_of_input() {
+    assert_eq!(session(""), "> ");
+    assert_eq!(session("\n\n"), "> > > ");
+}
+
+#[test]
+fn explains_without_reading_input() {
+    let output = Command::cargo_bin("calculator-repl").unwrap().arg("--explain")
+        .write_stdin("1 + 1\n").output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("33calculator-repl: a calculator REPL"), "{}", stdout);
+    assert!(!stdout.contains("> "), "{}", stdout);
+}
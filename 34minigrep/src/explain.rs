@@ -0,0 +1,36 @@
+// What `minigrep --explain` prints: the chapter's notes, in short.
+use rustfun_common::{Explanation, Text, Topic};
+
+pub const EXPLANATION: Explanation = Explanation {
+    chapter: "34minigrep",
+    summary: Text {
+        en: "grep, smaller: search files for a pattern",
+        zh: "小号的 grep: 在文件中搜索模式",
+    },
+    topics: &[
+        Topic {
+            title: Text { en: "Split like grep", zh: "像 grep 一样拆分" },
+            body: Text {
+                en: "Parse the command line into a Config, find the files, search each line by\n\
+                     line, and print. Only the last step touches the terminal.",
+                zh: "把命令行解析成 Config, 找到文件, 逐行搜索, 然后打印。只有最后一步接触终端。",
+            },
+        },
+        Topic {
+            title: Text { en: "Writing through a trait", zh: "通过 trait 输出" },
+            body: Text {
+                en: "Colors go through termcolor's WriteColor, so the same code writes to a\n\
+                     terminal, a pipe, or a buffer in the tests.",
+                zh: "颜色通过 termcolor 的 WriteColor 输出, 同一段代码可以写到终端、管道, 或者\n\
+                     测试中的缓冲区。",
+            },
+        },
+        Topic {
+            title: Text { en: "Exit status", zh: "退出状态" },
+            body: Text {
+                en: "0 if any line matched, 1 if none did, 2 on an error, as grep does.",
+                zh: "和 grep 一样: 有匹配的行为 0, 没有为 1, 出错为 2。",
+            },
+        },
+    ],
+};
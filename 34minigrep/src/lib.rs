@@ -0,0 +1,233 @@
+//
+// minigrep: search files for lines matching a regular expression.
+//
+// Note:
+//
+// 1.) The work is split the way grep's is: parse the command line into a Config, find
+//     the files (walking directories recursively), search each one line by line, and
+//     print. Only the last step touches the terminal, so the rest is easy to test.
+// 2.) Colors go through termcolor's WriteColor trait rather than raw ANSI escapes, so
+//     the same printing code writes plain text to a pipe or a Buffer in tests.
+// 3.) Exit status follows grep: 0 if any line was selected, 1 if none, 2 on error.
+//
+use regex::{Regex, RegexBuilder};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use termcolor::{Color, ColorChoice, ColorSpec, WriteColor};
+use walkdir::WalkDir;
+
+pub const USAGE: &str = "\
+Usage: minigrep [OPTIONS] PATTERN [PATH ...]
+
+Search PATHs (default: the current directory) for lines matching PATTERN.
+Directories are searched recursively.
+
+Options:
+  -c, --count          print only the number of selected lines per file
+  -v, --invert         select lines that do NOT match
+  -i, --ignore-case    case-insensitive matching
+      --color WHEN     always, never or auto (default)
+  -h, --help           show this message";
+
+pub struct Config {
+    pub pattern: Regex,
+    pub paths: Vec<PathBuf>,
+    pub count: bool,
+    pub invert: bool,
+    pub color: ColorChoice,
+}
+
+impl Config {
+    /// Parse the arguments after the program name.
+    /// Returns Ok(None) when help was requested.
+    pub fn from_args<I: IntoIterator<Item = String>>(args: I) -> Result<Option<Config>, String> {
+        let mut count = false;
+        let mut invert = false;
+        let mut ignore_case = false;
+        let mut color = ColorChoice::Auto;
+        let mut positional = Vec::new();
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-c" | "--count" => count = true,
+                "-v" | "--invert" => invert = true,
+                "-i" | "--ignore-case" => ignore_case = true,
+                "-h" | "--help" => return Ok(None),
+                "--color" => {
+                    let when = args.next().ok_or("--color needs a value")?;
+                    color = parse_color(&when)?;
+                }
+                _ if arg.starts_with("--color=") => color = parse_color(&arg["--color=".len()..])?,
+                // 1.  "--" ends the options, so patterns that start with '-' can be searched.
+                "--" => {
+                    positional.extend(args.by_ref());
+                }
+                _ if arg.starts_with('-') && arg.len() > 1 => {
+                    return Err(format!("unknown option '{}'", arg));
+                }
+                _ => positional.push(arg),
+            }
+        }
+
+        let mut positional = positional.into_iter();
+        let pattern = positional.next().ok_or("missing PATTERN")?;
+        let pattern = RegexBuilder::new(&pattern)
+            .case_insensitive(ignore_case)
+            .build()
+            .map_err(|e| e.to_string())?;
+        let mut paths: Vec<PathBuf> = positional.map(PathBuf::from).collect();
+        if paths.is_empty() {
+            paths.push(PathBuf::from("."));
+        }
+        Ok(Some(Config { pattern, paths, count, invert, color }))
+    }
+}
+
+fn parse_color(when: &str) -> Result<ColorChoice, String> {
+    match when {
+        "always" => Ok(ColorChoice::Always),
+        "never" => Ok(ColorChoice::Never),
+        "auto" => Ok(ColorChoice::Auto),
+        _ => Err(format!("--color must be always, never or auto, not '{}'", when)),
+    }
+}
+
+/// The selected lines of `text`, with 1-based line numbers.
+pub fn search<'a>(pattern: &Regex, text: &'a str, invert: bool) -> Vec<(usize, &'a str)> {
+    text.lines()
+        .enumerate()
+        .filter(|&(_, line)| pattern.is_match(line) != invert)
+        .map(|(i, line)| (i + 1, line))
+        .collect()
+}
+
+#[test]
+fn test_search() {
+    let text = "one fish\ntwo fish\nred fish\nblue whale";
+    let pattern = Regex::new(r"^\w{3} fish").unwrap();
+    assert_eq!(search(&pattern, text, false), vec![(1, "one fish"), (2, "two fish"), (3, "red fish")]);
+    assert_eq!(search(&pattern, text, true), vec![(4, "blue whale")]);
+    assert_eq!(search(&pattern, "", false), vec![]);
+}
+
+// 2.  Every file under each path, sorted so the output doesn't depend on the order the
+//     filesystem happens to return directory entries in.
+pub fn files(paths: &[PathBuf]) -> Vec<Result<PathBuf, walkdir::Error>> {
+    let mut out = Vec::new();
+    for path in paths {
+        for entry in WalkDir::new(path).sort_by_file_name() {
+            match entry {
+                Ok(entry) => {
+                    if entry.file_type().is_file() {
+                        out.push(Ok(entry.into_path()));
+                    }
+                }
+                Err(e) => out.push(Err(e)),
+            }
+        }
+    }
+    out
+}
+
+/// Search everything in `config` and print results to `out`.
+/// Returns whether any line was selected.
+pub fn run<W: WriteColor>(config: &Config, out: &mut W) -> io::Result<bool> {
+    // 3.  Like grep, show file names only when more than one file could be involved.
+    let with_names = config.paths.len() > 1 || config.paths.iter().any(|p| p.is_dir());
+    let mut any = false;
+    for file in files(&config.paths) {
+        let path = match file {
+            Ok(path) => path,
+            Err(e) => {
+                log::warn!("{}", e);
+                continue;
+            }
+        };
+        // Binary or otherwise non-UTF-8 files are skipped, not fatal: a warning that
+        // RUST_LOG=error silences, like grep -s.
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) => {
+                log::warn!("{}: {}", path.display(), e);
+                continue;
+            }
+        };
+        let selected = search(&config.pattern, &text, config.invert);
+        any |= !selected.is_empty();
+
+        if config.count {
+            if with_names {
+                print_name(out, &path)?;
+                write!(out, ":")?;
+            }
+            writeln!(out, "{}", selected.len())?;
+            continue;
+        }
+        for (number, line) in selected {
+            if with_names {
+                print_name(out, &path)?;
+                write!(out, ":")?;
+            }
+            out.set_color(ColorSpec::new().set_fg(Some(Color::Green)))?;
+            write!(out, "{}", number)?;
+            out.reset()?;
+            write!(out, ":")?;
+            if config.invert {
+                writeln!(out, "{}", line)?;
+            } else {
+                print_highlighted(out, &config.pattern, line)?;
+            }
+        }
+    }
+    Ok(any)
+}
+
+fn print_name<W: WriteColor>(out: &mut W, path: &Path) -> io::Result<()> {
+    out.set_color(ColorSpec::new().set_fg(Some(Color::Magenta)))?;
+    write!(out, "{}", path.display())?;
+    out.reset()
+}
+
+fn print_highlighted<W: WriteColor>(out: &mut W, pattern: &Regex, line: &str) -> io::Result<()> {
+    let mut last = 0;
+    for m in pattern.find_iter(line) {
+        write!(out, "{}", &line[last..m.start()])?;
+        out.set_color(ColorSpec::new().set_fg(Some(Color::Red)).set_bold(true))?;
+        write!(out, "{}", m.as_str())?;
+        out.reset()?;
+        last = m.end();
+    }
+    writeln!(out, "{}", &line[last..])
+}
+
+#[cfg(test)]
+fn config(args: &[&str]) -> Config {
+    Config::from_args(args.iter().map(|s| s.to_string())).unwrap().unwrap()
+}
+
+#[test]
+fn test_from_args() {
+    let c = config(&["-c", "-v", "fn", "src", "tests"]);
+    assert!(c.count && c.invert);
+    assert_eq!(c.pattern.as_str(), "fn");
+    assert_eq!(c.paths, vec![PathBuf::from("src"), PathBuf::from("tests")]);
+    assert_eq!(config(&["x"]).paths, vec![PathBuf::from(".")]);
+    assert_eq!(config(&["--", "-v"]).pattern.as_str(), "-v");
+    assert!(config(&["-i", "HELLO"]).pattern.is_match("hello"));
+
+    let err = |args: &[&str]| Config::from_args(args.iter().map(|s| s.to_string())).err().unwrap();
+    assert_eq!(err(&[]), "missing PATTERN");
+    assert_eq!(err(&["--nope", "x"]), "unknown option '--nope'");
+    assert_eq!(err(&["--color=pink", "x"]), "--color must be always, never or auto, not 'pink'");
+    assert!(Config::from_args(vec!["--help".to_string()]).unwrap().is_none());
+}
+
+#[test]
+fn test_highlight_escapes() {
+    let mut buffer = termcolor::Buffer::ansi();
+    print_highlighted(&mut buffer, &Regex::new("o+").unwrap(), "foo bar boo").unwrap();
+    let text = String::from_utf8(buffer.into_inner()).unwrap();
+    assert_eq!(text, "f\x1b[0m\x1b[1m\x1b[31moo\x1b[0m bar b\x1b[0m\x1b[1m\x1b[31moo\x1b[0m\n");
+}
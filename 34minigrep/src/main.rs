@@ -0,0 +1,43 @@
+use minigrep::{run, Config, USAGE};
+use std::env;
+use std::io::{self, IsTerminal};
+use std::process;
+use termcolor::{ColorChoice, StandardStream};
+
+mod explain;
+
+fn main() {
+    rustfun_common::init_logging();
+    explain::EXPLANATION.on_request();
+    // Sorted walks already make the output the same every run; --deterministic is
+    // accepted like everywhere else, and changes nothing.
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    rustfun_common::deterministic::take_flag(&mut args);
+    let config = match Config::from_args(args) {
+        Ok(Some(config)) => config,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return;
+        }
+        Err(message) => {
+            eprintln!("minigrep: {}\n\n{}", message, USAGE);
+            process::exit(2);
+        }
+    };
+
+    // termcolor's Auto only looks at TERM and NO_COLOR; also turn colors off when
+    // stdout is piped, so `minigrep fn src | less` gets plain text.
+    let color = match config.color {
+        ColorChoice::Auto if !io::stdout().is_terminal() => ColorChoice::Never,
+        choice => choice,
+    };
+    let mut out = StandardStream::stdout(color);
+    match run(&config, &mut out) {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(e) => {
+            eprintln!("minigrep: {}", e);
+            process::exit(2);
+        }
+    }
+}
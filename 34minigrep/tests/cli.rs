@@ -0,0 +1,119 @@
+// Run the real binary against the files in tests/fixtures/tree and check its output
+// and exit status, the way a user (or a shell script) would see them.
+use std::path::Path;
+use std::process::Command;
+
+struct Output {
+    status: i32,
+    stdout: String,
+    stderr: String,
+}
+
+fn minigrep(args: &[&str]) -> Output {
+    minigrep_logging(None, args)
+}
+
+// RUST_LOG is cleared unless given, so the caller's environment can't change the output.
+fn minigrep_logging(rust_log: Option<&str>, args: &[&str]) -> Output {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_minigrep"));
+    match rust_log {
+        Some(filter) => command.env("RUST_LOG", filter),
+        None => command.env_remove("RUST_LOG"),
+    };
+    let output = command
+        .args(args)
+        .current_dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures"))
+        .output()
+        .unwrap();
+    Output {
+        status: output.status.code().unwrap(),
+        stdout: String::from_utf8(output.stdout).unwrap(),
+        stderr: String::from_utf8(output.stderr).unwrap(),
+    }
+}
+
+#[test]
+fn searches_directories_recursively() {
+    let out = minigrep(&["--color=never", "frog", "tree"]);
+    assert_eq!(out.status, 0);
+    assert_eq!(out.stdout, "\
+tree/nested/deeper/notes.md:3:A frog in a well knows nothing of the sea.
+tree/nested/todo.txt:2:fix the frog typo
+tree/poem.txt:7:How public, like a frog
+");
+}
+
+#[test]
+fn single_file_has_no_name_prefix() {
+    let out = minigrep(&["--color=never", r"^How", "tree/poem.txt"]);
+    assert_eq!(out.stdout, "6:How dreary to be somebody!\n7:How public, like a frog\n");
+}
+
+#[test]
+fn ignore_case() {
+    let out = minigrep(&["--color=never", "-i", "^todo", "tree/nested/todo.txt"]);
+    assert_eq!(out.stdout, "1:TODO: write the README\n3:todo: lowercase entry\n");
+}
+
+#[test]
+fn invert() {
+    let out = minigrep(&["--color=never", "--invert", "o", "tree/poem.txt"]);
+    assert_eq!(out.stdout, "5:\n");
+}
+
+#[test]
+fn count() {
+    let out = minigrep(&["-c", "nobody", "tree"]);
+    assert_eq!(out.stdout, "\
+tree/nested/deeper/empty-match.txt:0
+tree/nested/deeper/notes.md:0
+tree/nested/todo.txt:0
+tree/poem.txt:2
+");
+    let out = minigrep(&["--count", "--invert", "nobody", "tree/poem.txt"]);
+    assert_eq!(out.stdout, "7\n");
+}
+
+#[test]
+fn color_always_highlights_matches() {
+    let out = minigrep(&["--color=always", "frog", "tree/poem.txt"]);
+    assert_eq!(out.stdout, "\x1b[0m\x1b[32m7\x1b[0m:How public, like a \x1b[0m\x1b[1m\x1b[31mfrog\x1b[0m\n");
+}
+
+#[test]
+fn no_match_exits_with_1() {
+    let out = minigrep(&["zebra", "tree"]);
+    assert_eq!(out.status, 1);
+    assert_eq!(out.stdout, "");
+}
+
+#[test]
+fn errors_exit_with_2() {
+    let out = minigrep(&["(unclosed", "tree"]);
+    assert_eq!(out.status, 2);
+    assert!(out.stderr.contains("unclosed group"), "{}", out.stderr);
+
+    let out = minigrep(&["--bogus", "x"]);
+    assert_eq!(out.status, 2);
+    assert!(out.stderr.starts_with("minigrep: unknown option '--bogus'"));
+}
+
+#[test]
+fn missing_path_is_reported_but_not_fatal() {
+    let out = minigrep(&["--color=never", "frog", "no-such-dir", "tree/poem.txt"]);
+    assert_eq!(out.status, 0);
+    assert_eq!(out.stdout, "tree/poem.txt:7:How public, like a frog\n");
+    assert!(out.stderr.contains("no-such-dir"), "{}", out.stderr);
+}
+
+#[test]
+fn skipped_files_are_warnings_that_rust_log_can_hide() {
+    let args = ["--color=never", "frog", "no-such-dir", "tree/poem.txt"];
+    let out = minigrep(&args);
+    assert!(out.stderr.starts_with("[WARN  minigrep] "), "{}", out.stderr);
+
+    let out = minigrep_logging(Some("error"), &args);
+    assert_eq!(out.status, 0);
+    assert_eq!(out.stdout, "tree/poem.txt:7:How public, like a frog\n");
+    assert_eq!(out.stderr, "");
+}
@@ -0,0 +1,39 @@
+// What `serde-formats --explain` prints: the chapter's notes, in short.
+use rustfun_common::{Explanation, Text, Topic};
+
+pub const EXPLANATION: Explanation = Explanation {
+    chapter: "35serde-formats",
+    summary: Text {
+        en: "one struct through serde as JSON, YAML, TOML and MessagePack",
+        zh: "同一个结构体经 serde 转成 JSON、YAML、TOML 和 MessagePack",
+    },
+    topics: &[
+        Topic {
+            title: Text { en: "Two halves", zh: "两半" },
+            body: Text {
+                en: "The types derive Serialize and Deserialize, describing their shape once; each\n\
+                     format crate knows how to write and read that shape.",
+                zh: "类型派生 Serialize 和 Deserialize, 只描述一次自己的结构; 每个格式 crate\n\
+                     知道如何读写这种结构。",
+            },
+        },
+        Topic {
+            title: Text { en: "Attributes", zh: "属性" },
+            body: Text {
+                en: "rename_all, default, skip_serializing_if and with tune what the derive\n\
+                     writes, down to hand-written code for a single field.",
+                zh: "rename_all、default、skip_serializing_if 和 with 调整派生出来的代码,\n\
+                     甚至可以为单个字段换上手写的代码。",
+            },
+        },
+        Topic {
+            title: Text { en: "Format limits", zh: "格式的局限" },
+            body: Text {
+                en: "TOML has no null, so None fields are left out; MessagePack is binary, so it's\n\
+                     only checked to read back the same value.",
+                zh: "TOML 没有 null, 所以 None 字段会被省略; MessagePack 是二进制的, 只检查它能\n\
+                     读回同样的值。",
+            },
+        },
+    ],
+};
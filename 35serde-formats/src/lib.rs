@@ -0,0 +1,260 @@
+//
+// One data model, four formats: JSON, YAML, TOML and MessagePack, all through serde.
+//
+// Note:
+//
+// 1.) serde splits serialization in two. Our types describe their shape once, by
+//     deriving Serialize and Deserialize; each format crate (serde_json, serde_yaml,
+//     toml, rmp_serde) knows how to write and read that shape. Neither side knows
+//     about the other, which is why adding a format costs one line in Cargo.toml.
+// 2.) Attributes tune the derived code: `rename_all` changes field or variant names,
+//     `default` fills in missing fields, `skip_serializing_if` leaves fields out, and
+//     `with` swaps in hand-written (de)serializers for one field.
+// 3.) Not every format can say everything. TOML has no null, so None fields must be
+//     left out rather than written; MessagePack is binary, so the tests only check that
+//     it reads back to the same value.
+//
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A calendar date. Deliberately not Serialize/Deserialize itself: fields of this
+/// type pick a representation with `#[serde(with = "...")]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    /// A validated date, or None if there is no such day.
+    pub fn new(year: i32, month: u32, day: u32) -> Option<Date> {
+        let leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+        let days_in_month = match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if leap => 29,
+            2 => 28,
+            _ => return None,
+        };
+        if day >= 1 && day <= days_in_month {
+            Some(Date { year, month, day })
+        } else {
+            None
+        }
+    }
+
+    /// Parse the ISO 8601 form, "YYYY-MM-DD".
+    pub fn parse(text: &str) -> Option<Date> {
+        let mut parts = text.splitn(3, '-');
+        let year = parts.next()?;
+        let month = parts.next()?;
+        let day = parts.next()?;
+        if year.len() != 4 || month.len() != 2 || day.len() != 2 {
+            return None;
+        }
+        Date::new(year.parse().ok()?, month.parse().ok()?, day.parse().ok()?)
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// Dates as "YYYY-MM-DD" strings; use with `#[serde(with = "iso_date")]`.
+// 1.  A `with` module is just two functions with these exact signatures. Serializing
+//     is easy: turn the date into a string and hand it to the serializer. Deserializing
+//     goes the other way, and invalid input becomes the format's own error type via
+//     de::Error::custom, so the caller sees e.g. a line and column from serde_json.
+pub mod iso_date {
+    use super::Date;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(date: &Date, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(date)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Date, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        Date::parse(&text).ok_or_else(|| {
+            de::Error::custom(format!("invalid date '{}', expected YYYY-MM-DD", text))
+        })
+    }
+
+    /// The same, for `Option<Date>` fields.
+    // 1.1  Option needs its own pair: `with` replaces the whole field's (de)serializer,
+    //      so the Some/None layer has to be handled here too.
+    pub mod option {
+        use super::super::Date;
+        use serde::{Deserialize, Deserializer, Serializer};
+
+        pub fn serialize<S: Serializer>(date: &Option<Date>, serializer: S) -> Result<S::Ok, S::Error> {
+            match *date {
+                Some(ref date) => super::serialize(date, serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Date>, D::Error> {
+            #[derive(Deserialize)]
+            struct Wrapper(#[serde(with = "super")] Date);
+
+            let wrapper: Option<Wrapper> = Option::deserialize(deserializer)?;
+            Ok(wrapper.map(|Wrapper(date)| date))
+        }
+    }
+}
+
+// 2.  The model: a nested struct with a Vec, an Option, and an enum whose variants
+//     carry different data.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Library {
+    pub name: String,
+    #[serde(with = "iso_date")]
+    pub founded: Date,
+    pub books: Vec<Book>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Book {
+    pub title: String,
+    pub author: Author,
+    #[serde(with = "iso_date")]
+    pub first_published: Date,
+    pub edition: Edition,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Author {
+    pub name: String,
+    // 2.1  Without skip_serializing_if, None would be written as null, which TOML
+    //      can't express.
+    #[serde(default, with = "iso_date::option", skip_serializing_if = "Option::is_none")]
+    pub born: Option<Date>,
+}
+
+// 2.2  Enums are "externally tagged" by default: {"hardcover": {"pages": 281}}.
+//      Unit variants are plain strings: "out-of-print".
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Edition {
+    Hardcover { pages: u32 },
+    Ebook { size_kb: u32, drm: bool },
+    Audiobook { minutes: u32, narrator: String },
+    OutOfPrint,
+}
+
+/// A small library to play with.
+pub fn sample() -> Library {
+    Library {
+        name: "Rustfun Reading Room".to_string(),
+        founded: Date::new(2017, 9, 1).unwrap(),
+        books: vec![
+            Book {
+                title: "The Rust Programming Language".to_string(),
+                author: Author { name: "Steve Klabnik".to_string(), born: None },
+                first_published: Date::new(2018, 6, 26).unwrap(),
+                edition: Edition::Ebook { size_kb: 4096, drm: false },
+                tags: vec!["rust".to_string(), "beginner".to_string()],
+            },
+            Book {
+                title: "To Kill a Mockingbird".to_string(),
+                author: Author { name: "Harper Lee".to_string(), born: Date::new(1926, 4, 28) },
+                first_published: Date::new(1960, 7, 11).unwrap(),
+                edition: Edition::Audiobook { minutes: 738, narrator: "Sissy Spacek".to_string() },
+                tags: vec![],
+            },
+            Book {
+                title: "A Leap Year Almanac".to_string(),
+                author: Author { name: "Anonymous".to_string(), born: None },
+                first_published: Date::new(2000, 2, 29).unwrap(),
+                edition: Edition::OutOfPrint,
+                tags: vec![],
+            },
+            Book {
+                title: "Programming Rust".to_string(),
+                author: Author { name: "Jim Blandy".to_string(), born: None },
+                first_published: Date::new(2017, 12, 21).unwrap(),
+                edition: Edition::Hardcover { pages: 622 },
+                tags: vec!["rust".to_string()],
+            },
+        ],
+    }
+}
+
+#[test]
+fn test_date() {
+    assert_eq!(Date::parse("2000-02-29"), Date::new(2000, 2, 29));
+    assert_eq!(Date::parse("1900-02-29"), None);
+    assert_eq!(Date::parse("2021-13-01"), None);
+    assert_eq!(Date::parse("2021-1-01"), None);
+    assert_eq!(Date::parse("2021-01-01-01"), None);
+    assert_eq!(Date::new(987, 6, 5).unwrap().to_string(), "0987-06-05");
+}
+
+#[test]
+fn test_round_trip_json() {
+    let library = sample();
+    let text = serde_json::to_string_pretty(&library).unwrap();
+    assert_eq!(serde_json::from_str::<Library>(&text).unwrap(), library);
+}
+
+#[test]
+fn test_round_trip_yaml() {
+    let library = sample();
+    let text = serde_yaml::to_string(&library).unwrap();
+    assert_eq!(serde_yaml::from_str::<Library>(&text).unwrap(), library);
+}
+
+#[test]
+fn test_round_trip_toml() {
+    let library = sample();
+    let text = toml::to_string(&library).unwrap();
+    assert_eq!(toml::from_str::<Library>(&text).unwrap(), library);
+}
+
+#[test]
+fn test_round_trip_messagepack() {
+    let library = sample();
+    // to_vec writes structs as arrays (compact, positional); to_vec_named writes maps
+    // with field names (larger, but readable by non-Rust code that expects keys).
+    let compact = rmp_serde::to_vec(&library).unwrap();
+    let named = rmp_serde::to_vec_named(&library).unwrap();
+    assert!(compact.len() < named.len());
+    assert_eq!(rmp_serde::from_slice::<Library>(&compact).unwrap(), library);
+    assert_eq!(rmp_serde::from_slice::<Library>(&named).unwrap(), library);
+}
+
+#[test]
+fn test_json_shape() {
+    let book = &sample().books[3];
+    assert_eq!(serde_json::to_string(book).unwrap(),
+               r#"{"title":"Programming Rust","author":{"name":"Jim Blandy"},"first-published":"2017-12-21","edition":{"hardcover":{"pages":622}},"tags":["rust"]}"#);
+    let edition = serde_json::to_string(&Edition::OutOfPrint).unwrap();
+    assert_eq!(edition, r#""out-of-print""#);
+}
+
+#[test]
+fn test_missing_optional_fields() {
+    let json = r#"{"title": "T", "author": {"name": "A"}, "first-published": "1999-12-31",
+                   "edition": "out-of-print"}"#;
+    let book: Book = serde_json::from_str(json).unwrap();
+    assert_eq!(book.author.born, None);
+    assert!(book.tags.is_empty());
+}
+
+#[test]
+fn test_invalid_date_is_a_format_error() {
+    let json = r#"{"name": "X", "founded": "2019-02-30", "books": []}"#;
+    let err = serde_json::from_str::<Library>(json).unwrap_err();
+    assert_eq!(err.to_string(), "invalid date '2019-02-30', expected YYYY-MM-DD at line 1 column 37");
+
+    let toml = "name = \"X\"\nfounded = \"tomorrow\"\nbooks = []\n";
+    let err = toml::from_str::<Library>(toml).unwrap_err();
+    assert!(err.to_string().contains("invalid date 'tomorrow'"), "{}", err);
+}
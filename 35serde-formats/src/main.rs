@@ -0,0 +1,42 @@
+use rustfun_error::{Error, Result};
+use serde_formats::{sample, Library};
+
+mod explain;
+
+fn main() {
+    rustfun_common::init_logging();
+    explain::EXPLANATION.on_request();
+    if let Err(e) = run() {
+        e.exit("serde-formats");
+    }
+}
+
+// Each format has an error type of its own; Error::parse turns any of them into ours.
+fn run() -> Result<()> {
+    let library = sample();
+
+    let json = serde_json::to_string_pretty(&library).map_err(Error::parse)?;
+    let yaml = serde_yaml::to_string(&library).map_err(Error::parse)?;
+    let toml = toml::to_string(&library).map_err(Error::parse)?;
+    let msgpack = rmp_serde::to_vec_named(&library).map_err(Error::parse)?;
+
+    println!("=== JSON ({} bytes)\n{}\n", json.len(), json);
+    println!("=== YAML ({} bytes)\n{}", yaml.len(), yaml);
+    println!("=== TOML ({} bytes)\n{}", toml.len(), toml);
+    println!("=== MessagePack ({} bytes)", msgpack.len());
+    for chunk in msgpack.chunks(32) {
+        let hex: Vec<String> = chunk.iter().map(|b| format!("{:02x}", b)).collect();
+        println!("{}", hex.join(" "));
+    }
+
+    // Every format reads back to the same value.
+    let back: Vec<Library> = vec![
+        serde_json::from_str(&json).map_err(Error::parse)?,
+        serde_yaml::from_str(&yaml).map_err(Error::parse)?,
+        toml::from_str(&toml).map_err(Error::parse)?,
+        rmp_serde::from_slice(&msgpack).map_err(Error::parse)?,
+    ];
+    assert!(back.iter().all(|l| *l == library));
+    println!("\nall four formats round-trip");
+    Ok(())
+}
@@ -0,0 +1,14 @@
+// Run the chapter's demo binary and check the parts of its output that are deterministic.
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+#[test]
+fn prints_every_format() {
+    Command::cargo_bin("serde-formats").unwrap().assert()
+        .success()
+        .stdout(predicate::str::starts_with("=== JSON (")
+            .and(predicate::str::contains("\"founded\": \"2017-09-01\""))
+            .and(predicate::str::contains("=== YAML ("))
+            .and(predicate::str::contains("=== TOML ("))
+            .and(predicate::str::contains("=== MessagePack (")));
+}
@@ -0,0 +1,105 @@
+// Built-in commands. Each is an ordinary type implementing Command; nothing else in the
+// crate needs to know they exist.
+use crate::{Command, Context};
+
+/// `echo WORDS...`: print the words, with `$name` replaced by variables.
+pub struct Echo;
+
+impl Command for Echo {
+    fn name(&self) -> &str {
+        "echo"
+    }
+    fn description(&self) -> &str {
+        "print the arguments; $name expands a variable"
+    }
+    fn run(&mut self, args: &[&str], ctx: &mut Context) -> Result<(), String> {
+        let words: Vec<String> = args.iter().map(|word| {
+            if let Some(name) = word.strip_prefix('$') {
+                ctx.vars.get(name).cloned().unwrap_or_default()
+            } else {
+                word.to_string()
+            }
+        }).collect();
+        ctx.print(words.join(" "));
+        Ok(())
+    }
+}
+
+/// `set NAME VALUE...`: store a variable.
+pub struct Set;
+
+impl Command for Set {
+    fn name(&self) -> &str {
+        "set"
+    }
+    fn description(&self) -> &str {
+        "set NAME VALUE: store a variable"
+    }
+    fn run(&mut self, args: &[&str], ctx: &mut Context) -> Result<(), String> {
+        match args.split_first() {
+            Some((name, value)) if !value.is_empty() => {
+                ctx.vars.insert(name.to_string(), value.join(" "));
+                Ok(())
+            }
+            _ => Err("usage: set NAME VALUE".to_string()),
+        }
+    }
+}
+
+/// `count`: a stateful command. The registry keeps the same Counter between calls,
+/// and the runner reads `total` back by downcasting.
+#[derive(Default)]
+pub struct Counter {
+    pub total: usize,
+}
+
+impl Command for Counter {
+    fn name(&self) -> &str {
+        "count"
+    }
+    fn description(&self) -> &str {
+        "count how many times it has run"
+    }
+    fn run(&mut self, _args: &[&str], ctx: &mut Context) -> Result<(), String> {
+        self.total += 1;
+        ctx.print(format!("count: {}", self.total));
+        Ok(())
+    }
+}
+
+/// `sum NUMBERS...`: add numbers, failing on anything that isn't one.
+pub struct Sum;
+
+impl Command for Sum {
+    fn name(&self) -> &str {
+        "sum"
+    }
+    fn description(&self) -> &str {
+        "add the numbers given as arguments"
+    }
+    fn run(&mut self, args: &[&str], ctx: &mut Context) -> Result<(), String> {
+        let mut total = 0.0;
+        for arg in args {
+            total += arg.parse::<f64>().map_err(|_| format!("'{}' is not a number", arg))?;
+        }
+        ctx.print(total.to_string());
+        Ok(())
+    }
+}
+
+#[test]
+fn test_echo_expands_variables() {
+    let mut ctx = Context::default();
+    Set.run(&["who", "the", "world"], &mut ctx).unwrap();
+    Echo.run(&["hello", "$who", "$missing"], &mut ctx).unwrap();
+    assert_eq!(ctx.output, vec!["hello the world "]);
+    assert_eq!(Set.run(&["lonely"], &mut ctx), Err("usage: set NAME VALUE".to_string()));
+}
+
+#[test]
+fn test_sum() {
+    let mut ctx = Context::default();
+    Sum.run(&["1", "2.5", "-0.5"], &mut ctx).unwrap();
+    assert_eq!(ctx.output, vec!["3"]);
+    assert_eq!(Sum.run(&["1", "two"], &mut ctx), Err("'two' is not a number".to_string()));
+}
@@ -0,0 +1,36 @@
+// What `plugin-architecture --explain` prints: the chapter's notes, in short.
+use rustfun_common::{Explanation, Text, Topic};
+
+pub const EXPLANATION: Explanation = Explanation {
+    chapter: "36plugin-architecture",
+    summary: Text {
+        en: "commands as trait objects in a registry",
+        zh: "注册表里作为 trait 对象的命令",
+    },
+    topics: &[
+        Topic {
+            title: Text { en: "Trait objects", zh: "trait 对象" },
+            body: Text {
+                en: "The registry holds Box<dyn Command> values of different types side by side,\n\
+                     and calls run through a vtable.",
+                zh: "注册表并排存放不同类型的 Box<dyn Command>, 通过虚表调用 run。",
+            },
+        },
+        Topic {
+            title: Text { en: "Downcasting with Any", zh: "用 Any 向下转型" },
+            body: Text {
+                en: "downcast_ref::<Counter>() gets the concrete type back, and returns Some only\n\
+                     if the command really is a Counter.",
+                zh: "downcast_ref::<Counter>() 取回具体类型, 只有命令确实是 Counter 时才返回 Some。",
+            },
+        },
+        Topic {
+            title: Text { en: "Macros", zh: "宏" },
+            body: Text {
+                en: "command! turns a closure into a Command, and register! adds several commands\n\
+                     in one statement.",
+                zh: "command! 把闭包变成 Command, register! 在一条语句里注册多个命令。",
+            },
+        },
+    ],
+};
@@ -0,0 +1,227 @@
+//
+// Plugins as trait objects: a registry of Box<dyn Command>, looked up by name at runtime.
+//
+// Note:
+//
+// 1.) `Box<dyn Command>` erases the concrete type: the registry holds an Echo, a
+//     Counter and a closure-based command side by side in one map, and calls them all
+//     through the same vtable-dispatched `run`. Adding a command never touches the
+//     registry or the runner.
+// 2.) Sometimes the caller does need the concrete type back, e.g. to read a Counter's
+//     total. `std::any::Any` allows a checked downcast: `downcast_ref::<Counter>()`
+//     returns Some only if the object really is a Counter.
+// 3.) Macros remove the boilerplate: `command!` turns a closure into a Command, and
+//     `register!` adds several commands to a registry in one statement.
+//
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::fmt;
+
+pub mod commands;
+
+/// State shared by all commands during one run: variables and collected output.
+#[derive(Debug, Default)]
+pub struct Context {
+    pub vars: BTreeMap<String, String>,
+    pub output: Vec<String>,
+}
+
+impl Context {
+    pub fn print(&mut self, line: String) {
+        self.output.push(line);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    UnknownCommand(String),
+    Failed { command: String, message: String },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::UnknownCommand(ref name) => write!(f, "unknown command '{}'", name),
+            Error::Failed { ref command, ref message } => write!(f, "{}: {}", command, message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+// 1.  `trait Command: Any` alone isn't enough, since a `&dyn Command` couldn't be turned
+//     into a `&dyn Any` before trait upcasting arrived in Rust 1.86. The usual trick is a
+//     helper trait with a blanket impl, so every Command gets `as_any` for free.
+pub trait AsAny {
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: Any> AsAny for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// A named task the runner can invoke.
+pub trait Command: AsAny + 'static {
+    fn name(&self) -> &str;
+    fn description(&self) -> &str;
+    fn run(&mut self, args: &[&str], ctx: &mut Context) -> Result<(), String>;
+}
+
+/// All known commands, by name.
+#[derive(Default)]
+pub struct Registry {
+    commands: BTreeMap<String, Box<dyn Command>>,
+}
+
+impl Registry {
+    pub fn new() -> Registry {
+        Registry::default()
+    }
+
+    /// Add a command, replacing any earlier one with the same name.
+    pub fn register(&mut self, command: Box<dyn Command>) {
+        self.commands.insert(command.name().to_string(), command);
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.commands.keys().map(|k| k.as_str()).collect()
+    }
+
+    pub fn describe(&self) -> Vec<(&str, &str)> {
+        self.commands.values().map(|c| (c.name(), c.description())).collect()
+    }
+
+    /// Dynamic dispatch: look the command up by name and run it.
+    pub fn run(&mut self, name: &str, args: &[&str], ctx: &mut Context) -> Result<(), Error> {
+        let command = self.commands.get_mut(name)
+            .ok_or_else(|| Error::UnknownCommand(name.to_string()))?;
+        command.run(args, ctx)
+            .map_err(|message| Error::Failed { command: name.to_string(), message })
+    }
+
+    /// Run one line of a task file: "name arg arg ...". Blank lines and '#' comments
+    /// do nothing.
+    pub fn run_line(&mut self, line: &str, ctx: &mut Context) -> Result<(), Error> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return Ok(());
+        }
+        let mut words = line.split_whitespace();
+        let name = words.next().unwrap();
+        let args: Vec<&str> = words.collect();
+        self.run(name, &args, ctx)
+    }
+
+    // 2.  The downcast: None if there's no such command, or if it isn't a T.
+    //
+    //     Mind the `**c`. `c` is a `&Box<dyn Command>`, and Box<dyn Command> is itself
+    //     Any, so the blanket impl would happily give us the Box as a `&dyn Any`, and
+    //     every downcast to T would fail. Dereferencing twice reaches the command inside.
+    pub fn get<T: Command>(&self, name: &str) -> Option<&T> {
+        self.commands.get(name).and_then(|c| (**c).as_any().downcast_ref::<T>())
+    }
+
+    pub fn get_mut<T: Command>(&mut self, name: &str) -> Option<&mut T> {
+        self.commands.get_mut(name).and_then(|c| (**c).as_any_mut().downcast_mut::<T>())
+    }
+}
+
+/// A Command built from a closure, as made by `command!`.
+pub struct FnCommand<F> {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub body: F,
+}
+
+impl<F> Command for FnCommand<F>
+    where F: FnMut(&[&str], &mut Context) -> Result<(), String> + 'static
+{
+    fn name(&self) -> &str {
+        self.name
+    }
+    fn description(&self) -> &str {
+        self.description
+    }
+    fn run(&mut self, args: &[&str], ctx: &mut Context) -> Result<(), String> {
+        (self.body)(args, ctx)
+    }
+}
+
+/// Make a boxed Command from a closure: `command!("name", "description", |args, ctx| body)`.
+#[macro_export]
+macro_rules! command {
+    ($name:expr, $description:expr, |$args:ident, $ctx:ident| $body:expr) => {
+        Box::new($crate::FnCommand {
+            name: $name,
+            description: $description,
+            body: move |$args: &[&str], $ctx: &mut $crate::Context| -> Result<(), String> { $body },
+        }) as Box<dyn $crate::Command>
+    };
+}
+
+/// Register several commands at once: `register!(registry, Echo, Counter::default())`.
+#[macro_export]
+macro_rules! register {
+    ($registry:expr, $($command:expr),+ $(,)*) => {
+        $( $registry.register(Box::new($command)); )+
+    };
+}
+
+#[cfg(test)]
+use crate::commands::{Counter, Echo};
+
+#[test]
+fn test_dispatch_by_name() {
+    let mut registry = Registry::new();
+    register!(registry, Echo, Counter::default());
+    let mut ctx = Context::default();
+    registry.run("echo", &["hello", "world"], &mut ctx).unwrap();
+    registry.run_line("  count  ", &mut ctx).unwrap();
+    registry.run_line("# a comment", &mut ctx).unwrap();
+    assert_eq!(ctx.output, vec!["hello world", "count: 1"]);
+    assert_eq!(registry.names(), vec!["count", "echo"]);
+    assert_eq!(registry.run("nope", &[], &mut ctx), Err(Error::UnknownCommand("nope".to_string())));
+}
+
+#[test]
+fn test_downcast() {
+    let mut registry = Registry::new();
+    register!(registry, Echo, Counter::default());
+    let mut ctx = Context::default();
+    for _ in 0..3 {
+        registry.run("count", &[], &mut ctx).unwrap();
+    }
+    assert_eq!(registry.get::<Counter>("count").map(|c| c.total), Some(3));
+    // the name exists, but it's the wrong type
+    assert!(registry.get::<Counter>("echo").is_none());
+    registry.get_mut::<Counter>("count").unwrap().total = 0;
+    registry.run("count", &[], &mut ctx).unwrap();
+    assert_eq!(ctx.output.last().unwrap(), "count: 1");
+}
+
+#[test]
+fn test_command_macro() {
+    let mut registry = Registry::new();
+    let mut calls = 0;
+    registry.register(command!("shout", "print arguments in capitals", |args, ctx| {
+        calls += 1;
+        if args.is_empty() {
+            return Err("nothing to shout".to_string());
+        }
+        ctx.print(format!("{} (#{})", args.join(" ").to_uppercase(), calls));
+        Ok(())
+    }));
+    let mut ctx = Context::default();
+    registry.run_line("shout hi there", &mut ctx).unwrap();
+    let err = registry.run_line("shout", &mut ctx).unwrap_err();
+    assert_eq!(err.to_string(), "shout: nothing to shout");
+    registry.run_line("shout again", &mut ctx).unwrap();
+    assert_eq!(ctx.output, vec!["HI THERE (#1)", "AGAIN (#3)"]);
+    assert_eq!(registry.describe(), vec![("shout", "print arguments in capitals")]);
+}
@@ -0,0 +1,60 @@
+use plugin_architecture::commands::{Counter, Echo, Set, Sum};
+use plugin_architecture::{command, register, Context, Registry};
+use std::env;
+use std::fs;
+use std::process;
+
+// A tiny task runner. Give it a task file, or a single command on the command line:
+//
+mod explain;
+
+//   $ cargo run -- tasks.txt
+//   $ cargo run -- sum 1 2 3
+//   $ cargo run -- help
+fn main() {
+    rustfun_common::init_logging();
+    explain::EXPLANATION.on_request();
+    let mut registry = Registry::new();
+    register!(registry, Echo, Set, Sum, Counter::default());
+    registry.register(command!("shout", "print the arguments in capitals", |args, ctx| {
+        ctx.print(args.join(" ").to_uppercase());
+        Ok(())
+    }));
+
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    rustfun_common::deterministic::take_flag(&mut args);
+    let lines = match args.first().map(|s| s.as_str()) {
+        None | Some("help") => {
+            println!("Usage: plugin-architecture TASKFILE | COMMAND [ARGS...]\n\nCommands:");
+            for (name, description) in registry.describe() {
+                println!("  {:<8}{}", name, description);
+            }
+            return;
+        }
+        Some(path) if path.ends_with(".txt") => match fs::read_to_string(path) {
+            Ok(text) => text.lines().map(String::from).collect(),
+            Err(e) => {
+                eprintln!("error reading {}: {}", path, e);
+                process::exit(1);
+            }
+        },
+        Some(_) => vec![args.join(" ")],
+    };
+
+    let mut ctx = Context::default();
+    for (number, line) in lines.iter().enumerate() {
+        let result = registry.run_line(line, &mut ctx);
+        for output in ctx.output.drain(..) {
+            println!("{}", output);
+        }
+        if let Err(e) = result {
+            eprintln!("line {}: {}", number + 1, e);
+            process::exit(1);
+        }
+    }
+
+    // Downcast to read state out of a specific plugin.
+    if let Some(counter) = registry.get::<Counter>("count") {
+        println!("(count ran {} times)", counter.total);
+    }
+}
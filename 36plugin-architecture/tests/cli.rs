@@ -0,0 +1,55 @@
+// The task runner binary: help, a task file, a one-off command, and failures.
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+fn runner() -> Command {
+    let mut cmd = Command::cargo_bin("plugin-architecture").unwrap();
+    cmd.current_dir(env!("CARGO_MANIFEST_DIR"));
+    cmd
+}
+
+#[test]
+fn help_lists_every_command() {
+    for args in &[&[][..], &["help"][..]] {
+        runner().args(*args).assert()
+            .success()
+            .stdout(predicate::str::starts_with("Usage: plugin-architecture TASKFILE | COMMAND [ARGS...]\n")
+                .and(predicate::str::contains("  shout   print the arguments in capitals\n")));
+    }
+}
+
+#[test]
+fn runs_a_task_file() {
+    runner().arg("tasks.txt").assert()
+        .success()
+        .stdout("\
+building the docs
+target is wasm32
+count: 1
+6.5
+count: 2
+DONE
+(count ran 2 times)
+");
+}
+
+#[test]
+fn runs_a_single_command() {
+    runner().args(["sum", "1", "2", "3"]).assert()
+        .success()
+        .stdout("6\n(count ran 0 times)\n");
+}
+
+#[test]
+fn unknown_command_fails() {
+    runner().args(["frobnicate", "1"]).assert()
+        .code(1)
+        .stderr("line 1: unknown command 'frobnicate'\n");
+}
+
+#[test]
+fn missing_task_file_fails() {
+    runner().arg("missing.txt").assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("error reading missing.txt: "));
+}
@@ -0,0 +1,178 @@
+//
+// The Mandelbrot renderer from 03mandelbrot, compiled to WebAssembly and drawn on an
+// HTML canvas.
+//
+// Note:
+//
+// 1.) The math is 03mandelbrot's own library, the `mandelbrot` crate: render_region hands
+//     the view to its render_into, so the page draws the picture `mandelbrot` draws with
+//     its defaults, pixel for pixel, gray levels (255 - iterations) and all. The library
+//     touches no file and no thread, so it builds for wasm32 as it is, and `cargo test`
+//     runs this crate natively.
+// 2.) `#[wasm_bindgen]` marks what JavaScript may call. wasm-bindgen generates the glue:
+//     a `Vec<u8>` returned from Rust arrives in JS as a Uint8Array, an f64 is a Number.
+// 3.) Canvas ImageData wants 4 bytes per pixel (RGBA), so the browser entry point
+//     expands the grayscale buffer before handing it over.
+// 4.) On a struct, `#[wasm_bindgen]` makes a JS class: `new Viewport(...)` calls the
+//     function marked `constructor`, and each pub field of a Copy type gets a getter and
+//     a setter. JS holds a handle to the Rust value, and passes it back by reference.
+// 5.) A function that returns a Result throws its Err in JS, as an Error with the
+//     message: a picture too big for the buffer is an exception there, not a crash.
+//
+// Build with `wasm-pack build --target web`, then serve this directory; see README.md.
+//
+use mandelbrot::escape::Power;
+use mandelbrot::render::{buffer_len, render_into, RenderOptions};
+use num::Complex;
+use wasm_bindgen::prelude::*;
+
+/// A rectangle of the complex plane: the real parts at its left and right edges, the
+/// imaginary parts at its top and bottom.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Viewport {
+    pub left: f64,
+    pub top: f64,
+    pub right: f64,
+    pub bottom: f64,
+}
+
+#[wasm_bindgen]
+impl Viewport {
+    #[wasm_bindgen(constructor)]
+    pub fn new(left: f64, top: f64, right: f64, bottom: f64) -> Viewport {
+        Viewport { left, top, right, bottom }
+    }
+
+    /// The point at fraction (`fx`, `fy`) of the way across and down the view: (0, 0) is
+    /// the top-left corner, (1, 1) the bottom-right. Returns [re, im].
+    pub fn point_at(&self, fx: f64, fy: f64) -> Vec<f64> {
+        vec![self.left + fx * (self.right - self.left), self.top - fy * (self.top - self.bottom)]
+    }
+
+    /// The view `scale` times the size, centred on `re` + `im` i: a scale under 1 zooms in.
+    pub fn zoom(&self, re: f64, im: f64, scale: f64) -> Viewport {
+        let half_width = (self.right - self.left) * scale / 2.0;
+        let half_height = (self.top - self.bottom) * scale / 2.0;
+        Viewport::new(re - half_width, im + half_height, re + half_width, im - half_height)
+    }
+}
+
+/// How the points are iterated: the `mandelbrot` program's --limit, --power and
+/// --escape-radius.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Settings {
+    pub limit: u32,
+    pub power: f64,
+    pub escape_radius: f64,
+}
+
+#[wasm_bindgen]
+impl Settings {
+    #[wasm_bindgen(constructor)]
+    pub fn new(limit: u32, power: f64, escape_radius: f64) -> Settings {
+        Settings { limit, power, escape_radius }
+    }
+}
+
+/// The program's: 255 iterations of z*z + c, and the circle of radius 2.
+impl Default for Settings {
+    fn default() -> Settings {
+        Settings::new(255, 2.0, 2.0)
+    }
+}
+
+/// Render `viewport` into a `width` x `height` RGBA buffer, ready for `new ImageData(...)`.
+#[wasm_bindgen]
+pub fn render_region(width: usize, height: usize, viewport: &Viewport) -> Result<Vec<u8>, String> {
+    render_with(width, height, viewport, &Settings::default())
+}
+
+/// The same, iterating each point as `settings` say.
+#[wasm_bindgen]
+pub fn render_with(width: usize, height: usize, viewport: &Viewport, settings: &Settings)
+    -> Result<Vec<u8>, String>
+{
+    let region = mandelbrot::render::Viewport {
+        bounds: (width, height),
+        upper_left: Complex { re: viewport.left, im: viewport.top },
+        lower_right: Complex { re: viewport.right, im: viewport.bottom },
+    };
+    let options = RenderOptions {
+        settings: mandelbrot::escape::Settings {
+            power: Power::new(settings.power),
+            limit: settings.limit,
+            escape_radius: settings.escape_radius,
+            ..mandelbrot::escape::Settings::default()
+        },
+        ..RenderOptions::default()
+    };
+    let len = buffer_len(region.bounds, 4).map_err(|e| e.to_string())?;
+    let mut gray = vec![0; options.buffer_len(&region).map_err(|e| e.to_string())?];
+    render_into(&mut gray, &region, &options);
+
+    let mut rgba = Vec::with_capacity(len);
+    for &g in &gray {
+        rgba.extend_from_slice(&[g, g, g, 255]);
+    }
+    Ok(rgba)
+}
+
+/// The same, for the region between (`left`, `top`) and (`right`, `bottom`).
+// 1.  Plain numbers cross the JS boundary cheaply, so the corners come in as four f64s
+//     rather than as Complex values, which JS has no way to build. This and point_at
+//     were the page's first interface, before Viewport, and stay for pages written
+//     against it.
+#[wasm_bindgen]
+pub fn render_rgba(width: usize, height: usize,
+                   left: f64, top: f64, right: f64, bottom: f64) -> Result<Vec<u8>, String> {
+    render_region(width, height, &Viewport::new(left, top, right, bottom))
+}
+
+/// `Viewport::point_at`, for the view between (`left`, `top`) and (`right`, `bottom`).
+#[wasm_bindgen]
+pub fn point_at(fx: f64, fy: f64, left: f64, top: f64, right: f64, bottom: f64) -> Vec<f64> {
+    Viewport::new(left, top, right, bottom).point_at(fx, fy)
+}
+
+#[test]
+fn test_render_region() {
+    let rgba = render_region(4, 2, &Viewport::new(-2.0, 1.0, 2.0, -1.0)).unwrap();
+    assert_eq!(rgba.len(), 4 * 2 * 4);
+    // every pixel is opaque gray
+    for pixel in rgba.chunks(4) {
+        assert_eq!((pixel[0], pixel[3]), (pixel[1], 255));
+        assert_eq!(pixel[1], pixel[2]);
+    }
+    // column 2 of row 1 is the point 0+0i, inside the set, so black
+    assert_eq!(&rgba[(4 + 2) * 4..(4 + 2) * 4 + 4], &[0, 0, 0, 255]);
+    // the top-left corner -2+1i escapes at once: 255 - 0
+    assert_eq!(rgba[0], 255);
+    assert_eq!(render_rgba(4, 2, -2.0, 1.0, 2.0, -1.0).unwrap(), rgba);
+
+    // the same gray levels as the mandelbrot program's, pixel for pixel
+    let view = Viewport::new(-1.20, 0.35, -1.0, 0.20);
+    let rgba = render_region(40, 30, &view).unwrap();
+    for (i, pixel) in rgba.chunks(4).enumerate() {
+        let [re, im] = view.point_at((i % 40) as f64 / 40.0, (i / 40) as f64 / 30.0)[..] else { unreachable!() };
+        let escape = mandelbrot::escape::escape_time(Complex { re, im }, mandelbrot::escape::Settings::default());
+        assert_eq!(pixel[0], escape.map_or(0, |count| 255 - count as u8), "pixel {}", i);
+    }
+    assert_eq!(render_with(40, 30, &view, &Settings::default()).unwrap(), rgba);
+    // the cube, z^3 + c, is another picture
+    assert_ne!(render_with(40, 30, &view, &Settings::new(255, 3.0, 2.0)).unwrap(), rgba);
+
+    // too big for a buffer: an error, which JS gets as an exception
+    let error = render_region(usize::MAX, 2, &view).unwrap_err();
+    assert!(error.contains("too many"), "{}", error);
+}
+
+#[test]
+fn test_point_at() {
+    assert_eq!(point_at(0.25, 0.75, -1.0, 1.0, 1.0, -1.0), vec![-0.5, -0.5]);
+    let view = Viewport::new(-1.0, 1.0, 1.0, -1.0);
+    assert_eq!(view.point_at(0.25, 0.75), vec![-0.5, -0.5]);
+    assert_eq!(view.zoom(-0.5, -0.5, 0.5), Viewport::new(-1.0, 0.0, 0.0, -1.0));
+    assert_eq!(view.zoom(0.0, 0.0, 2.0), Viewport::new(-2.0, 2.0, 2.0, -2.0));
+}
@@ -0,0 +1,166 @@
+// A vector with a fixed capacity N, stored inline: no heap, so it works under no_std.
+use core::fmt;
+use core::mem::MaybeUninit;
+use core::ops::{Deref, DerefMut};
+use core::ptr;
+use core::slice;
+
+/// Returned by `push` when the ArrayVec is full; gives the rejected value back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapacityError<T>(pub T);
+
+impl<T> fmt::Display for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("ArrayVec is full")
+    }
+}
+
+// 1.  `[T; N]` won't do for storage: every slot would need a value from the start, and
+//     dropping the array would drop all N of them. MaybeUninit<T> is a slot that may or
+//     may not hold a T, and never drops anything by itself; `len` records which slots
+//     (0..len) are initialized, and all the unsafe code below relies on that invariant.
+pub struct ArrayVec<T, const N: usize> {
+    items: [MaybeUninit<T>; N],
+    len: usize,
+}
+
+impl<T, const N: usize> ArrayVec<T, N> {
+    pub const fn new() -> ArrayVec<T, N> {
+        ArrayVec { items: [const { MaybeUninit::uninit() }; N], len: 0 }
+    }
+
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Append `value`, or hand it back if there's no room.
+    pub fn push(&mut self, value: T) -> Result<(), CapacityError<T>> {
+        if self.len == N {
+            return Err(CapacityError(value));
+        }
+        self.items[self.len].write(value);
+        self.len += 1;
+        Ok(())
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // 2.  Safe: slot `len` was initialized, and decrementing len first means we
+        //     will never read or drop it again, so the value is moved out exactly once.
+        Some(unsafe { self.items[self.len].assume_init_read() })
+    }
+
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    pub fn truncate(&mut self, len: usize) {
+        while self.len > len {
+            self.pop();
+        }
+    }
+}
+
+impl<T, const N: usize> Default for ArrayVec<T, N> {
+    fn default() -> ArrayVec<T, N> {
+        ArrayVec::new()
+    }
+}
+
+// 3.  Deref to a slice gives us len(), iter(), indexing, sort() and the rest of the
+//     slice API for free, just as Vec does.
+impl<T, const N: usize> Deref for ArrayVec<T, N> {
+    type Target = [T];
+    fn deref(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.items.as_ptr() as *const T, self.len) }
+    }
+}
+
+impl<T, const N: usize> DerefMut for ArrayVec<T, N> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.items.as_mut_ptr() as *mut T, self.len) }
+    }
+}
+
+// 4.  Since MaybeUninit never drops its contents, we have to: exactly the first `len`.
+impl<T, const N: usize> Drop for ArrayVec<T, N> {
+    fn drop(&mut self) {
+        unsafe { ptr::drop_in_place(&mut **self as *mut [T]) }
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for ArrayVec<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for ArrayVec<T, N> {
+    fn clone(&self) -> ArrayVec<T, N> {
+        let mut copy = ArrayVec::new();
+        for item in self.iter() {
+            // can't fail: the copy has the same capacity
+            let _ = copy.push(item.clone());
+        }
+        copy
+    }
+}
+
+#[test]
+fn test_push_pop() {
+    let mut v: ArrayVec<u32, 3> = ArrayVec::new();
+    assert_eq!(v.capacity(), 3);
+    assert!(v.is_empty());
+    v.push(1).unwrap();
+    v.push(2).unwrap();
+    v.push(3).unwrap();
+    assert!(v.is_full());
+    assert_eq!(v.push(4), Err(CapacityError(4)));
+    assert_eq!(&*v, &[1, 2, 3]);
+    assert_eq!(v.pop(), Some(3));
+    assert_eq!(v.pop(), Some(2));
+    v.push(5).unwrap();
+    assert_eq!(&*v, &[1, 5]);
+}
+
+#[test]
+fn test_slice_methods() {
+    let mut v: ArrayVec<i32, 8> = ArrayVec::new();
+    for x in &[5, -1, 3, 9] {
+        v.push(*x).unwrap();
+    }
+    v.sort();
+    assert_eq!(&*v, &[-1, 3, 5, 9]);
+    v[0] = 100;
+    assert_eq!(v.iter().sum::<i32>(), 117);
+    assert!(v.contains(&9));
+    assert_eq!(format!("{:?}", v), "[100, 3, 5, 9]");
+    assert_eq!(&*v.clone(), &*v);
+}
+
+#[test]
+fn test_drops_exactly_the_live_elements() {
+    use std::rc::Rc;
+    let counter = Rc::new(());
+    {
+        let mut v: ArrayVec<Rc<()>, 4> = ArrayVec::new();
+        v.push(counter.clone()).unwrap();
+        v.push(counter.clone()).unwrap();
+        v.push(counter.clone()).unwrap();
+        drop(v.pop());
+        assert_eq!(Rc::strong_count(&counter), 3);
+        // the full error gives the value back instead of leaking it
+        let mut full: ArrayVec<Rc<()>, 0> = ArrayVec::new();
+        let CapacityError(back) = full.push(counter.clone()).unwrap_err();
+        assert_eq!(Rc::strong_count(&counter), 4);
+        drop(back);
+    }
+    assert_eq!(Rc::strong_count(&counter), 1);
+}
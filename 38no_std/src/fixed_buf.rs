@@ -0,0 +1,89 @@
+// A fixed-size text buffer that core::fmt can write into.
+use core::fmt;
+use core::str;
+
+/// Up to N bytes of UTF-8 text, stored inline.
+pub struct FixedBuf<const N: usize> {
+    bytes: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> FixedBuf<N> {
+    pub const fn new() -> FixedBuf<N> {
+        FixedBuf { bytes: [0; N], len: 0 }
+    }
+
+    pub fn as_str(&self) -> &str {
+        // Safe: write_str only ever copies in whole &strs, so bytes[..len] is UTF-8.
+        unsafe { str::from_utf8_unchecked(&self.bytes[..self.len]) }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+}
+
+impl<const N: usize> Default for FixedBuf<N> {
+    fn default() -> FixedBuf<N> {
+        FixedBuf::new()
+    }
+}
+
+// 1.  core::fmt::Write has one required method. write! calls it once per piece of the
+//     formatted output; returning fmt::Error stops formatting and makes write! fail.
+//     A piece that doesn't fit is rejected whole, so the buffer never ends in the
+//     middle of a UTF-8 character.
+impl<const N: usize> fmt::Write for FixedBuf<N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        if end > N {
+            return Err(fmt::Error);
+        }
+        self.bytes[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+impl<const N: usize> fmt::Display for FixedBuf<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<const N: usize> fmt::Debug for FixedBuf<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+#[test]
+fn test_write() {
+    use core::fmt::Write;
+    let mut buf: FixedBuf<16> = FixedBuf::new();
+    write!(buf, "{}+{}={}", 2, 2, 2 + 2).unwrap();
+    assert_eq!(buf.as_str(), "2+2=4");
+    write!(buf, " {:>5}|", "ok").unwrap();
+    assert_eq!(buf.as_str(), "2+2=4    ok|");
+    assert_eq!(buf.len(), 12);
+    buf.clear();
+    assert!(buf.is_empty());
+}
+
+#[test]
+fn test_overflow_keeps_whole_pieces() {
+    use core::fmt::Write;
+    let mut buf: FixedBuf<5> = FixedBuf::new();
+    let word = "abcd";
+    assert!(write!(buf, "{}é", word).is_err());
+    // "abcd" fit; the two-byte 'é' didn't, and wasn't split
+    assert_eq!(buf.as_str(), "abcd");
+}
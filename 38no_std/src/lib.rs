@@ -0,0 +1,51 @@
+//
+// #![no_std]: what's left when the standard library is taken away.
+//
+// Note:
+//
+// 1.) The standard library is three layers. `core` needs nothing from the platform:
+//     Option, Result, iterators, slices, str, fmt, MaybeUninit, atomics. `alloc` adds
+//     the heap: Box, Vec, String, Rc. `std` adds the operating system: files, threads,
+//     sockets, time, println!. A `#![no_std]` crate gets only `core` (and `alloc` if
+//     it asks and someone provides an allocator).
+// 2.) Without a heap there's no Vec, so ArrayVec keeps its elements inline in a
+//     fixed-size array, with the capacity a const generic. Running out of room is an
+//     ordinary error, returned to the caller, not an allocation.
+// 3.) Without println! there's nowhere obvious to write text, but core::fmt still does
+//     all the formatting: anything that implements core::fmt::Write can be the target
+//     of write!. FixedBuf is such a sink, backed by a byte array.
+// 4.) The crate itself never links std. The tests do: when compiled for `cargo test`
+//     we pull in std for the harness, and tests/host.rs uses the crate from an
+//     ordinary std program.
+//
+#![no_std]
+
+#[cfg(test)]
+#[macro_use]
+extern crate std;
+
+pub mod array_vec;
+pub mod fixed_buf;
+
+pub use crate::array_vec::{ArrayVec, CapacityError};
+pub use crate::fixed_buf::FixedBuf;
+
+use core::fmt::{self, Write};
+
+/// Format a reading like "sensor 3: 21.50 C (ok)" into any core::fmt::Write sink.
+// 1.  This is the kind of function firmware is full of: it doesn't know or care whether
+//     the text ends up in a FixedBuf, a UART, or (on a host) a String.
+pub fn write_reading<W: Write>(out: &mut W, sensor: u8, celsius: f32) -> fmt::Result {
+    let status = if (-10.0..=40.0).contains(&celsius) { "ok" } else { "out of range" };
+    write!(out, "sensor {}: {:.2} C ({})", sensor, celsius, status)
+}
+
+#[test]
+fn test_write_reading() {
+    let mut buf: FixedBuf<32> = FixedBuf::new();
+    write_reading(&mut buf, 3, 21.5).unwrap();
+    assert_eq!(buf.as_str(), "sensor 3: 21.50 C (ok)");
+
+    let mut tiny: FixedBuf<8> = FixedBuf::new();
+    assert!(write_reading(&mut tiny, 3, 99.0).is_err());
+}
@@ -0,0 +1,50 @@
+// The host-side harness: an ordinary std program using the no_std crate. std's types
+// and ours meet through core's traits, so the results can be checked against the real
+// Vec and format!.
+use no_std::{write_reading, ArrayVec, FixedBuf};
+use std::fmt::Write;
+
+#[test]
+fn array_vec_matches_vec() {
+    let mut ours: ArrayVec<String, 16> = ArrayVec::new();
+    let mut std_vec: Vec<String> = Vec::new();
+    for i in 0..40 {
+        let word = format!("w{}", i);
+        if i % 3 == 2 {
+            assert_eq!(ours.pop(), std_vec.pop());
+        } else if ours.push(word.clone()).is_ok() {
+            std_vec.push(word);
+        } else {
+            assert_eq!(std_vec.len(), 16);
+        }
+        assert_eq!(&ours[..], &std_vec[..]);
+    }
+}
+
+#[test]
+fn fixed_buf_matches_format() {
+    for &(sensor, celsius) in &[(0, 0.0), (7, -12.25), (255, 39.999)] {
+        let mut ours: FixedBuf<64> = FixedBuf::new();
+        write_reading(&mut ours, sensor, celsius).unwrap();
+
+        let mut string = String::new();
+        write_reading(&mut string, sensor, celsius).unwrap();
+
+        assert_eq!(ours.as_str(), string);
+        assert_eq!(format!("{}", ours), string);
+    }
+}
+
+#[test]
+fn fixed_buf_is_a_write_sink_like_any_other() {
+    fn log<W: Write>(out: &mut W, lines: &[&str]) -> std::fmt::Result {
+        for (i, line) in lines.iter().enumerate() {
+            writeln!(out, "{:02} {}", i, line)?;
+        }
+        Ok(())
+    }
+    let mut buf: FixedBuf<32> = FixedBuf::new();
+    log(&mut buf, &["boot", "ready"]).unwrap();
+    assert_eq!(buf.as_str(), "00 boot\n01 ready\n");
+    assert!(log(&mut buf, &["this line does not fit"]).is_err());
+}
@@ -0,0 +1,47 @@
+// What `panics-and-unwinding --explain` prints: the chapter's notes, in short.
+use rustfun_common::{Explanation, Text, Topic};
+
+pub const EXPLANATION: Explanation = Explanation {
+    chapter: "39panics-and-unwinding",
+    summary: Text {
+        en: "panics, catch_unwind, and poisoned locks",
+        zh: "panic、catch_unwind 和中毒的锁",
+    },
+    topics: &[
+        Topic {
+            title: Text { en: "Result or panic", zh: "Result 还是 panic" },
+            body: Text {
+                en: "Return a Result for failures the caller can do something about, like bad\n\
+                     input; panic for bugs, like a broken invariant.",
+                zh: "调用者能处理的失败 (比如错误的输入) 返回 Result; 程序错误 (比如不变量被破坏)\n\
+                     则 panic。",
+            },
+        },
+        Topic {
+            title: Text { en: "Unwinding", zh: "栈展开" },
+            body: Text {
+                en: "A panic walks back up the stack running every Drop. In a spawned thread,\n\
+                     join() returns Err with the panic's payload.",
+                zh: "panic 沿着调用栈往回走, 执行沿途每个 Drop。在新开的线程里, join() 返回带着\n\
+                     panic 载荷的 Err。",
+            },
+        },
+        Topic {
+            title: Text { en: "catch_unwind and hooks", zh: "catch_unwind 与钩子" },
+            body: Text {
+                en: "catch_unwind turns a panic into an Err at a boundary of your choosing;\n\
+                     set_hook replaces the message printed before unwinding starts.",
+                zh: "catch_unwind 在你选定的边界把 panic 变成 Err; set_hook 替换展开开始前\n\
+                     打印的信息。",
+            },
+        },
+        Topic {
+            title: Text { en: "Poisoned locks", zh: "中毒的锁" },
+            body: Text {
+                en: "A Mutex whose guard is dropped in a panic is poisoned; its data is still\n\
+                     reachable through the PoisonError.",
+                zh: "持有守卫时发生 panic 的 Mutex 会中毒; 数据仍可以通过 PoisonError 取得。",
+            },
+        },
+    ],
+};
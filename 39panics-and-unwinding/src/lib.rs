@@ -0,0 +1,248 @@
+//
+// Panics: when to use them instead of Result, and what happens when one occurs.
+//
+// Note:
+//
+// 1.) Result is for failures the caller should expect and handle: bad input, missing
+//     files, network errors. A panic is for bugs: a broken invariant, an index out of
+//     range, an `expect` on something that "can't" be None. The rule of thumb: if the
+//     caller could reasonably do something about it, return a Result.
+// 2.) By default a panic unwinds: it walks back up the stack, running every Drop on the
+//     way, until it leaves the thread. In the main thread that ends the program; in a
+//     spawned thread, `join()` returns Err with the panic's payload.
+// 3.) `std::panic::catch_unwind` stops unwinding at a chosen point and turns the panic
+//     into an Err. It is meant for boundaries (thread pools, FFI, test harnesses), not
+//     as try/catch for ordinary errors. With `panic = "abort"` in Cargo.toml there's no
+//     unwinding at all, and none of this applies.
+// 4.) Before unwinding, the panic hook runs; the default hook prints "thread 'main'
+//     panicked at ...". `set_hook` replaces it process-wide.
+// 5.) A Mutex whose guard is dropped during a panic becomes poisoned: the data might be
+//     half-updated. Later `lock()` calls return Err, but the data is still reachable
+//     through the PoisonError for code that knows how to check it.
+//
+use std::any::Any;
+use std::panic::{self, PanicHookInfo, UnwindSafe};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::thread;
+
+// 1.  Result vs panic, side by side.
+
+/// Parse a percentage from user input. Bad input is expected here, so: Result.
+pub fn parse_percent(text: &str) -> Result<u8, String> {
+    let value: u8 = text.trim().trim_end_matches('%').parse()
+        .map_err(|_| format!("'{}' is not a number", text))?;
+    if value > 100 {
+        return Err(format!("{}% is more than 100%", value));
+    }
+    Ok(value)
+}
+
+/// Scale `total` by a percentage the program itself computed. A value over 100 here
+/// means a bug somewhere else, so: panic.
+pub fn apply_percent(total: u32, percent: u8) -> u32 {
+    assert!(percent <= 100, "percent out of range: {}", percent);
+    total * percent as u32 / 100
+}
+
+#[test]
+fn test_parse_percent() {
+    assert_eq!(parse_percent("42%"), Ok(42));
+    assert_eq!(parse_percent(" 100 "), Ok(100));
+    assert_eq!(parse_percent("150"), Err("150% is more than 100%".to_string()));
+    assert_eq!(parse_percent("lots"), Err("'lots' is not a number".to_string()));
+}
+
+#[test]
+#[should_panic(expected = "percent out of range: 101")]
+fn test_apply_percent_panics_on_bug() {
+    apply_percent(200, 101);
+}
+
+/// The message a panic was raised with. `panic!("literal")` carries a &'static str,
+/// `panic!("{}", x)` a String; anything else (from `panic_any`) is opaque.
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "Box<dyn Any>".to_string()
+    }
+}
+
+// 2.  catch_unwind turns a panic into a value. The closure must be UnwindSafe: a promise
+//     that if it panics halfway, nobody will observe broken state it left behind.
+//     `&mut` captures aren't UnwindSafe; AssertUnwindSafe overrides the check when
+//     we know better.
+/// Run `f`, converting a panic into Err(message).
+pub fn catch<F: FnOnce() -> R + UnwindSafe, R>(f: F) -> Result<R, String> {
+    panic::catch_unwind(f).map_err(|payload| panic_message(&*payload))
+}
+
+/// Divide each value; integer division by zero panics, and that panic is caught.
+pub fn divide_all(values: &[i32], divisor: i32) -> Result<Vec<i32>, String> {
+    catch(|| values.iter().map(|v| v / divisor).collect())
+}
+
+#[test]
+fn test_catch() {
+    assert_eq!(divide_all(&[10, 20], 5), Ok(vec![2, 4]));
+    assert_eq!(divide_all(&[10, 20], 0), Err("attempt to divide by zero".to_string()));
+    assert_eq!(catch(|| -> () { panic!("code {}", 7) }), Err("code 7".to_string()));
+    assert_eq!(catch(|| -> () { panic::panic_any(7) }), Err("Box<dyn Any>".to_string()));
+}
+
+// 3.  Threads are a natural panic boundary: the panic ends that thread only.
+
+/// Run `f` on its own thread; a panic comes back as Err from join().
+pub fn spawn_checked<F, R>(f: F) -> Result<R, String>
+    where F: FnOnce() -> R + Send + 'static, R: Send + 'static
+{
+    thread::spawn(f).join().map_err(|payload| panic_message(&*payload))
+}
+
+/// Run jobs one after another on a single worker thread. A panicking job must not take
+/// the worker (and every job queued after it) down, so each runs under catch_unwind.
+pub fn run_jobs(jobs: Vec<Box<dyn FnOnce() -> i32 + Send + UnwindSafe>>) -> Vec<Result<i32, String>> {
+    thread::spawn(move || jobs.into_iter().map(catch).collect()).join().unwrap()
+}
+
+#[test]
+fn test_thread_boundaries() {
+    assert_eq!(spawn_checked(|| 6 * 7), Ok(42));
+    let v: Vec<i32> = vec![];
+    assert_eq!(spawn_checked(move || v[3]),
+               Err("index out of bounds: the len is 0 but the index is 3".to_string()));
+
+    let results = run_jobs(vec![
+        Box::new(|| 1),
+        Box::new(|| panic!("job 2 failed")),
+        Box::new(|| 3),
+    ]);
+    assert_eq!(results, vec![Ok(1), Err("job 2 failed".to_string()), Ok(3)]);
+}
+
+// 4.  Unwinding runs destructors, so RAII cleanup happens even on the panic path.
+pub struct Cleanup<'a> {
+    pub name: &'static str,
+    pub log: &'a Mutex<Vec<String>>,
+}
+
+impl<'a> Drop for Cleanup<'a> {
+    fn drop(&mut self) {
+        let how = if thread::panicking() { "while unwinding" } else { "normally" };
+        lock_recovering(self.log).push(format!("{} dropped {}", self.name, how));
+    }
+}
+
+#[test]
+fn test_drop_runs_during_unwinding() {
+    let log = Mutex::new(Vec::new());
+    let result = catch(|| {
+        let _outer = Cleanup { name: "outer", log: &log };
+        {
+            let _done = Cleanup { name: "done", log: &log };
+        }
+        let _inner = Cleanup { name: "inner", log: &log };
+        panic!("boom");
+    });
+    assert_eq!(result, Err("boom".to_string()));
+    assert_eq!(*log.lock().unwrap(), vec![
+        "done dropped normally",
+        "inner dropped while unwinding",
+        "outer dropped while unwinding",
+    ]);
+}
+
+// 5.  A custom panic hook. The hook is global, so this takes care to (a) let only one
+//     caller swap hooks at a time, (b) only capture panics from the calling thread and
+//     pass everything else to the previous hook, and (c) put the previous hook back.
+/// Run `f`, recording "message at file:line" for each panic on this thread instead of
+/// printing it. Returns f's result and the recorded messages.
+pub fn capture_panics<F: FnOnce() -> R + UnwindSafe, R>(f: F) -> (Result<R, String>, Vec<String>) {
+    static HOOK_LOCK: Mutex<()> = Mutex::new(());
+    let _only_us = lock_recovering(&HOOK_LOCK);
+
+    let captured = Arc::new(Mutex::new(Vec::new()));
+    let previous = Arc::new(panic::take_hook());
+    let me = thread::current().id();
+    {
+        let captured = captured.clone();
+        let previous = previous.clone();
+        panic::set_hook(Box::new(move |info: &PanicHookInfo| {
+            if thread::current().id() != me {
+                return (**previous)(info);
+            }
+            let place = info.location().map_or(String::new(), |l| format!(" at {}:{}", l.file(), l.line()));
+            lock_recovering(&captured).push(format!("{}{}", panic_message(info.payload()), place));
+        }));
+    }
+
+    let result = catch(f);
+
+    // Dropping our hook drops its clone of `previous`, leaving us the only owner.
+    drop(panic::take_hook());
+    match Arc::try_unwrap(previous) {
+        Ok(previous) => panic::set_hook(previous),
+        Err(_) => unreachable!("the hook was the only other owner"),
+    }
+    let messages = lock_recovering(&captured).clone();
+    (result, messages)
+}
+
+#[test]
+fn test_capture_panics() {
+    let (result, messages) = capture_panics(|| {
+        let inner = catch(|| -> () { panic!("first") });
+        assert!(inner.is_err());
+        panic!("second {}", 2);
+    });
+    assert_eq!(result, Err::<(), _>("second 2".to_string()));
+    assert_eq!(messages.len(), 2);
+    // the file is relative to wherever cargo was run from: the crate or the workspace
+    assert!(messages[0].starts_with("first at ") && messages[0].contains("src/lib.rs:"),
+            "{}", messages[0]);
+    assert!(messages[1].starts_with("second 2 at ") && messages[1].contains("src/lib.rs:"),
+            "{}", messages[1]);
+
+    let (result, messages) = capture_panics(|| 5);
+    assert_eq!((result, messages), (Ok(5), vec![]));
+}
+
+// 6.  Poisoning. `lock_recovering` is the usual "I know what I'm doing" escape hatch:
+//     take the guard out of the PoisonError and carry on.
+pub fn lock_recovering<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// A transfer that panics between its two halves, leaving the balances inconsistent
+/// and the mutex poisoned.
+pub fn transfer_then_panic(accounts: &Arc<Mutex<(i64, i64)>>, amount: i64) {
+    let accounts = accounts.clone();
+    let _ = thread::spawn(move || {
+        let mut guard = accounts.lock().unwrap();
+        guard.0 -= amount;
+        if amount > 0 {
+            panic!("crashed mid-transfer");
+        }
+        guard.1 += amount;
+    }).join();
+}
+
+#[test]
+fn test_poisoned_mutex() {
+    let accounts = Arc::new(Mutex::new((100, 0)));
+    transfer_then_panic(&accounts, 30);
+
+    assert!(accounts.is_poisoned());
+    assert!(accounts.lock().is_err());
+
+    // The data is still there, including the half-finished update.
+    let mut guard = lock_recovering(&accounts);
+    assert_eq!(*guard, (70, 0));
+    // Repair it, then tell the mutex the data is consistent again.
+    guard.1 += 30;
+    drop(guard);
+    accounts.clear_poison();
+    assert_eq!(*accounts.lock().unwrap(), (70, 30));
+}
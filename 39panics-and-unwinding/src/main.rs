@@ -0,0 +1,49 @@
+use panics_and_unwinding::*;
+use std::panic;
+use std::sync::{Arc, Mutex};
+
+/// With --deterministic: report panics as the default hook does, but without the
+/// thread's id, which changes from run to run.
+fn quiet_thread_ids() {
+    panic::set_hook(Box::new(|info| {
+        let thread = std::thread::current();
+        let location = info.location().map(|l| l.to_string()).unwrap_or_default();
+        eprintln!("thread '{}' panicked at {}:\n{}", thread.name().unwrap_or("<unnamed>"),
+                  location, panic_message(info.payload()));
+    }));
+}
+
+mod explain;
+
+fn main() {
+    rustfun_common::init_logging();
+    explain::EXPLANATION.on_request();
+    if rustfun_common::deterministic::is_on() {
+        quiet_thread_ids();
+    }
+
+    // 1.  Result for expected failures...
+    for input in &["42%", "150", "lots"] {
+        match parse_percent(input) {
+            Ok(p) => println!("{:>5} -> {}% of 200 is {}", input, p, apply_percent(200, p)),
+            Err(e) => println!("{:>5} -> error: {}", input, e),
+        }
+    }
+
+    // 2.  ...and panics for bugs, caught at a boundary. The default hook still prints
+    //     "thread 'main' panicked at ..." to stderr before we see the Err.
+    println!("divide_all by 0: {:?}", divide_all(&[1, 2, 3], 0));
+
+    // 3.  A custom hook keeps stderr quiet and records where the panic happened.
+    let (result, messages) = capture_panics(|| apply_percent(10, 200));
+    println!("captured: {:?} {:?}", result, messages);
+
+    // 4.  A worker that survives a panicking job.
+    let results = run_jobs(vec![Box::new(|| 1), Box::new(|| panic!("bad job")), Box::new(|| 3)]);
+    println!("jobs: {:?}", results);
+
+    // 5.  Poisoning, and recovering from it.
+    let accounts = Arc::new(Mutex::new((100, 0)));
+    transfer_then_panic(&accounts, 30);
+    println!("poisoned: {}, balances: {:?}", accounts.is_poisoned(), *lock_recovering(&accounts));
+}
@@ -0,0 +1,35 @@
+// Run the chapter's demo binary and check the parts of its output that are deterministic.
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+// main panics on purpose several times; every panic is caught, so it still exits 0,
+// and the default hook's reports land on stderr.
+#[test]
+fn catches_its_own_panics() {
+    Command::cargo_bin("panics-and-unwinding").unwrap().env("RUST_BACKTRACE", "0").assert()
+        .success()
+        .stdout(predicate::str::starts_with("  42% -> 42% of 200 is 84
+  150 -> error: 150% is more than 100%
+ lots -> error: 'lots' is not a number
+divide_all by 0: Err(\"attempt to divide by zero\")
+captured: Err(\"percent out of range: 200\") [\"percent out of range: 200 at ")
+            // the path in between depends on whether the crate or the workspace was built
+            .and(predicate::str::ends_with("src/lib.rs:43\"]
+jobs: [Ok(1), Err(\"bad job\"), Ok(3)]
+poisoned: true, balances: (70, 0)
+")))
+        .stderr(predicate::str::contains("attempt to divide by zero")
+            .and(predicate::str::contains("bad job"))
+            .and(predicate::str::contains("crashed mid-transfer")));
+}
+
+// The default hook's reports name the thread by id, as in "thread 'main' (10103)";
+// --deterministic leaves the id out.
+#[test]
+fn reports_panics_without_thread_ids_when_deterministic() {
+    Command::cargo_bin("panics-and-unwinding").unwrap().arg("--deterministic").assert()
+        .success()
+        .stderr(predicate::str::contains("thread 'main' panicked at ")
+            .and(predicate::str::contains("thread '<unnamed>' panicked at "))
+            .and(predicate::str::is_match(r"thread '[^']*' \(").unwrap().not()));
+}
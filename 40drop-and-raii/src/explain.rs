@@ -0,0 +1,38 @@
+// What `drop-and-raii --explain` prints: the chapter's notes, in short.
+use rustfun_common::{Explanation, Text, Topic};
+
+pub const EXPLANATION: Explanation = Explanation {
+    chapter: "40drop-and-raii",
+    summary: Text {
+        en: "Drop, guards, and the order values are dropped in",
+        zh: "Drop、守卫, 以及值被丢弃的顺序",
+    },
+    topics: &[
+        Topic {
+            title: Text { en: "RAII", zh: "RAII" },
+            body: Text {
+                en: "A resource belongs to a value: acquired when it's created, released by Drop.\n\
+                     Cleanup runs on every way out of a scope, panics included.",
+                zh: "资源属于一个值: 创建时获取, 由 Drop 释放。离开作用域的每条路径上都会清理,\n\
+                     包括 panic。",
+            },
+        },
+        Topic {
+            title: Text { en: "Drop order", zh: "释放顺序" },
+            body: Text {
+                en: "Locals drop in reverse order of declaration, fields in declaration order,\n\
+                     temporaries at the end of their statement; a moved-out value isn't dropped.",
+                zh: "局部变量按声明的相反顺序释放, 字段按声明顺序, 临时值在语句结束时;\n\
+                     被移走的值不会在原处释放。",
+            },
+        },
+        Topic {
+            title: Text { en: "Scope guards", zh: "作用域守卫" },
+            body: Text {
+                en: "A guard runs a closure when the scope ends, like Go's defer, unless it's\n\
+                     dismissed first.",
+                zh: "守卫在作用域结束时运行一个闭包, 类似 Go 的 defer, 除非事先解除。",
+            },
+        },
+    ],
+};
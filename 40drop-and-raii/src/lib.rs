@@ -0,0 +1,264 @@
+//
+// Drop and RAII: cleanup that happens because a value goes out of scope.
+//
+// Note:
+//
+// 1.) RAII ("resource acquisition is initialization") ties a resource to a value: the
+//     constructor acquires it, Drop releases it. Files, locks, sockets and heap memory
+//     in std all work this way, so cleanup can't be forgotten and runs on every path
+//     out of a scope: return, `?`, break, or a panic unwinding.
+// 2.) The order is fixed and worth knowing:
+//     - local variables drop in reverse order of declaration (last in, first out);
+//     - struct and tuple fields drop in declaration order, after the struct's own Drop;
+//     - Vec and array elements drop front to back;
+//     - temporaries drop at the end of the statement that created them;
+//     - a value that was moved out of a variable is not dropped there at all.
+// 3.) A scope guard is RAII for arbitrary code: "run this closure when the scope ends",
+//     like `defer` in Go, unless it's dismissed first, e.g. on the success path.
+//
+use std::cell::RefCell;
+use std::ops::{Deref, DerefMut};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+/// A shared, append-only record of events, for watching drops happen.
+pub type Log = Rc<RefCell<Vec<String>>>;
+
+pub fn new_log() -> Log {
+    Rc::new(RefCell::new(Vec::new()))
+}
+
+/// Records "drop NAME" in its log when dropped.
+pub struct Noisy {
+    pub name: String,
+    log: Log,
+}
+
+impl Noisy {
+    pub fn new(name: &str, log: &Log) -> Noisy {
+        Noisy { name: name.to_string(), log: log.clone() }
+    }
+}
+
+impl Drop for Noisy {
+    fn drop(&mut self) {
+        self.log.borrow_mut().push(format!("drop {}", self.name));
+    }
+}
+
+// 1.  A guard owns a value and a closure. On drop, the closure gets the value back.
+//     Both live in an Option so `dismiss` can take them out, leaving Drop nothing to do.
+pub struct ScopeGuard<T, F: FnOnce(T)> {
+    inner: Option<(T, F)>,
+}
+
+/// Run `on_drop(value)` when the guard goes out of scope.
+pub fn guard<T, F: FnOnce(T)>(value: T, on_drop: F) -> ScopeGuard<T, F> {
+    ScopeGuard { inner: Some((value, on_drop)) }
+}
+
+/// Run `f` when the returned guard goes out of scope.
+pub fn defer<F: FnOnce()>(f: F) -> ScopeGuard<(), impl FnOnce(())> {
+    guard((), move |()| f())
+}
+
+impl<T, F: FnOnce(T)> ScopeGuard<T, F> {
+    /// Disarm the guard and take the value back; the closure never runs.
+    // An associated function rather than a method, like Box::leak, so that it can't be
+    // confused with a method of T reached through Deref.
+    pub fn dismiss(mut this: ScopeGuard<T, F>) -> T {
+        let (value, _on_drop) = this.inner.take().expect("guard already dismissed");
+        value
+    }
+}
+
+impl<T, F: FnOnce(T)> Deref for ScopeGuard<T, F> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.inner.as_ref().expect("guard already dismissed").0
+    }
+}
+
+impl<T, F: FnOnce(T)> DerefMut for ScopeGuard<T, F> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.inner.as_mut().expect("guard already dismissed").0
+    }
+}
+
+impl<T, F: FnOnce(T)> Drop for ScopeGuard<T, F> {
+    fn drop(&mut self) {
+        if let Some((value, on_drop)) = self.inner.take() {
+            on_drop(value);
+        }
+    }
+}
+
+/// `defer!(expr);` runs `expr` at the end of the enclosing scope.
+// The guard needs a name: `let _ = ...` would drop it, and run the code, immediately.
+#[macro_export]
+macro_rules! defer {
+    ($($body:tt)*) => {
+        let _guard = $crate::defer(|| { $($body)*; });
+    };
+}
+
+// 2.  A timing guard: measure a scope by creating a Timer at its top. The report
+//     callback is a parameter so tests can capture it; main just prints.
+pub struct Timer<F: FnMut(&str, Duration)> {
+    label: &'static str,
+    start: Instant,
+    report: F,
+}
+
+impl<F: FnMut(&str, Duration)> Timer<F> {
+    pub fn start(label: &'static str, report: F) -> Timer<F> {
+        Timer { label, start: Instant::now(), report }
+    }
+}
+
+impl<F: FnMut(&str, Duration)> Drop for Timer<F> {
+    fn drop(&mut self) {
+        (self.report)(self.label, self.start.elapsed());
+    }
+}
+
+/// An example of guards on the error path: copy `items` into `out` all-or-nothing.
+/// If a negative item turns up partway through, the guard rolls `out` back.
+pub fn append_all_or_nothing(out: &mut Vec<i32>, items: &[i32]) -> Result<(), String> {
+    let original_len = out.len();
+    let mut out = guard(out, |out| out.truncate(original_len));
+    for &item in items {
+        if item < 0 {
+            return Err(format!("negative item {}", item));
+        }
+        out.push(item);
+    }
+    ScopeGuard::dismiss(out);
+    Ok(())
+}
+
+#[cfg(test)]
+fn events(log: &Log) -> Vec<String> {
+    std::mem::take(&mut *log.borrow_mut())
+}
+
+#[test]
+fn test_locals_drop_in_reverse() {
+    let log = new_log();
+    {
+        let _a = Noisy::new("a", &log);
+        let _b = Noisy::new("b", &log);
+        let _c = Noisy::new("c", &log);
+    }
+    assert_eq!(events(&log), vec!["drop c", "drop b", "drop a"]);
+}
+
+#[test]
+fn test_fields_drop_in_declaration_order() {
+    struct Pair {
+        _first: Noisy,
+        _second: Noisy,
+        log: Log,
+    }
+    impl Drop for Pair {
+        fn drop(&mut self) {
+            self.log.borrow_mut().push("drop Pair".to_string());
+        }
+    }
+    let log = new_log();
+    drop(Pair { _first: Noisy::new("first", &log), _second: Noisy::new("second", &log), log: log.clone() });
+    // the struct's own Drop runs first, while its fields are still intact
+    assert_eq!(events(&log), vec!["drop Pair", "drop first", "drop second"]);
+
+    drop((Noisy::new("t0", &log), Noisy::new("t1", &log)));
+    assert_eq!(events(&log), vec!["drop t0", "drop t1"]);
+}
+
+#[test]
+fn test_collections_drop_front_to_back() {
+    let log = new_log();
+    let v: Vec<Noisy> = ["x", "y", "z"].iter().map(|n| Noisy::new(n, &log)).collect();
+    drop(v);
+    assert_eq!(events(&log), vec!["drop x", "drop y", "drop z"]);
+
+    let mut v: Vec<Noisy> = ["p", "q", "r"].iter().map(|n| Noisy::new(n, &log)).collect();
+    v.remove(1);
+    assert_eq!(events(&log), vec!["drop q"]);
+    v.truncate(0);
+    assert_eq!(events(&log), vec!["drop p", "drop r"]);
+}
+
+#[test]
+fn test_moves_temporaries_and_underscore() {
+    let log = new_log();
+    {
+        let a = Noisy::new("a", &log);
+        let b = a; // moved: only `b` will drop it
+        log.borrow_mut().push(format!("moved {}", b.name));
+        let _ = Noisy::new("underscore", &log); // `_` doesn't bind: dropped right here
+        let len = Noisy::new("temporary", &log).name.len(); // dropped at the `;`
+        log.borrow_mut().push(format!("len {}", len));
+        let _kept = Noisy::new("kept", &log); // `_kept` does bind: lives to scope end
+        std::mem::forget(Noisy::new("forgotten", &log)); // leaked: never dropped
+    }
+    assert_eq!(events(&log), vec![
+        "moved a", "drop underscore", "drop temporary", "len 9", "drop kept", "drop a",
+    ]);
+}
+
+#[test]
+fn test_scope_guard() {
+    let log = new_log();
+    {
+        let _g = defer(|| log.borrow_mut().push("deferred".to_string()));
+        log.borrow_mut().push("body".to_string());
+    }
+    assert_eq!(events(&log), vec!["body", "deferred"]);
+
+    // guards are locals too, so several run in reverse order
+    {
+        defer!(log.borrow_mut().push("first registered".to_string()));
+        let _g = defer(|| log.borrow_mut().push("second registered".to_string()));
+    }
+    assert_eq!(events(&log), vec!["second registered", "first registered"]);
+
+    let g = guard(41, |n| log.borrow_mut().push(format!("cleanup {}", n)));
+    assert_eq!(*g + 1, 42);
+    assert_eq!(ScopeGuard::dismiss(g), 41);
+    assert!(events(&log).is_empty());
+}
+
+#[test]
+fn test_scope_guard_runs_on_panic() {
+    use std::panic;
+    let log = new_log();
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        let _g = defer(|| log.borrow_mut().push("cleaned up".to_string()));
+        panic!("failure");
+    }));
+    assert!(result.is_err());
+    assert_eq!(events(&log), vec!["cleaned up"]);
+}
+
+#[test]
+fn test_rollback_guard() {
+    let mut out = vec![1];
+    assert_eq!(append_all_or_nothing(&mut out, &[2, 3]), Ok(()));
+    assert_eq!(out, vec![1, 2, 3]);
+    assert_eq!(append_all_or_nothing(&mut out, &[4, -5, 6]), Err("negative item -5".to_string()));
+    assert_eq!(out, vec![1, 2, 3]);
+}
+
+#[test]
+fn test_timer() {
+    let log = new_log();
+    {
+        let _outer = Timer::start("outer", |label, _| log.borrow_mut().push(label.to_string()));
+        let _inner = Timer::start("inner", |label, elapsed| {
+            assert!(elapsed >= Duration::from_millis(5));
+            log.borrow_mut().push(label.to_string());
+        });
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    assert_eq!(events(&log), vec!["inner", "outer"]);
+}
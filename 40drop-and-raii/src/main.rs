@@ -0,0 +1,35 @@
+use drop_and_raii::{defer, new_log, Noisy, Timer};
+use std::thread;
+use std::time::Duration;
+
+mod explain;
+
+fn main() {
+    rustfun_common::init_logging();
+    explain::EXPLANATION.on_request();
+
+    // 1.  Time a scope: the report happens when `_timer` drops at the closing brace.
+    //     The time differs a little every run, so --deterministic only says it was enough.
+    {
+        let _timer = Timer::start("sleepy scope", |label, elapsed| {
+            if rustfun_common::deterministic::is_on() {
+                println!("{} took at least 20ms", label)
+            } else {
+                println!("{} took {:?}", label, elapsed)
+            }
+        });
+        thread::sleep(Duration::from_millis(20));
+    }
+
+    // 2.  Watch the drop order of locals, a struct-like tuple, and a Vec.
+    let log = new_log();
+    {
+        defer!(println!("deferred: runs last, since it was declared first"));
+        let _a = Noisy::new("local a", &log);
+        let _tuple = (Noisy::new("tuple.0", &log), Noisy::new("tuple.1", &log));
+        let _vec: Vec<Noisy> = ["vec[0]", "vec[1]"].iter().map(|n| Noisy::new(n, &log)).collect();
+    }
+    for event in log.borrow().iter() {
+        println!("{}", event);
+    }
+}
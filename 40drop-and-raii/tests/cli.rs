@@ -0,0 +1,25 @@
+// Run the chapter's demo binary and check the parts of its output that are deterministic.
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+#[test]
+fn prints_the_drop_order() {
+    Command::cargo_bin("drop-and-raii").unwrap().assert()
+        .success()
+        .stdout(predicate::str::starts_with("sleepy scope took ")
+            .and(predicate::str::ends_with("\
+deferred: runs last, since it was declared first
+drop vec[0]
+drop vec[1]
+drop tuple.0
+drop tuple.1
+drop local a
+")));
+}
+
+#[test]
+fn prints_no_timing_when_deterministic() {
+    Command::cargo_bin("drop-and-raii").unwrap().arg("--deterministic").assert()
+        .success()
+        .stdout(predicate::str::starts_with("sleepy scope took at least 20ms\n"));
+}
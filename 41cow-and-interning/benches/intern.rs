@@ -0,0 +1,63 @@
+// Interned symbols against plain Strings, on the kind of work a compiler's front end does
+// with identifiers: keep every occurrence, count the distinct ones, compare pairs.
+//
+//   $ cargo bench
+//
+// Building the interned list costs a hash per word either way; the win shows up in
+// memory (one copy per distinct word) and in everything done afterwards.
+use cow_and_interning::{normalize_whitespace, Interner, Symbol};
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use std::collections::HashSet;
+
+// A few thousand words from a small vocabulary, so most of them repeat.
+fn words() -> Vec<String> {
+    let vocabulary = ["let", "mut", "fn", "self", "value", "index", "count", "buffer",
+                      "iter", "map", "collect", "result", "error", "into", "clone", "len"];
+    (0..5_000usize).map(|i| vocabulary[(i * 7 + i / 3) % vocabulary.len()].to_string()).collect()
+}
+
+// Naive: every occurrence is its own String, compared byte by byte.
+fn naive(words: &[String]) -> usize {
+    let owned: Vec<String> = words.to_vec();
+    let distinct: HashSet<String> = owned.iter().cloned().collect();
+    let repeats = owned.windows(2).filter(|w| w[0] == w[1]).count();
+    distinct.len() + repeats
+}
+
+// Interned: every occurrence is a 4-byte Symbol, compared as an integer.
+fn interned(words: &[String]) -> usize {
+    let mut interner = Interner::new();
+    let symbols: Vec<Symbol> = words.iter().map(|w| interner.intern(w)).collect();
+    let distinct: HashSet<Symbol> = symbols.iter().cloned().collect();
+    let repeats = symbols.windows(2).filter(|w| w[0] == w[1]).count();
+    distinct.len() + repeats
+}
+
+fn bench_interning(c: &mut Criterion) {
+    let words = words();
+    assert_eq!(naive(&words), interned(&words));
+
+    let mut group = c.benchmark_group("identifiers");
+    group.throughput(Throughput::Elements(words.len() as u64));
+    group.bench_function("naive String", |b| b.iter(|| naive(&words)));
+    group.bench_function("interned", |b| b.iter(|| interned(&words)));
+    group.finish();
+}
+
+// Cow on input that is already clean: no allocation, against always building a String.
+fn bench_normalize(c: &mut Criterion) {
+    let line = "a line that is already normalized and needs no changes at all";
+    let mut group = c.benchmark_group("normalize clean input");
+    group.bench_function("Cow", |b| b.iter(|| normalize_whitespace(line).len()));
+    group.bench_function("always String", |b| {
+        b.iter(|| line.split_whitespace().collect::<Vec<_>>().join(" ").len())
+    });
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = rustfun_benches::criterion();
+    targets = bench_interning, bench_normalize
+}
+criterion_main!(benches);
@@ -0,0 +1,37 @@
+// What `cow-and-interning --explain` prints: the chapter's notes, in short.
+use rustfun_common::{Explanation, Text, Topic};
+
+pub const EXPLANATION: Explanation = Explanation {
+    chapter: "41cow-and-interning",
+    summary: Text {
+        en: "Cow<str> and a string interner",
+        zh: "Cow<str> 和字符串驻留",
+    },
+    topics: &[
+        Topic {
+            title: Text { en: "Cow<str>", zh: "Cow<str>" },
+            body: Text {
+                en: "Borrowed when the input comes back unchanged, Owned only when something\n\
+                     changed: most calls allocate nothing.",
+                zh: "输入原样返回时是 Borrowed, 只有真正修改时才是 Owned: 大多数调用不分配内存。",
+            },
+        },
+        Topic {
+            title: Text { en: "Interning", zh: "字符串驻留" },
+            body: Text {
+                en: "Each distinct string is stored once and named by a small Copy Symbol, so\n\
+                     comparing and hashing are integer operations.",
+                zh: "每个不同的字符串只存一次, 用一个小的 Copy 类型 Symbol 表示, 比较和哈希\n\
+                     都成了整数运算。",
+            },
+        },
+        Topic {
+            title: Text { en: "One arena", zh: "一块内存区" },
+            body: Text {
+                en: "The interner keeps all the text in one String, and a Symbol indexes a table\n\
+                     of spans in it.",
+                zh: "驻留器把所有文本放在一个 String 里, Symbol 是其中片段表的下标。",
+            },
+        },
+    ],
+};
@@ -0,0 +1,125 @@
+// A string interner: each distinct string stored once in an arena, named by a Symbol.
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
+
+/// A handle for an interned string: 4 bytes, Copy, and compared as an integer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+#[derive(Default)]
+pub struct Interner {
+    arena: String,
+    spans: Vec<Range<usize>>,
+    // 1.  Keyed by the string's hash, not the string, so the text isn't stored twice.
+    //     Different strings can share a hash, so each bucket is a (short) list of
+    //     candidates, checked against the arena.
+    by_hash: HashMap<u64, Vec<Symbol>>,
+}
+
+fn hash_of(text: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    text.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl Interner {
+    pub fn new() -> Interner {
+        Interner::default()
+    }
+
+    /// The symbol for `text`, adding it if it hasn't been seen before.
+    pub fn intern(&mut self, text: &str) -> Symbol {
+        let hash = hash_of(text);
+        if let Some(symbol) = self.find(hash, text) {
+            return symbol;
+        }
+        let start = self.arena.len();
+        self.arena.push_str(text);
+        let symbol = Symbol(self.spans.len() as u32);
+        self.spans.push(start..self.arena.len());
+        self.by_hash.entry(hash).or_default().push(symbol);
+        symbol
+    }
+
+    /// The symbol for `text` if it has been interned, without adding it.
+    pub fn get(&self, text: &str) -> Option<Symbol> {
+        self.find(hash_of(text), text)
+    }
+
+    fn find(&self, hash: u64, text: &str) -> Option<Symbol> {
+        self.by_hash.get(&hash)?.iter().cloned().find(|&s| self.resolve(s) == text)
+    }
+
+    /// The text of `symbol`. Panics if it came from a different interner and is out of
+    /// range; a symbol from another interner that happens to be in range gives the
+    /// wrong string, so don't mix them.
+    // 2.  The returned &str borrows the interner, so it can't outlive it, and the
+    //     interner can't be changed (and its arena reallocated) while it's in use.
+    pub fn resolve(&self, symbol: Symbol) -> &str {
+        &self.arena[self.spans[symbol.index()].clone()]
+    }
+
+    /// Number of distinct strings.
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// Bytes of text stored, counting each distinct string once.
+    pub fn arena_bytes(&self) -> usize {
+        self.arena.len()
+    }
+}
+
+#[test]
+fn test_intern_and_resolve() {
+    let mut interner = Interner::new();
+    let a = interner.intern("apple");
+    let b = interner.intern("banana");
+    let a2 = interner.intern("apple");
+    assert_eq!(a, a2);
+    assert_ne!(a, b);
+    assert_eq!(interner.resolve(a), "apple");
+    assert_eq!(interner.resolve(b), "banana");
+    assert_eq!(interner.len(), 2);
+    assert_eq!(interner.arena_bytes(), "applebanana".len());
+    assert_eq!(interner.get("banana"), Some(b));
+    assert_eq!(interner.get("cherry"), None);
+    assert_eq!(interner.len(), 2);
+}
+
+#[test]
+fn test_empty_and_overlapping_strings() {
+    let mut interner = Interner::new();
+    let empty = interner.intern("");
+    let ab = interner.intern("ab");
+    let a = interner.intern("a");
+    // "a" is a prefix of "ab" in the arena, but it's still its own symbol
+    assert_ne!(a, ab);
+    assert_eq!(interner.resolve(empty), "");
+    assert_eq!(interner.resolve(a), "a");
+    assert_eq!(interner.intern(""), empty);
+}
+
+#[test]
+fn test_many_words() {
+    let text = "the quick brown fox jumps over the lazy dog the end";
+    let mut interner = Interner::new();
+    let symbols: Vec<Symbol> = text.split(' ').map(|w| interner.intern(w)).collect();
+    assert_eq!(symbols.len(), 11);
+    assert_eq!(interner.len(), 9);
+    assert_eq!(symbols[0], symbols[6]);
+    let back: Vec<&str> = symbols.iter().map(|&s| interner.resolve(s)).collect();
+    assert_eq!(back.join(" "), text);
+}
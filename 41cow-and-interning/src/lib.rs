@@ -0,0 +1,22 @@
+//
+// Avoiding string copies: Cow<str> for "copy only if you must", and interning for
+// "store each distinct string once".
+//
+// Note:
+//
+// 1.) `Cow<'a, str>` ("clone on write") is either Borrowed(&'a str) or Owned(String).
+//     A function that usually returns its input unchanged can return Borrowed and
+//     allocate nothing, and only build an Owned String when it really changes
+//     something. Callers use it like a &str either way, through Deref.
+// 2.) An interner maps each distinct string to a small Copy id (a Symbol). After that,
+//     comparing, hashing and copying are integer operations, and each distinct string
+//     is stored only once. Compilers do this for identifiers.
+// 3.) Our interner keeps all the text in one String (the arena) and a Symbol is an index
+//     into a table of spans in it. The lookup table is keyed by hash, so it doesn't
+//     need a second copy of every string either.
+//
+pub mod interner;
+pub mod normalize;
+
+pub use crate::interner::{Interner, Symbol};
+pub use crate::normalize::{escape_html, normalize_whitespace};
@@ -0,0 +1,42 @@
+use cow_and_interning::{escape_html, normalize_whitespace, Interner};
+use std::borrow::Cow;
+
+fn describe(cow: Cow<str>) -> String {
+    let how = match cow {
+        Cow::Borrowed(_) => "borrowed",
+        Cow::Owned(_) => "owned",
+    };
+    format!("{:?} ({})", cow, how)
+}
+
+mod explain;
+
+fn main() {
+    rustfun_common::init_logging();
+    explain::EXPLANATION.on_request();
+
+    // 1.  Cow: only the inputs that actually change get a new String.
+    for line in &["already clean", "  needs   trimming ", "tabs\tand\nnewlines"] {
+        println!("{:<24} -> {}", format!("{:?}", line), describe(normalize_whitespace(line)));
+    }
+    for text in &["plain text", "<b>bold</b> & more"] {
+        println!("{:<24} -> {}", format!("{:?}", text), describe(escape_html(text)));
+    }
+
+    // 2.  Interning the words of a text: each distinct word stored once.
+    let text = "the cat sat on the mat and the cat saw the rat on the mat";
+    let mut interner = Interner::new();
+    let symbols: Vec<_> = text.split(' ').map(|w| interner.intern(w)).collect();
+    println!();
+    println!("{} words, {} distinct", symbols.len(), interner.len());
+    // a String is a 3-word header (pointer, capacity, length) plus its heap bytes
+    let as_strings = symbols.len() * std::mem::size_of::<String>() + text.len() - (symbols.len() - 1);
+    let as_symbols = symbols.len() * std::mem::size_of_val(&symbols[0]) + interner.arena_bytes();
+    println!("as Strings: {} bytes; as Symbols: {} bytes plus the interner's tables", as_strings, as_symbols);
+    for (i, word) in ["the", "cat", "dog"].iter().enumerate() {
+        match interner.get(word) {
+            Some(symbol) => println!("{}. {:?} is {:?}", i + 1, word, symbol),
+            None => println!("{}. {:?} was never seen", i + 1, word),
+        }
+    }
+}
@@ -0,0 +1,97 @@
+// Cow<str>: return the input as-is when possible, a new String only when needed.
+use std::borrow::Cow;
+
+/// Trim, and collapse every run of whitespace into a single space.
+// 1.  First check whether there's anything to do. Most real input is already clean, so
+//     the common case is one scan and no allocation.
+pub fn normalize_whitespace(text: &str) -> Cow<'_, str> {
+    let mut previous_space = true; // true so that leading whitespace counts as a change
+    let clean = text.chars().all(|c| {
+        let ok = c == ' ' && !previous_space || !c.is_whitespace();
+        previous_space = c.is_whitespace();
+        ok
+    }) && !text.ends_with(' ');
+    if clean {
+        return Cow::Borrowed(text);
+    }
+    // 2.  split_whitespace already skips leading, trailing and repeated whitespace.
+    let mut out = String::with_capacity(text.len());
+    for word in text.split_whitespace() {
+        if !out.is_empty() {
+            out.push(' ');
+        }
+        out.push_str(word);
+    }
+    Cow::Owned(out)
+}
+
+/// Escape the characters that are special in HTML.
+// 3.  Another common shape: find the first character that needs work; if there is
+//     none, borrow. Otherwise copy the clean prefix once and continue from there.
+pub fn escape_html(text: &str) -> Cow<'_, str> {
+    let needs_escape = |c: char| matches!(c, '<' | '>' | '&' | '"' | '\'');
+    let first = match text.find(needs_escape) {
+        None => return Cow::Borrowed(text),
+        Some(i) => i,
+    };
+    let mut out = String::with_capacity(text.len() + 16);
+    out.push_str(&text[..first]);
+    for c in text[first..].chars() {
+        match c {
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '&' => out.push_str("&amp;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    Cow::Owned(out)
+}
+
+#[cfg(test)]
+#[allow(clippy::ptr_arg)] // the whole point is to look at the Cow itself
+fn is_borrowed(cow: &Cow<str>) -> bool {
+    match cow {
+        Cow::Borrowed(_) => true,
+        Cow::Owned(_) => false,
+    }
+}
+
+#[test]
+fn test_normalize_whitespace() {
+    for clean in &["", "a", "hello world", "one two three"] {
+        let result = normalize_whitespace(clean);
+        assert!(is_borrowed(&result), "{:?} should be borrowed", clean);
+        assert_eq!(result, *clean);
+    }
+    for &(dirty, expected) in &[(" a", "a"), ("a ", "a"), ("a  b", "a b"), ("a\tb", "a b"),
+                                ("\n x \r\n y \t", "x y"), ("   ", "")] {
+        let result = normalize_whitespace(dirty);
+        assert!(!is_borrowed(&result), "{:?} should be owned", dirty);
+        assert_eq!(result, expected);
+    }
+}
+
+#[test]
+fn test_escape_html() {
+    let plain = "nothing to see here";
+    let result = escape_html(plain);
+    assert!(is_borrowed(&result));
+    // borrowed really means the very same bytes, not a copy
+    assert_eq!(result.as_ptr(), plain.as_ptr());
+
+    assert_eq!(escape_html("a < b && c > \"d\""), "a &lt; b &amp;&amp; c &gt; &quot;d&quot;");
+    assert_eq!(escape_html("it's"), "it&#39;s");
+}
+
+#[test]
+fn test_cow_into_owned() {
+    // A Cow can be turned into a String when ownership is needed after all; for
+    // Borrowed that's the point where the copy happens.
+    let owned: String = normalize_whitespace("already clean").into_owned();
+    assert_eq!(owned, "already clean");
+    let mut cow = escape_html("x");
+    cow.to_mut().push('!'); // to_mut copies a Borrowed value first
+    assert_eq!(cow, "x!");
+}
@@ -0,0 +1,13 @@
+// Run the chapter's demo binary and check the parts of its output that are deterministic.
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+#[test]
+fn prints_the_demo() {
+    Command::cargo_bin("cow-and-interning").unwrap().assert()
+        .success()
+        .stdout(predicate::str::contains("\"already clean\"          -> \"already clean\" (borrowed)\n")
+            .and(predicate::str::contains("\"  needs   trimming \"    -> \"needs trimming\" (owned)\n"))
+            .and(predicate::str::contains("15 words, 8 distinct\n"))
+            .and(predicate::str::ends_with("3. \"dog\" was never seen\n")));
+}
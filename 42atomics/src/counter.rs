@@ -0,0 +1,136 @@
+// Counters: a correct lock-free one, a broken one, and a compare-and-swap loop.
+use crate::sync::{AtomicUsize, Ordering};
+
+/// A counter any number of threads can bump at once.
+// 1.  fetch_add is a single read-modify-write, so no increment is ever lost. Relaxed is
+//     enough: the count doesn't guard any other data, we only want the total.
+#[derive(Debug, Default)]
+pub struct Counter {
+    count: AtomicUsize,
+}
+
+impl Counter {
+    pub fn new() -> Counter {
+        Counter::default()
+    }
+
+    /// Add one and return the previous value.
+    pub fn increment(&self) -> usize {
+        self.count.fetch_add(1, Ordering::Relaxed)
+    }
+
+    pub fn get(&self) -> usize {
+        self.count.load(Ordering::Relaxed)
+    }
+}
+
+/// The same counter done wrong: a separate load and store.
+// 2.  There's no data race here in Rust's sense (every access is atomic, so this is
+//     not undefined behaviour), but there is a race condition: two threads can both
+//     load 5 and both store 6, and one increment is lost. tests/loom.rs shows loom
+//     finding exactly that interleaving.
+#[derive(Debug, Default)]
+pub struct RacyCounter {
+    count: AtomicUsize,
+}
+
+impl RacyCounter {
+    pub fn new() -> RacyCounter {
+        RacyCounter::default()
+    }
+
+    pub fn increment(&self) {
+        let current = self.count.load(Ordering::Relaxed);
+        self.count.store(current + 1, Ordering::Relaxed);
+    }
+
+    pub fn get(&self) -> usize {
+        self.count.load(Ordering::Relaxed)
+    }
+}
+
+/// A counter that never goes above `limit`, e.g. for "at most N connections".
+// 3.  fetch_add can't refuse, so this uses the general tool: load, compute, then
+//     compare_exchange, which only stores if the value is still what we loaded. If
+//     another thread got there first, it fails, returns the new value, and we retry.
+//     `_weak` may also fail spuriously, which is fine in a loop and cheaper on ARM.
+#[derive(Debug)]
+pub struct BoundedCounter {
+    count: AtomicUsize,
+    limit: usize,
+}
+
+impl BoundedCounter {
+    pub fn new(limit: usize) -> BoundedCounter {
+        BoundedCounter { count: AtomicUsize::new(0), limit }
+    }
+
+    /// Add one, returning the new count, or Err(limit) if the counter is full.
+    pub fn try_increment(&self) -> Result<usize, usize> {
+        let mut current = self.count.load(Ordering::Relaxed);
+        loop {
+            if current >= self.limit {
+                return Err(self.limit);
+            }
+            match self.count.compare_exchange_weak(current, current + 1, Ordering::Relaxed, Ordering::Relaxed) {
+                Ok(_) => return Ok(current + 1),
+                Err(actual) => current = actual,
+            }
+        }
+    }
+
+    /// Give one back. Panics if the count is already zero.
+    pub fn decrement(&self) {
+        let previous = self.count.fetch_sub(1, Ordering::Relaxed);
+        assert!(previous > 0, "decrement below zero");
+    }
+
+    pub fn get(&self) -> usize {
+        self.count.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(all(test, not(loom)))]
+fn hammer<F: Fn() + Sync>(threads: usize, per_thread: usize, f: F) {
+    std::thread::scope(|s| {
+        for _ in 0..threads {
+            s.spawn(|| for _ in 0..per_thread { f() });
+        }
+    });
+}
+
+#[test]
+fn test_counter() {
+    let counter = Counter::new();
+    assert_eq!(counter.increment(), 0);
+    assert_eq!(counter.increment(), 1);
+    hammer(4, 10_000, || { counter.increment(); });
+    assert_eq!(counter.get(), 40_002);
+}
+
+#[test]
+fn test_racy_counter_single_thread() {
+    // On one thread the broken counter is fine; it only loses updates under contention,
+    // which a normal test can't reliably provoke (see tests/loom.rs).
+    let counter = RacyCounter::new();
+    for _ in 0..100 {
+        counter.increment();
+    }
+    assert_eq!(counter.get(), 100);
+}
+
+#[test]
+fn test_bounded_counter() {
+    let counter = BoundedCounter::new(2);
+    assert_eq!(counter.try_increment(), Ok(1));
+    assert_eq!(counter.try_increment(), Ok(2));
+    assert_eq!(counter.try_increment(), Err(2));
+    counter.decrement();
+    assert_eq!(counter.try_increment(), Ok(2));
+
+    // however many threads try, exactly `limit` succeed
+    let counter = BoundedCounter::new(1_000);
+    let successes = Counter::new();
+    hammer(4, 500, || if counter.try_increment().is_ok() { successes.increment(); });
+    assert_eq!((counter.get(), successes.get()), (1_000, 1_000));
+}
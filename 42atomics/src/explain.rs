@@ -0,0 +1,46 @@
+// What `atomics --explain` prints: the chapter's notes, in short.
+use rustfun_common::{Explanation, Text, Topic};
+
+pub const EXPLANATION: Explanation = Explanation {
+    chapter: "42atomics",
+    summary: Text {
+        en: "atomic counters, a spinlock, and a release/acquire one-shot",
+        zh: "原子计数器、自旋锁和 release/acquire 单次通知",
+    },
+    topics: &[
+        Topic {
+            title: Text { en: "Atomic types", zh: "原子类型" },
+            body: Text {
+                en: "An atomic can be changed through a shared reference from many threads; each\n\
+                     operation is indivisible, and fetch_add never loses an update.",
+                zh: "原子类型可以通过共享引用被多个线程修改; 每个操作不可分割, fetch_add 不会\n\
+                     丢失更新。",
+            },
+        },
+        Topic {
+            title: Text { en: "Orderings", zh: "内存顺序" },
+            body: Text {
+                en: "Relaxed promises only atomicity. A Release store paired with an Acquire load\n\
+                     publishes everything written before the store. SeqCst adds one total order.",
+                zh: "Relaxed 只保证原子性。Release 存储与 Acquire 加载配对, 发布存储之前写入的\n\
+                     所有内容。SeqCst 再加上一个全局顺序。",
+            },
+        },
+        Topic {
+            title: Text { en: "A spinlock", zh: "自旋锁" },
+            body: Text {
+                en: "compare_exchange with Acquire takes the lock, a Release store gives it back;\n\
+                     the guard's Drop does the releasing.",
+                zh: "用 Acquire 的 compare_exchange 获取锁, 用 Release 的存储释放; 释放由守卫的\n\
+                     Drop 完成。",
+            },
+        },
+        Topic {
+            title: Text { en: "Testing with loom", zh: "用 loom 测试" },
+            body: Text {
+                en: "Threads in tests sample a few interleavings; loom explores all of them.",
+                zh: "测试中的线程只能碰到少数几种交错; loom 会穷举所有交错。",
+            },
+        },
+    ],
+};
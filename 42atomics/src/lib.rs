@@ -0,0 +1,35 @@
+//
+// Atomics: sharing data between threads without locks, and what Ordering means.
+//
+// Note:
+//
+// 1.) An atomic type (AtomicBool, AtomicUsize, ...) can be read and changed through a
+//     shared reference from many threads at once. Each operation is indivisible: no
+//     thread ever sees half a store, and read-modify-write operations like fetch_add
+//     and compare_exchange can't lose updates.
+// 2.) Every operation takes an Ordering, which says what it guarantees about the
+//     *other* memory around it:
+//     - Relaxed: the operation itself is atomic, nothing more. Fine for counters and
+//       statistics where no other data depends on the value.
+//     - Release (on a store) and Acquire (on a load) work as a pair: if a load with
+//       Acquire sees the value written by a store with Release, everything the storing
+//       thread wrote before the store is visible to the loading thread after the load.
+//       This is how a lock or a "ready" flag publishes data.
+//     - AcqRel: both, for read-modify-write operations.
+//     - SeqCst: Acquire/Release plus one total order of all SeqCst operations that
+//       every thread agrees on. The safe default when in doubt.
+// 3.) Getting orderings wrong rarely shows up on x86, which is strongly ordered, and
+//     then fails on ARM. Tests that run threads only sample a few interleavings; loom
+//     (tests/loom.rs) explores all of them, under the C++ memory model.
+// 4.) To run under loom, the code must use loom's atomics and threads instead of std's.
+//     The `sync` module switches between them with `cfg(loom)`.
+//
+mod sync;
+
+pub mod counter;
+pub mod oneshot;
+pub mod spinlock;
+
+pub use crate::counter::{BoundedCounter, Counter, RacyCounter};
+pub use crate::oneshot::OneShot;
+pub use crate::spinlock::{SpinGuard, SpinLock};
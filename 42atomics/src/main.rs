@@ -0,0 +1,83 @@
+use atomics::{BoundedCounter, Counter, OneShot, RacyCounter, SpinLock};
+use std::thread;
+use std::time::Instant;
+
+const THREADS: usize = 4;
+const PER_THREAD: usize = 1_000_000;
+
+mod explain;
+
+fn main() {
+    rustfun_common::init_logging();
+    explain::EXPLANATION.on_request();
+    // What the races did, and the timings, differ from run to run; --deterministic prints
+    // only what's certain.
+    let deterministic = rustfun_common::deterministic::is_on();
+
+    // 1.  The correct counter and the racy one, under the same contention.
+    let counter = Counter::new();
+    let racy = RacyCounter::new();
+    thread::scope(|s| {
+        for _ in 0..THREADS {
+            s.spawn(|| for _ in 0..PER_THREAD {
+                counter.increment();
+                racy.increment();
+            });
+        }
+    });
+    if deterministic {
+        println!("expected {}: Counter got {}", THREADS * PER_THREAD, counter.get());
+    } else {
+        println!("expected {}: Counter got {}, RacyCounter got {} (lost {})",
+                 THREADS * PER_THREAD, counter.get(), racy.get(), THREADS * PER_THREAD - racy.get());
+    }
+
+    // 2.  A spinlock guarding a plain Vec.
+    let lock = SpinLock::new(Vec::new());
+    let start = Instant::now();
+    thread::scope(|s| {
+        for id in 0..THREADS {
+            let lock = &lock;
+            s.spawn(move || for i in 0..1_000 { lock.lock().push(id * 1_000 + i); });
+        }
+    });
+    if deterministic {
+        println!("spinlock: {} pushes", lock.lock().len());
+    } else {
+        println!("spinlock: {} pushes in {:?}", lock.lock().len(), start.elapsed());
+    }
+
+    // 3.  At most two of the four threads get in at a time.
+    let slots = BoundedCounter::new(2);
+    thread::scope(|s| {
+        for id in 0..THREADS {
+            let slots = &slots;
+            s.spawn(move || match slots.try_increment() {
+                _ if deterministic => {}
+                Ok(n) => println!("thread {} got slot {}", id, n),
+                Err(limit) => println!("thread {} turned away (limit {})", id, limit),
+            });
+        }
+    });
+    if deterministic {
+        println!("{} of {} threads got a slot", slots.get(), THREADS);
+    }
+
+    // 4.  Hand a value to another thread through a Release/Acquire flag.
+    let slot = OneShot::new();
+    thread::scope(|s| {
+        s.spawn(|| slot.send(String::from("computed on another thread")));
+        let mut spins = 0u64;
+        let message = loop {
+            match slot.get() {
+                Some(message) => break message,
+                None => spins += 1,
+            }
+        };
+        if deterministic {
+            println!("received {:?}", message);
+        } else {
+            println!("received {:?} after {} spins", message, spins);
+        }
+    });
+}
@@ -0,0 +1,69 @@
+// Publishing a value to another thread with a Release store and an Acquire load.
+use crate::sync::{AtomicBool, Ordering, UnsafeCell};
+
+/// A slot written once by one thread and read by another, without a lock.
+pub struct OneShot<T> {
+    claimed: AtomicBool,
+    ready: AtomicBool,
+    value: UnsafeCell<Option<T>>,
+}
+
+// Readers on other threads get &T, so T must be Sync as well as Send.
+unsafe impl<T: Send + Sync> Sync for OneShot<T> {}
+
+impl<T> Default for OneShot<T> {
+    fn default() -> OneShot<T> {
+        OneShot::new()
+    }
+}
+
+impl<T> OneShot<T> {
+    pub fn new() -> OneShot<T> {
+        OneShot { claimed: AtomicBool::new(false), ready: AtomicBool::new(false), value: UnsafeCell::new(None) }
+    }
+
+    /// Store the value. Panics if called twice.
+    // 1.  Write the value first, then set the flag with Release. A reader that sees
+    //     the flag with Acquire is guaranteed to see the value too. With Relaxed on
+    //     either side the reader could see `ready` but a stale `value`; loom reports
+    //     that as a causality violation.
+    //     Two senders writing `value` at once would be a real data race, so the first
+    //     sender claims the slot with a swap; a second one sees `true` and panics.
+    pub fn send(&self, value: T) {
+        assert!(!self.claimed.swap(true, Ordering::Relaxed), "OneShot::send called twice");
+        self.value.with_mut(|p| unsafe { *p = Some(value) });
+        self.ready.store(true, Ordering::Release);
+    }
+
+    /// The value, if it has been sent.
+    pub fn get(&self) -> Option<&T> {
+        if self.ready.load(Ordering::Acquire) {
+            self.value.with(|p| unsafe { (*p).as_ref() })
+        } else {
+            None
+        }
+    }
+}
+
+#[test]
+fn test_oneshot() {
+    let slot = OneShot::new();
+    std::thread::scope(|s| {
+        s.spawn(|| slot.send(String::from("hello")));
+        let seen = loop {
+            if let Some(value) = slot.get() {
+                break value;
+            }
+            std::hint::spin_loop();
+        };
+        assert_eq!(seen, "hello");
+    });
+}
+
+#[test]
+#[should_panic(expected = "OneShot::send called twice")]
+fn test_oneshot_send_twice() {
+    let slot = OneShot::new();
+    slot.send(1);
+    slot.send(2);
+}
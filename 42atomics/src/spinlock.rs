@@ -0,0 +1,109 @@
+// A spinlock: the smallest possible mutex, built on one AtomicBool.
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use crate::sync::{spin_loop, AtomicBool, Ordering, UnsafeCell};
+
+/// A mutual-exclusion lock that busy-waits instead of sleeping.
+// Spinning burns CPU while waiting, so a spinlock only makes sense when the lock is held
+// for a few instructions. std's Mutex spins briefly too, then asks the OS to park the
+// thread; use that in real code.
+pub struct SpinLock<T> {
+    locked: AtomicBool,
+    data: UnsafeCell<T>,
+}
+
+// 1.  UnsafeCell makes SpinLock !Sync. We promise the compiler that the lock makes
+//     sharing safe, as long as T itself can be sent to whichever thread holds the lock.
+unsafe impl<T: Send> Sync for SpinLock<T> {}
+
+/// Access to the data while the lock is held. Unlocks on drop.
+pub struct SpinGuard<'a, T: 'a> {
+    lock: &'a SpinLock<T>,
+}
+
+impl<T> SpinLock<T> {
+    pub fn new(value: T) -> SpinLock<T> {
+        SpinLock { locked: AtomicBool::new(false), data: UnsafeCell::new(value) }
+    }
+
+    // 2.  Lock: swap false -> true. Acquire on success pairs with the Release in unlock,
+    //     so we see everything the previous holder wrote. On failure, wait with plain
+    //     loads until the lock looks free before trying the (more expensive) swap
+    //     again; this "test and test-and-set" keeps the cache line shared while waiting.
+    pub fn lock(&self) -> SpinGuard<'_, T> {
+        while self.locked.compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed).is_err() {
+            while self.locked.load(Ordering::Relaxed) {
+                spin_loop();
+            }
+        }
+        SpinGuard { lock: self }
+    }
+
+    pub fn try_lock(&self) -> Option<SpinGuard<'_, T>> {
+        match self.locked.compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed) {
+            Ok(_) => Some(SpinGuard { lock: self }),
+            Err(_) => None,
+        }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+}
+
+// 3.  Deref hands out a reference that lives as long as the guard, so it escapes the
+//     `with` closure. That's sound because only the guard's owner can reach it.
+impl<'a, T> Deref for SpinGuard<'a, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        self.lock.data.with(|p| unsafe { &*p })
+    }
+}
+
+impl<'a, T> DerefMut for SpinGuard<'a, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.lock.data.with_mut(|p| unsafe { &mut *p })
+    }
+}
+
+// 4.  Unlock: Release publishes our writes to the next thread that acquires the lock.
+impl<'a, T> Drop for SpinGuard<'a, T> {
+    fn drop(&mut self) {
+        self.lock.locked.store(false, Ordering::Release);
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for SpinLock<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.try_lock() {
+            Some(guard) => f.debug_struct("SpinLock").field("data", &*guard).finish(),
+            None => f.write_str("SpinLock { <locked> }"),
+        }
+    }
+}
+
+#[test]
+fn test_lock_and_unlock() {
+    let lock = SpinLock::new(vec![1]);
+    {
+        let mut guard = lock.lock();
+        guard.push(2);
+        assert!(lock.try_lock().is_none());
+        assert_eq!(format!("{:?}", lock), "SpinLock { <locked> }");
+    }
+    assert_eq!(format!("{:?}", lock), "SpinLock { data: [1, 2] }");
+    lock.try_lock().unwrap().push(3);
+    assert_eq!(lock.into_inner(), vec![1, 2, 3]);
+}
+
+#[test]
+fn test_spinlock_under_contention() {
+    // A non-atomic read-modify-write of a plain usize: correct only if the lock works.
+    let lock = SpinLock::new(0usize);
+    std::thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| for _ in 0..10_000 { *lock.lock() += 1; });
+        }
+    });
+    assert_eq!(lock.into_inner(), 40_000);
+}
@@ -0,0 +1,27 @@
+// Run the chapter's demo binary and check the parts of its output that are deterministic.
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+// How many increments the racy counter loses depends on the scheduler; the correct
+// counter's total doesn't.
+#[test]
+fn prints_the_demo() {
+    Command::cargo_bin("atomics").unwrap().assert()
+        .success()
+        .stdout(predicate::str::starts_with("expected 4000000: Counter got 4000000, RacyCounter got ")
+            .and(predicate::str::contains("spinlock: 4000 pushes in "))
+            .and(predicate::str::contains("received \"computed on another thread\" after ")));
+}
+
+// --deterministic leaves out what the races did and how long things took.
+#[test]
+fn prints_the_same_every_run_when_deterministic() {
+    Command::cargo_bin("atomics").unwrap().arg("--deterministic").assert()
+        .success()
+        .stdout("\
+expected 4000000: Counter got 4000000
+spinlock: 4000 pushes
+2 of 4 threads got a slot
+received \"computed on another thread\"
+");
+}
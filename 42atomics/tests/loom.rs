@@ -0,0 +1,89 @@
+// Model-checked tests: loom runs each closure under every possible interleaving of
+// its threads (and every outcome the memory model allows for each atomic load).
+//
+//   $ RUSTFLAGS="--cfg loom" cargo test --release --test loom
+//
+// Without `--cfg loom` this file compiles to nothing.
+#![cfg(loom)]
+
+use atomics::{BoundedCounter, Counter, OneShot, RacyCounter, SpinLock};
+use loom::sync::Arc;
+use loom::thread;
+
+#[test]
+fn counter_never_loses_an_increment() {
+    loom::model(|| {
+        let counter = Arc::new(Counter::new());
+        let other = {
+            let counter = counter.clone();
+            thread::spawn(move || { counter.increment(); })
+        };
+        counter.increment();
+        other.join().unwrap();
+        assert_eq!(counter.get(), 2);
+    });
+}
+
+// loom finds the interleaving where both threads load 0 and both store 1.
+#[test]
+#[should_panic]
+fn racy_counter_loses_an_increment() {
+    loom::model(|| {
+        let counter = Arc::new(RacyCounter::new());
+        let other = {
+            let counter = counter.clone();
+            thread::spawn(move || counter.increment())
+        };
+        counter.increment();
+        other.join().unwrap();
+        assert_eq!(counter.get(), 2);
+    });
+}
+
+// Also checks that the data is never accessed by two threads at once: loom's UnsafeCell
+// panics if the Acquire/Release pair in the lock didn't order the accesses.
+#[test]
+fn spinlock_is_mutually_exclusive() {
+    loom::model(|| {
+        let lock = Arc::new(SpinLock::new(0));
+        let other = {
+            let lock = lock.clone();
+            thread::spawn(move || *lock.lock() += 1)
+        };
+        *lock.lock() += 1;
+        other.join().unwrap();
+        assert_eq!(*lock.lock(), 2);
+    });
+}
+
+#[test]
+fn oneshot_reader_sees_the_value() {
+    loom::model(|| {
+        let slot = Arc::new(OneShot::new());
+        let sender = {
+            let slot = slot.clone();
+            thread::spawn(move || slot.send(42))
+        };
+        // Either the value isn't there yet, or all of it is.
+        if let Some(&value) = slot.get() {
+            assert_eq!(value, 42);
+        }
+        sender.join().unwrap();
+        assert_eq!(slot.get(), Some(&42));
+    });
+}
+
+#[test]
+fn bounded_counter_admits_exactly_one() {
+    loom::model(|| {
+        let counter = Arc::new(BoundedCounter::new(1));
+        let other = {
+            let counter = counter.clone();
+            thread::spawn(move || counter.try_increment())
+        };
+        let mine = counter.try_increment();
+        let theirs = other.join().unwrap();
+        assert!(mine.is_ok() != theirs.is_ok(), "{:?} {:?}", mine, theirs);
+        assert_eq!(counter.get(), 1);
+    });
+}
@@ -0,0 +1,73 @@
+// Stress tests with real threads. They can only show that nothing went wrong in the
+// interleavings that happened to run; tests/loom.rs checks all of them.
+#![cfg(not(loom))]
+
+use atomics::{BoundedCounter, Counter, OneShot, SpinLock};
+use std::sync::Arc;
+use std::thread;
+
+const THREADS: usize = 8;
+
+#[test]
+fn spinlock_keeps_an_invariant() {
+    // Two fields that must always be equal. Without mutual exclusion, a reader would
+    // sometimes catch a writer between its two updates.
+    let pair = Arc::new(SpinLock::new((0u64, 0u64)));
+    let handles: Vec<_> = (0..THREADS).map(|_| {
+        let pair = pair.clone();
+        thread::spawn(move || {
+            for _ in 0..5_000 {
+                let mut guard = pair.lock();
+                assert_eq!(guard.0, guard.1);
+                guard.0 += 1;
+                guard.1 += 1;
+            }
+        })
+    }).collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(*pair.lock(), (40_000, 40_000));
+}
+
+#[test]
+fn oneshot_always_delivers_the_whole_value() {
+    for round in 0..1_000 {
+        let slot = Arc::new(OneShot::new());
+        let sender = {
+            let slot = slot.clone();
+            thread::spawn(move || slot.send(vec![round; 16]))
+        };
+        let value = loop {
+            if let Some(value) = slot.get() {
+                break value.clone();
+            }
+            thread::yield_now();
+        };
+        assert_eq!(value, vec![round; 16]);
+        sender.join().unwrap();
+    }
+}
+
+#[test]
+fn bounded_counter_never_exceeds_its_limit() {
+    let counter = Arc::new(BoundedCounter::new(3));
+    let admitted = Arc::new(Counter::new());
+    let handles: Vec<_> = (0..THREADS).map(|_| {
+        let (counter, admitted) = (counter.clone(), admitted.clone());
+        thread::spawn(move || {
+            for _ in 0..2_000 {
+                if let Ok(now) = counter.try_increment() {
+                    assert!(now <= 3);
+                    admitted.increment();
+                    counter.decrement();
+                }
+            }
+        })
+    }).collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(counter.get(), 0);
+    assert!(admitted.get() > 0);
+}
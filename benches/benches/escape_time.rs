@@ -0,0 +1,47 @@
+// The Mandelbrot kernel: escape_time on single points, and a small frame rendered
+// serially and on the thread pool. The kernel is the one from 03mandelbrot; we reach it
+// through 28threadpool, which has it in a library.
+//
+//   $ cargo bench --bench escape_time
+//
+// A point inside the set runs the full `limit` iterations, so its time is the cost of
+// 255 iterations; a point far outside escapes almost at once.
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use num::Complex;
+use std::hint::black_box;
+use threadpool::mandelbrot::{escape_time, render, render_on_pool};
+use threadpool::ThreadPool;
+
+fn bench_points(c: &mut Criterion) {
+    let mut group = c.benchmark_group("threadpool::mandelbrot::escape_time");
+    let points = [("inside", Complex { re: -0.5, im: 0.0 }),
+                  ("edge", Complex { re: -0.75, im: 0.1 }),
+                  ("outside", Complex { re: 1.0, im: 1.0 })];
+    for &(name, point) in &points {
+        group.bench_with_input(BenchmarkId::from_parameter(name), &point,
+                               |b, &point| b.iter(|| escape_time(black_box(point), 255)));
+    }
+    group.finish();
+}
+
+// The same view as 03mandelbrot's README example, at 200x150.
+fn bench_render(c: &mut Criterion) {
+    let bounds = (200, 150);
+    let upper_left = Complex { re: -1.20, im: 0.35 };
+    let lower_right = Complex { re: -1.0, im: 0.20 };
+    let pool = ThreadPool::new(4);
+
+    let mut group = c.benchmark_group("threadpool::mandelbrot::render");
+    group.throughput(Throughput::Elements((bounds.0 * bounds.1) as u64));
+    group.bench_function("serial", |b| b.iter(|| render(bounds, upper_left, lower_right)));
+    group.bench_function("pool of 4",
+                         |b| b.iter(|| render_on_pool(&pool, bounds, upper_left, lower_right)));
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = rustfun_benches::criterion();
+    targets = bench_points, bench_render
+}
+criterion_main!(benches);
@@ -0,0 +1,44 @@
+// rustfun_common::gcd, the first function of the book, on small and large arguments.
+//
+//   $ cargo bench --bench gcd
+//
+// Euclid's algorithm takes the most steps on consecutive Fibonacci numbers, so those are
+// the worst case for a given size.
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rustfun_common::{gcd, gcd_all};
+use std::hint::black_box;
+
+fn fibonacci_pair(n: usize) -> (u64, u64) {
+    let (mut a, mut b) = (1u64, 1u64);
+    for _ in 0..n {
+        let next = a + b;
+        a = b;
+        b = next;
+    }
+    (b, a)
+}
+
+fn bench_gcd(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rustfun_common::gcd");
+    let inputs = [("small", (42, 56)),
+                  ("fibonacci 30", fibonacci_pair(30)),
+                  ("fibonacci 90", fibonacci_pair(90))];
+    for &(name, (n, m)) in &inputs {
+        group.bench_with_input(BenchmarkId::from_parameter(name), &(n, m),
+                               |b, &(n, m)| b.iter(|| gcd(black_box(n), black_box(m))));
+    }
+    group.finish();
+}
+
+fn bench_gcd_all(c: &mut Criterion) {
+    let numbers: Vec<u64> = (1..=1000).map(|i| i * 2 * 3 * 5 * 7).collect();
+    c.bench_function("rustfun_common::gcd_all/1000 numbers",
+                     |b| b.iter(|| gcd_all(black_box(&numbers))));
+}
+
+criterion_group! {
+    name = benches;
+    config = rustfun_benches::criterion();
+    targets = bench_gcd, bench_gcd_all
+}
+criterion_main!(benches);
@@ -0,0 +1,43 @@
+// Parsing: the command-line pairs of 03mandelbrot ("1000x750", "-1.20,0.35") and whole
+// JSON documents with 32json-parser.
+//
+//   $ cargo bench --bench parsing
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use rustfun_common::parse_pair;
+use std::hint::black_box;
+
+fn bench_parse_pair(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rustfun_common::parse_pair");
+    group.bench_function("usize 1000x750",
+                         |b| b.iter(|| parse_pair::<usize>(black_box("1000x750"), 'x')));
+    group.bench_function("f64 -1.20,0.35",
+                         |b| b.iter(|| parse_pair::<f64>(black_box("-1.20,0.35"), ',')));
+    // failing early, on a missing separator, should be much cheaper than parsing numbers
+    group.bench_function("no separator",
+                         |b| b.iter(|| parse_pair::<f64>(black_box("-1.20 0.35"), ',')));
+    group.finish();
+}
+
+// An array of small objects, the shape of most JSON found in the wild.
+fn document(records: usize) -> String {
+    let items: Vec<String> = (0..records).map(|i| {
+        format!(r#"{{"id": {}, "name": "item {}", "price": {}.5, "tags": ["a", "b"], "ok": true}}"#,
+                i, i, i)
+    }).collect();
+    format!("[{}]", items.join(",\n"))
+}
+
+fn bench_json(c: &mut Criterion) {
+    let text = document(1000);
+    let mut group = c.benchmark_group("json_parser::parse");
+    group.throughput(Throughput::Bytes(text.len() as u64));
+    group.bench_function("1000 records", |b| b.iter(|| json_parser::parse(black_box(&text))));
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = rustfun_benches::criterion();
+    targets = bench_parse_pair, bench_json
+}
+criterion_main!(benches);
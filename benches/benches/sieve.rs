@@ -0,0 +1,40 @@
+// rustfun_common::sieve at a few sizes; the time per element should stay roughly flat,
+// since the sieve is O(n log log n).
+//
+//   $ cargo bench --bench sieve
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rustfun_common::{primes_between, primes_up_to, sieve};
+use std::hint::black_box;
+
+fn bench_sieve(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rustfun_common::sieve");
+    for &n in &[10_000usize, 100_000, 1_000_000] {
+        group.throughput(Throughput::Elements(n as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(n), &n,
+                               |b, &n| b.iter(|| sieve(black_box(n))));
+    }
+    group.finish();
+}
+
+// The extra cost of turning the table of flags into a list of primes.
+fn bench_primes_up_to(c: &mut Criterion) {
+    c.bench_function("rustfun_common::primes_up_to/100000",
+                     |b| b.iter(|| primes_up_to(black_box(100_000))));
+}
+
+// The segmented sieve over the same range, and over one as long, far above it, where
+// only the primes up to its square root have to be sieved first.
+fn bench_primes_between(c: &mut Criterion) {
+    c.bench_function("rustfun_common::primes_between/0..100000",
+                     |b| b.iter(|| primes_between(black_box(0), black_box(100_000))));
+    c.bench_function("rustfun_common::primes_between/10^12..+100000",
+                     |b| b.iter(|| primes_between(black_box(1_000_000_000_000),
+                                                  black_box(1_000_000_100_000))));
+}
+
+criterion_group! {
+    name = benches;
+    config = rustfun_benches::criterion();
+    targets = bench_sieve, bench_primes_up_to, bench_primes_between
+}
+criterion_main!(benches);
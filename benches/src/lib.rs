@@ -0,0 +1,34 @@
+//
+// The shared harness for the workspace's benchmarks: every bench target builds its
+// Criterion from `criterion()` here, so all of them run with the same settings and their
+// numbers can be put side by side.
+//
+// Note:
+//
+// 1.) `cargo bench` from the repository root runs every bench target in the workspace:
+//     the hot paths in this crate (gcd, escape_time, sieve, parsing) and the chapters' own
+//     benches. Criterion writes one HTML report for all of them to target/criterion/.
+// 2.) To compare two versions of the code, save a baseline before the change and compare
+//     against it after:
+//
+//       $ cargo bench -- --save-baseline before
+//       $ cargo bench -- --baseline before
+//
+// 3.) Group names say where the code lives, `crate::function`, and the benchmark id says
+//     what input it got, so a line of the report can be found without opening the bench.
+//
+use criterion::Criterion;
+use std::time::Duration;
+
+/// The Criterion settings used by every bench target in the workspace.
+// 1.  The defaults (100 samples, 5s each) make a full run of the workspace take a long
+//     time; 50 samples over 3s is still enough to see a change of a few percent.
+// 2.  Command-line flags like --save-baseline still apply on top of these:
+//     criterion_group! calls configure_from_args() on the config it's given.
+pub fn criterion() -> Criterion {
+    Criterion::default()
+        .sample_size(50)
+        .warm_up_time(Duration::from_secs(1))
+        .measurement_time(Duration::from_secs(3))
+        .noise_threshold(0.03)
+}
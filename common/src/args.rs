@@ -0,0 +1,294 @@
+// Command-line arguments for the chapter binaries. Positional values are taken in order
+// and parsed into numbers, pairs or paths; whatever goes wrong is reported the same way
+// by every program, its name first and its usage after:
+//
+//   mandelbrot: PIXELS: expected two values separated by 'x', got "40by30"
+//   Usage: mandelbrot FILE PIXELS UPPERLEFT LOWERRIGHT CONCURRENT
+//
+// `--lang zh` anywhere on the command line says all that in Chinese instead; see lang.rs.
+// `--help` prints the usage to stdout and exits, successfully.
+//
+use std::collections::VecDeque;
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::lang::Text;
+use crate::{parse_numbers, parse_pair, tr, NumbersError};
+
+/// The arguments not yet taken, and the usage text to show when they're wrong.
+pub struct Args {
+    usage: Text,
+    rest: VecDeque<String>,
+}
+
+/// A missing, malformed or extra argument.
+#[derive(Debug, PartialEq)]
+pub struct ArgError {
+    usage: Text,
+    message: String,
+}
+
+impl ArgError {
+    /// What was wrong, without the program name or usage.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Print the error and usage to stderr, and exit with status 1.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn exit(&self) -> ! {
+        eprintln!("{}", self);
+        std::process::exit(1);
+    }
+}
+
+// 1.  The program name is the first word of the usage text, so the two can't disagree.
+impl fmt::Display for ArgError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let program = self.usage.en.split_whitespace().next().unwrap_or("");
+        let usage = tr!("Usage: {}", "用法: {}", self.usage);
+        write!(f, "{}: {}\n{}", program, self.message, usage)
+    }
+}
+
+impl std::error::Error for ArgError {}
+
+impl Args {
+    /// The arguments of this process, program name skipped. `usage` starts with the
+    /// program name, like `"gcd NUMBER ..."`, and may go on for more lines; a Text gives
+    /// it in both languages.
+    ///
+    /// A `--lang LANG` among the arguments is taken out first, and sets the language;
+    /// so is `--deterministic`. With `--help`, the usage is all the program does.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_env<U: Into<Text>>(usage: U) -> Args {
+        let mut args: Vec<String> = std::env::args().skip(1).collect();
+        if crate::deterministic::take_flag(&mut args) {
+            crate::deterministic::set(true);
+        }
+        let flag = crate::lang::take_flag(&mut args);
+        let args = Args::new(usage, args);
+        match flag {
+            Ok(Some(lang)) => crate::lang::set(lang),
+            Ok(None) => {}
+            Err(message) => args.error(message).exit(),
+        }
+        if args.wants_help() {
+            println!("{}", args.usage());
+            std::process::exit(0);
+        }
+        args
+    }
+
+    /// Whether `--help` is among the arguments.
+    pub fn wants_help(&self) -> bool {
+        self.rest.iter().any(|a| a == "--help")
+    }
+
+    /// The usage, in the current language, as an error shows it.
+    pub fn usage(&self) -> String {
+        tr!("Usage: {}", "用法: {}", self.usage)
+    }
+
+    pub fn new<U: Into<Text>, I: IntoIterator<Item = String>>(usage: U, args: I) -> Args {
+        Args { usage: usage.into(), rest: args.into_iter().collect() }
+    }
+
+    /// An error about the arguments, for checks of the caller's own.
+    pub fn error<M: Into<String>>(&self, message: M) -> ArgError {
+        ArgError { usage: self.usage, message: message.into() }
+    }
+
+    /// The next argument, if there is one.
+    pub fn optional(&mut self) -> Option<String> {
+        self.rest.pop_front()
+    }
+
+    /// The next argument, left where it is: a subcommand, say.
+    pub fn peek(&self) -> Option<&str> {
+        self.rest.front().map(String::as_str)
+    }
+
+    /// The next argument, which must be there. `name` is how the usage calls it.
+    pub fn required(&mut self, name: &str) -> Result<String, ArgError> {
+        match self.rest.pop_front() {
+            Some(arg) => Ok(arg),
+            None => Err(self.error(tr!("missing {}", "缺少 {}", name))),
+        }
+    }
+
+    /// The next argument, parsed with `FromStr`.
+    pub fn parse<T: FromStr>(&mut self, name: &str) -> Result<T, ArgError> {
+        self.parse_with(name, |arg| arg.parse().ok())
+    }
+
+    /// The next argument, parsed by `parse`, which returns None if it can't.
+    pub fn parse_with<T, F>(&mut self, name: &str, parse: F) -> Result<T, ArgError>
+        where F: FnOnce(&str) -> Option<T>
+    {
+        let arg = self.required(name)?;
+        match parse(&arg) {
+            Some(value) => Ok(value),
+            None => Err(self.error(tr!("{}: can't parse {:?}", "{}: 无法解析 {:?}", name, arg))),
+        }
+    }
+
+    /// The next argument as a pair of values around `separator`, like `1000x750`.
+    pub fn pair<T: FromStr>(&mut self, name: &str, separator: char) -> Result<(T, T), ArgError> {
+        let arg = self.required(name)?;
+        match parse_pair(&arg, separator) {
+            Some(pair) => Ok(pair),
+            None => Err(self.error(tr!("{}: expected two values separated by {:?}, got {:?}",
+                                       "{}: 应为以 {:?} 分隔的两个值, 却是 {:?}",
+                                       name, separator, arg))),
+        }
+    }
+
+    /// The next argument as a file path.
+    pub fn path(&mut self, name: &str) -> Result<PathBuf, ArgError> {
+        self.required(name).map(PathBuf::from)
+    }
+
+    /// All the remaining arguments as gcd operands, as `parse_numbers` reads them.
+    pub fn numbers(&mut self, name: &str) -> Result<Vec<u64>, ArgError> {
+        let args: Vec<String> = self.rest.drain(..).collect();
+        parse_numbers(&args).map_err(|e| self.error(match e {
+            NumbersError::Empty => tr!("missing {}", "缺少 {}", name),
+            NumbersError::NotANumber(arg) => tr!("{}: not a number: {:?}", "{}: 不是数字: {:?}", name, arg),
+            NumbersError::Zero => tr!("{}: zero has no greatest common divisor",
+                                      "{}: 零没有最大公约数", name),
+        }))
+    }
+
+    /// The value of `--name VALUE` or `--name=VALUE`, taken out of the arguments
+    /// wherever it is, if it's there. `name` is the option with its dashes, and has to be
+    /// asked for before the positional arguments around it are taken.
+    pub fn option(&mut self, name: &str) -> Result<Option<String>, ArgError> {
+        let prefix = format!("{}=", name);
+        let Some(i) = self.rest.iter().position(|a| a == name || a.starts_with(&prefix)) else {
+            return Ok(None);
+        };
+        let arg = self.rest.remove(i).unwrap_or_default();
+        match arg.strip_prefix(&prefix) {
+            Some(value) => Ok(Some(value.to_string())),
+            None => match self.rest.remove(i) {
+                Some(value) => Ok(Some(value)),
+                None => Err(self.error(tr!("{} needs a value", "{} 需要一个值", name))),
+            },
+        }
+    }
+
+    /// The value of the option `name`, parsed by `parse`, which returns None if it can't.
+    pub fn option_with<T, F>(&mut self, name: &str, parse: F) -> Result<Option<T>, ArgError>
+        where F: FnOnce(&str) -> Option<T>
+    {
+        match self.option(name)? {
+            None => Ok(None),
+            Some(value) => match parse(&value) {
+                Some(parsed) => Ok(Some(parsed)),
+                None => Err(self.error(tr!("{}: can't parse {:?}", "{}: 无法解析 {:?}", name, value))),
+            },
+        }
+    }
+
+    /// Add `options`, each `--name=value` or a flag `--name`, except those the arguments
+    /// already give: defaults, from a file say, that the command line overrides.
+    pub fn default_options<I: IntoIterator<Item = String>>(&mut self, options: I) {
+        for option in options {
+            let name = option.split('=').next().unwrap_or_default();
+            let prefix = format!("{}=", name);
+            if !self.rest.iter().any(|a| a == name || a.starts_with(&prefix)) {
+                self.rest.push_back(option);
+            }
+        }
+    }
+
+    /// Whether the flag `name`, an option without a value, is there; it's taken out.
+    pub fn flag(&mut self, name: &str) -> bool {
+        let before = self.rest.len();
+        self.rest.retain(|a| a != name);
+        self.rest.len() < before
+    }
+
+    /// Check that every argument has been taken.
+    pub fn finish(self) -> Result<(), ArgError> {
+        match self.rest.front() {
+            None => Ok(()),
+            Some(extra) => Err(self.error(tr!("unexpected argument {:?}", "多余的参数 {:?}", extra))),
+        }
+    }
+}
+
+#[cfg(test)]
+fn args(list: &[&str]) -> Args {
+    Args::new("prog A B", list.iter().map(|s| s.to_string()))
+}
+
+#[test]
+fn test_args_in_order() {
+    let mut a = args(&["out.png", "40x30", "7", "extra"]);
+    assert_eq!(a.path("FILE"), Ok(PathBuf::from("out.png")));
+    assert_eq!(a.pair::<usize>("PIXELS", 'x'), Ok((40, 30)));
+    assert_eq!(a.parse::<u32>("LIMIT"), Ok(7));
+    assert_eq!(a.optional(), Some("extra".to_string()));
+    assert_eq!(a.optional(), None);
+    assert!(a.finish().is_ok());
+}
+
+#[test]
+fn test_arg_errors() {
+    let mut a = args(&["40by30", "x"]);
+    let e = a.pair::<usize>("PIXELS", 'x').unwrap_err();
+    assert_eq!(e.message(), "PIXELS: expected two values separated by 'x', got \"40by30\"");
+    assert_eq!(e.to_string(),
+               "prog: PIXELS: expected two values separated by 'x', got \"40by30\"\nUsage: prog A B");
+    assert_eq!(a.parse::<u32>("LIMIT").unwrap_err().message(), "LIMIT: can't parse \"x\"");
+    assert_eq!(a.required("FILE").unwrap_err().message(), "missing FILE");
+
+    assert_eq!(args(&["a", "b"]).finish().unwrap_err().message(), "unexpected argument \"a\"");
+}
+
+#[test]
+fn test_numbers() {
+    assert_eq!(args(&["12", "18"]).numbers("NUMBER"), Ok(vec![12, 18]));
+    assert_eq!(args(&[]).numbers("NUMBER").unwrap_err().message(), "missing NUMBER");
+    assert_eq!(args(&["12", "twelve"]).numbers("NUMBER").unwrap_err().message(),
+               "NUMBER: not a number: \"twelve\"");
+    assert_eq!(args(&["0"]).numbers("NUMBER").unwrap_err().message(),
+               "NUMBER: zero has no greatest common divisor");
+}
+
+#[test]
+fn test_options() {
+    let mut a = args(&["out.png", "--julia", "-0.8,0.156", "--limit=500", "--fast", "40x30"]);
+    assert_eq!(a.option("--julia"), Ok(Some("-0.8,0.156".to_string())));
+    assert_eq!(a.option_with("--limit", |v| v.parse::<u32>().ok()), Ok(Some(500)));
+    assert_eq!(a.option("--palette"), Ok(None));
+    assert!(a.flag("--fast"));
+    assert!(!a.flag("--fast"));
+    assert_eq!(a.path("FILE"), Ok(PathBuf::from("out.png")));
+    assert_eq!(a.pair::<usize>("PIXELS", 'x'), Ok((40, 30)));
+    assert!(a.finish().is_ok());
+
+    assert_eq!(args(&["--julia"]).option("--julia").unwrap_err().message(),
+               "--julia needs a value");
+    assert_eq!(args(&["--limit", "many"]).option_with("--limit", |v| v.parse::<u32>().ok())
+                   .unwrap_err().message(),
+               "--limit: can't parse \"many\"");
+    // an option that isn't asked for is left, and finish says so
+    assert_eq!(args(&["--nope"]).finish().unwrap_err().message(), "unexpected argument \"--nope\"");
+    assert!(args(&["out.png", "--help"]).wants_help() && !args(&["out.png"]).wants_help());
+    assert_eq!(args(&["stitch", "out.toml"]).peek(), Some("stitch"));
+}
+
+#[test]
+fn test_default_options() {
+    let mut a = args(&["--palette", "fire", "out.png"]);
+    a.default_options(["--palette=gray".to_string(), "--zoom=2".to_string(), "--smooth".to_string()]);
+    assert_eq!(a.option("--palette"), Ok(Some("fire".to_string())));
+    assert_eq!(a.option("--zoom"), Ok(Some("2".to_string())));
+    assert!(a.flag("--smooth"));
+    assert_eq!(a.required("FILE"), Ok("out.png".to_string()));
+    assert!(a.finish().is_ok());
+}
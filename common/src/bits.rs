@@ -0,0 +1,318 @@
+// Working with the bits of an unsigned integer: one bit at a time, a run of them at once
+// with a mask, or all of them turned round with a rotation. It grew out of chapter 04's
+// count_ones asserts:
+//
+//   use rustfun_common::bits::Bits;
+//   assert_eq!(0b1000u8.set_bit(0), 0b1001);
+//   assert_eq!(u8::mask(2..5), 0b0001_1100);
+//   assert_eq!(0b1000_0001u8.rotl(1), 0b0000_0011);
+//
+// and Perms, a set of flags kept in a u8, as the bitflags crate would make one.
+//
+// Note:
+//
+// 1.) Bits are numbered from 0, the lowest, to BITS - 1, the highest. `1 << n` is bit n
+//     alone, and each operation is one of | (set), & ! (clear), ^ (toggle) or & (test)
+//     with it. A bit number past the top panics, in release builds too: a shift that far
+//     would overflow, which release builds don't check.
+// 2.) A mask is a run of bits set, start..end as a range of bit numbers. 1 << BITS
+//     overflows, so the mask of all the bits is MAX, not (1 << BITS) - 1.
+// 3.) A rotation is a shift that brings back what falls off the end, at the other end:
+//     (x << n) | (x >> (BITS - n)). For n == 0 the right shift would be by BITS, so that
+//     case returns x; n counts modulo BITS, as rotate_left's does. Nothing is lost, so
+//     count_ones is the same after.
+// 4.) The methods are a trait's, Bits, implemented for every unsigned type by a macro,
+//     as ulps_key is in float.rs. Import the trait to call them.
+//
+use std::fmt;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Not, Range};
+
+/// Bit operations on the unsigned integer types: see the top of bits.rs.
+pub trait Bits: Copy {
+    /// The width of the type in bits: 8 for u8.
+    const BITS: u32;
+
+    /// With bit `n` set to 1.
+    fn set_bit(self, n: u32) -> Self;
+    /// With bit `n` cleared to 0.
+    fn clear_bit(self, n: u32) -> Self;
+    /// With bit `n` flipped.
+    fn toggle_bit(self, n: u32) -> Self;
+    /// Whether bit `n` is 1.
+    fn test_bit(self, n: u32) -> bool;
+    /// The bits numbered `bits.start` up to, but not including, `bits.end`, set.
+    fn mask(bits: Range<u32>) -> Self;
+    /// Rotated left by `n`: the high bits shifted out come back in at the bottom.
+    fn rotl(self, n: u32) -> Self;
+    /// Rotated right by `n`: the low bits shifted out come back in at the top.
+    fn rotr(self, n: u32) -> Self;
+}
+
+macro_rules! bits {
+    ($($int:ty),*) => {
+        $(impl Bits for $int {
+            const BITS: u32 = <$int>::BITS;
+
+            fn set_bit(self, n: u32) -> $int {
+                self | bit::<$int>(n)
+            }
+
+            fn clear_bit(self, n: u32) -> $int {
+                self & !bit::<$int>(n)
+            }
+
+            fn toggle_bit(self, n: u32) -> $int {
+                self ^ bit::<$int>(n)
+            }
+
+            fn test_bit(self, n: u32) -> bool {
+                self & bit::<$int>(n) != 0
+            }
+
+            fn mask(bits: Range<u32>) -> $int {
+                assert!(bits.start <= bits.end && bits.end <= Self::BITS,
+                        "bits {:?} of a {}-bit integer", bits, Self::BITS);
+                // the low `end` bits, less the low `start` (note 2)
+                let low = |n: u32| if n == Self::BITS { <$int>::MAX } else { (1 << n) - 1 };
+                low(bits.end) & !low(bits.start)
+            }
+
+            fn rotl(self, n: u32) -> $int {
+                let n = n % Self::BITS;
+                if n == 0 { self } else { (self << n) | (self >> (Self::BITS - n)) }
+            }
+
+            fn rotr(self, n: u32) -> $int {
+                self.rotl(Self::BITS - n % Self::BITS)
+            }
+        })*
+    };
+}
+bits!(u8, u16, u32, u64, u128, usize);
+
+/// Bit `n` alone, or a panic if the type has no bit `n` (note 1).
+fn bit<T: Bits + From<u8> + std::ops::Shl<u32, Output = T>>(n: u32) -> T {
+    assert!(n < T::BITS, "bit {} of a {}-bit integer", n, T::BITS);
+    T::from(1) << n
+}
+
+/// Read, write and execute permissions, as a set of flags in the low three bits of a
+/// u8: `Perms::READ | Perms::WRITE` is both, and prints as `rw-`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Perms(u8);
+
+impl Perms {
+    pub const READ: Perms = Perms(0b100);
+    pub const WRITE: Perms = Perms(0b010);
+    pub const EXECUTE: Perms = Perms(0b001);
+
+    /// No permissions.
+    pub const fn empty() -> Perms {
+        Perms(0)
+    }
+
+    /// Every permission.
+    pub const fn all() -> Perms {
+        Perms(Self::READ.0 | Self::WRITE.0 | Self::EXECUTE.0)
+    }
+
+    /// The permissions in `bits`, or None if it has a bit set that isn't one.
+    pub const fn from_bits(bits: u8) -> Option<Perms> {
+        if bits & !Self::all().0 == 0 { Some(Perms(bits)) } else { None }
+    }
+
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Whether every permission in `other` is in self too.
+    pub const fn contains(self, other: Perms) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn insert(&mut self, other: Perms) {
+        *self |= other;
+    }
+
+    pub fn remove(&mut self, other: Perms) {
+        *self &= !other;
+    }
+}
+
+impl BitOr for Perms {
+    type Output = Perms;
+
+    /// The permissions in either.
+    fn bitor(self, rhs: Perms) -> Perms {
+        Perms(self.0 | rhs.0)
+    }
+}
+
+impl BitAnd for Perms {
+    type Output = Perms;
+
+    /// The permissions in both.
+    fn bitand(self, rhs: Perms) -> Perms {
+        Perms(self.0 & rhs.0)
+    }
+}
+
+impl Not for Perms {
+    type Output = Perms;
+
+    /// The permissions not in self: only those three, not the u8's other five bits,
+    /// or !Perms::empty() would be a value from_bits refuses.
+    fn not(self) -> Perms {
+        Perms(!self.0 & Perms::all().0)
+    }
+}
+
+impl BitOrAssign for Perms {
+    fn bitor_assign(&mut self, rhs: Perms) {
+        *self = *self | rhs;
+    }
+}
+
+impl BitAndAssign for Perms {
+    fn bitand_assign(&mut self, rhs: Perms) {
+        *self = *self & rhs;
+    }
+}
+
+/// As ls shows them: `rwx`, with a `-` for each one missing.
+impl fmt::Display for Perms {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (perm, c) in [(Perms::READ, 'r'), (Perms::WRITE, 'w'), (Perms::EXECUTE, 'x')] {
+            write!(f, "{}", if self.contains(perm) { c } else { '-' })?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_single_bits() {
+    // every u8, every bit, against the same thing said with count_ones
+    for x in 0..=u8::MAX {
+        for n in 0..8 {
+            let was_set = x.test_bit(n);
+            assert_eq!(was_set, (x >> n) & 1 == 1);
+            assert!(x.set_bit(n).test_bit(n));
+            assert!(!x.clear_bit(n).test_bit(n));
+            assert_eq!(x.toggle_bit(n).test_bit(n), !was_set);
+            assert_eq!(x.toggle_bit(n).toggle_bit(n), x);
+            assert_eq!(x.set_bit(n).count_ones(), x.count_ones() + !was_set as u32);
+            assert_eq!(x.clear_bit(n).count_ones(), x.count_ones() - was_set as u32);
+            // the other seven bits are left as they were
+            assert_eq!(x.set_bit(n) & !(1 << n), x & !(1 << n));
+        }
+    }
+    assert_eq!(0u128.set_bit(127), 1 << 127);
+    assert!(usize::MAX.test_bit(usize::BITS - 1));
+    assert_eq!((0..64).fold(0u64, |x, n| x.set_bit(n)), u64::MAX);
+}
+
+#[test]
+#[should_panic(expected = "bit 8 of a 8-bit integer")]
+fn test_bit_out_of_range() {
+    0u8.set_bit(8);
+}
+
+#[test]
+fn test_masks() {
+    assert_eq!(u8::mask(2..5), 0b0001_1100);
+    assert_eq!(u8::mask(0..8), u8::MAX);
+    assert_eq!(u128::mask(0..128), u128::MAX);
+    assert_eq!(u32::mask(4..4), 0);
+    // every range of a u8 and a u16: the bits in it set, and no others
+    for end in 0..=16 {
+        for start in 0..=end {
+            let mask = u16::mask(start..end);
+            assert_eq!(mask.count_ones(), end - start);
+            assert!((0..16).all(|n| mask.test_bit(n) == (start..end).contains(&n)));
+            if end <= 8 {
+                assert_eq!(u8::mask(start..end) as u16, mask);
+            }
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "bits 3..9 of a 8-bit integer")]
+fn test_mask_out_of_range() {
+    u8::mask(3..9);
+}
+
+#[test]
+fn test_rotations() {
+    assert_eq!(0b1000_0001u8.rotl(1), 0b0000_0011);
+    assert_eq!(0b1000_0001u8.rotr(1), 0b1100_0000);
+    assert_eq!(1u64.rotr(1), 1 << 63);
+    // every u8 and every u16 by every amount, and some past the width, against std
+    for x in 0..=u8::MAX {
+        for n in 0..20 {
+            assert_eq!(x.rotl(n), x.rotate_left(n));
+            assert_eq!(x.rotr(n), x.rotate_right(n));
+            assert_eq!(x.rotl(n).rotr(n), x);
+            assert_eq!(x.rotl(n).count_ones(), x.count_ones());
+        }
+    }
+    for x in 0..=u16::MAX {
+        for n in 0..=16 {
+            assert_eq!(x.rotl(n), x.rotate_left(n));
+            assert_eq!(x.rotr(n), x.rotate_right(n));
+        }
+    }
+    let x = 0x0123_4567_89ab_cdefu64;
+    assert_eq!(x.rotl(16), 0x4567_89ab_cdef_0123);
+    assert_eq!(x.rotr(4), 0xf012_3456_789a_bcde);
+}
+
+#[test]
+fn test_perms() {
+    let rw = Perms::READ | Perms::WRITE;
+    assert_eq!(rw.to_string(), "rw-");
+    assert_eq!(rw.bits(), 0b110);
+    assert!(rw.contains(Perms::READ) && !rw.contains(Perms::EXECUTE));
+    assert_eq!(rw & (Perms::WRITE | Perms::EXECUTE), Perms::WRITE);
+    assert_eq!(!rw, Perms::EXECUTE);
+    assert_eq!(Perms::all().to_string(), "rwx");
+    assert_eq!(Perms::empty().to_string(), "---");
+    assert_eq!(Perms::default(), Perms::empty());
+
+    let mut p = Perms::EXECUTE;
+    p.insert(Perms::READ);
+    assert_eq!(p.to_string(), "r-x");
+    p.remove(Perms::EXECUTE | Perms::WRITE);
+    assert_eq!(p, Perms::READ);
+    p &= Perms::WRITE;
+    assert!(p.is_empty());
+}
+
+#[test]
+fn test_every_perms() {
+    // all eight sets, and all 256 u8s of which only those eight are Perms
+    let every: Vec<Perms> = (0..=u8::MAX).filter_map(Perms::from_bits).collect();
+    assert_eq!(every.len(), 8);
+    for &a in &every {
+        assert_eq!(!!a, a);
+        assert_eq!(a | !a, Perms::all());
+        assert_eq!(a & !a, Perms::empty());
+        assert_eq!(a.to_string().chars().filter(|&c| c != '-').count(),
+                   a.bits().count_ones() as usize);
+        for &b in &every {
+            assert_eq!((a | b).bits(), a.bits() | b.bits());
+            assert_eq!((a & b).bits(), a.bits() & b.bits());
+            assert_eq!(a.contains(b), a | b == a);
+            assert_eq!(!(a | b), !a & !b);
+            let mut c = a;
+            c.insert(b);
+            assert_eq!(c, a | b);
+            c.remove(b);
+            assert_eq!(c, a & !b);
+        }
+    }
+}
@@ -0,0 +1,58 @@
+// Deterministic output: with `--deterministic`, a binary prints the same thing every time
+// it runs, so its output can be checked against a golden file and its images reproduced.
+//
+//   $ cargo run -q -p atomics -- --deterministic
+//   expected 4000000: Counter got 4000000
+//   spinlock: 4000 pushes
+//   ...
+//
+// What changes from run to run is left out or put in order: how long things took, what
+// a race happened to do, the order threads printed in or a HashMap iterates in, and the
+// thread ids in panic messages. Thread counts are fixed numbers, not the machine's.
+//
+// Every binary takes the flag, even those with nothing to change; Args takes it out of
+// the arguments. The RUSTFUN_DETERMINISTIC environment variable, set to 1, does the same,
+// which is how the menu passes it on to the demos it runs.
+//
+use std::sync::atomic::{AtomicU8, Ordering};
+
+// 1.  As with the language, a process-wide setting in an atomic; UNSET means the command
+//     line and RUSTFUN_DETERMINISTIC haven't been looked at yet.
+const UNSET: u8 = 0;
+const OFF: u8 = 1;
+const ON: u8 = 2;
+static MODE: AtomicU8 = AtomicU8::new(UNSET);
+
+/// Whether the output should be the same on every run.
+pub fn is_on() -> bool {
+    match MODE.load(Ordering::Relaxed) {
+        ON => true,
+        OFF => false,
+        _ => {
+            let on = std::env::args().skip(1).any(|a| a == "--deterministic")
+                || std::env::var("RUSTFUN_DETERMINISTIC").is_ok_and(|v| v == "1");
+            set(on);
+            on
+        }
+    }
+}
+
+pub fn set(on: bool) {
+    MODE.store(if on { ON } else { OFF }, Ordering::Relaxed);
+}
+
+/// Take every `--deterministic` out of `args`, and say whether there was one.
+pub fn take_flag(args: &mut Vec<String>) -> bool {
+    let before = args.len();
+    args.retain(|a| a != "--deterministic");
+    args.len() < before
+}
+
+#[test]
+fn test_take_flag() {
+    let mut args: Vec<String> = ["12", "--deterministic", "18"].iter().map(|s| s.to_string()).collect();
+    assert!(take_flag(&mut args));
+    assert_eq!(args, ["12", "18"]);
+    assert!(!take_flag(&mut args));
+    assert_eq!(args, ["12", "18"]);
+}
@@ -0,0 +1,89 @@
+// A chapter's walkthrough, printed by `--explain`: what the program demonstrates, topic
+// by topic, so the teaching in the source can be read without opening it.
+//
+//   $ cargo run -q -p hello -- --explain
+//   01hello: the gcd of the command-line arguments
+//
+//   1. Functions
+//      fn introduces a function; parameters and the return type after -> are typed...
+//
+// The text is compiled into the binary, in both languages, and follows `--lang`.
+//
+use crate::lang::Text;
+
+/// One numbered topic: a short title and a paragraph or two. Line breaks in `body` are
+/// kept, and each line is indented under the title.
+pub struct Topic {
+    pub title: Text,
+    pub body: Text,
+}
+
+/// The walkthrough of a chapter.
+pub struct Explanation {
+    /// The chapter's directory, like `01hello`.
+    pub chapter: &'static str,
+    pub summary: Text,
+    pub topics: &'static [Topic],
+}
+
+impl Explanation {
+    /// The walkthrough as text, in the current language.
+    pub fn render(&self) -> String {
+        let mut out = format!("{}: {}\n", self.chapter, self.summary);
+        for (i, topic) in self.topics.iter().enumerate() {
+            let number = format!("{}. ", i + 1);
+            out.push('\n');
+            out.push_str(&number);
+            out.push_str(topic.title.get());
+            out.push('\n');
+            for line in topic.body.get().lines() {
+                if !line.is_empty() {
+                    out.push_str(&" ".repeat(number.len()));
+                    out.push_str(line);
+                }
+                out.push('\n');
+            }
+        }
+        out
+    }
+
+    /// If the command line has `--explain`, print the walkthrough and exit; otherwise
+    /// do nothing, and leave the arguments to the program. Call it first thing in main.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn on_request(&self) {
+        let mut args: Vec<String> = std::env::args().skip(1).collect();
+        if !args.iter().any(|a| a == "--explain") {
+            return;
+        }
+        if let Ok(Some(language)) = crate::lang::take_flag(&mut args) {
+            crate::lang::set(language);
+        }
+        print!("{}", self.render());
+        std::process::exit(0);
+    }
+}
+
+#[test]
+fn test_render() {
+    const EXAMPLE: Explanation = Explanation {
+        chapter: "00example",
+        summary: Text { en: "an example", zh: "一个例子" },
+        topics: &[
+            Topic {
+                title: Text { en: "First", zh: "第一" },
+                body: Text { en: "one line\n\nand another", zh: "一行" },
+            },
+            Topic {
+                title: Text { en: "Second", zh: "第二" },
+                body: Text { en: "last", zh: "最后" },
+            },
+        ],
+    };
+    use crate::lang;
+    // The language is whatever RUSTFUN_LANG says, so compare with what it picked.
+    let expected = match lang::current() {
+        lang::Lang::En => "00example: an example\n\n1. First\n   one line\n\n   and another\n\n2. Second\n   last\n",
+        lang::Lang::Zh => "00example: 一个例子\n\n1. 第一\n   一行\n\n2. 第二\n   最后\n",
+    };
+    assert_eq!(EXAMPLE.render(), expected);
+}
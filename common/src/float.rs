@@ -0,0 +1,99 @@
+// Comparing floats without ==: a computed f64 is usually a rounding or two away from the
+// number written down for it, so `0.1 + 0.2 == 0.3` is false. Two ways to allow for that:
+//
+//   approx_eq(0.1 + 0.2, 0.3, 1e-12)    within a fixed distance, for numbers of a known size
+//   ulps_eq(0.1 + 0.2, 0.3, 1)          within n representable floats, for any size
+//
+// Note:
+//
+// 1.) An absolute eps fits numbers near 1: 1e-12 is tight there, but below the gap
+//     between neighbouring f64s around 1e6, and enormous next to 1e-20. Counting ULPs
+//     ("units in the last place", the floats in between) scales with the numbers.
+// 2.) Neither is ever true for a NaN, which isn't equal to anything, itself included;
+//     infinities are equal only to themselves, and 0.0 and -0.0 are equal, as with ==.
+// 3.) The ULP count reads the bits: for floats of one sign, the order of their bit
+//     patterns as integers is the order of the floats. Negative floats count down from
+//     -0.0, so that -0.0 and 0.0 meet at 0 and the distance across zero adds up.
+//
+
+/// Whether `a` and `b` differ by at most `eps`: for numbers whose size is known, near 1
+/// say, and an eps to match. f32 values can be passed too.
+pub fn approx_eq(a: impl Into<f64>, b: impl Into<f64>, eps: f64) -> bool {
+    let (a, b) = (a.into(), b.into());
+    a == b || (a - b).abs() <= eps
+}
+
+/// f32 and f64, as their bits in order: see `ulps_between`.
+pub trait Ulps: Copy {
+    /// The float's place among all floats of its type, or None for a NaN.
+    fn ulps_key(self) -> Option<i64>;
+}
+
+macro_rules! ulps_key {
+    ($($float:ty),*) => {
+        $(impl Ulps for $float {
+            fn ulps_key(self) -> Option<i64> {
+                if self.is_nan() {
+                    return None;
+                }
+                // the bits without the sign count up from 0.0; a negative float's count
+                // down from it (note 3)
+                let magnitude = self.abs().to_bits() as i64;
+                Some(if self.is_sign_negative() { -magnitude } else { magnitude })
+            }
+        })*
+    };
+}
+ulps_key!(f32, f64);
+
+/// How many floats of their type it takes to get from `a` to `b`: 0 for the same number,
+/// 1 for neighbours. None if either is a NaN.
+pub fn ulps_between<F: Ulps>(a: F, b: F) -> Option<u64> {
+    Some(a.ulps_key()?.abs_diff(b.ulps_key()?))
+}
+
+/// Whether `a` and `b` are at most `max_ulps` floats apart.
+pub fn ulps_eq<F: Ulps>(a: F, b: F, max_ulps: u64) -> bool {
+    ulps_between(a, b).is_some_and(|ulps| ulps <= max_ulps)
+}
+
+#[test]
+fn test_approx_eq() {
+    assert_ne!(0.1 + 0.2, 0.3);
+    assert!(approx_eq(0.1 + 0.2, 0.3, 1e-12));
+    assert!(approx_eq(1.0, 1.1, 0.1 + 1e-12));
+    assert!(!approx_eq(1.0, 1.2, 0.1));
+    assert!(approx_eq(5f32.sqrt(), 2.236068, 1e-6));
+    assert!(approx_eq(-0.0, 0.0, 0.0));
+    assert!(approx_eq(f64::INFINITY, f64::INFINITY, 0.0));
+    assert!(!approx_eq(f64::INFINITY, f64::MAX, 1e300));
+    assert!(!approx_eq(f64::NAN, f64::NAN, f64::INFINITY));
+    // Note 1: 1e-12 is less than one step between floats near 1e6, and far more than
+    // all of 1e-20.
+    let next = f64::from_bits(1e6f64.to_bits() + 1);
+    assert!(!approx_eq(1e6, next, 1e-12));
+    assert!(approx_eq(1e-20, 5e-20, 1e-12));
+}
+
+#[test]
+fn test_ulps() {
+    assert_eq!(ulps_between(1.0, 1.0), Some(0));
+    assert_eq!(ulps_between(1.0, 1.0 + f64::EPSILON), Some(1));
+    assert_eq!(ulps_between(1.0f32, 1.0 + f32::EPSILON), Some(1));
+    assert_eq!(ulps_between(0.1 + 0.2, 0.3), Some(1));
+    assert_eq!(ulps_between(0.0, -0.0), Some(0));
+    // across zero: the smallest positive float and the smallest negative one
+    let tiny = f64::from_bits(1);
+    assert_eq!(ulps_between(-tiny, tiny), Some(2));
+    assert_eq!(ulps_between(f64::MAX, f64::INFINITY), Some(1));
+    assert_eq!(ulps_between(f32::NAN, 1.0), None);
+    assert_eq!(ulps_between(f64::MIN, f64::MAX), Some(2 * (f64::INFINITY.to_bits() - 1)));
+
+    assert!(ulps_eq(0.1 + 0.2, 0.3, 1));
+    assert!(!ulps_eq(0.1 + 0.2, 0.3, 0));
+    // the same count of steps works at any size, as no one eps does (note 1)
+    assert!(ulps_eq(1e20 * (0.1 + 0.2), 1e20 * 0.3, 2));
+    assert!(ulps_eq(1e-20 * (0.1 + 0.2), 1e-20 * 0.3, 2));
+    assert!(!ulps_eq(1e-20, 5e-20, 1000));
+    assert!(!ulps_eq(f64::NAN, f64::NAN, u64::MAX));
+}
@@ -0,0 +1,155 @@
+// The language of the text the binaries print for people: usage, argument errors, and
+// the menu's walk through the chapters. English or Chinese, chosen at run time:
+//
+//   $ cargo run -q -p hello -- --lang zh 12 x
+//   gcd: NUMBER: 不是数字: "x"
+//   用法: gcd NUMBER ...
+//
+// `--lang` on the command line wins; otherwise the RUSTFUN_LANG environment variable,
+// which is how the menu passes its language on to the demos it runs; otherwise English.
+// What a program computes (its results, and the notes in its source) stays as it is.
+//
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lang {
+    En,
+    Zh,
+}
+
+impl Lang {
+    /// The code `--lang` takes for this language.
+    pub fn code(self) -> &'static str {
+        match self {
+            Lang::En => "en",
+            Lang::Zh => "zh",
+        }
+    }
+}
+
+// 1.  Locale names like `zh_CN.UTF-8` or `en-US` are taken by their language alone.
+impl FromStr for Lang {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Lang, String> {
+        let language = s.split(['-', '_', '.']).next().unwrap_or("");
+        match language.to_ascii_lowercase().as_str() {
+            "en" => Ok(Lang::En),
+            "zh" => Ok(Lang::Zh),
+            _ => Err(format!("unknown language {:?}; try en or zh", s)),
+        }
+    }
+}
+
+#[test]
+fn test_parse_lang() {
+    assert_eq!("en".parse(), Ok(Lang::En));
+    assert_eq!("zh_CN.UTF-8".parse(), Ok(Lang::Zh));
+    assert_eq!("ZH-tw".parse(), Ok(Lang::Zh));
+    assert_eq!("fr".parse::<Lang>(), Err("unknown language \"fr\"; try en or zh".to_string()));
+}
+
+// 2.  The language is a process-wide setting, read from everywhere that prints, so it
+//     lives in a static. An atomic needs no lock; UNSET means RUSTFUN_LANG hasn't been
+//     looked at yet.
+const UNSET: u8 = 0;
+static CURRENT: AtomicU8 = AtomicU8::new(UNSET);
+
+fn to_u8(lang: Lang) -> u8 {
+    match lang {
+        Lang::En => 1,
+        Lang::Zh => 2,
+    }
+}
+
+/// The language to print in.
+pub fn current() -> Lang {
+    match CURRENT.load(Ordering::Relaxed) {
+        1 => Lang::En,
+        2 => Lang::Zh,
+        _ => {
+            let lang = std::env::var("RUSTFUN_LANG").ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(Lang::En);
+            CURRENT.store(to_u8(lang), Ordering::Relaxed);
+            lang
+        }
+    }
+}
+
+pub fn set(lang: Lang) {
+    CURRENT.store(to_u8(lang), Ordering::Relaxed);
+}
+
+/// Take `--lang LANG` or `--lang=LANG` out of `args`, wherever it is, and return the
+/// language it names, if it's there.
+pub fn take_flag(args: &mut Vec<String>) -> Result<Option<Lang>, String> {
+    let Some(i) = args.iter().position(|a| a == "--lang" || a.starts_with("--lang=")) else {
+        return Ok(None);
+    };
+    let flag = args.remove(i);
+    let value = match flag.strip_prefix("--lang=") {
+        Some(value) => value.to_string(),
+        None if i < args.len() => args.remove(i),
+        None => return Err("--lang needs a language: en or zh".to_string()),
+    };
+    value.parse().map(Some)
+}
+
+#[test]
+fn test_take_flag() {
+    let mut args: Vec<String> = ["12", "--lang", "zh", "18"].iter().map(|s| s.to_string()).collect();
+    assert_eq!(take_flag(&mut args), Ok(Some(Lang::Zh)));
+    assert_eq!(args, ["12", "18"]);
+
+    let mut args = vec!["--lang=en".to_string()];
+    assert_eq!(take_flag(&mut args), Ok(Some(Lang::En)));
+    assert!(args.is_empty());
+
+    assert_eq!(take_flag(&mut vec!["12".to_string()]), Ok(None));
+    assert!(take_flag(&mut vec!["--lang".to_string()]).is_err());
+    assert!(take_flag(&mut vec!["--lang".to_string(), "fr".to_string()]).is_err());
+}
+
+/// A piece of text in both languages, which shows in the current one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Text {
+    pub en: &'static str,
+    pub zh: &'static str,
+}
+
+impl Text {
+    pub fn get(&self) -> &'static str {
+        match current() {
+            Lang::En => self.en,
+            Lang::Zh => self.zh,
+        }
+    }
+}
+
+// 3.  Text that reads the same in both languages, like a bare command line.
+impl From<&'static str> for Text {
+    fn from(s: &'static str) -> Text {
+        Text { en: s, zh: s }
+    }
+}
+
+impl fmt::Display for Text {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.get())
+    }
+}
+
+/// format! in the current language: `tr!("missing {}", "缺少 {}", name)`. Both templates
+/// are checked against the arguments at compile time, as format! checks one.
+#[macro_export]
+macro_rules! tr {
+    ($en:literal, $zh:literal $(, $arg:expr)* $(,)?) => {
+        match $crate::lang::current() {
+            $crate::lang::Lang::En => format!($en $(, $arg)*),
+            $crate::lang::Lang::Zh => format!($zh $(, $arg)*),
+        }
+    };
+}
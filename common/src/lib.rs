@@ -0,0 +1,326 @@
+//
+// Helpers shared by the chapter crates, so each one is written (and tested) once.
+//
+// Note:
+//
+// 1.) The package is named `rustfun-common`; in code the hyphen becomes an underscore:
+//     `use rustfun_common::gcd;`.
+// 2.) Chapters depend on it by path: `rustfun-common = { path = "../common" }`.
+// 3.) The notes that came with each function in its original chapter came along too.
+// 4.) With the `logging` feature, init_logging sets up the log facade for a binary.
+// 5.) Args reads a binary's command line, so every chapter reports bad arguments alike.
+// 6.) lang holds the language, English or Chinese, of what the binaries say to people.
+// 7.) An Explanation is a chapter's walkthrough, which its binary prints for --explain.
+// 8.) It all builds for wasm32-unknown-unknown, for the browser, except what needs a
+//     process: reading its command line (Args::from_env, on_request) and exiting
+//     (ArgError::exit). Those are left out there with #[cfg(not(target_arch = "wasm32"))];
+//     there's no file or thread in here to leave out.
+// 9.) deterministic says whether --deterministic asked for the same output on every run.
+// 10.) float compares floats the way == can't: within an eps, or within a count of ULPs.
+// 11.) bits sets, clears and tests single bits, makes masks and rotates, for every
+//      unsigned type; Perms is a set of flags in a u8, combined with | and &.
+//
+use std::str::FromStr;
+
+pub mod args;
+pub use crate::args::{ArgError, Args};
+
+pub mod lang;
+pub use crate::lang::{Lang, Text};
+
+pub mod explain;
+pub use crate::explain::{Explanation, Topic};
+
+pub mod deterministic;
+
+pub mod float;
+pub use crate::float::{approx_eq, ulps_between, ulps_eq};
+
+pub mod bits;
+pub use crate::bits::{Bits, Perms};
+
+#[cfg(feature = "logging")]
+mod logging;
+#[cfg(feature = "logging")]
+pub use crate::logging::init_logging;
+
+//  1. The fn keyword (pronounced “fun”) introduces a function
+//  2. the mut keyword (pronounced “mute”, short for mutable) By default,
+//     once a variable is initialized, its value can’t be changed,
+//  3. type u64, an unsigned 64-bit integer.
+//  4. -> token precedes the return type
+/// The greatest common divisor of `n` and `m`, by Euclid's algorithm.
+/// Panics if either is zero.
+// Kept as written in chapter 01, hand-written swap and all.
+#[allow(clippy::manual_swap, clippy::assign_op_pattern)]
+pub fn gcd(mut n: u64, mut m: u64) -> u64 {
+    // 5. assert! macro, verifying that neither argument is zero.
+    // 6. The ! character marks this as a macro invocation, not a function call.
+    assert!(n != 0 && m != 0);
+    // 7. does not require parentheses around the conditional expressions
+    while m != 0 {
+        if m < n {
+            // 8. A let statement declares a local variable, don’t need to write out
+            //    t’s type, as long as Rust can infer it
+            let t = m;
+            m = n;
+            n = t;
+        }
+        m = m % n;
+    }
+    // 9. If a function body ends with an expression that is not followed by a semicolon,
+    // that’s the function’s return value.
+    n
+}
+
+// 10. #[test] marks a test function, test_gcd() skipped in normal compilations,
+//     but included and called automatically with the 'cargo test' command.
+// 11. #[test] is an attribute. like #ifdef in C and C++, or annotations in Java
+#[test]
+fn test_gcd() {
+    assert_eq!(gcd(14, 15), 1);
+    assert_eq!(gcd(2 * 3 * 5 * 11 * 17, 3 * 7 * 11 * 13 * 19), 3 * 11);
+}
+
+#[test]
+#[should_panic]
+fn test_gcd_of_zero() {
+    gcd(0, 5);
+}
+
+/// The greatest common divisor of all of `numbers`. Panics if the slice is empty or
+/// holds a zero.
+// 12.  &numbers[1..] borrows the elements from the second onward; `*m` dereferences
+//      each one to get the u64 it refers to.
+pub fn gcd_all(numbers: &[u64]) -> u64 {
+    let mut d = numbers[0];
+    for m in &numbers[1..] {
+        d = gcd(d, *m);
+    }
+    d
+}
+
+#[test]
+fn test_gcd_all() {
+    assert_eq!(gcd_all(&[42]), 42);
+    assert_eq!(gcd_all(&[12, 18, 30]), 6);
+    assert_eq!(gcd_all(&[7, 11, 13]), 1);
+}
+
+/// Parse the string `s` as a coordinate pair, like `"400x600"` or `"1.0,0.5"`.
+///
+/// Specifically, `s` should have the form <left><sep><right>, where <sep> is
+/// the character given by the `separator` argument, and <left> and <right> are both
+/// strings that can be parsed by `T::from_str`.
+///
+/// If `s` has the proper form, return `Some<(x, y)>`. If it doesn't parse
+/// correctly, return `None`.
+// 1.  The definition of parse_pair is a generic function
+// 1.1 When you use a generic function, Rust will often be able to infer type parameters for you
+// 1.2  <T: FromStr> means "For any type T that implements the FromStr trait..."
+// 2.  return type is Option<(T, T)>: either None, or a value Some((v1, v2)), where (v1, v2) is a
+//     tuple of two values, both of type T.
+pub fn parse_pair<T: FromStr>(s: &str, separator: char) -> Option<(T, T)> {
+    // 3. no explicit return statement, so its return value is the value of the last
+    //    expression in the match body
+    match s.find(separator) {
+        //4. the entire match expression evaluates to None, indicating that the parse failed.
+        None => None,
+        //5. Otherwise, we take index to be the separator’s position in the string.
+        Some(index) => {
+            // The power of the match expression in the Rust.
+            // 6.  The argument to the match is this tuple expression
+            //     (T::from_str(&s[..index]), T::from_str(&s[index + separator.len_utf8()..]))
+            // 7.  This pattern matches only if both elements of the tuple are Ok variants of
+            //     the Result type, indicating that both parses succeeded.
+            // 8.  The wildcard pattern _ matches anything, and ignores its value.
+            // 9.  `index` is a byte offset, and the separator may take more than one byte
+            //     in UTF-8; skipping just one would slice into the middle of it and panic.
+            match (T::from_str(&s[..index]), T::from_str(&s[index + separator.len_utf8()..])) {
+                (Ok(l), Ok(r)) => Some((l, r)),
+                _ => None
+            }
+        }
+    }
+}
+
+#[test]
+fn test_parse_pair() {
+    assert_eq!(parse_pair::<i32>("",        ','), None);
+    assert_eq!(parse_pair::<i32>("10,",     ','), None);
+    assert_eq!(parse_pair::<i32>(",10",     ','), None);
+    assert_eq!(parse_pair::<i32>("10,20",   ','), Some((10, 20)));
+    assert_eq!(parse_pair::<i32>("10,20xy", ','), None);
+    assert_eq!(parse_pair::<f64>("0.5x",    'x'), None);
+    assert_eq!(parse_pair::<f64>("0.5x1.5", 'x'), Some((0.5, 1.5)));
+    assert_eq!(parse_pair::<usize>("1000x750", 'x'), Some((1000, 750)));
+    assert_eq!(parse_pair::<i32>("10×20",   '×'), Some((10, 20)));
+}
+
+/// Why `parse_numbers` rejected its input.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NumbersError {
+    /// There were no numbers at all.
+    Empty,
+    /// This argument isn't a u64.
+    NotANumber(String),
+    /// A zero, which `gcd` doesn't accept.
+    Zero,
+}
+
+impl std::fmt::Display for NumbersError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            NumbersError::Empty => write!(f, "no numbers given"),
+            NumbersError::NotANumber(ref arg) => write!(f, "not a number: {:?}", arg),
+            NumbersError::Zero => write!(f, "zero has no greatest common divisor"),
+        }
+    }
+}
+
+/// Parse the arguments of a gcd command: one or more positive integers, each one a
+/// separate string. The result can always be given to `gcd_all` without it panicking.
+// 1.  AsRef<str> takes a &[String] (from env::args) as well as a &[&str] (from a form).
+pub fn parse_numbers<S: AsRef<str>>(args: &[S]) -> Result<Vec<u64>, NumbersError> {
+    if args.is_empty() {
+        return Err(NumbersError::Empty);
+    }
+    let mut numbers = Vec::with_capacity(args.len());
+    for arg in args {
+        let arg = arg.as_ref();
+        match u64::from_str(arg) {
+            Ok(0) => return Err(NumbersError::Zero),
+            Ok(n) => numbers.push(n),
+            Err(_) => return Err(NumbersError::NotANumber(arg.to_string())),
+        }
+    }
+    Ok(numbers)
+}
+
+#[test]
+fn test_parse_numbers() {
+    assert_eq!(parse_numbers(&["42", "56"]), Ok(vec![42, 56]));
+    assert_eq!(parse_numbers(&["18446744073709551615"]), Ok(vec![u64::MAX]));
+    assert_eq!(parse_numbers::<&str>(&[]), Err(NumbersError::Empty));
+    assert_eq!(parse_numbers(&["42", "0"]), Err(NumbersError::Zero));
+    assert_eq!(parse_numbers(&["42", "x"]), Err(NumbersError::NotANumber("x".to_string())));
+    assert_eq!(parse_numbers(&["18446744073709551616"]),
+               Err(NumbersError::NotANumber("18446744073709551616".to_string())));
+    assert_eq!(parse_numbers(&["-1"]), Err(NumbersError::NotANumber("-1".to_string())));
+    assert_eq!(parse_numbers(&["7".to_string()]), Ok(vec![7]));
+}
+
+/// The sieve of Eratosthenes: `sieve(n)[i]` is true exactly when `i` is a prime, for
+/// every `i` up to and including `n`.
+// 1.  vec![V; N] is the Vec version of the array expression [V; N]: N elements, each V.
+//     An array's length must be a compile-time constant, so a sieve whose size is an
+//     argument needs a Vec.
+// 2.  Only factors up to sqrt(n) need crossing out; every composite number up to n has
+//     one. And the multiples of i below i * i were already crossed out by smaller primes.
+pub fn sieve(n: usize) -> Vec<bool> {
+    let mut is_prime = vec![true; n + 1];
+    is_prime[0] = false;
+    if n >= 1 {
+        is_prime[1] = false;
+    }
+    let mut i = 2;
+    while i * i <= n {
+        if is_prime[i] {
+            let mut j = i * i;
+            while j <= n {
+                is_prime[j] = false;
+                j += i;
+            }
+        }
+        i += 1;
+    }
+    is_prime
+}
+
+/// All the primes up to and including `n`, in order.
+pub fn primes_up_to(n: usize) -> Vec<usize> {
+    sieve(n).iter().enumerate().filter(|&(_, &p)| p).map(|(i, _)| i).collect()
+}
+
+#[test]
+fn test_sieve() {
+    let is_prime = sieve(10000);
+    assert_eq!(is_prime.len(), 10001);
+    assert!(is_prime[211]);
+    assert!(!is_prime[9867]);
+    assert!(is_prime[9973]);
+    assert!(!is_prime[0] && !is_prime[1] && is_prime[2]);
+    assert_eq!(sieve(0), vec![false]);
+    assert_eq!(sieve(1), vec![false, false]);
+}
+
+#[test]
+fn test_primes_up_to() {
+    assert_eq!(primes_up_to(1), vec![]);
+    assert_eq!(primes_up_to(2), vec![2]);
+    assert_eq!(primes_up_to(30), vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+    assert_eq!(primes_up_to(10000).len(), 1229);
+}
+
+/// All the primes from `low` up to and including `high`, in order. It takes memory for
+/// the primes up to sqrt(high) and one segment of the range, not for all of 0..=high as
+/// `sieve` does, so a short range of big numbers, like the last hundred below 10^12, is
+/// as cheap as one of small numbers.
+// 3.  A segmented sieve. Every composite number up to high has a prime factor no bigger
+//     than sqrt(high), so those primes, from the plain sieve, are all it crosses out
+//     with, a segment of SEGMENT numbers at a time. In each segment a prime p starts at
+//     its first multiple there, but never below p * p, as in note 2.
+// 4.  Offsets from the segment's start, instead of the numbers themselves, keep every
+//     sum below SEGMENT, so nothing overflows however close `high` is to usize::MAX.
+pub fn primes_between(low: usize, high: usize) -> Vec<usize> {
+    const SEGMENT: usize = 1 << 15;
+    let mut primes = Vec::new();
+    if high < 2 || low > high {
+        return primes;
+    }
+    let base = primes_up_to(high.isqrt());
+    let mut is_prime = vec![true; SEGMENT];
+    let mut start = low.max(2);
+    loop {
+        let end = start.saturating_add(SEGMENT - 1).min(high);
+        let len = end - start + 1;
+        is_prime[..len].fill(true);
+        for &p in &base {
+            let square = p * p;
+            if square > end {
+                break;
+            }
+            let mut i = if square >= start { square - start } else { (p - start % p) % p };
+            while i < len {
+                is_prime[i] = false;
+                i += p;
+            }
+        }
+        primes.extend((0..len).filter(|&i| is_prime[i]).map(|i| start + i));
+        if end == high {
+            return primes;
+        }
+        start = end + 1;
+    }
+}
+
+#[test]
+fn test_primes_between() {
+    assert_eq!(primes_between(0, 10000), primes_up_to(10000));
+    assert_eq!(primes_between(10, 30), vec![11, 13, 17, 19, 23, 29]);
+    assert_eq!(primes_between(2, 2), vec![2]);
+    assert_eq!(primes_between(24, 28), vec![]);
+    assert_eq!(primes_between(0, 1), vec![]);
+    assert_eq!(primes_between(30, 10), vec![]);
+    // across several segments, starting and ending in the middle of one
+    let expected: Vec<usize> = primes_up_to(200_000).into_iter()
+        .filter(|&p| p >= 65_000)
+        .collect();
+    assert_eq!(primes_between(65_000, 200_000), expected);
+    // far beyond what sieve could hold, checked by trial division
+    let is_prime = |n: usize| (2..).take_while(|d| d * d <= n).all(|d| !n.is_multiple_of(d));
+    let low = 1_000_000_000_000 - 100;
+    let primes = primes_between(low, 1_000_000_000_000);
+    assert_eq!(primes, (low..=1_000_000_000_000).filter(|&n| is_prime(n)).collect::<Vec<_>>());
+    assert!(!primes.is_empty());
+}
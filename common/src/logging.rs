@@ -0,0 +1,14 @@
+// Logging for the binaries. Libraries only call the `log` macros (error!, warn!, info!,
+// debug!, trace!), which do nothing until a binary installs a logger; every main starts
+// with init_logging() so that RUST_LOG works the same way everywhere.
+use env_logger::{Builder, Env};
+
+/// Install env_logger for this process. RUST_LOG chooses what is shown, e.g.
+/// `RUST_LOG=debug` or `RUST_LOG=threadpool=trace`; without it, warnings and errors.
+/// Calling it a second time does nothing.
+pub fn init_logging() {
+    // No timestamps: the programs are short-lived, and their stderr ends up in tests.
+    let _ = Builder::from_env(Env::default().default_filter_or("warn"))
+        .format_timestamp(None)
+        .try_init();
+}
@@ -0,0 +1,113 @@
+//
+// One error type for the binaries' fallible functions, so each `main` reports failures
+// the same way instead of panicking in unwrap() or expect().
+//
+// Note:
+//
+// 1.) Five kinds cover what the chapters run into: input that doesn't parse, I/O,
+//     numbers too big for their type, the HTTP servers failing to start, and, for the
+//     repository's own tasks, a check that didn't pass.
+// 2.) thiserror's #[derive(Error)] writes the Display and std::error::Error impls from
+//     the #[error("...")] attributes; #[from] also writes a From impl, so `?` converts.
+// 3.) The other From impls pick the kind: a ParseIntError is Overflow when the digits
+//     were fine but too many, and Parse otherwise.
+//
+use std::fmt::Display;
+use std::io;
+use std::num::{IntErrorKind, ParseFloatError, ParseIntError};
+use std::path::Path;
+
+use rustfun_common::NumbersError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// Text that isn't in the expected format, read or written.
+    #[error("{0}")]
+    Parse(String),
+    #[error("{0}")]
+    Io(#[from] io::Error),
+    /// A number too big (or too small) for its type.
+    #[error("{0}")]
+    Overflow(String),
+    /// A web server that couldn't start or stopped with an error.
+    #[error("HTTP server: {0}")]
+    Http(String),
+    /// A check that didn't pass: a build, a test run, an image unlike its reference.
+    #[error("{0}")]
+    Check(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    /// A Parse error from any error that can be displayed, like serde's.
+    pub fn parse<E: Display>(e: E) -> Error {
+        Error::Parse(e.to_string())
+    }
+
+    /// An Io error that names the file it happened to.
+    pub fn io_at<P: AsRef<Path>>(path: P, e: io::Error) -> Error {
+        Error::Io(io::Error::new(e.kind(), format!("{}: {}", path.as_ref().display(), e)))
+    }
+
+    pub fn http<E: Display>(e: E) -> Error {
+        Error::Http(e.to_string())
+    }
+
+    /// Print `program: error` to stderr and exit with status 1.
+    pub fn exit(&self, program: &str) -> ! {
+        eprintln!("{}: {}", program, self);
+        std::process::exit(1);
+    }
+}
+
+impl From<ParseIntError> for Error {
+    fn from(e: ParseIntError) -> Error {
+        match e.kind() {
+            IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => Error::Overflow(e.to_string()),
+            _ => Error::Parse(e.to_string()),
+        }
+    }
+}
+
+impl From<ParseFloatError> for Error {
+    fn from(e: ParseFloatError) -> Error {
+        Error::Parse(e.to_string())
+    }
+}
+
+impl From<NumbersError> for Error {
+    fn from(e: NumbersError) -> Error {
+        Error::Parse(e.to_string())
+    }
+}
+
+#[cfg(test)]
+fn parse_u8(s: &str) -> Result<u8> {
+    Ok(s.parse()?)
+}
+
+#[test]
+fn test_question_mark_picks_the_kind() {
+    assert!(matches!(parse_u8("7"), Ok(7)));
+    assert!(matches!(parse_u8("x"), Err(Error::Parse(_))));
+    match parse_u8("256") {
+        Err(Error::Overflow(message)) => assert_eq!(message, "number too large to fit in target type"),
+        other => panic!("expected Overflow, got {:?}", other),
+    }
+    let e: Error = "1.5x".parse::<f64>().unwrap_err().into();
+    assert_eq!(e.to_string(), "invalid float literal");
+    let e: Error = NumbersError::Zero.into();
+    assert_eq!(e.to_string(), "zero has no greatest common divisor");
+}
+
+#[test]
+fn test_io_errors() {
+    let e = Error::io_at("mandel.png", io::Error::new(io::ErrorKind::NotFound, "no such file"));
+    assert_eq!(e.to_string(), "mandel.png: no such file");
+    match e {
+        Error::Io(inner) => assert_eq!(inner.kind(), io::ErrorKind::NotFound),
+        other => panic!("expected Io, got {:?}", other),
+    }
+    assert_eq!(Error::http("address in use").to_string(), "HTTP server: address in use");
+}
@@ -0,0 +1,15 @@
+// Chapter 01: a first function, and a first loop over a slice. See 01hello/src/main.rs.
+#![allow(unused_variables)] // until the todo!()s are filled in
+
+/// The greatest common divisor of `n` and `m`, by Euclid's algorithm: replace the larger
+/// of the two by the remainder of dividing it by the smaller, until one of them is zero.
+/// Panics if either argument is zero.
+pub fn gcd(n: u64, m: u64) -> u64 {
+    todo!()
+}
+
+/// The greatest common divisor of all the numbers. Panics if the slice is empty.
+/// (Hint: gcd(a, b, c) is gcd(gcd(a, b), c).)
+pub fn gcd_all(numbers: &[u64]) -> u64 {
+    todo!()
+}
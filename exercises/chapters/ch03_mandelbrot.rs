@@ -0,0 +1,35 @@
+// Chapter 03: the pieces of the Mandelbrot plotter, with a complex number written as a
+// plain (re, im) tuple. See 03mandelbrot/src/main.rs.
+#![allow(unused_variables)] // until the todo!()s are filled in
+
+use std::str::FromStr;
+
+/// Parse `s` as two values separated by `separator`, like "400x600" or "1.0,0.5".
+/// Return None unless both halves parse as a `T`.
+pub fn parse_pair<T: FromStr>(s: &str, separator: char) -> Option<(T, T)> {
+    todo!()
+}
+
+/// Square `z` and add `c`, the step of the Mandelbrot iteration:
+/// (a + bi)^2 = (a^2 - b^2) + 2abi.
+pub fn square_add(z: (f64, f64), c: (f64, f64)) -> (f64, f64) {
+    todo!()
+}
+
+/// Iterate z = z^2 + c from z = 0. Return Some(i) for the first iteration i after which
+/// z is further than 2 from the origin, or None if it never is within `limit` iterations.
+pub fn escape_time(c: (f64, f64), limit: u32) -> Option<u32> {
+    todo!()
+}
+
+/// The point of the complex plane under `pixel` (column, row), in an image of `bounds`
+/// (width, height) pixels covering the rectangle from `upper_left` to `lower_right`.
+/// Rows go down the image, but the imaginary axis goes up.
+pub fn pixel_to_point(bounds: (usize, usize),
+                      pixel: (usize, usize),
+                      upper_left: (f64, f64),
+                      lower_right: (f64, f64))
+    -> (f64, f64)
+{
+    todo!()
+}
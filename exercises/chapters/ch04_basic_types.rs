@@ -0,0 +1,26 @@
+// Chapter 04: integers that overflow, and text that is not one byte per character.
+// See 04basicbype/src/main.rs.
+#![allow(unused_variables)] // until the todo!()s are filled in
+
+/// The average of `a` and `b`, rounded down. It must work for every pair of u32s,
+/// including two that add up to more than u32::MAX.
+pub fn average(a: u32, b: u32) -> u32 {
+    todo!()
+}
+
+/// The sum of `numbers`, or None if it doesn't fit in an i32.
+/// (Hint: look at i32's checked_ methods.)
+pub fn checked_sum(numbers: &[i32]) -> Option<i32> {
+    todo!()
+}
+
+/// How many vowels are in `text`: a, e, i, o, u and á, é, í, ó, ú, in either case.
+/// Count characters, not bytes; "é" takes two bytes in UTF-8.
+pub fn count_vowels(text: &str) -> usize {
+    todo!()
+}
+
+/// The words of `text` in reverse order, separated by single spaces.
+pub fn reverse_words(text: &str) -> String {
+    todo!()
+}
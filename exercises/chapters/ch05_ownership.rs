@@ -0,0 +1,26 @@
+// Chapter 05: values with a single owner, and moving them out of collections.
+// See 05ownership/src/main.rs.
+#![allow(unused_variables, dead_code)] // until the todo!()s are filled in
+
+pub struct Person {
+    pub name: String,
+    pub birth: i32,
+}
+
+/// The first `n` numbers of the Padovan sequence: 1, 1, 1, then each one is the sum of
+/// the two before the one before it, P(n) = P(n-2) + P(n-3).
+pub fn padovan(n: usize) -> Vec<u64> {
+    todo!()
+}
+
+/// The names of everyone born before `year`, in order. The people are ours to consume,
+/// so move the names out rather than cloning them.
+pub fn names_born_before(people: Vec<Person>, year: i32) -> Vec<String> {
+    todo!()
+}
+
+/// Take the first string out of `v`, and give back the rest too. None if `v` is empty.
+/// (You can't move out of v[0]; what can you do instead?)
+pub fn split_first(v: Vec<String>) -> Option<(String, Vec<String>)> {
+    todo!()
+}
@@ -0,0 +1,24 @@
+// Chapter 06: shared and mutable references, and lifetimes in signatures.
+// See 06borrowing/src/main.rs.
+#![allow(unused_variables, clippy::ptr_arg)] // until the todo!()s are filled in
+
+/// Append a copy of every element of `slice` to `vec`.
+pub fn extend(vec: &mut Vec<f64>, slice: &[f64]) {
+    todo!()
+}
+
+/// A reference to the smallest element of `v`, or None if it's empty.
+pub fn smallest(v: &[i32]) -> Option<&i32> {
+    todo!()
+}
+
+/// The longer of the two strings (the first one if they are the same length).
+/// The signature says the result may borrow from either argument.
+pub fn longest<'a>(a: &'a str, b: &'a str) -> &'a str {
+    todo!()
+}
+
+/// Double every element in place.
+pub fn double_all(v: &mut [i32]) {
+    todo!()
+}
@@ -0,0 +1,22 @@
+// Chapter 07: if, match and loops are expressions that produce values.
+// See 07expression/src/main.rs.
+#![allow(unused_variables)] // until the todo!()s are filled in
+
+/// Describe an error code, as 07expression's main does: 0 is "OK", 1 is
+/// "Wires Tangled", 2 is "User Asleep", anything else is "Unrecognized Error N", and no
+/// code at all is "None input". Write it as a single match expression.
+pub fn describe(code: Option<i32>) -> String {
+    todo!()
+}
+
+/// "Fizz" for multiples of 3, "Buzz" for multiples of 5, "FizzBuzz" for both, and the
+/// number itself otherwise.
+pub fn fizzbuzz(n: u32) -> String {
+    todo!()
+}
+
+/// How many steps of the Collatz map (n / 2 if n is even, 3n + 1 if odd) it takes to
+/// reach 1 from `n`. Panics if `n` is zero. (Hint: `loop` can `break` with a value.)
+pub fn collatz_steps(n: u64) -> u32 {
+    todo!()
+}
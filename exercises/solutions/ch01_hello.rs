@@ -0,0 +1,20 @@
+// Chapter 01, worked answers.
+
+pub fn gcd(mut n: u64, mut m: u64) -> u64 {
+    assert!(n != 0 && m != 0);
+    while m != 0 {
+        if m < n {
+            std::mem::swap(&mut n, &mut m);
+        }
+        m %= n;
+    }
+    n
+}
+
+pub fn gcd_all(numbers: &[u64]) -> u64 {
+    let mut d = numbers[0];
+    for &m in &numbers[1..] {
+        d = gcd(d, m);
+    }
+    d
+}
@@ -0,0 +1,37 @@
+// Chapter 03, worked answers.
+
+use std::str::FromStr;
+
+pub fn parse_pair<T: FromStr>(s: &str, separator: char) -> Option<(T, T)> {
+    let index = s.find(separator)?;
+    match (T::from_str(&s[..index]), T::from_str(&s[index + separator.len_utf8()..])) {
+        (Ok(l), Ok(r)) => Some((l, r)),
+        _ => None,
+    }
+}
+
+pub fn square_add(z: (f64, f64), c: (f64, f64)) -> (f64, f64) {
+    (z.0 * z.0 - z.1 * z.1 + c.0, 2.0 * z.0 * z.1 + c.1)
+}
+
+pub fn escape_time(c: (f64, f64), limit: u32) -> Option<u32> {
+    let mut z = (0.0, 0.0);
+    for i in 0..limit {
+        z = square_add(z, c);
+        if z.0 * z.0 + z.1 * z.1 > 4.0 {
+            return Some(i);
+        }
+    }
+    None
+}
+
+pub fn pixel_to_point(bounds: (usize, usize),
+                      pixel: (usize, usize),
+                      upper_left: (f64, f64),
+                      lower_right: (f64, f64))
+    -> (f64, f64)
+{
+    let (width, height) = (lower_right.0 - upper_left.0, upper_left.1 - lower_right.1);
+    (upper_left.0 + pixel.0 as f64 * width / bounds.0 as f64,
+     upper_left.1 - pixel.1 as f64 * height / bounds.1 as f64)
+}
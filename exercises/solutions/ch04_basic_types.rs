@@ -0,0 +1,24 @@
+// Chapter 04, worked answers.
+
+pub fn average(a: u32, b: u32) -> u32 {
+    // the halves can't overflow, and the last term adds back the 1 both of them lost
+    a / 2 + b / 2 + (a % 2 + b % 2) / 2
+}
+
+pub fn checked_sum(numbers: &[i32]) -> Option<i32> {
+    let mut sum: i32 = 0;
+    for &n in numbers {
+        sum = sum.checked_add(n)?;
+    }
+    Some(sum)
+}
+
+pub fn count_vowels(text: &str) -> usize {
+    text.chars()
+        .filter(|c| c.to_lowercase().any(|l| "aeiouáéíóú".contains(l)))
+        .count()
+}
+
+pub fn reverse_words(text: &str) -> String {
+    text.split_whitespace().rev().collect::<Vec<_>>().join(" ")
+}
@@ -0,0 +1,28 @@
+// Chapter 05, worked answers.
+
+pub struct Person {
+    pub name: String,
+    pub birth: i32,
+}
+
+pub fn padovan(n: usize) -> Vec<u64> {
+    let mut p = Vec::with_capacity(n);
+    for i in 0..n {
+        let next = if i < 3 { 1 } else { p[i - 2] + p[i - 3] };
+        p.push(next);
+    }
+    p
+}
+
+pub fn names_born_before(people: Vec<Person>, year: i32) -> Vec<String> {
+    // into_iter() takes the Vec by value, so each Person, and its name, is ours to move
+    people.into_iter().filter(|p| p.birth < year).map(|p| p.name).collect()
+}
+
+pub fn split_first(mut v: Vec<String>) -> Option<(String, Vec<String>)> {
+    if v.is_empty() {
+        return None;
+    }
+    let first = v.remove(0);
+    Some((first, v))
+}
@@ -0,0 +1,27 @@
+// Chapter 06, worked answers.
+
+pub fn extend(vec: &mut Vec<f64>, slice: &[f64]) {
+    for &elt in slice {
+        vec.push(elt);
+    }
+}
+
+pub fn smallest(v: &[i32]) -> Option<&i32> {
+    let mut s = v.first()?;
+    for r in &v[1..] {
+        if *r < *s {
+            s = r;
+        }
+    }
+    Some(s)
+}
+
+pub fn longest<'a>(a: &'a str, b: &'a str) -> &'a str {
+    if b.len() > a.len() { b } else { a }
+}
+
+pub fn double_all(v: &mut [i32]) {
+    for x in v.iter_mut() {
+        *x *= 2;
+    }
+}
@@ -0,0 +1,32 @@
+// Chapter 07, worked answers.
+
+pub fn describe(code: Option<i32>) -> String {
+    match code {
+        Some(0) => "OK".to_string(),
+        Some(1) => "Wires Tangled".to_string(),
+        Some(2) => "User Asleep".to_string(),
+        Some(n) => format!("Unrecognized Error {}", n),
+        None => "None input".to_string(),
+    }
+}
+
+pub fn fizzbuzz(n: u32) -> String {
+    match (n % 3, n % 5) {
+        (0, 0) => "FizzBuzz".to_string(),
+        (0, _) => "Fizz".to_string(),
+        (_, 0) => "Buzz".to_string(),
+        _ => n.to_string(),
+    }
+}
+
+pub fn collatz_steps(mut n: u64) -> u32 {
+    assert!(n != 0);
+    let mut steps = 0;
+    loop {
+        if n == 1 {
+            break steps;
+        }
+        n = if n.is_multiple_of(2) { n / 2 } else { 3 * n + 1 };
+        steps += 1;
+    }
+}
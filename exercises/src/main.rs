@@ -0,0 +1,126 @@
+//
+// Exercises: one file per chapter in chapters/, each a set of functions whose bodies are
+// todo!(). Fill them in until the chapter's tests pass; this runner reports how far along
+// each chapter is.
+//
+//   $ cargo run                      every chapter
+//   $ cargo run -- ch03_mandelbrot   just these chapters
+//   $ cargo run -- --solutions       against the worked answers
+//
+// Note:
+//
+// 1.) The tests live in tests/, one file per chapter, and are locked: change the code in
+//     chapters/, never the tests. `cargo test --test ch03_mandelbrot` runs one chapter.
+// 2.) todo!() panics with "not yet implemented", so an exercise nobody has started fails
+//     its tests instead of failing to compile.
+// 3.) Each test file pulls in its chapter with #[path], so every chapter is compiled on
+//     its own: a chapter that doesn't compile yet is reported as such, and neither the
+//     other chapters nor this runner are affected.
+// 4.) Worked answers are in solutions/. Building with `--features solutions` swaps them
+//     in for chapters/, which is how we check that the tests can be passed at all.
+//
+use std::env;
+use std::process::{exit, Command};
+
+const CHAPTERS: &[(&str, &str)] = &[
+    ("ch01_hello", "a first function: gcd"),
+    ("ch03_mandelbrot", "parsing, complex arithmetic, escape time"),
+    ("ch04_basic_types", "overflow, checked arithmetic, UTF-8 text"),
+    ("ch05_ownership", "moving values out of collections"),
+    ("ch06_borrowing", "references and lifetimes"),
+    ("ch07_expressions", "match, if and loop as expressions"),
+];
+
+// How one chapter went: the result of each test, or the reason there are none.
+enum Outcome {
+    Ran(Vec<(String, bool)>),
+    DidNotCompile(String),
+}
+
+fn run_chapter(chapter: &str, solutions: bool) -> Outcome {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let mut command = Command::new(cargo);
+    command.args(["test", "--manifest-path", concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml"),
+                  "--test", chapter]);
+    if solutions {
+        command.args(["--features", "solutions"]);
+    }
+    // one thread, so the panic messages of failing tests don't interleave
+    command.args(["--", "--test-threads=1"]);
+    log::debug!("{:?}", command);
+    let output = match command.output() {
+        Ok(output) => output,
+        Err(e) => {
+            eprintln!("exercises: can't run cargo: {}", e);
+            exit(2);
+        }
+    };
+
+    // 1.  libtest prints one "test NAME ... ok" (or FAILED) line per test.
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let results: Vec<(String, bool)> = stdout.lines().filter_map(|line| {
+        let rest = line.strip_prefix("test ")?;
+        let (name, result) = rest.rsplit_once(" ... ")?;
+        Some((name.trim_end_matches(" - should panic").to_string(), result == "ok"))
+    }).collect();
+    if results.is_empty() && !output.status.success() {
+        // 2.  No tests ran at all: the build failed. Show the first error.
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let error = stderr.lines()
+            .skip_while(|line| !line.starts_with("error"))
+            .take(12)
+            .collect::<Vec<_>>()
+            .join("\n");
+        return Outcome::DidNotCompile(error);
+    }
+    Outcome::Ran(results)
+}
+
+fn main() {
+    rustfun_common::init_logging();
+    let mut solutions = false;
+    let mut wanted = Vec::new();
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "--solutions" => solutions = true,
+            name if CHAPTERS.iter().any(|&(chapter, _)| chapter == name) => wanted.push(arg),
+            _ => {
+                eprintln!("Usage: exercises [--solutions] [CHAPTER ...]");
+                eprintln!("Chapters: {}", CHAPTERS.iter().map(|c| c.0).collect::<Vec<_>>().join(", "));
+                exit(1);
+            }
+        }
+    }
+
+    let (mut passed, mut total) = (0, 0);
+    for &(chapter, about) in CHAPTERS {
+        if !wanted.is_empty() && !wanted.iter().any(|w| w == chapter) {
+            continue;
+        }
+        match run_chapter(chapter, solutions) {
+            Outcome::Ran(results) => {
+                let ok = results.iter().filter(|r| r.1).count();
+                passed += ok;
+                total += results.len();
+                let mark = if ok == results.len() { "done" } else { "" };
+                println!("{:<18} {:>2}/{:<2} {:<5} {}", chapter, ok, results.len(), mark, about);
+                for (name, ok) in results {
+                    if !ok {
+                        println!("    todo  {}", name);
+                    }
+                }
+            }
+            Outcome::DidNotCompile(error) => {
+                println!("{:<18}  doesn't compile", chapter);
+                for line in error.lines() {
+                    println!("    {}", line);
+                }
+            }
+        }
+    }
+    println!();
+    println!("{} of {} exercises pass", passed, total);
+    if passed < total {
+        exit(1);
+    }
+}
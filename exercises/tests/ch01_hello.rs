@@ -0,0 +1,32 @@
+// Locked: these tests are the exercise. Make them pass by changing chapters/, not this file.
+#[cfg_attr(not(feature = "solutions"), path = "../chapters/ch01_hello.rs")]
+#[cfg_attr(feature = "solutions", path = "../solutions/ch01_hello.rs")]
+mod ch01_hello;
+
+use ch01_hello::*;
+
+#[test]
+fn gcd_of_small_numbers() {
+    assert_eq!(gcd(14, 15), 1);
+    assert_eq!(gcd(42, 56), 14);
+    assert_eq!(gcd(7, 7), 7);
+}
+
+#[test]
+fn gcd_either_order() {
+    assert_eq!(gcd(2 * 3 * 5 * 11 * 17, 3 * 7 * 11 * 13 * 19), 3 * 11);
+    assert_eq!(gcd(3 * 7 * 11 * 13 * 19, 2 * 3 * 5 * 11 * 17), 3 * 11);
+}
+
+#[test]
+#[should_panic(expected = "assert")]
+fn gcd_of_zero_panics() {
+    gcd(0, 5);
+}
+
+#[test]
+fn gcd_all_of_a_slice() {
+    assert_eq!(gcd_all(&[42]), 42);
+    assert_eq!(gcd_all(&[12, 18, 30]), 6);
+    assert_eq!(gcd_all(&[7, 11, 13]), 1);
+}
@@ -0,0 +1,46 @@
+// Locked: these tests are the exercise. Make them pass by changing chapters/, not this file.
+#[cfg_attr(not(feature = "solutions"), path = "../chapters/ch03_mandelbrot.rs")]
+#[cfg_attr(feature = "solutions", path = "../solutions/ch03_mandelbrot.rs")]
+mod ch03_mandelbrot;
+
+use ch03_mandelbrot::*;
+
+#[test]
+fn parse_pair_of_numbers() {
+    assert_eq!(parse_pair::<i32>("10,20", ','), Some((10, 20)));
+    assert_eq!(parse_pair::<usize>("1000x750", 'x'), Some((1000, 750)));
+    assert_eq!(parse_pair::<f64>("0.5x1.5", 'x'), Some((0.5, 1.5)));
+}
+
+#[test]
+fn parse_pair_rejects_bad_input() {
+    assert_eq!(parse_pair::<i32>("", ','), None);
+    assert_eq!(parse_pair::<i32>("10,", ','), None);
+    assert_eq!(parse_pair::<i32>(",10", ','), None);
+    assert_eq!(parse_pair::<i32>("10,20xy", ','), None);
+    assert_eq!(parse_pair::<f64>("0.5x", 'x'), None);
+}
+
+#[test]
+fn square_add_multiplies_complex_numbers() {
+    assert_eq!(square_add((0.0, 0.0), (1.0, 2.0)), (1.0, 2.0));
+    assert_eq!(square_add((0.0, 1.0), (0.0, 0.0)), (-1.0, 0.0)); // i * i = -1
+    assert_eq!(square_add((1.0, 1.0), (0.5, 0.0)), (0.5, 2.0));
+}
+
+#[test]
+fn escape_time_inside_and_outside() {
+    assert_eq!(escape_time((0.0, 0.0), 255), None);
+    assert_eq!(escape_time((-1.0, 0.0), 255), None);
+    assert_eq!(escape_time((1.0, 1.0), 255), Some(1));
+    assert_eq!(escape_time((3.0, 0.0), 255), Some(0));
+    assert_eq!(escape_time((0.3, 0.5), 10), None); // it does escape, but takes longer
+}
+
+#[test]
+fn pixel_to_point_maps_the_corners() {
+    let (ul, lr) = ((-1.0, 1.0), (1.0, -1.0));
+    assert_eq!(pixel_to_point((100, 100), (25, 75), ul, lr), (-0.5, -0.5));
+    assert_eq!(pixel_to_point((100, 100), (0, 0), ul, lr), ul);
+    assert_eq!(pixel_to_point((100, 100), (100, 100), ul, lr), lr);
+}
@@ -0,0 +1,44 @@
+// Locked: these tests are the exercise. Make them pass by changing chapters/, not this file.
+#[cfg_attr(not(feature = "solutions"), path = "../chapters/ch04_basic_types.rs")]
+#[cfg_attr(feature = "solutions", path = "../solutions/ch04_basic_types.rs")]
+mod ch04_basic_types;
+
+use ch04_basic_types::*;
+
+#[test]
+fn average_of_small_numbers() {
+    assert_eq!(average(2, 4), 3);
+    assert_eq!(average(3, 4), 3);
+    assert_eq!(average(0, 1), 0);
+}
+
+#[test]
+fn average_without_overflow() {
+    assert_eq!(average(u32::MAX, u32::MAX), u32::MAX);
+    assert_eq!(average(u32::MAX, u32::MAX - 2), u32::MAX - 1);
+    assert_eq!(average(u32::MAX, 1), 1 << 31);
+}
+
+#[test]
+fn checked_sum_detects_overflow() {
+    assert_eq!(checked_sum(&[]), Some(0));
+    assert_eq!(checked_sum(&[1, 2, 3]), Some(6));
+    assert_eq!(checked_sum(&[i32::MAX, -1, 1]), Some(i32::MAX));
+    assert_eq!(checked_sum(&[i32::MAX, 1, -1]), None);
+    assert_eq!(checked_sum(&[i32::MIN, -1]), None);
+}
+
+#[test]
+fn count_vowels_counts_characters() {
+    assert_eq!(count_vowels("hello world"), 3);
+    assert_eq!(count_vowels("AEIOU xyz"), 5);
+    assert_eq!(count_vowels("égalité"), 4);
+    assert_eq!(count_vowels(""), 0);
+}
+
+#[test]
+fn reverse_words_reverses_words() {
+    assert_eq!(reverse_words("liberté égalité fraternité"), "fraternité égalité liberté");
+    assert_eq!(reverse_words("  one   two "), "two one");
+    assert_eq!(reverse_words(""), "");
+}
@@ -0,0 +1,34 @@
+// Locked: these tests are the exercise. Make them pass by changing chapters/, not this file.
+#[cfg_attr(not(feature = "solutions"), path = "../chapters/ch05_ownership.rs")]
+#[cfg_attr(feature = "solutions", path = "../solutions/ch05_ownership.rs")]
+mod ch05_ownership;
+
+use ch05_ownership::*;
+
+#[test]
+fn padovan_sequence() {
+    assert_eq!(padovan(10), vec![1, 1, 1, 2, 2, 3, 4, 5, 7, 9]);
+    assert_eq!(padovan(2), vec![1, 1]);
+    assert_eq!(padovan(0), vec![]);
+}
+
+fn composers() -> Vec<Person> {
+    vec![Person { name: "Palestrina".to_string(), birth: 1525 },
+         Person { name: "Dowland".to_string(), birth: 1563 },
+         Person { name: "Lully".to_string(), birth: 1632 }]
+}
+
+#[test]
+fn names_are_moved_out() {
+    assert_eq!(names_born_before(composers(), 1600), vec!["Palestrina", "Dowland"]);
+    assert_eq!(names_born_before(composers(), 1500), Vec::<String>::new());
+}
+
+#[test]
+fn split_first_gives_back_the_rest() {
+    let v = vec!["liberté".to_string(), "égalité".to_string(), "fraternité".to_string()];
+    let (first, rest) = split_first(v).unwrap();
+    assert_eq!(first, "liberté");
+    assert_eq!(rest, vec!["égalité", "fraternité"]);
+    assert!(split_first(vec![]).is_none());
+}
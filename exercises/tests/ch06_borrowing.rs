@@ -0,0 +1,43 @@
+// Locked: these tests are the exercise. Make them pass by changing chapters/, not this file.
+#[cfg_attr(not(feature = "solutions"), path = "../chapters/ch06_borrowing.rs")]
+#[cfg_attr(feature = "solutions", path = "../solutions/ch06_borrowing.rs")]
+mod ch06_borrowing;
+
+use ch06_borrowing::*;
+
+#[test]
+fn extend_appends_a_slice() {
+    let mut wave = Vec::new();
+    let head = vec![0.0, 1.0];
+    let tail = [0.0, -1.0];
+    extend(&mut wave, &head);
+    extend(&mut wave, &tail);
+    assert_eq!(wave, vec![0.0, 1.0, 0.0, -1.0]);
+    assert_eq!(head, vec![0.0, 1.0]); // only borrowed
+}
+
+#[test]
+fn smallest_borrows_from_the_slice() {
+    let parabola = [9, 4, 1, 0, 1, 4, 9];
+    let s = smallest(&parabola).unwrap();
+    assert_eq!(*s, 0);
+    assert!(std::ptr::eq(s, &parabola[3])); // a reference into it, not a copy
+    assert_eq!(smallest(&[]), None);
+}
+
+#[test]
+fn longest_of_two() {
+    assert_eq!(longest("short", "longer"), "longer");
+    assert_eq!(longest("same", "size"), "same");
+    let owned = String::from("owned and long");
+    assert_eq!(longest(&owned, "x"), "owned and long");
+}
+
+#[test]
+fn double_all_in_place() {
+    let mut v = vec![1, -2, 3];
+    double_all(&mut v);
+    assert_eq!(v, vec![2, -4, 6]);
+    double_all(&mut v[1..]);
+    assert_eq!(v, vec![2, -8, 12]);
+}
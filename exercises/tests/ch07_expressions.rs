@@ -0,0 +1,29 @@
+// Locked: these tests are the exercise. Make them pass by changing chapters/, not this file.
+#[cfg_attr(not(feature = "solutions"), path = "../chapters/ch07_expressions.rs")]
+#[cfg_attr(feature = "solutions", path = "../solutions/ch07_expressions.rs")]
+mod ch07_expressions;
+
+use ch07_expressions::*;
+
+#[test]
+fn describe_error_codes() {
+    assert_eq!(describe(Some(0)), "OK");
+    assert_eq!(describe(Some(1)), "Wires Tangled");
+    assert_eq!(describe(Some(2)), "User Asleep");
+    assert_eq!(describe(Some(7)), "Unrecognized Error 7");
+    assert_eq!(describe(Some(-1)), "Unrecognized Error -1");
+    assert_eq!(describe(None), "None input");
+}
+
+#[test]
+fn fizzbuzz_words() {
+    let words: Vec<String> = (1..16).map(fizzbuzz).collect();
+    assert_eq!(words.join(" "), "1 2 Fizz 4 Buzz Fizz 7 8 Fizz Buzz 11 Fizz 13 14 FizzBuzz");
+}
+
+#[test]
+fn collatz_step_counts() {
+    assert_eq!(collatz_steps(1), 0);
+    assert_eq!(collatz_steps(6), 8);
+    assert_eq!(collatz_steps(27), 111);
+}
@@ -0,0 +1,19 @@
+// 03mandelbrot's parse_complex, which reads the UPPERLEFT and LOWERRIGHT arguments, on
+// arbitrary text. A corpus file holds one argument, like "-1.20,0.35".
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use mandelbrot::parse_complex;
+
+fuzz_target!(|data: &[u8]| {
+    let s = match std::str::from_utf8(data) {
+        Ok(s) => s,
+        Err(_) => return,
+    };
+    if let Some(c) = parse_complex(s) {
+        // f64::from_str accepts "inf" and "NaN"; the point is still one we were given.
+        let (re, im) = s.split_at(s.find(',').unwrap());
+        assert_eq!(c.re.to_bits(), re.parse::<f64>().unwrap().to_bits());
+        assert_eq!(c.im.to_bits(), im[1..].parse::<f64>().unwrap().to_bits());
+    }
+});
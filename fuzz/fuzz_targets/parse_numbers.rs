@@ -0,0 +1,21 @@
+// The gcd programs' argument parsing: arbitrary text, split on whitespace like a shell
+// would, goes through parse_numbers, and whatever it accepts goes to gcd_all. A corpus
+// file holds one command line's arguments, like "42 56".
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rustfun_common::{gcd_all, parse_numbers};
+
+fuzz_target!(|data: &[u8]| {
+    let text = match std::str::from_utf8(data) {
+        Ok(text) => text,
+        Err(_) => return,
+    };
+    let args: Vec<&str> = text.split_whitespace().collect();
+    if let Ok(numbers) = parse_numbers(&args) {
+        // parse_numbers promises gcd_all won't panic on what it returns
+        let d = gcd_all(&numbers);
+        assert!(d >= 1);
+        assert!(numbers.iter().all(|n| n % d == 0));
+    }
+});
@@ -0,0 +1,36 @@
+// parse_pair on arbitrary text, with an arbitrary separator: the first character of the
+// input is the separator, the rest is the string to parse. A corpus file "x1000x750"
+// is the call parse_pair("1000x750", 'x').
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rustfun_common::parse_pair;
+
+fuzz_target!(|data: &[u8]| {
+    let text = match std::str::from_utf8(data) {
+        Ok(text) => text,
+        Err(_) => return,
+    };
+    let mut chars = text.chars();
+    let separator = match chars.next() {
+        Some(c) => c,
+        None => return,
+    };
+    let s = chars.as_str();
+
+    // None of these may panic, whatever the input.
+    if parse_pair::<usize>(s, separator).is_some() || parse_pair::<f64>(s, separator).is_some() {
+        assert!(s.contains(separator));
+    }
+    let _ = parse_pair::<i64>(s, separator);
+
+    // Every string parses as a String, so this checks the split itself: at the first
+    // separator, with nothing lost or added on either side.
+    match parse_pair::<String>(s, separator) {
+        Some((l, r)) => {
+            assert!(!l.contains(separator));
+            assert_eq!(format!("{}{}{}", l, separator, r), s);
+        }
+        None => assert!(!s.contains(separator)),
+    }
+});
@@ -0,0 +1,162 @@
+// The table of chapters: where each one lives, what it's about, and how to run its demo.
+// What's said to the reader comes in English and Chinese; see rustfun_common::lang.
+use rustfun_common::Text;
+
+/// How the menu runs a chapter's binary.
+pub enum Run {
+    /// `cargo run` with these arguments; `{tmp}` stands for the temporary directory.
+    Args(&'static [&'static str]),
+    /// `cargo run`, with this text piped to its standard input.
+    Input(&'static str),
+    /// `cargo run`, reading from the keyboard until the demo says it's done.
+    Interactive(Text),
+    /// A server or a library: the menu shows how to use it instead of running it.
+    Manual(Text),
+}
+
+pub struct Chapter {
+    /// The number in the directory name, which is also what to type in the menu.
+    pub number: u32,
+    pub dir: &'static str,
+    pub package: &'static str,
+    pub about: Text,
+    pub run: Run,
+}
+
+// 1.  A const slice of structs: all of it is built at compile time, and the &'static str
+//     fields point into the binary itself.
+pub const CHAPTERS: &[Chapter] = &[
+    Chapter { number: 1, dir: "01hello", package: "hello",
+              about: Text { en: "the gcd of the command-line arguments",
+                            zh: "命令行参数的最大公约数" },
+              run: Run::Args(&["42", "56"]) },
+    Chapter { number: 2, dir: "02webserver", package: "iron-gcd",
+              about: Text { en: "a web form that computes gcds, on iron or axum",
+                            zh: "计算最大公约数的网页表单, 基于 iron 或 axum" },
+              run: Run::Manual(Text {
+                  en: "a server: `cargo run -p iron-gcd [iron|axum]`, then open http://localhost:3000",
+                  zh: "一个服务器: `cargo run -p iron-gcd [iron|axum]`, 然后打开 http://localhost:3000",
+              }) },
+    Chapter { number: 3, dir: "03mandelbrot", package: "mandelbrot",
+              about: Text { en: "plot the Mandelbrot set to a PNG, on every core",
+                            zh: "用所有核心把 Mandelbrot 集画成 PNG" },
+              run: Run::Args(&["{tmp}/mandel.png", "400x300", "-1.20,0.35", "-1,0.20", "fast"]) },
+    Chapter { number: 4, dir: "04basicbype", package: "basictype",
+              about: Text { en: "integers, floats, chars, tuples, arrays, vectors and strings",
+                            zh: "整数、浮点数、字符、元组、数组、向量和字符串" },
+              run: Run::Args(&[]) },
+    Chapter { number: 5, dir: "05ownership", package: "ownership",
+              about: Text { en: "owners, moves, and what the compiler says about them",
+                            zh: "所有者、移动, 以及编译器对它们的检查" },
+              run: Run::Args(&[]) },
+    Chapter { number: 6, dir: "06borrowing", package: "borrowing",
+              about: Text { en: "shared and mutable references, and lifetimes",
+                            zh: "共享引用、可变引用和生命周期" },
+              run: Run::Args(&[]) },
+    Chapter { number: 7, dir: "07expression", package: "expression",
+              about: Text { en: "if, match and loops as expressions",
+                            zh: "作为表达式的 if、match 和循环" },
+              run: Run::Args(&["1"]) },
+    Chapter { number: 25, dir: "25associated-types", package: "associated-types",
+              about: Text { en: "a Graph trait with associated types, and generic shortest paths",
+                            zh: "带关联类型的 Graph trait, 以及泛型最短路径" },
+              run: Run::Args(&[]) },
+    Chapter { number: 26, dir: "26processes-and-signals", package: "processes-and-signals",
+              about: Text { en: "spawning child processes, pipes, exit codes and signals",
+                            zh: "启动子进程、管道、退出码和信号" },
+              run: Run::Args(&[]) },
+    Chapter { number: 27, dir: "27tcp-chat", package: "tcp-chat",
+              about: Text { en: "a threaded TCP chat server and client",
+                            zh: "多线程的 TCP 聊天服务器和客户端" },
+              run: Run::Manual(Text {
+                  en: "a server: `cargo run -p tcp-chat -- server`, then \
+                       `cargo run -p tcp-chat -- client 127.0.0.1:7878 NAME` in other terminals",
+                  zh: "一个服务器: `cargo run -p tcp-chat -- server`, 然后在其他终端里运行 \
+                       `cargo run -p tcp-chat -- client 127.0.0.1:7878 NAME`",
+              }) },
+    Chapter { number: 28, dir: "28threadpool", package: "threadpool",
+              about: Text { en: "a thread pool, rendering the Mandelbrot set (slow in debug builds)",
+                            zh: "线程池, 用来渲染 Mandelbrot 集 (debug 构建较慢)" },
+              run: Run::Args(&[]) },
+    Chapter { number: 29, dir: "29binary-search-tree", package: "binary-search-tree",
+              about: Text { en: "a binary search tree with Option<Box<Node>>",
+                            zh: "用 Option<Box<Node>> 实现的二叉搜索树" },
+              run: Run::Args(&[]) },
+    Chapter { number: 30, dir: "30linked-list", package: "linked-list",
+              about: Text { en: "doubly linked lists, with Rc/Weak and with raw pointers",
+                            zh: "双向链表, 分别用 Rc/Weak 和裸指针实现" },
+              run: Run::Args(&[]) },
+    Chapter { number: 31, dir: "31channels-from-scratch", package: "channels-from-scratch",
+              about: Text { en: "channels built from a Mutex and a Condvar",
+                            zh: "用 Mutex 和 Condvar 搭建的通道" },
+              run: Run::Args(&[]) },
+    Chapter { number: 32, dir: "32json-parser", package: "json-parser",
+              about: Text { en: "a recursive-descent JSON parser and pretty-printer",
+                            zh: "递归下降的 JSON 解析器和格式化输出" },
+              run: Run::Input(r#"{"name": "rustfun", "chapters": [1, 2, 3], "done": false}"#) },
+    Chapter { number: 33, dir: "33calculator-repl", package: "calculator-repl",
+              about: Text { en: "a calculator REPL with variables and functions",
+                            zh: "支持变量和函数的计算器 REPL" },
+              run: Run::Interactive(Text {
+                  en: "type expressions like `r = 2` and `pi * r ^ 2`; :q to quit",
+                  zh: "输入 `r = 2`、`pi * r ^ 2` 这样的表达式; :q 退出",
+              }) },
+    Chapter { number: 34, dir: "34minigrep", package: "minigrep",
+              about: Text { en: "grep, smaller: search files for a pattern",
+                            zh: "小号的 grep: 在文件中搜索模式" },
+              run: Run::Args(&["--ignore-case", "frog", "tests/fixtures/tree"]) },
+    Chapter { number: 35, dir: "35serde-formats", package: "serde-formats",
+              about: Text { en: "one struct through serde as JSON, YAML, TOML and MessagePack",
+                            zh: "同一个结构体经 serde 转成 JSON、YAML、TOML 和 MessagePack" },
+              run: Run::Args(&[]) },
+    Chapter { number: 36, dir: "36plugin-architecture", package: "plugin-architecture",
+              about: Text { en: "commands as trait objects in a registry",
+                            zh: "注册表里作为 trait 对象的命令" },
+              run: Run::Args(&["tasks.txt"]) },
+    Chapter { number: 37, dir: "37wasm-mandelbrot", package: "wasm-mandelbrot",
+              about: Text { en: "the Mandelbrot renderer compiled to WebAssembly",
+                            zh: "编译成 WebAssembly 的 Mandelbrot 渲染器" },
+              run: Run::Manual(Text {
+                  en: "a library for the browser: see 37wasm-mandelbrot/README.md",
+                  zh: "一个给浏览器用的库: 见 37wasm-mandelbrot/README.md",
+              }) },
+    Chapter { number: 38, dir: "38no_std", package: "no-std",
+              about: Text { en: "a library that works without the standard library",
+                            zh: "不依赖标准库的库" },
+              run: Run::Manual(Text {
+                  en: "a library: `cargo test -p no-std`",
+                  zh: "一个库: `cargo test -p no-std`",
+              }) },
+    Chapter { number: 39, dir: "39panics-and-unwinding", package: "panics-and-unwinding",
+              about: Text { en: "panics, catch_unwind, and poisoned locks",
+                            zh: "panic、catch_unwind 和中毒的锁" },
+              run: Run::Args(&[]) },
+    Chapter { number: 40, dir: "40drop-and-raii", package: "drop-and-raii",
+              about: Text { en: "Drop, guards, and the order values are dropped in",
+                            zh: "Drop、守卫, 以及值被丢弃的顺序" },
+              run: Run::Args(&[]) },
+    Chapter { number: 41, dir: "41cow-and-interning", package: "cow-and-interning",
+              about: Text { en: "Cow<str> and a string interner",
+                            zh: "Cow<str> 和字符串驻留" },
+              run: Run::Args(&[]) },
+    Chapter { number: 42, dir: "42atomics", package: "atomics",
+              about: Text { en: "atomic counters, a spinlock, and a release/acquire one-shot",
+                            zh: "原子计数器、自旋锁和 release/acquire 单次通知" },
+              run: Run::Args(&[]) },
+];
+
+/// The chapter numbered `number`, if there is one.
+pub fn find(number: u32) -> Option<&'static Chapter> {
+    CHAPTERS.iter().find(|c| c.number == number)
+}
+
+#[test]
+fn test_table_matches_the_directories() {
+    let root = concat!(env!("CARGO_MANIFEST_DIR"), "/..");
+    for chapter in CHAPTERS {
+        assert!(chapter.dir.starts_with(&format!("{:02}", chapter.number)), "{}", chapter.dir);
+        let manifest = std::fs::read_to_string(format!("{}/{}/Cargo.toml", root, chapter.dir))
+            .unwrap();
+        assert!(manifest.contains(&format!("name = \"{}\"", chapter.package)), "{}", chapter.dir);
+    }
+}
@@ -0,0 +1,214 @@
+//
+// A menu of the chapters: pick one to run its demo, or step through its source one
+// numbered note at a time.
+//
+//   $ cargo run -p rustfun-menu            the menu
+//   $ cargo run -p rustfun-menu -- 3       run chapter 03 straight away
+//   $ cargo run -p rustfun-menu -- -s 3    step through chapter 03
+//   $ cargo run -p rustfun-menu -- --lang zh    the menu, in Chinese
+//
+// Note:
+//
+// 1.) Demos are started with `cargo run -p PACKAGE` in the chapter's own directory, so
+//     they're built on first use and relative paths in their arguments just work.
+// 2.) Plain stdin, no terminal library: a "keypress" is a line, so stepping waits for
+//     Enter. It also means the menu can be driven from a pipe, which is how it's tested.
+// 3.) The menu's language goes to the demos it runs in RUSTFUN_LANG, so their usage and
+//     errors come out in it too. The notes it steps through are the source's own, in
+//     English either way. --deterministic goes to them the same way, in
+//     RUSTFUN_DETERMINISTIC.
+//
+use std::env;
+use std::fs;
+use std::io;
+use std::io::{BufRead, Write};
+use std::process::{Command, Stdio};
+
+mod chapters;
+mod sections;
+
+use crate::chapters::{Chapter, Run, CHAPTERS};
+use rustfun_common::{deterministic, lang, tr};
+
+const ROOT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/..");
+
+fn print_menu() {
+    println!("{}", tr!("rustfun chapters:", "rustfun 章节:"));
+    println!();
+    for chapter in CHAPTERS {
+        println!("  {:>2}  {:<28} {}", chapter.number, chapter.dir, chapter.about);
+    }
+    println!();
+    println!("{}", tr!("Type a number to run that chapter's demo, s and a number (s 3) to step through
+its source, l to list the chapters again, or q to quit.",
+                       "输入数字运行该章的演示, 输入 s 加数字 (s 3) 逐节阅读它的源码,
+l 重新列出章节, q 退出。"));
+}
+
+// 1.  What a line typed at the menu asks for.
+#[derive(Debug, PartialEq)]
+enum Choice {
+    Run(u32),
+    Step(u32),
+    List,
+    Quit,
+    Unknown(String),
+}
+
+fn parse_choice(line: &str) -> Choice {
+    let line = line.trim();
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let number = |s: &str| s.parse::<u32>().ok().filter(|&n| chapters::find(n).is_some());
+    match words[..] {
+        [] | ["l"] => Choice::List,
+        ["q"] => Choice::Quit,
+        [n] => number(n).map_or(Choice::Unknown(line.to_string()), Choice::Run),
+        ["s", n] | ["-s", n] => number(n).map_or(Choice::Unknown(line.to_string()), Choice::Step),
+        _ => Choice::Unknown(line.to_string()),
+    }
+}
+
+#[test]
+fn test_parse_choice() {
+    assert_eq!(parse_choice("3\n"), Choice::Run(3));
+    assert_eq!(parse_choice(" 03 "), Choice::Run(3));
+    assert_eq!(parse_choice("s 42"), Choice::Step(42));
+    assert_eq!(parse_choice(""), Choice::List);
+    assert_eq!(parse_choice("q"), Choice::Quit);
+    assert_eq!(parse_choice("8"), Choice::Unknown("8".to_string()));
+    assert_eq!(parse_choice("s"), Choice::Unknown("s".to_string()));
+}
+
+fn run(chapter: &Chapter) -> io::Result<()> {
+    let mut command = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()));
+    command.args(["run", "-q", "-p", chapter.package, "--"])
+        .current_dir(format!("{}/{}", ROOT, chapter.dir))
+        .env("RUSTFUN_LANG", lang::current().code());
+    if deterministic::is_on() {
+        command.env("RUSTFUN_DETERMINISTIC", "1");
+    }
+    log::debug!("in {}: {:?}", chapter.dir, command);
+
+    let status = match chapter.run {
+        Run::Manual(how) => {
+            println!("{}: {}", chapter.dir, how);
+            return Ok(());
+        }
+        Run::Args(args) => {
+            let tmp = env::temp_dir();
+            command.args(args.iter().map(|a| a.replace("{tmp}", &tmp.to_string_lossy())));
+            println!("--- cargo run -p {} -- {}", chapter.package, args.join(" "));
+            command.status()?
+        }
+        Run::Input(text) => {
+            println!("--- echo '{}' | cargo run -p {}", text, chapter.package);
+            let mut child = command.stdin(Stdio::piped()).spawn()?;
+            // 2.  Taking stdin out of the child and dropping it closes the pipe, so the
+            //     demo sees end of input after our text.
+            child.stdin.take().unwrap().write_all(text.as_bytes())?;
+            child.wait()?
+        }
+        Run::Interactive(hint) => {
+            println!("--- cargo run -p {}   ({})", chapter.package, hint);
+            command.status()?
+        }
+    };
+    println!("{}", tr!("--- {} exited with {}", "--- {} 结束, {}", chapter.dir, status));
+    Ok(())
+}
+
+// 3.  Source files to step through: main.rs and then lib.rs, whichever exist.
+fn step<B: BufRead>(chapter: &Chapter, input: &mut io::Lines<B>) -> io::Result<()> {
+    let mut parts = Vec::new();
+    for file in &["src/main.rs", "src/lib.rs"] {
+        if let Ok(source) = fs::read_to_string(format!("{}/{}/{}", ROOT, chapter.dir, file)) {
+            for section in sections::sections(&source) {
+                parts.push((file, section));
+            }
+        }
+    }
+    let total = parts.len();
+    for (i, (file, section)) in parts.into_iter().enumerate() {
+        println!("--- {}/{} [{}/{}]", chapter.dir, file, i + 1, total);
+        println!("{}", section);
+        if i + 1 == total {
+            break;
+        }
+        print!("{}", tr!("--- Enter for the next section, q to stop: ", "--- 回车看下一节, q 停止: "));
+        io::stdout().flush()?;
+        match input.next() {
+            Some(Ok(ref line)) if line.trim() != "q" => {}
+            _ => break,
+        }
+    }
+    println!("{}", tr!("--- end of {}", "--- {} 到此为止", chapter.dir));
+    Ok(())
+}
+
+// Carry out one choice; false once it's time to quit.
+fn perform<B: BufRead>(choice: Choice, input: &mut io::Lines<B>) -> bool {
+    let result = match choice {
+        Choice::Run(n) => run(chapters::find(n).unwrap()),
+        Choice::Step(n) => step(chapters::find(n).unwrap(), input),
+        Choice::List => {
+            print_menu();
+            Ok(())
+        }
+        Choice::Quit => return false,
+        Choice::Unknown(line) => {
+            println!("{}", tr!("no such chapter or command: {:?}", "没有这个章节或命令: {:?}", line));
+            Ok(())
+        }
+    };
+    if let Err(e) = result {
+        eprintln!("rustfun: {}", e);
+    }
+    true
+}
+
+fn main() {
+    rustfun_common::init_logging();
+    let stdin = io::stdin();
+    let mut input = stdin.lock().lines();
+
+    // 4.  Arguments are read like a menu line, to run or step through one chapter
+    //     without the menu, once any --lang and --deterministic have been taken out.
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    if deterministic::take_flag(&mut args) {
+        deterministic::set(true);
+    }
+    match lang::take_flag(&mut args) {
+        Ok(Some(language)) => lang::set(language),
+        Ok(None) => {}
+        Err(message) => {
+            eprintln!("rustfun: {}", message);
+            std::process::exit(1);
+        }
+    }
+    if !args.is_empty() {
+        match parse_choice(&args.join(" ")) {
+            choice @ Choice::Run(_) | choice @ Choice::Step(_) => {
+                perform(choice, &mut input);
+            }
+            _ => {
+                eprintln!("{}", tr!("Usage: rustfun [--lang LANG] [--deterministic] [[-s] CHAPTER]",
+                                    "用法: rustfun [--lang LANG] [--deterministic] [[-s] CHAPTER]"));
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    print_menu();
+    loop {
+        print!("{}", tr!("\nchapter> ", "\n章节> "));
+        io::stdout().flush().unwrap();
+        let choice = match input.next() {
+            Some(Ok(line)) => parse_choice(&line),
+            _ => Choice::Quit,
+        };
+        if !perform(choice, &mut input) {
+            break;
+        }
+    }
+}
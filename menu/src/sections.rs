@@ -0,0 +1,76 @@
+// Split a chapter's source into the sections its numbered notes mark out, for stepping
+// through it one at a time.
+//
+// A section starts at a comment whose first word is a whole number and a dot, like
+// `// 3.` or `// 12.)`, and runs to the next one. `// 3.1` is a sub-note and stays in its
+// section. Whatever comes before the first note is a section of its own.
+
+fn starts_section(line: &str) -> bool {
+    let comment = match line.trim_start().strip_prefix("//") {
+        Some(rest) if !rest.starts_with('/') => rest.trim_start(),
+        _ => return false,
+    };
+    let digits = comment.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits == 0 {
+        return false;
+    }
+    let mut after = comment[digits..].chars();
+    after.next() == Some('.') && match after.next() {
+        None => true,
+        Some(c) => c == ' ' || c == ')',
+    }
+}
+
+/// The sections of `source`, each with its trailing blank lines dropped.
+pub fn sections(source: &str) -> Vec<String> {
+    let mut sections = Vec::new();
+    let mut current = String::new();
+    for line in source.lines() {
+        if starts_section(line) && !current.trim().is_empty() {
+            sections.push(current.trim_end().to_string());
+            current.clear();
+        }
+        current.push_str(line);
+        current.push('\n');
+    }
+    if !current.trim().is_empty() {
+        sections.push(current.trim_end().to_string());
+    }
+    sections
+}
+
+#[test]
+fn test_starts_section() {
+    assert!(starts_section("// 1. The fn keyword"));
+    assert!(starts_section("    // 12.  indented"));
+    assert!(starts_section("//3. no space"));
+    assert!(starts_section("// 1.) header note"));
+    assert!(starts_section("// 4."));
+    assert!(!starts_section("// 1.1 a sub-note"));
+    assert!(!starts_section("// 2018 was a year"));
+    assert!(!starts_section("/// 1. a doc comment"));
+    assert!(!starts_section("let x = 1; // 1. trailing"));
+    assert!(!starts_section("// Note:"));
+}
+
+#[test]
+fn test_sections() {
+    let source = "\
+// intro
+use std::io;
+
+// 1. first
+fn a() {}
+// 1.1 still first
+
+// 2.) second
+fn b() {}
+";
+    assert_eq!(sections(source), vec![
+        "// intro\nuse std::io;",
+        "// 1. first\nfn a() {}\n// 1.1 still first",
+        "// 2.) second\nfn b() {}",
+    ]);
+    assert_eq!(sections("// 1. only\n"), vec!["// 1. only"]);
+    assert_eq!(sections(""), Vec::<String>::new());
+}
@@ -0,0 +1,80 @@
+// The menu driven through a pipe. Running a demo means building it with cargo, so these
+// stick to the parts that don't: listing, stepping through source, and the manual entries.
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+fn rustfun() -> Command {
+    Command::cargo_bin("rustfun").unwrap()
+}
+
+#[test]
+fn lists_the_chapters_and_quits() {
+    rustfun().write_stdin("q\n").assert()
+        .success()
+        .stdout(predicate::str::starts_with("rustfun chapters:\n")
+            .and(predicate::str::contains("   3  03mandelbrot"))
+            .and(predicate::str::contains("  42  42atomics"))
+            .and(predicate::str::ends_with("\nchapter> ")));
+}
+
+#[test]
+fn end_of_input_quits_too() {
+    rustfun().write_stdin("").assert().success();
+}
+
+#[test]
+fn steps_through_the_sections() {
+    // two sections, then q back to the menu, then q to quit
+    rustfun().write_stdin("s 1\n\nq\nq\n").assert()
+        .success()
+        .stdout(predicate::str::contains("--- 01hello/src/main.rs [1/")
+            .and(predicate::str::contains("--- 01hello/src/main.rs [2/"))
+            .and(predicate::str::contains("--- 01hello/src/main.rs [3/").not())
+            .and(predicate::str::contains("--- end of 01hello\n")));
+}
+
+#[test]
+fn steps_from_the_command_line() {
+    // -s with a chapter skips the menu; the last section needs no Enter
+    let enters = "\n".repeat(100);
+    rustfun().args(["-s", "40"]).write_stdin(enters).assert()
+        .success()
+        .stdout(predicate::str::starts_with("--- 40drop-and-raii/src/main.rs [1/")
+            .and(predicate::str::contains("40drop-and-raii/src/lib.rs"))
+            .and(predicate::str::ends_with("--- end of 40drop-and-raii\n")));
+}
+
+#[test]
+fn servers_are_explained_not_run() {
+    rustfun().write_stdin("2\n27\nq\n").assert()
+        .success()
+        .stdout(predicate::str::contains("02webserver: a server: `cargo run -p iron-gcd [iron|axum]`")
+            .and(predicate::str::contains("27tcp-chat: a server:")));
+}
+
+#[test]
+fn unknown_choices() {
+    rustfun().write_stdin("8\nhello\nq\n").assert()
+        .success()
+        .stdout(predicate::str::contains("no such chapter or command: \"8\"")
+            .and(predicate::str::contains("no such chapter or command: \"hello\"")));
+    rustfun().arg("hello").assert()
+        .code(1)
+        .stderr("Usage: rustfun [--lang LANG] [--deterministic] [[-s] CHAPTER]\n");
+}
+
+#[test]
+fn speaks_chinese_with_lang_zh() {
+    rustfun().args(["--lang", "zh"]).write_stdin("8\nq\n").assert()
+        .success()
+        .stdout(predicate::str::starts_with("rustfun 章节:\n")
+            .and(predicate::str::contains("   1  01hello                      命令行参数的最大公约数"))
+            .and(predicate::str::contains("没有这个章节或命令: \"8\""))
+            .and(predicate::str::ends_with("\n章节> ")));
+    rustfun().env("RUSTFUN_LANG", "zh_CN.UTF-8").arg("-s").assert()
+        .code(1)
+        .stderr("用法: rustfun [--lang LANG] [--deterministic] [[-s] CHAPTER]\n");
+    rustfun().args(["--lang", "fr"]).assert()
+        .code(1)
+        .stderr("rustfun: unknown language \"fr\"; try en or zh\n");
+}
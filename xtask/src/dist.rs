@@ -0,0 +1,161 @@
+// `cargo xtask dist [TARGET]`: every chapter's binary, built for release and linked
+// statically, in target/dist/TARGET/, ready to copy to a machine without Rust.
+//
+//   $ rustup target add x86_64-unknown-linux-musl
+//   $ cargo xtask dist
+//   $ cargo xtask dist aarch64-unknown-linux-musl
+//
+// TARGET is x86_64-unknown-linux-musl unless given. musl is linked statically anyway;
+// the task asks for it with `-C target-feature=+crt-static` all the same, which also
+// makes a static binary of a glibc target, like x86_64-unknown-linux-gnu. Building for
+// another architecture needs a linker for it; Cargo takes one from the environment:
+//
+//   $ CARGO_TARGET_AARCH64_UNKNOWN_LINUX_MUSL_LINKER=aarch64-linux-gnu-gcc \
+//         cargo xtask dist aarch64-unknown-linux-musl
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use rustfun_common::Args;
+use rustfun_error::{Error, Result};
+
+use crate::verify::cargo_with;
+
+const DEFAULT_TARGET: &str = "x86_64-unknown-linux-musl";
+
+pub fn run(root: &Path, mut args: Args) -> Result<()> {
+    let target = args.optional().unwrap_or_else(|| DEFAULT_TARGET.to_string());
+    args.finish().unwrap_or_else(|e| e.exit());
+
+    let packages = chapters(root)?;
+    let mut build = vec!["build", "--release", "--target", &target];
+    for package in &packages {
+        build.extend(["-p", package.as_str()]);
+    }
+    // 1.  RUSTFLAGS set here replaces any from the environment or .cargo/config.toml.
+    //     With --target, Cargo leaves build scripts and proc macros out of it: they run
+    //     on the host, and can't be static.
+    cargo_with(root, &[("RUSTFLAGS", "-C target-feature=+crt-static")], &build)?;
+
+    let dist = root.join("target/dist").join(&target);
+    fs::create_dir_all(&dist).map_err(|e| Error::io_at(&dist, e))?;
+    let release = root.join("target").join(&target).join("release");
+    for package in &packages {
+        let binary = release.join(executable(package, &target));
+        let bytes = fs::read(&binary).map_err(|e| Error::io_at(&binary, e))?;
+        if is_dynamic(&bytes) == Some(true) {
+            return Err(Error::Check(format!("{} is linked dynamically", binary.display())));
+        }
+        let copy = dist.join(executable(package, &target));
+        fs::write(&copy, &bytes).map_err(|e| Error::io_at(&copy, e))?;
+        copy_permissions(&binary, &copy)?;
+    }
+    println!("--- {} static binaries in {}", packages.len(), dist.display());
+    Ok(())
+}
+
+/// The package of every chapter with a binary: a directory named NNsomething, with a
+/// src/main.rs, in number order. The libraries, like 37wasm-mandelbrot, have none.
+fn chapters(root: &Path) -> Result<Vec<String>> {
+    let mut dirs: Vec<PathBuf> = fs::read_dir(root).map_err(|e| Error::io_at(root, e))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|dir| {
+            let name = dir.file_name().unwrap_or_default().to_string_lossy();
+            name.starts_with(|c: char| c.is_ascii_digit()) && dir.join("src/main.rs").exists()
+        })
+        .collect();
+    dirs.sort();
+    dirs.iter()
+        .map(|dir| {
+            let manifest = dir.join("Cargo.toml");
+            let text = fs::read_to_string(&manifest).map_err(|e| Error::io_at(&manifest, e))?;
+            package_name(&text)
+                .ok_or_else(|| Error::parse(format!("{}: no package name", manifest.display())))
+        })
+        .collect()
+}
+
+/// The `name` of the `[package]` section: the first one in the manifest.
+fn package_name(manifest: &str) -> Option<String> {
+    manifest.lines()
+        .find_map(|line| line.strip_prefix("name = \"")?.strip_suffix('"'))
+        .map(str::to_string)
+}
+
+#[test]
+fn test_package_name() {
+    assert_eq!(package_name("[package]\nname = \"iron-gcd\"\nversion = \"0.1.0\"\n"),
+               Some("iron-gcd".to_string()));
+    assert_eq!(package_name("[workspace]\n"), None);
+}
+
+// 2.  The binary keeps the package's hyphens; only Windows adds an extension.
+fn executable(package: &str, target: &str) -> String {
+    if target.contains("windows") {
+        format!("{}.exe", package)
+    } else {
+        package.to_string()
+    }
+}
+
+fn copy_permissions(from: &Path, to: &Path) -> Result<()> {
+    let permissions = fs::metadata(from).map_err(|e| Error::io_at(from, e))?.permissions();
+    fs::set_permissions(to, permissions).map_err(|e| Error::io_at(to, e))
+}
+
+// 3.  A dynamically linked ELF executable names the loader that links it at run time in
+//     a PT_INTERP program header; a static one has none. The headers' offset, size and
+//     count are at fixed places in the ELF header. Only 64-bit little-endian ELF, which
+//     covers the x86_64 and aarch64 targets, is read: None for anything else.
+fn is_dynamic(bytes: &[u8]) -> Option<bool> {
+    const PT_INTERP: u32 = 3;
+    if !bytes.starts_with(b"\x7fELF\x02\x01") {
+        return None;
+    }
+    let u16_at = |at: usize| Some(u16::from_le_bytes(bytes.get(at..at + 2)?.try_into().ok()?));
+    let u32_at = |at: usize| Some(u32::from_le_bytes(bytes.get(at..at + 4)?.try_into().ok()?));
+    let u64_at = |at: usize| Some(u64::from_le_bytes(bytes.get(at..at + 8)?.try_into().ok()?));
+    let offset = usize::try_from(u64_at(0x20)?).ok()?;
+    let size = u16_at(0x36)? as usize;
+    let count = u16_at(0x38)? as usize;
+    for i in 0..count {
+        if u32_at(offset.checked_add(i * size)?)? == PT_INTERP {
+            return Some(true);
+        }
+    }
+    Some(false)
+}
+
+#[test]
+fn test_is_dynamic() {
+    // An ELF header with two program headers of 56 bytes right after it.
+    let elf = |types: [u32; 2]| {
+        let mut bytes = vec![0; 64 + 2 * 56];
+        bytes[..6].copy_from_slice(b"\x7fELF\x02\x01");
+        bytes[0x20] = 64;
+        bytes[0x36] = 56;
+        bytes[0x38] = 2;
+        for (i, t) in types.iter().enumerate() {
+            bytes[64 + i * 56..][..4].copy_from_slice(&t.to_le_bytes());
+        }
+        bytes
+    };
+    assert_eq!(is_dynamic(&elf([6, 3])), Some(true));
+    assert_eq!(is_dynamic(&elf([6, 1])), Some(false));
+    assert_eq!(is_dynamic(b"MZ"), None);
+    assert_eq!(is_dynamic(&elf([6, 3])[..100]), None);
+}
+
+#[test]
+fn test_chapters() {
+    let root = tempfile::tempdir().unwrap();
+    for (dir, name, main) in [("03mandelbrot", "mandelbrot", true), ("01hello", "hello", true),
+                              ("38no_std", "no-std", false), ("common", "rustfun-common", false)] {
+        fs::create_dir_all(root.path().join(dir).join("src")).unwrap();
+        fs::write(root.path().join(dir).join("Cargo.toml"),
+                  format!("[package]\nname = \"{}\"\n", name)).unwrap();
+        let file = if main { "src/main.rs" } else { "src/lib.rs" };
+        fs::write(root.path().join(dir).join(file), "").unwrap();
+    }
+    assert_eq!(chapters(root.path()).unwrap(), vec!["hello", "mandelbrot"]);
+}
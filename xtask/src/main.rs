@@ -0,0 +1,62 @@
+//
+// The repository's own tooling, as a Cargo command: `cargo xtask COMMAND ...`.
+//
+//   $ cargo xtask new-chapter 43 pattern-matching
+//   $ cargo xtask verify
+//
+// Note:
+//
+// 1.) The "xtask" pattern: a plain binary crate in the workspace, and an alias in
+//     .cargo/config.toml that runs it. Nothing to install, and the tasks are Rust code
+//     that is built, linted and tested with the rest.
+// 2.) Every path is relative to the workspace root, found from this crate's own
+//     directory, so a task works from wherever cargo is run.
+//
+use std::path::PathBuf;
+
+use rustfun_common::Args;
+use rustfun_error::Result;
+
+mod dist;
+mod new_chapter;
+mod verify;
+mod wasm;
+
+const USAGE: &str = "xtask COMMAND ...
+Commands:
+  new-chapter NN NAME    a new chapter crate, NNNAME/, in the workspace
+  build                  build every crate in the workspace
+  test                   run every crate's tests
+  render [FILE]          render the reference Mandelbrot image, by default to
+                         target/xtask/mandel.png
+  verify [--bless]       build, test, render, and compare the image with the golden
+                         one in xtask/golden/; --bless replaces the golden image
+  dist [TARGET]          every chapter's binary, static, for TARGET (by default
+                         x86_64-unknown-linux-musl), in target/dist/TARGET/
+  wasm                   build the crates meant for the browser for wasm32, and check
+                         the exports of 37wasm-mandelbrot's module";
+
+/// The workspace root, one level above this crate.
+fn root() -> PathBuf {
+    let root = PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/.."));
+    root.canonicalize().unwrap_or(root)
+}
+
+fn main() {
+    rustfun_common::init_logging();
+    let mut args = Args::from_env(USAGE);
+    let command = args.required("COMMAND").unwrap_or_else(|e| e.exit());
+    let result: Result<()> = match command.as_str() {
+        "new-chapter" => new_chapter::run(&root(), args),
+        "build" => verify::build(&root(), args),
+        "test" => verify::test(&root(), args),
+        "render" => verify::render(&root(), args),
+        "verify" => verify::verify(&root(), args),
+        "dist" => dist::run(&root(), args),
+        "wasm" => wasm::run(&root(), args),
+        other => args.error(format!("unknown command {:?}", other)).exit(),
+    };
+    if let Err(e) = result {
+        e.exit("xtask");
+    }
+}
@@ -0,0 +1,249 @@
+// `cargo xtask new-chapter NN NAME`: a new chapter crate laid out like the others, and
+// listed in the workspace.
+//
+//   NNNAME/Cargo.toml       package NAME, with rustfun-common for logging and --explain
+//   NNNAME/README.md        how to run and test it
+//   NNNAME/src/main.rs      header and notes, main, and a first function with its test
+//   NNNAME/src/explain.rs   what --explain prints, to be filled in
+//   NNNAME/tests/cli.rs     the binary run as a user would
+//
+// The menu's table (menu/src/chapters.rs) says what each chapter is about and how to
+// run its demo; that's for the author to write, so the task only reminds them.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use rustfun_common::Args;
+use rustfun_error::{Error, Result};
+
+pub fn run(root: &Path, mut args: Args) -> Result<()> {
+    let number = args.parse_with("NN", |s| s.parse().ok().filter(|n| (1..100).contains(n)))
+        .unwrap_or_else(|e| e.exit());
+    let name = args.required("NAME").unwrap_or_else(|e| e.exit());
+    if let Err(message) = check(root, number, &name) {
+        args.error(message).exit();
+    }
+    args.finish().unwrap_or_else(|e| e.exit());
+
+    create(root, number, &name)?;
+    let dir_name = dir_name(number, &name);
+    println!("created {}/, and added it to the workspace", dir_name);
+    println!("next: describe it in {}/src/explain.rs, and add it to menu/src/chapters.rs",
+             dir_name);
+    Ok(())
+}
+
+fn dir_name(number: u32, name: &str) -> String {
+    format!("{:02}{}", number, name)
+}
+
+// 1.  A directory like `38no_std` keeps its underscore; the package name, `no-std`,
+//     takes a hyphen, as Cargo package names usually do.
+fn package_name(name: &str) -> String {
+    name.replace('_', "-")
+}
+
+/// Why NAME or NN can't be used, if they can't: the name must be lowercase words, and
+/// the number must not belong to a chapter already.
+fn check(root: &Path, number: u32, name: &str) -> std::result::Result<(), String> {
+    let valid = name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+    if !valid {
+        return Err(format!("NAME: use lowercase letters, digits, '-' and '_', got {:?}", name));
+    }
+    let prefix = format!("{:02}", number);
+    let entries = fs::read_dir(root).map_err(|e| e.to_string())?;
+    for entry in entries.flatten() {
+        let existing = entry.file_name().to_string_lossy().into_owned();
+        if existing.starts_with(&prefix) && entry.path().join("Cargo.toml").exists() {
+            return Err(format!("NN: chapter {} is already {}", prefix, existing));
+        }
+    }
+    Ok(())
+}
+
+/// Write the new chapter under `root` and add it to the workspace. Return its directory.
+fn create(root: &Path, number: u32, name: &str) -> Result<PathBuf> {
+    let dir_name = dir_name(number, name);
+    let dir = root.join(&dir_name);
+    let package = package_name(name);
+    let title = name.replace(['-', '_'], " ");
+
+    let files = [
+        ("Cargo.toml", cargo_toml(&package)),
+        ("README.md", README.to_string()),
+        ("src/main.rs", main_rs(&title)),
+        ("src/explain.rs", explain_rs(&dir_name, &package, &title)),
+        ("tests/cli.rs", cli_rs(&dir_name, &package, &title)),
+    ];
+    for (file, contents) in &files {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().unwrap()).map_err(|e| Error::io_at(&path, e))?;
+        fs::write(&path, contents).map_err(|e| Error::io_at(&path, e))?;
+    }
+
+    let manifest = root.join("Cargo.toml");
+    let workspace = fs::read_to_string(&manifest).map_err(|e| Error::io_at(&manifest, e))?;
+    let workspace = add_member(&workspace, &dir_name)
+        .ok_or_else(|| Error::parse(format!("{}: no workspace members list", manifest.display())))?;
+    fs::write(&manifest, workspace).map_err(|e| Error::io_at(&manifest, e))?;
+    Ok(dir)
+}
+
+// 2.  The members list is edited as text, not parsed and written back as TOML, so its
+//     comments and layout survive. Chapters are listed in number order after the
+//     helper crates, and the new one goes before the first chapter numbered above it.
+fn add_member(manifest: &str, dir_name: &str) -> Option<String> {
+    // From the first member's line to the closing bracket's, which is left out.
+    let start = manifest.find("members = [")?;
+    let start = start + manifest[start..].find('\n')? + 1;
+    let end = start + manifest[start..].find("]")?;
+    let is_chapter = |entry: &str| entry.starts_with(|c: char| c.is_ascii_digit());
+
+    let mut lines: Vec<&str> = manifest[start..end].lines().collect();
+    let entry = format!("    \"{}\",", dir_name);
+    let at = lines.iter()
+        .position(|line| {
+            let member = line.trim().trim_matches(|c| c == '"' || c == ',');
+            is_chapter(member) && member > dir_name
+        })
+        .unwrap_or(lines.len());
+    lines.insert(at, &entry);
+    Some(format!("{}{}\n{}", &manifest[..start], lines.join("\n"), &manifest[end..]))
+}
+
+#[test]
+fn test_add_member() {
+    let manifest = "[workspace]\nmembers = [\n    \"common\",\n    \"01hello\",\n    \"42atomics\",\n]\nexclude = []\n";
+    assert_eq!(add_member(manifest, "07expression").unwrap(),
+               "[workspace]\nmembers = [\n    \"common\",\n    \"01hello\",\n    \"07expression\",\n    \"42atomics\",\n]\nexclude = []\n");
+    assert_eq!(add_member(manifest, "43patterns").unwrap(),
+               "[workspace]\nmembers = [\n    \"common\",\n    \"01hello\",\n    \"42atomics\",\n    \"43patterns\",\n]\nexclude = []\n");
+    assert_eq!(add_member("[package]\n", "43patterns"), None);
+}
+
+fn cargo_toml(package: &str) -> String {
+    format!(r#"[package]
+name = "{package}"
+version = "0.1.0"
+edition = "2021"
+authors = ["Alex Wu <dindinw@users.noreply.github.com>"]
+
+[dependencies]
+rustfun-common = {{ path = "../common", features = ["logging"] }}
+
+[dev-dependencies]
+assert_cmd = "2"
+predicates = "3"
+"#)
+}
+
+const README: &str = "### Usage
+
+```
+$ cargo run
+$ cargo run -- --explain
+```
+
+### Test
+
+```
+$ cargo test
+```
+";
+
+fn main_rs(title: &str) -> String {
+    format!(r#"//
+// {title}: what this chapter shows, in a line or two.
+//
+// Note:
+//
+// 1.) The big ideas, one per note.
+//
+mod explain;
+
+fn main() {{
+    rustfun_common::init_logging();
+    explain::EXPLANATION.on_request();
+
+    println!("{{}}", greeting());
+}}
+
+// 1.  The chapter's code goes here, a numbered note for each step, and each function's
+//     test right after it.
+fn greeting() -> &'static str {{
+    "Hello, {title}!"
+}}
+
+#[test]
+fn test_greeting() {{
+    assert_eq!(greeting(), "Hello, {title}!");
+}}
+"#)
+}
+
+fn explain_rs(dir_name: &str, package: &str, title: &str) -> String {
+    format!(r#"// What `{package} --explain` prints: the chapter's notes, in short.
+use rustfun_common::{{Explanation, Text, Topic}};
+
+pub const EXPLANATION: Explanation = Explanation {{
+    chapter: "{dir_name}",
+    summary: Text {{
+        en: "{title}",
+        zh: "{title}",
+    }},
+    topics: &[
+        Topic {{
+            title: Text {{ en: "To do", zh: "待写" }},
+            body: Text {{
+                en: "One topic for each of the chapter's big ideas.",
+                zh: "本章的每个要点写成一个主题。",
+            }},
+        }},
+    ],
+}};
+"#)
+}
+
+fn cli_rs(dir_name: &str, package: &str, title: &str) -> String {
+    format!(r#"// Run the chapter's demo binary and check what it prints.
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+#[test]
+fn prints_the_demo() {{
+    Command::cargo_bin("{package}").unwrap().assert()
+        .success()
+        .stdout("Hello, {title}!\n");
+}}
+
+#[test]
+fn explains_itself() {{
+    Command::cargo_bin("{package}").unwrap().arg("--explain").assert()
+        .success()
+        .stdout(predicate::str::starts_with("{dir_name}: "));
+}}
+"#)
+}
+
+#[test]
+fn test_create() {
+    let root = tempfile::tempdir().unwrap();
+    fs::write(root.path().join("Cargo.toml"), "[workspace]\nmembers = [\n    \"01hello\",\n]\n").unwrap();
+    fs::create_dir(root.path().join("01hello")).unwrap();
+    fs::write(root.path().join("01hello/Cargo.toml"), "").unwrap();
+
+    assert_eq!(check(root.path(), 1, "again"), Err("NN: chapter 01 is already 01hello".to_string()));
+    assert!(check(root.path(), 43, "Pattern Matching").is_err());
+    assert_eq!(check(root.path(), 43, "pattern_matching"), Ok(()));
+
+    let dir = create(root.path(), 43, "pattern_matching").unwrap();
+    assert_eq!(dir, root.path().join("43pattern_matching"));
+    let read = |file: &str| fs::read_to_string(dir.join(file)).unwrap();
+    assert!(read("Cargo.toml").contains("name = \"pattern-matching\""));
+    assert!(read("src/main.rs").contains("\"Hello, pattern matching!\""));
+    assert!(read("src/explain.rs").contains("chapter: \"43pattern_matching\","));
+    assert!(read("tests/cli.rs").contains("Command::cargo_bin(\"pattern-matching\")"));
+    assert!(dir.join("README.md").exists());
+    assert_eq!(fs::read_to_string(root.path().join("Cargo.toml")).unwrap(),
+               "[workspace]\nmembers = [\n    \"01hello\",\n    \"43pattern_matching\",\n]\n");
+}
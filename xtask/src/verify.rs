@@ -0,0 +1,185 @@
+// The verification pipeline: build everything, test everything, then render the
+// reference Mandelbrot image and compare it with the golden copy in xtask/golden/.
+//
+//   $ cargo xtask build
+//   $ cargo xtask test
+//   $ cargo xtask render [FILE]
+//   $ cargo xtask verify [--bless]
+//
+// The tests check what each chapter says it does; the golden image checks that the
+// mandelbrot binary still draws exactly what it drew before, pixel for pixel. A change
+// that's meant to alter the picture updates the golden copy with `verify --bless`, and
+// the new PNG goes into the same commit, where a reviewer can look at it.
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use image::{ImageBuffer, Luma, Rgba};
+use rustfun_common::Args;
+use rustfun_error::{Error, Result};
+
+/// The reference image: the view the menu's demo renders, at the same size.
+const REFERENCE: [&str; 4] = ["400x300", "-1.20,0.35", "-1,0.20", "fast"];
+
+fn golden(root: &Path) -> PathBuf {
+    root.join("xtask/golden/mandel.png")
+}
+
+// Rendered images and diffs go under target/, with everything else that's generated.
+fn output(root: &Path, file: &str) -> PathBuf {
+    root.join("target/xtask").join(file)
+}
+
+// 1.  Cargo runs us with CARGO set to the cargo that did, so the steps use the same
+//     toolchain, whatever `cargo` on the PATH would be.
+pub fn cargo(root: &Path, args: &[&str]) -> Result<()> {
+    cargo_with(root, &[], args)
+}
+
+/// Run cargo with variables added to its environment, like `RUSTFLAGS`.
+pub fn cargo_with(root: &Path, vars: &[(&str, &str)], args: &[&str]) -> Result<()> {
+    let shown: Vec<String> = vars.iter().map(|(k, v)| format!("{}={:?} ", k, v)).collect();
+    println!("--- {}cargo {}", shown.concat(), args.join(" "));
+    let status = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()))
+        .args(args)
+        .envs(vars.iter().copied())
+        .current_dir(root)
+        .status()?;
+    if !status.success() {
+        return Err(Error::Check(format!("cargo {} failed: {}", args.join(" "), status)));
+    }
+    Ok(())
+}
+
+pub fn build(root: &Path, args: Args) -> Result<()> {
+    args.finish().unwrap_or_else(|e| e.exit());
+    cargo(root, &["build", "--workspace"])
+}
+
+pub fn test(root: &Path, args: Args) -> Result<()> {
+    args.finish().unwrap_or_else(|e| e.exit());
+    cargo(root, &["test", "--workspace"])
+}
+
+pub fn render(root: &Path, mut args: Args) -> Result<()> {
+    // cargo runs in the root; a FILE given relative to where we were started still
+    // means what it says.
+    let file = match args.optional() {
+        Some(file) => env::current_dir()?.join(file),
+        None => output(root, "mandel.png"),
+    };
+    args.finish().unwrap_or_else(|e| e.exit());
+    render_to(root, &file)
+}
+
+fn render_to(root: &Path, file: &Path) -> Result<()> {
+    if let Some(dir) = file.parent() {
+        fs::create_dir_all(dir).map_err(|e| Error::io_at(dir, e))?;
+    }
+    let file = file.to_string_lossy();
+    let mut run = vec!["run", "-q", "-p", "mandelbrot", "--", &file];
+    run.extend(REFERENCE);
+    cargo(root, &run)
+}
+
+pub fn verify(root: &Path, mut args: Args) -> Result<()> {
+    let bless = match args.optional().as_deref() {
+        None => false,
+        Some("--bless") => true,
+        Some(other) => args.error(format!("unexpected argument {:?}", other)).exit(),
+    };
+    args.finish().unwrap_or_else(|e| e.exit());
+
+    cargo(root, &["build", "--workspace"])?;
+    cargo(root, &["test", "--workspace"])?;
+    let rendered = output(root, "mandel.png");
+    render_to(root, &rendered)?;
+
+    let golden = golden(root);
+    if bless {
+        fs::copy(&rendered, &golden).map_err(|e| Error::io_at(&golden, e))?;
+        println!("--- {} updated", golden.display());
+        return Ok(());
+    }
+    let actual = load(&rendered)?;
+    let expected = load(&golden)?;
+    println!("--- compare with {}", golden.display());
+    match compare(&actual, &expected) {
+        Comparison::Same => {
+            println!("--- all verified");
+            Ok(())
+        }
+        Comparison::Size(message) => Err(Error::Check(message)),
+        Comparison::Pixels(count, diff) => {
+            let file = output(root, "mandel-diff.png");
+            diff.save(&file).map_err(|e| Error::io_at(&file, io_error(e)))?;
+            Err(Error::Check(format!(
+                "{} of {} pixels differ from the golden image; they're white in {}",
+                count, actual.width() * actual.height(), file.display())))
+        }
+    }
+}
+
+// 2.  Pixels are compared as RGBA with 16 bits a channel, which any PNG the renderer
+//     might write converts to without loss: 8-bit or 16-bit, gray or color.
+type Pixels = ImageBuffer<Rgba<u16>, Vec<u16>>;
+
+fn load(file: &Path) -> Result<Pixels> {
+    let image = image::open(file).map_err(|e| Error::io_at(file, io_error(e)))?;
+    Ok(image.into_rgba16())
+}
+
+fn io_error(e: image::ImageError) -> std::io::Error {
+    match e {
+        image::ImageError::IoError(e) => e,
+        e => std::io::Error::other(e),
+    }
+}
+
+enum Comparison {
+    Same,
+    /// The images aren't even the same size.
+    Size(String),
+    /// How many pixels differ, and an image of them: white where they differ.
+    Pixels(u32, ImageBuffer<Luma<u8>, Vec<u8>>),
+}
+
+fn compare(actual: &Pixels, expected: &Pixels) -> Comparison {
+    if actual.dimensions() != expected.dimensions() {
+        return Comparison::Size(format!("the image is {}x{}, the golden image {}x{}",
+                                        actual.width(), actual.height(),
+                                        expected.width(), expected.height()));
+    }
+    let mut count = 0;
+    let diff = ImageBuffer::from_fn(actual.width(), actual.height(), |x, y| {
+        if actual.get_pixel(x, y) == expected.get_pixel(x, y) {
+            Luma([0])
+        } else {
+            count += 1;
+            Luma([255])
+        }
+    });
+    if count == 0 { Comparison::Same } else { Comparison::Pixels(count, diff) }
+}
+
+#[test]
+fn test_compare() {
+    let gray = |v: u16| Pixels::from_pixel(3, 2, Rgba([v, v, v, u16::MAX]));
+    assert!(matches!(compare(&gray(10), &gray(10)), Comparison::Same));
+
+    let mut changed = gray(10);
+    changed.put_pixel(2, 1, Rgba([11, 10, 10, u16::MAX]));
+    match compare(&changed, &gray(10)) {
+        Comparison::Pixels(count, diff) => {
+            assert_eq!(count, 1);
+            assert_eq!(diff.as_raw(), &[0, 0, 0, 0, 0, 255]);
+        }
+        _ => panic!("expected one pixel to differ"),
+    }
+
+    match compare(&Pixels::new(4, 2), &gray(10)) {
+        Comparison::Size(message) => assert_eq!(message, "the image is 4x2, the golden image 3x2"),
+        _ => panic!("expected a size mismatch"),
+    }
+}
@@ -0,0 +1,130 @@
+// `cargo xtask wasm`: the smoke test for the browser builds. The crates that are pure
+// computation build for wasm32-unknown-unknown, and the module the 37wasm-mandelbrot
+// page loads exports the functions it calls.
+//
+//   rustfun-common    gcd, parse_pair and the other helpers; no Args::from_env there
+//   mandelbrot --lib  the mandelbrot program's library, without image and rayon
+//   wasm-mandelbrot   the WebAssembly module itself
+//
+// The target has to be installed: `rustup target add wasm32-unknown-unknown`.
+use std::fs;
+use std::path::Path;
+
+use rustfun_common::Args;
+use rustfun_error::{Error, Result};
+
+use crate::verify::cargo;
+
+const TARGET: &str = "wasm32-unknown-unknown";
+
+/// What index.html and the README import from the module.
+const EXPORTS: [&str; 4] = ["render_region", "render_with", "render_rgba", "point_at"];
+
+pub fn run(root: &Path, args: Args) -> Result<()> {
+    args.finish().unwrap_or_else(|e| e.exit());
+    cargo(root, &["build", "--target", TARGET,
+                  "-p", "rustfun-common", "-p", "mandelbrot", "--lib", "-p", "wasm-mandelbrot"])?;
+
+    let module = root.join("target").join(TARGET).join("debug/wasm_mandelbrot.wasm");
+    let bytes = fs::read(&module).map_err(|e| Error::io_at(&module, e))?;
+    let exports = exports(&bytes)
+        .ok_or_else(|| Error::Check(format!("{}: not a WebAssembly module", module.display())))?;
+    println!("--- exports of {}", module.display());
+    for name in EXPORTS {
+        if !exports.iter().any(|e| is_export(e, name)) {
+            return Err(Error::Check(format!("{} doesn't export {}", module.display(), name)));
+        }
+    }
+    println!("--- all built, and {} exported", EXPORTS.join(", "));
+    Ok(())
+}
+
+// 1.  Newer wasm-bindgen versions add a hash to what the compiler exports, like
+//     `render_rgba_958a502bda8a066d`, and take it off again in the JavaScript glue.
+fn is_export(export: &str, name: &str) -> bool {
+    match export.strip_prefix(name) {
+        Some("") => true,
+        Some(rest) => rest.strip_prefix('_')
+            .is_some_and(|hash| !hash.is_empty() && hash.chars().all(|c| c.is_ascii_hexdigit())),
+        None => false,
+    }
+}
+
+#[test]
+fn test_is_export() {
+    assert!(is_export("point_at", "point_at"));
+    assert!(is_export("point_at_958a502bda8a066d", "point_at"));
+    assert!(!is_export("point_at_x", "point_at"));
+    assert!(!is_export("point_atlas", "point_at"));
+    assert!(!is_export("__wbindgen_describe_point_at_958a502bda8a066d", "point_at"));
+}
+
+// 2.  A module is a magic number and a version, then sections: an id byte, a size,
+//     and that many bytes. The export section, id 7, is a count and then, for each
+//     export, its name, a kind byte and an index. Numbers are unsigned LEB128: seven
+//     bits a byte, low bits first, the high bit set on all but the last byte.
+fn leb128(bytes: &[u8], at: &mut usize) -> Option<usize> {
+    let mut value = 0;
+    for shift in (0..35).step_by(7) {
+        let byte = *bytes.get(*at)?;
+        *at += 1;
+        value |= ((byte & 0x7f) as usize) << shift;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+    None
+}
+
+/// The names a module exports, or None if `bytes` isn't a well-formed module.
+fn exports(bytes: &[u8]) -> Option<Vec<String>> {
+    if !bytes.starts_with(b"\0asm\x01\0\0\0") {
+        return None;
+    }
+    let mut at = 8;
+    while at < bytes.len() {
+        let id = bytes[at];
+        at += 1;
+        let size = leb128(bytes, &mut at)?;
+        let end = at.checked_add(size).filter(|&end| end <= bytes.len())?;
+        if id == 7 {
+            let mut names = Vec::new();
+            for _ in 0..leb128(bytes, &mut at)? {
+                let len = leb128(bytes, &mut at)?;
+                let name = bytes.get(at..at.checked_add(len)?)?;
+                names.push(String::from_utf8_lossy(name).into_owned());
+                at += len;
+                at += 1; // the kind
+                leb128(bytes, &mut at)?;
+            }
+            return Some(names);
+        }
+        at = end;
+    }
+    Some(Vec::new())
+}
+
+#[test]
+fn test_exports() {
+    let header = b"\0asm\x01\0\0\0";
+    // A type section to skip, then exports "f" (function 0) and "memory" (memory 0).
+    let mut module = header.to_vec();
+    module.extend_from_slice(&[1, 4, 1, 0x60, 0, 0]);
+    module.extend_from_slice(&[7, 14, 2, 1, b'f', 0, 0, 6]);
+    module.extend_from_slice(b"memory");
+    module.extend_from_slice(&[2, 0]);
+    assert_eq!(exports(&module), Some(vec!["f".to_string(), "memory".to_string()]));
+
+    assert_eq!(exports(header), Some(vec![]));
+    assert_eq!(exports(b"\x7fELF"), None);
+    // The section says it's longer than the module.
+    assert_eq!(exports(&[&header[..], &[7, 20, 0]].concat()), None);
+}
+
+#[test]
+fn test_leb128() {
+    let mut at = 0;
+    assert_eq!(leb128(&[0xe5, 0x8e, 0x26, 3], &mut at), Some(624485));
+    assert_eq!(at, 3);
+    assert_eq!(leb128(&[0x80], &mut 0), None);
+}